@@ -126,7 +126,7 @@ pub fn aim_hitscan(
 }
 
 /// Convert a direction vector to Q2 view angles (yaw, pitch) in degrees.
-fn vec3_to_angles(dir: Vec3) -> (f32, f32) {
+pub(crate) fn vec3_to_angles(dir: Vec3) -> (f32, f32) {
     let yaw = if dir.x == 0.0 && dir.y == 0.0 {
         0.0
     } else {
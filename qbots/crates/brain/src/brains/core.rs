@@ -14,11 +14,13 @@ use std::sync::Arc;
 use world::{CollisionModel, NavGraph};
 
 use glam::Vec3;
+use serde::{Deserialize, Serialize};
 
 use crate::move_ctrl::MovementIntent;
 use crate::nav::NavGoal;
 use crate::nav_mode::Navigator;
 use crate::perception::{EntityClass, Worldview};
+use crate::timeline::Decision;
 use crate::weapons::Weapon;
 
 /// A static item spawn known from the map file (Plan 30). Lets the brain seek resources by their
@@ -67,29 +69,44 @@ pub struct BrainMap {
     /// Static item spawns known from the map file (Plan 30) — for map-known resource seeking
     /// (health-when-hurt, ammo re-arm) beyond PVS. Empty until wired at a call site.
     pub items: Vec<MapItem>,
+    /// Baked light level (`usercmd.lightlevel` scale) at eye height per A* node, from the
+    /// map's lightmaps — lets a shadow-loving persona camp in the dark. Empty = unknown.
+    pub node_light: Vec<u8>,
+    /// The map's collision model, for brains that trace outside a `tick`'s borrowed
+    /// [`BrainContext::cm`] (the script brain's `can_see`). `None` where no BSP is loaded.
+    pub cm: Option<Arc<CollisionModel>>,
 }
 
 /// Tunables that select a brain *flavor* without changing the decision code.
 ///
-/// The default reproduces the live fleet bot exactly. The movement-scenario runner overrides
-/// both fields (combat off, goal pinned).
+/// The default reproduces the live fleet bot exactly. The movement-scenario runner turns combat
+/// off; a replay capture pins the seed.
 #[derive(Debug, Clone)]
 pub struct BrainConfig {
     /// When `false`, combat is never evaluated (no target, no fire) — the bot only navigates.
     /// Used by the movement-test scenarios (and `--brain main` A/B pathing runs).
     pub combat_enabled: bool,
+    /// Per-bot seed for the brains that desync bots by a process-wide ordinal (`xon`'s Lcg,
+    /// `zb2`'s roam offset). `None` takes the next ordinal; a replay passes the captured one.
+    pub seed: Option<u32>,
+    /// Aim through a modeled mouse hand ([`crate::mouse`]): flicks that overshoot, Fitts's-law
+    /// corrections and tracking lag, with the character's own [`MouseParams`](crate::mouse::MouseParams).
+    /// Off by default — the brains' own turn models, unchanged.
+    pub human_aim: bool,
 }
 
 impl Default for BrainConfig {
     fn default() -> Self {
         Self {
             combat_enabled: true,
+            seed: None,
+            human_aim: false,
         }
     }
 }
 
 /// What one brain tick decides, handed to the caller's driver layer.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BrainOutput {
     /// The movement intent to encode into a `Usercmd`.
     pub intent: MovementIntent,
@@ -123,6 +140,11 @@ pub trait Brain: Send {
     fn status(&self) -> &str {
         "?"
     }
+    /// What the last `tick` decided — target, goal, the alternatives weighed — for the decision
+    /// timeline ([`crate::timeline`]). Default: nothing beyond `status`.
+    fn decision(&self) -> Decision {
+        Decision::default()
+    }
 }
 
 #[cfg(test)]
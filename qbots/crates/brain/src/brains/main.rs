@@ -21,16 +21,21 @@ use world::NavGraph;
 
 use crate::brains::core::{BrainContext, BrainMap, MapItem};
 use crate::combat::{CombatDecision, CombatDriver};
+use crate::ctf::{self, FlagBases};
 use crate::danger::DangerDriver;
 use crate::fsm::{BehaviorIntent, BehaviorState};
+use crate::itemrun::ItemRun;
 use crate::move_ctrl::MovementIntent;
 use crate::nav::NavGoal;
+use crate::opponent::OpponentModel;
 use crate::perception::EntityClass;
 use crate::persona::Persona;
 use crate::recover::{Recovery, RecoveryAction};
 use crate::skill::BotSkill;
 use crate::steer::{move_from_world_dir, Steering};
-use crate::traverse::{TraversalExecutor, TraversalFrame};
+use crate::strafejump::{self, StrafeJump, StrafeJumpInput};
+use crate::timeline::Decision;
+use crate::traverse::{TraversalExecutor, TraversalFrame, PMF_ON_GROUND};
 use crate::weapons::Weapon;
 use crate::{hazard, items, los, weapons};
 
@@ -42,6 +47,11 @@ pub use crate::brains::core::{BrainConfig, BrainOutput};
 // `self.persona.flee_health()` (was 30), `.kite_health()` (was 50), `.kite_dist()` (was 450).
 // The default persona reproduces those exact values, so this is behavior-preserving.
 
+/// A planned item run must collect at least this much discounted value (a neutral-greed bot) to
+/// preempt roaming: an armor or weapon it needs, or a full loop of pads — not a stroll past
+/// health it can't use.
+const ITEM_RUN_MIN_SCORE: f32 = 6.0;
+
 /// The `main` decision brain: owns combat/FSM/dodge/steering/recovery/skill/roam state.
 pub struct MainBrain {
     skill: BotSkill,
@@ -56,9 +66,13 @@ pub struct MainBrain {
     /// The shared ladder/swim/ride executor (Plan 46). MainBrain previously had only a stateless
     /// ride + swim and NO ladder machine; delegating gains all three (and the stateful board lock).
     traverse: TraversalExecutor,
+    /// Strafe-jump chains on straight, safe stretches of the route while roaming.
+    strafe_jump: StrafeJump,
     /// Roam goal cursor (node indices into the A* graph) + position in it.
     roam_nodes: Vec<usize>,
     roam_idx: usize,
+    /// The node held for the current roam stop ([`MainBrain::roam_spot`]); `None` until resolved.
+    roam_target: Option<usize>,
     /// The A* graph handle, kept so the navmesh backend can resolve a roam node index to
     /// a world position. `None` until the map loads.
     nav_graph: Option<Arc<NavGraph>>,
@@ -68,8 +82,16 @@ pub struct MainBrain {
     /// Static item spawns known from the map file (Plan 30) — for map-known resource seeking
     /// (health-when-hurt, ammo re-arm) beyond PVS. Populated at `set_map`.
     map_items: Vec<MapItem>,
+    /// Baked light level per A* node (empty = unknown) — where the shadows are.
+    node_light: Vec<u8>,
+    /// CTF flag pads from `map_items` (empty off CTF maps).
+    flag_bases: FlagBases,
     /// Per-bot memory of which map items are currently taken (Plan 30 T2), PVS-honest.
     item_memory: items::ItemMemory,
+    /// The planned multi-stop pickup route over `map_items`; dropped whenever combat takes over.
+    item_run: ItemRun,
+    /// Where the enemies we can't see are likely to be — the Hunt state's search spots.
+    opponents: OpponentModel,
     /// Monotonic seconds since connect (accumulated from `dt`) — the clock for `item_memory`.
     time: f32,
     /// Winning/losing read for chase/disengage decisions (Plan 29). Reset on target change.
@@ -81,6 +103,8 @@ pub struct MainBrain {
     /// True while pursuing a lost-LOS target (Plan 47 T1 edge state for `EVT chase
     /// start/convert/abort` counters).
     chasing: bool,
+    /// The nav goal set this tick (the decision timeline's `goal`).
+    goal: Option<NavGoal>,
     cfg: BrainConfig,
 }
 
@@ -91,26 +115,38 @@ impl MainBrain {
     pub fn new(skill: BotSkill, cfg: BrainConfig) -> Self {
         let steering = Steering::new(skill.combat());
         let persona = Persona::from_bot_skill(&skill);
+        let combat = if cfg.human_aim {
+            CombatDriver::new().with_mouse(skill.mouse_params())
+        } else {
+            CombatDriver::new()
+        };
         Self {
             skill,
             persona,
             fsm: BehaviorState::Roam,
-            combat: CombatDriver::new(),
+            combat,
             danger: DangerDriver::new(),
             steering,
             recovery: Recovery::new(),
             traverse: TraversalExecutor::new(),
+            strafe_jump: StrafeJump::new(),
             roam_nodes: Vec::new(),
             roam_idx: 0,
+            roam_target: None,
             nav_graph: None,
             roam_as_position: false,
             map_items: Vec::new(),
+            node_light: Vec::new(),
+            flag_bases: FlagBases::default(),
             item_memory: items::ItemMemory::new(),
+            item_run: ItemRun::new(),
+            opponents: OpponentModel::new(),
             time: 0.0,
             engage: crate::engage::EngageTracker::new(),
             last_health: 100,
             last_target: None,
             chasing: false,
+            goal: None,
             cfg,
         }
     }
@@ -133,9 +169,9 @@ impl MainBrain {
     }
 
     /// Where to run while disengaging an unwinnable fight (Plan 45): grab the best resource
-    /// (weapon when weak, health/armor when hurt) if one is visible, else step directly away
-    /// from `enemy_pos`. Mirrors the Q3 brain's `retreat_goal` but uses `main`'s loadout-aware
-    /// item picker.
+    /// (weapon when weak, health/armor when hurt) if one is visible, else duck behind the
+    /// nearest node the enemy can't see, else step directly away from `enemy_pos`. Mirrors the
+    /// Q3 brain's `retreat_goal` but uses `main`'s loadout-aware item picker.
     fn retreat_goal(
         &self,
         view: &crate::perception::Worldview,
@@ -161,6 +197,14 @@ impl MainBrain {
         {
             return NavGoal::Position(p);
         }
+        // Nothing to grab: break line of sight via the map's visibility table (when built).
+        if let (Some(graph), Some(e)) = (self.nav_graph.as_deref(), enemy_pos) {
+            if let Some(p) =
+                crate::nav::retreat_spot(graph, ss.origin, &[e], RETREAT_COVER_MAX_COST)
+            {
+                return NavGoal::Position(p);
+            }
+        }
         let pos = ss.origin;
         let away = enemy_pos
             .map(|e| {
@@ -171,6 +215,73 @@ impl MainBrain {
         NavGoal::Position(pos + away * 300.0)
     }
 
+    /// Where to hold roam stop `node`: a camper watches it from the best-covered connected node
+    /// that sees it ([`crate::nav::ambush_spot`]); everyone else, or a map without a visibility
+    /// table, stands on the stop itself.
+    fn roam_spot(&self, node: usize) -> usize {
+        if !self.persona.camper {
+            return node;
+        }
+        self.nav_graph
+            .as_deref()
+            .and_then(|g| crate::nav::ambush_spot(g, node))
+            .unwrap_or(node)
+    }
+
+    /// Advance the roam cursor one stride. A shadow-loving persona (guard, sniper) looks
+    /// [`Persona::shadow_candidates`] stops ahead and dwells at the darkest, so its camp spots
+    /// sit in the map's shadows; without light data (or preference) it's the plain next stop.
+    fn next_roam_idx(&self) -> usize {
+        let n = self.roam_nodes.len();
+        let step = |i: usize| (i + n / 7 + 1) % n;
+        let light = |i: usize| {
+            self.node_light
+                .get(self.roam_nodes[i])
+                .copied()
+                .unwrap_or(u8::MAX)
+        };
+        let mut idx = step(self.roam_idx);
+        let mut best = idx;
+        if self.node_light.is_empty() {
+            return best;
+        }
+        for _ in 1..self.persona.shadow_candidates() {
+            idx = step(idx);
+            if light(idx) < light(best) {
+                best = idx;
+            }
+        }
+        best
+    }
+
+    /// The next pad on the planned item run (see [`crate::itemrun`]), re-planning when due. Items
+    /// are rated like the visible-item picker: [`items::item_value`] scaled by this tick's
+    /// loadout/health [`items::need_multiplier`], and by the persona's `item_greed` (neutral 0.5
+    /// = ×1). A route must score [`ITEM_RUN_MIN_SCORE`] to beat roaming, and campers never run
+    /// one — they hold their roam/dwell spots. `None` before the graph loads or with no route.
+    fn item_run_goal(&mut self, view: &crate::perception::Worldview) -> Option<Vec3> {
+        if self.persona.camper {
+            return None;
+        }
+        let graph = self.nav_graph.as_deref()?;
+        let ss = view.self_state();
+        let skill = &self.skill;
+        let greed = self.persona.item_greed * 2.0;
+        self.item_run.goal(
+            graph,
+            &self.map_items,
+            &self.item_memory,
+            ss.origin,
+            self.time,
+            ITEM_RUN_MIN_SCORE,
+            |it| {
+                greed
+                    * items::item_value(it.class, skill)
+                    * items::need_multiplier(it.class, ss.held_weapon, ss.health, ss.armor)
+            },
+        )
+    }
+
     /// The world origin of the nearest **reachable** map-known item whose class is in `classes`
     /// and which `item_memory` believes is available (Plan 30 T3). "Reachable/near" is measured by
     /// **A\* path length** through the nav graph, not euclidean distance, and is capped at
@@ -221,6 +332,10 @@ impl MainBrain {
 /// across the map (the unbounded seek halved combat activity in the 2026-07-10 q2dm1 A/B).
 const FLEE_HEALTH_MAX_ASTAR: f32 = 900.0;
 
+/// Max walk cost (units) to a hidden node when a retreating `main` has nothing to grab. Kept
+/// short: cover a long run away isn't cover, and past it the straight step-away is as good.
+const RETREAT_COVER_MAX_COST: f32 = 600.0;
+
 /// Cap on how many map-item candidates get an A* path scored per tick (euclidean-nearest first),
 /// so the health-seek stays cheap on large graphs (Plan 30 T3 Risk #1).
 const ITEM_ASTAR_CANDIDATES: usize = 8;
@@ -235,13 +350,21 @@ impl crate::brains::core::Brain for MainBrain {
             nav_graph,
             roam_as_position,
             items,
+            node_light,
+            cm: _,
         } = map;
         self.roam_nodes = roam_nodes;
         // Stale cursor from the previous map panics on a smaller roster (Plan 65 T4).
         self.roam_idx = 0;
+        self.roam_target = None;
         self.nav_graph = Some(nav_graph);
         self.roam_as_position = roam_as_position;
+        self.opponents.reset();
+        // Route stops index the previous map's item table.
+        self.item_run = ItemRun::new();
+        self.flag_bases = FlagBases::from_items(&items);
         self.map_items = items;
+        self.node_light = node_light;
     }
 
     /// The danger/popularity heatmap cost weights for this bot — the caller feeds these into the
@@ -271,9 +394,20 @@ impl crate::brains::core::Brain for MainBrain {
         }
     }
 
-    /// React to scoring a frag (Eraser auto-skill bump).
+    fn decision(&self) -> Decision {
+        Decision {
+            target: self.combat.target_entity(),
+            ..Decision::heading(self.goal.as_ref(), self.nav_graph.as_deref())
+        }
+    }
+
+    /// React to scoring a frag (Eraser auto-skill bump). The frag was most likely our target,
+    /// who respawns anywhere — drop its belief.
     fn on_kill(&mut self) {
         self.skill.on_kill();
+        if let Some(t) = self.last_target {
+            self.opponents.forget(t);
+        }
     }
 
     /// React to dying: reset the held-weapon tracking to the respawn loadout and ease the
@@ -302,6 +436,10 @@ impl crate::brains::core::Brain for MainBrain {
         // frame (Plan 30 T2/T3) — PVS-honest, per-bot.
         self.time += dt;
         self.item_memory.observe(&self.map_items, view, self.time);
+        if self.cfg.combat_enabled {
+            self.opponents
+                .observe(view, self.nav_graph.as_deref(), cm, self.time);
+        }
         let jitter = (ticks as f32) * 0.1;
         let combat_dec = if self.cfg.combat_enabled {
             self.combat.evaluate(view, &self.skill, jitter, cm)
@@ -323,6 +461,11 @@ impl crate::brains::core::Brain for MainBrain {
         let health = self_ss.health;
         let held = self_ss.held_weapon;
         let has_target = combat_dec.target_entity.is_some();
+        // A fight abandons the item run; the first quiet tick plans a fresh one from wherever
+        // the fight left us.
+        if has_target {
+            self.item_run.interrupt();
+        }
 
         // ── Engagement read (Plan 29): update the winning/losing estimator this combat tick.
         // `took_damage` (our health dropped) is the third-party signal; `should_fire` is our
@@ -361,6 +504,14 @@ impl crate::brains::core::Brain for MainBrain {
                 .find(|e| e.entity_number == t)
                 .map(|e| e.origin)
         });
+        // Where to look for the target (or, with none, whoever we saw last) once it is out of
+        // sight — the Hunt state's goal.
+        let search = match &self.nav_graph {
+            Some(g) => self
+                .opponents
+                .search_goal(combat_dec.target_entity, g, self_ss.origin),
+            None => None,
+        };
 
         // Pass combat target to FSM for navigation goal.
         // Only chase via nav when LOS holds (Plan 11 T4) — without
@@ -439,12 +590,12 @@ impl crate::brains::core::Brain for MainBrain {
                         tracing::debug!(target = ?combat_dec.target_entity, "EVT chase start");
                         self.chasing = true;
                     }
-                    self.fsm.tick(view, cm) // chase the last-known pos
+                    self.fsm.tick(view, cm, search) // chase the last-known pos
                 }
             }
         } else {
             self.chasing = false; // no target — any chase is over
-            self.fsm.tick(view, cm)
+            self.fsm.tick(view, cm, search)
         };
 
         let mut mv = MovementIntent::new();
@@ -467,10 +618,19 @@ impl crate::brains::core::Brain for MainBrain {
                 self.fsm = BehaviorState::Roam;
             }
 
+            // CTF (team games only): capturing or saving our flag outranks any pickup; attacking
+            // and escorting still grab what's in view on the way.
+            let ctf_goal = ctf::ctf_goal(view, &self.flag_bases);
             let goal = if let Some(g) = goal_override.clone() {
                 g
             } else if let Some(g) = fsm_intent.nav_goal {
                 g
+            } else if let Some(g) = ctf_goal.filter(|g| g.task.urgent()) {
+                NavGoal::Position(g.pos)
+            } else if let Some(p) = self.item_run_goal(view) {
+                // Run the planned item route (armor → weapon → mega, timed to respawns) over the
+                // map-known spawns.
+                NavGoal::Position(p)
             } else if let Some((item_pos, _)) = items::best_item_goal_weighted(
                 view,
                 &self.skill,
@@ -478,20 +638,29 @@ impl crate::brains::core::Brain for MainBrain {
                 view.self_state().health,
                 view.self_state().armor,
             ) {
-                // Seek the highest-value visible item (powerups, armor, weapons)
-                // weighted by value/distance and — for `main` (Plan 45) — by loadout
-                // need (weapon hunger when weak) and health/armor need when hurt.
+                // No route (no map item table, or nothing worth a trip): seek the
+                // highest-value visible item (powerups, armor, weapons) weighted by
+                // value/distance and — for `main` (Plan 45) — by loadout need (weapon hunger
+                // when weak) and health/armor need when hurt.
                 NavGoal::Position(item_pos)
+            } else if let Some(g) = ctf_goal {
+                NavGoal::Position(g.pos)
             } else if !self.roam_nodes.is_empty() {
-                // Campers dwell ~5x longer per node (first-cut
-                // camping; a true camp-node picker with cover/LOS
-                // is a follow-up). Default roamer cycles every 5s.
+                // Campers dwell ~5x longer per node, overlooking it from cover. Default
+                // roamer cycles every 5s.
                 let dwell = self.persona.roam_dwell();
                 if ticks.is_multiple_of(dwell) {
-                    self.roam_idx =
-                        (self.roam_idx + self.roam_nodes.len() / 7 + 1) % self.roam_nodes.len();
+                    self.roam_idx = self.next_roam_idx();
+                    self.roam_target = None;
                 }
-                let node = self.roam_nodes[self.roam_idx];
+                let node = match self.roam_target {
+                    Some(n) => n,
+                    None => {
+                        let n = self.roam_spot(self.roam_nodes[self.roam_idx]);
+                        self.roam_target = Some(n);
+                        n
+                    }
+                };
                 // The navmesh backend doesn't index the A* graph's nodes, so
                 // express the roam target as a world position it can path to.
                 if self.roam_as_position {
@@ -506,6 +675,7 @@ impl crate::brains::core::Brain for MainBrain {
                 NavGoal::Position(pos)
             };
 
+            self.goal = Some(goal.clone());
             nav.set_goal(goal, pos);
             // String-pull the path into longer straight runs (Plan 14 T1).
             if let Some(cm) = cm {
@@ -554,7 +724,14 @@ impl crate::brains::core::Brain for MainBrain {
             };
 
             // ── 1. Ideal view yaw (priority: fire-aim > enemy-face > path) ──
-            let (ideal_yaw, ideal_pitch) = if combat_dec.should_fire {
+            // A mouse hand (`human_aim`) aims while facing the enemy too, and its output is the
+            // view: it already moves at human speed, so it bypasses the turn-rate limit.
+            let hand_aims = self.combat.human_aim()
+                && (combat_dec.should_fire || enemy_dist_dir.is_some_and(|(d, _)| d < ideal_dist));
+            if self.combat.human_aim() && !hand_aims {
+                self.combat.release_aim();
+            }
+            let (ideal_yaw, ideal_pitch) = if combat_dec.should_fire || hand_aims {
                 (combat_dec.aim_yaw, combat_dec.aim_pitch)
             } else if let Some((d, dir)) = enemy_dist_dir {
                 if d < ideal_dist {
@@ -583,7 +760,12 @@ impl crate::brains::core::Brain for MainBrain {
             };
 
             // ── 2. Rate-limit the yaw turn toward ideal ───────────────────
-            let view_yaw = self.steering.change_yaw(ideal_yaw, dt);
+            let view_yaw = if hand_aims {
+                self.steering.set_view_yaw(ideal_yaw);
+                ideal_yaw
+            } else {
+                self.steering.change_yaw(ideal_yaw, dt)
+            };
             mv.look_at(view_yaw, ideal_pitch);
 
             // ── 3. World move direction + face-then-go mode ───────────────
@@ -780,6 +962,38 @@ impl crate::brains::core::Brain for MainBrain {
                 mv.jump();
             }
 
+            // ── 7b. Strafe-jumping — only roaming down a plain, straight, hazard-free stretch;
+            // combat, traversal, recovery, jump edges and creep all keep ordinary steering. ──
+            let plain = enemy_dist_dir.is_none()
+                && !combat_dec.should_fire
+                && !gates.any()
+                && matches!(rec_action, RecoveryAction::None)
+                && !nav.current_edge_is_jump()
+                && creep >= 1.0;
+            let hopping = match cm.filter(|_| plain) {
+                Some(c) => {
+                    let path = nav.path_ahead(strafejump::PATH_LOOKAHEAD);
+                    let input = StrafeJumpInput {
+                        cm: c,
+                        pos,
+                        vel: view.self_state().velocity,
+                        grounded: view.self_state().flags & PMF_ON_GROUND != 0,
+                        path: &path,
+                        // Eraser combat rating 1–5 → 0–1: combat 3 and up strafe-jump.
+                        skill: (self.skill.combat() - 1.0) / 4.0,
+                        frametime: dt,
+                    };
+                    self.strafe_jump.steer(&mut mv, &input)
+                }
+                None => {
+                    self.strafe_jump.reset();
+                    false
+                }
+            };
+            if hopping {
+                self.steering.set_view_yaw(mv.yaw);
+            }
+
             // ── 8. Traversal override (Plan 46): swim (Plan 40) + stateful ride/ladder (Plan
             // 43/35) movement is owned by the shared TraversalExecutor. On a swim/ride/ladder edge
             // it OVERWRITES the movement axes (and view) computed above; the fire decision stays
@@ -893,6 +1107,7 @@ mod tests {
             BotSkill::default(),
             BrainConfig {
                 combat_enabled: false,
+                ..Default::default()
             },
         );
         let view = Worldview::from_frame(&Frame::default(), &ConfigStrings::default(), 0);
@@ -916,4 +1131,212 @@ mod tests {
         assert!(brain.roam_nodes.is_empty());
         assert!(!brain.roam_as_position);
     }
+
+    #[test]
+    fn shadow_loving_persona_dwells_in_the_dark() {
+        use crate::brains::core::Brain as _;
+        // 14 roam nodes → stride 3; node 9 is the only dark one.
+        let n = 14;
+        let map = || BrainMap {
+            roam_nodes: (0..n).collect(),
+            nav_graph: Arc::new(NavGraph::from_raw(vec![[0.0; 3]; n], vec![Vec::new(); n])),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: (0..n).map(|i| if i == 9 { 4 } else { 120 }).collect(),
+            cm: None,
+        };
+        let mut roamer = MainBrain::new(BotSkill::default(), BrainConfig::default());
+        roamer.set_map(map());
+        assert_eq!(roamer.next_roam_idx(), 3, "no preference: plain stride");
+
+        let mut guard = MainBrain::new(BotSkill::default(), BrainConfig::default())
+            .with_persona(Some(Persona::guard()));
+        guard.set_map(map());
+        assert_eq!(guard.next_roam_idx(), 9, "looks ahead to the shadow");
+    }
+
+    /// Unarmored and unarmed next to armor and a weapon: a neutral bot runs the route, a camper
+    /// skips it and keeps to its roam/dwell spot.
+    #[test]
+    fn campers_roam_instead_of_running_items() {
+        use crate::brains::core::Brain as _;
+        use crate::nav_mode::StubNav;
+        use crate::perception::Worldview;
+        use client::parse::ConfigStrings;
+        use q2proto::Frame;
+
+        // Pads past the item memory's trust range, so the empty frame doesn't mark them taken.
+        let n = 12;
+        let graph = Arc::new(NavGraph::from_raw(
+            (0..n).map(|i| [100.0 * i as f32, 0.0, 0.0]).collect(),
+            (0..n)
+                .map(|i| {
+                    let mut e = Vec::new();
+                    if i > 0 {
+                        e.push((i - 1, 100.0));
+                    }
+                    if i + 1 < n {
+                        e.push((i + 1, 100.0));
+                    }
+                    e
+                })
+                .collect(),
+        ));
+        let item = |class, x: f32| MapItem {
+            class,
+            origin: Vec3::new(x, 0.0, 0.0),
+            nav_node: graph.nearest(&[x, 0.0, 0.0]),
+        };
+        let map = || BrainMap {
+            roam_nodes: vec![11],
+            nav_graph: graph.clone(),
+            roam_as_position: false,
+            items: vec![
+                item(EntityClass::ItemArmor, 800.0),
+                item(EntityClass::ItemWeapon, 1000.0),
+            ],
+            node_light: Vec::new(),
+            cm: None,
+        };
+        let mut frame = Frame::default();
+        frame.playerstate.stats[1] = 100;
+        let view = Worldview::from_frame(&frame, &ConfigStrings::default(), 0);
+        let cfg = BrainConfig {
+            combat_enabled: false,
+            ..Default::default()
+        };
+        let goal_of = |brain: &mut MainBrain| {
+            let mut nav = StubNav::default();
+            let _ = brain.tick(BrainContext {
+                view: &view,
+                nav: Some(&mut nav),
+                cm: None,
+                dt: 0.1,
+                ticks: 1,
+                goal_override: None,
+            });
+            nav.last_goal
+        };
+
+        let mut runner = MainBrain::new(BotSkill::default(), cfg.clone());
+        runner.set_map(map());
+        assert_eq!(
+            goal_of(&mut runner),
+            Some(NavGoal::Position(Vec3::new(800.0, 0.0, 0.0)))
+        );
+
+        let mut camper =
+            MainBrain::new(BotSkill::default(), cfg).with_persona(Some(Persona::guard()));
+        camper.set_map(map());
+        assert_eq!(goal_of(&mut camper), Some(NavGoal::Waypoint(11)));
+    }
+
+    #[test]
+    fn retreat_ducks_behind_cover_when_nothing_to_grab() {
+        use crate::brains::core::Brain as _;
+        use crate::perception::Worldview;
+        use client::parse::ConfigStrings;
+        use q2proto::Frame;
+        use world::testmap::BspBuilder;
+
+        // A pillar at the origin; the bot stands south of it, the enemy further south, and
+        // one node sits hidden behind (north of) the pillar.
+        let mut b = BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [512.0, 512.0, 0.0])
+            .solid([-32.0, 0.0, 0.0], [32.0, 64.0, 128.0]);
+        let cm = world::CollisionModel::from_bsp(&b.build());
+        let mut graph = NavGraph::from_raw(
+            vec![
+                [-64.0, -64.0, 24.0],
+                [0.0, -64.0, 24.0],
+                [64.0, -64.0, 24.0],
+                [96.0, 32.0, 24.0],
+                [0.0, 128.0, 24.0],
+            ],
+            vec![
+                vec![(1, 64.0)],
+                vec![(0, 64.0), (2, 64.0)],
+                vec![(1, 64.0), (3, 100.0)],
+                vec![(2, 100.0), (4, 140.0)],
+                vec![(3, 140.0)],
+            ],
+        );
+        graph.set_vis_table(world::VisTable::build(&cm, &graph, None));
+        let mut frame = Frame::default();
+        frame.playerstate.stats[1] = 100;
+        frame.playerstate.pmove.origin = [0, -64 * 8, 24 * 8];
+        let view = Worldview::from_frame(&frame, &ConfigStrings::default(), 0);
+        let enemy = Vec3::new(0.0, -300.0, 24.0);
+
+        let mut brain = MainBrain::new(BotSkill::default(), BrainConfig::default());
+        assert_eq!(
+            brain.retreat_goal(&view, Some(enemy)),
+            NavGoal::Position(Vec3::new(0.0, 236.0, 24.0)),
+            "no map: step straight away"
+        );
+        brain.set_map(BrainMap {
+            roam_nodes: vec![0],
+            nav_graph: Arc::new(graph),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: Vec::new(),
+            cm: None,
+        });
+        assert_eq!(
+            brain.retreat_goal(&view, Some(enemy)),
+            NavGoal::Position(Vec3::new(0.0, 128.0, 24.0))
+        );
+    }
+
+    /// A camper with a visibility table watches its roam stop from a covered spot that sees
+    /// it; a neutral roamer stands on the stop.
+    #[test]
+    fn campers_overlook_their_roam_stop_from_cover() {
+        use crate::brains::core::Brain as _;
+        use world::testmap::BspBuilder;
+
+        let mut b = BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [512.0, 512.0, 0.0])
+            .solid([-32.0, 0.0, 0.0], [32.0, 64.0, 128.0]);
+        let cm = world::CollisionModel::from_bsp(&b.build());
+        let mut graph = NavGraph::from_raw(
+            vec![
+                [-64.0, -64.0, 24.0],
+                [0.0, -64.0, 24.0],
+                [64.0, -64.0, 24.0],
+                [96.0, 32.0, 24.0],
+                [0.0, 128.0, 24.0],
+            ],
+            vec![
+                vec![(1, 64.0)],
+                vec![(0, 64.0), (2, 64.0)],
+                vec![(1, 64.0), (3, 100.0)],
+                vec![(2, 100.0), (4, 140.0)],
+                vec![(3, 140.0)],
+            ],
+        );
+        graph.set_vis_table(world::VisTable::build(&cm, &graph, None));
+        let graph = Arc::new(graph);
+        let map = || BrainMap {
+            roam_nodes: vec![1],
+            nav_graph: graph.clone(),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: Vec::new(),
+            cm: None,
+        };
+
+        let mut roamer = MainBrain::new(BotSkill::default(), BrainConfig::default());
+        roamer.set_map(map());
+        assert_eq!(roamer.roam_spot(1), 1);
+
+        let mut camper = MainBrain::new(BotSkill::default(), BrainConfig::default())
+            .with_persona(Some(Persona::guard()));
+        camper.set_map(map());
+        let spot = camper.roam_spot(1);
+        let vis = graph.vis_table().unwrap();
+        assert_ne!(spot, 1);
+        assert!(vis.visible(spot, 1));
+        assert!(vis.visible_from(1).all(|n| vis.cover(n) <= vis.cover(spot)));
+    }
 }
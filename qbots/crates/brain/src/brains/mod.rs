@@ -8,7 +8,9 @@ pub mod core;
 pub mod main;
 pub mod q3;
 pub mod runtester;
+pub mod script;
 pub mod sentry;
+pub mod utility;
 pub mod xon;
 pub mod zb2;
 
@@ -17,12 +19,15 @@ pub use core::{Brain, BrainConfig, BrainContext, BrainMap, BrainOutput};
 use crate::brains::main::MainBrain;
 use crate::brains::q3::Q3Brain;
 use crate::brains::runtester::RunTesterBrain;
+use crate::brains::script::ScriptBrain;
 use crate::brains::sentry::SentryBrain;
+use crate::brains::utility::{UtilityBrain, UtilityProfile};
 use crate::brains::xon::XonBrain;
 use crate::brains::zb2::Zb2Brain;
-use crate::q3char::{CharPreset, Q3Character};
+use crate::persona::Persona;
+use crate::q3char::Q3Character;
 use crate::skill::BotSkill;
-use crate::xonchar::{XonCharPreset, XonSkill};
+use crate::xonchar::XonSkill;
 
 /// Which brain implementation a bot runs. Mirrors `NavMode` (the nav-backend selector); a
 /// `ValueEnum` derive + CLI flag land in Plan 25, more variants in Plan 24.
@@ -52,6 +57,14 @@ pub enum BrainKind {
     /// movement; Plan 60).
     #[value(name = "xon", alias = "xonotic")]
     Xon,
+    /// script — A Rhai script decides (goto/fight/look…) and the native layers carry it out;
+    /// hot-reloads when the file changes.
+    #[value(name = "scr", alias = "script")]
+    Script,
+    /// utility — Utility AI: every option (engage, grab, camp, hunt, retreat, dodge…) is scored
+    /// by response curves over its considerations and the best one runs.
+    #[value(name = "uti", alias = "utility")]
+    Utility,
 }
 
 /// Short kebab-case tag for `kind` — for logging + competition bot naming (mirrors `mode_tag`).
@@ -63,49 +76,111 @@ pub fn brain_tag(kind: BrainKind) -> &'static str {
         BrainKind::Quake3 => "q3",
         BrainKind::Zb2 => "zb2",
         BrainKind::Xon => "xon",
+        BrainKind::Script => "script",
+        BrainKind::Utility => "utility",
+    }
+}
+
+/// A brain's personality, in its own terms — what a character file sets. One variant per brain
+/// family that has a personality axis; `build_brain` hands each kind its own variant.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrainTraits {
+    Main(Persona),
+    Q3(Q3Character),
+    Xon(XonSkill),
+    /// The script file itself; the brain loads (and hot-reloads) it from here.
+    Script(std::path::PathBuf),
+    /// Boxed: a curve per consideration outweighs every other family's traits.
+    Utility(Box<UtilityProfile>),
+}
+
+impl BrainTraits {
+    /// The brain these traits configure.
+    pub fn kind(&self) -> BrainKind {
+        match self {
+            BrainTraits::Main(_) => BrainKind::Main,
+            BrainTraits::Q3(_) => BrainKind::Quake3,
+            BrainTraits::Xon(_) => BrainKind::Xon,
+            BrainTraits::Script(_) => BrainKind::Script,
+            BrainTraits::Utility(_) => BrainKind::Utility,
+        }
     }
 }
 
 /// Build the brain implementation for `kind`. Single match — the kind→impl mapping lives here,
 /// exactly mirroring `build_navigator` for nav backends. `Send` so a bot task can own the box.
 ///
-/// `char` selects a named Q3 personality for the `Quake3` brain (Plan 38 roster); `None` →
-/// `Q3Character::from_skill(skill)` (the Plan 37 default). `xonchar` is the same idea for the
-/// `Xon` brain (Plan 60/62 roster); `None` → a neutral `XonSkill` at the master skill level.
-/// Every other arm ignores both.
+/// `traits` is the brain's personality when its variant matches `kind`; otherwise (or `None`)
+/// each brain falls back to its neutral default: `Q3Character::from_skill(skill)` (Plan 37) for
+/// `Quake3`, a neutral `XonSkill` at the master skill level for `Xon`, the default persona and
+/// utility curves, and for `Script` a brain that stands idle with status `script-error`. A
+/// mismatched variant is a caller bug (a character resolved for the wrong family), so it is
+/// logged rather than dropped silently.
 pub fn build_brain(
     kind: BrainKind,
     skill: BotSkill,
     cfg: BrainConfig,
-    char: Option<CharPreset>,
-    persona: Option<crate::persona::Persona>,
-    xonchar: Option<XonCharPreset>,
+    traits: Option<BrainTraits>,
 ) -> Box<dyn Brain + Send> {
+    if let Some(t) = traits.as_ref().filter(|t| t.kind() != kind) {
+        tracing::warn!(
+            brain = brain_tag(kind),
+            traits = brain_tag(t.kind()),
+            "traits are for another brain; using this brain's defaults"
+        );
+    }
     match kind {
-        BrainKind::Main => Box::new(MainBrain::new(skill, cfg).with_persona(persona)),
+        BrainKind::Main => {
+            let persona = match traits {
+                Some(BrainTraits::Main(p)) => Some(p),
+                _ => None,
+            };
+            Box::new(MainBrain::new(skill, cfg).with_persona(persona))
+        }
         // Sentry ignores `cfg` (no nav, no goal override) — it's a proof-of-pluggability.
         BrainKind::Sentry => Box::new(SentryBrain::new(skill)),
         // RunTester is combat-free and goal-driven per tick; it needs neither skill nor cfg.
         BrainKind::RunTester => Box::new(RunTesterBrain::new()),
-        // Quake3: a named roster preset if given, else derive the character from the master skill
-        // level. `cfg` is unused: in a movement scenario there are no enemies, so the Q3 combat
-        // path never fires anyway.
+        // Quake3: the given character if any, else derive the character from the master skill
+        // level. Only `cfg.human_aim` applies: in a movement scenario there are no enemies, so
+        // the Q3 combat path never fires anyway.
         BrainKind::Quake3 => {
-            let ch = char
-                .map(CharPreset::character)
-                .unwrap_or_else(|| Q3Character::from_skill(skill.skill));
-            Box::new(Q3Brain::new(ch))
+            let ch = match traits {
+                Some(BrainTraits::Q3(c)) => c,
+                _ => Q3Character::from_skill(skill.skill),
+            };
+            Box::new(Q3Brain::new(ch).with_human_aim(cfg.human_aim))
         }
         // Zb2 reuses the shared combat driver; `cfg.combat_enabled` gates it for scenarios.
-        // It ignores `char`/`persona` (its personality IS the committed-route texture).
-        BrainKind::Zb2 => Box::new(Zb2Brain::new(skill, cfg.combat_enabled)),
-        // Xon: a named 12-axis roster preset if given, else neutral at the master skill.
+        // It takes no traits (its personality IS the committed-route texture).
+        BrainKind::Zb2 => Box::new(
+            Zb2Brain::new(skill, cfg.combat_enabled)
+                .with_seed(cfg.seed)
+                .with_human_aim(cfg.human_aim),
+        ),
+        // Xon: the given 12-axis character if any, else neutral at the master skill.
         BrainKind::Xon => {
-            let sk = xonchar
-                .map(XonCharPreset::skill)
-                .unwrap_or_else(|| XonSkill::new(skill.skill.min(10) as f32));
+            let sk = match traits {
+                Some(BrainTraits::Xon(x)) => x,
+                _ => XonSkill::new(skill.skill.min(10) as f32),
+            };
             Box::new(XonBrain::new(sk, cfg))
         }
+        // Script: `cfg` is unused — a scenario run just doesn't hand the script enemies to fight.
+        BrainKind::Script => {
+            let path = match traits {
+                Some(BrainTraits::Script(p)) => p,
+                _ => Default::default(),
+            };
+            Box::new(ScriptBrain::new(path, skill))
+        }
+        BrainKind::Utility => {
+            let profile = match traits {
+                Some(BrainTraits::Utility(u)) => *u,
+                _ => UtilityProfile::default(),
+            };
+            Box::new(UtilityBrain::new(profile, skill, cfg))
+        }
     }
 }
 
@@ -120,8 +195,6 @@ mod tests {
             BotSkill::default(),
             BrainConfig::default(),
             None,
-            None,
-            None,
         );
         assert_eq!(brain.status(), "roam");
     }
@@ -133,8 +206,6 @@ mod tests {
             BotSkill::default(),
             BrainConfig::default(),
             None,
-            None,
-            None,
         );
         assert_eq!(brain.status(), "sentry");
     }
@@ -158,12 +229,18 @@ mod tests {
         assert_eq!(BrainKind::from_str("zb2", true), Ok(BrainKind::Zb2));
         assert_eq!(BrainKind::from_str("xon", true), Ok(BrainKind::Xon));
         assert_eq!(BrainKind::from_str("xonotic", true), Ok(BrainKind::Xon));
+        assert_eq!(BrainKind::from_str("scr", true), Ok(BrainKind::Script));
+        assert_eq!(BrainKind::from_str("script", true), Ok(BrainKind::Script));
+        assert_eq!(BrainKind::from_str("uti", true), Ok(BrainKind::Utility));
+        assert_eq!(BrainKind::from_str("utility", true), Ok(BrainKind::Utility));
         assert!(BrainKind::from_str("nope", true).is_err());
         assert_eq!(brain_tag(BrainKind::Main), "main");
         assert_eq!(brain_tag(BrainKind::Sentry), "sentry");
         assert_eq!(brain_tag(BrainKind::RunTester), "runtester");
         assert_eq!(brain_tag(BrainKind::Quake3), "q3");
         assert_eq!(brain_tag(BrainKind::Xon), "xon");
+        assert_eq!(brain_tag(BrainKind::Script), "script");
+        assert_eq!(brain_tag(BrainKind::Utility), "utility");
     }
 
     #[test]
@@ -173,9 +250,20 @@ mod tests {
             BotSkill::default(),
             BrainConfig::default(),
             None,
-            None,
-            None,
         );
         assert_eq!(brain.status(), "seek-ltg");
     }
+
+    #[test]
+    fn mismatched_traits_fall_back_to_the_brain_defaults() {
+        let q3 = BrainTraits::Q3(Q3Character::camper());
+        assert_eq!(q3.kind(), BrainKind::Quake3);
+        let brain = build_brain(
+            BrainKind::Main,
+            BotSkill::default(),
+            BrainConfig::default(),
+            Some(q3),
+        );
+        assert_eq!(brain.status(), "roam");
+    }
 }
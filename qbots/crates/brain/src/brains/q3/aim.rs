@@ -4,18 +4,22 @@
 //! (`ai_dmq3.c:3555`, distilled §6). Distinct from the Eraser aim in [`crate::aim`]:
 //! **per-weapon accuracy/skill**, a **reaction-time sight gate**, a **direction-change accuracy
 //! penalty**, **hitscan distance falloff**, **radial ground-aim** for splash weapons, and the
-//! **fire-throttle duty cycle** + **radial self-preservation abort**.
+//! **fire-throttle duty cycle** + **radial self-preservation abort**. With a collision model,
+//! splash weapons go to the shared [`crate::splash`] solver in place of the radial ground-aim.
 //!
 //! AAS's exact movement prediction (`trap_AAS_PredictClientMovement`) has no qbots equivalent;
 //! we substitute the shared **constant-velocity lead** ([`crate::aim::aim_direction`]) — the
 //! exact-predict path was only for `aim_skill > 0.8`, so a high-skill bot just gets a better
-//! linear lead. The aim-error model is applied on top.
+//! linear lead. The aim-error model is applied on top. With [`AimState::with_mouse`] the
+//! result then goes through a [`MouseAim`] hand instead of snapping to the ideal angle.
 
 use glam::Vec3;
 use world::{CollisionModel, MASK_SOLID};
 
 use crate::aim::{aim_direction, AimRng, PITCH_CLAMP_DEG};
+use crate::mouse::{MouseAim, MouseParams};
 use crate::q3char::Q3Character;
+use crate::splash;
 use crate::weapons::Weapon;
 
 /// Enemy velocity memory for the direction-change accuracy penalty (`enemyposition_time`,
@@ -23,11 +27,13 @@ use crate::weapons::Weapon;
 /// gets faked out (`accuracy *= 0.7`).
 pub use crate::aim::would_self_splash;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct AimState {
     sample_time: f32,
     last_vel: Vec3,
     dir_changed: bool,
+    /// The opt-in mouse hand; `None` snaps to the ideal aim (the engine behaviour).
+    mouse: Option<MouseAim>,
 }
 
 impl AimState {
@@ -36,9 +42,17 @@ impl AimState {
             sample_time: f32::NEG_INFINITY,
             last_vel: Vec3::ZERO,
             dir_changed: false,
+            mouse: None,
         }
     }
 
+    /// Send the aim through a modeled mouse hand ([`crate::mouse`]) moving from
+    /// [`AimInput::view`] toward the ideal aim.
+    pub fn with_mouse(mut self, params: MouseParams) -> Self {
+        self.mouse = Some(MouseAim::new(params));
+        self
+    }
+
     /// Re-sample the enemy velocity every 0.5 s; flag a direction reversal for sub-0.9 skill.
     fn update(&mut self, time: f32, enemy_vel: Vec3, aim_skill: f32) {
         if time - self.sample_time >= 0.5 {
@@ -74,6 +88,8 @@ pub struct AimInput<'a> {
     /// Brain wall-clock seconds (for the 0.5 s velocity-memory sampling).
     pub time: f32,
     pub cm: Option<&'a CollisionModel>,
+    /// Our current view `(yaw, pitch)` — where a mouse hand picks up from.
+    pub view: (f32, f32),
 }
 
 /// The Q3 aim result.
@@ -98,6 +114,7 @@ pub fn aim_at_enemy(state: &mut AimState, input: &AimInput, rng: &mut impl AimRn
         visible,
         time,
         cm,
+        view,
     } = *input;
 
     let mut accuracy = ch.weapon_accuracy(weapon).clamp(0.0, 1.0);
@@ -127,10 +144,57 @@ pub fn aim_at_enemy(state: &mut AimState, input: &AimInput, rng: &mut impl AimRn
         accuracy *= 0.6 + (dist.min(150.0) / 150.0) * 0.4;
     }
 
+    // Splash weapons at `aim_skill > 0.6` fly the shot through the world ([`crate::splash`]):
+    // floor shots, wall shots and grenade bank shots. The error model still perturbs the result.
+    let solved = cm
+        .filter(|_| skill > 0.6 && weapon.self_dangerous())
+        .and_then(|cm| {
+            let origin = shooter_eye - Vec3::Z * crate::los::EYE_Z;
+            splash::solve(cm, origin, enemy_origin, enemy_vel, weapon)
+        });
+    let inacc = 1.0 - accuracy;
+    let (mut yaw, mut pitch) = match solved {
+        Some(shot) => (shot.yaw, shot.pitch),
+        None => lead_aim(input, accuracy, rng),
+    };
+
+    // Direction perturbation when inaccurate (Q3 `0.3·crandom·(1−accuracy)` per axis, radians).
+    if accuracy < 0.8 {
+        yaw += (rng.next_signed() * 0.3 * inacc).to_degrees();
+        pitch += (rng.next_signed() * 0.15 * inacc).to_degrees();
+    }
+
+    // Eraser's ±15° clamp is for the lead aim; a solved shot keeps its steep pitch.
+    let limit = match solved {
+        Some(_) => splash::MAX_PITCH_DEG,
+        None => PITCH_CLAMP_DEG,
+    };
+    let mut pitch = pitch.clamp(-limit, limit);
+    if let Some(m) = &mut state.mouse {
+        (yaw, pitch) = m.step(time, view, (yaw, pitch), dist, rng);
+    }
+
+    AimResult { yaw, pitch, ready }
+}
+
+/// The radial ground-aim, worldspace jitter and constant-velocity lead — the aim without a
+/// solved splash shot.
+fn lead_aim(input: &AimInput, accuracy: f32, rng: &mut impl AimRng) -> (f32, f32) {
+    let AimInput {
+        ch,
+        weapon,
+        shooter_eye,
+        enemy_origin,
+        enemy_vel,
+        cm,
+        ..
+    } = *input;
+
     // Radial ground-aim for splash weapons (`aim_skill > 0.6`, enemy not far above us): aim at
     // the floor under the enemy so the splash still hits.
     let mut target = enemy_origin;
-    let radial = skill > 0.6 && weapon.self_dangerous() && (enemy_origin.z - shooter_eye.z) < 16.0;
+    let radial =
+        ch.aim_skill > 0.6 && weapon.self_dangerous() && (enemy_origin.z - shooter_eye.z) < 16.0;
     if radial {
         if let Some(cm) = cm {
             if let Some(floor) = trace_floor(cm, enemy_origin) {
@@ -153,19 +217,7 @@ pub fn aim_at_enemy(state: &mut AimState, input: &AimInput, rng: &mut impl AimRn
     } else {
         None
     };
-    let (mut yaw, mut pitch) = aim_direction(shooter_eye, target, lead_vel, weapon);
-
-    // Direction perturbation when inaccurate (Q3 `0.3·crandom·(1−accuracy)` per axis, radians).
-    if accuracy < 0.8 {
-        yaw += (rng.next_signed() * 0.3 * inacc).to_degrees();
-        pitch += (rng.next_signed() * 0.15 * inacc).to_degrees();
-    }
-
-    AimResult {
-        yaw,
-        pitch: pitch.clamp(-PITCH_CLAMP_DEG, PITCH_CLAMP_DEG),
-        ready,
-    }
+    aim_direction(shooter_eye, target, lead_vel, weapon)
 }
 
 /// Trace straight down from just above an origin to find the floor point under it (for radial
@@ -201,6 +253,7 @@ mod tests {
                 visible: true,
                 time: 1.0,
                 cm: None,
+                view: (0.0, 0.0),
             },
         )
     }
@@ -216,6 +269,18 @@ mod tests {
         assert!(r.ready, "long-sighted precise bot is ready to fire");
     }
 
+    #[test]
+    fn solved_splash_shot_keeps_its_steep_pitch() {
+        let cm = world::CollisionModel::half_space([0.0, 0.0, 1.0], 0.0);
+        let (mut st, mut input) = precise_input(Weapon::RocketLauncher);
+        // On a ledge above an enemy nearly underneath us.
+        input.shooter_eye = Vec3::new(0.0, 0.0, 422.0);
+        input.enemy_origin = Vec3::new(160.0, 0.0, 24.125);
+        input.cm = Some(&cm);
+        let r = aim_at_enemy(&mut st, &input, &mut JitterRng::new(1));
+        assert!(r.pitch > PITCH_CLAMP_DEG + 20.0, "pitch {}", r.pitch);
+    }
+
     #[test]
     fn reaction_gate_blocks_early_fire_for_precise_bot() {
         let (mut st, mut input) = precise_input(Weapon::Railgun);
@@ -252,6 +317,7 @@ mod tests {
             visible: true,
             time: 1.0,
             cm: None,
+            view: (0.0, 0.0),
         };
         let mut rng = JitterRng::new(7);
         // Average several rolls: a low-accuracy bot should miss dead-center most of the time.
@@ -266,6 +332,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mouse_hand_flicks_from_the_current_view() {
+        let (st, mut input) = precise_input(Weapon::Railgun);
+        let mut st = st.with_mouse(input.ch.mouse_params());
+        input.enemy_origin = Vec3::new(0.0, 500.0, 0.0); // 90° left
+        let mut rng = JitterRng::new(3);
+        let mut yaws = Vec::new();
+        for i in 0..40 {
+            input.time = 1.0 + i as f32 * 0.025;
+            let r = aim_at_enemy(&mut st, &input, &mut rng);
+            input.view = (r.yaw, r.pitch);
+            yaws.push(r.yaw);
+        }
+        assert!(yaws[0].abs() < 5.0, "starts at the view: {}", yaws[0]);
+        assert!(yaws[7] > 10.0 && yaws[7] < 80.0, "mid-flick: {}", yaws[7]);
+        let peak = yaws.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(peak > 90.0, "the flick overshoots: {peak}");
+        assert!(
+            (yaws[39] - 90.0).abs() < 2.0,
+            "corrected on target: {}",
+            yaws[39]
+        );
+    }
+
     #[test]
     fn non_splash_never_self_aborts() {
         let cm = CollisionModel::half_space([1.0, 0.0, 0.0], 0.0);
@@ -27,10 +27,12 @@ use world::NavGraph;
 use crate::brains::core::{Brain, BrainContext, BrainMap, BrainOutput};
 use crate::move_ctrl::MovementIntent;
 use crate::nav::NavGoal;
+use crate::opponent::OpponentModel;
 use crate::q3char::Q3Character;
 use crate::recover::{Recovery, RecoveryAction};
 use crate::skill::BotSkill;
 use crate::steer::{move_from_world_dir, Steering};
+use crate::timeline::Decision;
 use crate::traverse::{TraversalExecutor, TraversalFrame};
 use crate::{items, weapons};
 
@@ -99,6 +101,8 @@ pub struct Q3Brain {
     roam_idx: usize,
     nav_graph: Option<Arc<NavGraph>>,
     roam_as_position: bool,
+    /// The nav goal set this tick (the decision timeline's `goal`).
+    goal: Option<NavGoal>,
 
     // ── steering / recovery (reused primitives) ────────────────────────────────────────
     steering: Steering,
@@ -115,6 +119,9 @@ pub struct Q3Brain {
     enemy: Option<i32>,
     /// Last position we saw the enemy at (chase/retreat goal).
     last_enemy_pos: Option<Vec3>,
+    /// Belief over where out-of-sight enemies are: a chase outlives the enemy leaving our PVS
+    /// and searches its likely hiding spots.
+    opponents: OpponentModel,
     /// Our health last tick — a drop this frame widens awareness FOV (Q3 §4).
     last_health: i32,
     /// Wall-clock seconds since connect (driven by `dt`); all timers are absolute seconds.
@@ -171,12 +178,14 @@ impl Q3Brain {
             roam_idx: 0,
             nav_graph: None,
             roam_as_position: false,
+            goal: None,
             steering,
             recovery: Recovery::new(),
             traverse: TraversalExecutor::new(),
             item_skill: BotSkill::default(),
             enemy: None,
             last_enemy_pos: None,
+            opponents: OpponentModel::new(),
             last_health: 100,
             time: 0.0,
             chase_deadline: 0.0,
@@ -196,6 +205,15 @@ impl Q3Brain {
         }
     }
 
+    /// Aim through the character's mouse hand ([`crate::mouse`]) instead of snapping to the
+    /// ideal angle ([`BrainConfig::human_aim`](crate::BrainConfig::human_aim)).
+    pub fn with_human_aim(mut self, on: bool) -> Self {
+        if on {
+            self.aim = aim::AimState::new().with_mouse(self.ch.mouse_params());
+        }
+        self
+    }
+
     /// A cheap deterministic `[0,1)` roll (per-bot LCG) for the random Q3 cadences (strafe flip,
     /// dodge chance, fire-throttle window) — keeps behavior repeatable in tests.
     fn roll(&mut self) -> f32 {
@@ -246,6 +264,8 @@ impl Q3Brain {
         mv: &mut MovementIntent,
     ) {
         nav.update(pos, None);
+        crate::traverse::gate_rocket_jumps(nav, self.ch.rocket_jumper(), &view.self_state);
+        self.goal = Some(goal.clone());
         nav.set_goal(goal, pos);
         if let Some(cm) = cm {
             nav.smooth_with_cm(cm, pos);
@@ -345,11 +365,12 @@ impl Q3Brain {
 
     // ── enemy / visibility helpers ─────────────────────────────────────────────────────
 
-    /// Is the current enemy entity still present in the worldview at all (even stale)? `false`
-    /// once the server stops sending it (dead / left PVS for good).
+    /// Is the current enemy entity still present in the worldview at all (even stale), or — out
+    /// of our PVS — still somewhere the opponent model hasn't ruled out? `false` once it's dead
+    /// or lost for good.
     fn enemy_alive(&self, view: &crate::perception::Worldview) -> bool {
         self.enemy
-            .map(|n| view.entities().any(|e| e.entity_number == n))
+            .map(|n| view.entities().any(|e| e.entity_number == n) || self.opponents.believed(n))
             .unwrap_or(false)
     }
 
@@ -402,13 +423,21 @@ impl Q3Brain {
         true
     }
 
-    /// Goal for a battle node: the enemy's current origin if known, else the last place we saw
-    /// it, else hold.
-    fn battle_goal(&self, view: &crate::perception::Worldview) -> NavGoal {
+    /// Goal for a battle node: the enemy's current origin if known, else the opponent model's
+    /// next spot to search, else the last place we saw it, else hold.
+    fn battle_goal(&mut self, view: &crate::perception::Worldview) -> NavGoal {
         if let Some(n) = self.enemy {
             if let Some(e) = view.entities().find(|x| x.entity_number == n) {
                 return NavGoal::Entity(e.origin);
             }
+            let pos = view.self_state().origin;
+            if let Some(p) = self
+                .nav_graph
+                .as_deref()
+                .and_then(|g| self.opponents.search_goal(Some(n), g, pos))
+            {
+                return NavGoal::Position(p);
+            }
         }
         if let Some(p) = self.last_enemy_pos {
             return NavGoal::Position(p);
@@ -466,7 +495,7 @@ impl Q3Brain {
                 view.entities()
                     .find(|e| e.entity_number == n && !e.is_stale)
             })
-            .map(|e| (e.origin - self_pos).length_squared());
+            .map(|e| e.priority_dist_sq(self_pos));
 
         let mut best: Option<(i32, Vec3, f32)> = None;
         for e in view.enemies() {
@@ -475,7 +504,9 @@ impl Q3Brain {
             if d2 > alert_range_sq {
                 continue; // out of detection range
             }
-            if matches!(cur_dist_sq, Some(cd) if d2 > cd) {
+            // Ranked by distance shortened by what this player has done to us before.
+            let rank = e.priority_dist_sq(self_pos);
+            if matches!(cur_dist_sq, Some(cd) if rank > cd) {
                 continue; // don't switch to a farther enemy than the current
             }
             let dist = d2.sqrt();
@@ -509,8 +540,8 @@ impl Q3Brain {
                 }
             }
 
-            if best.map(|(_, _, bd)| d2 < bd).unwrap_or(true) {
-                best = Some((e.entity_number, e.origin, d2));
+            if best.map(|(_, _, bd)| rank < bd).unwrap_or(true) {
+                best = Some((e.entity_number, e.origin, rank));
             }
         }
 
@@ -686,6 +717,7 @@ impl Q3Brain {
                 visible: true,
                 time: self.time,
                 cm,
+                view: (self.steering.view_yaw(), view.self_state().angles.x),
             },
             &mut rng,
         );
@@ -839,6 +871,8 @@ impl Brain for Q3Brain {
             nav_graph,
             roam_as_position,
             items: _, // q3 uses its own neutral PVS item picker (Plan 30 keeps q3 baseline)
+            node_light: _,
+            cm: _,
         } = map;
         self.roam_nodes = roam_nodes;
         // The cursor indexes the OLD map's roster; on a rotation to a smaller map a stale
@@ -847,15 +881,25 @@ impl Brain for Q3Brain {
         self.roam_idx = 0;
         self.nav_graph = Some(nav_graph);
         self.roam_as_position = roam_as_position;
+        self.opponents.reset();
     }
 
     fn status(&self) -> &str {
         self.node.label()
     }
 
+    fn decision(&self) -> Decision {
+        Decision {
+            target: self.enemy,
+            ..Decision::heading(self.goal.as_ref(), self.nav_graph.as_deref())
+        }
+    }
+
     fn on_kill(&mut self) {
         // Reacquire fresh next fight; keep the character fixed (no auto-skill drift in Q3).
-        self.enemy = None;
+        if let Some(n) = self.enemy.take() {
+            self.opponents.forget(n);
+        }
     }
 
     fn on_death(&mut self) {
@@ -884,6 +928,8 @@ impl Brain for Q3Brain {
         let took_damage = health < self.last_health;
 
         // ── 1. Perceive: acquire/refresh the enemy + this frame's aggression decision ──
+        self.opponents
+            .observe(view, self.nav_graph.as_deref(), cm, self.time);
         self.select_enemy(view, cm, took_damage);
         let enemy_visible = self.enemy_visible(view, cm);
         let hdelta = self.enemy_height_delta(view);
@@ -905,12 +951,16 @@ impl Brain for Q3Brain {
                     Q3Node::BattleFight if enemy_visible => {
                         // Keep nav warm (a path to the enemy) for an instant chase if LOS drops.
                         nav.update(pos, None);
-                        nav.set_goal(self.battle_goal(view), pos);
+                        let goal = self.battle_goal(view);
+                        self.goal = Some(goal.clone());
+                        nav.set_goal(goal, pos);
                         weapon_request = self.combat_drive(view, cm, pos, dt, false, &mut mv);
                     }
                     Q3Node::BattleRetreat if enemy_visible => {
                         nav.update(pos, None);
-                        nav.set_goal(self.retreat_goal(view), pos);
+                        let goal = self.retreat_goal(view);
+                        self.goal = Some(goal.clone());
+                        nav.set_goal(goal, pos);
                         weapon_request = self.combat_drive(view, cm, pos, dt, true, &mut mv);
                     }
                     other => {
@@ -1003,6 +1053,8 @@ mod tests {
             )),
             roam_as_position: false,
             items: Vec::new(),
+            node_light: Vec::new(),
+            cm: None,
         };
         let mut b = Q3Brain::new(Q3Character::default());
         b.set_map(map(10));
@@ -1143,6 +1195,52 @@ mod tests {
         assert!(b.enemy.is_none());
     }
 
+    #[test]
+    fn enemy_leaving_pvs_is_chased_to_where_it_could_be() {
+        // A corridor along +x; the enemy (at x=200) drops out of the frame entirely.
+        let n = 10;
+        let adj = (0..n)
+            .map(|i: usize| {
+                [i.checked_sub(1), (i + 1 < n).then_some(i + 1)]
+                    .into_iter()
+                    .flatten()
+                    .map(|j| (j, 100.0))
+                    .collect()
+            })
+            .collect();
+        let mut b = Q3Brain::new(Q3Character::sarge());
+        b.set_map(BrainMap {
+            roam_nodes: Vec::new(),
+            nav_graph: Arc::new(NavGraph::from_raw(
+                (0..n).map(|i| [i as f32 * 100.0, 0.0, 0.0]).collect(),
+                adj,
+            )),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: Vec::new(),
+            cm: None,
+        });
+        let view = view_enemy("models/weapons/v_rail/tris.md2", 100, 8);
+        drive(&mut b, &view);
+        assert_eq!(b.status(), "fight");
+
+        // Same loadout, no enemy entity.
+        let mut frame = Frame::default();
+        frame.playerstate.gunindex = 1;
+        frame.playerstate.stats[1] = 100;
+        frame.playerstate.stats[3] = 8;
+        frame.playerstate.stats[5] = 100;
+        let mut cs = ConfigStrings::default();
+        cs.set(32 + 1, "models/weapons/v_rail/tris.md2");
+        let gone = Worldview::from_frame(&frame, &cs, 0);
+        drive(&mut b, &gone);
+        assert_eq!(b.status(), "chase");
+        assert_eq!(b.enemy, Some(9));
+        assert!(
+            matches!(b.battle_goal(&gone), NavGoal::Position(p) if p == Vec3::new(200.0, 0.0, 0.0))
+        );
+    }
+
     /// Build a view with one enemy at a chosen distance + facing yaw + our health.
     fn view_enemy_at(
         dist: f32,
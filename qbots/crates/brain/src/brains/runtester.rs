@@ -16,8 +16,10 @@ use glam::Vec3;
 
 use crate::brains::core::{Brain, BrainContext, BrainMap, BrainOutput};
 use crate::move_ctrl::MovementIntent;
+use crate::nav::NavGoal;
 use crate::recover::{find_best_direction, Recovery, RecoveryAction};
 use crate::steer::{move_from_world_dir, Steering};
+use crate::timeline::Decision;
 use crate::traverse::{TraversalExecutor, TraversalFrame};
 
 /// The movement-scenario brain — drives the injected navigator to `ctx.goal_override`, never
@@ -34,6 +36,8 @@ pub struct RunTesterBrain {
     /// The shared ladder/swim/ride executor (Plan 46) — owns the water-exit hysteresis + the
     /// stateful board/carry lock that used to live as inline fields here.
     traverse: TraversalExecutor,
+    /// The goal it was last told to drive to (the decision timeline's `goal`).
+    goal: Option<NavGoal>,
 }
 
 impl RunTesterBrain {
@@ -45,6 +49,7 @@ impl RunTesterBrain {
             backoff_ticks: 0,
             escape_yaw: None,
             traverse: TraversalExecutor::new(),
+            goal: None,
         }
     }
 }
@@ -85,6 +90,7 @@ impl Brain for RunTesterBrain {
         // tick, Plan 26 T2; the only change is reading the goal from `goal_override`.)
         nav.update(pos, Some(cm));
         if let Some(goal) = goal_override {
+            self.goal = Some(goal.clone());
             nav.set_goal(goal, pos);
         }
         nav.smooth_with_cm(cm, pos);
@@ -227,6 +233,10 @@ impl Brain for RunTesterBrain {
     fn status(&self) -> &str {
         "runtester"
     }
+
+    fn decision(&self) -> Decision {
+        Decision::heading(self.goal.as_ref(), None)
+    }
 }
 
 #[cfg(test)]
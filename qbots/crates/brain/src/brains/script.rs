@@ -0,0 +1,709 @@
+//! # brain::brains::script — a brain whose decisions live in a Rhai script
+//!
+//! `ScriptBrain` hands each tick's perception to a [Rhai](https://rhai.rs) script and turns the
+//! map it returns into a `MovementIntent`. The script decides *what* to do — where to go, whom
+//! to fight, which weapon to hold — and the native layers decide *how*: a `move_to` order is
+//! walked by the same navigator/steering/recovery stack as [`RunTesterBrain`], a `fight` order is
+//! aimed and fired by the shared [`CombatDriver`]. So a twenty-line script still moves and shoots
+//! like the compiled brains, and a competition roster can field it next to them.
+//!
+//! A script defines any of these functions; only `tick` is required:
+//!
+//! ```text
+//! fn set_map(map)   // map.roam: [Vec3], map.items: [#{class, pos}]
+//! fn tick(bot)      // -> #{move_to, look, fight, fire, weapon, jump, crouch, forward, side}
+//! fn on_kill()
+//! fn on_death()
+//! fn status()       // -> short label for the periodic log line
+//! ```
+//!
+//! Inside a hook `this` is a per-bot object map that survives across ticks *and* reloads. The
+//! script's top-level statements never run, so state belongs on `this`. A watcher thread
+//! re-checks the file's mtime every [`RELOAD_POLL`], off the bot's task: the next tick after a
+//! change recompiles, a clean build swaps in and replays `set_map`, a broken one is logged once
+//! and the previous version keeps driving.
+//!
+//! Natives: `vec3(x, y, z)` with `.x/.y/.z`, `+ - *`, `length()`, `distance(b)`, `normalize()`;
+//! `can_see(a, b)` (a clear straight line between two points); `print`/`debug` go to the log.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+use std::time::{Duration, SystemTime};
+
+use glam::Vec3;
+use rhai::{Array, CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope, AST};
+use rhai::{FLOAT, INT};
+use world::CollisionModel;
+
+use crate::aim::vec3_to_angles;
+use crate::brains::core::{Brain, BrainContext, BrainMap, BrainOutput};
+use crate::brains::runtester::RunTesterBrain;
+use crate::combat::CombatDriver;
+use crate::los::{eye_origin, has_los, has_los_player};
+use crate::move_ctrl::MovementIntent;
+use crate::nav::NavGoal;
+use crate::perception::{EntityClass, SelfState};
+use crate::skill::BotSkill;
+use crate::steer::turn_keeping_course;
+use crate::timeline::Decision;
+use crate::weapons::Weapon;
+
+/// How often the watcher thread checks the script file's mtime.
+pub const RELOAD_POLL: Duration = Duration::from_secs(1);
+
+/// Rhai operations allowed per hook call — a runaway loop errors out instead of wedging the bot.
+const MAX_OPERATIONS: u64 = 200_000;
+
+/// The collision model `can_see` traces against, shared with the engine's native closure
+/// (which must be `'static`, so it can't borrow a tick's `cm`).
+type SharedCm = Arc<RwLock<Option<Arc<CollisionModel>>>>;
+
+/// A compiled script plus which of the optional hooks it defines.
+struct Loaded {
+    ast: AST,
+    set_map: bool,
+    on_kill: bool,
+    on_death: bool,
+    status: bool,
+}
+
+impl Loaded {
+    fn new(ast: AST) -> Result<Self, String> {
+        let has = |name: &str, arity: usize| {
+            ast.iter_functions()
+                .any(|f| f.name == name && f.params.len() == arity)
+        };
+        if !has("tick", 1) {
+            return Err("no `fn tick(bot)`".into());
+        }
+        Ok(Self {
+            set_map: has("set_map", 1),
+            on_kill: has("on_kill", 0),
+            on_death: has("on_death", 0),
+            status: has("status", 0),
+            ast,
+        })
+    }
+}
+
+/// A brain driven by the Rhai script at `path` (see the module docs for the script contract).
+pub struct ScriptBrain {
+    path: PathBuf,
+    engine: Engine,
+    cm: SharedCm,
+    loaded: Option<Loaded>,
+    /// Raised by the [`watch`] thread when the file's mtime moves; the next tick recompiles.
+    changed: Arc<AtomicBool>,
+    /// The script's `this` — persistent per-bot state.
+    this: Dynamic,
+    /// The last `set_map` argument, replayed after a hot reload.
+    map: Option<Map>,
+    legs: RunTesterBrain,
+    combat: CombatDriver,
+    skill: BotSkill,
+    status: String,
+    /// The last error logged, so a hook failing every tick warns once, not at 10 Hz.
+    last_error: Option<String>,
+}
+
+impl ScriptBrain {
+    /// Load the script at `path`. A missing or broken file doesn't fail construction: the brain
+    /// stands idle (status `script-error`) until a fixed version is saved.
+    pub fn new(path: PathBuf, skill: BotSkill) -> Self {
+        let cm = SharedCm::default();
+        let mut brain = Self {
+            engine: engine(Arc::clone(&cm), script_name(&path)),
+            path,
+            cm,
+            loaded: None,
+            changed: Arc::default(),
+            this: Dynamic::from_map(Map::new()),
+            map: None,
+            legs: RunTesterBrain::new(),
+            combat: CombatDriver::new(),
+            skill,
+            status: "script".into(),
+            last_error: None,
+        };
+        brain.reload();
+        watch(brain.path.clone(), Arc::downgrade(&brain.changed));
+        brain
+    }
+
+    /// Recompile from disk. On success the new AST replaces the old and `true` is returned; on
+    /// failure the old AST (if any) keeps running.
+    fn reload(&mut self) -> bool {
+        match compile(&self.engine, &self.path) {
+            Ok(loaded) => {
+                tracing::info!(script = %self.path.display(), "script loaded");
+                self.loaded = Some(loaded);
+                self.last_error = None;
+                true
+            }
+            Err(e) => {
+                self.fail(e);
+                false
+            }
+        }
+    }
+
+    /// Recompile if the watcher saw the file change, and replay `set_map`.
+    fn maybe_reload(&mut self) {
+        if !self.changed.swap(false, Ordering::Relaxed) {
+            return;
+        }
+        if self.reload() {
+            if let Some(map) = self.map.clone() {
+                self.call_set_map(map);
+            }
+        }
+    }
+
+    fn call_set_map(&mut self, map: Map) {
+        if self.loaded.as_ref().is_some_and(|l| l.set_map) {
+            self.call("set_map", (map,));
+        }
+    }
+
+    /// Call `hook` with `this` bound; `None` (and a logged error) if it fails.
+    fn call(&mut self, hook: &str, args: impl FuncArgs) -> Option<Dynamic> {
+        let loaded = self.loaded.as_ref()?;
+        let opts = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.this);
+        let res = self.engine.call_fn_with_options::<Dynamic>(
+            opts,
+            &mut Scope::new(),
+            &loaded.ast,
+            hook,
+            args,
+        );
+        match res {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.fail(format!("{hook}: {e}"));
+                None
+            }
+        }
+    }
+
+    /// Log `err` unless it is the one logged last.
+    fn fail(&mut self, err: String) {
+        if self.last_error.as_ref() != Some(&err) {
+            tracing::warn!(script = %self.path.display(), "{err}");
+            self.last_error = Some(err);
+        }
+    }
+
+    /// Carry out the map `tick` returned.
+    fn obey(&mut self, order: Map, ctx: BrainContext) -> BrainOutput {
+        let BrainContext {
+            view,
+            nav,
+            cm,
+            dt,
+            ticks,
+            goal_override: _,
+        } = ctx;
+        let me = view.self_state();
+        let eye = Vec3::from(eye_origin(me.origin.to_array()));
+
+        for key in order.keys() {
+            if !ORDER_KEYS.contains(&key.as_str()) {
+                self.fail(format!("tick: unknown order `{key}`"));
+            }
+        }
+
+        let mut out = match order.get("move_to").map(|g| g.clone().try_cast::<Vec3>()) {
+            Some(Some(goal)) => {
+                let mut out = self.legs.tick(BrainContext {
+                    view,
+                    nav,
+                    cm,
+                    dt,
+                    ticks,
+                    goal_override: Some(NavGoal::Position(goal)),
+                });
+                // The legs only set a view while moving; standing still must not snap to yaw 0.
+                if out.intent.yaw == 0.0 && out.intent.pitch == 0.0 {
+                    out.intent.look_at(me.angles.y, me.angles.x);
+                }
+                out
+            }
+            bad => {
+                if bad.is_some() {
+                    self.fail("tick: `move_to` must be a Vec3".into());
+                }
+                hold(me)
+            }
+        };
+
+        if let Some(at) = self.order_vec3(&order, "look") {
+            let (yaw, pitch) = vec3_to_angles(at - eye);
+            turn_keeping_course(&mut out.intent, yaw, pitch);
+        }
+        if self.order_flag(&order, "fight") {
+            let dec = self
+                .combat
+                .evaluate(view, &self.skill, ticks as f32 * 0.1, cm);
+            if dec.should_fire {
+                turn_keeping_course(&mut out.intent, dec.aim_yaw, dec.aim_pitch);
+                out.intent.attack();
+            }
+            out.weapon_request = dec.weapon_request.map(|r| r.0);
+        }
+        if let Some(f) = self.order_num(&order, "forward") {
+            out.intent.move_forward(f);
+        }
+        if let Some(s) = self.order_num(&order, "side") {
+            out.intent.move_side(s);
+        }
+        if self.order_flag(&order, "fire") {
+            out.intent.attack();
+        }
+        if self.order_flag(&order, "jump") {
+            out.intent.jump();
+        }
+        if self.order_flag(&order, "crouch") {
+            out.intent.crouch = true;
+        }
+        if let Some(w) = order.get("weapon") {
+            match w
+                .clone()
+                .into_string()
+                .ok()
+                .and_then(|n| Weapon::from_name(&n))
+            {
+                Some(w) => out.weapon_request = Some(w),
+                None => self.fail(format!("tick: unknown weapon {w}")),
+            }
+        }
+        out
+    }
+
+    fn order_flag(&mut self, order: &Map, key: &str) -> bool {
+        match order.get(key).map(Dynamic::as_bool) {
+            Some(Ok(b)) => b,
+            Some(Err(_)) => {
+                self.fail(format!("tick: `{key}` must be a bool"));
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn order_num(&mut self, order: &Map, key: &str) -> Option<f32> {
+        let v = order.get(key)?;
+        num(v)
+            .map_err(|_| self.fail(format!("tick: `{key}` must be a number")))
+            .ok()
+    }
+
+    fn order_vec3(&mut self, order: &Map, key: &str) -> Option<Vec3> {
+        let v = order.get(key)?.clone().try_cast::<Vec3>();
+        if v.is_none() {
+            self.fail(format!("tick: `{key}` must be a Vec3"));
+        }
+        v
+    }
+}
+
+/// Every key a `tick` order may carry; anything else is a typo worth a warning.
+const ORDER_KEYS: [&str; 9] = [
+    "move_to", "look", "fight", "fire", "weapon", "jump", "crouch", "forward", "side",
+];
+
+impl Brain for ScriptBrain {
+    fn set_map(&mut self, map: BrainMap) {
+        *self.cm.write().unwrap_or_else(|e| e.into_inner()) = map.cm.clone();
+        let roam: Array = map
+            .roam_nodes
+            .iter()
+            .map(|&n| Dynamic::from(Vec3::from(map.nav_graph.node_pos(n))))
+            .collect();
+        let items: Array = map
+            .items
+            .iter()
+            .map(|it| {
+                let mut m = Map::new();
+                m.insert("class".into(), class_name(it.class).into());
+                m.insert("pos".into(), Dynamic::from(it.origin));
+                m.into()
+            })
+            .collect();
+        let mut arg = Map::new();
+        arg.insert("roam".into(), roam.into());
+        arg.insert("items".into(), items.into());
+        self.map = Some(arg.clone());
+        self.call_set_map(arg);
+    }
+
+    fn tick(&mut self, ctx: BrainContext) -> BrainOutput {
+        self.maybe_reload();
+        let me = ctx.view.self_state();
+        if self.loaded.is_none() {
+            self.status = "script-error".into();
+            return hold(me);
+        }
+        let bot = bot_map(&ctx);
+        let order = match self.call("tick", (bot,)) {
+            Some(o) if o.is_unit() => Map::new(),
+            Some(o) => match o.try_cast::<Map>() {
+                Some(m) => m,
+                None => {
+                    self.fail("tick: must return an object map".into());
+                    Map::new()
+                }
+            },
+            None => Map::new(),
+        };
+        let out = self.obey(order, ctx);
+        if self.loaded.as_ref().is_some_and(|l| l.status) {
+            if let Some(s) = self.call("status", ()) {
+                self.status = s.to_string();
+            }
+        }
+        out
+    }
+
+    fn on_kill(&mut self) {
+        if self.loaded.as_ref().is_some_and(|l| l.on_kill) {
+            self.call("on_kill", ());
+        }
+    }
+
+    fn on_death(&mut self) {
+        self.combat.on_respawn();
+        if self.loaded.as_ref().is_some_and(|l| l.on_death) {
+            self.call("on_death", ());
+        }
+    }
+
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    fn decision(&self) -> Decision {
+        Decision {
+            target: self.combat.target_entity(),
+            ..self.legs.decision()
+        }
+    }
+}
+
+/// Compile-check the script at `path` without running it: it must parse and define
+/// `fn tick(bot)`. For loaders that want to reject a bad script up front.
+pub fn check(path: &Path) -> Result<(), String> {
+    compile(&engine(SharedCm::default(), script_name(path)), path).map(|_| ())
+}
+
+fn compile(engine: &Engine, path: &Path) -> Result<Loaded, String> {
+    let ast = engine
+        .compile_file(path.to_path_buf())
+        .map_err(|e| e.to_string())?;
+    Loaded::new(ast)
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Poll `path`'s mtime every [`RELOAD_POLL`] on a thread of its own, so the stat never blocks a
+/// bot's task, and raise `changed` when it moves. Exits once the brain owning the flag is gone.
+fn watch(path: PathBuf, changed: Weak<AtomicBool>) {
+    let mut seen = mtime(&path);
+    let spawned = std::thread::Builder::new()
+        .name("script-watch".into())
+        .spawn(move || loop {
+            std::thread::sleep(RELOAD_POLL);
+            let Some(flag) = changed.upgrade() else {
+                return;
+            };
+            let now = mtime(&path);
+            if now != seen {
+                seen = now;
+                flag.store(true, Ordering::Relaxed);
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::warn!("script watcher: {e}; hot reload is off");
+    }
+}
+
+fn script_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// An engine with the script API registered. `cm` backs `can_see`; `name` tags `print` lines.
+fn engine(cm: SharedCm, name: String) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    let tag = name.clone();
+    engine.on_print(move |s| tracing::info!(script = %tag, "{s}"));
+    engine.on_debug(move |s, _, _| tracing::debug!(script = %name, "{s}"));
+
+    let show = |v: &mut Vec3| format!("({:.1} {:.1} {:.1})", v.x, v.y, v.z);
+    engine
+        .register_type_with_name::<Vec3>("Vec3")
+        .register_fn("vec3", |x: Dynamic, y: Dynamic, z: Dynamic| {
+            Ok::<_, Box<EvalAltResult>>(Vec3::new(num(&x)?, num(&y)?, num(&z)?))
+        })
+        .register_get("x", |v: &mut Vec3| v.x)
+        .register_get("y", |v: &mut Vec3| v.y)
+        .register_get("z", |v: &mut Vec3| v.z)
+        .register_fn("+", |a: Vec3, b: Vec3| a + b)
+        .register_fn("-", |a: Vec3, b: Vec3| a - b)
+        .register_fn("-", |a: Vec3| -a)
+        .register_fn("*", |a: Vec3, k: FLOAT| a * k)
+        .register_fn("*", |k: FLOAT, a: Vec3| a * k)
+        .register_fn("*", |a: Vec3, k: INT| a * k as f32)
+        .register_fn("*", |k: INT, a: Vec3| a * k as f32)
+        .register_fn("length", |v: &mut Vec3| v.length())
+        .register_fn("distance", |a: &mut Vec3, b: Vec3| a.distance(b))
+        .register_fn("normalize", |v: &mut Vec3| v.normalize_or_zero())
+        .register_fn("to_string", show)
+        .register_fn("to_debug", show)
+        .register_fn("can_see", move |a: Vec3, b: Vec3| {
+            let cm = cm.read().unwrap_or_else(|e| e.into_inner());
+            // No geometry loaded → nothing can block the line.
+            cm.as_ref()
+                .is_none_or(|cm| has_los(cm, a.to_array(), b.to_array()))
+        });
+    engine
+}
+
+/// A script number as `f32`, accepting both `1` and `1.0`.
+fn num(d: &Dynamic) -> Result<f32, Box<EvalAltResult>> {
+    d.as_float()
+        .or_else(|_| d.as_int().map(|i| i as f32))
+        .map_err(|t| format!("expected a number, got {t}").into())
+}
+
+/// The script-facing name of an entity class.
+fn class_name(class: EntityClass) -> &'static str {
+    match class {
+        EntityClass::SelfPlayer => "self",
+        EntityClass::EnemyPlayer => "enemy",
+        EntityClass::AllyPlayer => "ally",
+        EntityClass::ItemHealth => "health",
+        EntityClass::ItemArmor => "armor",
+        EntityClass::ItemWeapon => "weapon",
+        EntityClass::ItemPowerup => "powerup",
+        EntityClass::ProjectileRocket => "rocket",
+        EntityClass::ProjectileGrenade => "grenade",
+        EntityClass::Flag(_) => "flag",
+        EntityClass::Unknown => "unknown",
+    }
+}
+
+/// The `bot` argument to `tick`: own state (`pos vel eye yaw pitch health armor frags weapon
+/// ammo`), frame timing (`dt ticks`), and what is in view — `enemies: [#{id pos vel dist
+/// visible weapon}]` and `items: [#{class pos dist}]`.
+fn bot_map(ctx: &BrainContext) -> Map {
+    let me = ctx.view.self_state();
+    let eye = eye_origin(me.origin.to_array());
+    let weapon = |w: Option<Weapon>| Dynamic::from(w.map_or("", Weapon::name));
+
+    let enemies: Array = ctx
+        .view
+        .enemies()
+        .map(|e| {
+            let mut m = Map::new();
+            m.insert("id".into(), (e.entity_number as INT).into());
+            m.insert("pos".into(), Dynamic::from(e.origin));
+            m.insert(
+                "vel".into(),
+                Dynamic::from(e.velocity.unwrap_or(Vec3::ZERO)),
+            );
+            m.insert("dist".into(), e.origin.distance(me.origin).into());
+            let visible = ctx
+                .cm
+                .is_none_or(|cm| has_los_player(cm, eye, e.origin.to_array()));
+            m.insert("visible".into(), visible.into());
+            m.insert("weapon".into(), weapon(e.held_weapon));
+            m.into()
+        })
+        .collect();
+    let items: Array = ctx
+        .view
+        .items()
+        .map(|it| {
+            let mut m = Map::new();
+            m.insert("class".into(), class_name(it.class).into());
+            m.insert("pos".into(), Dynamic::from(it.origin));
+            m.insert("dist".into(), it.origin.distance(me.origin).into());
+            m.into()
+        })
+        .collect();
+
+    let mut bot = Map::new();
+    bot.insert("pos".into(), Dynamic::from(me.origin));
+    bot.insert("vel".into(), Dynamic::from(me.velocity));
+    bot.insert("eye".into(), Dynamic::from(Vec3::from(eye)));
+    bot.insert("yaw".into(), me.angles.y.into());
+    bot.insert("pitch".into(), me.angles.x.into());
+    bot.insert("health".into(), (me.health as INT).into());
+    bot.insert("armor".into(), (me.armor as INT).into());
+    bot.insert("frags".into(), (me.frags as INT).into());
+    bot.insert("weapon".into(), weapon(me.held_weapon));
+    bot.insert("ammo".into(), (me.held_ammo() as INT).into());
+    bot.insert("dt".into(), ctx.dt.into());
+    bot.insert("ticks".into(), (ctx.ticks as INT).into());
+    bot.insert("enemies".into(), enemies.into());
+    bot.insert("items".into(), items.into());
+    bot
+}
+
+/// Stand still, keeping the current view.
+fn hold(me: &SelfState) -> BrainOutput {
+    let mut intent = MovementIntent::new();
+    intent.look_at(me.angles.y, me.angles.x);
+    BrainOutput {
+        intent,
+        weapon_request: None,
+        intent_forward: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav_mode::StubNav;
+    use crate::perception::Worldview;
+    use client::parse::ConfigStrings;
+    use q2proto::Frame;
+    use std::io::Write;
+
+    fn script(src: &str) -> tempfile::NamedTempFile {
+        let mut f = tempfile::Builder::new().suffix(".rhai").tempfile().unwrap();
+        f.write_all(src.as_bytes()).unwrap();
+        f
+    }
+    fn open_cm() -> CollisionModel {
+        CollisionModel::half_space([0.0, 0.0, 1.0], -100_000.0)
+    }
+    fn view0() -> Worldview {
+        Worldview::from_frame(&Frame::default(), &ConfigStrings::default(), 0)
+    }
+    fn tick(brain: &mut ScriptBrain, nav: &mut StubNav, ticks: u32) -> BrainOutput {
+        let view = view0();
+        let cm = open_cm();
+        brain.tick(BrainContext {
+            view: &view,
+            nav: Some(nav),
+            cm: Some(&cm),
+            dt: 0.1,
+            ticks,
+            goal_override: None,
+        })
+    }
+
+    #[test]
+    fn check_requires_a_tick_hook() {
+        assert!(check(script("fn tick(bot) { #{} }").path()).is_ok());
+        let err = check(script("fn think(bot) { #{} }").path()).unwrap_err();
+        assert!(err.contains("tick"), "{err}");
+        assert!(check(script("fn tick(bot) { #{ ").path()).is_err());
+        assert!(check(Path::new("/nonexistent/x.rhai")).is_err());
+    }
+
+    #[test]
+    fn move_to_order_drives_the_navigator() {
+        let f = script("fn tick(bot) { #{ move_to: vec3(100, 0, 0), jump: true } }");
+        let mut brain = ScriptBrain::new(f.path().to_path_buf(), BotSkill::default());
+        let mut nav = StubNav {
+            pursue: Some(Vec3::new(100.0, 0.0, 0.0)),
+            ..StubNav::default()
+        };
+        let out = tick(&mut brain, &mut nav, 1);
+        assert_eq!(brain.last_error, None);
+        assert!(matches!(nav.last_goal, Some(NavGoal::Position(p)) if p.x == 100.0));
+        assert!(out.intent.jump);
+        assert_eq!(brain.status(), "script");
+    }
+
+    #[test]
+    fn this_persists_and_status_and_weapon_come_from_the_script() {
+        let f = script(
+            r#"
+            fn tick(bot) { this.n = (this.n ?? 0) + 1; #{ weapon: "rocket-launcher", fire: true } }
+            fn on_kill() { this.kills = (this.kills ?? 0) + 1; }
+            fn status() { `n${this.n}k${this.kills ?? 0}` }
+            "#,
+        );
+        let mut brain = ScriptBrain::new(f.path().to_path_buf(), BotSkill::default());
+        let mut nav = StubNav::default();
+        tick(&mut brain, &mut nav, 1);
+        brain.on_kill();
+        let out = tick(&mut brain, &mut nav, 2);
+        assert_eq!(brain.status(), "n2k1");
+        assert_eq!(out.weapon_request, Some(Weapon::RocketLauncher));
+        assert!(out.intent.attack);
+    }
+
+    #[test]
+    fn look_turns_the_view_and_keeps_the_walk_direction() {
+        let f = script("fn tick(bot) { #{ look: bot.eye + vec3(0, 50, 0), forward: 1.0 } }");
+        let mut brain = ScriptBrain::new(f.path().to_path_buf(), BotSkill::default());
+        let out = tick(&mut brain, &mut StubNav::default(), 1);
+        assert!((out.intent.yaw - 90.0).abs() < 1e-3);
+        assert_eq!(out.intent.forward, 1.0);
+
+        let mut mv = MovementIntent::new();
+        mv.move_forward(1.0); // walking +X at yaw 0…
+        turn_keeping_course(&mut mv, 90.0, 0.0); // …then looking +Y: +X is now to the right
+        assert!(mv.forward.abs() < 1e-5 && (mv.side - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn a_broken_script_idles_and_a_fixed_one_hot_reloads() {
+        let f = script("fn tick(bot) { #{ oops ");
+        let mut brain = ScriptBrain::new(f.path().to_path_buf(), BotSkill::default());
+        let mut nav = StubNav::default();
+        let out = tick(&mut brain, &mut nav, 1);
+        assert_eq!(brain.status(), "script-error");
+        assert!(!out.intent.attack);
+
+        std::fs::write(f.path(), "fn tick(bot) { #{ fire: true } }").unwrap();
+        // Raise the flag by hand: don't wait on the watcher or the filesystem's mtime granularity.
+        brain.changed.store(true, Ordering::Relaxed);
+        let out = tick(&mut brain, &mut nav, 2);
+        assert!(out.intent.attack);
+    }
+
+    #[test]
+    fn the_shipped_hunter_roams_the_map_it_is_given() {
+        let hunter =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../characters/script/hunter.rhai");
+        let mut brain = ScriptBrain::new(hunter, BotSkill::default());
+        let graph = world::NavGraph::from_raw(
+            vec![[500.0, 0.0, 0.0], [900.0, 0.0, 0.0]],
+            vec![vec![(1, 400.0)], vec![(0, 400.0)]],
+        );
+        brain.set_map(BrainMap {
+            roam_nodes: vec![0, 1],
+            nav_graph: Arc::new(graph),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: Vec::new(),
+            cm: Some(Arc::new(open_cm())),
+        });
+        let mut nav = StubNav::default();
+        tick(&mut brain, &mut nav, 1);
+        assert_eq!(brain.last_error, None);
+        assert_eq!(brain.status(), "roam");
+        assert!(matches!(nav.last_goal, Some(NavGoal::Position(p)) if p.x == 500.0));
+    }
+
+    #[test]
+    fn a_runaway_script_errors_instead_of_hanging() {
+        let f = script("fn tick(bot) { loop {} }");
+        let mut brain = ScriptBrain::new(f.path().to_path_buf(), BotSkill::default());
+        let out = tick(&mut brain, &mut StubNav::default(), 1);
+        assert!(!out.intent.attack);
+        assert!(brain.last_error.is_some());
+    }
+}
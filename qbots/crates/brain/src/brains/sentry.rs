@@ -10,6 +10,7 @@ use crate::brains::core::{Brain, BrainContext, BrainMap, BrainOutput};
 use crate::combat::CombatDriver;
 use crate::move_ctrl::MovementIntent;
 use crate::skill::BotSkill;
+use crate::timeline::Decision;
 
 /// A stationary, combat-only brain: aim + fire at any LOS enemy, never move.
 pub struct SentryBrain {
@@ -49,6 +50,13 @@ impl Brain for SentryBrain {
     fn status(&self) -> &str {
         "sentry"
     }
+
+    fn decision(&self) -> Decision {
+        Decision {
+            target: self.combat.target_entity(),
+            ..Decision::default()
+        }
+    }
 }
 
 #[cfg(test)]
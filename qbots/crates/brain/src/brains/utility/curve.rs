@@ -0,0 +1,110 @@
+//! Response curves — how a consideration turns its `0..1` input into a `0..1` score.
+//!
+//! The shapes are the usual utility-AI set: a straight line, a power curve, a logistic
+//! S-curve and a hard step. Any of them can be flipped (`invert`), so "low health favours
+//! retreat" is the same logistic as "high health favours engage", mirrored.
+
+/// The shape of a [`Curve`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    /// `m·x + b`.
+    Linear { m: f32, b: f32 },
+    /// `x^k`: `k > 1` stays low until late, `k < 1` rises early.
+    Power { k: f32 },
+    /// S-curve crossing 0.5 at `mid`; `slope` sets how sharply (≈ 10 is a soft knee).
+    Logistic { mid: f32, slope: f32 },
+    /// 0 below `at`, 1 from it.
+    Step { at: f32 },
+}
+
+/// A response curve: a [`Shape`], optionally mirrored to `1 - y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Curve {
+    pub shape: Shape,
+    pub invert: bool,
+}
+
+impl Curve {
+    pub const fn linear(m: f32, b: f32) -> Self {
+        Self::of(Shape::Linear { m, b })
+    }
+
+    pub const fn power(k: f32) -> Self {
+        Self::of(Shape::Power { k })
+    }
+
+    pub const fn logistic(mid: f32, slope: f32) -> Self {
+        Self::of(Shape::Logistic { mid, slope })
+    }
+
+    pub const fn step(at: f32) -> Self {
+        Self::of(Shape::Step { at })
+    }
+
+    const fn of(shape: Shape) -> Self {
+        Self {
+            shape,
+            invert: false,
+        }
+    }
+
+    /// The same curve mirrored (`1 - y`).
+    pub const fn inverted(self) -> Self {
+        Self {
+            shape: self.shape,
+            invert: !self.invert,
+        }
+    }
+
+    /// The score for input `x`. Both `x` and the result are clamped to `0..=1`.
+    pub fn eval(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        let y = match self.shape {
+            Shape::Linear { m, b } => m * x + b,
+            Shape::Power { k } => x.powf(k),
+            Shape::Logistic { mid, slope } => 1.0 / (1.0 + (-slope * (x - mid)).exp()),
+            Shape::Step { at } => {
+                if x >= at {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        };
+        let y = if y.is_nan() { 0.0 } else { y.clamp(0.0, 1.0) };
+        if self.invert {
+            1.0 - y
+        } else {
+            y
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_hit_their_landmarks() {
+        assert_eq!(Curve::linear(1.0, 0.0).eval(0.25), 0.25);
+        assert_eq!(Curve::linear(-0.5, 1.0).eval(1.0), 0.5);
+        assert!((Curve::power(2.0).eval(0.5) - 0.25).abs() < 1e-6);
+        assert!((Curve::logistic(0.3, 12.0).eval(0.3) - 0.5).abs() < 1e-6);
+        assert!(Curve::logistic(0.3, 12.0).eval(0.9) > 0.99);
+        assert_eq!(Curve::step(0.5).eval(0.49), 0.0);
+        assert_eq!(Curve::step(0.5).eval(0.5), 1.0);
+    }
+
+    #[test]
+    fn output_is_clamped_and_inversion_mirrors_it() {
+        assert_eq!(Curve::linear(3.0, 0.0).eval(0.9), 1.0);
+        assert_eq!(Curve::linear(1.0, -2.0).eval(0.5), 0.0);
+        // Out-of-range input is clamped before the shape sees it.
+        assert_eq!(Curve::power(0.5).eval(-4.0), 0.0);
+        let c = Curve::power(2.0);
+        for x in [0.0, 0.3, 0.7, 1.0] {
+            assert!((c.inverted().eval(x) - (1.0 - c.eval(x))).abs() < 1e-6);
+        }
+        assert_eq!(c.inverted().inverted(), c);
+    }
+}
@@ -0,0 +1,726 @@
+//! # brain::brains::utility — the utility-AI brain (`uti`)
+//!
+//! `main` is a hand-written FSM, `q3` a node FSM, `xon` rates goals on one value/distance
+//! scale. `UtilityBrain` instead scores every option it has each tick and does the best one:
+//!
+//! - **Options** are concrete: engage *this* enemy, grab *that* armor, camp *that* pad, hunt
+//!   *that* lost opponent, retreat, dodge, or roam ([`Action`]).
+//! - **Considerations** score one normalized input of an option through a response
+//!   [`Curve`] — health, ammo, weapon, distance, threat, item need, how long the item will
+//!   still be gone ([`ItemMemory::respawn_in`]), how stale a hunt's trail is, how close a
+//!   projectile is ([`Input`]). An option's score is its action's weight times the
+//!   (compensated) product of its considerations ([`score`]).
+//! - **Profiles** — the weights and every curve — are data ([`UtilityProfile`]), loaded from
+//!   `characters/utility/` by the binary, so retuning a behavior never touches this file.
+//!
+//! The option already being carried out gets a [`MOMENTUM`] bonus, so two near-equal options
+//! don't flip every tick. Every tick's [`Breakdown`] — the winner plus the best option of each
+//! action with each consideration's input and response — goes to the `debug` log (`UTIL …`)
+//! and stays readable through [`UtilityBrain::breakdown`].
+//!
+//! Movement is [`RunTesterBrain`]'s navigator/steering/recovery stack driven to the option's
+//! goal; aiming and firing are the shared [`CombatDriver`], run whatever the option is.
+
+pub mod curve;
+pub mod score;
+
+use std::fmt;
+use std::sync::Arc;
+
+use glam::Vec3;
+use world::NavGraph;
+
+pub use curve::{Curve, Shape};
+pub use score::{Action, Input, Part, Scored, UtilityProfile};
+
+use crate::aim::vec3_to_angles;
+use crate::brains::core::{Brain, BrainConfig, BrainContext, BrainMap, BrainOutput, MapItem};
+use crate::brains::runtester::RunTesterBrain;
+use crate::combat::CombatDriver;
+use crate::danger::DangerDriver;
+use crate::items::ItemMemory;
+use crate::los::{eye_origin, has_los_player};
+use crate::move_ctrl::{MovementIntent, MAX_SPEED};
+use crate::nav::NavGoal;
+use crate::opponent::OpponentModel;
+use crate::perception::{EntityClass, PerceivedEntity, Worldview};
+use crate::skill::BotSkill;
+use crate::steer::{move_from_world_dir, turn_keeping_course, STRAFE_PERIOD_SECS};
+use crate::timeline::{rounded, Alternative, Decision};
+use crate::weapons::{ideal_range, Weapon};
+
+/// Score multiplier for the option being carried out (hysteresis against flip-flopping).
+pub const MOMENTUM: f32 = 1.2;
+
+/// Distance that normalizes to 1 for [`Input::Distance`].
+const DISTANCE_SCALE: f32 = 2048.0;
+/// Seconds that normalize to 1 for [`Input::Respawn`] (the common Q2 respawn time).
+const RESPAWN_SCALE: f32 = 30.0;
+/// Seconds that normalize to 1 for [`Input::Staleness`].
+const STALENESS_SCALE: f32 = 10.0;
+/// Pads farther than this are never camped.
+const CAMP_RANGE: f32 = 1024.0;
+/// Within this of the pad a camper stops and waits.
+const CAMP_HOLD_RADIUS: f32 = 32.0;
+/// Bonus (in units of distance from the enemy) a health pad gets as a retreat spot.
+const HEALTH_PULL: f32 = 400.0;
+
+/// Ammo that counts as a full stock for [`Input::Ammo`]: a few engagements' worth.
+fn comfortable_ammo(w: Weapon) -> Option<f32> {
+    match w {
+        Weapon::Blaster => None,
+        Weapon::Shotgun | Weapon::SuperShotgun => Some(20.0),
+        Weapon::Machinegun | Weapon::Chaingun | Weapon::Hyperblaster => Some(100.0),
+        Weapon::GrenadeLauncher | Weapon::RocketLauncher | Weapon::Railgun => Some(10.0),
+        Weapon::Bfg10k => Some(100.0),
+    }
+}
+
+/// What an option is aimed at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    None,
+    /// An enemy's entity number.
+    Enemy(i32),
+    /// An index into the map's item table.
+    Item(usize),
+}
+
+/// One scored option.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub action: Action,
+    pub target: Target,
+    /// Where carrying it out walks to.
+    pub goal: Vec3,
+    pub scored: Scored,
+    /// It is the option already being carried out (its score includes [`MOMENTUM`]).
+    pub current: bool,
+}
+
+impl Candidate {
+    /// `engage#12`, `grab@4`, `roam`.
+    pub fn label(&self) -> String {
+        match self.target {
+            Target::None => self.action.name().to_string(),
+            Target::Enemy(n) => format!("{}#{n}", self.action.name()),
+            Target::Item(i) => format!("{}@{i}", self.action.name()),
+        }
+    }
+}
+
+/// One tick's decision: the winner and the runner-up of every other action.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Breakdown {
+    /// The best option of each action that had one, best first; the first is the winner.
+    pub options: Vec<Candidate>,
+}
+
+impl Breakdown {
+    pub fn chosen(&self) -> Option<&Candidate> {
+        self.options.first()
+    }
+}
+
+impl fmt::Display for Breakdown {
+    /// `engage#12 0.71 (health 1.00→1.00 …) | grab@4 0.32 (…) | roam 0.15`, a `*` marking the
+    /// option that was already running.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (k, c) in self.options.iter().enumerate() {
+            let sep = if k == 0 { "" } else { " | " };
+            let mark = if c.current { "*" } else { "" };
+            write!(f, "{sep}{}{mark} {}", c.label(), c.scored)?;
+        }
+        Ok(())
+    }
+}
+
+/// Per-tick facts every option reads.
+struct Situation {
+    pos: Vec3,
+    health: f32,
+    ammo: f32,
+    weapon: f32,
+    /// Danger from every enemy in view, combined.
+    threat: f32,
+}
+
+/// The utility-AI brain. See the module docs.
+pub struct UtilityBrain {
+    profile: UtilityProfile,
+    skill: BotSkill,
+    cfg: BrainConfig,
+    legs: RunTesterBrain,
+    combat: CombatDriver,
+    danger: DangerDriver,
+    items: Vec<MapItem>,
+    item_memory: ItemMemory,
+    opponents: OpponentModel,
+    nav_graph: Option<Arc<NavGraph>>,
+    /// Roam cycle, as world positions.
+    roam: Vec<Vec3>,
+    roam_idx: usize,
+    /// Seconds since connect (accumulated from `dt`).
+    time: f32,
+    /// The option being carried out.
+    current: Option<(Action, Target)>,
+    breakdown: Breakdown,
+    status: &'static str,
+}
+
+impl UtilityBrain {
+    pub fn new(profile: UtilityProfile, skill: BotSkill, cfg: BrainConfig) -> Self {
+        let combat = if cfg.human_aim {
+            CombatDriver::new().with_mouse(skill.mouse_params())
+        } else {
+            CombatDriver::new()
+        };
+        Self {
+            profile,
+            skill,
+            cfg,
+            legs: RunTesterBrain::new(),
+            combat,
+            danger: DangerDriver::new(),
+            items: Vec::new(),
+            item_memory: ItemMemory::new(),
+            opponents: OpponentModel::new(),
+            nav_graph: None,
+            roam: Vec::new(),
+            roam_idx: 0,
+            time: 0.0,
+            current: None,
+            breakdown: Breakdown::default(),
+            status: "roam",
+        }
+    }
+
+    /// The last tick's decision.
+    pub fn breakdown(&self) -> &Breakdown {
+        &self.breakdown
+    }
+
+    /// Score every option open this tick, best per action, best action first.
+    fn decide(&mut self, view: &Worldview, cm: Option<&world::CollisionModel>) -> Breakdown {
+        let me = view.self_state();
+        let eye = eye_origin(me.origin.to_array());
+        let held = me.held_weapon.unwrap_or(Weapon::Blaster);
+        let visible: Vec<&PerceivedEntity> = if self.cfg.combat_enabled {
+            view.enemies()
+                .filter(|e| !e.is_stale)
+                .filter(|e| cm.is_none_or(|cm| has_los_player(cm, eye, e.origin.to_array())))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let sit = Situation {
+            pos: me.origin,
+            health: me.health as f32 / 100.0,
+            ammo: comfortable_ammo(held).map_or(1.0, |full| me.held_ammo() as f32 / full),
+            weapon: held.power() / 100.0,
+            threat: 1.0
+                - visible
+                    .iter()
+                    .map(|e| 1.0 - enemy_danger(e, me.origin))
+                    .product::<f32>(),
+        };
+
+        let mut options: Vec<Candidate> = Vec::new();
+        let p = self.profile;
+        let mut offer =
+            |action: Action, target: Target, goal: Vec3, input: &dyn Fn(Input) -> f32| {
+                options.push(Candidate {
+                    action,
+                    target,
+                    goal,
+                    scored: p.score(action, input),
+                    current: false,
+                });
+            };
+
+        for e in &visible {
+            let danger = enemy_danger(e, sit.pos);
+            let dist = e.origin.distance(sit.pos);
+            offer(
+                Action::Engage,
+                Target::Enemy(e.entity_number),
+                e.origin,
+                &|i| match i {
+                    Input::Distance => dist / DISTANCE_SCALE,
+                    Input::Threat => danger,
+                    _ => sit.input(i),
+                },
+            );
+        }
+        if let Some(spot) = self.retreat_spot(&visible, &sit) {
+            offer(Action::Retreat, Target::None, spot, &|i| sit.input(i));
+        }
+        for (i, item) in self.items.iter().enumerate() {
+            let need = need(item.class, &sit, me.armor);
+            if need <= 0.0 {
+                continue;
+            }
+            let dist = item.origin.distance(sit.pos);
+            let back_in = self.item_memory.respawn_in(i, item.class, self.time);
+            let wait = (back_in - dist / MAX_SPEED).max(0.0);
+            offer(Action::Grab, Target::Item(i), item.origin, &|k| match k {
+                Input::Need => need,
+                Input::Distance => dist / DISTANCE_SCALE,
+                Input::Respawn => wait / RESPAWN_SCALE,
+                _ => sit.input(k),
+            });
+            if back_in > 0.0 && dist < CAMP_RANGE {
+                offer(Action::Camp, Target::Item(i), item.origin, &|k| match k {
+                    Input::Need => need,
+                    Input::Distance => dist / DISTANCE_SCALE,
+                    Input::Respawn => back_in / RESPAWN_SCALE,
+                    _ => sit.input(k),
+                });
+            }
+        }
+        if let (true, Some(graph)) = (self.cfg.combat_enabled, self.nav_graph.clone()) {
+            let lost: Vec<(i32, f32)> = self
+                .opponents
+                .tracks()
+                .filter(|t| t.believed())
+                .map(|t| (t.entity, self.time - t.last_seen))
+                .collect();
+            for (n, since) in lost {
+                let Some(spot) = self.opponents.search_goal(Some(n), &graph, sit.pos) else {
+                    continue;
+                };
+                let dist = spot.distance(sit.pos);
+                offer(Action::Hunt, Target::Enemy(n), spot, &|i| match i {
+                    Input::Staleness => since / STALENESS_SCALE,
+                    Input::Distance => dist / DISTANCE_SCALE,
+                    _ => sit.input(i),
+                });
+            }
+        }
+        if self.cfg.combat_enabled {
+            let dodge = self.danger.evaluate(view, self.skill.combat());
+            if dodge.is_active() {
+                offer(
+                    Action::Dodge,
+                    Target::None,
+                    sit.pos + dodge.strafe_dir * 64.0,
+                    &|i| match i {
+                        Input::Danger => dodge.urgency,
+                        _ => sit.input(i),
+                    },
+                );
+            }
+        }
+        let roam = self.roam.get(self.roam_idx).copied().unwrap_or(sit.pos);
+        offer(Action::Roam, Target::None, roam, &|i| sit.input(i));
+
+        for c in &mut options {
+            if self.current == Some((c.action, c.target)) {
+                c.scored.score *= MOMENTUM;
+                c.current = true;
+            }
+        }
+        // Best first; then keep only each action's best.
+        options.sort_by(|a, b| b.scored.score.total_cmp(&a.scored.score));
+        let mut seen = Vec::new();
+        options.retain(|c| {
+            let first = !seen.contains(&c.action);
+            seen.push(c.action);
+            first
+        });
+        Breakdown { options }
+    }
+
+    /// Where to fall back to: the spot (an available health pad, else a roam point) that puts
+    /// the most distance between us and the enemies in view, for the least running.
+    /// `None` with nobody in view.
+    fn retreat_spot(&self, visible: &[&PerceivedEntity], sit: &Situation) -> Option<Vec3> {
+        if visible.is_empty() {
+            return None;
+        }
+        let away = |p: Vec3| {
+            let gap = visible
+                .iter()
+                .map(|e| e.origin.distance(p))
+                .fold(f32::MAX, f32::min);
+            gap - 0.5 * p.distance(sit.pos)
+        };
+        let health = self.items.iter().enumerate().filter(|(i, it)| {
+            it.class == EntityClass::ItemHealth
+                && self.item_memory.available(*i, it.class, self.time)
+        });
+        health
+            .map(|(_, it)| (it.origin, away(it.origin) + HEALTH_PULL))
+            .chain(self.roam.iter().map(|&p| (p, away(p))))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(p, _)| p)
+    }
+
+    /// Carry out `choice`: walk to its goal, or for engage/dodge/camp, move in place.
+    fn act(&mut self, choice: &Candidate, ctx: BrainContext) -> BrainOutput {
+        let me = ctx.view.self_state();
+        let here = me.origin;
+        match choice.action {
+            Action::Engage => {
+                let band = ideal_range(me.held_weapon.unwrap_or(Weapon::Blaster));
+                let to = choice.goal - here;
+                let dist = to.length();
+                let (yaw, pitch) = vec3_to_angles(to);
+                if dist > band.ideal {
+                    let mut out = self.walk(choice.goal, ctx);
+                    turn_keeping_course(&mut out.intent, yaw, pitch);
+                    return out;
+                }
+                // In the band: circle-strafe, backing off if inside it.
+                let flat = Vec3::new(to.x, to.y, 0.0).normalize_or_zero();
+                let side = Vec3::new(-flat.y, flat.x, 0.0) * self.strafe_sign();
+                let back = if dist < band.backup {
+                    -flat
+                } else {
+                    Vec3::ZERO
+                };
+                let (fwd, side) =
+                    move_from_world_dir((side + back).normalize_or_zero(), yaw, false);
+                let mut intent = MovementIntent::new();
+                intent.look_at(yaw, pitch);
+                intent.move_forward(fwd);
+                intent.move_side(side);
+                still(intent)
+            }
+            Action::Dodge => {
+                let (fwd, side) = move_from_world_dir(choice.goal - here, me.angles.y, false);
+                let mut intent = MovementIntent::new();
+                intent.look_at(me.angles.y, me.angles.x);
+                intent.move_forward(fwd);
+                intent.move_side(side);
+                if me.velocity.z.abs() < 40.0 {
+                    intent.jump();
+                }
+                still(intent)
+            }
+            Action::Camp if here.distance(choice.goal) < CAMP_HOLD_RADIUS => {
+                let mut intent = MovementIntent::new();
+                intent.look_at(me.angles.y, me.angles.x);
+                still(intent)
+            }
+            _ => self.walk(choice.goal, ctx),
+        }
+    }
+
+    /// Drive the legs to `goal`, keeping the current view when they stand still.
+    fn walk(&mut self, goal: Vec3, ctx: BrainContext) -> BrainOutput {
+        let angles = ctx.view.self_state().angles;
+        let mut out = self.legs.tick(BrainContext {
+            goal_override: Some(NavGoal::Position(goal)),
+            ..ctx
+        });
+        if out.intent.yaw == 0.0 && out.intent.pitch == 0.0 {
+            out.intent.look_at(angles.y, angles.x);
+        }
+        out
+    }
+
+    /// +1/-1, flipping every [`STRAFE_PERIOD_SECS`].
+    fn strafe_sign(&self) -> f32 {
+        if ((self.time / STRAFE_PERIOD_SECS) as u32).is_multiple_of(2) {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// Step the roam cycle once we reach its current point.
+    fn advance_roam(&mut self, pos: Vec3) {
+        if let Some(&p) = self.roam.get(self.roam_idx) {
+            if p.distance(pos) < 64.0 {
+                self.roam_idx = (self.roam_idx + self.roam.len() / 7 + 1) % self.roam.len();
+            }
+        }
+    }
+}
+
+impl Situation {
+    /// The inputs that don't depend on the option.
+    fn input(&self, i: Input) -> f32 {
+        match i {
+            Input::Health => self.health,
+            Input::Ammo => self.ammo,
+            Input::Weapon => self.weapon,
+            Input::Threat => self.threat,
+            Input::Distance | Input::Need | Input::Respawn | Input::Staleness | Input::Danger => {
+                0.0
+            }
+        }
+    }
+}
+
+/// How dangerous `e` is to a bot at `pos`, `0..=1`: its gun's power, more so up close, and
+/// more again for a rival with a history of beating us.
+fn enemy_danger(e: &PerceivedEntity, pos: Vec3) -> f32 {
+    let power = e.held_weapon.map_or(0.5, |w| w.power() / 100.0);
+    let near = 1.0 - (e.origin.distance(pos) / DISTANCE_SCALE).min(1.0);
+    (power * (0.5 + 0.5 * near) * (1.0 + e.threat)).min(1.0)
+}
+
+/// How much an item of `class` is wanted, `0..=1`.
+fn need(class: EntityClass, sit: &Situation, armor: i32) -> f32 {
+    match class {
+        EntityClass::ItemHealth => (1.0 - sit.health).clamp(0.0, 1.0),
+        EntityClass::ItemArmor => (1.0 - armor as f32 / 100.0).clamp(0.0, 1.0),
+        EntityClass::ItemWeapon => (1.0 - sit.weapon).max(1.0 - sit.ammo).clamp(0.0, 1.0),
+        EntityClass::ItemPowerup => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// An output that moves without the navigator.
+fn still(intent: MovementIntent) -> BrainOutput {
+    BrainOutput {
+        intent,
+        weapon_request: None,
+        intent_forward: intent.forward,
+    }
+}
+
+impl Brain for UtilityBrain {
+    fn set_map(&mut self, map: BrainMap) {
+        self.roam = map
+            .roam_nodes
+            .iter()
+            .map(|&n| Vec3::from(map.nav_graph.node_pos(n)))
+            .collect();
+        self.roam_idx = 0;
+        self.items = map.items;
+        self.item_memory = ItemMemory::new();
+        self.opponents.reset();
+        self.nav_graph = Some(map.nav_graph);
+        self.current = None;
+    }
+
+    fn tick(&mut self, ctx: BrainContext) -> BrainOutput {
+        self.time += ctx.dt;
+        let view = ctx.view;
+        let cm = ctx.cm;
+        let pos = view.self_state().origin;
+        self.item_memory.observe(&self.items, view, self.time);
+        if self.cfg.combat_enabled {
+            self.opponents
+                .observe(view, self.nav_graph.as_deref(), cm, self.time);
+        }
+        self.advance_roam(pos);
+
+        let mut out = if let Some(goal) = ctx.goal_override.clone() {
+            self.status = "override";
+            self.breakdown = Breakdown::default();
+            self.legs.tick(BrainContext {
+                goal_override: Some(goal),
+                ..ctx
+            })
+        } else {
+            self.breakdown = self.decide(view, cm);
+            tracing::debug!("UTIL {}", self.breakdown);
+            let choice = self.breakdown.chosen().cloned();
+            match choice {
+                Some(c) => {
+                    self.current = Some((c.action, c.target));
+                    self.status = c.action.name();
+                    self.act(&c, ctx)
+                }
+                None => still(MovementIntent::new()),
+            }
+        };
+
+        if self.cfg.combat_enabled {
+            let dec = self.combat.evaluate(view, &self.skill, self.time, cm);
+            if dec.should_fire {
+                turn_keeping_course(&mut out.intent, dec.aim_yaw, dec.aim_pitch);
+                out.intent.attack();
+            } else {
+                self.combat.release_aim();
+            }
+            out.weapon_request = dec.weapon_request.map(|r| r.0);
+        }
+        out
+    }
+
+    fn on_kill(&mut self) {
+        if let Some((Action::Engage, Target::Enemy(n))) = self.current {
+            self.opponents.forget(n);
+        }
+    }
+
+    fn on_death(&mut self) {
+        self.combat.on_respawn();
+        self.current = None;
+    }
+
+    fn status(&self) -> &str {
+        self.status
+    }
+
+    fn decision(&self) -> Decision {
+        let Some(chosen) = self.breakdown.chosen() else {
+            return self.legs.decision();
+        };
+        let options = self.breakdown.options.iter().enumerate();
+        Decision {
+            target: match chosen.target {
+                Target::Enemy(n) => Some(n),
+                _ => self.combat.target_entity(),
+            },
+            goal: Some(rounded(chosen.goal)),
+            options: options
+                .map(|(k, c)| Alternative::new(c.label(), c.scored.score, k == 0))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nav_mode::StubNav;
+    use client::parse::ConfigStrings;
+    use q2proto::Frame;
+
+    fn view_with(health: i32) -> Worldview {
+        let mut frame = Frame::default();
+        frame.playerstate.stats[1] = health as i16; // STAT_HEALTH
+        Worldview::from_frame(&frame, &ConfigStrings::default(), 0)
+    }
+
+    fn brain_on(items: Vec<MapItem>) -> UtilityBrain {
+        let mut brain = UtilityBrain::new(
+            UtilityProfile::default(),
+            BotSkill::default(),
+            BrainConfig::default(),
+        );
+        let graph = NavGraph::from_raw(
+            vec![[600.0, 0.0, 0.0], [0.0, 600.0, 0.0]],
+            vec![vec![(1, 850.0)], vec![(0, 850.0)]],
+        );
+        brain.set_map(BrainMap {
+            roam_nodes: vec![0, 1],
+            nav_graph: Arc::new(graph),
+            roam_as_position: false,
+            items,
+            node_light: Vec::new(),
+            cm: None,
+        });
+        brain
+    }
+
+    fn open_cm() -> world::CollisionModel {
+        world::CollisionModel::half_space([0.0, 0.0, 1.0], -100_000.0)
+    }
+
+    fn tick(brain: &mut UtilityBrain, view: &Worldview, nav: &mut StubNav) -> BrainOutput {
+        brain.tick(BrainContext {
+            view,
+            nav: Some(nav),
+            cm: Some(&open_cm()),
+            dt: 0.1,
+            ticks: 1,
+            goal_override: None,
+        })
+    }
+
+    fn item(class: EntityClass, x: f32) -> MapItem {
+        MapItem {
+            class,
+            origin: Vec3::new(x, 0.0, 0.0),
+            nav_node: None,
+        }
+    }
+
+    #[test]
+    fn a_hurt_bot_grabs_health_and_a_healthy_one_roams() {
+        // Out of sight range, so the pad is assumed stocked.
+        let items = vec![item(EntityClass::ItemHealth, 800.0)];
+        let mut nav = StubNav::default();
+
+        let mut hurt = brain_on(items.clone());
+        tick(&mut hurt, &view_with(25), &mut nav);
+        assert_eq!(hurt.status(), "grab");
+        assert!(matches!(nav.last_goal, Some(NavGoal::Position(p)) if p.x == 800.0));
+        let b = hurt.breakdown();
+        assert_eq!(b.chosen().unwrap().label(), "grab@0");
+        assert!(b.options.iter().any(|c| c.action == Action::Roam));
+        let d = hurt.decision();
+        assert_eq!(d.chosen(), Some("grab@0"));
+        assert_eq!(d.goal, Some([800.0, 0.0, 0.0]));
+        assert_eq!(d.options.len(), b.options.len());
+
+        let mut fit = brain_on(items);
+        tick(&mut fit, &view_with(100), &mut nav);
+        assert_eq!(fit.status(), "roam");
+        assert!(matches!(nav.last_goal, Some(NavGoal::Position(p)) if p.x == 600.0));
+    }
+
+    #[test]
+    fn an_item_gone_for_a_while_is_camped_not_run_to() {
+        // Armor 100 u away, seen taken 2 s ago: 18 s left, so grabbing it means waiting there.
+        let mut brain = brain_on(vec![item(EntityClass::ItemArmor, 100.0)]);
+        brain.time = 2.0;
+        brain
+            .item_memory
+            .observe(&brain.items.clone(), &view_with(100), 0.0);
+        let b = brain.decide(&view_with(100), None);
+        let score = |a| {
+            b.options
+                .iter()
+                .find(|c| c.action == a)
+                .unwrap()
+                .scored
+                .score
+        };
+        assert!(score(Action::Camp) > score(Action::Grab), "{b}");
+        assert_eq!(b.chosen().unwrap().action, Action::Camp);
+    }
+
+    #[test]
+    fn the_running_option_keeps_its_momentum() {
+        let mut brain = brain_on(vec![item(EntityClass::ItemHealth, 800.0)]);
+        let mut nav = StubNav::default();
+        tick(&mut brain, &view_with(60), &mut nav);
+        tick(&mut brain, &view_with(60), &mut nav);
+        let chosen = brain.breakdown().chosen().unwrap();
+        assert!(chosen.current);
+        assert!(brain
+            .breakdown()
+            .to_string()
+            .contains(&format!("{}* ", chosen.label())));
+    }
+
+    #[test]
+    fn a_goal_override_bypasses_the_decision() {
+        let mut brain = brain_on(Vec::new());
+        let mut nav = StubNav::default();
+        let goal = NavGoal::Position(Vec3::new(5.0, 6.0, 7.0));
+        brain.tick(BrainContext {
+            view: &view_with(100),
+            nav: Some(&mut nav),
+            cm: Some(&open_cm()),
+            dt: 0.1,
+            ticks: 1,
+            goal_override: Some(goal.clone()),
+        });
+        assert_eq!(brain.status(), "override");
+        assert_eq!(nav.last_goal, Some(goal));
+    }
+
+    #[test]
+    fn need_tracks_what_is_missing() {
+        let sit = Situation {
+            pos: Vec3::ZERO,
+            health: 0.4,
+            ammo: 1.0,
+            weapon: 1.0,
+            threat: 0.0,
+        };
+        assert!((need(EntityClass::ItemHealth, &sit, 0) - 0.6).abs() < 1e-6);
+        assert_eq!(need(EntityClass::ItemArmor, &sit, 100), 0.0);
+        assert_eq!(need(EntityClass::ItemWeapon, &sit, 0), 0.0);
+        assert_eq!(need(EntityClass::ItemPowerup, &sit, 0), 1.0);
+    }
+}
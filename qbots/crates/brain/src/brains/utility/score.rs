@@ -0,0 +1,329 @@
+//! Actions, considerations and the profile that weighs them.
+//!
+//! An action's score is its weight times the product of its considerations, each a curve
+//! over one normalized input. A product punishes long lists (five 0.9s are 0.59), so each
+//! factor gets the usual compensation: `y + y·(1 - y)·(1 - 1/n)` for `n` considerations.
+//! Which inputs an action looks at is fixed here ([`DEFAULTS`]); the curves and weights are
+//! the tunable [`UtilityProfile`].
+
+use std::fmt;
+
+use super::curve::Curve;
+
+/// Something the utility brain can decide to do this tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Fight a visible enemy: close to the held weapon's range band and strafe.
+    Engage,
+    /// Break off toward health or away from the enemies in view.
+    Retreat,
+    /// Run to a map item we believe is on its pad (or will be when we arrive).
+    Grab,
+    /// Search for an enemy we lost sight of.
+    Hunt,
+    /// Wait on an empty pad for its item to respawn.
+    Camp,
+    /// Sidestep an incoming rocket or grenade.
+    Dodge,
+    /// Nothing better to do: walk the roam cycle.
+    Roam,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::Engage,
+        Action::Retreat,
+        Action::Grab,
+        Action::Hunt,
+        Action::Camp,
+        Action::Dodge,
+        Action::Roam,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Engage => "engage",
+            Action::Retreat => "retreat",
+            Action::Grab => "grab",
+            Action::Hunt => "hunt",
+            Action::Camp => "camp",
+            Action::Dodge => "dodge",
+            Action::Roam => "roam",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A normalized (`0..=1`) fact a consideration scores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Input {
+    /// Own health, `/ 100`.
+    Health,
+    /// Held weapon's ammo against a comfortable stock for it (the Blaster is always 1).
+    Ammo,
+    /// Held weapon's power (`Weapon::power / 100`).
+    Weapon,
+    /// Straight-line distance to the option's target, `/ 2048`.
+    Distance,
+    /// Danger from the target enemy (engage), or from everyone in view (anything else).
+    Threat,
+    /// How much we want the item: missing health/armor, a weak or dry gun; 1 for a powerup.
+    Need,
+    /// Seconds the item will still be gone when we get there (camp: from now), `/ 30`.
+    Respawn,
+    /// Seconds since the hunted enemy was last seen, `/ 10`.
+    Staleness,
+    /// The incoming projectile's [`urgency`](crate::danger::DodgeAction::urgency).
+    Danger,
+}
+
+impl Input {
+    pub const ALL: [Input; 9] = [
+        Input::Health,
+        Input::Ammo,
+        Input::Weapon,
+        Input::Distance,
+        Input::Threat,
+        Input::Need,
+        Input::Respawn,
+        Input::Staleness,
+        Input::Danger,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Input::Health => "health",
+            Input::Ammo => "ammo",
+            Input::Weapon => "weapon",
+            Input::Distance => "distance",
+            Input::Threat => "threat",
+            Input::Need => "need",
+            Input::Respawn => "respawn",
+            Input::Staleness => "staleness",
+            Input::Danger => "danger",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|i| i.name() == name)
+    }
+}
+
+/// Every consideration the brain scores, with its default curve. An action multiplies exactly
+/// the rows naming it; `Roam` has none, so it scores its bare weight — the floor to beat.
+pub const DEFAULTS: [(Action, Input, Curve); 21] = [
+    // Fight when healthy, stocked and armed; nearer and less dangerous targets first.
+    (Action::Engage, Input::Health, Curve::logistic(0.3, 10.0)),
+    (Action::Engage, Input::Ammo, Curve::logistic(0.15, 20.0)),
+    (Action::Engage, Input::Weapon, Curve::linear(0.6, 0.4)),
+    (Action::Engage, Input::Distance, Curve::linear(-0.6, 1.0)),
+    (Action::Engage, Input::Threat, Curve::linear(-0.4, 1.0)),
+    // Run when hurt, outgunned, or facing someone dangerous.
+    (
+        Action::Retreat,
+        Input::Health,
+        Curve::logistic(0.35, 12.0).inverted(),
+    ),
+    (Action::Retreat, Input::Threat, Curve::linear(0.6, 0.4)),
+    (Action::Retreat, Input::Weapon, Curve::linear(-0.7, 1.0)),
+    // Grab what we need, near, and back on its pad by the time we get there.
+    (Action::Grab, Input::Need, Curve::power(0.5)),
+    (Action::Grab, Input::Distance, Curve::linear(-0.8, 1.0)),
+    (
+        Action::Grab,
+        Input::Respawn,
+        Curve::logistic(0.15, 20.0).inverted(),
+    ),
+    (Action::Grab, Input::Threat, Curve::linear(-0.5, 1.0)),
+    // Hunt while healthy and stocked, and while the trail is fresh.
+    (Action::Hunt, Input::Health, Curve::logistic(0.5, 10.0)),
+    (Action::Hunt, Input::Ammo, Curve::logistic(0.15, 20.0)),
+    (Action::Hunt, Input::Staleness, Curve::linear(-1.0, 1.0)),
+    (Action::Hunt, Input::Distance, Curve::linear(-0.5, 1.0)),
+    // Camp a nearby pad that is back soon, if we want what is on it and nobody is shooting.
+    (Action::Camp, Input::Respawn, Curve::linear(-1.2, 1.0)),
+    (Action::Camp, Input::Need, Curve::power(0.5)),
+    (Action::Camp, Input::Distance, Curve::linear(-1.0, 1.0)),
+    (Action::Camp, Input::Threat, Curve::linear(-1.0, 1.0)),
+    (Action::Dodge, Input::Danger, Curve::logistic(0.1, 30.0)),
+];
+
+/// Default per-action weights, in [`Action::ALL`] order.
+pub const DEFAULT_WEIGHTS: [f32; 7] = [1.0, 0.9, 0.8, 0.6, 0.5, 1.5, 0.15];
+
+/// A utility character: one weight per action and one curve per [`DEFAULTS`] row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UtilityProfile {
+    weights: [f32; 7],
+    curves: [Curve; DEFAULTS.len()],
+}
+
+impl Default for UtilityProfile {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_WEIGHTS,
+            curves: DEFAULTS.map(|(_, _, c)| c),
+        }
+    }
+}
+
+impl UtilityProfile {
+    pub fn weight(&self, action: Action) -> f32 {
+        self.weights[action.index()]
+    }
+
+    pub fn set_weight(&mut self, action: Action, weight: f32) {
+        self.weights[action.index()] = weight;
+    }
+
+    /// The curve `action` applies to `input`; `None` if it doesn't consider that input.
+    pub fn curve(&self, action: Action, input: Input) -> Option<Curve> {
+        row(action, input).map(|r| self.curves[r])
+    }
+
+    pub fn set_curve(&mut self, action: Action, input: Input, curve: Curve) -> Result<(), String> {
+        let r = row(action, input)
+            .ok_or_else(|| format!("{} has no `{}` consideration", action.name(), input.name()))?;
+        self.curves[r] = curve;
+        Ok(())
+    }
+
+    /// The inputs `action` considers, with this profile's curve for each.
+    pub fn considerations(&self, action: Action) -> impl Iterator<Item = (Input, Curve)> + '_ {
+        DEFAULTS
+            .iter()
+            .zip(&self.curves)
+            .filter(move |((a, _, _), _)| *a == action)
+            .map(|((_, i, _), c)| (*i, *c))
+    }
+
+    /// Score `action` given each input's value (see the module docs for the formula).
+    pub fn score(&self, action: Action, input: impl Fn(Input) -> f32) -> Scored {
+        let n = self.considerations(action).count();
+        let make_up = if n == 0 { 0.0 } else { 1.0 - 1.0 / n as f32 };
+        let mut score = self.weight(action);
+        let mut parts = Vec::with_capacity(n);
+        for (i, curve) in self.considerations(action) {
+            let x = input(i);
+            let y = curve.eval(x);
+            score *= y + y * (1.0 - y) * make_up;
+            parts.push(Part { input: i, x, y });
+        }
+        Scored { score, parts }
+    }
+}
+
+fn row(action: Action, input: Input) -> Option<usize> {
+    DEFAULTS
+        .iter()
+        .position(|&(a, i, _)| a == action && i == input)
+}
+
+/// One consideration's share of a score.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Part {
+    pub input: Input,
+    /// The normalized input.
+    pub x: f32,
+    /// The curve's response to it.
+    pub y: f32,
+}
+
+/// An action's score and how it was reached.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scored {
+    pub score: f32,
+    pub parts: Vec<Part>,
+}
+
+impl fmt::Display for Scored {
+    /// `0.62 (health 0.80→0.97 ammo 1.00→1.00)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2}", self.score)?;
+        for (k, p) in self.parts.iter().enumerate() {
+            let open = if k == 0 { " (" } else { " " };
+            write!(f, "{open}{} {:.2}→{:.2}", p.input.name(), p.x, p.y)?;
+        }
+        if !self.parts.is_empty() {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for a in Action::ALL {
+            assert_eq!(Action::from_name(a.name()), Some(a));
+        }
+        for i in Input::ALL {
+            assert_eq!(Input::from_name(i.name()), Some(i));
+        }
+        assert_eq!(Action::from_name("flee"), None);
+    }
+
+    #[test]
+    fn roam_scores_its_weight_and_engage_falls_with_health() {
+        let p = UtilityProfile::default();
+        assert_eq!(p.score(Action::Roam, |_| 0.0).score, 0.15);
+        let fit = p.score(
+            Action::Engage,
+            |i| if i == Input::Health { 1.0 } else { 0.5 },
+        );
+        let hurt = p.score(
+            Action::Engage,
+            |i| if i == Input::Health { 0.1 } else { 0.5 },
+        );
+        assert!(fit.score > 2.0 * hurt.score, "{fit} vs {hurt}");
+        assert_eq!(fit.parts.len(), p.considerations(Action::Engage).count());
+    }
+
+    #[test]
+    fn compensation_keeps_long_lists_competitive() {
+        // Four considerations at 0.9 each: a bare product would be 0.66.
+        let mut p = UtilityProfile::default();
+        let defaults = p;
+        for (i, _) in defaults.considerations(Action::Camp) {
+            p.set_curve(Action::Camp, i, Curve::linear(0.0, 0.9))
+                .unwrap();
+        }
+        p.set_weight(Action::Camp, 1.0);
+        let s = p.score(Action::Camp, |_| 0.0).score;
+        assert!(s > 0.8 && s < 0.9, "{s}");
+    }
+
+    #[test]
+    fn only_existing_considerations_can_be_tuned() {
+        let mut p = UtilityProfile::default();
+        let c = Curve::power(3.0);
+        p.set_curve(Action::Hunt, Input::Staleness, c).unwrap();
+        assert_eq!(p.curve(Action::Hunt, Input::Staleness), Some(c));
+        let err = p.set_curve(Action::Dodge, Input::Ammo, c).unwrap_err();
+        assert_eq!(err, "dodge has no `ammo` consideration");
+        assert_eq!(p.curve(Action::Roam, Input::Health), None);
+    }
+
+    #[test]
+    fn breakdown_reads_left_to_right() {
+        let s = Scored {
+            score: 0.5,
+            parts: vec![Part {
+                input: Input::Health,
+                x: 0.8,
+                y: 0.97,
+            }],
+        };
+        assert_eq!(s.to_string(), "0.50 (health 0.80→0.97)");
+    }
+}
@@ -108,8 +108,7 @@ impl EnemyTracker {
         Self::default()
     }
 
-    /// The current enemy entity number, if engaged. Consumed by T4/T5 (chase awareness).
-    #[allow(dead_code)]
+    /// The current enemy entity number, if engaged.
     pub fn current(&self) -> Option<i32> {
         self.current
     }
@@ -181,8 +180,8 @@ impl EnemyTracker {
             .entities()
             .filter(|e| e.class == EntityClass::EnemyPlayer && visible(e))
             .min_by(|a, b| {
-                let da = (a.origin - pos).length_squared();
-                let db = (b.origin - pos).length_squared();
+                let da = a.priority_dist_sq(pos);
+                let db = b.priority_dist_sq(pos);
                 da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
             })
             .map(|e| Enemy {
@@ -25,6 +25,7 @@ use glam::Vec3;
 use world::NavGraph;
 
 use crate::brains::core::MapItem;
+use crate::ctf::{CtfGoal, CtfTask};
 use crate::items::ItemMemory;
 use crate::perception::EntityClass;
 use crate::weapons::Weapon;
@@ -50,6 +51,8 @@ const PROGRESS_EPS: f32 = 8.0;
 const REACH_RADIUS: f32 = 48.0;
 /// Assumed enemy hp+armor (not on the Q2 wire; distilled §2 adaptation).
 const ENEMY_HP_EST: f32 = 100.0;
+/// An enemy we can't see is worth half one we can — the search spot is only a guess.
+const HUNT_SCALE: f32 = 0.5;
 /// Class-level item base values (Q2 adaptation of `bot_pickupbasevalue`; the BSP table is
 /// class-granular — the exact weapon/health size on a pad isn't tracked).
 const POWERUP_VALUE: f32 = 10_000.0;
@@ -57,6 +60,19 @@ const WEAPON_CLASS_VALUE: f32 = 6_000.0;
 const HEALTH_ARMOR_BASE: f32 = 5_000.0;
 /// Assumed pickup amount for class-level health/armor rating.
 const HA_AMOUNT_EST: f32 = 25.0;
+/// How many of a rating session's candidates are kept for the decision timeline.
+const RATED_KEEP: usize = 8;
+
+/// CTF objective values (`sv_ctf.qc` `havocbot_role_ctf_*`): the flag goals outrate every
+/// item, and a capture in hand outrates everything.
+fn ctf_value(task: CtfTask) -> f32 {
+    match task {
+        CtfTask::Capture => 50_000.0,
+        CtfTask::Return => 40_000.0,
+        CtfTask::Escort => 25_000.0,
+        CtfTask::Attack => 20_000.0,
+    }
+}
 
 /// What the current committed goal is (the ignore list is keyed on this).
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -67,6 +83,20 @@ pub enum GoalKey {
     Enemy(i32),
     /// Roam node index.
     Wander(usize),
+    /// The current CTF objective ([`crate::ctf::ctf_goal`]).
+    Ctf(CtfTask),
+}
+
+impl GoalKey {
+    /// `item@4`, `enemy#12`, `wander@310`, `ctf:return`.
+    pub fn label(&self) -> String {
+        match self {
+            GoalKey::Item(i) => format!("item@{i}"),
+            GoalKey::Enemy(n) => format!("enemy#{n}"),
+            GoalKey::Wander(n) => format!("wander@{n}"),
+            GoalKey::Ctf(t) => format!("ctf:{}", format!("{t:?}").to_lowercase()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -128,12 +158,17 @@ pub struct RatingCtx<'a> {
     pub memory: &'a ItemMemory,
     /// PVS-visible enemies this frame: (entity number, origin).
     pub enemies: &'a [(i32, Vec3)],
+    /// Enemies out of sight, at the opponent model's next spot to search for them
+    /// ([`crate::opponent::OpponentModel::search_goal`]).
+    pub hunted: &'a [(i32, Vec3)],
     pub roam_nodes: &'a [usize],
     pub pos: Vec3,
     pub health: f32,
     pub armor: f32,
     /// Held weapon — the arsenal-value estimate for the weapon discount.
     pub held: Weapon,
+    /// This frame's CTF objective (`None` outside team games).
+    pub ctf: Option<CtfGoal>,
     pub now: f32,
 }
 
@@ -154,6 +189,8 @@ pub struct XonGoals {
     wd: Watchdog,
     /// Last two wander destinations (the ×0.1 revisit penalty, `roles.qc:31-34`).
     wander_prev: [Option<Vec3>; 2],
+    /// The last rating session's best candidates and their ratings, best first.
+    rated: Vec<(GoalKey, f32)>,
 }
 
 impl XonGoals {
@@ -165,6 +202,7 @@ impl XonGoals {
             ignore: Vec::new(),
             wd: Watchdog::default(),
             wander_prev: [None; 2],
+            rated: Vec::new(),
         }
         .with_stagger(stagger)
     }
@@ -216,12 +254,24 @@ impl XonGoals {
                     }
                 }
             }
-            // Live-track a committed enemy's position (movable goal).
+            // Live-track a committed enemy's position (movable goal) — or, out of sight, the
+            // spot we are searching for it.
             if let GoalKey::Enemy(id) = cur.key {
-                if let Some(&(_, p)) = ctx.enemies.iter().find(|(e, _)| *e == id) {
+                if let Some(&(_, p)) = ctx.enemies.iter().chain(ctx.hunted).find(|(e, _)| *e == id)
+                {
                     self.current = Some(Committed { pos: p, ..cur });
                 }
             }
+            // A CTF objective tracks its flag/carrier; when the objective itself changes
+            // (flag taken, dropped, returned) re-rate at once.
+            if let GoalKey::Ctf(task) = cur.key {
+                match ctx.ctf {
+                    Some(g) if g.task == task => {
+                        self.current = Some(Committed { pos: g.pos, ..cur });
+                    }
+                    _ => expire = true,
+                }
+            }
             // Progress watchdog: strike 1 → replan; strike 2 → dump + ignore 3 s.
             if !expire && self.wd.stalled(ctx.pos, cur.pos, dt) {
                 if self.wd.strikes >= 2 {
@@ -244,7 +294,9 @@ impl XonGoals {
 
         // ── Rating session (one flood; only when uncommitted) ─────────────────────────
         if self.current.is_none() {
-            self.current = self.rate(rng, sk, ctx);
+            let (best, rated) = self.rate(rng, sk, ctx);
+            self.current = best;
+            self.rated = rated;
             if let Some(c) = self.current {
                 self.wd.reset(ctx.pos, c.pos);
                 replan = true; // fresh goal — drop any stale polyline
@@ -261,10 +313,28 @@ impl XonGoals {
         })
     }
 
+    /// The committed goal and where it is.
+    pub fn current(&self) -> Option<(GoalKey, Vec3)> {
+        self.current.map(|c| (c.key, c.pos))
+    }
+
+    /// The last rating session's best candidates, best first.
+    pub fn rated(&self) -> &[(GoalKey, f32)] {
+        &self.rated
+    }
+
     /// One rating session (`navigation_goalrating_start..end`): flood once, rate every
-    /// candidate, commit the best.
-    fn rate(&self, rng: &mut Lcg, sk: &XonSkill, ctx: &RatingCtx<'_>) -> Option<Committed> {
-        let source = ctx.graph.nearest(&[ctx.pos.x, ctx.pos.y, ctx.pos.z])?;
+    /// candidate, commit the best. Also returns the best few candidates with their ratings.
+    fn rate(
+        &self,
+        rng: &mut Lcg,
+        sk: &XonSkill,
+        ctx: &RatingCtx<'_>,
+    ) -> (Option<Committed>, Vec<(GoalKey, f32)>) {
+        let mut rated = Vec::new();
+        let Some(source) = ctx.graph.nearest(&[ctx.pos.x, ctx.pos.y, ctx.pos.z]) else {
+            return (None, rated);
+        };
         let costs = ctx.graph.flood_costs(source);
         let cost_s = |node: usize| -> Option<f32> {
             let c = *costs.get(node)?;
@@ -274,6 +344,7 @@ impl XonGoals {
         let mut best: Option<(f32, Committed)> = None;
         fn consider(
             best: &mut Option<(f32, Committed)>,
+            rated: &mut Vec<(GoalKey, f32)>,
             score: f32,
             key: GoalKey,
             pos: Vec3,
@@ -286,6 +357,7 @@ impl XonGoals {
                 } else {
                     STRATEGY_INTERVAL
                 };
+            rated.push((key, score));
             if best.as_ref().is_none_or(|(s, _)| score > *s) {
                 *best = Some((score, Committed { key, pos, until }));
             }
@@ -326,6 +398,7 @@ impl XonGoals {
             }
             consider(
                 &mut best,
+                &mut rated,
                 route_rating(value, RANGEBIAS_QU, cost),
                 key,
                 item.origin,
@@ -334,8 +407,10 @@ impl XonGoals {
             );
         }
 
-        // Enemies (PVS-visible only — that IS Xonotic's visibility check for us).
-        for &(id, pos) in ctx.enemies {
+        // Enemies: PVS-visible (that IS Xonotic's visibility check for us), then the ones we
+        // lost, discounted.
+        let enemies = ctx.enemies.iter().map(|&e| (e, 1.0));
+        for ((id, pos), scale) in enemies.chain(ctx.hunted.iter().map(|&e| (e, HUNT_SCALE))) {
             let key = GoalKey::Enemy(id);
             let dist = (pos - ctx.pos).length();
             if self.ignored(key, ctx.now) || dist < rating::ENEMY_MIN_RATE_DIST {
@@ -345,12 +420,13 @@ impl XonGoals {
                 continue;
             };
             let Some(cost) = cost_s(node) else { continue };
-            let value = enemy_rating(ctx.health + ctx.armor, ENEMY_HP_EST, sk.skill);
+            let value = enemy_rating(ctx.health + ctx.armor, ENEMY_HP_EST, sk.skill) * scale;
             if value <= 0.0 {
                 continue;
             }
             consider(
                 &mut best,
+                &mut rated,
                 route_rating(value, RANGEBIAS_QU, cost),
                 key,
                 pos,
@@ -359,6 +435,27 @@ impl XonGoals {
             );
         }
 
+        // The CTF objective, rated like any other goal but valued above every item.
+        if let Some(g) = ctx.ctf {
+            let key = GoalKey::Ctf(g.task);
+            let cost = ctx
+                .graph
+                .nearest(&[g.pos.x, g.pos.y, g.pos.z])
+                .and_then(cost_s);
+            if let (false, Some(cost)) = (self.ignored(key, ctx.now), cost) {
+                let movable = matches!(g.task, CtfTask::Return | CtfTask::Escort);
+                consider(
+                    &mut best,
+                    &mut rated,
+                    route_rating(ctf_value(g.task), RANGEBIAS_QU, cost),
+                    key,
+                    g.pos,
+                    movable,
+                    ctx.now,
+                );
+            }
+        }
+
         // Wander fallback — ONLY when nothing else rated (`roles.qc:19-20`).
         if best.is_none() {
             for &node in ctx.roam_nodes {
@@ -375,6 +472,7 @@ impl XonGoals {
                 let f = wander_value(rng, near0, near1);
                 consider(
                     &mut best,
+                    &mut rated,
                     route_rating(f, RANGEBIAS_QU, cost),
                     key,
                     p,
@@ -384,7 +482,9 @@ impl XonGoals {
             }
         }
 
-        best.map(|(_, c)| c)
+        rated.sort_by(|a, b| b.1.total_cmp(&a.1));
+        rated.truncate(RATED_KEEP);
+        (best.map(|(_, c)| c), rated)
     }
 }
 
@@ -431,11 +531,13 @@ mod tests {
             items,
             memory,
             enemies,
+            hunted: &[],
             roam_nodes: roam,
             pos: Vec3::ZERO,
             health: 100.0,
             armor: 0.0,
             held: Weapon::Blaster,
+            ctf: None,
             now,
         }
     }
@@ -462,6 +564,10 @@ mod tests {
             .expect("a goal");
         assert_eq!(d.key, GoalKey::Item(1), "powerup wins the session");
         assert!(d.replan, "fresh goal requests a replan");
+        // The session's candidates are kept, best first, for the decision timeline.
+        let rated: Vec<_> = goals.rated().iter().map(|(k, _)| k.label()).collect();
+        assert_eq!(rated, ["item@1", "item@0"]);
+        assert_eq!(goals.current().map(|(k, _)| k), Some(GoalKey::Item(1)));
     }
 
     #[test]
@@ -504,6 +610,63 @@ mod tests {
         assert_eq!(d.key, GoalKey::Enemy(7));
     }
 
+    #[test]
+    fn lost_enemy_is_rated_at_its_search_spot_for_less() {
+        let g = line_graph();
+        let mem = ItemMemory::new();
+        let seen = [(7, Vec3::new(400.0, 0.0, 0.0))];
+        let mut goals = XonGoals::new(0.0);
+        let mut rng = Lcg::new(1);
+        let sk = XonSkill::default();
+        let mut c = ctx(&g, &[], &mem, &[], &[], 1.0);
+        c.armor = 100.0;
+        c.hunted = &seen;
+        let d = goals.tick(&mut rng, &sk, &c, 0.1).expect("a goal");
+        assert_eq!(d.key, GoalKey::Enemy(7));
+        assert_eq!(d.goal_pos, Vec3::new(400.0, 0.0, 0.0));
+
+        // The search moves on: so does the goal.
+        let next = [(7, Vec3::new(600.0, 0.0, 0.0))];
+        c.hunted = &next;
+        let d = goals.tick(&mut rng, &sk, &c, 0.1).expect("a goal");
+        assert_eq!(d.goal_pos, Vec3::new(600.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn ctf_objective_outrates_items_and_follows_the_carrier() {
+        let g = line_graph();
+        let items = [item(EntityClass::ItemPowerup, 1, &g)];
+        let mem = ItemMemory::new();
+        let mut goals = XonGoals::new(0.0);
+        let mut rng = Lcg::new(1);
+        let mut c = ctx(&g, &items, &mem, &[], &[], 1.0);
+        c.ctf = Some(CtfGoal {
+            task: CtfTask::Escort,
+            pos: Vec3::new(600.0, 0.0, 0.0),
+        });
+        let d = goals
+            .tick(&mut rng, &XonSkill::default(), &c, 0.1)
+            .expect("a goal");
+        assert_eq!(d.key, GoalKey::Ctf(CtfTask::Escort));
+
+        // The carrier moves: the committed goal moves with them.
+        c.ctf = Some(CtfGoal {
+            task: CtfTask::Escort,
+            pos: Vec3::new(400.0, 0.0, 0.0),
+        });
+        let d = goals
+            .tick(&mut rng, &XonSkill::default(), &c, 0.1)
+            .expect("a goal");
+        assert_eq!(d.goal_pos, Vec3::new(400.0, 0.0, 0.0));
+
+        // The run is over (no objective): re-rate at once, back to the powerup.
+        c.ctf = None;
+        let d = goals
+            .tick(&mut rng, &XonSkill::default(), &c, 0.1)
+            .expect("a goal");
+        assert_eq!(d.key, GoalKey::Item(0));
+    }
+
     #[test]
     fn wander_only_when_nothing_else_rates() {
         let g = line_graph();
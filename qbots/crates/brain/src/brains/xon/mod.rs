@@ -36,6 +36,7 @@ use crate::perception::EntityClass;
 use crate::recover::{Recovery, RecoveryAction};
 use crate::skill::BotSkill;
 use crate::steer::{move_from_world_dir, Steering};
+use crate::timeline::{rounded, Alternative, Decision};
 use crate::traverse::{TraversalExecutor, TraversalFrame};
 use crate::xonchar::XonSkill;
 use crate::xoncore::aim::{AimInputs, Angles, XonAim};
@@ -53,6 +54,11 @@ use goals::{RatingCtx, XonGoals};
 /// flood the graph on the same frame (the poor-man's strategy token, `bot.qc:784-811`).
 static BOT_ORDINAL: AtomicUsize = AtomicUsize::new(0);
 
+/// `skill + bot_aimskill` from which rockets and grenades aim through the [`crate::splash`]
+/// solver instead of straight at the led enemy — the same mid-skill cut as `main`'s accuracy 3
+/// and Q3's `aim_skill > 0.6`, on the vendor's `[0, 10]` skill scale.
+const SPLASH_SOLVER_MIN_AIM: f32 = 6.0;
+
 /// The Xonotic-derived decision brain. Owns the goal-stack strategy (T2), combat (T3–T5),
 /// and the locomotion state; the `Navigator` is injected each tick.
 pub struct XonBrain {
@@ -73,6 +79,10 @@ pub struct XonBrain {
     map_items: Vec<MapItem>,
     /// PVS-honest taken/respawn memory over `map_items` (shared `items::ItemMemory`).
     item_memory: ItemMemory,
+    /// CTF flag pads from `map_items` (empty off CTF maps).
+    flag_bases: crate::ctf::FlagBases,
+    /// Where the enemies we lost sight of could be — rated as goals at their search spots.
+    opponents: crate::opponent::OpponentModel,
     /// The goal-stack strategy layer (T2).
     goals: XonGoals,
     /// Sticky enemy selection (T3).
@@ -108,15 +118,24 @@ impl XonBrain {
     /// Build an `xon` brain with the given personality. Roam goals + the nav graph arrive
     /// later via [`set_map`](Brain::set_map).
     pub fn new(sk: XonSkill, cfg: BrainConfig) -> Self {
-        Self::with_ordinal(sk, cfg, BOT_ORDINAL.fetch_add(1, Ordering::Relaxed))
+        let ordinal = match cfg.seed {
+            Some(seed) => seed as usize,
+            None => BOT_ORDINAL.fetch_add(1, Ordering::Relaxed),
+        };
+        Self::with_ordinal(sk, cfg, ordinal)
     }
 
     /// Deterministic constructor: everything seeded from `ordinal` (tests pin it; `new`
-    /// takes the process-wide counter).
+    /// takes [`BrainConfig::seed`] or else the process-wide counter).
     fn with_ordinal(sk: XonSkill, cfg: BrainConfig, ordinal: usize) -> Self {
         // Path-following turn rate scales with movement skill (XonAim owns combat turning
         // from T4); qport-independent, deterministic.
         let steering = Steering::new(1.0 + (sk.movement() / 10.0).clamp(0.0, 1.0) * 4.0);
+        let aim = if cfg.human_aim {
+            XonAim::new().with_mouse(sk.mouse_params())
+        } else {
+            XonAim::new()
+        };
         Self {
             sk,
             rng: Lcg::new(0x584f_4e21 ^ ordinal as u32), // "XON!" + per-bot ordinal
@@ -127,11 +146,13 @@ impl XonBrain {
             roam_as_position: false,
             map_items: Vec::new(),
             item_memory: ItemMemory::new(),
+            flag_bases: crate::ctf::FlagBases::default(),
+            opponents: crate::opponent::OpponentModel::new(),
             goals: XonGoals::new(ordinal as f32 * 0.35),
             enemy: EnemyTracker::new(),
             weapon: WeaponChooser::new(),
             fired_at: None,
-            aim: XonAim::new(),
+            aim,
             view_pitch: 0.0,
             keyboard: KeyboardEmu::new(),
             overshoot_until: 0.0,
@@ -189,17 +210,32 @@ impl XonBrain {
         } else {
             Vec::new()
         };
+        let hunted: Vec<(i32, Vec3)> = if self.cfg.combat_enabled {
+            let lost: Vec<i32> = self
+                .opponents
+                .tracks()
+                .filter(|t| t.believed())
+                .map(|t| t.entity)
+                .collect();
+            lost.into_iter()
+                .filter_map(|n| Some((n, self.opponents.search_goal(Some(n), &graph, pos)?)))
+                .collect()
+        } else {
+            Vec::new()
+        };
         let ss = view.self_state();
         let ctx = RatingCtx {
             graph: &graph,
             items: &self.map_items,
             memory: &self.item_memory,
             enemies: &enemies,
+            hunted: &hunted,
             roam_nodes: &self.roam_nodes,
             pos,
             health: ss.health as f32,
             armor: ss.armor as f32,
             held: ss.held_weapon.unwrap_or(crate::weapons::Weapon::Blaster),
+            ctf: crate::ctf::ctf_goal(view, &self.flag_bases),
             now: self.time,
         };
         let d = self.goals.tick(&mut self.rng, &self.sk, &ctx, dt)?;
@@ -207,6 +243,7 @@ impl XonBrain {
             goals::GoalKey::Item(_) => "xon-item",
             goals::GoalKey::Enemy(_) => "xon-enemy",
             goals::GoalKey::Wander(_) => "xon-wander",
+            goals::GoalKey::Ctf(_) => "xon-ctf",
         };
         Some((NavGoal::Position(d.goal_pos), d.replan))
     }
@@ -226,6 +263,7 @@ impl XonBrain {
         mv: &mut MovementIntent,
     ) -> (bool, f32) {
         nav.update(pos, None);
+        crate::traverse::gate_rocket_jumps(nav, self.sk.rocket_jumper(), &view.self_state);
         nav.set_goal(goal, pos);
         if let Some(cm) = cm {
             nav.smooth_with_cm(cm, pos);
@@ -343,13 +381,17 @@ impl Brain for XonBrain {
             nav_graph,
             roam_as_position,
             items,
+            node_light: _,
+            cm: _,
         } = map;
         self.roam_nodes = roam_nodes;
         // Stale cursor from the previous map panics on a smaller roster (Plan 65 T4).
         self.roam_idx = 0;
         self.nav_graph = Some(nav_graph);
         self.roam_as_position = roam_as_position;
+        self.opponents.reset();
         // The static item table feeds the rating sessions (values × ItemMemory availability).
+        self.flag_bases = crate::ctf::FlagBases::from_items(&items);
         self.map_items = items;
     }
 
@@ -357,6 +399,21 @@ impl Brain for XonBrain {
         self.status
     }
 
+    fn decision(&self) -> Decision {
+        let current = self.goals.current();
+        let chosen = current.map(|(k, _)| k);
+        Decision {
+            target: self.enemy.current(),
+            goal: current.map(|(_, p)| rounded(p)),
+            options: self
+                .goals
+                .rated()
+                .iter()
+                .map(|&(k, s)| Alternative::new(k.label(), s, Some(k) == chosen))
+                .collect(),
+        }
+    }
+
     fn tick(&mut self, ctx: BrainContext) -> BrainOutput {
         let BrainContext {
             view,
@@ -379,6 +436,8 @@ impl Brain for XonBrain {
         // ── Combat perception (T3): sticky enemy + weapon choice ───────────────────────
         let mut weapon_request = None;
         let enemy = if self.cfg.combat_enabled {
+            self.opponents
+                .observe(view, self.nav_graph.as_deref(), cm, self.time);
             self.enemy.tick(view, cm, self.time)
         } else {
             None
@@ -468,10 +527,21 @@ impl Brain for XonBrain {
                             t.fraction * 1000.0
                         })
                         .unwrap_or(f32::INFINITY);
+                    // Skilled aimers send rockets and grenades where the splash solver flies
+                    // them (the vendor's `findtrajectorywithleading`); the point already
+                    // carries the lead.
+                    let (target_pos, target_vel) = cm
+                        .filter(|_| self.sk.aim() >= SPLASH_SOLVER_MIN_AIM)
+                        .and_then(|c| crate::splash::solve(c, pos, e.pos, e.vel, held))
+                        .map(|shot| {
+                            let reach = (e.pos - eye_v).length();
+                            (eye_v + shot.direction() * reach, Vec3::ZERO)
+                        })
+                        .unwrap_or((e.pos, e.vel.unwrap_or(Vec3::ZERO)));
                     let inputs = AimInputs {
                         eye: eye_v,
-                        target_pos: e.pos,
-                        target_vel: e.vel.unwrap_or(Vec3::ZERO),
+                        target_pos,
+                        target_vel,
                         shot_speed: held.projectile_speed().unwrap_or(1_000_000.0),
                         // Fixed latency estimate (real RTT plumbing is a follow-up; since
                         // Plan 57 our real ping ≈ 16 ms + interp).
@@ -620,6 +690,13 @@ impl Brain for XonBrain {
         }
     }
 
+    fn on_kill(&mut self) {
+        // The frag was most likely our target, who respawns anywhere — drop its belief.
+        if let Some(n) = self.enemy.current() {
+            self.opponents.forget(n);
+        }
+    }
+
     fn on_death(&mut self) {
         // Respawned elsewhere — steering/recovery state is stale; loadout back to Blaster.
         self.recovery.reset();
@@ -40,6 +40,7 @@ use crate::perception::EntityClass;
 use crate::recover::{Recovery, RecoveryAction};
 use crate::skill::BotSkill;
 use crate::steer::{move_from_world_dir, view_forward, view_right, Steering};
+use crate::timeline::Decision;
 use crate::traverse::{TraversalExecutor, TraversalFrame};
 use crate::weapons::Weapon;
 use crate::{items, los};
@@ -331,6 +332,23 @@ impl Zb2Brain {
         }
     }
 
+    /// Pin the ordinal to `seed` ([`BrainConfig::seed`](crate::BrainConfig::seed)) so a replay
+    /// starts the roam cursor where the captured bot did. `None` keeps the process-wide one.
+    pub fn with_seed(mut self, seed: Option<u32>) -> Self {
+        if let Some(seed) = seed {
+            self.ordinal = seed as usize;
+        }
+        self
+    }
+
+    /// Aim through the bot's mouse hand ([`BrainConfig::human_aim`](crate::BrainConfig::human_aim)).
+    pub fn with_human_aim(mut self, on: bool) -> Self {
+        if on {
+            self.combat = CombatDriver::new().with_mouse(self.skill.mouse_params());
+        }
+        self
+    }
+
     /// True while `n` is the temporarily-blocked destination (Z3).
     fn is_blocked(&self, n: usize) -> bool {
         self.goal_block.is_some_and(|(b, _)| b == n)
@@ -391,6 +409,8 @@ impl Brain for Zb2Brain {
             nav_graph,
             roam_as_position: _, // zb2 always routes on the A* graph (see module docs)
             items: _,            // v1 uses the PVS item picker, not the static table
+            node_light: _,
+            cm: _,
         } = map;
         self.roam_nodes = roam_nodes;
         // R3: start each bot one roam-stride apart so a fleet of zb2s doesn't convoy to
@@ -690,6 +710,8 @@ impl Brain for Zb2Brain {
                 mv.move_forward(ff);
                 mv.move_side(ss);
                 mv.attack();
+            } else {
+                self.combat.release_aim();
             }
         } else {
             // No committed route (plan failed — e.g. off-graph after a fall, or every roam
@@ -718,6 +740,7 @@ impl Brain for Zb2Brain {
                 mv.attack();
                 intent_forward = ff;
             } else {
+                self.combat.release_aim();
                 mv.look_at(view_yaw, 0.0);
                 let (ff, ss) = move_from_world_dir(free, view_yaw, true);
                 mv.move_forward(ff);
@@ -777,6 +800,14 @@ impl Brain for Zb2Brain {
     fn status(&self) -> &str {
         "zb2"
     }
+
+    fn decision(&self) -> Decision {
+        let goal = self.route.as_ref().map(|r| NavGoal::Waypoint(r.goal_node));
+        Decision {
+            target: self.combat.target_entity(),
+            ..Decision::heading(goal.as_ref(), self.nav_graph.as_deref())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -947,6 +978,8 @@ mod tests {
             nav_graph: Arc::clone(&graph),
             roam_as_position: false,
             items: Vec::new(),
+            node_light: Vec::new(),
+            cm: None,
         };
         let mut a = Zb2Brain::new(BotSkill::default(), false);
         let mut b = Zb2Brain::new(BotSkill::default(), false);
@@ -10,8 +10,10 @@
 //! only if we own the weapon.
 
 use crate::aim::{aim_direction, aim_hitscan, JitterRng};
+use crate::mouse::{MouseAim, MouseParams};
 use crate::perception::{EntityClass, Worldview};
 use crate::skill::BotSkill;
+use crate::splash;
 use crate::weapons::{self, Weapon};
 use world::CollisionModel;
 
@@ -39,6 +41,11 @@ const PAIN_FOV_DEG: f32 = 180.0;
 /// to frames at this cadence.
 const TICK_HZ: f32 = 10.0;
 
+/// Accuracy rating (1-5) from which rockets and grenades are aimed by the [`splash`] solver
+/// (floor and wall shots) instead of Eraser's fixed lead and lob — the same mid-skill cut Q3 uses
+/// for its radial ground-aim (`aim_skill > 0.6`).
+const SPLASH_SOLVER_MIN_ACCURACY: f32 = 3.0;
+
 /// Withhold `BUTTON_ATTACK` briefly after requesting a weapon switch so we don't fire the
 /// old weapon mid-change. Eraser used a full `0.9 s` (`BOT_CHANGEWEAPON_DELAY`), but that —
 /// stacked on the reaction delay — left `main` idle >1 s at the start of every engagement
@@ -101,6 +108,10 @@ pub struct CombatDriver {
     last_health: Option<i32>,
     /// Frames of pain-widened acquisition remaining (Plan 49).
     pain_frames: u32,
+    /// Seconds of evaluated ticks (at [`TICK_HZ`]) — the clock for `mouse`.
+    clock: f32,
+    /// The opt-in mouse hand ([`Self::with_mouse`]); `None` returns the ideal aim.
+    mouse: Option<MouseAim>,
 }
 
 impl CombatDriver {
@@ -115,6 +126,29 @@ impl CombatDriver {
             sight_grace_remaining: 0,
             last_health: None,
             pain_frames: 0,
+            clock: 0.0,
+            mouse: None,
+        }
+    }
+
+    /// Aim through a modeled mouse hand ([`crate::mouse`]): the decision's `aim_yaw`/`aim_pitch`
+    /// become where the hand has got to this tick instead of the ideal aim.
+    pub fn with_mouse(mut self, params: MouseParams) -> Self {
+        self.mouse = Some(MouseAim::new(params));
+        self
+    }
+
+    /// True when [`Self::with_mouse`] is set — the brain should then look where the decision
+    /// aims whenever it has a target, or [`Self::release_aim`] when it looks elsewhere.
+    pub fn human_aim(&self) -> bool {
+        self.mouse.is_some()
+    }
+
+    /// The brain looked away from the decision's aim this tick; the mouse hand picks up from the
+    /// view next time.
+    pub fn release_aim(&mut self) {
+        if let Some(m) = &mut self.mouse {
+            m.release();
         }
     }
 
@@ -144,6 +178,8 @@ impl CombatDriver {
         jitter_seed: f32,
         los: Option<&CollisionModel>,
     ) -> CombatDecision {
+        self.clock += 1.0 / TICK_HZ;
+
         // Pain detection (Plan 49): a health drop widens fresh acquisition to the full
         // sphere for PAIN_AWARENESS_FRAMES so an attacker behind us can be acquired.
         let health = view.self_state().health;
@@ -233,9 +269,30 @@ impl CombatDriver {
                 accuracy,
                 &mut rng,
             )
+        } else if let Some(shot) = los
+            .filter(|_| accuracy >= SPLASH_SOLVER_MIN_ACCURACY)
+            .and_then(|cm| {
+                splash::solve(cm, view.self_state().origin, t.origin, t.velocity, weapon)
+            })
+        {
+            (shot.yaw, shot.pitch)
         } else {
             aim_direction(view.self_state().origin, t.origin, t.velocity, weapon)
         };
+        // The hand chases the ideal aim; it picks up from the current view after a gap.
+        let (yaw, pitch) = match &mut self.mouse {
+            Some(m) => {
+                let angles = view.self_state().angles;
+                m.step(
+                    self.clock,
+                    (angles.y, angles.x),
+                    (yaw, pitch),
+                    distance,
+                    &mut rng,
+                )
+            }
+            None => (yaw, pitch),
+        };
 
         // Gate `should_fire` on both timing gates AND current LOS (or grace period).
         let should_fire = fire_allowed && self.should_fire(weapon, distance, combat);
@@ -470,6 +527,46 @@ mod tests {
         );
     }
 
+    /// With a mouse hand the decision's aim starts at the current view and sweeps onto the
+    /// target over several ticks instead of reporting the ideal angle at once.
+    #[test]
+    fn mouse_hand_sweeps_onto_the_target() {
+        use q2proto::EntityState;
+
+        // Facing +x; enemy 60° to the left, 400 u out (inside the acquisition cone).
+        let mut frame = Frame::default();
+        frame.playerstate.stats[1] = 100;
+        frame.entities = vec![EntityState {
+            number: 7,
+            origin: [200.0, 346.4, 0.0],
+            modelindex: 255,
+            ..Default::default()
+        }];
+        let view = crate::perception::Worldview::from_frame(&frame, &ConfigStrings::default(), 0);
+        let skill = BotSkill::default();
+
+        let ideal = CombatDriver::new()
+            .evaluate(&view, &skill, 0.0, None)
+            .aim_yaw;
+        assert!((ideal - 60.0).abs() < 1.0, "ideal aim {ideal}");
+
+        let mut driver = CombatDriver::new().with_mouse(skill.mouse_params());
+        let yaws: Vec<f32> = (0..20)
+            .map(|i| driver.evaluate(&view, &skill, i as f32, None).aim_yaw)
+            .collect();
+        assert!(
+            yaws[0].abs() < 1.0,
+            "starts from the current view: {}",
+            yaws[0]
+        );
+        assert!(yaws[2] > 0.0 && yaws[2] < ideal, "on the way: {}", yaws[2]);
+        let last = yaws[19];
+        assert!(
+            (last - ideal).abs() <= crate::mouse::target_width(400.0),
+            "settled on the target: {last}"
+        );
+    }
+
     /// A driver with all timing gates satisfied, so `should_fire` depends only
     /// on weapon + distance (range/safety). Each test then perturbs one gate.
     fn ready_driver() -> CombatDriver {
@@ -0,0 +1,326 @@
+//! Team play and Capture the Flag (Q2 CTF, yquake2 `ctf/src/g_ctf.c`).
+//!
+//! Q2 puts no team on the wire. A CTF server forces every player's skin to `<model>/ctf_r` or
+//! `<model>/ctf_b` (`CTFAssignSkin`), so the `CS_PLAYERSKINS` string *is* the team; a teamplay
+//! deathmatch with skin teams (`DF_SKINTEAMS`) groups players by the same string, so a bot given
+//! a team wears the CTF skin there too. A flag carrier's entity has `EF_FLAG1`/`EF_FLAG2` set
+//! (`CTFEffects`), and the flags are the `item_flag_team1/2` items, whose spawn pads are the
+//! bases.
+//!
+//! [`ctf_goal`] turns what this bot can see into one objective — capture, return, escort or
+//! attack — for any brain to route to. Like the item table it is PVS-honest: a flag out of view
+//! is assumed home.
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+
+use crate::brains::core::MapItem;
+use crate::perception::{EntityClass, Worldview};
+
+/// `EF_FLAG1` (`q_shared.h`) — the entity carries the red flag.
+pub const EF_FLAG1: u32 = 0x0004_0000;
+/// `EF_FLAG2` — the entity carries the blue flag.
+pub const EF_FLAG2: u32 = 0x0008_0000;
+
+/// A flag this far from its pad is loose (dropped), not home (`CTFFlagThink` drops to the floor
+/// where the carrier died; a home flag sits on its pad).
+const FLAG_HOME_RADIUS: f32 = 64.0;
+
+/// A CTF team (`CTF_TEAM1` red, `CTF_TEAM2` blue).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+impl Team {
+    /// Parse a config/roster token: `red`/`blue` (the `team` command's words) or `1`/`2`.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "red" | "1" => Some(Team::Red),
+            "blue" | "2" => Some(Team::Blue),
+            _ => None,
+        }
+    }
+
+    /// The word the `team` stringcmd takes (`CTFTeam_f`).
+    pub fn name(self) -> &'static str {
+        match self {
+            Team::Red => "red",
+            Team::Blue => "blue",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            Team::Red => Team::Blue,
+            Team::Blue => Team::Red,
+        }
+    }
+
+    /// The forced team skin name (`CTF_TEAM1_SKIN` / `CTF_TEAM2_SKIN`).
+    pub fn skin_name(self) -> &'static str {
+        match self {
+            Team::Red => "ctf_r",
+            Team::Blue => "ctf_b",
+        }
+    }
+
+    /// `skin` (a `model/skin` userinfo value, or bare model) re-skinned for this team, keeping
+    /// the model: `female/athena` → `female/ctf_r`. No model → `male`.
+    pub fn skin_for(self, skin: Option<&str>) -> String {
+        let model = skin
+            .and_then(|s| s.split('/').next())
+            .filter(|m| !m.is_empty())
+            .unwrap_or("male");
+        format!("{model}/{}", self.skin_name())
+    }
+
+    /// The team a `model/skin` string belongs to, if it is a team skin.
+    pub fn from_skin(skin: &str) -> Option<Self> {
+        let name = skin.rsplit('/').next().unwrap_or(skin);
+        [Team::Red, Team::Blue]
+            .into_iter()
+            .find(|t| name.eq_ignore_ascii_case(t.skin_name()))
+    }
+
+    /// The flag an entity with these `effects` is carrying.
+    pub fn carried(effects: u32) -> Option<Self> {
+        if effects & EF_FLAG1 != 0 {
+            Some(Team::Red)
+        } else if effects & EF_FLAG2 != 0 {
+            Some(Team::Blue)
+        } else {
+            None
+        }
+    }
+}
+
+/// Both flag pads, from the map's `item_flag_team1/2` entities. Empty on a non-CTF map.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlagBases {
+    pub red: Option<Vec3>,
+    pub blue: Option<Vec3>,
+}
+
+impl FlagBases {
+    /// Pick the flag pads out of the static item table.
+    pub fn from_items(items: &[MapItem]) -> Self {
+        let pad = |t: Team| {
+            items
+                .iter()
+                .find(|it| it.class == EntityClass::Flag(t))
+                .map(|it| it.origin)
+        };
+        Self {
+            red: pad(Team::Red),
+            blue: pad(Team::Blue),
+        }
+    }
+
+    pub fn base(&self, team: Team) -> Option<Vec3> {
+        match team {
+            Team::Red => self.red,
+            Team::Blue => self.blue,
+        }
+    }
+
+    /// Is this a CTF map (both pads present)?
+    pub fn is_ctf(&self) -> bool {
+        self.red.is_some() && self.blue.is_some()
+    }
+}
+
+/// What a CTF objective asks of the bot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CtfTask {
+    /// We carry their flag: run it to our pad.
+    Capture,
+    /// Our flag is loose or being carried off: touch it (or kill its carrier) to send it home.
+    Return,
+    /// A teammate carries their flag: stay on them.
+    Escort,
+    /// Go and take their flag — loose where we saw it, else from their pad.
+    Attack,
+}
+
+impl CtfTask {
+    /// Worth more than any pickup: a capture in hand, or our flag in play.
+    pub fn urgent(self) -> bool {
+        matches!(self, CtfTask::Capture | CtfTask::Return)
+    }
+}
+
+/// One CTF objective: the task and where it is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CtfGoal {
+    pub task: CtfTask,
+    pub pos: Vec3,
+}
+
+/// This bot's CTF objective this frame, or `None` when it has no team (FFA) or the map has no
+/// flags. Priority follows the game's scoring (`CTF_CAPTURE_BONUS` over returns over escorts):
+/// capture, then return our flag, then escort a teammate's run, then attack.
+pub fn ctf_goal(view: &Worldview, bases: &FlagBases) -> Option<CtfGoal> {
+    if !bases.is_ctf() {
+        return None;
+    }
+    let me = view.self_state();
+    let ours = me.team?;
+    let theirs = ours.other();
+    let goal = |task, pos| Some(CtfGoal { task, pos });
+
+    if me.carrying == Some(theirs) {
+        return goal(CtfTask::Capture, bases.base(ours)?);
+    }
+    if let Some(f) = view.flag(ours) {
+        if bases
+            .base(ours)
+            .is_some_and(|b| (f.origin - b).length() > FLAG_HOME_RADIUS)
+        {
+            return goal(CtfTask::Return, f.origin);
+        }
+    }
+    if let Some(c) = view.flag_carrier(ours) {
+        return goal(CtfTask::Return, c.origin);
+    }
+    if let Some(c) = view.flag_carrier(theirs) {
+        return goal(CtfTask::Escort, c.origin);
+    }
+    let pos = view.flag(theirs).map(|f| f.origin).or(bases.base(theirs))?;
+    goal(CtfTask::Attack, pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perception::{CS_MODELS, CS_PLAYERSKINS};
+    use client::parse::ConfigStrings;
+    use q2proto::{EntityState, Frame};
+
+    const RED_BASE: Vec3 = Vec3::new(-1000.0, 0.0, 0.0);
+    const BLUE_BASE: Vec3 = Vec3::new(1000.0, 0.0, 0.0);
+
+    fn bases() -> FlagBases {
+        FlagBases {
+            red: Some(RED_BASE),
+            blue: Some(BLUE_BASE),
+        }
+    }
+
+    /// We are slot 0 (entity 1) on red; `others` are `(entity, skin, origin, effects)` players,
+    /// `flags` are `(team, origin)` flag items.
+    fn view(
+        my_effects: u32,
+        others: &[(i32, &str, [f32; 3], u32)],
+        flags: &[(Team, [f32; 3])],
+    ) -> Worldview {
+        let mut cs = ConfigStrings::default();
+        cs.set(CS_MODELS + 1, "players/male/flag1.md2");
+        cs.set(CS_MODELS + 2, "players/male/flag2.md2");
+        cs.set(CS_PLAYERSKINS, "Me\\male/ctf_r");
+        let mut entities = vec![EntityState {
+            number: 1,
+            modelindex: 255,
+            effects: my_effects,
+            ..Default::default()
+        }];
+        for &(n, skin, origin, effects) in others {
+            cs.set(CS_PLAYERSKINS + (n - 1) as usize, format!("p{n}\\{skin}"));
+            entities.push(EntityState {
+                number: n,
+                modelindex: 255,
+                origin,
+                effects,
+                ..Default::default()
+            });
+        }
+        for (i, &(team, origin)) in flags.iter().enumerate() {
+            entities.push(EntityState {
+                number: 100 + i as i32,
+                modelindex: if team == Team::Red { 1 } else { 2 },
+                origin,
+                ..Default::default()
+            });
+        }
+        let frame = Frame {
+            entities,
+            ..Default::default()
+        };
+        Worldview::from_frame(&frame, &cs, 0)
+    }
+
+    #[test]
+    fn team_skins_round_trip() {
+        assert_eq!(Team::Red.skin_for(Some("female/athena")), "female/ctf_r");
+        assert_eq!(Team::Blue.skin_for(None), "male/ctf_b");
+        assert_eq!(Team::from_skin("cyborg/CTF_B"), Some(Team::Blue));
+        assert_eq!(Team::from_skin("male/grunt"), None);
+        assert_eq!(Team::parse("Red"), Some(Team::Red));
+        assert_eq!(Team::carried(EF_FLAG2), Some(Team::Blue));
+    }
+
+    #[test]
+    fn teammates_are_allies_and_flags_are_not_players() {
+        let v = view(
+            0,
+            &[
+                (2, "female/ctf_r", [0.0; 3], 0),
+                (3, "male/ctf_b", [0.0; 3], 0),
+            ],
+            &[(Team::Blue, BLUE_BASE.into())],
+        );
+        assert_eq!(v.self_state().team, Some(Team::Red));
+        let class = |n| v.entities().find(|e| e.entity_number == n).unwrap().class;
+        assert_eq!(class(2), EntityClass::AllyPlayer);
+        assert_eq!(class(3), EntityClass::EnemyPlayer);
+        assert_eq!(class(100), EntityClass::Flag(Team::Blue));
+        assert_eq!(v.enemies().count(), 1);
+    }
+
+    #[test]
+    fn objectives_follow_the_flag_state() {
+        let b = bases();
+        let task = |v: &Worldview| ctf_goal(v, &b).map(|g| (g.task, g.pos));
+
+        // Nothing in view → attack their pad.
+        let v = view(0, &[], &[]);
+        assert_eq!(task(&v), Some((CtfTask::Attack, BLUE_BASE)));
+
+        // Carrying theirs → home.
+        let v = view(EF_FLAG2, &[], &[]);
+        assert_eq!(task(&v), Some((CtfTask::Capture, RED_BASE)));
+
+        // Our flag lying loose mid-map → return it; at home it is no objective.
+        let loose = [0.0, 200.0, 0.0];
+        let v = view(0, &[], &[(Team::Red, loose)]);
+        assert_eq!(task(&v), Some((CtfTask::Return, Vec3::from(loose))));
+        let v = view(0, &[], &[(Team::Red, RED_BASE.into())]);
+        assert_eq!(task(&v), Some((CtfTask::Attack, BLUE_BASE)));
+
+        // An enemy running with ours → chase them; a teammate running with theirs → escort.
+        let thief = [300.0, 0.0, 0.0];
+        let v = view(0, &[(3, "male/ctf_b", thief, EF_FLAG1)], &[]);
+        assert_eq!(task(&v), Some((CtfTask::Return, Vec3::from(thief))));
+        let runner = [-300.0, 0.0, 0.0];
+        let v = view(0, &[(2, "male/ctf_r", runner, EF_FLAG2)], &[]);
+        assert_eq!(task(&v), Some((CtfTask::Escort, Vec3::from(runner))));
+    }
+
+    #[test]
+    fn no_team_or_no_flags_means_no_objective() {
+        let mut cs = ConfigStrings::default();
+        cs.set(CS_PLAYERSKINS, "Me\\male/grunt");
+        let frame = Frame {
+            entities: vec![EntityState {
+                number: 1,
+                modelindex: 255,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let ffa = Worldview::from_frame(&frame, &cs, 0);
+        assert_eq!(ctf_goal(&ffa, &bases()), None);
+        assert_eq!(ctf_goal(&view(0, &[], &[]), &FlagBases::default()), None);
+    }
+}
@@ -33,6 +33,8 @@ pub struct DodgeAction {
     pub strafe_dir: Vec3,
     /// Whether to also jump this frame.
     pub jump: bool,
+    /// How close the threat is, `0.0..=1.0` of its dodge radius (1 = on top of us; 0 = none).
+    pub urgency: f32,
 }
 
 impl DodgeAction {
@@ -55,6 +57,7 @@ impl DangerDriver {
     pub fn evaluate(&self, view: &Worldview, combat: f32) -> DodgeAction {
         let origin = view.self_state().origin;
         let mut best: Option<(f32, Vec3)> = None; // (closeness score, dodge dir)
+        let mut best_urgency = 0.0;
 
         for e in view.entities() {
             let is_rocket = e.class == EntityClass::ProjectileRocket;
@@ -106,6 +109,7 @@ impl DangerDriver {
             let score = max_dist - dist;
             if best.is_none_or(|(s, _)| score > s) {
                 best = Some((score, side));
+                best_urgency = score / max_dist;
             }
         }
 
@@ -118,6 +122,7 @@ impl DangerDriver {
                 DodgeAction {
                     strafe_dir: dir,
                     jump,
+                    urgency: best_urgency,
                 }
             }
             None => DodgeAction::default(),
@@ -155,6 +160,12 @@ mod tests {
         // Dodge is perpendicular to +x travel → along ±y.
         assert!(d.strafe_dir.x.abs() < 0.1);
         assert!(d.strafe_dir.y.abs() > 0.9);
+        // 200 u into a 300 u dodge radius.
+        assert!(
+            (d.urgency - 1.0 / 3.0).abs() < 1e-3,
+            "urgency {}",
+            d.urgency
+        );
     }
 
     #[test]
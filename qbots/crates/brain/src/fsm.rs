@@ -17,7 +17,8 @@ use world::CollisionModel;
 pub enum BehaviorState {
     /// Seeking random roam nodes or high-value items.
     Roam,
-    /// Moving toward last-known enemy position.
+    /// Moving toward the last-known enemy position, or wherever the opponent model
+    /// ([`crate::opponent`]) says to look next.
     Hunt { last_enemy_pos: Option<Vec3> },
     /// Enemy in sight — engage with combat.
     Engage { target_entity: i32 },
@@ -38,10 +39,16 @@ pub struct BehaviorIntent {
 impl BehaviorState {
     /// Tick the FSM based on current worldview. Returns movement intent. `los` is
     /// the collision model for the Roam→Engage "enemy in sight" gate (Plan 11): when
-    /// `None` it degrades to FOV-only sighting.
-    pub fn tick(&mut self, view: &Worldview, los: Option<&CollisionModel>) -> BehaviorIntent {
+    /// `None` it degrades to FOV-only sighting. `search` is the opponent model's next spot
+    /// to look for a lost enemy; while hunting it replaces the last-known position.
+    pub fn tick(
+        &mut self,
+        view: &Worldview,
+        los: Option<&CollisionModel>,
+        search: Option<Vec3>,
+    ) -> BehaviorIntent {
         // Check transitions first
-        self.transition(view, los);
+        self.transition(view, los, search);
 
         // Execute current state - extract values to avoid borrow issues
         match self {
@@ -62,7 +69,7 @@ impl BehaviorState {
         }
     }
 
-    fn transition(&mut self, view: &Worldview, los: Option<&CollisionModel>) {
+    fn transition(&mut self, view: &Worldview, los: Option<&CollisionModel>, search: Option<Vec3>) {
         // Low health → Flee (highest priority)
         if view.is_low_health_with_threshold(30) {
             *self = Self::Flee;
@@ -125,16 +132,21 @@ impl BehaviorState {
         // State-specific transitions when no enemy is visible
         match self {
             Self::Hunt { last_enemy_pos } => {
+                if search.is_some() {
+                    *last_enemy_pos = search;
+                }
                 if last_enemy_pos.is_none() {
                     *self = Self::Roam;
                 }
             }
             Self::Engage { target_entity } => {
-                // Enemy left FOV — remember last-known position and Hunt.
+                // Enemy left FOV — remember last-known position and Hunt. Out of the PVS
+                // altogether, search where the opponent model puts it.
                 let last_pos = view
                     .entities()
                     .find(|e| e.entity_number == *target_entity)
-                    .map(|e| e.origin);
+                    .map(|e| e.origin)
+                    .or(search);
                 *self = Self::Hunt {
                     last_enemy_pos: last_pos,
                 };
@@ -208,6 +220,43 @@ mod tests {
         assert_eq!(fsm, BehaviorState::Roam);
     }
 
+    #[test]
+    fn lost_enemy_is_hunted_where_the_opponent_model_points() {
+        use client::parse::ConfigStrings;
+        use q2proto::Frame;
+        let mut view = Worldview::from_frame(&Frame::default(), &ConfigStrings::default(), 0);
+        view.self_state.health = 100;
+        let spot = Vec3::new(512.0, 0.0, 24.0);
+
+        // The target has left the PVS: hunt the search spot instead of giving up.
+        let mut fsm = BehaviorState::Engage { target_entity: 2 };
+        let intent = fsm.tick(&view, None, Some(spot));
+        assert_eq!(
+            fsm,
+            BehaviorState::Hunt {
+                last_enemy_pos: Some(spot)
+            }
+        );
+        assert!(matches!(intent.nav_goal, Some(NavGoal::Position(p)) if p == spot));
+
+        // The next spot replaces it; with no belief the last one is kept.
+        let next = Vec3::new(0.0, 512.0, 24.0);
+        fsm.tick(&view, None, Some(next));
+        fsm.tick(&view, None, None);
+        assert_eq!(
+            fsm,
+            BehaviorState::Hunt {
+                last_enemy_pos: Some(next)
+            }
+        );
+
+        // Nothing to go on at all → roam.
+        let mut fsm = BehaviorState::Engage { target_entity: 2 };
+        fsm.tick(&view, None, None);
+        fsm.tick(&view, None, None);
+        assert_eq!(fsm, BehaviorState::Roam);
+    }
+
     #[test]
     fn low_health_triggers_flee() {
         // This would need a mock worldview with low health
@@ -12,6 +12,16 @@
 //! around death-traps and toward hot lanes. Per-bot (no shared mutable state —
 //! AGENTS.md §Concurrency): each bot's heatmap reflects its own PVS-limited
 //! observations, exactly like a real player's mental map.
+//!
+//! Between sessions the live values are gone (they decay in a minute anyway), but
+//! the heatmap also keeps undecayed session totals. [`Heatmap::record`] hands them
+//! out as a [`HeatRecord`]; the fleet averages every bot's record into one file
+//! per map, stamped with the nav cache's [`Fingerprint::digest`] so it is dropped
+//! with the graph it indexes. [`Heatmap::seed`] starts a new session from it.
+//!
+//! [`Fingerprint::digest`]: world::Fingerprint::digest
+
+use std::path::{Path, PathBuf};
 
 /// Danger time-constant: a node's danger decays to ~37% over this many seconds.
 /// Short-term "this place is hot right now." (`distilled/eraser.md` §10/§13-D.)
@@ -30,6 +40,16 @@ pub const DANGER_BUMP_DEATH: f32 = 1.0;
 pub const DANGER_BUMP_DAMAGE: f32 = 0.25;
 /// Cap per-node danger so a single spot can't become an infinite wall.
 pub const DANGER_MAX: f32 = 8.0;
+/// Danger a seeded session starts with at the map's worst recorded spot (two
+/// deaths' worth); other nodes scale down from it.
+pub const SEED_DANGER: f32 = 2.0;
+/// Popularity a seeded session starts with at the busiest recorded spot.
+pub const SEED_POP: f32 = 0.5;
+/// Sessions a [`HeatRecord`] averages at most, so old sessions fade as new ones
+/// arrive instead of freezing the map's heat forever.
+pub const MAX_SESSIONS: u32 = 32;
+/// First line of a saved heat record.
+const HEAT_HEADER: &str = "# qbots heatmap v1";
 
 /// Per-node danger + popularity overlay for a nav graph of `node_count` nodes.
 #[derive(Debug, Clone)]
@@ -38,6 +58,10 @@ pub struct Heatmap {
     popularity: Vec<f32>,
     /// Tracked for diagnostics (max danger seen).
     max_danger: f32,
+    /// Undecayed danger bumped into each node this session (for [`Self::record`]).
+    seen_danger: Vec<f32>,
+    /// Undecayed seconds an enemy was seen at each node this session.
+    seen_presence: Vec<f32>,
 }
 
 impl Heatmap {
@@ -46,6 +70,8 @@ impl Heatmap {
             danger: vec![0.0; node_count],
             popularity: vec![0.0; node_count],
             max_danger: 0.0,
+            seen_danger: vec![0.0; node_count],
+            seen_presence: vec![0.0; node_count],
         }
     }
 
@@ -77,6 +103,7 @@ impl Heatmap {
             if *d > self.max_danger {
                 self.max_danger = *d;
             }
+            self.seen_danger[node] += amount;
         }
     }
 
@@ -88,6 +115,9 @@ impl Heatmap {
             // p += (target - p) * (1 - exp(-K*dt))
             let alpha = 1.0 - (-POP_K * dt).exp();
             *p += (target - *p) * alpha;
+            if present {
+                self.seen_presence[node] += dt;
+            }
         }
     }
 
@@ -141,6 +171,168 @@ impl Heatmap {
     pub fn total_danger(&self) -> f32 {
         self.danger.iter().sum()
     }
+
+    /// This session's undecayed totals, as one session's [`HeatRecord`].
+    pub fn record(&self) -> HeatRecord {
+        HeatRecord {
+            danger: self.seen_danger.clone(),
+            presence: self.seen_presence.clone(),
+            sessions: 1,
+        }
+    }
+
+    /// Start from what earlier sessions learned: danger and popularity scaled so the
+    /// worst recorded node gets [`SEED_DANGER`] / [`SEED_POP`]. They decay like live
+    /// heat, so a seed is a prior that this session's own deaths soon outweigh.
+    /// Returns `false` (and seeds nothing) if `rec` was recorded on another graph.
+    pub fn seed(&mut self, rec: &HeatRecord) -> bool {
+        if rec.danger.len() != self.node_count() || rec.presence.len() != self.node_count() {
+            return false;
+        }
+        let scale = |v: &[f32], top: f32| {
+            let max = v.iter().copied().fold(0.0, f32::max);
+            let k = if max > 0.0 { top / max } else { 0.0 };
+            v.iter().map(|x| x * k).collect::<Vec<_>>()
+        };
+        for (d, s) in self.danger.iter_mut().zip(scale(&rec.danger, SEED_DANGER)) {
+            *d = d.max(s);
+            self.max_danger = self.max_danger.max(*d);
+        }
+        for (p, s) in self
+            .popularity
+            .iter_mut()
+            .zip(scale(&rec.presence, SEED_POP))
+        {
+            *p = p.max(s);
+        }
+        true
+    }
+}
+
+/// A map's heat averaged over bot-sessions: per-node danger bumped and seconds of
+/// enemy presence. Saved per map between runs (see the module docs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatRecord {
+    /// Danger bumped into each node per session (a death counts [`DANGER_BUMP_DEATH`]).
+    pub danger: Vec<f32>,
+    /// Seconds an enemy was seen at each node per session.
+    pub presence: Vec<f32>,
+    /// Bot-sessions averaged in, capped at [`MAX_SESSIONS`].
+    pub sessions: u32,
+}
+
+impl HeatRecord {
+    /// Fold `other` in, weighting each side by its sessions. Returns `false` (and
+    /// changes nothing) if the two were recorded on graphs of different sizes.
+    pub fn merge(&mut self, other: &HeatRecord) -> bool {
+        if other.danger.len() != self.danger.len() || other.presence.len() != self.presence.len() {
+            return false;
+        }
+        let (a, b) = (self.sessions as f32, other.sessions as f32);
+        if a + b <= 0.0 {
+            return true;
+        }
+        let mix = |x: &mut f32, y: f32| *x = (*x * a + y * b) / (a + b);
+        for (x, &y) in self.danger.iter_mut().zip(&other.danger) {
+            mix(x, y);
+        }
+        for (x, &y) in self.presence.iter_mut().zip(&other.presence) {
+            mix(x, y);
+        }
+        self.sessions = (self.sessions + other.sessions).min(MAX_SESSIONS);
+        true
+    }
+
+    /// Nothing learned (every node cold).
+    pub fn is_empty(&self) -> bool {
+        self.danger.iter().chain(&self.presence).all(|&v| v == 0.0)
+    }
+
+    /// Load the record saved at `path` for the graph with this `fingerprint`
+    /// ([`world::Fingerprint::digest`]). `Ok(None)` when there is no file yet or it
+    /// belongs to another graph (the nav cache was regenerated); a malformed file is
+    /// an error.
+    pub fn load(path: &Path, fingerprint: u64) -> Result<Option<Self>, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => {
+                Self::parse(&text, fingerprint).map_err(|e| format!("{}: {e}", path.display()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("read {}: {e}", path.display())),
+        }
+    }
+
+    /// Write the record to `path` (creating its directory), stamped with `fingerprint`. Like
+    /// [`crate::RivalBook::save`] it goes through `<path>.tmp` and a rename, so a run killed
+    /// mid-write leaves the previous record whole.
+    pub fn save(&self, path: &Path, fingerprint: u64) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| format!("create {}: {e}", dir.display()))?;
+        }
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, self.to_text(fingerprint))
+            .map_err(|e| format!("write {}: {e}", tmp.display()))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("rename {}: {e}", tmp.display()))
+    }
+
+    /// Header (`fingerprint`, `nodes`, `sessions` lines), then `node danger presence`
+    /// for every node that is not cold.
+    pub fn to_text(&self, fingerprint: u64) -> String {
+        let mut out = format!(
+            "{HEAT_HEADER}\nfingerprint {fingerprint:016x}\nnodes {}\nsessions {}\n",
+            self.danger.len(),
+            self.sessions
+        );
+        for (i, (d, p)) in self.danger.iter().zip(&self.presence).enumerate() {
+            if *d > 0.0 || *p > 0.0 {
+                out.push_str(&format!("{i} {d:.3} {p:.2}\n"));
+            }
+        }
+        out
+    }
+
+    /// Parse [`Self::to_text`] output; `Ok(None)` if it was saved for another graph.
+    pub fn parse(text: &str, fingerprint: u64) -> Result<Option<Self>, String> {
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        if lines.next().map(str::trim) != Some(HEAT_HEADER) {
+            return Err(format!("not a heatmap file (expected `{HEAT_HEADER}`)"));
+        }
+        let mut field = |key: &str| {
+            lines
+                .next()
+                .and_then(|l| l.strip_prefix(key))
+                .map(str::trim)
+                .ok_or_else(|| format!("missing `{key}` line"))
+        };
+        let fp = u64::from_str_radix(field("fingerprint")?, 16).map_err(|e| e.to_string())?;
+        let nodes: usize = field("nodes")?.parse().map_err(|e| format!("nodes: {e}"))?;
+        let sessions: u32 = field("sessions")?
+            .parse()
+            .map_err(|e| format!("sessions: {e}"))?;
+        if fp != fingerprint {
+            return Ok(None);
+        }
+        let mut rec = HeatRecord {
+            danger: vec![0.0; nodes],
+            presence: vec![0.0; nodes],
+            sessions,
+        };
+        for line in lines {
+            let bad = || format!("bad node line `{line}`");
+            let mut f = line.split_whitespace();
+            let i: usize = f.next().and_then(|v| v.parse().ok()).ok_or_else(bad)?;
+            let d: f32 = f.next().and_then(|v| v.parse().ok()).ok_or_else(bad)?;
+            let p: f32 = f.next().and_then(|v| v.parse().ok()).ok_or_else(bad)?;
+            if i >= nodes {
+                return Err(bad());
+            }
+            rec.danger[i] = d;
+            rec.presence[i] = p;
+        }
+        Ok(Some(rec))
+    }
 }
 
 #[cfg(test)]
@@ -243,4 +435,67 @@ mod tests {
         assert!((hot[1].1 - 1.0).abs() < 0.001);
         assert!(h.total_danger() > 2.9, "total danger ~3.0");
     }
+
+    #[test]
+    fn session_totals_outlive_decay_and_seed_the_next_session() {
+        let mut h = Heatmap::new(3);
+        h.record_death(2);
+        h.record_death(2);
+        h.record_damage(0);
+        h.sample_presence(1, true, 0.5);
+        h.decay(10.0 * TAU_DANGER);
+        assert!(h.danger(2) < 0.01, "live danger is long gone");
+        let rec = h.record();
+        assert_eq!(rec.danger, vec![0.25, 0.0, 2.0]);
+        assert_eq!(rec.presence, vec![0.0, 0.5, 0.0]);
+
+        let mut next = Heatmap::new(3);
+        assert!(next.seed(&rec));
+        assert_eq!(next.danger(2), SEED_DANGER);
+        assert!((next.danger(0) - SEED_DANGER / 8.0).abs() < 1e-6);
+        assert_eq!(next.popularity(1), SEED_POP);
+        // A seed doesn't count as something this session saw.
+        assert!(next.record().is_empty());
+        // A record from another graph is refused.
+        assert!(!Heatmap::new(4).seed(&rec));
+    }
+
+    #[test]
+    fn records_merge_by_sessions_and_round_trip_against_the_fingerprint() {
+        let one = |d: f32| HeatRecord {
+            danger: vec![d, 0.0],
+            presence: vec![0.0, 1.0],
+            sessions: 1,
+        };
+        let mut rec = one(3.0);
+        assert!(rec.merge(&one(1.0)));
+        assert_eq!(rec.danger, vec![2.0, 0.0]);
+        assert_eq!(rec.sessions, 2);
+        let mut heavy = HeatRecord {
+            sessions: MAX_SESSIONS,
+            ..one(0.0)
+        };
+        assert!(heavy.merge(&one(3.0)));
+        assert_eq!(
+            heavy.sessions, MAX_SESSIONS,
+            "capped, so new sessions keep weight"
+        );
+        assert!(!rec.merge(&HeatRecord {
+            danger: vec![0.0; 3],
+            presence: vec![0.0; 3],
+            sessions: 1,
+        }));
+
+        let text = rec.to_text(0xfeed);
+        assert_eq!(HeatRecord::parse(&text, 0xfeed), Ok(Some(rec.clone())));
+        assert_eq!(HeatRecord::parse(&text, 0xbeef), Ok(None), "stale graph");
+        assert!(HeatRecord::parse("# qbots heatmap v0\n", 0xfeed).is_err());
+
+        let path = std::env::temp_dir().join(format!("qbots-heat-{}.txt", std::process::id()));
+        assert_eq!(HeatRecord::load(&path, 0xfeed), Ok(None), "no file yet");
+        rec.save(&path, 0xfeed).unwrap();
+        let back = HeatRecord::load(&path, 0xfeed);
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(back, Ok(Some(rec)));
+    }
 }
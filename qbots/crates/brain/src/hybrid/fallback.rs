@@ -105,6 +105,17 @@ impl Navigator for HybridFallback {
         }
     }
 
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.sub.astar.set_rocket_jump_allowed(allowed);
+    }
+
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        match self.active {
+            Backend::Astar => self.sub.astar.current_rocket_jump(),
+            Backend::Navmesh => None,
+        }
+    }
+
     fn force_replan(&mut self) {
         match self.active {
             // A* is wedged — switch to the navmesh for the rest of this goal. Clearing the
@@ -138,6 +149,13 @@ impl Navigator for HybridFallback {
         }
     }
 
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        match self.active {
+            Backend::Astar => Navigator::path_ahead(&self.sub.astar, max),
+            Backend::Navmesh => Navigator::path_ahead(&self.sub.navmesh, max),
+        }
+    }
+
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         // Only A* has a node path to string-pull; navmesh paths are already smooth.
         if self.active == Backend::Astar {
@@ -161,6 +179,12 @@ impl Navigator for HybridFallback {
             Backend::Navmesh => self.sub.navmesh.speed_scale(pos),
         }
     }
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.sub.astar.note_nav_state(state);
+    }
+    fn backend(&self) -> &'static str {
+        self.active.name()
+    }
 }
 
 #[cfg(test)]
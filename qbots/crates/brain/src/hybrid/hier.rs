@@ -100,6 +100,14 @@ impl Navigator for HybridHier {
         self.sub.astar.current_ride_info()
     }
 
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.sub.astar.set_rocket_jump_allowed(allowed);
+    }
+
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        self.sub.astar.current_rocket_jump()
+    }
+
     fn force_replan(&mut self) {
         // Replan both layers: a new corridor and a fresh local route off it.
         self.sub.navmesh.force_replan();
@@ -118,6 +126,10 @@ impl Navigator for HybridHier {
         Navigator::current_waypoint_pos(&self.sub.astar)
     }
 
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        Navigator::path_ahead(&self.sub.astar, max)
+    }
+
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         self.sub.astar.smooth_with_cm(cm, from);
     }
@@ -133,6 +145,13 @@ impl Navigator for HybridHier {
     fn speed_scale(&self, pos: Vec3) -> f32 {
         self.sub.astar.speed_scale(pos)
     }
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.sub.astar.note_nav_state(state);
+    }
+    fn backend(&self) -> &'static str {
+        // A* executes every sub-goal; the navmesh only routes.
+        "astar"
+    }
 }
 
 #[cfg(test)]
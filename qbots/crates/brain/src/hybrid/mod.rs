@@ -43,6 +43,16 @@ pub(crate) enum Backend {
     Navmesh,
 }
 
+impl Backend {
+    /// The driver's `--navmode` name.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Backend::Astar => "astar",
+            Backend::Navmesh => "navmesh",
+        }
+    }
+}
+
 /// The two sub-drivers a hybrid owns, plus the shared graph (needed to translate waypoint
 /// goals into world positions for the navmesh and to inspect edges for jump-link routing).
 pub(crate) struct Sub {
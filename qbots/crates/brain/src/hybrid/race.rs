@@ -164,6 +164,17 @@ impl Navigator for HybridRace {
         }
     }
 
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.sub.astar.set_rocket_jump_allowed(allowed);
+    }
+
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        match self.active {
+            Backend::Astar => self.sub.astar.current_rocket_jump(),
+            Backend::Navmesh => None,
+        }
+    }
+
     fn force_replan(&mut self) {
         // The active backend wedged here — bias the next race against it, then replan it.
         self.bump_stuck(self.active);
@@ -194,6 +205,13 @@ impl Navigator for HybridRace {
         }
     }
 
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        match self.active {
+            Backend::Astar => Navigator::path_ahead(&self.sub.astar, max),
+            Backend::Navmesh => Navigator::path_ahead(&self.sub.navmesh, max),
+        }
+    }
+
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         if self.active == Backend::Astar {
             self.sub.astar.smooth_with_cm(cm, from);
@@ -214,6 +232,12 @@ impl Navigator for HybridRace {
             Backend::Navmesh => self.sub.navmesh.speed_scale(pos),
         }
     }
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.sub.astar.note_nav_state(state);
+    }
+    fn backend(&self) -> &'static str {
+        self.active.name()
+    }
 }
 
 #[cfg(test)]
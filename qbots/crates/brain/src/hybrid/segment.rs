@@ -161,6 +161,17 @@ impl Navigator for HybridSegment {
         }
     }
 
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.sub.astar.set_rocket_jump_allowed(allowed);
+    }
+
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        match self.active {
+            Backend::Astar => self.sub.astar.current_rocket_jump(),
+            Backend::Navmesh => None,
+        }
+    }
+
     fn force_replan(&mut self) {
         match self.active {
             Backend::Astar => self.sub.astar.force_replan(),
@@ -189,6 +200,13 @@ impl Navigator for HybridSegment {
         }
     }
 
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        match self.active {
+            Backend::Astar => Navigator::path_ahead(&self.sub.astar, max),
+            Backend::Navmesh => Navigator::path_ahead(&self.sub.navmesh, max),
+        }
+    }
+
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         if self.active == Backend::Astar {
             self.sub.astar.smooth_with_cm(cm, from);
@@ -209,6 +227,12 @@ impl Navigator for HybridSegment {
             Backend::Navmesh => self.sub.navmesh.speed_scale(pos),
         }
     }
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.sub.astar.note_nav_state(state);
+    }
+    fn backend(&self) -> &'static str {
+        self.active.name()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,468 @@
+//! Item runs — ordered multi-stop pickup routes with respawn timing.
+//!
+//! [`best_item_goal_weighted`](crate::items::best_item_goal_weighted) picks one visible item at a
+//! time, so a bot zig-zags: it runs for the best pack, then turns round for the next best one it
+//! passed on the way. A strong DM player runs a *loop* instead — armor, then the rocket launcher
+//! next door, then back past the mega just as it respawns.
+//!
+//! [`plan`] treats that as a small orienteering problem over the map-known spawns
+//! ([`MapItem`]s). Travel times come from one [`NavGraph::flood_costs`] per candidate (plus one
+//! from the bot) at [`RUN_SPEED`]; availability comes from [`ItemMemory::respawn_in`]. A depth-first
+//! search over up to [`MAX_STOPS`] stops scores each route as the sum of its pickups' values, each
+//! discounted by when it is collected. A pad still on its respawn timer can be a stop: the bot
+//! arrives early and holds the pad for up to [`MAX_WAIT`] seconds, which is how it times armor and
+//! the mega.
+//!
+//! [`ItemRun`] keeps the current route, drops stops as they are collected (or taken by someone
+//! else), re-plans every [`REPLAN_INTERVAL`], and is [`interrupt`](ItemRun::interrupt)ed by combat
+//! so the route resumes from wherever the fight left the bot.
+
+use glam::Vec3;
+use world::NavGraph;
+
+use crate::brains::core::MapItem;
+use crate::items::ItemMemory;
+use crate::opponent::RUN_SPEED;
+
+/// Most pickups in one route. Three covers a typical armor/weapon/health loop; more only adds
+/// stops the next re-plan would reorder anyway.
+pub const MAX_STOPS: usize = 3;
+/// Spawns considered per plan (highest discounted value first). Each costs one graph flood.
+const MAX_CANDIDATES: usize = 8;
+/// How far ahead a route may reach (seconds). A pad further away than this, or not back until
+/// later, waits for a later plan.
+const HORIZON: f32 = 20.0;
+/// Longest the bot will stand on a pad waiting for its respawn (seconds).
+pub const MAX_WAIT: f32 = 5.0;
+/// Per-second decay of a pickup's value. It makes the search prefer the same items sooner, and
+/// stops a far pad from outranking a near one of nearly the same value.
+const DISCOUNT: f32 = 0.9;
+/// Re-plan a live route this often (seconds), so new sightings in [`ItemMemory`] are used.
+pub const REPLAN_INTERVAL: f32 = 4.0;
+/// With no route, retry planning this often (seconds) rather than flooding every tick.
+const RETRY_INTERVAL: f32 = 1.0;
+/// The bot is on a stop's pad within this distance of its origin.
+const STOP_RADIUS: f32 = 48.0;
+/// Seconds past a stop's predicted respawn before an on-pad bot gives up on it. The timer starts
+/// when we first saw the pad empty, so the real respawn is never later than the prediction.
+const PAD_GRACE: f32 = 0.5;
+/// A pad the bot stood on without the pickup registering (full health, nothing to take) is left
+/// out of plans for this long (seconds), so the route doesn't keep sending it back.
+const PAD_COOLDOWN: f32 = 15.0;
+
+/// One stop of a planned route. Times are on the caller's clock (the `now` given to [`plan`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stop {
+    /// Index into the map item table.
+    pub item: usize,
+    /// When the bot is expected on the pad.
+    pub arrive: f32,
+    /// When the item is expected back on the pad (`<= arrive` for an available item).
+    pub ready_at: f32,
+}
+
+impl Stop {
+    /// Seconds the bot is planned to hold the pad before the item returns.
+    pub fn wait(&self) -> f32 {
+        (self.ready_at - self.arrive).max(0.0)
+    }
+}
+
+/// A planned route: its stops in order and the discounted value it collects.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Route {
+    pub stops: Vec<Stop>,
+    pub score: f32,
+}
+
+/// A spawn worth routing through: its item index, nav node, value and respawn delay.
+struct Candidate {
+    item: usize,
+    node: usize,
+    value: f32,
+    ready_in: f32,
+}
+
+/// Plan the best route from `pos` at time `now`. `value` rates a map item (`0.0` = skip it);
+/// callers pass their own weighting, e.g. `main`'s loadout-aware
+/// [`item_value`](crate::items::item_value) × [`need_multiplier`](crate::items::need_multiplier).
+/// Returns an empty route when the bot is off the graph or nothing is worth a trip.
+pub fn plan(
+    graph: &NavGraph,
+    items: &[MapItem],
+    memory: &ItemMemory,
+    pos: Vec3,
+    now: f32,
+    value: impl Fn(&MapItem) -> f32,
+) -> Route {
+    plan_rated(graph, items, memory, pos, now, |_, it| value(it))
+}
+
+/// [`plan`] with a rating that also sees the item's index (so [`ItemRun`] can zero its cooling
+/// pads).
+fn plan_rated(
+    graph: &NavGraph,
+    items: &[MapItem],
+    memory: &ItemMemory,
+    pos: Vec3,
+    now: f32,
+    value: impl Fn(usize, &MapItem) -> f32,
+) -> Route {
+    let Some(from) = graph.nearest(&[pos.x, pos.y, pos.z]) else {
+        return Route::default();
+    };
+    let start = graph.flood_costs(from);
+
+    // Rank every reachable, valued spawn by what it would be worth as a single trip, and keep the
+    // best few: each one kept costs a flood.
+    let mut ranked: Vec<(f32, Candidate)> = items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, it)| {
+            let node = it.nav_node?;
+            let travel = start.get(node).copied().filter(|c| c.is_finite())? / RUN_SPEED;
+            let ready_in = memory.respawn_in(i, it.class, now);
+            let v = value(i, it);
+            if v <= 0.0 || ready_in > travel + MAX_WAIT || travel.max(ready_in) > HORIZON {
+                return None;
+            }
+            let cand = Candidate {
+                item: i,
+                node,
+                value: v,
+                ready_in,
+            };
+            Some((v * DISCOUNT.powf(travel.max(ready_in)), cand))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked.truncate(MAX_CANDIDATES);
+    let cands: Vec<Candidate> = ranked.into_iter().map(|(_, c)| c).collect();
+    if cands.is_empty() {
+        return Route::default();
+    }
+
+    // Travel seconds from the bot (`times[0]`) and from each candidate (`times[1 + j]`) to every
+    // candidate.
+    let to_cands = |costs: &[f32]| -> Vec<f32> {
+        cands
+            .iter()
+            .map(|c| costs.get(c.node).copied().unwrap_or(f32::INFINITY) / RUN_SPEED)
+            .collect()
+    };
+    let mut times = vec![to_cands(&start)];
+    times.extend(cands.iter().map(|c| to_cands(&graph.flood_costs(c.node))));
+
+    let mut best = Route::default();
+    let mut path = Vec::with_capacity(MAX_STOPS);
+    search(&cands, &times, 0, 0.0, 0.0, &mut path, &mut best);
+    for s in &mut best.stops {
+        s.arrive += now;
+        s.ready_at += now;
+    }
+    best
+}
+
+/// Depth-first search over stop orders. `at` indexes `times` (0 = the bot), `t` is seconds since
+/// the plan started, `score` what `path` has collected so far.
+fn search(
+    cands: &[Candidate],
+    times: &[Vec<f32>],
+    at: usize,
+    t: f32,
+    score: f32,
+    path: &mut Vec<Stop>,
+    best: &mut Route,
+) {
+    if score > best.score {
+        best.score = score;
+        best.stops = path.clone();
+    }
+    if path.len() == MAX_STOPS {
+        return;
+    }
+    for (j, c) in cands.iter().enumerate() {
+        if path.iter().any(|s| s.item == c.item) {
+            continue;
+        }
+        let arrive = t + times[at][j];
+        let depart = arrive.max(c.ready_in);
+        if !arrive.is_finite() || depart - arrive > MAX_WAIT || depart > HORIZON {
+            continue;
+        }
+        path.push(Stop {
+            item: c.item,
+            arrive,
+            ready_at: c.ready_in,
+        });
+        let gain = c.value * DISCOUNT.powf(depart);
+        search(cands, times, 1 + j, depart, score + gain, path, best);
+        path.pop();
+    }
+}
+
+/// A bot's live item run: the planned route, when it was planned, and the pads on cooldown.
+#[derive(Debug, Clone)]
+pub struct ItemRun {
+    route: Vec<Stop>,
+    planned_at: f32,
+    /// `(item, until)` for pads we stood on that had nothing for us.
+    cooldown: Vec<(usize, f32)>,
+}
+
+impl Default for ItemRun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ItemRun {
+    pub fn new() -> Self {
+        Self {
+            route: Vec::new(),
+            planned_at: f32::NEG_INFINITY,
+            cooldown: Vec::new(),
+        }
+    }
+
+    /// The remaining stops, next first.
+    pub fn stops(&self) -> &[Stop] {
+        &self.route
+    }
+
+    /// Drop the route (combat took over); the next [`goal`](Self::goal) re-plans from wherever
+    /// the bot is then. Pad cooldowns survive; a new map needs a fresh [`ItemRun`].
+    pub fn interrupt(&mut self) {
+        self.route.clear();
+        self.planned_at = f32::NEG_INFINITY;
+    }
+
+    /// The pad to run to (or hold) now, re-planning when due. A stop is done once the bot has
+    /// stood on its pad past the predicted respawn, or once `memory` has the item back on a later
+    /// timer than planned (we picked it up, or someone beat us to it). A pad stood on without the
+    /// pickup registering goes on [`PAD_COOLDOWN`]. A plan scoring under `min_score` is dropped.
+    /// `None` = no route worth running; the caller falls back to its other goals.
+    #[allow(clippy::too_many_arguments)]
+    pub fn goal(
+        &mut self,
+        graph: &NavGraph,
+        items: &[MapItem],
+        memory: &ItemMemory,
+        pos: Vec3,
+        now: f32,
+        min_score: f32,
+        value: impl Fn(&MapItem) -> f32,
+    ) -> Option<Vec3> {
+        let mut stood = Vec::new();
+        self.route.retain(|s| {
+            let Some(it) = items.get(s.item) else {
+                return false;
+            };
+            let back_in = memory.respawn_in(s.item, it.class, now);
+            if back_in > 0.0 && now + back_in > s.ready_at + PAD_GRACE {
+                return false;
+            }
+            let on_pad = (it.origin - pos).length() <= STOP_RADIUS;
+            if on_pad && now >= s.ready_at + PAD_GRACE {
+                stood.push(s.item);
+                return false;
+            }
+            true
+        });
+        self.cooldown.retain(|&(_, until)| until > now);
+        self.cooldown
+            .extend(stood.into_iter().map(|i| (i, now + PAD_COOLDOWN)));
+
+        let since = now - self.planned_at;
+        if since >= REPLAN_INTERVAL || (self.route.is_empty() && since >= RETRY_INTERVAL) {
+            let cooldown = &self.cooldown;
+            let rate = |i: usize, it: &MapItem| {
+                if cooldown.iter().any(|&(c, _)| c == i) {
+                    0.0
+                } else {
+                    value(it)
+                }
+            };
+            let route = plan_rated(graph, items, memory, pos, now, rate);
+            self.route = if route.score >= min_score {
+                route.stops
+            } else {
+                Vec::new()
+            };
+            self.planned_at = now;
+        }
+        self.route
+            .first()
+            .and_then(|s| items.get(s.item))
+            .map(|it| it.origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perception::EntityClass;
+
+    /// A straight corridor of nodes every 100 u along +x from `x0`, edges costed by length.
+    fn corridor(x0: f32, n: usize) -> NavGraph {
+        let nodes: Vec<[f32; 3]> = (0..n).map(|i| [x0 + 100.0 * i as f32, 0.0, 0.0]).collect();
+        let adj = (0..n)
+            .map(|i| {
+                let mut e = Vec::new();
+                if i > 0 {
+                    e.push((i - 1, 100.0));
+                }
+                if i + 1 < n {
+                    e.push((i + 1, 100.0));
+                }
+                e
+            })
+            .collect();
+        NavGraph::from_raw(nodes, adj)
+    }
+
+    fn item(graph: &NavGraph, class: EntityClass, x: f32) -> MapItem {
+        MapItem {
+            class,
+            origin: Vec3::new(x, 0.0, 0.0),
+            nav_node: graph.nearest(&[x, 0.0, 0.0]),
+        }
+    }
+
+    fn flat(_: &MapItem) -> f32 {
+        1.0
+    }
+
+    #[test]
+    fn route_sweeps_one_way_instead_of_zig_zagging() {
+        // Bot in the middle; one pack just behind it, two ahead. Greedy grabs the near one
+        // behind, then runs back past the start; the route takes the far side in one sweep.
+        let g = corridor(-1000.0, 31);
+        let items = [
+            item(&g, EntityClass::ItemHealth, -200.0),
+            item(&g, EntityClass::ItemArmor, 300.0),
+            item(&g, EntityClass::ItemArmor, 600.0),
+        ];
+        let mem = ItemMemory::new();
+        let value = |it: &MapItem| match it.class {
+            EntityClass::ItemArmor => 4.0,
+            _ => 1.0,
+        };
+        let route = plan(&g, &items, &mem, Vec3::ZERO, 0.0, value);
+        let order: Vec<usize> = route.stops.iter().map(|s| s.item).collect();
+        assert_eq!(
+            order,
+            vec![1, 2, 0],
+            "armor run first, the pack on the way back"
+        );
+        assert!(
+            (route.stops[0].arrive - 1.0).abs() < 0.01,
+            "300 u at run speed"
+        );
+        assert!(route.stops.windows(2).all(|w| w[0].arrive < w[1].arrive));
+    }
+
+    #[test]
+    fn arrives_early_and_holds_a_pad_for_its_respawn() {
+        let g = corridor(0.0, 10);
+        let items = [item(&g, EntityClass::ItemArmor, 300.0)];
+        let mut mem = ItemMemory::new();
+        // Armor taken at t=0 respawns at t=20; from t=17 it is 3 s away, travel is 1 s.
+        mem.mark_taken(0, 0.0);
+        let route = plan(&g, &items, &mem, Vec3::ZERO, 17.0, flat);
+        assert_eq!(route.stops.len(), 1);
+        let s = route.stops[0];
+        assert!((s.arrive - 18.0).abs() < 0.01);
+        assert!((s.ready_at - 20.0).abs() < 0.01);
+        assert!((s.wait() - 2.0).abs() < 0.01);
+
+        // Standing on the pad before the respawn keeps the stop.
+        let mut run = ItemRun::new();
+        let pad = items[0].origin;
+        assert_eq!(
+            run.goal(&g, &items, &mem, Vec3::ZERO, 17.0, 0.0, flat),
+            Some(pad)
+        );
+        assert_eq!(run.goal(&g, &items, &mem, pad, 19.0, 0.0, flat), Some(pad));
+    }
+
+    #[test]
+    fn skips_a_pad_that_respawns_too_late_to_wait_for() {
+        let g = corridor(0.0, 10);
+        let items = [item(&g, EntityClass::ItemArmor, 300.0)];
+        let mut mem = ItemMemory::new();
+        mem.mark_taken(0, 10.0); // back at t=30; arriving at t=11 would mean a 19 s wait
+        assert!(plan(&g, &items, &mem, Vec3::ZERO, 10.0, flat)
+            .stops
+            .is_empty());
+        // Zero-valued items are never routed.
+        let mem = ItemMemory::new();
+        assert!(plan(&g, &items, &mem, Vec3::ZERO, 0.0, |_| 0.0)
+            .stops
+            .is_empty());
+    }
+
+    #[test]
+    fn a_pad_with_nothing_for_us_cools_down() {
+        // Standing on a stocked pad that never empties (full health): the stop is dropped and
+        // the re-plan leaves that pad out instead of sending us straight back.
+        let g = corridor(0.0, 10);
+        let items = [item(&g, EntityClass::ItemHealth, 200.0)];
+        let mem = ItemMemory::new();
+        let mut run = ItemRun::new();
+        let pad = items[0].origin;
+        assert_eq!(
+            run.goal(&g, &items, &mem, Vec3::ZERO, 0.0, 0.0, flat),
+            Some(pad)
+        );
+        assert_eq!(run.goal(&g, &items, &mem, pad, 1.0, 0.0, flat), None);
+        assert_eq!(run.goal(&g, &items, &mem, pad, 10.0, 0.0, flat), None);
+        assert_eq!(run.goal(&g, &items, &mem, pad, 17.0, 0.0, flat), Some(pad));
+    }
+
+    #[test]
+    fn a_route_under_the_minimum_score_is_not_run() {
+        let g = corridor(0.0, 10);
+        let items = [item(&g, EntityClass::ItemHealth, 300.0)];
+        let mem = ItemMemory::new();
+        let mut run = ItemRun::new();
+        assert_eq!(run.goal(&g, &items, &mem, Vec3::ZERO, 0.0, 2.0, flat), None);
+        assert_eq!(
+            run.goal(&g, &items, &mem, Vec3::ZERO, 5.0, 0.5, flat),
+            Some(items[0].origin)
+        );
+    }
+
+    #[test]
+    fn pickup_advances_the_route_and_interrupt_replans_from_here() {
+        let g = corridor(0.0, 10);
+        let items = [
+            item(&g, EntityClass::ItemArmor, 200.0),
+            item(&g, EntityClass::ItemArmor, 500.0),
+        ];
+        let mut mem = ItemMemory::new();
+        let mut run = ItemRun::new();
+        assert_eq!(
+            run.goal(&g, &items, &mem, Vec3::ZERO, 0.0, 0.0, flat),
+            Some(items[0].origin)
+        );
+        assert_eq!(run.stops().len(), 2);
+
+        // We grab the first armor: the pad reads empty, so the next stop is the second one.
+        mem.mark_taken(0, 0.7);
+        assert_eq!(
+            run.goal(&g, &items, &mem, items[0].origin, 0.7, 0.0, flat),
+            Some(items[1].origin)
+        );
+        assert_eq!(run.stops().len(), 1);
+
+        // A fight drags us past the second pad; after it the plan starts again from there.
+        run.interrupt();
+        assert!(run.stops().is_empty());
+        let here = Vec3::new(900.0, 0.0, 0.0);
+        assert_eq!(
+            run.goal(&g, &items, &mem, here, 1.0, 0.0, flat),
+            Some(items[1].origin)
+        );
+    }
+}
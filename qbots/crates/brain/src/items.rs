@@ -123,6 +123,21 @@ impl ItemMemory {
             .get(&i)
             .is_none_or(|&t| now - t > respawn_time(class))
     }
+
+    /// Seconds until map item `i` (of `class`) should be back on its pad: `0.0` when it is
+    /// [`available`](Self::available), else the rest of its respawn timer.
+    pub fn respawn_in(&self, i: usize, class: EntityClass, now: f32) -> f32 {
+        self.taken
+            .get(&i)
+            .map_or(0.0, |&t| (t + respawn_time(class) - now).max(0.0))
+    }
+
+    /// Record map item `i` as seen taken at `now` (tests that need a pad on a respawn timer
+    /// without building a frame).
+    #[cfg(test)]
+    pub(crate) fn mark_taken(&mut self, i: usize, now: f32) {
+        self.taken.insert(i, now);
+    }
 }
 
 /// Base desirability of an item class (higher = more worth detouring for).
@@ -187,45 +202,56 @@ pub fn best_item_goal_weighted(
     armor: i32,
 ) -> Option<(Vec3, EntityClass)> {
     let origin = view.self_state().origin;
-
-    // A real weapon is worth detouring for when we're stuck on the spawn loadout.
-    let weapon_mult = match held_weapon {
-        None | Some(Weapon::Blaster) => 4.0,
-        Some(Weapon::Machinegun) | Some(Weapon::Chaingun) => 2.0,
-        _ => 1.0,
-    };
-    // Health / armor hunger ramps as we drop below full.
-    let health_mult = if health < 50 {
-        3.0
-    } else if health < 80 {
-        1.6
-    } else {
-        1.0
-    };
-    let armor_mult = if armor < 30 {
-        2.5
-    } else if armor < 80 {
-        1.4
-    } else {
-        1.0
-    };
-
     view.items()
         .map(|e| {
             let dist = (e.origin - origin).length().max(1.0);
-            let mut val = item_value(e.class, skill);
-            match e.class {
-                EntityClass::ItemWeapon => val *= weapon_mult,
-                EntityClass::ItemHealth => val *= health_mult,
-                EntityClass::ItemArmor => val *= armor_mult,
-                _ => {}
-            }
+            let val =
+                item_value(e.class, skill) * need_multiplier(e.class, held_weapon, health, armor);
             (val / dist, e.origin, e.class)
         })
         .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
         .map(|(_, origin, class)| (origin, class))
 }
 
+/// The loadout/health need scaling of [`best_item_goal_weighted`] for one item class: weapon
+/// hunger on the spawn loadout, health/armor hunger as they drop. Shared with the item-run
+/// planner ([`crate::itemrun`]) so both rank a pickup the same way.
+pub fn need_multiplier(
+    class: EntityClass,
+    held_weapon: Option<Weapon>,
+    health: i32,
+    armor: i32,
+) -> f32 {
+    match class {
+        // A real weapon is worth detouring for when we're stuck on the spawn loadout.
+        EntityClass::ItemWeapon => match held_weapon {
+            None | Some(Weapon::Blaster) => 4.0,
+            Some(Weapon::Machinegun) | Some(Weapon::Chaingun) => 2.0,
+            _ => 1.0,
+        },
+        // Health / armor hunger ramps as we drop below full.
+        EntityClass::ItemHealth => {
+            if health < 50 {
+                3.0
+            } else if health < 80 {
+                1.6
+            } else {
+                1.0
+            }
+        }
+        EntityClass::ItemArmor => {
+            if armor < 30 {
+                2.5
+            } else if armor < 80 {
+                1.4
+            } else {
+                1.0
+            }
+        }
+        _ => 1.0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -335,6 +361,8 @@ mod tests {
             mem.available(0, EntityClass::ItemHealth, 41.0),
             "respawned after 30 s"
         );
+        assert_eq!(mem.respawn_in(0, EntityClass::ItemHealth, 25.0), 15.0);
+        assert_eq!(mem.respawn_in(0, EntityClass::ItemHealth, 41.0), 0.0);
     }
 
     #[test]
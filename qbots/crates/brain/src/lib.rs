@@ -9,29 +9,38 @@
 pub mod aim;
 pub mod brains;
 pub mod combat;
+pub mod ctf;
 pub mod danger;
 pub mod engage;
 pub mod fsm;
 pub mod hazard;
 pub mod heatmap;
 pub mod hybrid;
+pub mod itemrun;
 pub mod items;
 pub mod los;
+pub mod mouse;
 pub mod move_ctrl;
 pub mod nav;
 pub mod nav_mode;
 pub mod navmesh_driver;
 pub mod observed;
+pub mod opponent;
 pub mod perception;
 pub mod persona;
 pub mod pursuit;
 pub mod q3char;
 pub mod recorder;
 pub mod recover;
+pub mod replay;
 pub mod ride;
+pub mod rivals;
 pub mod skill;
+pub mod splash;
 pub mod stall;
 pub mod steer;
+pub mod strafejump;
+pub mod timeline;
 pub mod traverse;
 pub mod water;
 pub mod weapons;
@@ -45,11 +54,14 @@ pub mod xonnav;
 pub use brains::core::{Brain, BrainConfig, BrainContext, BrainMap, BrainOutput};
 pub use brains::main::MainBrain;
 pub use brains::runtester::RunTesterBrain;
+pub use brains::script::ScriptBrain;
 pub use brains::sentry::SentryBrain;
-pub use brains::{brain_tag, build_brain, BrainKind};
+pub use brains::utility::{UtilityBrain, UtilityProfile};
+pub use brains::{brain_tag, build_brain, BrainKind, BrainTraits};
 pub use combat::{CombatDecision, CombatDriver};
+pub use ctf::Team;
 pub use danger::{DangerDriver, DodgeAction};
-pub use heatmap::Heatmap;
+pub use heatmap::{HeatRecord, Heatmap};
 pub use move_ctrl::{MovementController, MovementIntent};
 pub use nav::{NavGoal, NavigationDriver};
 pub use nav_mode::{DangerSource, Navigator};
@@ -57,16 +69,19 @@ pub use navmesh_driver::NavmeshDriver;
 pub use observed::{
     classify_env_death, parse_obituary, EnvDeath, HeatmapObserver, HeatmapSnapshot, Obituary,
 };
+pub use opponent::OpponentModel;
 pub use perception::{EntityClass, PerceivedEntity, SelfState, Worldview};
-pub use q3char::{CharPreset, Q3Character};
+pub use q3char::Q3Character;
 pub use recorder::{
     CmWallProbe, FrameRecord, MovementRecorder, RunSummary, Sample, WallBump, WallProbe,
 };
 pub use recover::{Recovery, RecoveryAction, StuckDetector, StuckLevel};
+pub use rivals::{RivalBook, ThreatProfile};
 pub use skill::{BotSkill, Personality, SkillLevel, SkillRegistry};
 pub use stall::{StallEpisode, StallMonitor, StallSample};
+pub use timeline::{Decision, TickRecord, Timeline, TimelineWriter};
 pub use weapons::Weapon;
-pub use xonchar::{XonCharPreset, XonSkill};
+pub use xonchar::XonSkill;
 pub use xonnav::XonNavDriver;
 
 #[cfg(test)]
@@ -0,0 +1,387 @@
+//! Human mouse-motion aim dynamics — an opt-in layer between a brain's *ideal* aim and the view
+//! it actually sends.
+//!
+//! Every aim model here decides where the crosshair *should* be (Eraser lead + jitter, Q3's
+//! accuracy-scaled error, havocbot's filter cascade) and then gets there at a turn rate, which
+//! reads as perfect tracking with noise on top. A hand on a mouse moves differently, and
+//! [`MouseAim`] models that:
+//! 1. **Ballistic sub-movements.** An off-target crosshair moves by a planned stroke with a
+//!    minimum-jerk (bell-shaped speed) profile. Its duration follows **Fitts's law**,
+//!    `MT = a + b·log2(D/W + 1)`, for amplitude `D` and the target's angular width `W`.
+//! 2. **Overshoot and scatter.** A primary flick (≥ [`FLICK_DEG`]) lands past the target by
+//!    [`MouseParams::overshoot`] on average. Every endpoint scatters in proportion to the stroke's
+//!    amplitude (signal-dependent motor noise), so a big flick misses by more.
+//! 3. **Corrective sub-movements.** After a [`MouseParams::correction_delay`] visual-feedback
+//!    pause, any error still wider than the target starts another, shorter stroke.
+//! 4. **Tracking lag.** On target, the hand follows the target's angular velocity at
+//!    [`MouseParams::pursuit_gain`] plus a first-order correction. Its time constant grows with
+//!    that velocity, so a fast strafer leaves the crosshair trailing.
+//!
+//! [`MouseParams`] are per character: [`MouseParams::from_skill`] spans novice to expert, and
+//! `BotSkill`, `Q3Character` and `XonSkill` each map their own traits onto it. The slots are
+//! [`CombatDriver::with_mouse`](crate::combat::CombatDriver::with_mouse),
+//! the Q3 brain's `AimState::with_mouse` and
+//! [`XonAim::with_mouse`](crate::xoncore::aim::XonAim::with_mouse);
+//! [`BrainConfig::human_aim`](crate::BrainConfig::human_aim) fills them.
+//!
+//! Angles are `(yaw, pitch)` in degrees, in the caller's pitch convention; yaw differences are
+//! wrapped. All randomness comes from the caller's [`AimRng`].
+
+use crate::aim::AimRng;
+use crate::xoncore::wrap180;
+
+/// Half the width of a player's bounding box (`mins`/`maxs` ±16) — sets the target's angular
+/// width at a distance.
+const PLAYER_HALF_WIDTH: f32 = 16.0;
+/// A target never counts as narrower than this (degrees), however far away.
+const MIN_TARGET_DEG: f32 = 1.0;
+/// A stroke at least this long (degrees) is a primary flick and overshoots; shorter ones are
+/// corrections.
+pub const FLICK_DEG: f32 = 15.0;
+/// An ideal-aim jump this large (degrees) in one step is a new target, not target motion.
+const RETARGET_DEG: f32 = 20.0;
+/// Not stepped for this long (seconds) means the hand let go; the next step picks up from the
+/// caller's current view.
+const RESYNC_GAP: f32 = 0.25;
+/// Smoothing of the target angular-velocity estimate per step.
+const VEL_SMOOTH: f32 = 0.5;
+/// Vertical endpoint scatter relative to horizontal (wrist motion is mostly side to side).
+const PITCH_SCATTER: f32 = 0.6;
+
+/// One character's mouse hand. See the module doc for what each knob models.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MouseParams {
+    /// Fitts's-law intercept (seconds).
+    pub fitts_a: f32,
+    /// Fitts's-law slope (seconds per bit).
+    pub fitts_b: f32,
+    /// Mean overshoot of a primary flick, as a fraction of its amplitude.
+    pub overshoot: f32,
+    /// Endpoint scatter (standard deviation) as a fraction of stroke amplitude.
+    pub endpoint_noise: f32,
+    /// Visual-feedback pause between strokes (seconds).
+    pub correction_delay: f32,
+    /// Fraction of the target's angular velocity the hand follows without looking.
+    pub pursuit_gain: f32,
+    /// Tracking time constant on a still target (seconds).
+    pub track_lag: f32,
+    /// Extra tracking time constant per 100°/s of target angular velocity (seconds).
+    pub track_lag_per_speed: f32,
+}
+
+impl MouseParams {
+    /// A hand from novice (`0.0`) to expert (`1.0`). A 90° flick onto a near target takes about
+    /// 0.8 s at 0 and 0.35 s at 1.
+    pub fn from_skill(skill: f32) -> Self {
+        let s = skill.clamp(0.0, 1.0);
+        let lerp = |novice: f32, expert: f32| novice + (expert - novice) * s;
+        Self {
+            fitts_a: lerp(0.10, 0.04),
+            fitts_b: lerp(0.14, 0.06),
+            overshoot: lerp(0.18, 0.06),
+            endpoint_noise: lerp(0.10, 0.03),
+            correction_delay: lerp(0.20, 0.08),
+            pursuit_gain: lerp(0.70, 0.95),
+            track_lag: lerp(0.20, 0.06),
+            track_lag_per_speed: lerp(0.15, 0.04),
+        }
+    }
+
+    /// Fitts's-law movement time (seconds) for a `distance`-degree stroke onto a target `width`
+    /// degrees wide.
+    pub fn movement_time(&self, distance: f32, width: f32) -> f32 {
+        self.fitts_a + self.fitts_b * (distance / width.max(MIN_TARGET_DEG) + 1.0).log2()
+    }
+}
+
+impl Default for MouseParams {
+    fn default() -> Self {
+        Self::from_skill(0.5)
+    }
+}
+
+/// Angular width (degrees) of a player `dist` units away.
+pub fn target_width(dist: f32) -> f32 {
+    (2.0 * (PLAYER_HALF_WIDTH / dist.max(1.0)).atan().to_degrees()).max(MIN_TARGET_DEG)
+}
+
+/// Minimum-jerk position profile: the fraction of a stroke covered at normalized time `t`.
+fn min_jerk(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * t * (10.0 - 15.0 * t + 6.0 * t * t)
+}
+
+/// An approximately unit-normal roll (sum of three uniforms on `[-1, 1)`).
+fn gauss(rng: &mut impl AimRng) -> f32 {
+    rng.next_signed() + rng.next_signed() + rng.next_signed()
+}
+
+/// `a − b` with the yaw wrapped.
+fn diff(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+    (wrap180(a.0 - b.0), a.1 - b.1)
+}
+
+fn len(a: (f32, f32)) -> f32 {
+    (a.0 * a.0 + a.1 * a.1).sqrt()
+}
+
+/// A planned sub-movement: the full displacement and when it runs.
+#[derive(Debug, Clone, Copy)]
+struct Stroke {
+    delta: (f32, f32),
+    start: f32,
+    duration: f32,
+}
+
+/// The per-bot hand: where the crosshair is, the stroke in flight, and the target-motion
+/// estimate. One per bot; it survives target changes.
+#[derive(Debug, Clone)]
+pub struct MouseAim {
+    params: MouseParams,
+    last_step: f32,
+    hand: (f32, f32),
+    last_ideal: (f32, f32),
+    /// Smoothed angular velocity of the ideal aim (degrees per second).
+    target_vel: (f32, f32),
+    stroke: Option<Stroke>,
+    /// No new stroke before this (the feedback pause after the last one).
+    hold_until: f32,
+}
+
+impl MouseAim {
+    pub fn new(params: MouseParams) -> Self {
+        Self {
+            params,
+            last_step: f32::NEG_INFINITY,
+            hand: (0.0, 0.0),
+            last_ideal: (0.0, 0.0),
+            target_vel: (0.0, 0.0),
+            stroke: None,
+            hold_until: f32::NEG_INFINITY,
+        }
+    }
+
+    pub fn params(&self) -> &MouseParams {
+        &self.params
+    }
+
+    /// True while a stroke is in flight.
+    pub fn moving(&self) -> bool {
+        self.stroke.is_some()
+    }
+
+    /// Let go: the view went elsewhere, so the next step picks up from the caller's view as
+    /// after a gap.
+    pub fn release(&mut self) {
+        self.last_step = f32::NEG_INFINITY;
+        self.stroke = None;
+    }
+
+    /// Move the hand toward `ideal` and return the view to send. `now` is the caller's clock
+    /// (seconds); `current` its present view, used when the hand picks up after a gap;
+    /// `dist` the range to the target (it sets the target's width).
+    pub fn step(
+        &mut self,
+        now: f32,
+        current: (f32, f32),
+        ideal: (f32, f32),
+        dist: f32,
+        rng: &mut impl AimRng,
+    ) -> (f32, f32) {
+        let dt = now - self.last_step;
+        self.last_step = now;
+        if !(0.0..=RESYNC_GAP).contains(&dt) {
+            // Picking the mouse up again: start from where the view is, with no motion history.
+            self.hand = current;
+            self.last_ideal = ideal;
+            self.target_vel = (0.0, 0.0);
+            self.stroke = None;
+            self.hold_until = now;
+            return self.step_settled(now, ideal, dist, 0.0, rng);
+        }
+
+        let moved = diff(ideal, self.last_ideal);
+        self.last_ideal = ideal;
+        if dt > 0.0 {
+            self.target_vel = if len(moved) > RETARGET_DEG {
+                (0.0, 0.0)
+            } else {
+                let v = (moved.0 / dt, moved.1 / dt);
+                (
+                    self.target_vel.0 + (v.0 - self.target_vel.0) * VEL_SMOOTH,
+                    self.target_vel.1 + (v.1 - self.target_vel.1) * VEL_SMOOTH,
+                )
+            };
+        }
+
+        // The hand keeps moving with the target during a stroke (feed-forward on top).
+        if let Some(s) = self.stroke {
+            let before = min_jerk((now - dt - s.start) / s.duration);
+            let after = min_jerk((now - s.start) / s.duration);
+            let f = after - before;
+            let ff = self.feed_forward(dt);
+            self.hand = (
+                wrap180(self.hand.0 + s.delta.0 * f + ff.0),
+                self.hand.1 + s.delta.1 * f + ff.1,
+            );
+            if now - s.start >= s.duration {
+                self.stroke = None;
+                self.hold_until = now + self.params.correction_delay;
+            }
+            return self.hand;
+        }
+        self.step_settled(now, ideal, dist, dt, rng)
+    }
+
+    /// No stroke in flight: plan one if the error is wider than the target (and the feedback
+    /// pause is over), else track.
+    fn step_settled(
+        &mut self,
+        now: f32,
+        ideal: (f32, f32),
+        dist: f32,
+        dt: f32,
+        rng: &mut impl AimRng,
+    ) -> (f32, f32) {
+        let p = self.params;
+        let ff = self.feed_forward(dt);
+        let err = diff(ideal, self.hand);
+        let d = len(err);
+        let width = target_width(dist);
+        if now < self.hold_until {
+            // Waiting on visual feedback: only the pursuit carries on.
+            self.hand = (wrap180(self.hand.0 + ff.0), self.hand.1 + ff.1);
+        } else if d > width {
+            let gain = if d >= FLICK_DEG {
+                1.0 + p.overshoot * (1.0 + 0.5 * rng.next_signed())
+            } else {
+                1.0
+            };
+            let sigma = p.endpoint_noise * d;
+            let scatter = (gauss(rng) * sigma, gauss(rng) * sigma * PITCH_SCATTER);
+            self.stroke = Some(Stroke {
+                delta: (err.0 * gain + scatter.0, err.1 * gain + scatter.1),
+                start: now,
+                duration: p.movement_time(d, width),
+            });
+        } else {
+            let speed = len(self.target_vel);
+            let lag = p.track_lag + p.track_lag_per_speed * speed / 100.0;
+            // The error already holds this tick's target motion; correct only what the
+            // feed-forward leaves.
+            let k = (dt / lag).min(1.0);
+            self.hand = (
+                wrap180(self.hand.0 + ff.0 + (err.0 - ff.0) * k),
+                self.hand.1 + ff.1 + (err.1 - ff.1) * k,
+            );
+        }
+        self.hand
+    }
+
+    fn feed_forward(&self, dt: f32) -> (f32, f32) {
+        let g = self.params.pursuit_gain * dt;
+        (self.target_vel.0 * g, self.target_vel.1 * g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aim::JitterRng;
+
+    const DT: f32 = 0.1;
+
+    /// Step a hand at `DT` from `start` toward a fixed `ideal` for `ticks` ticks; the views.
+    fn run(params: MouseParams, start: (f32, f32), ideal: (f32, f32), ticks: usize) -> Vec<f32> {
+        let mut m = MouseAim::new(params);
+        let mut rng = JitterRng::new(5);
+        (0..ticks)
+            .map(|i| m.step(i as f32 * DT, start, ideal, 500.0, &mut rng).0)
+            .collect()
+    }
+
+    #[test]
+    fn fitts_time_grows_with_distance_and_shrinks_with_width() {
+        let p = MouseParams::from_skill(0.5);
+        assert!(p.movement_time(90.0, 2.0) > p.movement_time(10.0, 2.0));
+        assert!(p.movement_time(90.0, 8.0) < p.movement_time(90.0, 2.0));
+        let novice = MouseParams::from_skill(0.0).movement_time(90.0, 3.0);
+        let expert = MouseParams::from_skill(1.0).movement_time(90.0, 3.0);
+        assert!((0.7..0.9).contains(&novice), "novice 90° flick {novice}");
+        assert!((0.3..0.4).contains(&expert), "expert 90° flick {expert}");
+        assert!(target_width(100.0) > target_width(1000.0));
+        assert_eq!(target_width(1e6), MIN_TARGET_DEG);
+    }
+
+    #[test]
+    fn a_flick_overshoots_then_corrects_onto_the_target() {
+        // 90° flick with no scatter: the primary stroke lands past 90, and a correction brings
+        // the crosshair back within the target's width.
+        let p = MouseParams {
+            endpoint_noise: 0.0,
+            ..MouseParams::from_skill(0.0)
+        };
+        let yaws = run(p, (0.0, 0.0), (90.0, 0.0), 40);
+        let peak = yaws.iter().cloned().fold(f32::MIN, f32::max);
+        assert!(peak > 92.0, "primary flick overshoots, peak {peak}");
+        let settled = *yaws.last().unwrap();
+        assert!(
+            (settled - 90.0).abs() <= target_width(500.0),
+            "corrected onto the target, at {settled}"
+        );
+        // Not a snap: the first ticks are still on the way.
+        assert!(yaws[1] < 45.0, "bell-shaped start, at {}", yaws[1]);
+    }
+
+    #[test]
+    fn an_expert_settles_sooner_than_a_novice() {
+        let settle = |skill: f32| {
+            let yaws = run(MouseParams::from_skill(skill), (0.0, 0.0), (120.0, 0.0), 60);
+            let w = target_width(500.0);
+            // Last tick outside the target: everything after it stays on.
+            yaws.iter()
+                .rposition(|y| (y - 120.0).abs() > w)
+                .map_or(0, |i| i + 1)
+        };
+        let (novice, expert) = (settle(0.0), settle(1.0));
+        assert!(expert < novice, "expert {expert} ticks vs novice {novice}");
+    }
+
+    #[test]
+    fn tracking_lag_grows_with_target_angular_speed() {
+        // A target sweeping at constant angular speed: the mean trailing error once settled is
+        // larger for the faster sweep, and behind (not ahead).
+        let trail = |speed: f32| {
+            let mut m = MouseAim::new(MouseParams::from_skill(0.5));
+            let mut rng = JitterRng::new(9);
+            let mut err = 0.0;
+            for i in 0..100 {
+                let t = i as f32 * DT;
+                let ideal = (wrap180(speed * t), 0.0);
+                let view = m.step(t, (0.0, 0.0), ideal, 300.0, &mut rng);
+                if i >= 50 {
+                    err += wrap180(ideal.0 - view.0) / 50.0;
+                }
+            }
+            err
+        };
+        let slow = trail(30.0);
+        let fast = trail(120.0);
+        assert!(
+            slow >= 0.0 && fast > 0.0,
+            "trailing behind: {slow} / {fast}"
+        );
+        assert!(fast > slow, "fast {fast}° vs slow {slow}°");
+    }
+
+    #[test]
+    fn a_gap_picks_up_from_the_callers_view() {
+        let mut m = MouseAim::new(MouseParams::default());
+        let mut rng = JitterRng::new(1);
+        m.step(0.0, (0.0, 0.0), (0.0, 0.0), 500.0, &mut rng);
+        // Half a second without a step: the hand resyncs to the view it is handed.
+        let v = m.step(0.5, (40.0, 5.0), (40.0, 5.0), 500.0, &mut rng);
+        assert_eq!(v, (40.0, 5.0));
+        assert!(!m.moving());
+    }
+}
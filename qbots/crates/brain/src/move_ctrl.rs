@@ -5,6 +5,7 @@
 //! Uses Q2 movement constants from `pmove.c`.
 
 use q2proto::Usercmd;
+use serde::{Deserialize, Serialize};
 
 pub const MAX_SPEED: f32 = 320.0;
 pub const JUMP_VELOCITY: f32 = 270.0;
@@ -14,7 +15,7 @@ pub const BUTTON_ATTACK: u8 = 1;
 pub const BUTTON_USE: u8 = 2;
 pub const BUTTON_ANY: u8 = 128;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct MovementIntent {
     pub yaw: f32,
     pub pitch: f32,
@@ -83,6 +84,9 @@ pub struct MovementController {
     /// a desired world angle, or every aim and movement direction is rotated by
     /// a constant `spawn_yaw` — bots walk into walls away from their target.
     delta_angles: [i16; 3],
+    /// `usercmd.lightlevel`: how lit the bot's eye is, as the engine client reports it
+    /// (`R_SetLightLevel`). Full light (255) until a map's lightmaps say otherwise.
+    lightlevel: u8,
 }
 
 impl MovementController {
@@ -91,6 +95,7 @@ impl MovementController {
             last_cmd: Usercmd::default(),
             msec: 33, // ~30 Hz
             delta_angles: [0, 0, 0],
+            lightlevel: 255,
         }
     }
 
@@ -109,6 +114,12 @@ impl MovementController {
         self.msec = (dt_secs * 1000.0).clamp(1.0, 250.0) as u8;
     }
 
+    /// Set the light level reported in subsequent usercmds — sample it at the eye each
+    /// tick with [`world::LightMap::lightlevel`].
+    pub fn set_lightlevel(&mut self, level: u8) {
+        self.lightlevel = level;
+    }
+
     /// Convert intent to a `Usercmd`.
     pub fn build_cmd(&mut self, intent: MovementIntent) -> Usercmd {
         let mut cmd = Usercmd {
@@ -122,7 +133,7 @@ impl MovementController {
             sidemove: (intent.side * MAX_SPEED) as i16,
             upmove: (intent.up * MAX_SPEED) as i16,
             impulse: intent.weapon.unwrap_or(0),
-            lightlevel: 0, // TODO: get from environment
+            lightlevel: self.lightlevel,
             buttons: 0,
         };
 
@@ -136,8 +147,6 @@ impl MovementController {
             cmd.buttons |= BUTTON_ATTACK;
         }
 
-        cmd.lightlevel = 255; // Assume full light for now
-
         self.last_cmd = cmd;
         cmd
     }
@@ -253,6 +262,10 @@ mod tests {
         let cmd = controller.build_cmd(intent);
         assert!(cmd.forwardmove > 0);
         assert!(cmd.buttons & BUTTON_ATTACK != 0);
+        assert_eq!(cmd.lightlevel, 255, "full light until a map says otherwise");
+
+        controller.set_lightlevel(40);
+        assert_eq!(controller.build_cmd(intent).lightlevel, 40);
     }
 
     #[test]
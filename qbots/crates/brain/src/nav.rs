@@ -1,12 +1,13 @@
 //! Navigation driver — A* over the nav graph with stuck recovery.
 
 use glam::Vec3;
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
 use world::collision::MASK_SOLID;
 use world::{
     navgraph::{HULL_MAXS, HULL_MINS},
-    CollisionModel, EdgeKind, NavGraph,
+    CollisionModel, EdgeKind, EdgeState, NavGraph, NavState,
 };
 
 /// Hard cap on pursuing a single goal without reaching a waypoint. At 10 Hz
@@ -57,7 +58,7 @@ const ORBIT_RADIUS: f32 = world::GRID_SPACING * 2.0;
 /// Extra time lets bots navigate around corners before orbit fires.
 pub const ORBIT_FRAMES: u32 = 25;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NavGoal {
     Waypoint(usize),
     Position(Vec3),
@@ -96,6 +97,17 @@ pub struct NavigationDriver {
     /// node blacklisting: lets A* still reach `dest` via other incoming edges
     /// (different approach directions) while avoiding the exact dangerous path.
     edge_blacklist: HashSet<(usize, usize)>,
+    /// Rocket-jump edges may be planned through (the bot holds a loaded rocket launcher and
+    /// can spare the health). Set each tick by the brain; off by default.
+    rocket_jump_allowed: bool,
+    /// Edges a shut triggered door closes ([`world::EdgeState::Blocked`]); planned around
+    /// like the edge blacklist until the mover state reopens them.
+    mover_blocked: HashSet<(usize, usize)>,
+    /// Edges whose door/lift/train is closed for now but opens on its own
+    /// ([`world::EdgeState::Wait`]): the give-up and orbit watchdogs hold while on one.
+    mover_waiting: HashSet<(usize, usize)>,
+    /// [`NavState::revision`] last applied, so an unchanged state costs nothing.
+    mover_revision: Option<u64>,
 }
 
 impl NavigationDriver {
@@ -114,6 +126,10 @@ impl NavigationDriver {
             waypoint_blacklist: std::collections::VecDeque::new(),
             ledge_blacklist: std::collections::VecDeque::new(),
             edge_blacklist: HashSet::new(),
+            rocket_jump_allowed: false,
+            mover_blocked: HashSet::new(),
+            mover_waiting: HashSet::new(),
+            mover_revision: None,
         }
     }
 
@@ -130,6 +146,83 @@ impl NavigationDriver {
         self.risk_overlay = None;
     }
 
+    /// Allow or forbid rocket-jump edges in the next plan. Cheap to call every tick: only a
+    /// change does anything. Enabling replans so a launch that now shortcuts the route is
+    /// picked up; disabling drops the path only if it still has a rocket jump ahead.
+    pub fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        if allowed == self.rocket_jump_allowed {
+            return;
+        }
+        self.rocket_jump_allowed = allowed;
+        let rj_ahead = self
+            .current_path
+            .windows(2)
+            .any(|w| self.nav_graph.is_rocket_jump_edge(w[0], w[1]));
+        if (allowed && self.nav_graph.rocket_jump_count() > 0) || rj_ahead {
+            tracing::debug!(allowed, "rocket-jump permission changed — replanning");
+            self.force_replan();
+        }
+    }
+
+    /// Apply this frame's live mover state: replan when a door shuts across the path
+    /// ahead or a blocked door reopens, and remember which edges are only waiting.
+    /// Cheap to call every tick — nothing happens unless the state's revision moved.
+    pub fn note_nav_state(&mut self, state: &NavState) {
+        if self.mover_revision == Some(state.revision()) {
+            return;
+        }
+        self.mover_revision = Some(state.revision());
+        self.mover_waiting = state.edges_in(EdgeState::Wait).into_iter().collect();
+        let blocked: HashSet<(usize, usize)> =
+            state.edges_in(EdgeState::Blocked).into_iter().collect();
+        if blocked == self.mover_blocked {
+            return;
+        }
+        let reopened = self.mover_blocked.difference(&blocked).next().is_some();
+        let ahead = self.edges_ahead().any(|e| blocked.contains(&e));
+        self.mover_blocked = blocked;
+        if ahead || reopened {
+            tracing::debug!(ahead, reopened, "mover state changed — replanning");
+            self.force_replan();
+        }
+    }
+
+    /// Node positions from the current waypoint on, at most `max`.
+    pub fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        let Some(i) = self
+            .current_waypoint
+            .and_then(|wp| self.current_path.iter().position(|&w| w == wp))
+        else {
+            return Vec::new();
+        };
+        self.current_path[i..]
+            .iter()
+            .take(max)
+            .map(|&n| self.nav_graph.nodes[n])
+            .collect()
+    }
+
+    /// Directed edges of the current path still to be traversed, starting with the
+    /// one into the current waypoint.
+    fn edges_ahead(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let from = self
+            .current_waypoint
+            .and_then(|wp| self.current_path.iter().position(|&w| w == wp))
+            .map_or(self.current_path.len(), |i| i.saturating_sub(1));
+        self.current_path[from.min(self.current_path.len())..]
+            .windows(2)
+            .map(|w| (w[0], w[1]))
+    }
+
+    /// True when the current edge's door, lift or train is closed but will open on its
+    /// own — the bot should hold rather than give up on it.
+    pub fn current_edge_waiting(&self) -> bool {
+        match (self.prev_waypoint, self.current_waypoint) {
+            (Some(from), Some(to)) => self.mover_waiting.contains(&(from, to)),
+            _ => false,
+        }
+    }
+
     /// Set (or update) the navigation goal. Replans the A* path only when the goal
     /// changes or the current path is exhausted. `set_goal` is safe to call every tick.
     pub fn set_goal(&mut self, goal: NavGoal, from_position: Vec3) {
@@ -211,17 +304,33 @@ impl NavigationDriver {
     /// degenerate (absurdly long) detour — the whole region is hot — re-query
     /// with the overlay dropped ("desperate re-query with W_d=0").
     /// Also applies the waypoint blacklist to avoid repeatedly-stuck nodes.
-    fn plan_path(&self, start: usize, target: usize) -> Option<Vec<usize>> {
+    fn plan_path(&mut self, start: usize, target: usize) -> Option<Vec<usize>> {
         let bl = self.blacklist_set();
+        let edges: HashSet<(usize, usize)> = self
+            .edge_blacklist
+            .union(&self.mover_blocked)
+            .copied()
+            .collect();
+        // Rocket jumps allowed → the one search that sees those edges (overlay folded in).
+        if self.rocket_jump_allowed && self.nav_graph.rocket_jump_count() > 0 {
+            let overlay = self.risk_overlay.as_deref().unwrap_or(&[]);
+            return self
+                .nav_graph
+                .path_with_rocket_jumps(start, target, overlay, &bl, &edges);
+        }
         // No overlay → blacklist-only A* (with edge blacklist applied).
         let Some(overlay) = self.risk_overlay.as_deref() else {
             return self
                 .nav_graph
-                .path_excluding_edges(start, target, &bl, &self.edge_blacklist);
+                .path_excluding_edges(start, target, &bl, &edges);
         };
         // Weighted path with blacklist penalty already embedded in the overlay.
         // Build a combined overlay: overlay[n] + PENALTY for blacklisted nodes.
-        let path = if bl.is_empty() {
+        // Large maps share one cluster hierarchy per graph; the overlay applies per query.
+        let hier = self.nav_graph.hier().filter(|_| bl.is_empty());
+        let path = if let Some(hier) = hier {
+            hier.path(&self.nav_graph, start, target, overlay)?
+        } else if bl.is_empty() {
             self.nav_graph.path_weighted(start, target, overlay)?
         } else {
             const PENALTY: f32 = 1_000_000.0;
@@ -232,6 +341,16 @@ impl NavigationDriver {
                 .collect();
             self.nav_graph.path_weighted(start, target, &combined)?
         };
+        // The weighted searches know nothing of edge exclusions: a route through a shut
+        // door falls back to the plain search that does.
+        if path
+            .windows(2)
+            .any(|w| self.mover_blocked.contains(&(w[0], w[1])))
+        {
+            return self
+                .nav_graph
+                .path_excluding_edges(start, target, &bl, &edges);
+        }
         // Degeneracy guard.
         let straight = Vec3::from(self.nav_graph.nodes[start])
             .distance(Vec3::from(self.nav_graph.nodes[target]));
@@ -325,6 +444,15 @@ impl NavigationDriver {
         }
     }
 
+    /// The [`world::RocketJumpInfo`] when the current path edge (prev_waypoint →
+    /// current_waypoint) is a rocket jump.
+    pub fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        match (self.prev_waypoint, self.current_waypoint) {
+            (Some(from), Some(to)) => self.nav_graph.rocket_jump_info(from, to),
+            _ => None,
+        }
+    }
+
     /// True when the current path edge (prev_waypoint → current_waypoint) is a
     /// teleporter link (Plan 52).
     pub fn current_edge_is_teleport(&self) -> bool {
@@ -473,6 +601,8 @@ impl NavigationDriver {
         self.goal_abandoned = false;
 
         if let Some(wp_idx) = self.current_waypoint {
+            // A door/lift/train that opens on its own gates this edge: hold both watchdogs.
+            let waiting = self.current_edge_waiting();
             let wp_pos = Vec3::from(self.nav_graph.nodes[wp_idx]);
             let delta_xy = (wp_pos - position).truncate();
             let horiz = delta_xy.length();
@@ -490,7 +620,7 @@ impl NavigationDriver {
             // 47↔52 u) from continuously resetting the giveup timer and making
             // it impossible to ever give up on the stuck waypoint.
             const ORBIT_ENTRY_MIN: u32 = 3;
-            let orbit_force = if horiz < ORBIT_RADIUS {
+            let orbit_force = if horiz < ORBIT_RADIUS && !waiting {
                 self.near_wp_ticks += 1;
                 if self.near_wp_ticks >= ORBIT_ENTRY_MIN {
                     self.goal_age_ticks = 0; // sustained orbit entry: orbit owns this
@@ -699,7 +829,7 @@ impl NavigationDriver {
                         return true;
                     }
                 }
-            } else {
+            } else if !waiting {
                 // Still pursuing — age the goal toward the give-up cap.
                 self.goal_age_ticks += 1;
                 if self.goal_age_ticks > GOAL_GIVEUP_TICKS {
@@ -866,6 +996,12 @@ impl crate::nav_mode::Navigator for NavigationDriver {
     fn current_ride_info(&self) -> Option<world::RideInfo> {
         NavigationDriver::current_ride_info(self)
     }
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        NavigationDriver::set_rocket_jump_allowed(self, allowed)
+    }
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        NavigationDriver::current_rocket_jump(self)
+    }
     fn force_replan(&mut self) {
         NavigationDriver::force_replan(self)
     }
@@ -878,6 +1014,9 @@ impl crate::nav_mode::Navigator for NavigationDriver {
     fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
         NavigationDriver::current_waypoint(self).map(|i| self.nav_graph.nodes[i])
     }
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        NavigationDriver::path_ahead(self, max)
+    }
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         NavigationDriver::smooth_with_cm(self, cm, from)
     }
@@ -887,6 +1026,39 @@ impl crate::nav_mode::Navigator for NavigationDriver {
     fn set_risk_overlay(&mut self, overlay: Vec<f32>) {
         NavigationDriver::set_risk_overlay(self, overlay)
     }
+    fn note_nav_state(&mut self, state: &NavState) {
+        NavigationDriver::note_nav_state(self, state)
+    }
+    fn backend(&self) -> &'static str {
+        "astar"
+    }
+}
+
+/// Where to break line of sight: the cheapest-to-reach graph node (walk cost at most
+/// `max_cost`) that none of the `threats` can see, from the map's precomputed visibility
+/// table. `None` when the map was built without one or nothing is in reach.
+pub fn retreat_spot(graph: &NavGraph, from: Vec3, threats: &[Vec3], max_cost: f32) -> Option<Vec3> {
+    let vis = graph.vis_table()?;
+    let start = graph.nearest(&from.to_array())?;
+    let threats: Vec<usize> = threats
+        .iter()
+        .filter_map(|t| graph.nearest(&t.to_array()))
+        .collect();
+    let spot = vis.nearest_hidden(graph, start, &threats, max_cost)?;
+    Some(Vec3::from(graph.nodes[spot]))
+}
+
+/// Ambush spots [`ambush_spot`] considers, best cover first, before giving up on a connected one.
+const AMBUSH_CANDIDATES: usize = 4;
+
+/// Where to camp node `target`: the best-covered node that can see it and that the graph
+/// connects to it, from the map's precomputed visibility table. `None` when the map was built
+/// without one or no connected spot sees `target`.
+pub fn ambush_spot(graph: &NavGraph, target: usize) -> Option<usize> {
+    let vis = graph.vis_table()?;
+    vis.ambush_spots(target, AMBUSH_CANDIDATES)
+        .into_iter()
+        .find(|&s| graph.path(target, s).is_some())
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -931,6 +1103,143 @@ mod tests {
         assert_eq!(nav.planned_jump_count(), 1);
     }
 
+    #[test]
+    fn retreat_spot_breaks_line_of_sight_behind_a_pillar() {
+        use world::testmap::BspBuilder;
+        // A floor with a pillar at the origin; nodes in a line west → east past it, plus one
+        // node tucked behind (north of) the pillar.
+        let mut b = BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [512.0, 512.0, 0.0])
+            .solid([-32.0, 0.0, 0.0], [32.0, 64.0, 128.0]);
+        let cm = CollisionModel::from_bsp(&b.build());
+        let nodes = vec![
+            [-256.0, -64.0, 24.0],
+            [-64.0, -64.0, 24.0],
+            [0.0, -64.0, 24.0],
+            [64.0, -64.0, 24.0],
+            [96.0, 32.0, 24.0],
+            [0.0, 128.0, 24.0],
+        ];
+        let adj = vec![
+            vec![(1, 192.0)],
+            vec![(0, 192.0), (2, 64.0)],
+            vec![(1, 64.0), (3, 64.0)],
+            vec![(2, 64.0), (4, 100.0)],
+            vec![(3, 100.0), (5, 140.0)],
+            vec![(4, 140.0)],
+        ];
+        let mut g = NavGraph::from_raw(nodes, adj);
+        assert_eq!(retreat_spot(&g, Vec3::ZERO, &[], 100.0), None, "no table");
+
+        g.set_vis_table(world::VisTable::build(&cm, &g, None));
+        let threat = Vec3::new(0.0, -300.0, 24.0);
+        let spot =
+            retreat_spot(&g, Vec3::new(0.0, -64.0, 24.0), &[threat], 1000.0).expect("hidden spot");
+        assert_eq!(spot, Vec3::new(0.0, 128.0, 24.0));
+    }
+
+    #[test]
+    fn shut_door_reroutes_and_waiting_door_holds_the_watchdog() {
+        use std::sync::Arc;
+        use world::collision::CONTENTS_SOLID;
+        use world::testmap::BspBuilder;
+        // A door across x=0 (model *1); node 0 → 1 goes straight through it, 0 → 2 → 1
+        // detours north around it.
+        let door = |fields: &[(&str, &str)]| {
+            let mut b = BspBuilder::new();
+            b.solid([-256.0, -256.0, -16.0], [256.0, 256.0, 0.0])
+                .brush_entity(
+                    "func_door",
+                    fields,
+                    CONTENTS_SOLID,
+                    [-8.0, -64.0, 0.0],
+                    [8.0, 64.0, 128.0],
+                );
+            b.build()
+        };
+        let g = Arc::new(NavGraph::from_raw(
+            vec![[-48.0, 0.0, 24.0], [48.0, 0.0, 24.0], [0.0, 200.0, 24.0]],
+            vec![
+                vec![(1, 96.0), (2, 210.0)],
+                vec![(0, 96.0), (2, 210.0)],
+                vec![(0, 210.0), (1, 210.0)],
+            ],
+        ));
+        let from = Vec3::new(-48.0, 0.0, 24.0);
+
+        // Triggered door shut across the path ahead → replan around it.
+        let mut state = NavState::new(&g, &door(&[("angle", "90"), ("targetname", "gate")]));
+        let mut nav = NavigationDriver::new(Arc::clone(&g));
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        assert_eq!(nav.current_path, vec![0, 1]);
+        state.observe([(1, [0.0, 0.0, 0.0])]);
+        nav.note_nav_state(&state);
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        assert_eq!(nav.current_path, vec![0, 2, 1], "detours the shut door");
+
+        // Auto door shut: keep the direct route and don't age the goal on it.
+        let mut state = NavState::new(&g, &door(&[("angle", "90")]));
+        let mut nav = NavigationDriver::new(Arc::clone(&g));
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        state.observe([(1, [0.0, 0.0, 0.0])]);
+        nav.note_nav_state(&state);
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        assert_eq!(nav.current_path, vec![0, 1]);
+        nav.prev_waypoint = Some(0);
+        nav.current_waypoint = Some(1);
+        assert!(nav.current_edge_waiting());
+        for _ in 0..GOAL_GIVEUP_TICKS * 2 {
+            nav.update(from, None);
+        }
+        assert_eq!(
+            nav.goal_age_ticks, 0,
+            "waits at the door instead of giving up"
+        );
+        assert_eq!(nav.current_waypoint, Some(1));
+    }
+
+    #[test]
+    fn large_graph_weighted_plans_use_cluster_hierarchy() {
+        use std::sync::Arc;
+        // 70×70 4-connected grid (past world::HIER_MIN_NODES); a danger wall across the middle row
+        // band with one cold gap at the far east edge.
+        const W: usize = 70;
+        let idx = |x: usize, y: usize| y * W + x;
+        let mut nodes = Vec::new();
+        let mut adj = vec![Vec::new(); W * W];
+        for y in 0..W {
+            for x in 0..W {
+                nodes.push([x as f32 * 24.0, y as f32 * 24.0, 0.0]);
+                for (nx, ny) in [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ] {
+                    if nx < W && ny < W {
+                        adj[idx(x, y)].push((idx(nx, ny), 24.0));
+                    }
+                }
+            }
+        }
+        let g = Arc::new(NavGraph::from_raw(nodes, adj));
+        let mut overlay = vec![0.0; W * W];
+        for y in 33..37 {
+            for x in 0..W - 2 {
+                overlay[idx(x, y)] = 5000.0;
+            }
+        }
+        let mut nav = NavigationDriver::new(Arc::clone(&g));
+        nav.set_risk_overlay(overlay.clone());
+        nav.set_goal(NavGoal::Waypoint(idx(5, 65)), Vec3::new(120.0, 120.0, 0.0));
+        assert!(g.hier().is_some(), "large graph gets a shared hierarchy");
+        assert_eq!(nav.current_path.last(), Some(&idx(5, 65)));
+        assert!(
+            nav.current_path.iter().all(|&n| overlay[n] == 0.0),
+            "route takes the cold gap"
+        );
+    }
+
     #[test]
     fn risk_overlay_detours_driver_around_danger() {
         use std::sync::Arc;
@@ -972,6 +1281,32 @@ mod tests {
         );
     }
 
+    /// Rocket-jump edges are planned through only while allowed; revoking the permission
+    /// drops a path that still needs the launch.
+    #[test]
+    fn rocket_jump_permission_gates_planning() {
+        use std::sync::Arc;
+        // 0 floor, 1 ledge top (160u up): the only link is a rocket jump 0→1.
+        let mut g = NavGraph::from_raw(
+            vec![[0.0, 0.0, 24.0], [112.0, 0.0, 184.0]],
+            vec![vec![], vec![]],
+        );
+        g.set_rocket_jumps(vec![(0, 1, 0.0, 50.0)]);
+        let mut nav = NavigationDriver::new(Arc::new(g));
+        let from = Vec3::new(0.0, 0.0, 24.0);
+
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        // Unreachable → the fallback plans to the nearest reachable node: where we stand.
+        assert_eq!(nav.current_waypoint(), Some(0), "no launcher → no route up");
+
+        nav.set_rocket_jump_allowed(true);
+        nav.set_goal(NavGoal::Waypoint(1), from);
+        assert_eq!(nav.current_waypoint(), Some(1));
+
+        nav.set_rocket_jump_allowed(false);
+        assert_eq!(nav.current_waypoint(), None, "path with a launch dropped");
+    }
+
     /// Plan 52: on a Teleport leg the pursue target is the PAD until the server snap
     /// lands the bot on the destination side — never a lookahead toward the (far)
     /// destination the moment the pad waypoint is "reached".
@@ -49,6 +49,15 @@ pub trait Navigator {
     fn current_ride_info(&self) -> Option<world::RideInfo> {
         None
     }
+    /// Allow (or forbid) rocket-jump edges in this backend's plans. The brain sets it each tick
+    /// from [`crate::traverse::rocket_jump_ready`] + its persona; forbidding it while a rocket
+    /// jump is on the current path drops that path. No-op for backends without them (navmesh).
+    fn set_rocket_jump_allowed(&mut self, _allowed: bool) {}
+    /// The [`world::RocketJumpInfo`] for the current edge when it is a rocket jump, else `None`.
+    /// Drives the executor's aim-down/jump/fire launch.
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        None
+    }
     /// Drop the current path so the next `set_goal` replans from scratch.
     fn force_replan(&mut self);
     /// If the current target is hull-blocked from `pos`, blacklist it before a replan.
@@ -63,6 +72,11 @@ pub trait Navigator {
     fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
         None
     }
+    /// The path still ahead — the current target first, then the points after it — capped at
+    /// `max` points (movement layers that look down the route; empty if N/A).
+    fn path_ahead(&self, _max: usize) -> Vec<[f32; 3]> {
+        Vec::new()
+    }
     /// String-pull/smooth the current path (A*-only; navmesh paths are already smooth).
     fn smooth_with_cm(&mut self, _cm: &CollisionModel, _from: Vec3) {}
     /// Apply a per-node risk/popularity cost overlay for the next replan (A* heatmap
@@ -82,6 +96,15 @@ pub trait Navigator {
     /// Push this frame's PVS-observed threats (Plan 61): a danger-pricing backend (`xg`)
     /// folds them into its path costs; every other backend ignores them (no-op default).
     fn note_dangers(&mut self, _dangers: &[DangerSource]) {}
+
+    /// Push this frame's live door/lift/train state: an A* backend plans around edges a
+    /// shut door blocks and holds its give-up watchdog on edges that are only waiting.
+    fn note_nav_state(&mut self, _state: &world::NavState) {}
+    /// Which backend is steering, for the decision timeline: a plain driver names itself, a
+    /// hybrid the sub-driver it is delegating to right now.
+    fn backend(&self) -> &'static str {
+        "?"
+    }
 }
 
 /// A scriptable `Navigator` stub for deterministic brain tests (no nav graph / server needed).
@@ -174,6 +174,15 @@ impl Navigator for NavmeshDriver {
         Some([v.x, v.y, v.z])
     }
 
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        let from = (self.seg + 1).min(self.path.len());
+        self.path[from..]
+            .iter()
+            .take(max)
+            .map(|v| v.to_array())
+            .collect()
+    }
+
     fn goal_abandoned(&self) -> bool {
         false // navmesh never self-abandons a goal; recovery handles real stalls
     }
@@ -186,6 +195,10 @@ impl Navigator for NavmeshDriver {
             .and_then(|s| s.parse().ok())
             .unwrap_or(1.0)
     }
+
+    fn backend(&self) -> &'static str {
+        "navmesh"
+    }
 }
 
 #[cfg(test)]
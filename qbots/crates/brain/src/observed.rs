@@ -24,7 +24,7 @@ use client::parse::ConfigStrings;
 use glam::Vec3;
 use world::NavGraph;
 
-use crate::heatmap::Heatmap;
+use crate::heatmap::{HeatRecord, Heatmap};
 use crate::perception::{player_name, Worldview};
 
 /// How many frames a cached player-node stays "trusted" for obituary
@@ -224,6 +224,11 @@ impl HeatmapObserver {
         }
     }
 
+    /// The nav graph the heatmap is indexed by.
+    pub fn graph(&self) -> &NavGraph {
+        &self.graph
+    }
+
     /// Read-only access to the underlying heatmap (diagnostics).
     pub fn heatmap(&self) -> &Heatmap {
         &self.heatmap
@@ -299,6 +304,19 @@ impl HeatmapObserver {
         );
     }
 
+    /// Player names we've observed so far (our own first), for obituary matching elsewhere.
+    pub fn known_names(&self) -> &[String] {
+        &self.known_names
+    }
+
+    /// `(name, node origin)` of every named player [`Self::sample_presence`] placed on `frame`.
+    pub fn sightings(&self, frame: i32) -> impl Iterator<Item = (&str, Vec3)> {
+        self.player_nodes
+            .iter()
+            .filter(move |(_, &(_, f))| f == frame)
+            .map(|(name, &(node, _))| (name.as_str(), Vec3::from(self.graph.node_pos(node))))
+    }
+
     /// Build the per-node cost overlay for risk-weighted A\*:
     /// `W_d·danger − W_p·popularity`. High-skill bots weight danger more
     /// (risk-averse); aggressive bots weight popularity more (seek action).
@@ -306,6 +324,16 @@ impl HeatmapObserver {
         self.heatmap.cost_overlay(w_danger, w_pop)
     }
 
+    /// This session's undecayed heat, for saving per map (see [`Heatmap::record`]).
+    pub fn record(&self) -> HeatRecord {
+        self.heatmap.record()
+    }
+
+    /// Seed from earlier sessions' heat on this map (see [`Heatmap::seed`]).
+    pub fn seed(&mut self, rec: &HeatRecord) -> bool {
+        self.heatmap.seed(rec)
+    }
+
     /// A compact snapshot for periodic debug logging / a danger-map overlay
     /// (Plan 08 T4). `hot_k` caps how many hot nodes to report.
     pub fn snapshot(&self, hot_k: usize) -> HeatmapSnapshot {
@@ -0,0 +1,607 @@
+//! Opponent belief model — where an enemy we have lost sight of probably is.
+//!
+//! Q2 only sends a client the entities in its PVS (`SV_BuildClientFrame`, `sv_ents.c`), so an
+//! enemy that ducks round a corner simply drops out of the frame and every brain used to forget
+//! it. [`OpponentModel`] keeps one [`Track`] per enemy: the last sighting, the weapon it was
+//! wielding (VWep), and a health estimate from its pain animations.
+//!
+//! Once an enemy is out of sight its position becomes a belief over nav nodes: every node it
+//! could have run to since (walk cost ≤ [`RUN_SPEED`] × time unseen), minus every node we have
+//! looked at since and found empty. "Looked at" is the graph's precomputed node visibility
+//! ([`world::VisTable`], itself PVS-culled) or, on a graph built without one, a capped number
+//! of live [`los`] traces. Pruning is sound because anything in our line of sight is in our
+//! PVS, and the server would have sent it. A pruned node refills from the surviving belief at
+//! run speed, so a sweep that turns its back doesn't keep a room clean forever.
+//!
+//! Brains read [`OpponentModel::search_goal`] — the nearest well-covered node the enemy could
+//! be hiding at — instead of roaming once the last-known position is a dead end.
+
+use glam::Vec3;
+use world::{CollisionModel, NavGraph, VIS_RANGE};
+
+use crate::los;
+use crate::perception::Worldview;
+use crate::weapons::Weapon;
+
+/// Player run speed (`pm_maxspeed`, `pmove.c`) — how fast the belief spreads.
+pub const RUN_SPEED: f32 = 300.0;
+/// An enemy unseen this long is forgotten (seconds).
+const MEMORY_SECS: f32 = 20.0;
+/// Most enemies tracked at once; the stalest track goes first.
+const MAX_TRACKS: usize = 8;
+/// Live LOS traces per tick when the graph has no visibility table.
+const TRACE_BUDGET: usize = 32;
+/// Health a pain animation is taken to mean (`player_pain` only plays once per 0.7 s, so
+/// this is a floor on the damage dealt, not an estimate of it).
+const PAIN_HIT_EST: f32 = 20.0;
+/// A pain animation this recent reads as "health falling" (seconds).
+const TREND_SECS: f32 = 3.0;
+/// How much a fully covered node outweighs an exposed one as a hiding spot.
+const COVER_BIAS: f32 = 2.0;
+/// Distance (units) at which a search spot's score halves — nearer spots are checked first.
+const SEARCH_FALLOFF: f32 = 512.0;
+/// Least time between refills of a pruned belief (seconds). A refill is a full-graph flood;
+/// in between, ruled-out spots stay out and only what lies past them is left loose.
+const REFILL_SECS: f32 = 0.5;
+
+/// Player model pain frames (`m_player.h`: `FRAME_pain101..pain304`, `FRAME_crpain1..4`).
+fn pain_frame(frame: i32) -> bool {
+    (54..=65).contains(&frame) || (169..=172).contains(&frame)
+}
+
+/// Player model death frames (`FRAME_crdeath1..death308`).
+fn death_frame(frame: i32) -> bool {
+    (173..=197).contains(&frame)
+}
+
+/// Which way an enemy's health looks to be going.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthTrend {
+    /// It played a pain animation in the last few seconds.
+    Falling,
+    Steady,
+}
+
+/// What one bot knows about one enemy.
+#[derive(Debug, Clone)]
+pub struct Track {
+    pub entity: i32,
+    /// Where we last saw it.
+    pub last_pos: Vec3,
+    /// When we last saw it (the model's clock, seconds).
+    pub last_seen: f32,
+    /// The last weapon we saw it wield; kept while it is out of sight.
+    pub weapon: Option<Weapon>,
+    /// Health estimate: 100 on first sight, less [`PAIN_HIT_EST`] per pain animation seen.
+    pub health_est: f32,
+    last_pain: Option<f32>,
+    in_pain: bool,
+    visible: bool,
+    /// Per node, the run distance after `last_seen` from which the enemy could be there.
+    /// Empty while it is in view (or without a graph).
+    reach: Vec<f32>,
+    /// Run distance covered since `last_seen`.
+    radius: f32,
+    /// When `reach` was last flooded (the model's clock).
+    refilled: f32,
+    /// Spots were ruled out since `refilled`, so `reach` is due a refill.
+    stale: bool,
+    /// The search spot handed out last, kept while it is still a possibility.
+    search: Option<usize>,
+}
+
+impl Track {
+    fn new(entity: i32, pos: Vec3, now: f32) -> Self {
+        Self {
+            entity,
+            last_pos: pos,
+            last_seen: now,
+            weapon: None,
+            health_est: 100.0,
+            last_pain: None,
+            in_pain: false,
+            visible: true,
+            reach: Vec::new(),
+            radius: 0.0,
+            refilled: now,
+            stale: false,
+            search: None,
+        }
+    }
+
+    /// In view this frame.
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn health_trend(&self, now: f32) -> HealthTrend {
+        match self.last_pain {
+            Some(t) if now - t < TREND_SECS => HealthTrend::Falling,
+            _ => HealthTrend::Steady,
+        }
+    }
+
+    /// Could the enemy be at node `n` right now?
+    pub fn possible(&self, n: usize) -> bool {
+        self.reach.get(n).is_some_and(|&r| r <= self.radius)
+    }
+
+    /// Every node the enemy could be at right now. Empty while it is in view.
+    pub fn possible_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.reach
+            .iter()
+            .enumerate()
+            .filter(|&(_, &r)| r <= self.radius)
+            .map(|(n, _)| n)
+    }
+
+    /// Is there a belief to search (out of sight, and not every spot ruled out)?
+    pub fn believed(&self) -> bool {
+        !self.visible && self.possible_nodes().next().is_some()
+    }
+}
+
+/// Hiding-spot weight of node `n`: covered nodes are likelier than exposed ones.
+fn spot_weight(graph: &NavGraph, n: usize) -> f32 {
+    1.0 + COVER_BIAS * graph.vis_table().map_or(0.0, |v| v.cover(n))
+}
+
+/// Per-bot memory of every enemy seen and a belief over where each unseen one is.
+#[derive(Debug, Clone, Default)]
+pub struct OpponentModel {
+    tracks: Vec<Track>,
+}
+
+impl OpponentModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget everything — a new map invalidates every node index.
+    pub fn reset(&mut self) {
+        self.tracks.clear();
+    }
+
+    /// Forget one enemy (we just fragged it: it respawns anywhere).
+    pub fn forget(&mut self, entity: i32) {
+        self.tracks.retain(|t| t.entity != entity);
+    }
+
+    pub fn tracks(&self) -> impl Iterator<Item = &Track> {
+        self.tracks.iter()
+    }
+
+    pub fn track(&self, entity: i32) -> Option<&Track> {
+        self.tracks.iter().find(|t| t.entity == entity)
+    }
+
+    /// Is `entity` out of sight with somewhere left to look for it?
+    pub fn believed(&self, entity: i32) -> bool {
+        self.track(entity).is_some_and(Track::believed)
+    }
+
+    /// Feed one frame: refresh the tracks of every enemy in view, spread the belief of every
+    /// other one by the time elapsed, and rule out what we can see. `now` is the brain's
+    /// clock in seconds. Without a `graph` only the sightings are kept.
+    pub fn observe(
+        &mut self,
+        view: &Worldview,
+        graph: Option<&NavGraph>,
+        cm: Option<&CollisionModel>,
+        now: f32,
+    ) {
+        for t in &mut self.tracks {
+            t.visible = false;
+        }
+        for e in view.enemies() {
+            if death_frame(e.frame) {
+                self.forget(e.entity_number);
+                continue;
+            }
+            let i = match self.tracks.iter().position(|t| t.entity == e.entity_number) {
+                Some(i) => i,
+                None => {
+                    self.tracks.push(Track::new(e.entity_number, e.origin, now));
+                    self.tracks.len() - 1
+                }
+            };
+            let t = &mut self.tracks[i];
+            let pain = pain_frame(e.frame);
+            if pain && !t.in_pain {
+                t.health_est = (t.health_est - PAIN_HIT_EST).max(1.0);
+                t.last_pain = Some(now);
+            }
+            t.in_pain = pain;
+            t.visible = true;
+            t.last_pos = e.origin;
+            t.last_seen = now;
+            t.weapon = e.held_weapon.or(t.weapon);
+            t.reach.clear();
+            t.stale = false;
+            t.search = None;
+        }
+        self.tracks
+            .retain(|t| t.visible || now - t.last_seen < MEMORY_SECS);
+        if self.tracks.len() > MAX_TRACKS {
+            self.tracks
+                .sort_by(|a, b| b.last_seen.total_cmp(&a.last_seen));
+            self.tracks.truncate(MAX_TRACKS);
+        }
+
+        let Some(graph) = graph else { return };
+        for t in self.tracks.iter_mut().filter(|t| !t.visible) {
+            if t.reach.is_empty() {
+                let Some(n) = graph.nearest(&t.last_pos.to_array()) else {
+                    continue;
+                };
+                t.reach = graph.flood_costs(n);
+                t.refilled = t.last_seen;
+            }
+            t.radius = RUN_SPEED * (now - t.last_seen);
+        }
+        if self.tracks.iter().all(|t| t.visible || t.reach.is_empty()) {
+            return;
+        }
+
+        let seen = self.seen_nodes(view, graph, cm);
+        self.tracks.retain_mut(|t| {
+            if t.visible || t.reach.is_empty() {
+                return true;
+            }
+            let mut pruned = false;
+            for &n in &seen {
+                if t.possible(n) {
+                    t.reach[n] = f32::INFINITY;
+                    pruned = true;
+                }
+            }
+            if pruned && t.possible_nodes().next().is_none() {
+                // Ruled out everywhere — it died, teleported or respawned.
+                return false;
+            }
+            t.stale |= pruned;
+            if !t.stale || now - t.refilled < REFILL_SECS {
+                return true;
+            }
+            // The enemy is somewhere in what survived, right now: refill from there.
+            let sources: Vec<(usize, f32)> = t.possible_nodes().map(|n| (n, t.radius)).collect();
+            t.reach = graph.flood_costs_multi(&sources);
+            t.refilled = now;
+            t.stale = false;
+            true
+        });
+    }
+
+    /// Nodes we can see from where we stand (our own node included).
+    fn seen_nodes(
+        &self,
+        view: &Worldview,
+        graph: &NavGraph,
+        cm: Option<&CollisionModel>,
+    ) -> Vec<usize> {
+        let pos = view.self_state().origin;
+        let Some(here) = graph.nearest(&pos.to_array()) else {
+            return Vec::new();
+        };
+        if let Some(vis) = graph.vis_table() {
+            return std::iter::once(here)
+                .chain(vis.visible_from(here))
+                .collect();
+        }
+        let Some(cm) = cm else {
+            return vec![here];
+        };
+        // No table: trace to the nearest candidate spots in range, a budget per tick.
+        let mut cands: Vec<(f32, usize)> = self
+            .tracks
+            .iter()
+            .flat_map(Track::possible_nodes)
+            .map(|n| ((Vec3::from(graph.node_pos(n)) - pos).length(), n))
+            .filter(|&(d, n)| d <= VIS_RANGE && n != here)
+            .collect();
+        cands.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        cands.dedup_by_key(|c| c.1);
+        let eye = los::eye_origin(pos.to_array());
+        std::iter::once(here)
+            .chain(
+                cands
+                    .into_iter()
+                    .take(TRACE_BUDGET)
+                    .map(|(_, n)| n)
+                    .filter(|&n| {
+                        let p = graph.node_pos(n);
+                        los::has_los(cm, eye, [p[0], p[1], p[2] + los::EYE_Z])
+                    }),
+            )
+            .collect()
+    }
+
+    /// The belief over `entity`'s position as normalized `(node, probability)` pairs,
+    /// covered spots weighted up. Empty while it is in view or untracked.
+    pub fn belief(&self, entity: i32, graph: &NavGraph) -> Vec<(usize, f32)> {
+        let Some(t) = self.track(entity) else {
+            return Vec::new();
+        };
+        let mut b: Vec<(usize, f32)> = t
+            .possible_nodes()
+            .map(|n| (n, spot_weight(graph, n)))
+            .collect();
+        let total: f32 = b.iter().map(|&(_, w)| w).sum();
+        for (_, w) in &mut b {
+            *w /= total;
+        }
+        b
+    }
+
+    /// Where to go looking for `entity` (or, for `None`, the enemy seen most recently): its
+    /// position while in view, else the best hiding spot to check next from `from` — likely
+    /// (covered) and near. The chosen spot is kept until it is ruled out, so the route
+    /// doesn't flap. `None` when there is nothing left to search.
+    pub fn search_goal(
+        &mut self,
+        entity: Option<i32>,
+        graph: &NavGraph,
+        from: Vec3,
+    ) -> Option<Vec3> {
+        let t = match entity {
+            Some(n) => self.tracks.iter_mut().find(|t| t.entity == n)?,
+            None => self
+                .tracks
+                .iter_mut()
+                .filter(|t| t.visible || !t.reach.is_empty())
+                .max_by(|a, b| a.last_seen.total_cmp(&b.last_seen))?,
+        };
+        if t.visible {
+            return Some(t.last_pos);
+        }
+        if let Some(n) = t.search.filter(|&n| t.possible(n)) {
+            return Some(Vec3::from(graph.node_pos(n)));
+        }
+        let score = |n: usize| {
+            let d = (Vec3::from(graph.node_pos(n)) - from).length();
+            spot_weight(graph, n) / (1.0 + d / SEARCH_FALLOFF)
+        };
+        let best = t
+            .possible_nodes()
+            .max_by(|&a, &b| score(a).total_cmp(&score(b)).then(b.cmp(&a)))?;
+        t.search = Some(best);
+        Some(Vec3::from(graph.node_pos(best)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perception::CS_PLAYERSKINS;
+    use client::parse::ConfigStrings;
+    use q2proto::{EntityState, Frame};
+    use world::testmap::BspBuilder;
+
+    /// We are entity 1 at `me`; `enemy` is entity 2 `(origin, frame)` when in view.
+    fn view(me: [f32; 3], enemy: Option<([f32; 3], i32)>) -> Worldview {
+        let mut cs = ConfigStrings::default();
+        cs.set(CS_PLAYERSKINS, "Me\\male/grunt");
+        cs.set(CS_PLAYERSKINS + 1, "Them\\male/grunt");
+        let mut entities = vec![EntityState {
+            number: 1,
+            modelindex: 255,
+            origin: me,
+            ..Default::default()
+        }];
+        if let Some((origin, frame)) = enemy {
+            entities.push(EntityState {
+                number: 2,
+                modelindex: 255,
+                origin,
+                frame,
+                ..Default::default()
+            });
+        }
+        let mut v = Worldview::from_frame(
+            &Frame {
+                entities,
+                ..Default::default()
+            },
+            &cs,
+            0,
+        );
+        v.self_state.origin = Vec3::from(me);
+        v
+    }
+
+    /// Nodes every 100 u along +x from the origin.
+    fn corridor(n: usize) -> NavGraph {
+        let nodes = (0..n).map(|i| [i as f32 * 100.0, 0.0, 24.0]).collect();
+        let adj = (0..n)
+            .map(|i| {
+                let mut e = Vec::new();
+                if i > 0 {
+                    e.push((i - 1, 100.0));
+                }
+                if i + 1 < n {
+                    e.push((i + 1, 100.0));
+                }
+                e
+            })
+            .collect();
+        NavGraph::from_raw(nodes, adj)
+    }
+
+    #[test]
+    fn belief_spreads_at_run_speed_and_pain_marks_health() {
+        let g = corridor(20);
+        let mut m = OpponentModel::new();
+        // Seen at node 10, in pain, then gone. Our own spot (far off the corridor) sees nothing.
+        let far = [0.0, 5000.0, 24.0];
+        m.observe(
+            &view(far, Some(([1000.0, 0.0, 24.0], 0))),
+            Some(&g),
+            None,
+            0.0,
+        );
+        m.observe(
+            &view(far, Some(([1000.0, 0.0, 24.0], 55))),
+            Some(&g),
+            None,
+            0.1,
+        );
+        let t = m.track(2).unwrap();
+        assert!(t.visible());
+        assert_eq!(t.health_est, 80.0);
+        assert_eq!(t.health_trend(0.1), HealthTrend::Falling);
+
+        m.observe(&view(far, None), Some(&g), None, 1.1);
+        let t = m.track(2).unwrap();
+        assert!(!t.visible() && t.believed());
+        let possible: Vec<usize> = t.possible_nodes().collect();
+        assert_eq!(
+            possible,
+            (7..=13).collect::<Vec<_>>(),
+            "one second at 300 u/s"
+        );
+        assert_eq!(t.health_trend(5.0), HealthTrend::Steady);
+
+        let b = m.belief(2, &g);
+        assert_eq!(b.len(), 7);
+        assert!((b.iter().map(|&(_, p)| p).sum::<f32>() - 1.0).abs() < 1e-5);
+
+        // Out of memory → forgotten.
+        m.observe(&view(far, None), Some(&g), None, 1.1 + MEMORY_SECS);
+        assert!(m.track(2).is_none());
+    }
+
+    #[test]
+    fn pruned_beliefs_refill_at_most_once_per_interval() {
+        let g = corridor(20);
+        let at = |n: usize| [n as f32 * 100.0, 0.0, 24.0];
+        let mut m = OpponentModel::new();
+        m.observe(&view(at(0), Some((at(10), 0))), Some(&g), None, 0.0);
+        // Standing on a possible spot rules it out and refills from what is left.
+        m.observe(&view(at(12), None), Some(&g), None, 1.0);
+        let t = m.track(2).unwrap();
+        assert!(!t.possible(12) && !t.stale);
+        assert_eq!(t.refilled, 1.0);
+        // Another spot ruled out straight after stays out, but the refill waits.
+        m.observe(&view(at(13), None), Some(&g), None, 1.2);
+        let t = m.track(2).unwrap();
+        assert!(!t.possible(13) && t.stale);
+        assert_eq!(t.refilled, 1.0);
+        // Once the interval is up it catches up, with nothing new in view.
+        m.observe(&view(at(0), None), Some(&g), None, 1.5);
+        let t = m.track(2).unwrap();
+        assert!(!t.possible(13) && !t.stale);
+        assert_eq!(t.refilled, 1.5);
+    }
+
+    #[test]
+    fn death_frames_and_frags_drop_the_track() {
+        let g = corridor(4);
+        let mut m = OpponentModel::new();
+        m.observe(
+            &view([0.0; 3], Some(([300.0, 0.0, 24.0], 0))),
+            Some(&g),
+            None,
+            0.0,
+        );
+        assert!(m.track(2).is_some());
+        m.observe(
+            &view([0.0; 3], Some(([300.0, 0.0, 24.0], 180))),
+            Some(&g),
+            None,
+            0.1,
+        );
+        assert!(m.track(2).is_none());
+        m.observe(
+            &view([0.0; 3], Some(([300.0, 0.0, 24.0], 0))),
+            Some(&g),
+            None,
+            0.2,
+        );
+        m.forget(2);
+        assert!(m.tracks().next().is_none());
+    }
+
+    /// A floor split by a wall along y = 0 with a doorway at the west end: the south corridor
+    /// (y = -128) and the north corridor (y = +128) can't see each other.
+    fn two_corridors() -> (CollisionModel, NavGraph) {
+        let mut b = BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [1536.0, 512.0, 0.0])
+            .solid([0.0, -16.0, 0.0], [1536.0, 16.0, 256.0]);
+        let cm = CollisionModel::from_bsp(&b.build());
+        // 0..=7 south row (x = 0..700), 8..=15 north row, doorway link 0 ↔ 8 via x = -100.
+        let mut nodes: Vec<[f32; 3]> = (0..8).map(|i| [i as f32 * 100.0, -128.0, 24.0]).collect();
+        nodes.extend((0..8).map(|i| [i as f32 * 100.0, 128.0, 24.0]));
+        let mut adj: Vec<Vec<(usize, f32)>> = vec![Vec::new(); 16];
+        for row in [0, 8] {
+            for i in row..row + 7 {
+                adj[i].push((i + 1, 100.0));
+                adj[i + 1].push((i, 100.0));
+            }
+        }
+        adj[0].push((8, 400.0));
+        adj[8].push((0, 400.0));
+        (cm, NavGraph::from_raw(nodes, adj))
+    }
+
+    #[test]
+    fn what_we_see_is_ruled_out_and_the_search_goes_round_the_wall() {
+        let (cm, mut g) = two_corridors();
+        g.set_vis_table(world::VisTable::build(&cm, &g, None));
+        let mut m = OpponentModel::new();
+        // We stand at the east end of the south row; the enemy was at its west end.
+        let me = [700.0, -128.0, 24.0];
+        m.observe(
+            &view(me, Some(([0.0, -128.0, 24.0], 0))),
+            Some(&g),
+            Some(&cm),
+            0.0,
+        );
+        // Three seconds later it could be 900 u away — but the whole south row is in view.
+        m.observe(&view(me, None), Some(&g), Some(&cm), 3.0);
+        let t = m.track(2).unwrap();
+        assert!(t.possible_nodes().all(|n| n >= 8), "south row cleared");
+        assert!(t.possible(8) && t.possible(12));
+        assert!(
+            !t.possible(15),
+            "800 u north of the doorway is beyond reach"
+        );
+        let goal = m.search_goal(Some(2), &g, Vec3::from(me)).unwrap();
+        assert!(goal.y > 0.0, "search the north row: {goal:?}");
+        // The spot is sticky while it stays possible.
+        assert_eq!(m.search_goal(None, &g, Vec3::from(me)), Some(goal));
+    }
+
+    #[test]
+    fn live_traces_prune_without_a_vis_table() {
+        let (cm, g) = two_corridors();
+        let mut m = OpponentModel::new();
+        let me = [700.0, -128.0, 24.0];
+        m.observe(
+            &view(me, Some(([0.0, -128.0, 24.0], 0))),
+            Some(&g),
+            Some(&cm),
+            0.0,
+        );
+        m.observe(&view(me, None), Some(&g), Some(&cm), 3.0);
+        let t = m.track(2).unwrap();
+        assert!(t.possible_nodes().all(|n| n >= 8));
+        assert!(t.believed());
+    }
+
+    #[test]
+    fn a_belief_ruled_out_everywhere_is_dropped() {
+        let (cm, mut g) = two_corridors();
+        g.set_vis_table(world::VisTable::build(&cm, &g, None));
+        let mut m = OpponentModel::new();
+        let me = [700.0, -128.0, 24.0];
+        // Gone for only a third of a second: it can't have reached the doorway link.
+        m.observe(
+            &view(me, Some(([300.0, -128.0, 24.0], 0))),
+            Some(&g),
+            Some(&cm),
+            0.0,
+        );
+        m.observe(&view(me, None), Some(&g), Some(&cm), 0.3);
+        assert!(m.track(2).is_none());
+    }
+}
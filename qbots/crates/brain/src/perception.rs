@@ -4,13 +4,15 @@
 //! current frame's PVS are marked "stale" (not removed), with last-known-position
 //! decay. Classification is based on configstrings (CS_MODELS, CS_PLAYERSKINS).
 
+use crate::ctf::Team;
 use crate::weapons::Weapon;
 use client::parse::ConfigStrings;
 use glam::Vec3;
 use q2proto::{Frame, PlayerState};
+use serde::{Deserialize, Serialize};
 
 /// Configstring index where the models table starts (`CS_MODELS`, `shared.h:1193`).
-const CS_MODELS: usize = 32;
+pub const CS_MODELS: usize = 32;
 
 /// `CS_PLAYERSKINS` — start of the per-client infostring table (`shared.h:1208`).
 /// Derived for yquake2 (MAX_CLIENTS = MAX_MODELS = MAX_SOUNDS = MAX_IMAGES =
@@ -34,7 +36,7 @@ const STAT_FRAGS: usize = 14;
 const STALE_THRESHOLD: i32 = 10; // ~1 second at 10 Hz
 
 /// Classification of an entity.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EntityClass {
     SelfPlayer,
     EnemyPlayer,
@@ -45,11 +47,13 @@ pub enum EntityClass {
     ItemPowerup,
     ProjectileRocket,
     ProjectileGrenade,
+    /// A CTF flag (`item_flag_team1/2`) — on its pad or dropped.
+    Flag(Team),
     Unknown,
 }
 
 /// A classified entity with state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerceivedEntity {
     pub entity_number: i32,
     pub class: EntityClass,
@@ -63,6 +67,19 @@ pub struct PerceivedEntity {
     /// → CS_MODELS, Plan 28). `None` for non-players, when VWep is off, or an unknown model — we
     /// never guess. Lets `main` read the matchup (hold range vs a railgunner, rush a shotgunner).
     pub held_weapon: Option<Weapon>,
+    /// Inline BSP model (`*N` in CS_MODELS) for brush entities — doors, plats, trains.
+    /// Feeds [`world::NavState::observe`]; `None` for everything else.
+    pub inline_model: Option<u32>,
+    /// A player's team, from its CTF skin (see [`crate::ctf`]). `None` in free-for-all.
+    pub team: Option<Team>,
+    /// The flag this player is carrying (`EF_FLAG1`/`EF_FLAG2`).
+    pub carrying: Option<Team>,
+    /// Animation frame (`s.frame`). A player's pain and death frames are the only health
+    /// signal the wire carries for anyone but us (see [`crate::opponent`]).
+    pub frame: i32,
+    /// How much this player has hurt us in past matches, `0.0..1.0` (see [`crate::rivals`]).
+    /// Zero for strangers and everything that is not a player; the caller marks it per frame.
+    pub threat: f32,
     pub last_seen_frame: i32,
     pub is_stale: bool,
     /// Previous frame's origin for velocity calculation.
@@ -70,8 +87,17 @@ pub struct PerceivedEntity {
     last_origin: Option<Vec3>,
 }
 
+impl PerceivedEntity {
+    /// Squared distance from `from`, shortened by [`Self::threat`]: a rival at full threat ranks
+    /// level with a stranger half as far away. Target pickers compare this instead of the raw
+    /// distance, so an unknown player still wins when they are much closer.
+    pub fn priority_dist_sq(&self, from: Vec3) -> f32 {
+        (self.origin - from).length_squared() / (1.0 + self.threat).powi(2)
+    }
+}
+
 /// The bot's own state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelfState {
     pub origin: Vec3,
     pub velocity: Vec3,
@@ -87,6 +113,10 @@ pub struct SelfState {
     /// an unrecognized view model. This is qbots' wire-visible proxy for Q3's "best owned
     /// weapon" (see [`crate::q3char`]).
     pub held_weapon: Option<Weapon>,
+    /// Our team, from our own `CS_PLAYERSKINS` skin. `None` in free-for-all.
+    pub team: Option<Team>,
+    /// The flag we are carrying, from our own entity's effects.
+    pub carrying: Option<Team>,
 }
 
 impl SelfState {
@@ -97,13 +127,14 @@ impl SelfState {
 }
 
 /// A complete worldview for one frame.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Worldview {
     pub frame_number: i32,
     pub self_state: SelfState,
     entities: Vec<PerceivedEntity>,
     /// Pre-built lookup: modelindex → EntityClass.
     #[allow(dead_code)]
+    #[serde(skip)]
     model_to_class: Vec<EntityClass>,
     /// Previous frame's health for detecting damage.
     prev_health: i32,
@@ -118,6 +149,8 @@ impl Worldview {
         let mut model_to_class = vec![EntityClass::Unknown; 256];
         // Parallel lookup for the VWep wield model (`modelindex2`) → enemy's held weapon (Plan 28).
         let mut model_to_weapon: Vec<Option<Weapon>> = vec![None; 256];
+        // And brush models: `*N` names inline BSP model N (doors, plats, trains).
+        let mut model_to_inline: Vec<Option<u32>> = vec![None; 256];
         for (i, model_str) in configstrings.iter() {
             if i < CS_MODELS {
                 continue;
@@ -130,6 +163,8 @@ impl Worldview {
                 if let Some(w) = Weapon::from_wield_model(model_str) {
                     model_to_weapon[modelindex] = Some(w);
                 }
+                model_to_inline[modelindex] =
+                    model_str.strip_prefix('*').and_then(|n| n.parse().ok());
             }
         }
 
@@ -143,11 +178,12 @@ impl Worldview {
                 .and_then(Weapon::from_view_model);
         }
         let self_entity = (playernum + 1) as i32;
+        self_state.team = player_skin(configstrings, self_entity).and_then(Team::from_skin);
 
         // Parse entities
         let mut entities: Vec<PerceivedEntity> = Vec::new();
         for entity_state in &frame.entities {
-            let class = if entity_state.number == self_entity {
+            let mut class = if entity_state.number == self_entity {
                 EntityClass::SelfPlayer
             } else if entity_state.modelindex == 255 {
                 // Q2 protocol sentinel: modelindex=255 means "use player skin from
@@ -159,6 +195,22 @@ impl Worldview {
                     .copied()
                     .unwrap_or(EntityClass::Unknown)
             };
+            let is_player = matches!(class, EntityClass::SelfPlayer | EntityClass::EnemyPlayer);
+            let team = if is_player {
+                player_skin(configstrings, entity_state.number).and_then(Team::from_skin)
+            } else {
+                None
+            };
+            // Same team skin as ours → a teammate (CTF / skin-teams DM).
+            if class == EntityClass::EnemyPlayer && team.is_some() && team == self_state.team {
+                class = EntityClass::AllyPlayer;
+            }
+            let carrying = is_player
+                .then(|| Team::carried(entity_state.effects))
+                .flatten();
+            if class == EntityClass::SelfPlayer {
+                self_state.carrying = carrying;
+            }
 
             let origin = Vec3::from(entity_state.origin);
             let prev = entities
@@ -195,6 +247,14 @@ impl Worldview {
                             .flatten()
                     })
                     .flatten(),
+                inline_model: model_to_inline
+                    .get(entity_state.modelindex as usize)
+                    .copied()
+                    .flatten(),
+                team,
+                carrying,
+                frame: entity_state.frame,
+                threat: 0.0,
                 last_seen_frame: frame.serverframe,
                 is_stale: false,
                 last_origin: Some(origin),
@@ -289,6 +349,27 @@ impl Worldview {
             .filter(|e| e.class == EntityClass::EnemyPlayer && !e.is_stale)
     }
 
+    /// Iterate over teammates (always empty in free-for-all).
+    pub fn allies(&self) -> impl Iterator<Item = &PerceivedEntity> {
+        self.entities
+            .iter()
+            .filter(|e| e.class == EntityClass::AllyPlayer && !e.is_stale)
+    }
+
+    /// `team`'s flag, if it is in view (on its pad or dropped; not while carried).
+    pub fn flag(&self, team: Team) -> Option<&PerceivedEntity> {
+        self.entities
+            .iter()
+            .find(|e| e.class == EntityClass::Flag(team) && !e.is_stale)
+    }
+
+    /// The player in view carrying `team`'s flag (never us — see [`SelfState::carrying`]).
+    pub fn flag_carrier(&self, team: Team) -> Option<&PerceivedEntity> {
+        self.entities
+            .iter()
+            .find(|e| e.carrying == Some(team) && e.class != EntityClass::SelfPlayer && !e.is_stale)
+    }
+
     /// Iterate over items.
     pub fn items(&self) -> impl Iterator<Item = &PerceivedEntity> {
         self.entities.iter().filter(|e| {
@@ -302,15 +383,15 @@ impl Worldview {
         })
     }
 
-    /// Find the nearest enemy within FOV.
+    /// Find the nearest enemy within FOV (by [`PerceivedEntity::priority_dist_sq`]).
     pub fn nearest_enemy(&self, fov_degrees: f32) -> Option<&PerceivedEntity> {
         let origin = self.self_state.origin;
 
         self.enemies()
             .filter(|e| self.in_fov(e.origin, fov_degrees))
             .min_by(|a, b| {
-                let da = (a.origin - origin).length_squared();
-                let db = (b.origin - origin).length_squared();
+                let da = a.priority_dist_sq(origin);
+                let db = b.priority_dist_sq(origin);
                 da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
             })
     }
@@ -329,8 +410,8 @@ impl Worldview {
             .filter(|e| self.in_fov(e.origin, fov_degrees))
             .filter(|e| crate::los::has_los_player(cm, eye, e.origin.into()))
             .min_by(|a, b| {
-                let da = (a.origin - self.self_state.origin).length_squared();
-                let db = (b.origin - self.self_state.origin).length_squared();
+                let da = a.priority_dist_sq(self.self_state.origin);
+                let db = b.priority_dist_sq(self.self_state.origin);
                 da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
             })
     }
@@ -423,21 +504,51 @@ impl SelfState {
             flags: ps.pmove.pm_flags as u32,
             // Resolved by `Worldview::from_frame` (needs the configstring model table).
             held_weapon: None,
+            team: None,
+            carrying: None,
         }
     }
 }
 
-/// A player's display name from their `CS_PLAYERSKINS` infostring. `entity_number`
+/// A player's display name from their `CS_PLAYERSKINS` string. `entity_number`
 /// is 1-based (the player's slot + 1, as carried in `svc_packetentities`). Used
 /// by the heatmap observer to attribute obituary deaths to a victim's name.
-/// Returns `None` for non-client entity numbers or unset skin strings.
+/// The server writes `name\model/skin` (`ClientUserinfoChanged`); an infostring
+/// with a `name` key is read too. Returns `None` for non-client entity numbers or
+/// unset skin strings.
 pub fn player_name(cs: &ConfigStrings, entity_number: i32) -> Option<String> {
     if !(1..=MAX_CLIENTS as i32).contains(&entity_number) {
         return None;
     }
-    let idx = CS_PLAYERSKINS + (entity_number - 1) as usize;
-    cs.get(idx)
-        .and_then(|info| infostring_value(info, "name").map(str::to_owned))
+    let info = cs
+        .get(CS_PLAYERSKINS + (entity_number - 1) as usize)?
+        .trim_start_matches('\\');
+    let name = match info.split_once('\\') {
+        Some((name, skin)) if !skin.contains('\\') => name,
+        _ => infostring_value(info, "name")?,
+    };
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Every player name in the `CS_PLAYERSKINS` table — who is on the server, in view or not.
+pub fn player_names(cs: &ConfigStrings) -> Vec<String> {
+    (1..=MAX_CLIENTS as i32)
+        .filter_map(|n| player_name(cs, n))
+        .collect()
+}
+
+/// A player's `model/skin` from their `CS_PLAYERSKINS` string. The server writes
+/// `name\model/skin` (`ClientUserinfoChanged`); an infostring with a `skin` key is read too.
+pub fn player_skin(cs: &ConfigStrings, entity_number: i32) -> Option<&str> {
+    if !(1..=MAX_CLIENTS as i32).contains(&entity_number) {
+        return None;
+    }
+    let info = cs.get(CS_PLAYERSKINS + (entity_number - 1) as usize)?;
+    infostring_value(info, "skin").or_else(|| {
+        info.trim_start_matches('\\')
+            .split_once('\\')
+            .map(|(_, skin)| skin)
+    })
 }
 
 /// Read one `\key\value\` pair out of a Q2 infostring. Handles both leading and
@@ -457,6 +568,13 @@ fn infostring_value<'a>(info: &'a str, key: &str) -> Option<&'a str> {
 /// Classify an entity based on its model string.
 fn classify_model(model_str: &str) -> Option<EntityClass> {
     let s = model_str.to_lowercase();
+    // CTF flags live under `players/` too (`players/male/flag1.md2`, `g_ctf.c`).
+    if s.ends_with("/flag1.md2") {
+        return Some(EntityClass::Flag(Team::Red));
+    }
+    if s.ends_with("/flag2.md2") {
+        return Some(EntityClass::Flag(Team::Blue));
+    }
     // Player models: "players/male/tris.md2", "players/female/tris.md2", etc.
     if s.starts_with("players/") {
         return Some(EntityClass::EnemyPlayer);
@@ -489,7 +607,11 @@ fn classify_model(model_str: &str) -> Option<EntityClass> {
 /// Plan 30 T4 refines ammo handling). Returns `None` for non-item classnames (spawns, triggers…).
 pub fn classify_item_classname(classname: &str) -> Option<EntityClass> {
     let s = classname.to_ascii_lowercase();
-    if s.starts_with("item_health") {
+    if s == "item_flag_team1" {
+        Some(EntityClass::Flag(Team::Red))
+    } else if s == "item_flag_team2" {
+        Some(EntityClass::Flag(Team::Blue))
+    } else if s.starts_with("item_health") {
         Some(EntityClass::ItemHealth)
     } else if s.starts_with("item_armor") {
         Some(EntityClass::ItemArmor)
@@ -600,6 +722,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn brush_entities_resolve_their_inline_model() {
+        use q2proto::{EntityState, Frame};
+        let mut cs = ConfigStrings::default();
+        cs.set(CS_MODELS + 1, "maps/q2dm1.bsp");
+        cs.set(CS_MODELS + 2, "*7");
+        cs.set(CS_MODELS + 3, "models/items/armor/body/tris.md2");
+        let frame = Frame {
+            entities: (2..=3)
+                .map(|m| EntityState {
+                    number: m,
+                    modelindex: m,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let view = Worldview::from_frame(&frame, &cs, 0);
+        let inline: Vec<_> = view.entities().map(|e| e.inline_model).collect();
+        assert_eq!(inline, vec![Some(7), None]);
+    }
+
     #[test]
     fn player_name_from_skin_infostring() {
         let mut cs = ConfigStrings::default();
@@ -611,6 +755,10 @@ mod tests {
         cs.set(CS_PLAYERSKINS + 1, "\\name\\Foe\\skin\\female/cyborg");
         assert_eq!(player_name(&cs, 2).as_deref(), Some("Foe"));
 
+        // What the server actually sends: `name\model/skin`.
+        cs.set(CS_PLAYERSKINS + 2, "Regular\\female/athena");
+        assert_eq!(player_name(&cs, 3).as_deref(), Some("Regular"));
+
         // Out-of-range / unset → None.
         assert_eq!(player_name(&cs, 0), None);
         assert_eq!(player_name(&cs, -1), None);
@@ -9,6 +9,10 @@
 //! **Contract:** [`Persona::default`] / [`Persona::from_bot_skill`] reproduce `main`'s pre-Plan-27
 //! constants **exactly** (30 / 50 / 450 / 50-or-250), so converting `main` to read a persona is
 //! behavior-preserving for every existing bot; only the opt-in named presets differ.
+//!
+//! The fleet no longer picks presets by name here: it loads personas from the data files in
+//! `characters/main/` (see qbots `characters.rs`). [`Persona::rusher`] & co. stay as the reference
+//! values those shipped files carry.
 
 use crate::skill::BotSkill;
 use crate::weapons::Weapon;
@@ -17,8 +21,6 @@ use crate::weapons::Weapon;
 /// values from. Additive — later plans (29 chase, 30 items) consume `chase_commit`/`item_greed`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Persona {
-    /// Display label (preset name) for logs/scoreboards.
-    pub name: &'static str,
     /// How eagerly it presses a fight (high → closes distance, fights on).
     pub aggression: f32,
     /// Tolerance for fighting hurt before disengaging (high → low flee/kite thresholds).
@@ -31,6 +33,9 @@ pub struct Persona {
     pub chase_commit: f32,
     /// How much it detours for items (Plan 30 consumer). `[0,1]`.
     pub item_greed: f32,
+    /// Preference for dark spots when picking where to dwell (ambush in the shadows). `[0,1]`;
+    /// 0 = ignores light.
+    pub shadow_pref: f32,
 }
 
 impl Default for Persona {
@@ -38,13 +43,13 @@ impl Default for Persona {
     /// reproduce `main`'s pre-Plan-27 constants exactly.
     fn default() -> Self {
         Self {
-            name: "default",
             aggression: 0.5,
             risk_tolerance: 0.5,
             weapon_pref: None,
             camper: false,
             chase_commit: 0.5,
             item_greed: 0.5,
+            shadow_pref: 0.0,
         }
     }
 }
@@ -53,7 +58,7 @@ impl Persona {
     /// Derive the persona from a bot's [`BotSkill`], **preserving today's behavior exactly**:
     /// pre-Plan-27 `main` used global tactical consts regardless of skill/personality, so the
     /// traits are the neutral 0.5 defaults; only `camper` carries over (it already drove roam
-    /// dwell). Named presets ([`Persona::preset`]) are where real differentiation lives.
+    /// dwell). Named personas (`characters/main/*.yaml`) are where real differentiation lives.
     pub fn from_bot_skill(skill: &BotSkill) -> Self {
         Self {
             camper: skill.camper,
@@ -88,70 +93,61 @@ impl Persona {
         }
     }
 
-    /// Resolve a named preset (`--persona <name>`), or `None` for an unknown name.
-    pub fn preset(name: &str) -> Option<Self> {
-        Some(match name.to_ascii_lowercase().as_str() {
-            "rusher" => Self::rusher(),
-            "sniper" => Self::sniper(),
-            "scavenger" => Self::scavenger(),
-            "guard" => Self::guard(),
-            "default" | "balanced" => Self::default(),
-            _ => return None,
-        })
+    /// How many upcoming roam stops a dwell compares, settling on the darkest (1 = just take
+    /// the next one). Scales with [`shadow_pref`](Self::shadow_pref): 0 → 1, 1 → 5.
+    pub fn shadow_candidates(&self) -> usize {
+        1 + (self.shadow_pref.clamp(0.0, 1.0) * 4.0).round() as usize
     }
 
-    /// All selectable preset names (for CLI help / competition matrices).
-    pub const PRESET_NAMES: [&'static str; 4] = ["rusher", "sniper", "scavenger", "guard"];
-
     /// Closes distance, fights hurt, chases kills; shotgun bias.
     pub fn rusher() -> Self {
         Self {
-            name: "rusher",
             aggression: 0.9,
             risk_tolerance: 0.8,
             weapon_pref: Some(Weapon::SuperShotgun),
             camper: false,
             chase_commit: 0.9,
             item_greed: 0.4,
+            shadow_pref: 0.0,
         }
     }
 
     /// Holds range, bails early, patient; railgun bias.
     pub fn sniper() -> Self {
         Self {
-            name: "sniper",
             aggression: 0.2,
             risk_tolerance: 0.3,
             weapon_pref: Some(Weapon::Railgun),
             camper: false,
             chase_commit: 0.3,
             item_greed: 0.3,
+            shadow_pref: 0.6,
         }
     }
 
     /// Hoards items; moderate fighter.
     pub fn scavenger() -> Self {
         Self {
-            name: "scavenger",
             aggression: 0.4,
             risk_tolerance: 0.5,
             weapon_pref: None,
             camper: false,
             chase_commit: 0.4,
             item_greed: 0.95,
+            shadow_pref: 0.0,
         }
     }
 
     /// Camps a spot, holds position, rarely chases.
     pub fn guard() -> Self {
         Self {
-            name: "guard",
             aggression: 0.5,
             risk_tolerance: 0.4,
             weapon_pref: None,
             camper: true,
             chase_commit: 0.2,
             item_greed: 0.3,
+            shadow_pref: 0.9,
         }
     }
 }
@@ -292,14 +288,4 @@ mod tests {
         // A risk-tolerant rusher is less danger-averse than a cautious sniper at the same mood.
         assert!(Persona::rusher().heatmap_scale(mood).0 < Persona::sniper().heatmap_scale(mood).0);
     }
-
-    #[test]
-    fn preset_lookup_is_case_insensitive_and_bounded() {
-        assert_eq!(Persona::preset("RUSHER").unwrap().name, "rusher");
-        assert!(Persona::preset("nonesuch").is_none());
-        // Every advertised preset name resolves.
-        for n in Persona::PRESET_NAMES {
-            assert!(Persona::preset(n).is_some(), "{n} must resolve");
-        }
-    }
 }
@@ -23,6 +23,9 @@
 //! a *different shape* (named `[0,1]` traits, per-weapon accuracy, firethrottle/alertness
 //! texture) layered alongside — it does not replace `BotSkill`, so `MainBrain` stays byte-
 //! identical and the Q3 brain can reuse the shared combat modules while adding Q3 texture.
+//!
+//! The named characters a fleet fields are data (`characters/q3/*.yaml`, loaded by qbots);
+//! [`Q3Character::grunt`] & co. are the reference values the shipped files carry.
 
 use crate::perception::Worldview;
 use crate::skill::SkillLevel;
@@ -102,6 +105,25 @@ impl Q3Character {
         (50.0 - (self.aggression - 0.5) * 40.0).clamp(10.0, 90.0)
     }
 
+    /// Rocket-jumps ledges (nav [`world::EdgeKind::RocketJump`] edges): a confident shooter
+    /// (`attack_skill ≥ 0.7`) who is either a crack aim or a habitual jumper (`aim_skill` or
+    /// `jumper ≥ 0.8`). Major and Sarge qualify; Grunt and Camper walk.
+    pub fn rocket_jumper(&self) -> bool {
+        self.attack_skill >= 0.7 && self.aim_skill.max(self.jumper) >= 0.8
+    }
+
+    /// The mouse hand for [`BrainConfig::human_aim`](crate::BrainConfig::human_aim): stroke speed
+    /// from [`Self::aim_skill`], overshoot and endpoint scatter from [`Self::aim_accuracy`].
+    pub fn mouse_params(&self) -> crate::mouse::MouseParams {
+        let hand = crate::mouse::MouseParams::from_skill(self.aim_skill);
+        let eye = crate::mouse::MouseParams::from_skill(self.aim_accuracy);
+        crate::mouse::MouseParams {
+            overshoot: eye.overshoot,
+            endpoint_noise: eye.endpoint_noise,
+            ..hand
+        }
+    }
+
     /// Map a master skill level `[0,10]` to a monotonic `Q3Character` (à la Eraser's
     /// `AdjustRatingsToSkill`). Higher skill → higher aim accuracy/skill/attack_skill and
     /// alertness/self-preservation, lower reaction time, lower firethrottle (less spray).
@@ -224,61 +246,6 @@ impl Default for Q3Character {
     }
 }
 
-/// A selectable named Q3 personality (Plan 38 roster). A clap `ValueEnum` so `--q3char <name>`
-/// picks one; each maps to a [`Q3Character`] preset and carries a stable [`tag`](Self::tag) for
-/// per-character bot names + scoreboard grouping.
-/// Each variant's canonical CLI token is its short code (`gru`, `maj`, …) — the same code the
-/// competition scoreboard/bot-names use — with the long name kept as an accepted alias, so
-/// both `--q3char gru` and `--q3char grunt` work. The `--help` line leads with the short code.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
-pub enum CharPreset {
-    /// grunt — Low skill, high spray, weak aim ([`Q3Character::grunt`]).
-    #[value(name = "gru", alias = "grunt")]
-    Grunt,
-    /// major — High aim skill, precise, low firethrottle ([`Q3Character::major`]).
-    #[value(name = "maj", alias = "major")]
-    Major,
-    /// sarge — High aggression + jumper, mobile brawler ([`Q3Character::sarge`]).
-    #[value(name = "sar", alias = "sarge")]
-    Sarge,
-    /// camper — High camper/alertness, low aggression ([`Q3Character::camper`]).
-    #[value(name = "cam", alias = "camper")]
-    Camper,
-}
-
-impl CharPreset {
-    /// The `Q3Character` this preset selects.
-    pub fn character(self) -> Q3Character {
-        match self {
-            CharPreset::Grunt => Q3Character::grunt(),
-            CharPreset::Major => Q3Character::major(),
-            CharPreset::Sarge => Q3Character::sarge(),
-            CharPreset::Camper => Q3Character::camper(),
-        }
-    }
-
-    /// Stable kebab tag for names / scoreboard grouping.
-    pub fn tag(self) -> &'static str {
-        match self {
-            CharPreset::Grunt => "grunt",
-            CharPreset::Major => "major",
-            CharPreset::Sarge => "sarge",
-            CharPreset::Camper => "camper",
-        }
-    }
-
-    /// A distinct Q2 player skin (`model/skin`) per character, so the roster is visually
-    /// recognizable in-game and on the scoreboard.
-    pub fn skin(self) -> &'static str {
-        match self {
-            CharPreset::Grunt => "male/grunt",
-            CharPreset::Major => "male/major",
-            CharPreset::Sarge => "male/sarge",
-            CharPreset::Camper => "female/athena",
-        }
-    }
-}
-
 /// Does the **held** weapon have enough ammo to count toward aggression? Thresholds mirror
 /// the Q3 ladder (`ai_dmq3.c:2199`, distilled §2), read against the only ammo we see —
 /// `STAT_AMMO`, the held weapon's count. Weapons that are never a "real" aggression weapon
@@ -552,30 +519,6 @@ mod tests {
         assert!((neutral.retreat_threshold() - 50.0).abs() < 1e-3);
     }
 
-    #[test]
-    fn presets_map_to_characters_and_tags() {
-        use clap::ValueEnum;
-        // Long names (aliases) and short codes (canonical CLI tokens) both parse.
-        assert_eq!(CharPreset::from_str("grunt", true), Ok(CharPreset::Grunt));
-        assert_eq!(CharPreset::from_str("major", true), Ok(CharPreset::Major));
-        assert_eq!(CharPreset::from_str("gru", true), Ok(CharPreset::Grunt));
-        assert_eq!(CharPreset::from_str("maj", true), Ok(CharPreset::Major));
-        assert_eq!(CharPreset::from_str("sar", true), Ok(CharPreset::Sarge));
-        assert_eq!(CharPreset::from_str("cam", true), Ok(CharPreset::Camper));
-        assert!(CharPreset::from_str("nope", true).is_err());
-        assert_eq!(CharPreset::Sarge.character(), Q3Character::sarge());
-        assert_eq!(CharPreset::Camper.tag(), "camper");
-        // Each preset has a distinct skin.
-        let skins = [
-            CharPreset::Grunt.skin(),
-            CharPreset::Major.skin(),
-            CharPreset::Sarge.skin(),
-            CharPreset::Camper.skin(),
-        ];
-        let unique: std::collections::HashSet<_> = skins.iter().collect();
-        assert_eq!(unique.len(), 4, "distinct skins per character");
-    }
-
     #[test]
     fn weapon_accuracy_falls_back_to_base() {
         let ch = Q3Character::major();
@@ -589,4 +532,14 @@ mod tests {
         assert_eq!(ch2.weapon_accuracy(Weapon::Railgun), 0.99);
         assert_eq!(ch2.weapon_accuracy(Weapon::Blaster), 0.5);
     }
+
+    #[test]
+    fn rocket_jumpers_are_the_skilled_presets() {
+        assert!(Q3Character::major().rocket_jumper());
+        assert!(Q3Character::sarge().rocket_jumper());
+        assert!(!Q3Character::grunt().rocket_jumper());
+        assert!(!Q3Character::camper().rocket_jumper());
+        assert!(Q3Character::from_skill(10).rocket_jumper());
+        assert!(!Q3Character::from_skill(5).rocket_jumper());
+    }
 }
@@ -131,13 +131,17 @@ pub fn find_best_direction(cm: &CollisionModel, origin: Vec3, view_yaw: f32) ->
     let lifted = [origin.x, origin.y, origin.z + STEPSIZE];
     let mut best: Option<(f32, f32)> = None;
 
-    for &offset in &OFFSETS_DEG {
+    let ends = OFFSETS_DEG.map(|offset| {
+        let r = (view_yaw + offset).to_radians();
+        [
+            lifted[0] + r.cos() * TRACE_DIST,
+            lifted[1] + r.sin() * TRACE_DIST,
+            lifted[2],
+        ]
+    });
+    let traces = cm.trace_many(&lifted, &ends, &HULL_MINS, &HULL_MAXS, MASK_SOLID);
+    for (&offset, t) in OFFSETS_DEG.iter().zip(&traces) {
         let yaw = view_yaw + offset;
-        let r = yaw.to_radians();
-        let dir = [r.cos() * TRACE_DIST, r.sin() * TRACE_DIST, 0.0];
-        let end = [lifted[0] + dir[0], lifted[1] + dir[1], lifted[2] + dir[2]];
-
-        let t = cm.trace(&lifted, &end, &HULL_MINS, &HULL_MAXS, MASK_SOLID);
         if t.startsolid {
             continue;
         }
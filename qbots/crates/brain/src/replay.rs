@@ -0,0 +1,874 @@
+//! Brain replay — capture exactly what a brain was fed, and feed it again offline.
+//!
+//! A bot misbehaving on a live server is hard to catch under a debugger: by the time it is
+//! attached the moment has passed. A capture records everything a brain consumes — each tick's
+//! [`Worldview`], `dt`, tick counter and goal override, the `set_map`/`on_kill`/`on_death`
+//! hooks in order, and the seed it was built with — plus what it answered (its
+//! [`BrainOutput`] and `status`). [`replay`] runs a freshly built brain through the same
+//! events and reports the first tick whose output differs, compared as serialized JSON (so
+//! down to the last bit of every float).
+//!
+//! The navigator is taped rather than rebuilt: [`tick_taped`] wraps the live one and records
+//! each call the brain makes with its arguments and answer, and the replay plays those answers
+//! back. A replay therefore doesn't need the nav backend to behave identically — the heatmap
+//! overlay, door state and danger feed the bot loop pushes into it are baked into the answers —
+//! and a brain that starts asking its navigator different questions diverges visibly. The
+//! collision model and [`BrainMap`] are rebuilt from the map (the capture names it and its
+//! nav fingerprint); they are pure functions of the BSP.
+//!
+//! # File format
+//!
+//! JSON lines. Line 1 is a [`CaptureHeader`]; every further line is one [`Event`]:
+//!
+//! ```text
+//! {"bot":"xon_1","brain":"xon","seed":1377,"combat":true,"started":1760000000}
+//! {"map":{"name":"q2dm1","fingerprint":1234,"roam_as_position":false}}
+//! {"tick":{"view":{...},"dt":0.1,"ticks":12,"cm":true,"nav":[{"set_goal":...}],"out":{...},"status":"xon-item"}}
+//! "death"
+//! ```
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use world::{CollisionModel, RideInfo, RocketJumpInfo};
+
+use crate::brains::core::{Brain, BrainContext, BrainMap, BrainOutput};
+use crate::nav::NavGoal;
+use crate::nav_mode::Navigator;
+use crate::perception::Worldview;
+
+/// Flush the file every this many events, so a killed bot loses at most a second of ticks.
+const FLUSH_EVERY: u32 = 10;
+
+/// The first line of a capture: enough to build the same brain again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureHeader {
+    pub bot: String,
+    /// [`brain_tag`](crate::brain_tag) of the captured brain.
+    pub brain: String,
+    /// The character file it played, by name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub character: Option<String>,
+    /// That character's traits as the caller serialized them, so a replay builds the character
+    /// the bot had even if the file has been edited since. Opaque to this module.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traits: Option<String>,
+    /// [`BrainConfig::seed`](crate::BrainConfig::seed) it was built with.
+    pub seed: u32,
+    /// [`BrainConfig::combat_enabled`](crate::BrainConfig::combat_enabled).
+    pub combat: bool,
+    /// [`BrainConfig::human_aim`](crate::BrainConfig::human_aim); absent in older captures.
+    #[serde(default)]
+    pub human_aim: bool,
+    /// Unix seconds when the capture was started.
+    pub started: u64,
+}
+
+/// The map a `set_map` event handed over. The replay rebuilds the [`BrainMap`] from it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MapRef {
+    pub name: String,
+    /// The map's nav fingerprint, so a replay can refuse a different build of the same name.
+    pub fingerprint: u64,
+    pub roam_as_position: bool,
+}
+
+/// One brain tick: its inputs, the navigator's side of the conversation, and its answer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tick {
+    pub view: Worldview,
+    pub dt: f32,
+    pub ticks: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal_override: Option<NavGoal>,
+    /// Whether the tick had the map's collision model.
+    pub cm: bool,
+    /// Every navigator call the brain made, in order; `None` when it had no navigator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nav: Option<Vec<NavCall>>,
+    pub out: BrainOutput,
+    pub status: String,
+}
+
+/// Something a brain was handed, in the order it was handed it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Event {
+    Map(MapRef),
+    Tick(Box<Tick>),
+    Kill,
+    Death,
+}
+
+/// One call a brain made on its navigator, with the arguments it passed and what it got back.
+///
+/// `set_risk_overlay`, `note_dangers` and `note_nav_state` aren't taped: the bot loop, not the
+/// brain, makes them, and their effect shows up in the answers that are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavCall {
+    SetGoal { goal: NavGoal, from: Vec3 },
+    Update { pos: Vec3, cm: bool, reached: bool },
+    PursueTarget { from: Vec3, target: Option<Vec3> },
+    PursueTargetSafe { from: Vec3, target: Option<Vec3> },
+    EdgeIsJump(bool),
+    EdgeIsSwim(bool),
+    EdgeIsRide(bool),
+    RideInfo(Option<RideInfo>),
+    RocketJumpAllowed(bool),
+    RocketJump(Option<RocketJumpInfo>),
+    ForceReplan,
+    Blacklist { pos: Vec3 },
+    Waypoint(Option<usize>),
+    WaypointPos(Option<[f32; 3]>),
+    PathAhead { max: usize, path: Vec<[f32; 3]> },
+    Smooth { from: Vec3 },
+    GoalAbandoned(bool),
+    SpeedScale { pos: Vec3, scale: f32 },
+}
+
+/// Run one `brain.tick(ctx)` with the navigator taped. Returns what the brain decided and the
+/// [`Event`] that reproduces it.
+pub fn tick_taped(brain: &mut dyn Brain, ctx: BrainContext) -> (BrainOutput, Event) {
+    let BrainContext {
+        view,
+        nav,
+        cm,
+        dt,
+        ticks,
+        goal_override,
+    } = ctx;
+    let mut tape = nav.map(|inner| NavTape {
+        inner,
+        calls: RefCell::new(Vec::new()),
+    });
+    let out = brain.tick(BrainContext {
+        view,
+        nav: tape.as_mut().map(|t| t as &mut dyn Navigator),
+        cm,
+        dt,
+        ticks,
+        goal_override: goal_override.clone(),
+    });
+    let tick = Tick {
+        view: view.clone(),
+        dt,
+        ticks,
+        goal_override,
+        cm: cm.is_some(),
+        nav: tape.map(|t| t.calls.into_inner()),
+        out,
+        status: brain.status().to_string(),
+    };
+    (out, Event::Tick(Box::new(tick)))
+}
+
+/// Streams [`Event`]s to a capture file.
+pub struct CaptureWriter {
+    out: BufWriter<File>,
+    unflushed: u32,
+}
+
+impl CaptureWriter {
+    /// Create `path` (and its directory) and write the header line.
+    pub fn create(path: &Path, header: &CaptureHeader) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut w = Self {
+            out: BufWriter::new(File::create(path)?),
+            unflushed: 0,
+        };
+        serde_json::to_writer(&mut w.out, header)?;
+        w.out.write_all(b"\n")?;
+        w.out.flush()?;
+        Ok(w)
+    }
+
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, event)?;
+        self.out.write_all(b"\n")?;
+        self.unflushed += 1;
+        // A map change is rare and everything after it depends on it: don't lose it.
+        if self.unflushed >= FLUSH_EVERY || matches!(event, Event::Map(_)) {
+            self.unflushed = 0;
+            self.out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// A capture file, read back.
+#[derive(Debug, Clone)]
+pub struct Capture {
+    pub header: CaptureHeader,
+    pub events: Vec<Event>,
+}
+
+impl Capture {
+    /// Read a capture file. A torn last line (the bot was killed mid-write) is dropped.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        let first = lines
+            .next()
+            .ok_or_else(|| format!("{}: empty capture", path.display()))?
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let header: CaptureHeader = serde_json::from_str(&first)
+            .map_err(|e| format!("{}: bad header: {e}", path.display()))?;
+        let lines: Vec<String> = lines
+            .collect::<io::Result<_>>()
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut events = Vec::with_capacity(lines.len());
+        for (k, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(e) => events.push(e),
+                Err(_) if k + 1 == lines.len() => break,
+                Err(e) => return Err(format!("{}:{}: {e}", path.display(), k + 2)),
+            }
+        }
+        Ok(Self { header, events })
+    }
+}
+
+/// The first tick a replay disagreed with its capture on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    /// Index of the tick among the capture's ticks.
+    pub tick: usize,
+    /// Its [`BrainContext::ticks`] counter.
+    pub ticks: u32,
+    /// What differed, captured value first.
+    pub what: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tick {} (ticks={}): {}",
+            self.tick, self.ticks, self.what
+        )
+    }
+}
+
+/// How a replay went.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Ticks replayed.
+    pub ticks: usize,
+    /// Ticks whose output (or navigator calls) differed from the capture.
+    pub diverged: usize,
+    pub first: Option<Divergence>,
+}
+
+impl ReplayReport {
+    pub fn identical(&self) -> bool {
+        self.diverged == 0
+    }
+}
+
+/// Feed `events` to `brain` and diff every tick's output against the captured one.
+///
+/// `load_map` turns a [`MapRef`] back into the `BrainMap` the brain was given; its collision
+/// model is what the following ticks trace against. An error from it, or a tick that had a
+/// collision model when the map gave none, stops the replay — it couldn't be faithful.
+pub fn replay(
+    brain: &mut dyn Brain,
+    events: &[Event],
+    mut load_map: impl FnMut(&MapRef) -> Result<BrainMap, String>,
+) -> Result<ReplayReport, String> {
+    let mut cm: Option<Arc<CollisionModel>> = None;
+    let mut report = ReplayReport::default();
+    for event in events {
+        let tick = match event {
+            Event::Map(m) => {
+                let map = load_map(m)?;
+                cm = map.cm.clone();
+                brain.set_map(map);
+                continue;
+            }
+            Event::Kill => {
+                brain.on_kill();
+                continue;
+            }
+            Event::Death => {
+                brain.on_death();
+                continue;
+            }
+            Event::Tick(t) => t,
+        };
+        let tick_cm = match (tick.cm, &cm) {
+            (false, _) => None,
+            (true, Some(cm)) => Some(&**cm),
+            (true, None) => {
+                return Err(format!(
+                    "tick {} traced the map but the replay has no collision model",
+                    report.ticks
+                ))
+            }
+        };
+        let mut playback = tick.nav.as_ref().map(|calls| NavPlayback::new(calls));
+        let out = brain.tick(BrainContext {
+            view: &tick.view,
+            nav: playback.as_mut().map(|p| p as &mut dyn Navigator),
+            cm: tick_cm,
+            dt: tick.dt,
+            ticks: tick.ticks,
+            goal_override: tick.goal_override.clone(),
+        });
+        let want = answer(&tick.out, &tick.status);
+        let got = answer(&out, brain.status());
+        let what = if want != got {
+            Some(format!("output\n  captured {want}\n  replayed {got}"))
+        } else {
+            playback.and_then(NavPlayback::finish)
+        };
+        if let Some(what) = what {
+            report.diverged += 1;
+            report.first.get_or_insert(Divergence {
+                tick: report.ticks,
+                ticks: tick.ticks,
+                what,
+            });
+        }
+        report.ticks += 1;
+    }
+    Ok(report)
+}
+
+fn answer(out: &BrainOutput, status: &str) -> String {
+    serde_json::to_string(&(out, status)).unwrap_or_default()
+}
+
+/// Forwards to the live navigator and writes down every exchange.
+struct NavTape<'a> {
+    inner: &'a mut dyn Navigator,
+    calls: RefCell<Vec<NavCall>>,
+}
+
+impl NavTape<'_> {
+    fn log(&self, call: NavCall) {
+        self.calls.borrow_mut().push(call);
+    }
+}
+
+impl Navigator for NavTape<'_> {
+    fn set_goal(&mut self, goal: NavGoal, from: Vec3) {
+        self.log(NavCall::SetGoal {
+            goal: goal.clone(),
+            from,
+        });
+        self.inner.set_goal(goal, from);
+    }
+
+    fn update(&mut self, pos: Vec3, cm: Option<&CollisionModel>) -> bool {
+        let reached = self.inner.update(pos, cm);
+        self.log(NavCall::Update {
+            pos,
+            cm: cm.is_some(),
+            reached,
+        });
+        reached
+    }
+
+    fn pursue_target(&self, from: Vec3) -> Option<Vec3> {
+        let target = self.inner.pursue_target(from);
+        self.log(NavCall::PursueTarget { from, target });
+        target
+    }
+
+    fn pursue_target_safe(&self, from: Vec3, cm: &CollisionModel) -> Option<Vec3> {
+        let target = self.inner.pursue_target_safe(from, cm);
+        self.log(NavCall::PursueTargetSafe { from, target });
+        target
+    }
+
+    fn current_edge_is_jump(&self) -> bool {
+        let b = self.inner.current_edge_is_jump();
+        self.log(NavCall::EdgeIsJump(b));
+        b
+    }
+
+    fn current_edge_is_swim(&self) -> bool {
+        let b = self.inner.current_edge_is_swim();
+        self.log(NavCall::EdgeIsSwim(b));
+        b
+    }
+
+    fn current_edge_is_ride(&self) -> bool {
+        let b = self.inner.current_edge_is_ride();
+        self.log(NavCall::EdgeIsRide(b));
+        b
+    }
+
+    fn current_ride_info(&self) -> Option<RideInfo> {
+        let r = self.inner.current_ride_info();
+        self.log(NavCall::RideInfo(r));
+        r
+    }
+
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.log(NavCall::RocketJumpAllowed(allowed));
+        self.inner.set_rocket_jump_allowed(allowed);
+    }
+
+    fn current_rocket_jump(&self) -> Option<RocketJumpInfo> {
+        let r = self.inner.current_rocket_jump();
+        self.log(NavCall::RocketJump(r));
+        r
+    }
+
+    fn force_replan(&mut self) {
+        self.log(NavCall::ForceReplan);
+        self.inner.force_replan();
+    }
+
+    fn blacklist_waypoint_if_blocked(&mut self, pos: Vec3, cm: &CollisionModel) {
+        self.log(NavCall::Blacklist { pos });
+        self.inner.blacklist_waypoint_if_blocked(pos, cm);
+    }
+
+    fn current_waypoint(&self) -> Option<usize> {
+        let w = self.inner.current_waypoint();
+        self.log(NavCall::Waypoint(w));
+        w
+    }
+
+    fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
+        let p = self.inner.current_waypoint_pos();
+        self.log(NavCall::WaypointPos(p));
+        p
+    }
+
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        let path = self.inner.path_ahead(max);
+        self.log(NavCall::PathAhead {
+            max,
+            path: path.clone(),
+        });
+        path
+    }
+
+    fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
+        self.log(NavCall::Smooth { from });
+        self.inner.smooth_with_cm(cm, from);
+    }
+
+    fn set_risk_overlay(&mut self, overlay: Vec<f32>) {
+        self.inner.set_risk_overlay(overlay);
+    }
+
+    fn goal_abandoned(&self) -> bool {
+        let b = self.inner.goal_abandoned();
+        self.log(NavCall::GoalAbandoned(b));
+        b
+    }
+
+    fn speed_scale(&self, pos: Vec3) -> f32 {
+        let scale = self.inner.speed_scale(pos);
+        self.log(NavCall::SpeedScale { pos, scale });
+        scale
+    }
+
+    fn note_dangers(&mut self, dangers: &[crate::nav_mode::DangerSource]) {
+        self.inner.note_dangers(dangers);
+    }
+
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.inner.note_nav_state(state);
+    }
+
+    fn backend(&self) -> &'static str {
+        self.inner.backend()
+    }
+}
+
+/// Answers a brain's navigator calls from a tape. The first call that doesn't match the tape
+/// (a different method, or the same one with different arguments) is remembered; from then on
+/// the brain gets neutral answers, since it has left the captured run anyway.
+struct NavPlayback<'a> {
+    calls: &'a [NavCall],
+    next: Cell<usize>,
+    miss: RefCell<Option<String>>,
+}
+
+impl<'a> NavPlayback<'a> {
+    fn new(calls: &'a [NavCall]) -> Self {
+        Self {
+            calls,
+            next: Cell::new(0),
+            miss: RefCell::new(None),
+        }
+    }
+
+    /// The taped answer to the brain's next call, if `matches` accepts the taped call.
+    fn take<T>(&self, asked: &str, matches: impl FnOnce(&NavCall) -> Option<T>) -> Option<T> {
+        if self.miss.borrow().is_some() {
+            return None;
+        }
+        let k = self.next.get();
+        self.next.set(k + 1);
+        let taped = self.calls.get(k);
+        let answer = taped.and_then(matches);
+        if answer.is_none() {
+            let taped = taped.map_or("nothing".to_string(), |c| format!("{c:?}"));
+            *self.miss.borrow_mut() = Some(format!(
+                "navigator call #{k}\n  captured {taped}\n  replayed {asked}"
+            ));
+        }
+        answer
+    }
+
+    /// What went wrong, if anything: a mismatched call, or taped calls the brain never made.
+    fn finish(self) -> Option<String> {
+        let k = self.next.get();
+        self.miss.into_inner().or_else(|| {
+            (k < self.calls.len()).then(|| {
+                format!(
+                    "navigator call #{k}\n  captured {:?}\n  replayed nothing",
+                    self.calls[k]
+                )
+            })
+        })
+    }
+}
+
+impl Navigator for NavPlayback<'_> {
+    fn set_goal(&mut self, goal: NavGoal, from: Vec3) {
+        self.take(&format!("set_goal({goal:?}, {from})"), |c| match c {
+            NavCall::SetGoal { goal: g, from: f } if *g == goal && *f == from => Some(()),
+            _ => None,
+        });
+    }
+
+    fn update(&mut self, pos: Vec3, cm: Option<&CollisionModel>) -> bool {
+        let has_cm = cm.is_some();
+        self.take(&format!("update({pos}, cm={has_cm})"), |c| match c {
+            NavCall::Update {
+                pos: p,
+                cm,
+                reached,
+            } if *p == pos && *cm == has_cm => Some(*reached),
+            _ => None,
+        })
+        .unwrap_or(false)
+    }
+
+    fn pursue_target(&self, from: Vec3) -> Option<Vec3> {
+        self.take(&format!("pursue_target({from})"), |c| match c {
+            NavCall::PursueTarget { from: f, target } if *f == from => Some(*target),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn pursue_target_safe(&self, from: Vec3, _cm: &CollisionModel) -> Option<Vec3> {
+        self.take(&format!("pursue_target_safe({from})"), |c| match c {
+            NavCall::PursueTargetSafe { from: f, target } if *f == from => Some(*target),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn current_edge_is_jump(&self) -> bool {
+        self.take("current_edge_is_jump()", |c| match c {
+            NavCall::EdgeIsJump(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+    }
+
+    fn current_edge_is_swim(&self) -> bool {
+        self.take("current_edge_is_swim()", |c| match c {
+            NavCall::EdgeIsSwim(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+    }
+
+    fn current_edge_is_ride(&self) -> bool {
+        self.take("current_edge_is_ride()", |c| match c {
+            NavCall::EdgeIsRide(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+    }
+
+    fn current_ride_info(&self) -> Option<RideInfo> {
+        self.take("current_ride_info()", |c| match c {
+            NavCall::RideInfo(r) => Some(*r),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.take(&format!("set_rocket_jump_allowed({allowed})"), |c| {
+            (*c == NavCall::RocketJumpAllowed(allowed)).then_some(())
+        });
+    }
+
+    fn current_rocket_jump(&self) -> Option<RocketJumpInfo> {
+        self.take("current_rocket_jump()", |c| match c {
+            NavCall::RocketJump(r) => Some(*r),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn force_replan(&mut self) {
+        self.take("force_replan()", |c| {
+            (*c == NavCall::ForceReplan).then_some(())
+        });
+    }
+
+    fn blacklist_waypoint_if_blocked(&mut self, pos: Vec3, _cm: &CollisionModel) {
+        self.take(&format!("blacklist_waypoint_if_blocked({pos})"), |c| {
+            (*c == NavCall::Blacklist { pos }).then_some(())
+        });
+    }
+
+    fn current_waypoint(&self) -> Option<usize> {
+        self.take("current_waypoint()", |c| match c {
+            NavCall::Waypoint(w) => Some(*w),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
+        self.take("current_waypoint_pos()", |c| match c {
+            NavCall::WaypointPos(p) => Some(*p),
+            _ => None,
+        })
+        .flatten()
+    }
+
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        self.take(&format!("path_ahead({max})"), |c| match c {
+            NavCall::PathAhead { max: m, path } if *m == max => Some(path.clone()),
+            _ => None,
+        })
+        .unwrap_or_default()
+    }
+
+    fn smooth_with_cm(&mut self, _cm: &CollisionModel, from: Vec3) {
+        self.take(&format!("smooth_with_cm({from})"), |c| {
+            (*c == NavCall::Smooth { from }).then_some(())
+        });
+    }
+
+    fn goal_abandoned(&self) -> bool {
+        self.take("goal_abandoned()", |c| match c {
+            NavCall::GoalAbandoned(b) => Some(*b),
+            _ => None,
+        })
+        .unwrap_or(false)
+    }
+
+    fn speed_scale(&self, pos: Vec3) -> f32 {
+        self.take(&format!("speed_scale({pos})"), |c| match c {
+            NavCall::SpeedScale { pos: p, scale } if *p == pos => Some(*scale),
+            _ => None,
+        })
+        .unwrap_or(1.0)
+    }
+
+    fn backend(&self) -> &'static str {
+        "replay"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::brains::{build_brain, BrainKind};
+    use crate::nav_mode::StubNav;
+    use crate::{BotSkill, BrainConfig};
+    use client::parse::ConfigStrings;
+    use q2proto::Frame;
+    use world::NavGraph;
+
+    fn brain(kind: BrainKind) -> Box<dyn Brain + Send> {
+        let cfg = BrainConfig {
+            seed: Some(7),
+            ..BrainConfig::default()
+        };
+        build_brain(kind, BotSkill::default(), cfg, None)
+    }
+
+    fn map() -> BrainMap {
+        let nodes = (0..6).map(|i| [i as f32 * 200.0, 0.0, 0.0]).collect();
+        let edges = (0..6)
+            .map(|i: usize| {
+                [i.wrapping_sub(1), i + 1]
+                    .into_iter()
+                    .filter(|&j| j < 6)
+                    .map(|j| (j, 200.0))
+                    .collect()
+            })
+            .collect();
+        BrainMap {
+            roam_nodes: (0..6).collect(),
+            nav_graph: Arc::new(NavGraph::from_raw(nodes, edges)),
+            roam_as_position: false,
+            items: Vec::new(),
+            node_light: Vec::new(),
+            cm: Some(Arc::new(CollisionModel::half_space(
+                [0.0, 0.0, 1.0],
+                -100_000.0,
+            ))),
+        }
+    }
+
+    /// Run `kind` live for a while against a stub navigator that walks it along the x axis,
+    /// capturing as the bot loop does.
+    fn capture(kind: BrainKind) -> Vec<Event> {
+        let mut b = brain(kind);
+        let mut events = vec![Event::Map(MapRef {
+            name: "synth".into(),
+            fingerprint: 1,
+            roam_as_position: false,
+        })];
+        let m = map();
+        let cm = m.cm.clone();
+        b.set_map(m);
+        let mut nav = StubNav::default();
+        for t in 0..80u32 {
+            let mut frame = Frame {
+                serverframe: t as i32,
+                ..Frame::default()
+            };
+            frame.playerstate.pmove.origin = [(t * 8 * 8) as i16, 0, 0];
+            frame.playerstate.stats[1] = if t == 40 { 0 } else { 100 };
+            let view = Worldview::from_frame(&frame, &ConfigStrings::default(), 0);
+            nav.pursue = Some(Vec3::new(t as f32 * 10.0 + 100.0, 0.0, 0.0));
+            nav.reached = t % 25 == 24;
+            let (_, ev) = tick_taped(
+                &mut *b,
+                BrainContext {
+                    view: &view,
+                    nav: Some(&mut nav),
+                    cm: cm.as_deref(),
+                    dt: 0.1,
+                    ticks: t,
+                    goal_override: None,
+                },
+            );
+            events.push(ev);
+            if t == 40 {
+                b.on_death();
+                events.push(Event::Death);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn a_capture_replays_identically_into_a_fresh_brain() {
+        for kind in [BrainKind::Quake3, BrainKind::Xon, BrainKind::Zb2] {
+            let events = capture(kind);
+            let mut fresh = brain(kind);
+            let report = replay(&mut *fresh, &events, |m| {
+                assert_eq!(m.name, "synth");
+                Ok(map())
+            })
+            .unwrap();
+            assert_eq!(report.ticks, 80, "{kind:?}");
+            assert!(report.identical(), "{kind:?}: {:?}", report.first);
+        }
+    }
+
+    #[test]
+    fn the_seed_is_part_of_the_capture() {
+        let events = capture(BrainKind::Zb2);
+        let mut other = build_brain(
+            BrainKind::Zb2,
+            BotSkill::default(),
+            BrainConfig {
+                seed: Some(8),
+                ..BrainConfig::default()
+            },
+            None,
+        );
+        let report = replay(&mut *other, &events, |_| Ok(map())).unwrap();
+        assert!(!report.identical());
+    }
+
+    #[test]
+    fn a_changed_answer_is_reported_at_the_first_tick_it_shows() {
+        let mut events = capture(BrainKind::Xon);
+        // Pretend the navigator had steered somewhere else from tick 30 on.
+        for ev in &mut events {
+            if let Event::Tick(t) = ev {
+                if t.ticks >= 30 {
+                    for c in t.nav.iter_mut().flatten() {
+                        if let NavCall::PursueTarget { target, .. }
+                        | NavCall::PursueTargetSafe { target, .. } = c
+                        {
+                            *target = Some(Vec3::new(0.0, 900.0, 0.0));
+                        }
+                    }
+                }
+            }
+        }
+        let mut fresh = brain(BrainKind::Xon);
+        let report = replay(&mut *fresh, &events, |_| Ok(map())).unwrap();
+        let first = report.first.expect("diverges");
+        assert_eq!(first.ticks, 30, "{first}");
+        assert!(first.what.starts_with("output"), "{first}");
+    }
+
+    #[test]
+    fn a_different_question_to_the_navigator_is_a_divergence() {
+        let calls = [NavCall::EdgeIsJump(true), NavCall::ForceReplan];
+        let p = NavPlayback::new(&calls);
+        assert!(p.current_edge_is_jump());
+        assert_eq!(p.speed_scale(Vec3::ZERO), 1.0);
+        let miss = p.finish().unwrap();
+        assert!(
+            miss.contains("#1") && miss.contains("ForceReplan"),
+            "{miss}"
+        );
+
+        let p = NavPlayback::new(&calls);
+        assert!(p.current_edge_is_jump());
+        assert!(p.finish().unwrap().contains("replayed nothing"));
+    }
+
+    #[test]
+    fn a_written_capture_reads_back() {
+        let events = capture(BrainKind::Quake3);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cap/1.q3_1.capture.jsonl");
+        let header = CaptureHeader {
+            bot: "q3_1".into(),
+            brain: "q3".into(),
+            character: Some("sarge".into()),
+            traits: Some("code: sar\n".into()),
+            seed: 7,
+            combat: true,
+            human_aim: false,
+            started: 1,
+        };
+        {
+            let mut w = CaptureWriter::create(&path, &header).unwrap();
+            for e in &events {
+                w.record(e).unwrap();
+            }
+        }
+        let read = Capture::read(&path).unwrap();
+        assert_eq!(read.header, header);
+        assert_eq!(read.events.len(), events.len());
+        let mut fresh = brain(BrainKind::Quake3);
+        let report = replay(&mut *fresh, &read.events, |_| Ok(map())).unwrap();
+        assert!(report.identical(), "{:?}", report.first);
+    }
+}
@@ -0,0 +1,490 @@
+//! Rival memory — what we know about the players we meet, kept on disk between sessions.
+//!
+//! [`crate::opponent`] tracks where an enemy *is* during one fight; this book remembers who a
+//! player *is*: the weapon they tend to hold (VWep), where on each map they spend their time,
+//! and how often they frag us versus we frag them. It is fed from the same PVS-honest signals as
+//! the heatmap — [`HeatmapObserver`] sightings and obituary prints (`ClientObituary`,
+//! `p_hud.c`) — so a player we have never seen leaves no trace.
+//!
+//! Brains read it back two ways: [`RivalBook::mark_threats`] sets each visible enemy's
+//! [`PerceivedEntity::threat`], which the target pickers use to rank a known killer above a
+//! stranger at the same range, and [`RivalBook::route_danger`] prices the places the rivals on
+//! the server like to hang out, added to the heatmap's risk overlay.
+//!
+//! [`PerceivedEntity::threat`]: crate::perception::PerceivedEntity::threat
+//!
+//! Records are keyed by `(player name, map)`. The file is plain text, one line per record, so an
+//! operator can read or prune it by hand.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use client::parse::ConfigStrings;
+use glam::Vec3;
+use world::NavGraph;
+
+use crate::observed::{parse_obituary, HeatmapObserver};
+use crate::perception::{player_name, EntityClass, Worldview};
+use crate::weapons::{Weapon, ALL_WEAPONS};
+
+/// First line of a rivals file; anything else is refused rather than misread.
+const HEADER: &str = "# qbots rivals v1";
+/// Haunt grid cell (units). Cells rather than nav nodes, so a record survives a nav cache
+/// regenerated at another spacing.
+const CELL: f32 = 128.0;
+/// Haunt cells kept per record when saving (the busiest first).
+const MAX_HAUNTS: usize = 24;
+/// Frags exchanged before a rival's record counts for half: two lucky kills are not a habit.
+const CONFIDENCE_FRAGS: f32 = 5.0;
+
+/// One player on one map.
+#[derive(Debug, Clone, Default)]
+struct Record {
+    /// Times they fragged us.
+    kills: u32,
+    /// Times we fragged them.
+    deaths: u32,
+    /// Seconds we have had them in view.
+    seen_secs: f32,
+    /// Seconds seen holding each weapon, indexed like [`ALL_WEAPONS`].
+    weapons: [f32; ALL_WEAPONS.len()],
+    /// Seconds seen in each [`CELL`].
+    haunts: HashMap<[i32; 3], f32>,
+}
+
+/// What the book knows about one player, summed over every map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreatProfile {
+    pub name: String,
+    /// Times they fragged us.
+    pub kills: u32,
+    /// Times we fragged them.
+    pub deaths: u32,
+    /// Seconds we have had them in view.
+    pub seen_secs: f32,
+    /// The weapon we have seen them hold the longest.
+    pub favorite_weapon: Option<Weapon>,
+    /// `0.0..1.0`: how lopsided our record against them is, discounted while it is short.
+    pub threat: f32,
+}
+
+/// The on-disk memory of every rival, keyed by `(name, map)`. Each bot keeps its own book and
+/// excludes its fleet-mates, so it fills with the people worth remembering.
+#[derive(Debug, Clone, Default)]
+pub struct RivalBook {
+    records: HashMap<(String, String), Record>,
+    /// Names never recorded — our own bots. Not saved.
+    excluded: HashSet<String>,
+}
+
+impl RivalBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `path`. A missing file is an empty book (first run); a malformed one is an error.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(e) => Err(format!("read {}: {e}", path.display())),
+        }
+    }
+
+    /// Write the book to `path`, creating its directory. The text goes to `<path>.tmp` first
+    /// and is renamed over `path`, so a crash mid-write leaves the old book whole.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| format!("create {}: {e}", dir.display()))?;
+        }
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        std::fs::write(&tmp, self.to_text())
+            .map_err(|e| format!("write {}: {e}", tmp.display()))?;
+        std::fs::rename(&tmp, path).map_err(|e| format!("rename {}: {e}", tmp.display()))
+    }
+
+    /// Parse the text format written by [`Self::to_text`].
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        if lines.next().map(str::trim) != Some(HEADER) {
+            return Err(format!("not a rivals file (expected `{HEADER}`)"));
+        }
+        let mut book = Self::new();
+        for (i, line) in lines.enumerate() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let (key, record) =
+                parse_record(line).ok_or_else(|| format!("bad record {}", i + 1))?;
+            book.records.insert(key, record);
+        }
+        Ok(book)
+    }
+
+    /// One line per record: `name  map  kills  deaths  seen  weapons  haunts`, tab-separated.
+    /// Weapons are `Name=secs` joined by `,`; haunts are `x:y:z=secs` cells joined by spaces.
+    pub fn to_text(&self) -> String {
+        let mut keys: Vec<_> = self.records.keys().collect();
+        keys.sort();
+        let mut out = format!("{HEADER}\n");
+        for key in keys {
+            let r = &self.records[key];
+            let weapons: Vec<String> = ALL_WEAPONS
+                .iter()
+                .zip(r.weapons)
+                .filter(|(_, secs)| *secs > 0.0)
+                .map(|(w, secs)| format!("{}={secs:.1}", w.name()))
+                .collect();
+            let mut haunts: Vec<_> = r.haunts.iter().collect();
+            haunts.sort_by(|a, b| b.1.total_cmp(a.1).then(a.0.cmp(b.0)));
+            let haunts: Vec<String> = haunts
+                .into_iter()
+                .take(MAX_HAUNTS)
+                .map(|([x, y, z], secs)| format!("{x}:{y}:{z}={secs:.1}"))
+                .collect();
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{:.1}\t{}\t{}\n",
+                key.0,
+                key.1,
+                r.kills,
+                r.deaths,
+                r.seen_secs,
+                weapons.join(","),
+                haunts.join(" ")
+            ));
+        }
+        out
+    }
+
+    /// Never record `name` (one of our own bots).
+    pub fn exclude(&mut self, name: &str) {
+        self.excluded.insert(name.to_string());
+    }
+
+    /// Number of `(player, map)` records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Fold this frame's observations into the book: every player `obs` placed this `frame`
+    /// (where, and what they hold) and every obituary in `prints` that involves us.
+    /// `dt` is the seconds the frame covers.
+    #[allow(clippy::too_many_arguments)]
+    pub fn observe(
+        &mut self,
+        map: &str,
+        our_name: &str,
+        obs: &HeatmapObserver,
+        view: &Worldview,
+        cs: &ConfigStrings,
+        prints: &[String],
+        frame: i32,
+        dt: f32,
+    ) {
+        for (name, pos) in obs.sightings(frame) {
+            let weapon = view
+                .enemies()
+                .find(|e| player_name(cs, e.entity_number).as_deref() == Some(name))
+                .and_then(|e| e.held_weapon);
+            let Some(r) = self.record(name, map) else {
+                continue;
+            };
+            r.seen_secs += dt;
+            *r.haunts.entry(cell(pos)).or_default() += dt;
+            if let Some(i) = weapon.and_then(|w| ALL_WEAPONS.iter().position(|&a| a == w)) {
+                r.weapons[i] += dt;
+            }
+        }
+        let names: Vec<&str> = obs.known_names().iter().map(String::as_str).collect();
+        for text in prints {
+            let Some(obit) = parse_obituary(text, &names) else {
+                continue;
+            };
+            match obit.killer.as_deref() {
+                Some(killer) if obit.victim == our_name => {
+                    if let Some(r) = self.record(killer, map) {
+                        r.kills += 1;
+                    }
+                }
+                Some(killer) if killer == our_name => {
+                    if let Some(r) = self.record(&obit.victim, map) {
+                        r.deaths += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Everything the book knows about `name`, over all maps. `None` for a stranger.
+    pub fn threat_profile(&self, name: &str) -> Option<ThreatProfile> {
+        let mut total = Record::default();
+        let mut found = false;
+        for r in self
+            .records
+            .iter()
+            .filter(|((n, _), _)| n == name)
+            .map(|(_, r)| r)
+        {
+            found = true;
+            total.kills += r.kills;
+            total.deaths += r.deaths;
+            total.seen_secs += r.seen_secs;
+            for (t, w) in total.weapons.iter_mut().zip(r.weapons) {
+                *t += w;
+            }
+        }
+        if !found {
+            return None;
+        }
+        let favorite_weapon = ALL_WEAPONS
+            .iter()
+            .zip(total.weapons)
+            .filter(|(_, secs)| *secs > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(&w, _)| w);
+        Some(ThreatProfile {
+            name: name.to_string(),
+            kills: total.kills,
+            deaths: total.deaths,
+            seen_secs: total.seen_secs,
+            favorite_weapon,
+            threat: threat(total.kills, total.deaths),
+        })
+    }
+
+    /// Set each enemy's [`PerceivedEntity::threat`] in `view` from its name's profile.
+    ///
+    /// [`PerceivedEntity::threat`]: crate::perception::PerceivedEntity::threat
+    pub fn mark_threats(&self, view: &mut Worldview, cs: &ConfigStrings) {
+        for e in view.entities_mut() {
+            if e.class != EntityClass::EnemyPlayer {
+                continue;
+            }
+            e.threat = player_name(cs, e.entity_number)
+                .and_then(|n| self.threat_profile(&n))
+                .map_or(0.0, |p| p.threat);
+        }
+    }
+
+    /// Per-node route danger on `map` from the haunts of the `present` players, each scaled by
+    /// their threat: `threat × (time in cell / their busiest cell)` at the cell's nearest node.
+    pub fn route_danger(&self, map: &str, present: &[&str], graph: &NavGraph) -> Vec<f32> {
+        let mut out = vec![0.0; graph.node_count()];
+        for &name in present {
+            let Some(r) = self.records.get(&(name.to_string(), map.to_string())) else {
+                continue;
+            };
+            let t = self.threat_profile(name).map_or(0.0, |p| p.threat);
+            let busiest = r.haunts.values().copied().fold(0.0, f32::max);
+            if t <= 0.0 || busiest <= 0.0 {
+                continue;
+            }
+            for (&c, &secs) in &r.haunts {
+                let centre = (Vec3::new(c[0] as f32, c[1] as f32, c[2] as f32) + 0.5) * CELL;
+                if let Some(node) = graph.nearest(&centre.to_array()) {
+                    out[node] += t * secs / busiest;
+                }
+            }
+        }
+        out
+    }
+
+    /// The record for `(name, map)`, created on first use. `None` for excluded names.
+    fn record(&mut self, name: &str, map: &str) -> Option<&mut Record> {
+        if name.is_empty() || map.is_empty() || self.excluded.contains(name) {
+            return None;
+        }
+        // Tabs and newlines would break the line format; Q2 names never need them.
+        let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+        Some(self.records.entry((clean(name), clean(map))).or_default())
+    }
+}
+
+/// `0.0..1.0` from our frag record against a player: the smoothed share of our meetings they
+/// won, times the confidence the record has earned. Nobody who has never fragged us is a threat.
+fn threat(kills: u32, deaths: u32) -> f32 {
+    if kills == 0 {
+        return 0.0;
+    }
+    let n = (kills + deaths) as f32;
+    let lethality = (kills as f32 + 1.0) / (n + 2.0);
+    lethality * n / (n + CONFIDENCE_FRAGS)
+}
+
+fn cell(pos: Vec3) -> [i32; 3] {
+    let c = (pos / CELL).floor();
+    [c.x as i32, c.y as i32, c.z as i32]
+}
+
+fn parse_record(line: &str) -> Option<((String, String), Record)> {
+    let f: Vec<&str> = line.split('\t').collect();
+    let [name, map, kills, deaths, seen, weapons, haunts] = f[..] else {
+        return None;
+    };
+    let mut r = Record {
+        kills: kills.parse().ok()?,
+        deaths: deaths.parse().ok()?,
+        seen_secs: seen.parse().ok()?,
+        ..Default::default()
+    };
+    for w in weapons.split(',').filter(|s| !s.is_empty()) {
+        let (wname, secs) = w.split_once('=')?;
+        let i = ALL_WEAPONS.iter().position(|a| a.name() == wname)?;
+        r.weapons[i] = secs.parse().ok()?;
+    }
+    for h in haunts.split(' ').filter(|s| !s.is_empty()) {
+        let (c, secs) = h.split_once('=')?;
+        let mut xyz = c.split(':').map(str::parse::<i32>);
+        let c = [xyz.next()?.ok()?, xyz.next()?.ok()?, xyz.next()?.ok()?];
+        r.haunts.insert(c, secs.parse().ok()?);
+    }
+    Some(((name.to_string(), map.to_string()), r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perception::{CS_MODELS, CS_PLAYERSKINS};
+    use q2proto::{EntityState, Frame};
+    use std::sync::Arc;
+
+    const MAP: &str = "q2dm1";
+
+    /// We are `Me` (entity 1) at the origin; `Regular` (entity 2) and `Stranger` (entity 3) are
+    /// enemies at the given origins, `Regular` wielding a railgun.
+    fn scene(regular: [f32; 3], stranger: [f32; 3]) -> (Worldview, ConfigStrings) {
+        let mut cs = ConfigStrings::default();
+        cs.set(CS_MODELS + 1, "#w_railgun.md2");
+        cs.set(CS_PLAYERSKINS, "Me\\male/grunt");
+        cs.set(CS_PLAYERSKINS + 1, "Regular\\female/athena");
+        cs.set(CS_PLAYERSKINS + 2, "Stranger\\male/grunt");
+        let player = |number, origin, modelindex2| EntityState {
+            number,
+            modelindex: 255,
+            modelindex2,
+            origin,
+            ..Default::default()
+        };
+        let frame = Frame {
+            serverframe: 7,
+            entities: vec![
+                player(1, [0.0; 3], 0),
+                player(2, regular, 1),
+                player(3, stranger, 0),
+            ],
+            ..Default::default()
+        };
+        (Worldview::from_frame(&frame, &cs, 0), cs)
+    }
+
+    /// Nodes every 100 u along +x.
+    fn corridor() -> Arc<NavGraph> {
+        let nodes = (0..10).map(|i| [i as f32 * 100.0, 0.0, 0.0]).collect();
+        let edges = (0..10)
+            .map(|i: usize| {
+                [i.checked_sub(1), (i < 9).then_some(i + 1)]
+                    .into_iter()
+                    .flatten()
+                    .map(|j| (j, 100.0))
+                    .collect()
+            })
+            .collect();
+        Arc::new(NavGraph::from_raw(nodes, edges))
+    }
+
+    /// A book that watched `Regular` camp at x=800 with a railgun and frag us three times.
+    fn seasoned() -> RivalBook {
+        let graph = corridor();
+        let (view, cs) = scene([800.0, 0.0, 0.0], [300.0, 0.0, 0.0]);
+        let mut obs = HeatmapObserver::new(graph, "Me");
+        obs.sample_presence(&view, &cs, 0.1, 7);
+        let mut book = RivalBook::new();
+        book.exclude("Stranger");
+        let prints: Vec<String> = (0..3)
+            .map(|_| "Me was railed by Regular.\n".to_string())
+            .chain(["Regular ate Me's rocket.\n".to_string()])
+            .collect();
+        for _ in 0..50 {
+            book.observe(MAP, "Me", &obs, &view, &cs, &[], 7, 0.1);
+        }
+        book.observe(MAP, "Me", &obs, &view, &cs, &prints, 7, 0.1);
+        book
+    }
+
+    #[test]
+    fn sightings_and_obituaries_build_a_profile() {
+        let book = seasoned();
+        let p = book.threat_profile("Regular").expect("Regular was seen");
+        assert_eq!((p.kills, p.deaths), (3, 1));
+        assert_eq!(p.favorite_weapon, Some(Weapon::Railgun));
+        assert!((p.seen_secs - 5.1).abs() < 1e-3, "{}", p.seen_secs);
+        assert!(p.threat > 0.0 && p.threat < 1.0, "{}", p.threat);
+        // Excluded (a fleet-mate) and never-seen players leave no record.
+        assert!(book.threat_profile("Stranger").is_none());
+        assert!(book.threat_profile("Nobody").is_none());
+        // A record that never fragged us is no threat, however long it was seen.
+        assert_eq!(threat(0, 9), 0.0);
+        assert!(threat(9, 0) > threat(3, 1));
+    }
+
+    #[test]
+    fn the_book_survives_a_round_trip_to_disk() {
+        let book = seasoned();
+        let path = std::env::temp_dir().join(format!("qbots-rivals-{}.txt", std::process::id()));
+        std::fs::write(&path, "half a book").unwrap();
+        book.save(&path).unwrap();
+        assert!(
+            !path.with_extension("txt.tmp").exists(),
+            "the temp file is renamed over"
+        );
+        let back = RivalBook::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(back.len(), 1);
+        assert_eq!(back.to_text(), book.to_text());
+        let p = back.threat_profile("Regular").unwrap();
+        assert_eq!((p.kills, p.favorite_weapon), (3, Some(Weapon::Railgun)));
+
+        assert!(
+            RivalBook::load(&path).unwrap().is_empty(),
+            "missing file = empty book"
+        );
+        assert!(RivalBook::parse("# qbots rivals v0\n").is_err());
+        assert!(RivalBook::parse(&format!("{HEADER}\nRegular\tq2dm1\tx\n")).is_err());
+    }
+
+    #[test]
+    fn a_known_killer_outranks_a_nearer_stranger_and_prices_their_haunts() {
+        let book = seasoned();
+        // Regular at 350 u, the stranger at 300 u: unmarked, the stranger is nearest.
+        let (mut view, cs) = scene([350.0, 0.0, 0.0], [300.0, 0.0, 0.0]);
+        assert_eq!(view.nearest_enemy(180.0).unwrap().entity_number, 3);
+        book.mark_threats(&mut view, &cs);
+        assert_eq!(view.nearest_enemy(180.0).unwrap().entity_number, 2);
+        // A stranger far closer still wins.
+        let (mut view, cs) = scene([900.0, 0.0, 0.0], [100.0, 0.0, 0.0]);
+        book.mark_threats(&mut view, &cs);
+        assert_eq!(view.nearest_enemy(180.0).unwrap().entity_number, 3);
+
+        // Their camping spot is dangerous while they are on the server, and only on this map.
+        let graph = corridor();
+        let danger = book.route_danger(MAP, &["Regular"], &graph);
+        let hot = (0..danger.len()).max_by(|&a, &b| danger[a].total_cmp(&danger[b]));
+        assert_eq!(hot, Some(8));
+        assert!(danger[0] == 0.0);
+        assert!(book
+            .route_danger(MAP, &[], &graph)
+            .iter()
+            .all(|&d| d == 0.0));
+        assert!(book
+            .route_danger("q2dm2", &["Regular"], &graph)
+            .iter()
+            .all(|&d| d == 0.0));
+    }
+}
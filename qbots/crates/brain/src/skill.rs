@@ -147,6 +147,12 @@ impl BotSkill {
         (10 - self.skill) as f32 / 10.0
     }
 
+    /// The mouse hand for [`BrainConfig::human_aim`](crate::BrainConfig::human_aim): the accuracy
+    /// rating (1-5) read as novice → expert.
+    pub fn mouse_params(&self) -> crate::mouse::MouseParams {
+        crate::mouse::MouseParams::from_skill((self.accuracy() - 1.0) / 4.0)
+    }
+
     /// Reaction delay in frames based on skill and personality.
     /// Skill 0 = 10 frames, Skill 10 = 0 frames.
     pub fn reaction_delay_frames(&self) -> u32 {
@@ -0,0 +1,655 @@
+//! Splash aim solver — rocket and grenade shots flown through the collision model.
+//!
+//! [`crate::aim::aim_direction`] ports Eraser's fixed lead factors and GL lob, which assume open
+//! air: a rocket at a strafer's chest or a grenade lobbed at a fixed pitch misses the moment a
+//! floor, wall or corner is involved. Here each candidate shot is simulated the way the server
+//! runs it — `fire_rocket` flies straight until it touches something, `fire_grenade` tosses under
+//! `sv_gravity` with `MOVETYPE_BOUNCE` clipping at the 10 Hz server frame (`SV_Physics_Toss`) and
+//! goes off on contact or when its 2.5 s fuse runs out — and scored by the `T_RadiusDamage` it
+//! would deal to the enemy at its predicted position (`g_weapon.c`, `g_combat.c`).
+//!
+//! Rockets try the enemy's body, the floor under where it will be (a jumper can't dodge the
+//! ground it lands on) and walls within blast radius of it; grenades sweep a yaw × pitch grid, so
+//! bank shots and lobs around corners fall out of the search. The enemy is predicted by stepping
+//! its velocity through the world with gravity, and every shot is averaged over dodge samples
+//! that spread with flight time ([`DODGE_SPEED`]) — which is what makes a slow direct rocket at a
+//! far strafer lose to a splash at its feet.
+
+use glam::Vec3;
+use world::{CollisionModel, Trace, MASK_SOLID};
+
+use crate::aim::vec3_to_angles;
+use crate::weapons::Weapon;
+
+/// Steepest view pitch a solved shot may use, either way — the client's real look range, not
+/// Eraser's ±15° aim clamp ([`crate::aim::PITCH_CLAMP_DEG`]), so rockets can go straight at the
+/// floor below a ledge and grenades can be lobbed high over cover.
+pub const MAX_PITCH_DEG: f32 = 89.0;
+
+/// `sv_gravity` default.
+pub const GRAVITY: f32 = 800.0;
+
+/// Grenade launcher fuse (`weapon_grenadelauncher_fire`: `fire_grenade(.., 2.5, ..)`).
+pub const GRENADE_FUSE: f32 = 2.5;
+
+/// Server frame (`FRAMETIME`): toss physics and our enemy prediction step at this rate.
+const FRAME: f32 = 0.1;
+
+/// Launcher speed and the extra kick along the view's *up* vector (`fire_grenade`).
+const GRENADE_SPEED: f32 = 600.0;
+const GRENADE_UPKICK: f32 = 200.0;
+
+/// `MOVETYPE_BOUNCE` overbounce in `ClipVelocity`: half the into-surface speed comes back out.
+const BOUNCE_BACKOFF: f32 = 1.5;
+
+/// A bouncing grenade settles once it hits a floor (`normal.z > 0.7`) slower than this upward.
+const BOUNCE_STOP_SPEED: f32 = 60.0;
+
+/// Grenade damage, and its blast radius `damage + 40`.
+const GRENADE_DAMAGE: f32 = 120.0;
+const GRENADE_RADIUS: f32 = 160.0;
+
+/// Rocket speed, mean direct damage (`100 + rand % 20`) and `T_RadiusDamage(120, radius 120)`.
+/// The direct victim is excluded from the splash.
+const ROCKET_SPEED: f32 = 650.0;
+const ROCKET_DIRECT: f32 = 110.0;
+const ROCKET_SPLASH: f32 = 120.0;
+const ROCKET_RADIUS: f32 = 120.0;
+
+/// A rocket lives 8000/650 s; anything that hasn't hit by this range never will.
+const ROCKET_RANGE: f32 = 8000.0;
+
+/// `rocket_touch` explodes `0.02 × velocity` back from the contact point.
+const ROCKET_BACKOFF: f32 = 0.02 * ROCKET_SPEED;
+
+/// `P_ProjectSource` offset for both launchers: 8 forward, 8 right, `viewheight − 8` up.
+const MUZZLE_FORWARD: f32 = 8.0;
+const MUZZLE_RIGHT: f32 = 8.0;
+const MUZZLE_UP: f32 = 14.0;
+
+/// Player bbox, and its centre relative to the origin (`(mins + maxs) / 2`).
+const PLAYER_MINS: [f32; 3] = [-16.0, -16.0, -24.0];
+const PLAYER_MAXS: [f32; 3] = [16.0, 16.0, 32.0];
+const BODY_OFFSET: Vec3 = Vec3::new(0.0, 0.0, 4.0);
+
+/// How fast (u/s) an enemy can stray from its straight-line prediction. A strafing Q2 player
+/// reverses within a few frames, so a shot in flight for `t` seconds can be off by this × `t`.
+const DODGE_SPEED: f32 = 120.0;
+
+/// Wall candidates: this many horizontal directions probed out to the rocket's blast radius.
+const WALL_PROBES: usize = 8;
+
+/// Grenade search grid: yaw offsets around the line to the enemy, and a coarse pitch step over
+/// ±[`MAX_PITCH_DEG`], refined around the best shot at [`GRENADE_PITCH_FINE`].
+const GRENADE_YAW_OFFSETS: [f32; 9] = [-40.0, -25.0, -12.0, -6.0, 0.0, 6.0, 12.0, 25.0, 40.0];
+const GRENADE_PITCH_STEP: f32 = 6.0;
+const GRENADE_PITCH_FINE: f32 = 2.0;
+
+/// Shots expected to deal less than this aren't worth taking over the plain lead aim.
+const MIN_EXPECTED_DAMAGE: f32 = 15.0;
+
+/// The chosen splash shot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplashAim {
+    /// View angles to fire at, Q2 convention (pitch positive = down), pitch within
+    /// ±[`MAX_PITCH_DEG`].
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Where the projectile is predicted to go off (for an expected direct hit: the contact).
+    pub impact: Vec3,
+    /// Expected damage to the enemy, averaged over the dodge samples.
+    pub expected: f32,
+}
+
+impl SplashAim {
+    /// Unit vector the shot is fired along.
+    pub fn direction(&self) -> Vec3 {
+        angle_vectors(self.yaw, self.pitch).0
+    }
+}
+
+/// Solve the best rocket or grenade shot from `shooter` (our origin) at an enemy at `enemy`
+/// moving at `enemy_vel`. `None` for other weapons, or when no candidate is expected to deal
+/// [`MIN_EXPECTED_DAMAGE`] without splashing us — the caller falls back to the lead aim.
+pub fn solve(
+    cm: &CollisionModel,
+    shooter: Vec3,
+    enemy: Vec3,
+    enemy_vel: Option<Vec3>,
+    weapon: Weapon,
+) -> Option<SplashAim> {
+    let vel = enemy_vel.unwrap_or(Vec3::ZERO);
+    let steps = (GRENADE_FUSE / FRAME).round() as usize;
+    let path = predict_path(cm, enemy, vel, steps);
+    let samples = dodge_axes(vel);
+    let best = match weapon {
+        Weapon::RocketLauncher => solve_rocket(cm, shooter, &path, &samples),
+        Weapon::GrenadeLauncher => solve_grenade(cm, shooter, &path, &samples),
+        _ => None,
+    }?;
+    (best.expected >= MIN_EXPECTED_DAMAGE).then_some(best)
+}
+
+fn solve_rocket(
+    cm: &CollisionModel,
+    shooter: Vec3,
+    path: &[Vec3],
+    axes: &[Vec3; 2],
+) -> Option<SplashAim> {
+    let eye = shooter + Vec3::Z * MUZZLE_UP;
+    // Two passes of the usual constant-speed lead against the *simulated* enemy path.
+    let mut t = (path[0] - eye).length() / ROCKET_SPEED;
+    for _ in 0..2 {
+        t = (at(path, t) - eye).length() / ROCKET_SPEED;
+    }
+    let predicted = at(path, t);
+
+    let mut aims = vec![predicted + BODY_OFFSET];
+    if let Some(floor) = floor_under(cm, predicted) {
+        aims.push(floor);
+    }
+    for i in 0..WALL_PROBES {
+        let a = i as f32 * std::f32::consts::TAU / WALL_PROBES as f32;
+        let out = predicted + Vec3::new(a.cos(), a.sin(), 0.0) * ROCKET_RADIUS;
+        let tr = trace_point(cm, predicted, out);
+        if tr.fraction < 1.0 && !tr.startsolid {
+            aims.push(Vec3::from(tr.endpos));
+        }
+    }
+
+    aims.into_iter()
+        .filter_map(|aim| {
+            let (yaw, pitch) = vec3_to_angles(aim - eye);
+            let pitch = pitch.clamp(-MAX_PITCH_DEG, MAX_PITCH_DEG);
+            let shot = rocket_shot(cm, shooter, yaw, pitch, path, axes)?;
+            Some(SplashAim { yaw, pitch, ..shot })
+        })
+        .max_by(|a, b| a.expected.total_cmp(&b.expected))
+}
+
+/// Fly one rocket and score it; `None` if it would go off inside our own safe distance.
+fn rocket_shot(
+    cm: &CollisionModel,
+    shooter: Vec3,
+    yaw: f32,
+    pitch: f32,
+    path: &[Vec3],
+    axes: &[Vec3; 2],
+) -> Option<SplashAim> {
+    let (dir, start) = muzzle(shooter, yaw, pitch);
+    let tr = trace_point(cm, start, start + dir * ROCKET_RANGE);
+    if tr.startsolid {
+        return None;
+    }
+    let hit_world = tr.fraction < 1.0;
+    let reach = tr.fraction * ROCKET_RANGE;
+    let blast = Vec3::from(tr.endpos) - dir * ROCKET_BACKOFF;
+    let safe = Weapon::RocketLauncher.min_safe_distance();
+    if hit_world && (blast - shooter).length() < safe {
+        return None;
+    }
+    let t_blast = reach / ROCKET_SPEED;
+
+    let mut impact = blast;
+    let mut total = 0.0;
+    for k in 0..SAMPLES {
+        // Where along the flight the rocket passes this sample, then the sample at that moment.
+        let near = at(path, t_blast) + sample_offset(axes, k, t_blast);
+        let along = (near + BODY_OFFSET - start).dot(dir).clamp(0.0, reach);
+        let t = along / ROCKET_SPEED;
+        let body = at(path, t) + sample_offset(axes, k, t) + BODY_OFFSET;
+        let along = (body - start).dot(dir).clamp(0.0, reach);
+        let passes = start + dir * along;
+        if inside_player(passes, body - BODY_OFFSET) {
+            total += ROCKET_DIRECT;
+            if k == 0 {
+                impact = passes;
+            }
+        } else if hit_world {
+            total += splash_damage(cm, blast, body, ROCKET_SPLASH, ROCKET_RADIUS);
+        }
+    }
+    if (impact - shooter).length() < safe {
+        return None;
+    }
+    Some(SplashAim {
+        yaw,
+        pitch,
+        impact,
+        expected: total / SAMPLES as f32,
+    })
+}
+
+fn solve_grenade(
+    cm: &CollisionModel,
+    shooter: Vec3,
+    path: &[Vec3],
+    axes: &[Vec3; 2],
+) -> Option<SplashAim> {
+    let (base_yaw, _) = vec3_to_angles(path[0] - shooter);
+    let pitches = (0..)
+        .map(|i| -MAX_PITCH_DEG + i as f32 * GRENADE_PITCH_STEP)
+        .take_while(|p| *p <= MAX_PITCH_DEG + 1e-3);
+    let mut best: Option<SplashAim> = None;
+    let keep = |best: &mut Option<SplashAim>, shot: Option<SplashAim>| {
+        if let Some(shot) = shot.filter(|s| best.is_none_or(|b| s.expected > b.expected)) {
+            *best = Some(shot);
+        }
+    };
+    for pitch in pitches {
+        for off in GRENADE_YAW_OFFSETS {
+            let shot = grenade_shot(cm, shooter, base_yaw + off, pitch, path, axes);
+            keep(&mut best, shot);
+        }
+    }
+    // Arcs change fast with pitch: try either side of the best coarse pitch.
+    if let Some(coarse) = best {
+        for d in [-GRENADE_PITCH_FINE, GRENADE_PITCH_FINE] {
+            let pitch = (coarse.pitch + d).clamp(-MAX_PITCH_DEG, MAX_PITCH_DEG);
+            let shot = grenade_shot(cm, shooter, coarse.yaw, pitch, path, axes);
+            keep(&mut best, shot);
+        }
+    }
+    best
+}
+
+/// Toss one grenade and score it; `None` if it would go off inside our own safe distance.
+fn grenade_shot(
+    cm: &CollisionModel,
+    shooter: Vec3,
+    yaw: f32,
+    pitch: f32,
+    path: &[Vec3],
+    axes: &[Vec3; 2],
+) -> Option<SplashAim> {
+    let (_, _, up) = angle_vectors(yaw, pitch);
+    let (dir, start) = muzzle(shooter, yaw, pitch);
+    let flight = grenade_flight(cm, start, dir * GRENADE_SPEED + up * GRENADE_UPKICK);
+    let rest = *flight.last()?;
+    let safe = Weapon::GrenadeLauncher.min_safe_distance();
+    if (rest - shooter).length() < safe {
+        return None;
+    }
+
+    let mut impact = rest;
+    let mut total = 0.0;
+    for k in 0..SAMPLES {
+        // `Grenade_Touch` goes off on the first player it meets, dealing `dmg − ½·dist` to it.
+        let contact = flight.windows(2).enumerate().find_map(|(i, seg)| {
+            let t = (i + 1) as f32 * FRAME;
+            let origin = at(path, t) + sample_offset(axes, k, t);
+            let p = closest_on_segment(seg[0], seg[1], origin + BODY_OFFSET);
+            inside_player(p, origin).then_some((p, origin + BODY_OFFSET))
+        });
+        match contact {
+            Some((p, body)) => {
+                total += (GRENADE_DAMAGE - 0.5 * (body - p).length()).max(0.0);
+                if k == 0 {
+                    impact = p;
+                }
+            }
+            None => {
+                let body = at(path, GRENADE_FUSE) + sample_offset(axes, k, GRENADE_FUSE);
+                total +=
+                    splash_damage(cm, rest, body + BODY_OFFSET, GRENADE_DAMAGE, GRENADE_RADIUS);
+            }
+        }
+    }
+    if (impact - shooter).length() < safe {
+        return None;
+    }
+    Some(SplashAim {
+        yaw,
+        pitch,
+        impact,
+        expected: total / SAMPLES as f32,
+    })
+}
+
+/// A grenade's position at every server frame from launch to fuse (`SV_Physics_Toss`): gravity,
+/// one pushed move per frame, `ClipVelocity` with overbounce on contact, and at rest for good
+/// once it lands on a floor slowly enough.
+pub fn grenade_flight(cm: &CollisionModel, start: Vec3, vel: Vec3) -> Vec<Vec3> {
+    let steps = (GRENADE_FUSE / FRAME).round() as usize;
+    let mut points = Vec::with_capacity(steps + 1);
+    let (mut p, mut v) = (start, vel);
+    let mut resting = false;
+    points.push(p);
+    for _ in 0..steps {
+        if !resting {
+            v.z -= GRAVITY * FRAME;
+            let tr = trace_point(cm, p, p + v * FRAME);
+            if tr.startsolid {
+                break;
+            }
+            p = Vec3::from(tr.endpos);
+            if tr.fraction < 1.0 {
+                let n = Vec3::from(tr.plane.normal);
+                v -= n * (v.dot(n) * BOUNCE_BACKOFF);
+                if n.z > 0.7 && v.z < BOUNCE_STOP_SPEED {
+                    resting = true;
+                    v = Vec3::ZERO;
+                }
+            }
+        }
+        points.push(p);
+    }
+    points
+}
+
+/// The enemy's origin at every server frame for `steps` frames: its horizontal velocity slid
+/// along walls, gravity pulling it down until it stands on something.
+fn predict_path(cm: &CollisionModel, origin: Vec3, vel: Vec3, steps: usize) -> Vec<Vec3> {
+    let mut path = Vec::with_capacity(steps + 1);
+    let (mut p, mut v) = (origin, vel);
+    path.push(p);
+    for _ in 0..steps {
+        let across = trace_player(cm, p, p + Vec3::new(v.x, v.y, 0.0) * FRAME);
+        if across.startsolid {
+            // Embedded (quantised origin): coast rather than freeze the prediction.
+            p += Vec3::new(v.x, v.y, 0.0) * FRAME;
+            path.push(p);
+            continue;
+        }
+        p = Vec3::from(across.endpos);
+        if across.fraction < 1.0 {
+            let n = Vec3::from(across.plane.normal);
+            v -= n * v.dot(n);
+        }
+        v.z -= GRAVITY * FRAME;
+        let fall = trace_player(cm, p, p + Vec3::Z * v.z * FRAME);
+        if !fall.startsolid {
+            p = Vec3::from(fall.endpos);
+            if fall.fraction < 1.0 {
+                v.z = 0.0;
+            }
+        } else {
+            v.z = 0.0;
+        }
+        path.push(p);
+    }
+    path
+}
+
+/// Dodge samples: the prediction itself, then ± along and across the enemy's motion.
+const SAMPLES: usize = 5;
+
+/// The two horizontal dodge axes: along the enemy's motion (or +x when it's standing) and across.
+fn dodge_axes(vel: Vec3) -> [Vec3; 2] {
+    let along = Vec3::new(vel.x, vel.y, 0.0)
+        .try_normalize()
+        .unwrap_or(Vec3::X);
+    [along, Vec3::new(-along.y, along.x, 0.0)]
+}
+
+fn sample_offset(axes: &[Vec3; 2], k: usize, t: f32) -> Vec3 {
+    let spread = DODGE_SPEED * t;
+    match k {
+        1 => axes[0] * spread,
+        2 => -axes[0] * spread,
+        3 => axes[1] * spread,
+        4 => -axes[1] * spread,
+        _ => Vec3::ZERO,
+    }
+}
+
+/// Linear interpolation into a per-frame path; holds the last point past its end.
+fn at(path: &[Vec3], t: f32) -> Vec3 {
+    let f = (t / FRAME).max(0.0);
+    let i = f.floor() as usize;
+    if i + 1 >= path.len() {
+        return path[path.len() - 1];
+    }
+    path[i].lerp(path[i + 1], f - i as f32)
+}
+
+/// `T_RadiusDamage` on one body: `damage − ½·distance` to its centre, if the blast can see it
+/// (`CanDamage`).
+fn splash_damage(cm: &CollisionModel, blast: Vec3, body: Vec3, damage: f32, radius: f32) -> f32 {
+    let d = (body - blast).length();
+    if d > radius {
+        return 0.0;
+    }
+    let tr = trace_point(cm, blast, body);
+    if tr.fraction < 1.0 {
+        return 0.0;
+    }
+    (damage - 0.5 * d).max(0.0)
+}
+
+/// The floor point under `origin` (within a long fall), where a splash still reaches its feet.
+fn floor_under(cm: &CollisionModel, origin: Vec3) -> Option<Vec3> {
+    let tr = trace_point(cm, origin, origin - Vec3::Z * 512.0);
+    (tr.fraction < 1.0 && !tr.startsolid).then(|| Vec3::from(tr.endpos))
+}
+
+fn inside_player(p: Vec3, origin: Vec3) -> bool {
+    let d = p - origin;
+    d.x.abs() <= PLAYER_MAXS[0]
+        && d.y.abs() <= PLAYER_MAXS[1]
+        && d.z >= PLAYER_MINS[2]
+        && d.z <= PLAYER_MAXS[2]
+}
+
+fn closest_on_segment(a: Vec3, b: Vec3, p: Vec3) -> Vec3 {
+    let ab = b - a;
+    let len2 = ab.length_squared();
+    if len2 <= f32::EPSILON {
+        return a;
+    }
+    a + ab * ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+}
+
+/// Fired direction and projectile start for view angles (`P_ProjectSource`).
+fn muzzle(shooter: Vec3, yaw: f32, pitch: f32) -> (Vec3, Vec3) {
+    let (forward, right, _) = angle_vectors(yaw, pitch);
+    let start = shooter + Vec3::Z * MUZZLE_UP + forward * MUZZLE_FORWARD + right * MUZZLE_RIGHT;
+    (forward, start)
+}
+
+/// `AngleVectors` with no roll: forward, right, up.
+fn angle_vectors(yaw: f32, pitch: f32) -> (Vec3, Vec3, Vec3) {
+    let (sy, cy) = yaw.to_radians().sin_cos();
+    let (sp, cp) = pitch.to_radians().sin_cos();
+    (
+        Vec3::new(cp * cy, cp * sy, -sp),
+        Vec3::new(sy, -cy, 0.0),
+        Vec3::new(sp * cy, sp * sy, cp),
+    )
+}
+
+fn trace_point(cm: &CollisionModel, a: Vec3, b: Vec3) -> Trace {
+    cm.trace(
+        &a.to_array(),
+        &b.to_array(),
+        &[0.0; 3],
+        &[0.0; 3],
+        MASK_SOLID,
+    )
+}
+
+fn trace_player(cm: &CollisionModel, a: Vec3, b: Vec3) -> Trace {
+    cm.trace(
+        &a.to_array(),
+        &b.to_array(),
+        &PLAYER_MINS,
+        &PLAYER_MAXS,
+        MASK_SOLID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Solid below z = 0, open above.
+    fn floor() -> CollisionModel {
+        CollisionModel::half_space([0.0, 0.0, 1.0], 0.0)
+    }
+
+    /// Origin of a player standing on [`floor`] (feet just clear of it).
+    fn standing(x: f32, y: f32) -> Vec3 {
+        Vec3::new(x, y, 24.125)
+    }
+
+    #[test]
+    fn grenade_bounces_lower_each_time_then_rests() {
+        let cm = floor();
+        let flight = grenade_flight(&cm, Vec3::new(0.0, 0.0, 40.0), Vec3::new(300.0, 0.0, 300.0));
+        let rest = *flight.last().unwrap();
+        assert!(rest.z < 1.0, "settles on the floor, got {rest}");
+        assert!(rest.x > 300.0, "carries forward while bouncing, got {rest}");
+        // Peaks between floor contacts shrink (half the normal speed survives each bounce).
+        let peaks: Vec<f32> = flight
+            .windows(3)
+            .filter(|w| w[1].z > w[0].z && w[1].z >= w[2].z && w[1].z > 1.0)
+            .map(|w| w[1].z)
+            .collect();
+        assert!(peaks.len() >= 2, "bounced at least once: {peaks:?}");
+        assert!(peaks.windows(2).all(|p| p[1] < p[0]), "{peaks:?}");
+    }
+
+    #[test]
+    fn grenade_banks_off_a_wall() {
+        // Solid for x < 200: the grenade must come back off the wall, not pass through it.
+        let cm = CollisionModel::half_space([1.0, 0.0, 0.0], 200.0);
+        let flight = grenade_flight(
+            &cm,
+            Vec3::new(400.0, 0.0, 0.0),
+            Vec3::new(-600.0, 0.0, 200.0),
+        );
+        assert!(flight.iter().all(|p| p.x >= 200.0 - 0.1));
+        let last = *flight.last().unwrap();
+        assert!(last.x > 250.0, "rebounded off the wall, got {last}");
+    }
+
+    #[test]
+    fn predicted_jumper_lands_and_keeps_running() {
+        let cm = floor();
+        let path = predict_path(
+            &cm,
+            Vec3::new(0.0, 0.0, 80.0),
+            Vec3::new(0.0, 300.0, 0.0),
+            10,
+        );
+        let end = path[10];
+        assert!(
+            (end.z - 24.0).abs() < 1.0,
+            "landed at standing height, got {end}"
+        );
+        assert!(
+            (end.y - 300.0).abs() < 1.0,
+            "kept its horizontal speed, got {end}"
+        );
+    }
+
+    #[test]
+    fn rocket_takes_the_floor_under_a_strafer() {
+        let cm = floor();
+        let me = standing(0.0, 0.0);
+        let enemy = standing(500.0, 0.0);
+        let aim = solve(
+            &cm,
+            me,
+            enemy,
+            Some(Vec3::new(0.0, 300.0, 0.0)),
+            Weapon::RocketLauncher,
+        )
+        .expect("a splash shot");
+        assert!(aim.impact.z < 2.0, "floor shot, got impact {}", aim.impact);
+        assert!(
+            aim.impact.y > 100.0,
+            "leads the strafer, got impact {}",
+            aim.impact
+        );
+        assert!(aim.pitch > 0.0, "aims down at the floor, got {}", aim.pitch);
+        assert!(aim.expected > 40.0, "expected {}", aim.expected);
+    }
+
+    #[test]
+    fn rocket_meets_an_airborne_target_at_its_landing_spot() {
+        let cm = floor();
+        let me = standing(0.0, 0.0);
+        // Mid-jump and drifting sideways: the floor it lands on is where the splash goes.
+        let enemy = Vec3::new(400.0, 0.0, 90.0);
+        let aim = solve(
+            &cm,
+            me,
+            enemy,
+            Some(Vec3::new(0.0, 200.0, 0.0)),
+            Weapon::RocketLauncher,
+        )
+        .expect("a splash shot");
+        assert!(aim.impact.z < 2.0, "floor shot, got impact {}", aim.impact);
+        assert!(
+            aim.impact.y > 40.0,
+            "under the landing spot, got {}",
+            aim.impact
+        );
+    }
+
+    #[test]
+    fn grenade_lands_near_a_standing_enemy() {
+        let cm = floor();
+        let enemy = standing(350.0, 0.0);
+        let aim = solve(
+            &cm,
+            standing(0.0, 0.0),
+            enemy,
+            None,
+            Weapon::GrenadeLauncher,
+        )
+        .expect("a grenade shot");
+        assert!(
+            (aim.impact - enemy).length() < GRENADE_RADIUS,
+            "goes off within blast radius, got {}",
+            aim.impact
+        );
+        assert!(aim.expected > 40.0, "expected {}", aim.expected);
+    }
+
+    #[test]
+    fn shots_down_from_a_ledge_pitch_past_the_eraser_clamp() {
+        let cm = floor();
+        // Up on a 376 u ledge, the enemy almost underneath: only a steep shot reaches it.
+        let me = Vec3::new(0.0, 0.0, 400.0);
+        let enemy = standing(160.0, 0.0);
+        for weapon in [Weapon::RocketLauncher, Weapon::GrenadeLauncher] {
+            let aim = solve(&cm, me, enemy, None, weapon).expect("a splash shot");
+            assert!(
+                aim.pitch > 45.0 && aim.pitch <= MAX_PITCH_DEG,
+                "{weapon:?} pitch {}",
+                aim.pitch
+            );
+            assert!(aim.expected > 40.0, "{weapon:?} expected {}", aim.expected);
+        }
+    }
+
+    #[test]
+    fn never_picks_a_shot_that_splashes_us() {
+        let cm = floor();
+        let me = standing(0.0, 0.0);
+        for weapon in [Weapon::RocketLauncher, Weapon::GrenadeLauncher] {
+            if let Some(aim) = solve(&cm, me, standing(70.0, 0.0), None, weapon) {
+                assert!(
+                    (aim.impact - me).length() >= weapon.min_safe_distance(),
+                    "{weapon:?} would splash us at {}",
+                    aim.impact
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn other_weapons_are_not_solved() {
+        let cm = floor();
+        let aim = solve(
+            &cm,
+            standing(0.0, 0.0),
+            standing(300.0, 0.0),
+            None,
+            Weapon::Railgun,
+        );
+        assert!(aim.is_none());
+    }
+}
@@ -6,6 +6,8 @@
 
 use glam::Vec3;
 
+use crate::move_ctrl::MovementIntent;
+
 /// Maximum turn rate at combat skill 0 (deg/s). 720°/s → 180° in 0.25 s.
 pub const YAW_SPEED_BASE: f32 = 720.0;
 /// Additional deg/s per combat-skill level above 0 (range 0–4 → +0..+480).
@@ -159,3 +161,13 @@ pub fn move_from_world_dir(world_move_dir: Vec3, view_yaw: f32, face_then_go: bo
         (out_fwd, out_side)
     }
 }
+
+/// Turn `mv`'s view to `(yaw, pitch)` while walking the same world direction as before — for a
+/// brain that aims one way and runs another (the legs set the move, combat then re-aims).
+pub fn turn_keeping_course(mv: &mut MovementIntent, yaw: f32, pitch: f32) {
+    let dir = mv.forward * view_forward(mv.yaw) + mv.side * view_right(mv.yaw);
+    let (fwd, side) = move_from_world_dir(dir, yaw, false);
+    mv.forward = fwd;
+    mv.side = side;
+    mv.look_at(yaw, pitch);
+}
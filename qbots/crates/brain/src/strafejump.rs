@@ -0,0 +1,379 @@
+//! Strafe-jumping — air-control speed technique on straight runs of the route.
+//!
+//! Q2 caps *wished* speed at `pm_maxspeed`, not actual speed: `PM_Accelerate` only adds speed
+//! while `velocity · wishdir < wishspeed`, so a wish direction turned off the velocity keeps
+//! accelerating the player past 320 u/s. In the air (`pm_airaccelerate 0` → accel 1) the gain
+//! per server frame is largest at `cos φ = (wishspeed − accel·frametime·wishspeed) / speed`
+//! ([`best_air_angle`]); the bot holds forward + strafe with its view 45° off that wish
+//! direction, exactly like a player, and picks the strafe side that swings its velocity back
+//! toward the route so consecutive hops zigzag along it.
+//!
+//! Jump timing follows `PM_CheckJump`: a jump needs the button *released* since the last one
+//! (`PMF_JUMP_HELD`) but pressing it in the air is free, so the button is let go for the first
+//! airborne frame and held from then on — the next jump fires on the first grounded frame,
+//! before `PM_Friction` can bleed speed. A chain starts with a circle-jump: the takeoff frame
+//! already wishes [`CIRCLE_JUMP_DEG`] off the route.
+//!
+//! Only runs where the route is straight for the next hop or more ([`straight_run`]) and
+//! that stretch is safe: floor under the whole run (`segment_has_floor`), no lava or blind drop
+//! ahead (`hazard`), no lava on the landing strip, and headroom for the arc. Anything else
+//! drops the chain and leaves ordinary steering in charge.
+
+use glam::Vec3;
+use world::{landing_strip_deadly, segment_has_floor, CollisionModel, MASK_SOLID};
+
+use crate::hazard;
+use crate::move_ctrl::{MovementIntent, JUMP_VELOCITY};
+use crate::steer::move_from_world_dir;
+
+/// Skill (0–1) below which a bot never strafe-jumps.
+pub const MIN_SKILL: f32 = 0.5;
+
+/// `pm_maxspeed`: the wished speed the server clamps forward + side input to.
+const WISH_SPEED: f32 = 300.0;
+/// Air acceleration (`PM_Accelerate(.., 1)` with `pm_airaccelerate 0`).
+const AIR_ACCEL: f32 = 1.0;
+/// `sv_gravity`, for the hop's airtime.
+const GRAVITY: f32 = 800.0;
+
+/// Don't start a chain below this ground speed — running gets there in a few frames anyway.
+const START_SPEED: f32 = 250.0;
+/// Takeoff wish angle off the route for the first hop of a chain.
+pub const CIRCLE_JUMP_DEG: f32 = 30.0;
+/// Forward + strafe puts the wish direction 45° off the view.
+const STRAFE_VIEW_DEG: f32 = 45.0;
+
+/// Route points further than this off the run's line end the straight stretch.
+const CORRIDOR: f32 = 32.0;
+/// Route points looked at for the straight stretch.
+pub const PATH_LOOKAHEAD: usize = 8;
+/// Margin kept between the landing and the end of the straight stretch (the turn).
+const TURN_MARGIN: f32 = 64.0;
+/// The velocity may swing this far off the route before the chain is abandoned.
+const MAX_HEADING_ERROR_DEG: f32 = 50.0;
+/// Headroom the jump arc needs (apex `JUMP_VELOCITY² / 2g` ≈ 46 u).
+const HEADROOM: f32 = JUMP_VELOCITY * JUMP_VELOCITY / (2.0 * GRAVITY);
+
+const PLAYER_MINS: [f32; 3] = [-16.0, -16.0, -24.0];
+const PLAYER_MAXS: [f32; 3] = [16.0, 16.0, 32.0];
+
+/// What the layer needs each tick.
+pub struct StrafeJumpInput<'a> {
+    pub cm: &'a CollisionModel,
+    pub pos: Vec3,
+    pub vel: Vec3,
+    pub grounded: bool,
+    /// The route ahead, current target first ([`crate::nav_mode::Navigator::path_ahead`]).
+    pub path: &'a [[f32; 3]],
+    /// Movement skill, 0–1; below [`MIN_SKILL`] the layer never engages.
+    pub skill: f32,
+    pub frametime: f32,
+}
+
+/// Per-bot strafe-jump chain state.
+#[derive(Debug, Clone, Default)]
+pub struct StrafeJump {
+    /// Inside a chain (jumped at least once and still on a straight run).
+    chaining: bool,
+    /// Grounded last tick — the first airborne frame after it releases the jump button.
+    was_grounded: bool,
+    /// Strafe side: +1 turns the wish direction left of the velocity, −1 right.
+    side: f32,
+    hops: u32,
+}
+
+impl StrafeJump {
+    pub fn new() -> Self {
+        Self {
+            side: 1.0,
+            ..Self::default()
+        }
+    }
+
+    /// True while a chain is running.
+    pub fn active(&self) -> bool {
+        self.chaining
+    }
+
+    /// Hops in the current chain.
+    pub fn hops(&self) -> u32 {
+        self.hops
+    }
+
+    /// Drop the chain (combat, traversal, recovery — anything that takes the legs).
+    pub fn reset(&mut self) {
+        self.chaining = false;
+        self.hops = 0;
+    }
+
+    /// Drive `mv` for one tick of strafe-jumping. Overwrites yaw, forward, side and jump and
+    /// returns `true`, or leaves `mv` alone, drops the chain and returns `false` when the run
+    /// ahead isn't straight, safe or fast enough.
+    pub fn steer(&mut self, mv: &mut MovementIntent, input: &StrafeJumpInput) -> bool {
+        let was_grounded = std::mem::replace(&mut self.was_grounded, input.grounded);
+        if input.skill < MIN_SKILL {
+            self.reset();
+            return false;
+        }
+        let flat = Vec3::new(input.vel.x, input.vel.y, 0.0);
+        let speed = flat.length();
+        let Some((dir, run)) = straight_run(input.pos, input.path) else {
+            self.reset();
+            return false;
+        };
+        // One hop covers `speed × airtime`; the run must hold the landing plus a margin.
+        let hop = speed.max(START_SPEED) * 2.0 * JUMP_VELOCITY / GRAVITY;
+        let heading_ok = flat
+            .try_normalize()
+            .is_some_and(|v| v.dot(dir) >= MAX_HEADING_ERROR_DEG.to_radians().cos());
+        let start_ok = self.chaining || (input.grounded && speed >= START_SPEED);
+        if run < hop + TURN_MARGIN
+            || !heading_ok
+            || !start_ok
+            || !run_is_safe(input.cm, input.pos, dir, hop)
+        {
+            self.reset();
+            return false;
+        }
+
+        let vel_dir = flat / speed;
+        let wish = if input.grounded && !self.chaining {
+            // Circle-jump takeoff: wish off the route toward the first strafe side.
+            self.side = self.correcting_side(input.pos, vel_dir, dir, input.path);
+            rotate(dir, self.side * CIRCLE_JUMP_DEG)
+        } else {
+            if !input.grounded {
+                self.side = self.correcting_side(input.pos, vel_dir, dir, input.path);
+            }
+            // Lower skill under-turns, trading gain for a steadier line.
+            let precision = 0.6 + 0.4 * ((input.skill - MIN_SKILL) / (1.0 - MIN_SKILL)).min(1.0);
+            let phi = best_air_angle(speed, input.frametime).to_degrees() * precision;
+            rotate(vel_dir, self.side * phi)
+        };
+        if input.grounded {
+            self.chaining = true;
+            self.hops += 1;
+        }
+
+        let wish_yaw = wish.y.atan2(wish.x).to_degrees();
+        let yaw = wish_yaw - self.side * STRAFE_VIEW_DEG;
+        let (fwd, side) = move_from_world_dir(wish, yaw, false);
+        mv.yaw = yaw;
+        mv.forward = fwd;
+        mv.side = side;
+        // Release for the first airborne frame after takeoff (clears `PMF_JUMP_HELD`), hold
+        // otherwise so the next jump fires the moment we touch down.
+        mv.jump = !was_grounded || input.grounded;
+        true
+    }
+
+    /// The strafe side that turns the velocity back toward the route line ahead; keeps the
+    /// current side while the velocity already points at it.
+    fn correcting_side(&self, pos: Vec3, vel_dir: Vec3, dir: Vec3, path: &[[f32; 3]]) -> f32 {
+        let anchor = Vec3::from(path[0]);
+        // Aim a little down the line from our projection onto it.
+        let along = (pos - anchor).dot(dir).max(0.0);
+        let target = anchor + dir * (along + 128.0);
+        let to = Vec3::new(target.x - pos.x, target.y - pos.y, 0.0);
+        let cross = vel_dir.x * to.y - vel_dir.y * to.x;
+        if cross.abs() < 1e-3 {
+            self.side
+        } else {
+            cross.signum()
+        }
+    }
+}
+
+/// The air wish angle (radians off the velocity) that adds the most speed in one frame.
+pub fn best_air_angle(speed: f32, frametime: f32) -> f32 {
+    let kick = AIR_ACCEL * frametime * WISH_SPEED;
+    let c = (WISH_SPEED - kick) / speed.max(1.0);
+    if c >= 1.0 {
+        0.0
+    } else {
+        c.max(0.0).acos()
+    }
+}
+
+/// Direction and length of the straight stretch of `path` starting from `pos`: every route point
+/// up to the first that strays more than [`CORRIDOR`] off the line `pos → path[0]` (extended).
+/// `None` when the route is empty or its first point is on top of us.
+pub fn straight_run(pos: Vec3, path: &[[f32; 3]]) -> Option<(Vec3, f32)> {
+    let first = Vec3::from(*path.first()?);
+    let dir = Vec3::new(first.x - pos.x, first.y - pos.y, 0.0).try_normalize()?;
+    let mut run = 0.0f32;
+    for p in path {
+        let d = Vec3::new(p[0] - pos.x, p[1] - pos.y, 0.0);
+        let along = d.dot(dir);
+        let off = (d - dir * along).length();
+        if off > CORRIDOR || along < run {
+            break;
+        }
+        run = along;
+    }
+    Some((dir, run))
+}
+
+/// The next hop along `dir` is survivable: floor all the way, no hazard straight ahead, no lava
+/// on the landing strip, and room overhead and in front for the arc.
+fn run_is_safe(cm: &CollisionModel, pos: Vec3, dir: Vec3, hop: f32) -> bool {
+    let land = pos + dir * hop;
+    if !segment_has_floor(cm, pos.to_array(), land.to_array())
+        || hazard::dir_is_hazardous(cm, pos, dir)
+        || landing_strip_deadly(cm, (land - Vec3::Z * 24.0).to_array(), [dir.x, dir.y])
+    {
+        return false;
+    }
+    let apex = pos + Vec3::Z * HEADROOM;
+    let up = cm.trace(
+        &pos.to_array(),
+        &apex.to_array(),
+        &PLAYER_MINS,
+        &PLAYER_MAXS,
+        MASK_SOLID,
+    );
+    let across = cm.trace(
+        &apex.to_array(),
+        &(land + Vec3::Z * HEADROOM).to_array(),
+        &PLAYER_MINS,
+        &PLAYER_MAXS,
+        MASK_SOLID,
+    );
+    up.fraction >= 1.0 && across.fraction >= 1.0 && !up.startsolid
+}
+
+/// `v` rotated about +Z by `deg` (counter-clockwise = left).
+fn rotate(v: Vec3, deg: f32) -> Vec3 {
+    let (s, c) = deg.to_radians().sin_cos();
+    Vec3::new(v.x * c - v.y * s, v.x * s + v.y * c, 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steer::{view_forward, view_right};
+
+    fn floor() -> CollisionModel {
+        CollisionModel::half_space([0.0, 0.0, 1.0], 0.0)
+    }
+
+    fn straight_path(len: f32) -> Vec<[f32; 3]> {
+        (1..=(len / 64.0) as usize)
+            .map(|i| [i as f32 * 64.0, 0.0, 24.125])
+            .collect()
+    }
+
+    fn input<'a>(
+        cm: &'a CollisionModel,
+        path: &'a [[f32; 3]],
+        vel: Vec3,
+        grounded: bool,
+    ) -> StrafeJumpInput<'a> {
+        StrafeJumpInput {
+            cm,
+            pos: Vec3::new(0.0, 0.0, 24.125),
+            vel,
+            grounded,
+            path,
+            skill: 1.0,
+            frametime: 0.1,
+        }
+    }
+
+    /// Q2 `PM_Accelerate` for one frame, for checking that the emitted input gains speed.
+    fn accelerate(vel: Vec3, mv: &MovementIntent, accel: f32, frametime: f32) -> Vec3 {
+        let wish = view_forward(mv.yaw) * mv.forward + view_right(mv.yaw) * mv.side;
+        let wishdir = wish.normalize();
+        let add = WISH_SPEED - vel.dot(wishdir);
+        if add <= 0.0 {
+            return vel;
+        }
+        vel + wishdir * (accel * frametime * WISH_SPEED).min(add)
+    }
+
+    #[test]
+    fn best_angle_opens_up_with_speed() {
+        assert_eq!(
+            best_air_angle(200.0, 0.1),
+            0.0,
+            "below wishspeed: push straight"
+        );
+        let slow = best_air_angle(320.0, 0.1).to_degrees();
+        let fast = best_air_angle(500.0, 0.1).to_degrees();
+        assert!((slow - 32.5).abs() < 1.0, "{slow}");
+        assert!(fast > slow);
+    }
+
+    #[test]
+    fn straight_run_stops_at_the_turn() {
+        let mut path = straight_path(512.0);
+        path.push([512.0, 200.0, 24.125]);
+        let (dir, run) = straight_run(Vec3::new(0.0, 0.0, 24.125), &path).unwrap();
+        assert!((dir - Vec3::X).length() < 1e-4);
+        assert!((run - 512.0).abs() < 1e-3, "{run}");
+    }
+
+    #[test]
+    fn air_strafing_gains_speed_over_a_chain() {
+        let cm = floor();
+        let path = straight_path(2048.0);
+        let mut sj = StrafeJump::new();
+        let mut vel = Vec3::new(300.0, 0.0, 0.0);
+        let mut mv = MovementIntent::new();
+        assert!(sj.steer(&mut mv, &input(&cm, &path, vel, true)));
+        assert!(mv.jump, "takes off");
+        vel = accelerate(vel, &mv, AIR_ACCEL, 0.1);
+        for _ in 0..6 {
+            let mut mv = MovementIntent::new();
+            assert!(sj.steer(&mut mv, &input(&cm, &path, vel, false)));
+            vel = accelerate(vel, &mv, AIR_ACCEL, 0.1);
+        }
+        let speed = Vec3::new(vel.x, vel.y, 0.0).length();
+        assert!(speed > 400.0, "one hop of air strafing: {speed}");
+        let heading = vel.y.atan2(vel.x).to_degrees().abs();
+        assert!(heading < 30.0, "still running along the route: {heading}");
+    }
+
+    #[test]
+    fn releases_jump_for_one_frame_after_takeoff() {
+        let cm = floor();
+        let path = straight_path(2048.0);
+        let mut sj = StrafeJump::new();
+        let vel = Vec3::new(320.0, 0.0, 0.0);
+        let mut jumps = Vec::new();
+        for grounded in [true, false, false, false] {
+            let mut mv = MovementIntent::new();
+            assert!(sj.steer(&mut mv, &input(&cm, &path, vel, grounded)));
+            jumps.push(mv.jump);
+        }
+        assert_eq!(jumps, [true, false, true, true]);
+    }
+
+    #[test]
+    fn gated_by_skill_speed_and_route() {
+        let cm = floor();
+        let path = straight_path(2048.0);
+        let fast = Vec3::new(320.0, 0.0, 0.0);
+        let mut mv = MovementIntent::new();
+        let mut low = input(&cm, &path, fast, true);
+        low.skill = 0.3;
+        assert!(!StrafeJump::new().steer(&mut mv, &low));
+        let slow = input(&cm, &path, Vec3::new(100.0, 0.0, 0.0), true);
+        assert!(
+            !StrafeJump::new().steer(&mut mv, &slow),
+            "builds speed first"
+        );
+        let short = straight_path(192.0);
+        assert!(!StrafeJump::new().steer(&mut mv, &input(&cm, &short, fast, true)));
+    }
+
+    #[test]
+    fn refuses_a_run_without_floor() {
+        // Open air: nothing to land on within a fall.
+        let cm = CollisionModel::half_space([0.0, 0.0, 1.0], -100_000.0);
+        let path = straight_path(2048.0);
+        let mut mv = MovementIntent::new();
+        let vel = Vec3::new(320.0, 0.0, 0.0);
+        assert!(!StrafeJump::new().steer(&mut mv, &input(&cm, &path, vel, true)));
+    }
+}
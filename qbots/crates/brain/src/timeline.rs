@@ -0,0 +1,378 @@
+//! Decision timeline — what a brain decided each tick, and why.
+//!
+//! The movement recorder ([`crate::recorder`]) says where a bot went; the timeline says what it
+//! was thinking on the way: its state label, the target and goal it picked, the alternatives it
+//! scored, the weapon it held or asked for, and which nav backend was steering. A brain fills
+//! the middle of that through [`Brain::decision`](crate::Brain::decision); the caller adds the
+//! rest from the frame ([`TickRecord::capture`]).
+//!
+//! # File format
+//!
+//! JSON lines. Line 1 is a [`Header`]; every further line is one [`TickRecord`]:
+//!
+//! ```text
+//! {"bot":"uti_1","brain":"utility","navmode":"astar","map":"q2dm1","started":1760000000}
+//! {"t":0.1,"frame":412,"pos":[1088,-344,24],"health":100,"state":"roam","weapon":"Blaster","nav":"astar","options":[{"label":"roam","score":0.15,"chosen":true}]}
+//! ```
+//!
+//! `frame` is the serverframe, the same column the movement log carries, so a scenario's
+//! `<ts>.<bot>.timeline.jsonl` lines up row for row with its `<ts>.<bot>.log`. Empty fields
+//! are left out. Positions are rounded to whole units and scores to three decimals — the file
+//! is for reading, not replaying.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use glam::Vec3;
+use serde::{Deserialize, Serialize};
+use world::NavGraph;
+
+use crate::brains::core::{Brain, BrainOutput};
+use crate::nav::NavGoal;
+use crate::nav_mode::Navigator;
+use crate::perception::Worldview;
+
+/// Flush the file every this many records, so a killed bot loses at most a second of ticks.
+const FLUSH_EVERY: u32 = 10;
+
+/// What a brain decided on its last tick, beyond its `status` label.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Decision {
+    /// Entity number of the enemy it is fighting or chasing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<i32>,
+    /// Where it is navigating to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<[f32; 3]>,
+    /// The alternatives it weighed, best first (empty for a brain that doesn't score).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<Alternative>,
+}
+
+impl Decision {
+    /// A decision heading for `goal` (rounded), resolving a waypoint goal through `graph`.
+    pub fn heading(goal: Option<&NavGoal>, graph: Option<&NavGraph>) -> Self {
+        Self {
+            goal: goal.and_then(|g| goal_pos(g, graph)).map(round3),
+            ..Self::default()
+        }
+    }
+
+    /// The chosen alternative's label, if the brain scored any.
+    pub fn chosen(&self) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|o| o.chosen)
+            .map(|o| o.label.as_str())
+    }
+}
+
+/// One scored alternative.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alternative {
+    /// What it would do, e.g. `grab@4` or `enemy#12`.
+    pub label: String,
+    pub score: f32,
+    /// The alternative the brain is acting on.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub chosen: bool,
+}
+
+impl Alternative {
+    pub fn new(label: impl Into<String>, score: f32, chosen: bool) -> Self {
+        Self {
+            label: label.into(),
+            score: (score * 1000.0).round() / 1000.0,
+            chosen,
+        }
+    }
+}
+
+/// The first line of a timeline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Header {
+    pub bot: String,
+    /// [`brain_tag`](crate::brain_tag) of the brain that wrote it.
+    pub brain: String,
+    pub navmode: String,
+    pub map: String,
+    /// Unix seconds when the file was started.
+    pub started: u64,
+}
+
+/// One tick of a timeline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TickRecord {
+    /// Seconds since the file was started.
+    pub t: f32,
+    pub frame: i32,
+    pub pos: [f32; 3],
+    pub health: i32,
+    /// The brain's [`status`](crate::Brain::status) label.
+    pub state: String,
+    /// The weapon held this frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weapon: Option<String>,
+    /// A weapon switch the brain asked for this tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub switch_to: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fire: bool,
+    /// Which nav backend steered ([`Navigator::backend`]); `-` before the map loads.
+    pub nav: String,
+    /// The nav node being walked to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waypoint: Option<[f32; 3]>,
+    #[serde(flatten)]
+    pub decision: Decision,
+}
+
+impl TickRecord {
+    /// Record the tick `brain` just ran: `out` is what it returned, `nav` the navigator it drove.
+    pub fn capture(
+        t: f32,
+        frame: i32,
+        view: &Worldview,
+        brain: &dyn Brain,
+        nav: Option<&dyn Navigator>,
+        out: &BrainOutput,
+    ) -> Self {
+        let ss = view.self_state();
+        Self {
+            t: (t * 1000.0).round() / 1000.0,
+            frame,
+            pos: round3(ss.origin.to_array()),
+            health: ss.health,
+            state: brain.status().to_string(),
+            weapon: ss.held_weapon.map(|w| w.name().to_string()),
+            switch_to: out.weapon_request.map(|w| w.name().to_string()),
+            fire: out.intent.attack,
+            nav: nav.map_or("-", |n| n.backend()).to_string(),
+            waypoint: nav.and_then(|n| n.current_waypoint_pos()).map(round3),
+            decision: brain.decision(),
+        }
+    }
+
+    /// What the bot was doing, in one word: the chosen alternative, else the state label.
+    pub fn headline(&self) -> &str {
+        self.decision.chosen().unwrap_or(&self.state)
+    }
+}
+
+/// A run of consecutive ticks with the same [`headline`](TickRecord::headline).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub headline: String,
+    /// Index of the first and last tick in the span.
+    pub first: usize,
+    pub last: usize,
+}
+
+/// A timeline file, read back.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    pub header: Header,
+    pub ticks: Vec<TickRecord>,
+}
+
+impl Timeline {
+    /// Read a timeline file. A torn last line (the bot was killed mid-write) is dropped.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        let first = lines
+            .next()
+            .ok_or_else(|| format!("{}: empty timeline", path.display()))?
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let header: Header = serde_json::from_str(&first)
+            .map_err(|e| format!("{}: bad header: {e}", path.display()))?;
+        let lines: Vec<String> = lines
+            .collect::<io::Result<_>>()
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+        let mut ticks = Vec::with_capacity(lines.len());
+        for (k, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(t) => ticks.push(t),
+                Err(_) if k + 1 == lines.len() => break,
+                Err(e) => return Err(format!("{}:{}: {e}", path.display(), k + 2)),
+            }
+        }
+        Ok(Self { header, ticks })
+    }
+
+    /// The ticks collapsed into runs of the same headline.
+    pub fn spans(&self) -> Vec<Span> {
+        let mut spans: Vec<Span> = Vec::new();
+        for (k, t) in self.ticks.iter().enumerate() {
+            match spans.last_mut() {
+                Some(s) if s.headline == t.headline() => s.last = k,
+                _ => spans.push(Span {
+                    headline: t.headline().to_string(),
+                    first: k,
+                    last: k,
+                }),
+            }
+        }
+        spans
+    }
+}
+
+/// Streams [`TickRecord`]s to a timeline file.
+pub struct TimelineWriter {
+    out: BufWriter<File>,
+    unflushed: u32,
+}
+
+impl TimelineWriter {
+    /// Create `path` (and its directory) and write the header line.
+    pub fn create(path: &Path, header: &Header) -> io::Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut w = Self {
+            out: BufWriter::new(File::create(path)?),
+            unflushed: 0,
+        };
+        w.line(header)?;
+        w.out.flush()?;
+        Ok(w)
+    }
+
+    pub fn record(&mut self, tick: &TickRecord) -> io::Result<()> {
+        self.line(tick)?;
+        self.unflushed += 1;
+        if self.unflushed >= FLUSH_EVERY {
+            self.unflushed = 0;
+            self.out.flush()?;
+        }
+        Ok(())
+    }
+
+    fn line(&mut self, value: &impl Serialize) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, value)?;
+        self.out.write_all(b"\n")
+    }
+}
+
+/// Where `goal` is in the world; a waypoint needs the graph to say.
+fn goal_pos(goal: &NavGoal, graph: Option<&NavGraph>) -> Option<[f32; 3]> {
+    match goal {
+        NavGoal::Position(p) | NavGoal::Entity(p) => Some(p.to_array()),
+        NavGoal::Waypoint(n) => graph.map(|g| g.node_pos(*n)),
+    }
+}
+
+fn round3(p: [f32; 3]) -> [f32; 3] {
+    p.map(f32::round)
+}
+
+/// Round a world position for a [`Decision`].
+pub fn rounded(p: Vec3) -> [f32; 3] {
+    round3(p.to_array())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(frame: i32, state: &str, options: &[(&str, f32, bool)]) -> TickRecord {
+        TickRecord {
+            t: frame as f32 * 0.1,
+            frame,
+            pos: [0.0; 3],
+            health: 100,
+            state: state.to_string(),
+            weapon: Some("Blaster".to_string()),
+            switch_to: None,
+            fire: false,
+            nav: "astar".to_string(),
+            waypoint: None,
+            decision: Decision {
+                target: None,
+                goal: None,
+                options: options
+                    .iter()
+                    .map(|&(l, s, c)| Alternative::new(l, s, c))
+                    .collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn a_written_timeline_reads_back_and_collapses_into_spans() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/1.qb0.jsonl");
+        let header = Header {
+            bot: "qb0".into(),
+            brain: "utility".into(),
+            navmode: "astar".into(),
+            map: "q2dm1".into(),
+            started: 1,
+        };
+        let ticks = [
+            tick(1, "roam", &[("roam", 0.15, true), ("grab@4", 0.1, false)]),
+            tick(
+                2,
+                "grab",
+                &[("grab@4", 0.41234, true), ("roam", 0.15, false)],
+            ),
+            tick(3, "grab", &[("grab@4", 0.5, true)]),
+            tick(4, "engage", &[]),
+        ];
+        {
+            let mut w = TimelineWriter::create(&path, &header).unwrap();
+            for t in &ticks {
+                w.record(t).unwrap();
+            }
+        }
+        let read = Timeline::read(&path).unwrap();
+        assert_eq!(read.header, header);
+        assert_eq!(read.ticks, ticks);
+        assert_eq!(read.ticks[1].decision.options[0].score, 0.412);
+
+        let spans: Vec<_> = read
+            .spans()
+            .into_iter()
+            .map(|s| (s.headline, s.first, s.last))
+            .collect();
+        assert_eq!(
+            spans,
+            [
+                ("roam".to_string(), 0, 0),
+                ("grab@4".to_string(), 1, 2),
+                ("engage".to_string(), 3, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_fields_are_left_out_and_a_torn_tail_is_dropped() {
+        let line = serde_json::to_string(&tick(7, "roam", &[])).unwrap();
+        assert_eq!(
+            line,
+            r#"{"t":0.7,"frame":7,"pos":[0.0,0.0,0.0],"health":100,"state":"roam","weapon":"Blaster","nav":"astar"}"#
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("torn.jsonl");
+        let header = r#"{"bot":"a","brain":"q3","navmode":"astar","map":"m","started":0}"#;
+        std::fs::write(&path, format!("{header}\n{line}\n{{\"t\":0.8,\"fra")).unwrap();
+        assert_eq!(Timeline::read(&path).unwrap().ticks.len(), 1);
+        // A bad line in the middle is an error, not silently skipped.
+        std::fs::write(&path, format!("{header}\nnope\n{line}\n")).unwrap();
+        assert!(Timeline::read(&path).unwrap_err().contains(":2:"));
+    }
+
+    #[test]
+    fn a_waypoint_goal_resolves_through_the_graph() {
+        let g = NavGraph::from_raw(vec![[1.2, 2.0, 3.0], [9.6, 8.0, 7.0]], vec![vec![], vec![]]);
+        let d = Decision::heading(Some(&NavGoal::Waypoint(1)), Some(&g));
+        assert_eq!(d.goal, Some([10.0, 8.0, 7.0]));
+        assert_eq!(
+            Decision::heading(Some(&NavGoal::Waypoint(1)), None).goal,
+            None
+        );
+        assert_eq!(Decision::heading(None, Some(&g)), Decision::default());
+    }
+}
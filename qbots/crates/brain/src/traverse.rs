@@ -12,7 +12,8 @@
 //! Movement is **owned by the executor while a traversal edge is active**; aim (the view) is
 //! steered by the executor too (you cannot free-aim while climbing a ladder — the bot fires along
 //! the traversal heading, which the plan accepts for v1). The brain keeps its *fire decision*
-//! (the attack button) — the executor never touches `attack`.
+//! (the attack button) — the executor never touches `attack`, with one exception: the single
+//! launch frame of a rocket jump, where firing at our own feet IS the traversal.
 //!
 //! Each tick the brain:
 //! 1. computes its normal steering (`view_yaw`, `steer_fwd`, `steer_side`),
@@ -21,26 +22,57 @@
 //!    a lift or a slow surface bob is not a wedge; recovery would false-fire and steer away),
 //! 3. runs its normal move/recovery only when *not* traversing, then
 //! 4. calls [`TraversalExecutor::apply`], which **overwrites** the movement axes when a traversal
-//!    is active and returns the recorder flag (`'S'` swim, `'P'` ride, `'L'` ladder).
+//!    is active and returns the recorder flag (`'S'` swim, `'P'` ride, `'L'` ladder,
+//!    `'R'` rocket jump).
 //!
 //! `ride.rs` / `water.rs` stay the pure helpers they always were; this module is the stateful
 //! sequencer over them (the `active_ride` edge lock + `ride_boarded` carry state + swim
-//! `exit_ticks` climb-out hysteresis).
+//! `exit_ticks` climb-out hysteresis + the rocket-jump approach/aim/fire/airborne phases).
 
 use glam::Vec3;
-use world::{CollisionModel, RideInfo};
+use world::{CollisionModel, RideInfo, RocketJumpInfo};
 
 use crate::move_ctrl::MovementIntent;
 use crate::nav_mode::Navigator;
-use crate::perception::Worldview;
+use crate::perception::{SelfState, Worldview};
 use crate::steer::move_from_world_dir;
 use crate::water::{
     is_swimming, water_level, EXIT_HYSTERESIS_TICKS, EXIT_LOOKUP_PITCH, SWIM_VERT_SCALE,
 };
+use crate::weapons::Weapon;
 
 /// The `PMF_ON_GROUND` bit in the playerstate `pm_flags` — "standing on solid ground this frame"
 /// (`SelfState::flags`, a `u32`).
-const PMF_ON_GROUND: u32 = 4;
+pub(crate) const PMF_ON_GROUND: u32 = 4;
+
+/// Horizontal distance (units) from the launch node at which the rocket-jump approach stops and
+/// the bot plants itself to aim.
+const RJ_LAUNCH_RADIUS: f32 = 20.0;
+/// Seconds airborne before a grounded frame counts as the landing — the launch frame itself is
+/// still grounded, and the first few ticks of the rise may be too.
+const RJ_MIN_AIR: f32 = 0.3;
+/// Seconds after which an unfinished rocket-jump leg is abandoned (stuck on the approach, the
+/// rocket never came out, or we landed somewhere we never registered as grounded).
+const RJ_TIMEOUT: f32 = 2.5;
+/// Health kept in reserve on top of the launch's self-damage: arriving on the ledge one hit from
+/// death is worse than walking the long way.
+const RJ_HEALTH_MARGIN: i32 = 25;
+
+/// True when `me` can afford a rocket jump right now: the rocket launcher is in hand with a
+/// rocket loaded, and health covers the launch's self-damage plus [`RJ_HEALTH_MARGIN`].
+pub fn rocket_jump_ready(me: &SelfState) -> bool {
+    let (_, cost) = world::navgraph::rocket_jump_launch(world::navgraph::ROCKET_JUMP_BLAST_DIST);
+    me.held_weapon == Some(Weapon::RocketLauncher)
+        && me.held_ammo() >= world::navgraph::ROCKET_JUMP_AMMO
+        && me.health > cost.ceil() as i32 + RJ_HEALTH_MARGIN
+}
+
+/// Grant `nav` rocket-jump edges for this tick's plan only when the persona rocket-jumps
+/// (`jumper`) and `me` is [`rocket_jump_ready`]. Brains call it every tick before `set_goal`,
+/// so the plan sees the permission at once.
+pub fn gate_rocket_jumps(nav: &mut dyn Navigator, jumper: bool, me: &SelfState) {
+    nav.set_rocket_jump_allowed(jumper && rocket_jump_ready(me));
+}
 
 /// The swim / ride gates for one frame — the brain suspends recovery + jump-edge activation while
 /// either holds. Returned by [`TraversalExecutor::gates`], consumed as a plain read.
@@ -50,12 +82,14 @@ pub struct TraversalGates {
     pub swimming: bool,
     /// On a ride edge, or mid-ride (boarded) — the platform is carrying us.
     pub ride_active: bool,
+    /// On a rocket-jump edge, or mid-launch (locked until landing) — see [`RocketJumpPhase`].
+    pub rocket_jump: bool,
 }
 
 impl TraversalGates {
-    /// Either traversal mode is engaged this frame.
+    /// Any traversal mode is engaged this frame.
     pub fn any(&self) -> bool {
-        self.swimming || self.ride_active
+        self.swimming || self.ride_active || self.rocket_jump
     }
 }
 
@@ -89,6 +123,27 @@ enum LiftPhase {
     BackOff,
 }
 
+/// Rocket-jump leg phase: walk onto the launch node → look straight down (one frame, so the
+/// usercmd angles settle before the shot) → jump + fire at our feet → steer onto the ledge in the
+/// air until we land.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RocketJumpPhase {
+    Approach,
+    Aim,
+    Fire,
+    Airborne,
+}
+
+/// The rocket-jump edge we are mid-traversal on, locked like `active_ride` so a nav waypoint
+/// advance mid-flight can't drop the air-steer.
+#[derive(Debug, Clone, Copy)]
+struct RocketJumpLeg {
+    info: RocketJumpInfo,
+    phase: RocketJumpPhase,
+    /// Seconds in the current leg (`Airborne`: since the launch).
+    timer: f32,
+}
+
 /// Stateful traversal sequencer shared by every brain (Plan 46). Owns only the small amount of
 /// state a traversal needs to persist across frames; the geometry helpers live in `ride.rs` /
 /// `water.rs`.
@@ -115,6 +170,8 @@ pub struct TraversalExecutor {
     /// Vertical-lift de-conflict phase + its timer (Plan 31).
     lift_phase: LiftPhase,
     lift_timer: f32,
+    /// The rocket jump in progress, if any.
+    rocket_jump: Option<RocketJumpLeg>,
 }
 
 impl TraversalExecutor {
@@ -144,13 +201,34 @@ impl TraversalExecutor {
             self.ride_boarded = false;
             self.active_ride = None;
         }
+        // Rocket jump: latch a leg when the nav reaches a rocket-jump edge; once latched it holds
+        // until `apply_rocket_jump` lands or times it out.
+        match (&mut self.rocket_jump, nav.current_rocket_jump()) {
+            (Some(leg), _) => {
+                leg.timer += dt;
+                if leg.timer > RJ_TIMEOUT {
+                    tracing::debug!(phase = ?leg.phase, "rocket jump timed out");
+                    self.rocket_jump = None;
+                }
+            }
+            (None, Some(info)) => {
+                self.rocket_jump = Some(RocketJumpLeg {
+                    info,
+                    phase: RocketJumpPhase::Approach,
+                    timer: 0.0,
+                });
+            }
+            (None, None) => {}
+        }
+        let rocket_jump = self.rocket_jump.is_some();
         // EVT counter (Plan 47 T1): a traversal leg just ended (falling edge of any traversal
-        // mode) — greppable proof of completed swims / rides / ladder climbs.
-        if !swimming && !ride_active {
+        // mode) — greppable proof of completed swims / rides / ladder climbs / rocket jumps.
+        if !swimming && !ride_active && !rocket_jump {
             if let Some(k) = self.last_flag.take() {
                 let kind = match k {
                     'S' => "swim",
                     'L' => "ladder",
+                    'R' => "rocket_jump",
                     _ => "ride",
                 };
                 tracing::debug!(kind, "EVT traverse done");
@@ -159,6 +237,7 @@ impl TraversalExecutor {
         TraversalGates {
             swimming,
             ride_active,
+            rocket_jump,
         }
     }
 
@@ -171,8 +250,9 @@ impl TraversalExecutor {
 
     /// Apply the traversal movement override into `mv` when a traversal is active this frame.
     ///
-    /// Priority: **ride overrides swim** (a ride edge in water rides). Returns the recorder flag
-    /// (`'P'` ride, `'L'` ladder, `'S'` swim) and the forward-progress intent (for the recorder's
+    /// Priority: **ride overrides rocket jump overrides swim** (a ride edge in water rides;
+    /// rocket-jump edges never launch from water). Returns the recorder flag
+    /// (`'P'` ride, `'L'` ladder, `'R'` rocket jump, `'S'` swim) and the forward-progress intent (for the recorder's
     /// `H` flag) via [`TraversalApply`]; returns `None` when no traversal is active (the brain's
     /// own steering / recovery output stands).
     ///
@@ -188,6 +268,8 @@ impl TraversalExecutor {
     ) -> Option<TraversalApply> {
         let result = if gates.ride_active {
             self.apply_ride(mv, nav, frame)
+        } else if gates.rocket_jump {
+            self.apply_rocket_jump(mv, frame)
         } else if gates.swimming {
             Some(self.apply_swim(mv, nav, frame))
         } else {
@@ -200,6 +282,86 @@ impl TraversalExecutor {
         result
     }
 
+    /// Drive the latched rocket-jump leg. Standard Q2 rocket jump: stand on the launch spot,
+    /// look straight down, then jump and fire on the same frame — `ClientThink` runs the jump's
+    /// pmove before `Think_Weapon` spawns the rocket, so the blast catches us just off the
+    /// floor. In the air, steer toward the landing (Q2 air control) until we touch down.
+    /// Returns `None` (and clears the leg) once landed.
+    fn apply_rocket_jump(
+        &mut self,
+        mv: &mut MovementIntent,
+        frame: &TraversalFrame,
+    ) -> Option<TraversalApply> {
+        let TraversalFrame {
+            view,
+            pos,
+            view_yaw,
+            ..
+        } = *frame;
+        let leg = self.rocket_jump.as_mut()?;
+        // Before the shot, re-check the launcher/ammo/health every frame: a mid-approach weapon
+        // switch or a hit must not end with a shotgun blast at our feet. The nav replans once
+        // the brain's permission drops.
+        if leg.phase != RocketJumpPhase::Airborne && !rocket_jump_ready(&view.self_state) {
+            tracing::debug!(phase = ?leg.phase, "rocket jump aborted — not ready");
+            self.rocket_jump = None;
+            return None;
+        }
+        let grounded = view.self_state.flags & PMF_ON_GROUND != 0;
+        let launch = Vec3::from(leg.info.launch);
+        let landing = Vec3::from(leg.info.landing);
+        // Movement is view-relative, so steer relative to whichever yaw we look along.
+        let steer = |mv: &mut MovementIntent, target: Vec3, yaw: f32| -> f32 {
+            let dir = (target - pos).truncate().extend(0.0).normalize_or_zero();
+            let (f, s) = move_from_world_dir(dir, yaw, true);
+            mv.look_at(yaw, 0.0);
+            mv.move_forward(f);
+            mv.move_side(s);
+            f
+        };
+        let intent_forward = match leg.phase {
+            RocketJumpPhase::Approach => {
+                let hd = (launch - pos).truncate().length();
+                if hd <= RJ_LAUNCH_RADIUS && grounded {
+                    leg.phase = RocketJumpPhase::Aim;
+                }
+                mv.jump = false;
+                steer(mv, launch, view_yaw)
+            }
+            RocketJumpPhase::Aim => {
+                mv.look_at(leg.info.launch_yaw, 89.0);
+                mv.move_forward(0.0);
+                mv.move_side(0.0);
+                mv.jump = false;
+                leg.phase = RocketJumpPhase::Fire;
+                0.0
+            }
+            RocketJumpPhase::Fire => {
+                mv.look_at(leg.info.launch_yaw, 89.0);
+                mv.move_forward(1.0);
+                mv.move_side(0.0);
+                mv.jump();
+                mv.attack();
+                tracing::debug!(health_cost = leg.info.health_cost, "EVT rocket jump");
+                leg.phase = RocketJumpPhase::Airborne;
+                leg.timer = 0.0;
+                1.0
+            }
+            RocketJumpPhase::Airborne => {
+                if grounded && leg.timer >= RJ_MIN_AIR {
+                    self.rocket_jump = None;
+                    return None;
+                }
+                mv.jump = false;
+                steer(mv, landing, leg.info.launch_yaw)
+            }
+        };
+        Some(TraversalApply {
+            flag: 'R',
+            intent_forward,
+        })
+    }
+
     /// Swim toward the 3-D look-ahead (Plan 40 T2/T3), lifted verbatim from `RunTesterBrain`.
     /// Sustained vertical thrust (never `jump()` in water — a one-shot launch); Q2 water-jump
     /// climb-out onto a dry ledge via `exit_ticks` hysteresis.
@@ -523,7 +685,8 @@ impl TraversalExecutor {
 /// The result of an active traversal frame: the recorder flag + forward-progress intent.
 #[derive(Debug, Clone, Copy)]
 pub struct TraversalApply {
-    /// Recorder flag char: `'S'` swimming, `'P'` riding a platform/lift/train, `'L'` on a ladder.
+    /// Recorder flag char: `'S'` swimming, `'P'` riding a platform/lift/train, `'L'` on a ladder,
+    /// `'R'` rocket jumping.
     pub flag: char,
     /// Forward-progress intent for the recorder's hindered (`H`) flag — `0.0` while deliberately
     /// standing still (waiting for / carried by a platform), the nav-step forward otherwise.
@@ -545,6 +708,7 @@ mod tests {
         ride: Option<RideInfo>,
         pursue: Option<Vec3>,
         waypoint: Option<[f32; 3]>,
+        rocket: Option<RocketJumpInfo>,
     }
     impl Navigator for StubNav {
         fn update(&mut self, _pos: Vec3, _cm: Option<&CollisionModel>) -> bool {
@@ -569,6 +733,9 @@ mod tests {
         fn current_ride_info(&self) -> Option<RideInfo> {
             self.ride
         }
+        fn current_rocket_jump(&self) -> Option<RocketJumpInfo> {
+            self.rocket
+        }
         fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
             self.waypoint
         }
@@ -890,4 +1057,136 @@ mod tests {
         let gates = ex.gates(&nav, Some(&cm), Vec3::ZERO, 0.1);
         assert!(!gates.ride_active);
     }
+
+    fn arm_rocket_launcher(view: &mut Worldview) {
+        view.self_state.health = 100;
+        view.self_state.held_weapon = Some(Weapon::RocketLauncher);
+        view.self_state.ammo[crate::perception::STAT_AMMO] = 5;
+    }
+
+    fn rocket_info() -> RocketJumpInfo {
+        RocketJumpInfo {
+            launch: [0.0, 0.0, 24.0],
+            landing: [112.0, 0.0, 184.0],
+            launch_yaw: 0.0,
+            health_cost: 50.0,
+        }
+    }
+
+    /// Rocket-jump leg: approach the launch spot, look down, jump + fire on one frame, then
+    /// air-steer (flag 'R', leg locked even after the nav drops the edge) until landing.
+    #[test]
+    fn rocket_jump_aims_down_fires_and_lands() {
+        let mut ex = TraversalExecutor::new();
+        let cm = empty_cm();
+        let mut nav = StubNav {
+            rocket: Some(rocket_info()),
+            ..Default::default()
+        };
+        let step = |ex: &mut TraversalExecutor, nav: &StubNav, at: [f32; 3], grounded: bool| {
+            let mut view = view_at(at, grounded, &[]);
+            arm_rocket_launcher(&mut view);
+            let pos = Vec3::from(at);
+            let gates = ex.gates(nav, Some(&cm), pos, 0.1);
+            let mut mv = MovementIntent::new();
+            let frame = TraversalFrame {
+                view: &view,
+                cm: Some(&cm),
+                pos,
+                view_yaw: 0.0,
+                steer_fwd: 1.0,
+                steer_side: 0.0,
+                dt: 0.1,
+            };
+            let out = ex.apply(&mut mv, gates, nav, &frame);
+            (gates, out, mv)
+        };
+
+        // Approach: still 100u short → walk forward, no fire.
+        let (gates, out, mv) = step(&mut ex, &nav, [-100.0, 0.0, 24.0], true);
+        assert!(gates.rocket_jump && gates.any());
+        assert_eq!(out.unwrap().flag, 'R');
+        assert!(mv.forward > 0.0 && !mv.attack && !mv.jump);
+
+        // On the spot → aim phase next frame.
+        step(&mut ex, &nav, [0.0, 0.0, 24.0], true);
+        let (_, _, mv) = step(&mut ex, &nav, [0.0, 0.0, 24.0], true);
+        assert_eq!(mv.pitch, 89.0, "look straight down");
+        assert!(!mv.attack);
+
+        // Fire: jump + attack on the same frame.
+        let (_, _, mv) = step(&mut ex, &nav, [0.0, 0.0, 24.0], true);
+        assert!(mv.jump && mv.attack, "launch frame jumps and fires");
+
+        // Airborne: the nav already advanced off the edge, the leg stays locked.
+        nav.rocket = None;
+        let (gates, out, mv) = step(&mut ex, &nav, [40.0, 0.0, 150.0], false);
+        assert!(gates.rocket_jump);
+        assert_eq!(out.unwrap().flag, 'R');
+        assert!(mv.forward > 0.0 && !mv.attack, "air-steer toward the ledge");
+
+        // Landed (grounded after the minimum air time) → leg done.
+        step(&mut ex, &nav, [100.0, 0.0, 184.0], false);
+        let (_, out, _) = step(&mut ex, &nav, [112.0, 0.0, 184.0], true);
+        assert!(out.is_none(), "landing ends the traversal");
+        let gates = ex.gates(&nav, Some(&cm), Vec3::new(112.0, 0.0, 184.0), 0.1);
+        assert!(!gates.rocket_jump);
+    }
+
+    /// A leg that never launches (the bot can't reach the spot) is abandoned on timeout.
+    #[test]
+    fn rocket_jump_times_out() {
+        let mut ex = TraversalExecutor::new();
+        let cm = empty_cm();
+        let nav = StubNav {
+            rocket: Some(rocket_info()),
+            ..Default::default()
+        };
+        assert!(ex.gates(&nav, Some(&cm), Vec3::ZERO, 0.1).rocket_jump);
+        let nav = StubNav::default();
+        let gates = ex.gates(&nav, Some(&cm), Vec3::ZERO, RJ_TIMEOUT + 0.1);
+        assert!(!gates.rocket_jump);
+    }
+
+    /// Unarmed at the launch spot → the leg aborts instead of firing the wrong weapon.
+    #[test]
+    fn rocket_jump_aborts_without_launcher() {
+        let mut ex = TraversalExecutor::new();
+        let cm = empty_cm();
+        let nav = StubNav {
+            rocket: Some(rocket_info()),
+            ..Default::default()
+        };
+        let view = view_at([0.0, 0.0, 24.0], true, &[]);
+        let pos = Vec3::new(0.0, 0.0, 24.0);
+        let gates = ex.gates(&nav, Some(&cm), pos, 0.1);
+        let mut mv = MovementIntent::new();
+        let frame = TraversalFrame {
+            view: &view,
+            cm: Some(&cm),
+            pos,
+            view_yaw: 0.0,
+            steer_fwd: 0.0,
+            steer_side: 0.0,
+            dt: 0.1,
+        };
+        assert!(ex.apply(&mut mv, gates, &nav, &frame).is_none());
+        assert!(!mv.attack);
+    }
+
+    #[test]
+    fn rocket_jump_ready_needs_launcher_ammo_and_health() {
+        let mut view = view_at([0.0; 3], true, &[]);
+        arm_rocket_launcher(&mut view);
+        let mut me = view.self_state;
+        assert!(rocket_jump_ready(&me));
+        me.health = 70; // 50 self-damage + 25 margin
+        assert!(!rocket_jump_ready(&me));
+        me.health = 100;
+        me.ammo[crate::perception::STAT_AMMO] = 0;
+        assert!(!rocket_jump_ready(&me));
+        me.ammo[crate::perception::STAT_AMMO] = 5;
+        me.held_weapon = Some(Weapon::Railgun);
+        assert!(!rocket_jump_ready(&me));
+    }
 }
@@ -9,9 +9,11 @@
 //! ownership is tracked optimistically: we request `use <name>` and the server
 //! grants it only if we own the weapon.
 
+use serde::{Deserialize, Serialize};
+
 /// Q2 weapons. Discriminant values are arbitrary (NOT impulse numbers); they
 /// only need a stable ordering. Switching is done via [`Weapon::name`] stringcmds.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Weapon {
     Blaster,
     Shotgun,
@@ -57,6 +59,19 @@ impl Weapon {
         }
     }
 
+    /// The weapon a human-typed [`Weapon::name`] means, ignoring case, spaces, `-` and `_`
+    /// (`railgun`, `super-shotgun`, `BFG10K`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let squash = |s: &str| -> String {
+            s.chars()
+                .filter(char::is_ascii_alphanumeric)
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        };
+        let want = squash(name);
+        ALL_WEAPONS.into_iter().find(|w| squash(w.name()) == want)
+    }
+
     /// Projectile speed in world units/sec, or `None` for hitscan weapons.
     /// Sources: `fire_blaster` speed=1000, `fire_rocket` speed=650,
     /// grenade 400–800 (default hold ~600), hyperblaster fires blaster bolts.
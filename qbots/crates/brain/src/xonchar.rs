@@ -109,6 +109,24 @@ impl XonSkill {
     pub fn ping(&self) -> f32 {
         self.skill + self.axes.ping
     }
+    /// Rocket-jumps ledges (nav [`world::EdgeKind::RocketJump`] edges): only strong movers —
+    /// `skill + bot_moveskill ≥ 7`, the same sum that gates havocbot's advanced keyboard tiers.
+    pub fn rocket_jumper(&self) -> bool {
+        self.movement() >= 7.0
+    }
+    /// The mouse hand for [`BrainConfig::human_aim`](crate::BrainConfig::human_aim): stroke speed
+    /// from [`Self::mouse`], the pause between corrections from [`Self::think`], and overshoot,
+    /// scatter and tracking from [`Self::aim`] (each sum read on its 0–10 scale).
+    pub fn mouse_params(&self) -> crate::mouse::MouseParams {
+        let at = |v: f32| crate::mouse::MouseParams::from_skill(v / 10.0);
+        let (mouse, think) = (at(self.mouse()), at(self.think()));
+        crate::mouse::MouseParams {
+            fitts_a: mouse.fitts_a,
+            fitts_b: mouse.fitts_b,
+            correction_delay: think.correction_delay,
+            ..at(self.aim())
+        }
+    }
     /// `bot_rangepreference` — standalone exponent for `2^rangepreference` distance bias
     /// (`havocbot.qc:1564`); the one axis the vendor does NOT add to skill.
     pub fn range_preference(&self) -> f32 {
@@ -123,99 +141,72 @@ impl Default for XonSkill {
     }
 }
 
-/// A selectable named Xonotic personality (Plan 62 roster; the Plan 38 `CharPreset` pattern).
-/// Canonical CLI token = the 3-char code used in competition bot names (Q2's 15-char
-/// `netname` limit, Plan 54); long name kept as an alias.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
-pub enum XonCharPreset {
-    /// rusher — presses hard: +aggres/+move/+dodge, closer range preference, sloppier aim.
-    #[value(name = "rus", alias = "rusher")]
-    Rusher,
-    /// sharp — the sniper: +aim/+mouse/+think, longer range preference, less aggressive.
-    #[value(name = "shp", alias = "sharp")]
-    Sharp,
-    /// turtle — cautious survivor: +dodge, −move/−aggres, mid range.
-    #[value(name = "trt", alias = "turtle")]
-    Turtle,
-    /// noob — low skill everywhere: clumsy keys, slow mouse, big aim offsets.
-    #[value(name = "nob", alias = "noob")]
-    Noob,
-}
+impl XonSkill {
+    // ── the Plan 62 roster — reference values for the shipped `characters/xon/*.yaml` ──
+    // (tuned against the K/D aggregator; treat the numbers as starting points).
 
-impl XonCharPreset {
-    /// The [`XonSkill`] this preset selects (global skill + axis offsets; Plan 62 tunes
-    /// these against the K/D aggregator — treat the numbers as starting points).
-    pub fn skill(self) -> XonSkill {
-        match self {
-            // Tuned 2026-07-11 (Plan 62 T3, N=3 aggregated): aim −1 made the rusher FEED
-            // (kd 0.36-0.60 across all runs — it closes distance then loses the duel);
-            // +1 keeps the identity (press + close range) with duel-viable aim.
-            XonCharPreset::Rusher => XonSkill {
-                skill: 5.0,
-                axes: XonAxes {
-                    aggres: 3.0,
-                    movement: 2.0,
-                    dodge: 1.0,
-                    aim: 1.0,
-                    offset: -1.0,
-                    rangepref: -1.0,
-                    ..XonAxes::default()
-                },
-            },
-            XonCharPreset::Sharp => XonSkill {
-                skill: 6.0,
-                axes: XonAxes {
-                    aim: 3.0,
-                    mouse: 2.0,
-                    think: 1.0,
-                    rangepref: 1.5,
-                    aggres: -1.0,
-                    ..XonAxes::default()
-                },
-            },
-            // Tuned 2026-07-11 (Plan 62 T3): skill 4 was fodder in 2 of 3 runs (0.10/0.21);
-            // 5 keeps the cautious identity without the free deaths.
-            XonCharPreset::Turtle => XonSkill {
-                skill: 5.0,
-                axes: XonAxes {
-                    dodge: 3.0,
-                    movement: -1.0,
-                    aggres: -2.0,
-                    rangepref: 0.5,
-                    ..XonAxes::default()
-                },
+    /// **Rusher** — presses hard: +aggres/+move/+dodge, closer range preference.
+    pub fn rusher() -> Self {
+        // Tuned 2026-07-11 (Plan 62 T3, N=3 aggregated): aim −1 made the rusher FEED
+        // (kd 0.36-0.60 across all runs — it closes distance then loses the duel);
+        // +1 keeps the identity (press + close range) with duel-viable aim.
+        Self {
+            skill: 5.0,
+            axes: XonAxes {
+                aggres: 3.0,
+                movement: 2.0,
+                dodge: 1.0,
+                aim: 1.0,
+                offset: -1.0,
+                rangepref: -1.0,
+                ..XonAxes::default()
             },
-            XonCharPreset::Noob => XonSkill {
-                skill: 2.0,
-                axes: XonAxes {
-                    keyboard: -1.0,
-                    aim: -2.0,
-                    mouse: -2.0,
-                    think: -1.0,
-                    offset: -2.0,
-                    ..XonAxes::default()
-                },
+        }
+    }
+
+    /// **Sharp** — the sniper: +aim/+mouse/+think, longer range preference, less aggressive.
+    pub fn sharp() -> Self {
+        Self {
+            skill: 6.0,
+            axes: XonAxes {
+                aim: 3.0,
+                mouse: 2.0,
+                think: 1.0,
+                rangepref: 1.5,
+                aggres: -1.0,
+                ..XonAxes::default()
             },
         }
     }
 
-    /// Stable kebab tag for logs / scoreboard grouping.
-    pub fn tag(self) -> &'static str {
-        match self {
-            XonCharPreset::Rusher => "rusher",
-            XonCharPreset::Sharp => "sharp",
-            XonCharPreset::Turtle => "turtle",
-            XonCharPreset::Noob => "noob",
+    /// **Turtle** — cautious survivor: +dodge, −move/−aggres, mid range.
+    pub fn turtle() -> Self {
+        // Tuned 2026-07-11 (Plan 62 T3): skill 4 was fodder in 2 of 3 runs (0.10/0.21);
+        // 5 keeps the cautious identity without the free deaths.
+        Self {
+            skill: 5.0,
+            axes: XonAxes {
+                dodge: 3.0,
+                movement: -1.0,
+                aggres: -2.0,
+                rangepref: 0.5,
+                ..XonAxes::default()
+            },
         }
     }
 
-    /// A distinct Q2 player skin per preset (visual roster recognition, Plan 38 pattern).
-    pub fn skin(self) -> &'static str {
-        match self {
-            XonCharPreset::Rusher => "male/viper",
-            XonCharPreset::Sharp => "male/sniper",
-            XonCharPreset::Turtle => "male/pmarine",
-            XonCharPreset::Noob => "female/jezebel",
+    /// **Noob** — low skill everywhere: clumsy keys, slow mouse, big aim offsets.
+    pub fn noob() -> Self {
+        Self {
+            skill: 2.0,
+            axes: XonAxes {
+                keyboard: -1.0,
+                aim: -2.0,
+                mouse: -2.0,
+                think: -1.0,
+                offset: -2.0,
+                ..XonAxes::default()
+            },
         }
     }
 }
@@ -261,19 +252,16 @@ mod tests {
     }
 
     #[test]
-    fn presets_are_distinct_and_tagged() {
+    fn presets_are_distinct() {
         let all = [
-            XonCharPreset::Rusher,
-            XonCharPreset::Sharp,
-            XonCharPreset::Turtle,
-            XonCharPreset::Noob,
+            XonSkill::rusher(),
+            XonSkill::sharp(),
+            XonSkill::turtle(),
+            XonSkill::noob(),
         ];
         for (i, a) in all.iter().enumerate() {
-            assert!(a.tag().len() >= 4);
-            assert!(!a.skin().is_empty());
             for b in &all[i + 1..] {
-                assert_ne!(a.skill(), b.skill(), "{:?} vs {:?}", a, b);
-                assert_ne!(a.skin(), b.skin());
+                assert_ne!(a, b);
             }
         }
     }
@@ -281,10 +269,13 @@ mod tests {
     #[test]
     fn preset_flavor_pins() {
         // The roster's defining contrasts (Plan 62 tunes magnitudes, not signs).
-        assert!(XonCharPreset::Rusher.skill().aggres() > XonCharPreset::Turtle.skill().aggres());
-        assert!(XonCharPreset::Sharp.skill().aim() > XonCharPreset::Noob.skill().aim());
-        assert!(XonCharPreset::Sharp.skill().range_preference() > 0.0);
-        assert!(XonCharPreset::Rusher.skill().range_preference() < 0.0);
+        assert!(XonSkill::rusher().aggres() > XonSkill::turtle().aggres());
+        assert!(XonSkill::sharp().aim() > XonSkill::noob().aim());
+        assert!(XonSkill::sharp().range_preference() > 0.0);
+        assert!(XonSkill::rusher().range_preference() < 0.0);
+        assert!(XonSkill::rusher().rocket_jumper());
+        assert!(!XonSkill::turtle().rocket_jumper());
+        assert!(!XonSkill::noob().rocket_jumper());
     }
 
     #[test]
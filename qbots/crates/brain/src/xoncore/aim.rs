@@ -22,12 +22,16 @@
 //!   to 0 for any positive skill in gmqcc — an upstream regression that disables the bad-aim
 //!   offset almost everywhere. We port the float semantics (the intended behavior).
 //! - SUPERBOT (`skill > 100`) instant snap (`aim.qc:167-180`) is not modeled.
-//! - `findtrajectorywithleading` ballistic search (`aim.qc:16-95`) is deferred to the brain
-//!   (needs a CM gravity trace); [`XonAim::step`] aims at the (lead-corrected) point.
+//! - `findtrajectorywithleading` ballistic search (`aim.qc:16-95`) is done by the brain with
+//!   [`crate::splash`] (it needs the CM); [`XonAim::step`] aims at the (lead-corrected) point.
+//!
+//! With [`XonAim::with_mouse`], stages 3 and 4 give way to a [`MouseAim`] hand while fighting:
+//! the offset and the cascade still pick the point, and the hand flicks and tracks onto it.
 
 use glam::Vec3;
 
 use super::{wrap180, Lcg};
+use crate::mouse::{MouseAim, MouseParams};
 use crate::xonchar::XonSkill;
 
 /// Cvar defaults (`xonotic-server.cfg:136-183`), named as in `cvars.qh`.
@@ -121,6 +125,8 @@ pub struct XonAim {
     mouse_until: f32,
     /// Trigger-down deadline (`bot_firetimer`).
     fire_timer: f32,
+    /// The opt-in mouse hand that replaces stages 3–4 while fighting.
+    mouse: Option<MouseAim>,
 }
 
 impl XonAim {
@@ -135,9 +141,17 @@ impl XonAim {
             mouse_aim: Angles::default(),
             mouse_until: 0.0,
             fire_timer: -1.0,
+            mouse: None,
         }
     }
 
+    /// Move the view with a modeled mouse hand ([`crate::mouse`]) while fighting, in place of
+    /// the mouse-think cadence and the turn-rate law.
+    pub fn with_mouse(mut self, params: MouseParams) -> Self {
+        self.mouse = Some(MouseAim::new(params));
+        self
+    }
+
     /// One aim tick: advance the pipeline and return the new view angles + fire decision.
     /// `current` is the bot's present view angles; `dt` the seconds since the last call.
     pub fn step(
@@ -208,27 +222,27 @@ impl XonAim {
         }
         desired.pitch = desired.pitch.clamp(-90.0, 90.0);
 
-        // ── 3. Mouse think: the internal target updates only at the think cadence with a
-        // random undershoot (`aim.qc:261-274`; aimskill_think = 1 → desired = mouse_aim) ──
-        let mdiff = desired.diff(self.mouse_aim);
-        if self.time >= self.mouse_until {
-            self.mouse_until = (self.mouse_until + 0.5 - 0.05 * eff.think()).max(self.time);
-            let under = 1.0 - rng.next() * 0.1 * (10.0 - eff.think()).clamp(1.0, 10.0);
-            self.mouse_aim.pitch += mdiff.pitch * under;
-            self.mouse_aim.yaw = wrap180(self.mouse_aim.yaw + mdiff.yaw * under);
-        }
-        desired = self.mouse_aim;
-
-        // ── 4. Turn-rate law (`aim.qc:289-295`) ───────────────────────────────────────
-        let tdiff = desired.diff(current);
-        let dist_ang = tdiff.len();
-        let fixedrate = AIMSKILL_FIXEDRATE / dist_ang.clamp(1.0, 1000.0);
-        let r = fixedrate.max(AIMSKILL_BLENDRATE);
-        let m = eff.mouse();
-        let r = (r * dt * (2.0 + m * m * m * 0.005 - rng.next())).clamp(dt, 1.0);
-        let angles = Angles {
-            pitch: (current.pitch + tdiff.pitch * r).clamp(-90.0, 90.0),
-            yaw: wrap180(current.yaw + tdiff.yaw * r),
+        let angles = match &mut self.mouse {
+            Some(m) if inp.fighting => {
+                let (yaw, pitch) = m.step(
+                    self.time,
+                    (current.yaw, current.pitch),
+                    (desired.yaw, desired.pitch),
+                    dist0,
+                    rng,
+                );
+                self.mouse_aim = desired; // stage 3 resumes from here after the fight
+                Angles {
+                    pitch: pitch.clamp(-90.0, 90.0),
+                    yaw: wrap180(yaw),
+                }
+            }
+            _ => {
+                if let Some(m) = &mut self.mouse {
+                    m.release();
+                }
+                self.think_and_turn(rng, &eff, current, desired, dt)
+            }
         };
 
         // ── 5. Fire cone + burst timer (`bot_aim` aim.qc:365-374 + `bot_aimdir` :315-330).
@@ -258,6 +272,38 @@ impl XonAim {
             fire: inp.fighting && self.time <= self.fire_timer,
         }
     }
+
+    /// Stages 3–4: the discrete mouse think, then the turn-rate law toward its target.
+    fn think_and_turn(
+        &mut self,
+        rng: &mut Lcg,
+        sk: &XonSkill,
+        current: Angles,
+        desired: Angles,
+        dt: f32,
+    ) -> Angles {
+        // ── 3. Mouse think: the internal target updates only at the think cadence with a
+        // random undershoot (`aim.qc:261-274`; aimskill_think = 1 → desired = mouse_aim) ──
+        let mdiff = desired.diff(self.mouse_aim);
+        if self.time >= self.mouse_until {
+            self.mouse_until = (self.mouse_until + 0.5 - 0.05 * sk.think()).max(self.time);
+            let under = 1.0 - rng.next() * 0.1 * (10.0 - sk.think()).clamp(1.0, 10.0);
+            self.mouse_aim.pitch += mdiff.pitch * under;
+            self.mouse_aim.yaw = wrap180(self.mouse_aim.yaw + mdiff.yaw * under);
+        }
+
+        // ── 4. Turn-rate law (`aim.qc:289-295`) ───────────────────────────────────────
+        let tdiff = self.mouse_aim.diff(current);
+        let dist_ang = tdiff.len();
+        let fixedrate = AIMSKILL_FIXEDRATE / dist_ang.clamp(1.0, 1000.0);
+        let r = fixedrate.max(AIMSKILL_BLENDRATE);
+        let m = sk.mouse();
+        let r = (r * dt * (2.0 + m * m * m * 0.005 - rng.next())).clamp(dt, 1.0);
+        Angles {
+            pitch: (current.pitch + tdiff.pitch * r).clamp(-90.0, 90.0),
+            yaw: wrap180(current.yaw + tdiff.yaw * r),
+        }
+    }
 }
 
 impl Default for XonAim {
@@ -378,6 +424,31 @@ mod tests {
         assert!(!cmd.fire);
     }
 
+    #[test]
+    fn mouse_hand_flicks_and_still_fires() {
+        // 90° off: the hand sweeps over (not one turn-rate step), lands, and the trigger follows.
+        let sk = skill(7.0);
+        let mut aim = XonAim::new().with_mouse(sk.mouse_params());
+        let mut rng = Lcg::new(4);
+        let mut cur = Angles {
+            pitch: 0.0,
+            yaw: 90.0,
+        };
+        let inp = inputs(Vec3::new(400.0, 0.0, 0.0), Vec3::ZERO);
+        let mut yaws = Vec::new();
+        let mut fired = 0;
+        for _ in 0..60 {
+            let cmd = aim.step(&mut rng, &sk, cur, &inp, 0.025);
+            cur = cmd.angles;
+            yaws.push(cur.yaw);
+            fired += cmd.fire as u32;
+        }
+        assert!(yaws[0] > 80.0, "starts at the view: {}", yaws[0]);
+        assert!(yaws[7] < 80.0 && yaws[7] > 10.0, "mid-flick: {}", yaws[7]);
+        assert!(cur.yaw.abs() < 5.0, "on target: {}", cur.yaw);
+        assert!(fired > 0);
+    }
+
     #[test]
     fn leads_a_moving_target() {
         // Slow projectile vs a +y mover: converged yaw must sit AHEAD of the direct line
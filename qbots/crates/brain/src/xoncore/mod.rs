@@ -28,6 +28,13 @@ impl Lcg {
     }
 }
 
+/// Lets the shared aim layers ([`crate::mouse`]) draw from a bot's `Lcg`.
+impl crate::aim::AimRng for Lcg {
+    fn next_signed(&mut self) -> f32 {
+        self.next() * 2.0 - 1.0
+    }
+}
+
 /// Wrap an angle difference to `[-180, 180)` degrees (the vendor's
 /// `diffang.y -= floor(diffang.y / 360) * 360; if (>=180) -= 360` idiom, `aim.qc:221-223`).
 pub fn wrap180(a: f32) -> f32 {
@@ -312,6 +312,12 @@ impl Navigator for XonNavDriver {
     fn current_ride_info(&self) -> Option<world::RideInfo> {
         self.inner.current_ride_info()
     }
+    fn set_rocket_jump_allowed(&mut self, allowed: bool) {
+        self.inner.set_rocket_jump_allowed(allowed);
+    }
+    fn current_rocket_jump(&self) -> Option<world::RocketJumpInfo> {
+        self.inner.current_rocket_jump()
+    }
     fn force_replan(&mut self) {
         self.inner.force_replan();
     }
@@ -324,6 +330,9 @@ impl Navigator for XonNavDriver {
     fn current_waypoint_pos(&self) -> Option<[f32; 3]> {
         self.inner.current_waypoint_pos()
     }
+    fn path_ahead(&self, max: usize) -> Vec<[f32; 3]> {
+        self.inner.path_ahead(max)
+    }
     fn smooth_with_cm(&mut self, cm: &CollisionModel, from: Vec3) {
         self.inner.smooth_with_cm(cm, from);
     }
@@ -349,6 +358,12 @@ impl Navigator for XonNavDriver {
             self.sources.drain(..drop);
         }
     }
+    fn note_nav_state(&mut self, state: &world::NavState) {
+        self.inner.note_nav_state(state);
+    }
+    fn backend(&self) -> &'static str {
+        "xg"
+    }
 }
 
 #[cfg(test)]
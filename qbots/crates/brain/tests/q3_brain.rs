@@ -71,8 +71,6 @@ fn build_q3_roundtrips_to_seek_ltg() {
         BotSkill::new(9, brain::Personality::Balanced),
         BrainConfig::default(),
         None,
-        None,
-        None,
     );
     assert_eq!(brain.status(), "seek-ltg");
 }
@@ -86,8 +84,6 @@ fn q3_fires_at_a_visible_enemy_after_reaction() {
         BotSkill::new(9, brain::Personality::Balanced),
         BrainConfig::default(),
         None,
-        None,
-        None,
     );
     let cm = open_world();
     let view = view_with_enemy("models/weapons/v_rail/tris.md2", 20);
@@ -130,8 +126,6 @@ fn q3_requests_a_better_weapon_against_a_ranged_enemy() {
         BotSkill::new(7, brain::Personality::Balanced),
         BrainConfig::default(),
         None,
-        None,
-        None,
     );
     let cm = open_world();
     // We "hold" a railgun on the wire but the brain tracks its own optimistic held weapon
@@ -165,8 +159,6 @@ fn q3_roams_with_no_enemy() {
         BotSkill::new(5, brain::Personality::Balanced),
         BrainConfig::default(),
         None,
-        None,
-        None,
     );
     let cm = open_world();
     let view = Worldview::from_frame(&Frame::default(), &ConfigStrings::default(), 0);
@@ -0,0 +1,938 @@
+//! Data-driven bot characters — `characters/<family>/<name>.yaml`.
+//!
+//! Personalities used to be compiled in (`Persona::rusher`, the `CharPreset`/`XonCharPreset`
+//! clap enums), so every tuning pass meant a rebuild. Now each named character is a YAML file,
+//! loaded and validated at startup. The directory a file sits in names its **family** — the brain
+//! it drives, and so its schema — and the file stem is its name:
+//!
+//! ```text
+//! characters/
+//!   main/rusher.yaml   # brain::persona::Persona — `--persona`, `[fleet].persona`, roster `persona:`
+//!   q3/grunt.yaml      # brain::Q3Character      — `--char`,    `[fleet].char`,    roster `char:`
+//!   xon/sharp.yaml     # brain::XonSkill         — `--xonchar`, `[fleet].xonchar`, roster `xonchar:`
+//!   script/hunter.rhai # brain::ScriptBrain      — `--script`,  `[fleet].script`,  roster `script:`
+//!   utility/brawler.yaml # brain::UtilityProfile — `--utility`, `[fleet].utility`,
+//!                        #   roster `utility:`
+//! ```
+//!
+//! Every trait is optional and falls back to the family's neutral default (`Persona::default`,
+//! `Q3Character::from_skill(5)`, `XonSkill::new(5)`, `UtilityProfile::default`), so a variant
+//! only spells out what it changes. Two keys are common to all families: `code`, the ≤3-char
+//! token competition bot names and scoreboards use (default: the name's first three letters or
+//! digits), and `skin`, the `model/skin` the character wears.
+//!
+//! ```yaml
+//! # characters/q3/grunt.yaml
+//! code: gru
+//! skin: male/grunt
+//! aim_accuracy: 0.40
+//! firethrottle: 0.70
+//! per_weapon_accuracy:     # optional; weapons left out use aim_accuracy
+//!   railgun: 0.30
+//! ```
+//!
+//! A `script` character is a Rhai program rather than a trait sheet: it is compile-checked at
+//! load, takes the default code, wears no fixed skin, and the brain re-reads it when it changes.
+//!
+//! A character is referenced by name or code (case-insensitive) within its family; a `q3` and an
+//! `xon` character may share a name.
+
+use brain::brains::utility::{Action, Curve, Input, Shape};
+use brain::persona::Persona;
+use brain::{BrainKind, BrainTraits, Q3Character, UtilityProfile, Weapon, XonSkill};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Widest character code: it rides inside `<brain>_<mode>_<code>_<i>` bot names, which must fit
+/// Q2's 15-char `netname`.
+const MAX_CODE: usize = 3;
+
+/// Which brain a character drives — and the subdirectory its files live in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Family {
+    Main,
+    Q3,
+    Xon,
+    Script,
+    Utility,
+}
+
+impl Family {
+    pub const ALL: [Family; 5] = [
+        Family::Main,
+        Family::Q3,
+        Family::Xon,
+        Family::Script,
+        Family::Utility,
+    ];
+
+    /// The subdirectory of the characters dir holding this family's files.
+    pub fn dir(self) -> &'static str {
+        match self {
+            Family::Main => "main",
+            Family::Q3 => "q3",
+            Family::Xon => "xon",
+            Family::Script => "script",
+            Family::Utility => "utility",
+        }
+    }
+
+    /// The key that selects a character of this family — the config/roster field and CLI flag.
+    pub fn key(self) -> &'static str {
+        match self {
+            Family::Main => "persona",
+            Family::Q3 => "char",
+            Family::Xon => "xonchar",
+            Family::Script => "script",
+            Family::Utility => "utility",
+        }
+    }
+
+    /// The brain this family's characters drive.
+    pub fn brain(self) -> BrainKind {
+        match self {
+            Family::Main => BrainKind::Main,
+            Family::Q3 => BrainKind::Quake3,
+            Family::Xon => BrainKind::Xon,
+            Family::Script => BrainKind::Script,
+            Family::Utility => BrainKind::Utility,
+        }
+    }
+
+    /// The file extensions this family's files use.
+    fn extensions(self) -> &'static [&'static str] {
+        match self {
+            Family::Script => &["rhai"],
+            _ => &["yaml", "yml"],
+        }
+    }
+
+    /// The family whose characters `brain` takes; `None` for brains without a personality axis.
+    pub fn for_brain(brain: BrainKind) -> Option<Family> {
+        Family::ALL.into_iter().find(|f| f.brain() == brain)
+    }
+}
+
+/// One loaded character file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Character {
+    /// The file stem — the log-facing name.
+    pub name: String,
+    /// Short token for bot names and scoreboards.
+    pub code: String,
+    /// The `model/skin` this character wears, if it names one.
+    pub skin: Option<String>,
+    pub traits: BrainTraits,
+}
+
+impl Character {
+    pub fn family(&self) -> Family {
+        match self.traits {
+            BrainTraits::Main(_) => Family::Main,
+            BrainTraits::Q3(_) => Family::Q3,
+            BrainTraits::Xon(_) => Family::Xon,
+            BrainTraits::Script(_) => Family::Script,
+            BrainTraits::Utility(_) => Family::Utility,
+        }
+    }
+
+    pub fn script(&self) -> Option<PathBuf> {
+        match &self.traits {
+            BrainTraits::Script(p) => Some(p.clone()),
+            _ => None,
+        }
+    }
+
+    /// Does `token` name this character (its name or code, case-insensitive)?
+    fn answers_to(&self, token: &str) -> bool {
+        self.name.eq_ignore_ascii_case(token) || self.code.eq_ignore_ascii_case(token)
+    }
+
+    /// Parse one character file's text. `name` is the file stem; `family` comes from its directory.
+    pub fn parse(family: Family, name: &str, text: &str) -> Result<Self, String> {
+        check_name(name)?;
+        let (code, skin, traits) = match family {
+            Family::Main => {
+                let f: MainFile = from_yaml(text)?;
+                {
+                    let traits = BrainTraits::Main(f.persona()?);
+                    (f.code, f.skin, traits)
+                }
+            }
+            Family::Q3 => {
+                let f: Q3File = from_yaml(text)?;
+                {
+                    let traits = BrainTraits::Q3(f.character()?);
+                    (f.code, f.skin, traits)
+                }
+            }
+            Family::Xon => {
+                let f: XonFile = from_yaml(text)?;
+                {
+                    let traits = BrainTraits::Xon(f.skill()?);
+                    (f.code, f.skin, traits)
+                }
+            }
+            Family::Utility => {
+                let f: UtilityFile = from_yaml(text)?;
+                {
+                    let traits = BrainTraits::Utility(Box::new(f.profile()?));
+                    (f.code, f.skin, traits)
+                }
+            }
+            Family::Script => return Err("script characters are .rhai files, not YAML".into()),
+        };
+        let code = code.unwrap_or_else(|| default_code(name));
+        check_code(&code)?;
+        if let Some(s) = &skin {
+            if s.split_once('/')
+                .is_none_or(|(m, k)| m.is_empty() || k.is_empty())
+            {
+                return Err(format!("skin '{s}' must be model/skin"));
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            code,
+            skin,
+            traits,
+        })
+    }
+
+    /// A `script` character from its `.rhai` file, which must compile and define `tick`.
+    pub fn from_script(name: &str, path: &Path) -> Result<Self, String> {
+        check_name(name)?;
+        brain::brains::script::check(path)?;
+        let code = default_code(name);
+        check_code(&code)?;
+        Ok(Self {
+            name: name.to_string(),
+            code,
+            skin: None,
+            traits: BrainTraits::Script(path.to_path_buf()),
+        })
+    }
+
+    /// This character as file text in its family's schema (every trait spelled out), such that
+    /// [`Character::parse`] of it under the same name gives it back. No header comment — callers
+    /// that write files add their own. A `script` character has no traits to write, only its code.
+    pub fn to_yaml(&self) -> String {
+        let mut out = format!("code: {}\n", self.code);
+        if let Some(s) = &self.skin {
+            out += &format!("skin: {s}\n");
+        }
+        let mut line = |k: &str, v: &dyn std::fmt::Display| out += &format!("{k}: {v}\n");
+        match &self.traits {
+            BrainTraits::Main(p) => {
+                line("aggression", &p.aggression);
+                line("risk_tolerance", &p.risk_tolerance);
+                if let Some(w) = p.weapon_pref {
+                    line("weapon_pref", &w.name().to_ascii_lowercase());
+                }
+                line("camper", &p.camper);
+                line("chase_commit", &p.chase_commit);
+                line("item_greed", &p.item_greed);
+                line("shadow_pref", &p.shadow_pref);
+            }
+            BrainTraits::Q3(c) => {
+                line("attack_skill", &c.attack_skill);
+                line("reaction_time", &c.reaction_time);
+                line("aim_accuracy", &c.aim_accuracy);
+                line("aim_skill", &c.aim_skill);
+                line("croucher", &c.croucher);
+                line("jumper", &c.jumper);
+                line("walker", &c.walker);
+                line("aggression", &c.aggression);
+                line("self_preservation", &c.self_preservation);
+                line("vengefulness", &c.vengefulness);
+                line("camper", &c.camper);
+                line("easy_fragger", &c.easy_fragger);
+                line("alertness", &c.alertness);
+                line("firethrottle", &c.firethrottle);
+                if let Some(acc) = &c.per_weapon_accuracy {
+                    out += "per_weapon_accuracy:\n";
+                    for w in brain::weapons::ALL_WEAPONS {
+                        let a = acc[brain::q3char::weapon_index(w)];
+                        out += &format!("  {}: {a}\n", w.name().to_ascii_lowercase());
+                    }
+                }
+            }
+            BrainTraits::Xon(x) => {
+                line("skill", &x.skill);
+                let a = &x.axes;
+                let axes = [
+                    ("keyboard", a.keyboard),
+                    ("movement", a.movement),
+                    ("dodge", a.dodge),
+                    ("ping", a.ping),
+                    ("weapon", a.weapon),
+                    ("aggres", a.aggres),
+                    ("rangepref", a.rangepref),
+                    ("aim", a.aim),
+                    ("offset", a.offset),
+                    ("mouse", a.mouse),
+                    ("think", a.think),
+                    ("ai", a.ai),
+                ];
+                // Axes are offsets from 0; leave the neutral ones out, as the shipped files do.
+                let set: Vec<_> = axes.iter().filter(|(_, v)| *v != 0.0).collect();
+                if !set.is_empty() {
+                    out += "axes:\n";
+                    for (k, v) in set {
+                        out += &format!("  {k}: {v}\n");
+                    }
+                }
+            }
+            BrainTraits::Script(_) => {}
+            BrainTraits::Utility(u) => {
+                out += "weights:\n";
+                for a in Action::ALL {
+                    out += &format!("  {}: {}\n", a.name(), u.weight(a));
+                }
+                out += "curves:\n";
+                for a in Action::ALL {
+                    for (i, c) in u.considerations(a) {
+                        out += &format!("  {}.{}: {}\n", a.name(), i.name(), curve_yaml(c));
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Deserialize a character file; an empty (or comment-only) file is an all-defaults character.
+fn from_yaml<T: serde::de::DeserializeOwned>(text: &str) -> Result<T, String> {
+    let v: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    let v = if v.is_null() {
+        serde_yaml::Value::Mapping(Default::default())
+    } else {
+        v
+    };
+    serde_yaml::from_value(v).map_err(|e| e.to_string())
+}
+
+/// The code a character gets when its file names none: the name's first three letters or digits.
+fn default_code(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(MAX_CODE)
+        .collect()
+}
+
+/// Names are lowercase `[a-z0-9-]`.
+fn check_name(name: &str) -> Result<(), String> {
+    let ok = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-';
+    if name.is_empty() || !name.chars().all(ok) {
+        return Err(format!(
+            "name '{name}' must be lowercase letters, digits or '-'"
+        ));
+    }
+    Ok(())
+}
+
+/// Codes are 1-3 lowercase letters or digits — no `_`, since bot names split on it.
+fn check_code(code: &str) -> Result<(), String> {
+    let ok = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit();
+    if code.is_empty() || code.len() > MAX_CODE || !code.chars().all(ok) {
+        return Err(format!(
+            "code '{code}' must be 1-{MAX_CODE} lowercase letters or digits"
+        ));
+    }
+    Ok(())
+}
+
+/// Every character under a characters directory, grouped by family in file-name order.
+#[derive(Clone, Debug, Default)]
+pub struct Characters {
+    list: Vec<Arc<Character>>,
+}
+
+impl Characters {
+    /// Load `<dir>/<family>/*.yaml` (`*.rhai` for scripts) for every family. A missing directory
+    /// (or family subdirectory) is simply empty; any unreadable, malformed or clashing file is an
+    /// error naming the file.
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let mut out = Self::default();
+        for family in Family::ALL {
+            let sub = dir.join(family.dir());
+            let entries = match std::fs::read_dir(&sub) {
+                Ok(e) => e,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("read {}: {e}", sub.display())),
+            };
+            let mut files: Vec<_> = entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.extension()
+                        .and_then(|s| s.to_str())
+                        .is_some_and(|e| family.extensions().contains(&e))
+                })
+                .collect();
+            files.sort();
+            for path in files {
+                let name = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
+                let parsed = if family == Family::Script {
+                    Character::from_script(name, &path)
+                } else {
+                    let text = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("read {}: {e}", path.display()))?;
+                    Character::parse(family, name, &text)
+                };
+                parsed
+                    .and_then(|c| out.insert(c))
+                    .map_err(|e| format!("{}: {e}", path.display()))?;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Add a character, refusing one whose name or code is already taken within its family.
+    pub fn insert(&mut self, c: Character) -> Result<(), String> {
+        let family = c.family();
+        if let Some(other) = self
+            .of(family)
+            .find(|o| o.answers_to(&c.name) || o.answers_to(&c.code))
+        {
+            return Err(format!(
+                "{} '{}' (code {}) clashes with '{}' (code {})",
+                family.key(),
+                c.name,
+                c.code,
+                other.name,
+                other.code
+            ));
+        }
+        self.list.push(Arc::new(c));
+        Ok(())
+    }
+
+    /// This family's characters, in load order.
+    pub fn of(&self, family: Family) -> impl Iterator<Item = &Arc<Character>> {
+        self.list.iter().filter(move |c| c.family() == family)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Look a character up by name or code within `family`; the error lists what is available.
+    pub fn resolve(&self, family: Family, token: &str) -> Result<Arc<Character>, String> {
+        if let Some(c) = self.of(family).find(|c| c.answers_to(token)) {
+            return Ok(Arc::clone(c));
+        }
+        let known: Vec<&str> = self.of(family).map(|c| c.name.as_str()).collect();
+        Err(if known.is_empty() {
+            format!(
+                "unknown {} '{token}' (no {} characters loaded)",
+                family.key(),
+                family.dir()
+            )
+        } else {
+            format!(
+                "unknown {} '{token}' (want one of: {})",
+                family.key(),
+                known.join(", ")
+            )
+        })
+    }
+}
+
+/// `characters/main/*.yaml` — a [`Persona`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MainFile {
+    code: Option<String>,
+    skin: Option<String>,
+    aggression: Option<f32>,
+    risk_tolerance: Option<f32>,
+    /// A weapon name (`railgun`, `super shotgun`, …); absent = pure auto-select.
+    weapon_pref: Option<String>,
+    camper: Option<bool>,
+    chase_commit: Option<f32>,
+    item_greed: Option<f32>,
+    shadow_pref: Option<f32>,
+}
+
+impl MainFile {
+    fn persona(&self) -> Result<Persona, String> {
+        let d = Persona::default();
+        Ok(Persona {
+            aggression: unit("aggression", self.aggression, d.aggression)?,
+            risk_tolerance: unit("risk_tolerance", self.risk_tolerance, d.risk_tolerance)?,
+            weapon_pref: self.weapon_pref.as_deref().map(weapon).transpose()?,
+            camper: self.camper.unwrap_or(d.camper),
+            chase_commit: unit("chase_commit", self.chase_commit, d.chase_commit)?,
+            item_greed: unit("item_greed", self.item_greed, d.item_greed)?,
+            shadow_pref: unit("shadow_pref", self.shadow_pref, d.shadow_pref)?,
+        })
+    }
+}
+
+/// `characters/q3/*.yaml` — a [`Q3Character`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Q3File {
+    code: Option<String>,
+    skin: Option<String>,
+    attack_skill: Option<f32>,
+    reaction_time: Option<f32>,
+    aim_accuracy: Option<f32>,
+    aim_skill: Option<f32>,
+    croucher: Option<f32>,
+    jumper: Option<f32>,
+    walker: Option<f32>,
+    aggression: Option<f32>,
+    self_preservation: Option<f32>,
+    vengefulness: Option<f32>,
+    camper: Option<f32>,
+    easy_fragger: Option<f32>,
+    alertness: Option<f32>,
+    firethrottle: Option<f32>,
+    /// Weapon name → accuracy; weapons left out use `aim_accuracy`.
+    per_weapon_accuracy: Option<BTreeMap<String, f32>>,
+}
+
+impl Q3File {
+    fn character(&self) -> Result<Q3Character, String> {
+        let d = Q3Character::default();
+        let aim_accuracy = unit("aim_accuracy", self.aim_accuracy, d.aim_accuracy)?;
+        let per_weapon_accuracy = match &self.per_weapon_accuracy {
+            Some(map) if !map.is_empty() => {
+                let mut acc = [aim_accuracy; 10];
+                for (w, &a) in map {
+                    acc[brain::q3char::weapon_index(weapon(w)?)] =
+                        unit("per_weapon_accuracy", Some(a), 0.0)?;
+                }
+                Some(acc)
+            }
+            _ => None,
+        };
+        Ok(Q3Character {
+            attack_skill: unit("attack_skill", self.attack_skill, d.attack_skill)?,
+            reaction_time: ranged(
+                "reaction_time",
+                self.reaction_time,
+                d.reaction_time,
+                0.0,
+                5.0,
+            )?,
+            aim_accuracy,
+            aim_skill: unit("aim_skill", self.aim_skill, d.aim_skill)?,
+            croucher: unit("croucher", self.croucher, d.croucher)?,
+            jumper: unit("jumper", self.jumper, d.jumper)?,
+            walker: unit("walker", self.walker, d.walker)?,
+            aggression: unit("aggression", self.aggression, d.aggression)?,
+            self_preservation: unit(
+                "self_preservation",
+                self.self_preservation,
+                d.self_preservation,
+            )?,
+            vengefulness: unit("vengefulness", self.vengefulness, d.vengefulness)?,
+            camper: unit("camper", self.camper, d.camper)?,
+            easy_fragger: unit("easy_fragger", self.easy_fragger, d.easy_fragger)?,
+            alertness: unit("alertness", self.alertness, d.alertness)?,
+            firethrottle: unit("firethrottle", self.firethrottle, d.firethrottle)?,
+            per_weapon_accuracy,
+        })
+    }
+}
+
+/// `characters/xon/*.yaml` — an [`XonSkill`]: the global `skill` plus the 12 offsets under `axes`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct XonFile {
+    code: Option<String>,
+    skin: Option<String>,
+    skill: Option<f32>,
+    #[serde(default)]
+    axes: XonAxesFile,
+}
+
+/// The [`brain::xonchar::XonAxes`] offsets, each defaulting to 0.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct XonAxesFile {
+    keyboard: f32,
+    movement: f32,
+    dodge: f32,
+    ping: f32,
+    weapon: f32,
+    aggres: f32,
+    rangepref: f32,
+    aim: f32,
+    offset: f32,
+    mouse: f32,
+    think: f32,
+    ai: f32,
+}
+
+/// Vendor `bot_config_file` rows stay within about ±3; anything past this is a typo.
+const MAX_XON_AXIS: f32 = 10.0;
+
+impl XonFile {
+    fn skill(&self) -> Result<XonSkill, String> {
+        let a = &self.axes;
+        let axis = |what: &str, v: f32| ranged(what, Some(v), 0.0, -MAX_XON_AXIS, MAX_XON_AXIS);
+        Ok(XonSkill {
+            skill: ranged("skill", self.skill, XonSkill::default().skill, 0.0, 10.0)?,
+            axes: brain::xonchar::XonAxes {
+                keyboard: axis("axes.keyboard", a.keyboard)?,
+                movement: axis("axes.movement", a.movement)?,
+                dodge: axis("axes.dodge", a.dodge)?,
+                ping: axis("axes.ping", a.ping)?,
+                weapon: axis("axes.weapon", a.weapon)?,
+                aggres: axis("axes.aggres", a.aggres)?,
+                rangepref: axis("axes.rangepref", a.rangepref)?,
+                aim: axis("axes.aim", a.aim)?,
+                offset: axis("axes.offset", a.offset)?,
+                mouse: axis("axes.mouse", a.mouse)?,
+                think: axis("axes.think", a.think)?,
+                ai: axis("axes.ai", a.ai)?,
+            },
+        })
+    }
+}
+
+/// `characters/utility/*.yaml` — a [`UtilityProfile`]: `weights` by action and `curves` by
+/// `<action>.<input>` (e.g. `retreat.health`), each replacing the default for that one entry.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UtilityFile {
+    code: Option<String>,
+    skin: Option<String>,
+    #[serde(default)]
+    weights: BTreeMap<String, f32>,
+    #[serde(default)]
+    curves: BTreeMap<String, CurveFile>,
+}
+
+/// One response curve: `curve` names the shape, which takes exactly its own parameters —
+/// `linear` `m`, `b`; `power` `k`; `logistic` `mid`, `slope`; `step` `at`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CurveFile {
+    curve: String,
+    m: Option<f32>,
+    b: Option<f32>,
+    k: Option<f32>,
+    mid: Option<f32>,
+    slope: Option<f32>,
+    at: Option<f32>,
+    #[serde(default)]
+    invert: bool,
+}
+
+/// Heavier than this, one action drowns every other; it is a typo, not a tuning.
+const MAX_UTILITY_WEIGHT: f32 = 5.0;
+
+impl UtilityFile {
+    fn profile(&self) -> Result<UtilityProfile, String> {
+        let mut p = UtilityProfile::default();
+        for (name, &w) in &self.weights {
+            let a = action(name).map_err(|e| format!("weights.{name}: {e}"))?;
+            let w = ranged(
+                &format!("weights.{name}"),
+                Some(w),
+                0.0,
+                0.0,
+                MAX_UTILITY_WEIGHT,
+            )?;
+            p.set_weight(a, w);
+        }
+        for (key, c) in &self.curves {
+            let what = format!("curves.{key}");
+            let (a, i) = key
+                .split_once('.')
+                .ok_or_else(|| format!("{what}: want <action>.<input>"))?;
+            let input =
+                Input::from_name(i).ok_or_else(|| format!("{what}: unknown input '{i}'"))?;
+            p.set_curve(
+                action(a).map_err(|e| format!("{what}: {e}"))?,
+                input,
+                c.curve(&what)?,
+            )
+            .map_err(|e| format!("{what}: {e}"))?;
+        }
+        Ok(p)
+    }
+}
+
+impl CurveFile {
+    fn curve(&self, what: &str) -> Result<Curve, String> {
+        let kind = self.curve.as_str();
+        let need = |p: &str, v: Option<f32>| {
+            v.ok_or_else(|| format!("{what}: a {kind} curve needs `{p}`"))
+        };
+        let (shape, takes): (Shape, &[&str]) = match kind {
+            "linear" => (
+                Shape::Linear {
+                    m: need("m", self.m)?,
+                    b: need("b", self.b)?,
+                },
+                &["m", "b"],
+            ),
+            "power" => (
+                Shape::Power {
+                    k: need("k", self.k)?,
+                },
+                &["k"],
+            ),
+            "logistic" => (
+                Shape::Logistic {
+                    mid: need("mid", self.mid)?,
+                    slope: need("slope", self.slope)?,
+                },
+                &["mid", "slope"],
+            ),
+            "step" => (
+                Shape::Step {
+                    at: need("at", self.at)?,
+                },
+                &["at"],
+            ),
+            _ => {
+                return Err(format!(
+                    "{what}: unknown curve '{kind}' (want linear, power, logistic or step)"
+                ))
+            }
+        };
+        let given = [
+            ("m", self.m),
+            ("b", self.b),
+            ("k", self.k),
+            ("mid", self.mid),
+            ("slope", self.slope),
+            ("at", self.at),
+        ];
+        if let Some((p, _)) = given
+            .iter()
+            .find(|(p, v)| v.is_some() && !takes.contains(p))
+        {
+            return Err(format!("{what}: a {kind} curve takes no `{p}`"));
+        }
+        Ok(Curve {
+            shape,
+            invert: self.invert,
+        })
+    }
+}
+
+/// `c` as a one-line [`CurveFile`] mapping.
+fn curve_yaml(c: Curve) -> String {
+    let body = match c.shape {
+        Shape::Linear { m, b } => format!("curve: linear, m: {m}, b: {b}"),
+        Shape::Power { k } => format!("curve: power, k: {k}"),
+        Shape::Logistic { mid, slope } => format!("curve: logistic, mid: {mid}, slope: {slope}"),
+        Shape::Step { at } => format!("curve: step, at: {at}"),
+    };
+    if c.invert {
+        format!("{{ {body}, invert: true }}")
+    } else {
+        format!("{{ {body} }}")
+    }
+}
+
+fn action(name: &str) -> Result<Action, String> {
+    Action::from_name(name).ok_or_else(|| format!("unknown action '{name}'"))
+}
+
+/// A `[0,1]` trait, or `default` when the file leaves it out.
+fn unit(what: &str, v: Option<f32>, default: f32) -> Result<f32, String> {
+    ranged(what, v, default, 0.0, 1.0)
+}
+
+fn ranged(what: &str, v: Option<f32>, default: f32, lo: f32, hi: f32) -> Result<f32, String> {
+    match v {
+        None => Ok(default),
+        Some(x) if (lo..=hi).contains(&x) => Ok(x),
+        Some(x) => Err(format!("{what} = {x} is outside [{lo}, {hi}]")),
+    }
+}
+
+/// A weapon by its `use` name ([`Weapon::from_name`]).
+fn weapon(name: &str) -> Result<Weapon, String> {
+    Weapon::from_name(name).ok_or_else(|| format!("unknown weapon '{name}'"))
+}
+
+/// The repo's own `characters/` directory, for tests across the crate.
+#[cfg(test)]
+pub(crate) fn shipped() -> Characters {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../characters");
+    Characters::load(&dir).expect("shipped characters load")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_files_carry_the_reference_presets() {
+        let chars = shipped();
+        let traits = |f, n: &str| chars.resolve(f, n).unwrap().traits.clone();
+        let main = |n| match traits(Family::Main, n) {
+            BrainTraits::Main(p) => p,
+            t => panic!("{t:?}"),
+        };
+        let q3 = |n| match traits(Family::Q3, n) {
+            BrainTraits::Q3(c) => c,
+            t => panic!("{t:?}"),
+        };
+        let xon = |n| match traits(Family::Xon, n) {
+            BrainTraits::Xon(x) => x,
+            t => panic!("{t:?}"),
+        };
+        assert_eq!(main("rusher"), Persona::rusher());
+        assert_eq!(main("sniper"), Persona::sniper());
+        assert_eq!(main("scavenger"), Persona::scavenger());
+        assert_eq!(main("guard"), Persona::guard());
+        assert_eq!(q3("gru"), Q3Character::grunt());
+        assert_eq!(q3("maj"), Q3Character::major());
+        assert_eq!(q3("sar"), Q3Character::sarge());
+        assert_eq!(q3("cam"), Q3Character::camper());
+        assert_eq!(xon("rus"), XonSkill::rusher());
+        assert_eq!(xon("shp"), XonSkill::sharp());
+        assert_eq!(xon("trt"), XonSkill::turtle());
+        assert_eq!(xon("nob"), XonSkill::noob());
+        // The same name lives in two families without clashing.
+        assert_eq!(chars.resolve(Family::Xon, "rusher").unwrap().code, "rus");
+        assert_eq!(
+            chars.resolve(Family::Main, "RUSHER").unwrap().family(),
+            Family::Main
+        );
+        assert_eq!(chars.len(), 16);
+    }
+
+    #[test]
+    fn utility_profiles_override_weights_and_curves_by_key() {
+        let chars = shipped();
+        let bal = chars.resolve(Family::Utility, "bal").unwrap();
+        assert_eq!(bal.traits, BrainTraits::Utility(Box::default()));
+        assert_eq!(Family::for_brain(BrainKind::Utility), Some(Family::Utility));
+
+        let c = Character::parse(
+            Family::Utility,
+            "coward",
+            r#"
+weights: { retreat: 2 }
+curves:
+  retreat.health: { curve: step, at: 0.6, invert: true }
+"#,
+        )
+        .unwrap();
+        let BrainTraits::Utility(u) = c.traits else {
+            panic!("{:?}", c.traits)
+        };
+        assert_eq!(u.weight(Action::Retreat), 2.0);
+        assert_eq!(u.weight(Action::Engage), 1.0);
+        assert_eq!(
+            u.curve(Action::Retreat, Input::Health),
+            Some(Curve::step(0.6).inverted())
+        );
+
+        let err = |t| Character::parse(Family::Utility, "x", t).unwrap_err();
+        assert!(err("weights: { flee: 1 }\n").contains("unknown action 'flee'"));
+        assert!(err("weights: { camp: 9 }\n").contains("outside"));
+        assert!(err("curves: { camp.ammo: { curve: step, at: 1 } }\n").contains("no `ammo`"));
+        assert!(err("curves: { camp.need: { curve: power } }\n").contains("needs `k`"));
+        assert!(err("curves: { camp.need: { curve: power, k: 1, at: 2 } }\n").contains("no `at`"));
+        assert!(err("curves: { camp.need: { curve: cubic } }\n").contains("unknown curve"));
+        assert!(err("curves: { campneed: { curve: step, at: 1 } }\n").contains("<action>.<input>"));
+    }
+
+    #[test]
+    fn script_characters_are_compile_checked_rhai_files() {
+        let chars = shipped();
+        let hunter = chars.resolve(Family::Script, "hun").unwrap();
+        assert_eq!(hunter.name, "hunter");
+        assert!(hunter.script().unwrap().ends_with("script/hunter.rhai"));
+        assert_eq!(Family::for_brain(BrainKind::Script), Some(Family::Script));
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("script")).unwrap();
+        std::fs::write(dir.path().join("script/idle.rhai"), "fn tick(bot) { #{} }").unwrap();
+        std::fs::write(dir.path().join("script/notes.txt"), "not a script").unwrap();
+        assert_eq!(Characters::load(dir.path()).unwrap().len(), 1);
+        std::fs::write(dir.path().join("script/broken.rhai"), "fn tick(bot) {").unwrap();
+        let err = Characters::load(dir.path()).unwrap_err();
+        assert!(err.contains("broken.rhai"), "{err}");
+    }
+
+    #[test]
+    fn omitted_traits_fall_back_to_the_family_default() {
+        let c = Character::parse(
+            Family::Q3,
+            "steady-hand",
+            "aim_accuracy: 0.8\nper_weapon_accuracy:\n  Rail-Gun: 0.95\n",
+        )
+        .unwrap();
+        assert_eq!(c.code, "ste");
+        let BrainTraits::Q3(q) = c.traits else {
+            panic!("{:?}", c.traits)
+        };
+        assert_eq!(q.alertness, Q3Character::default().alertness);
+        assert_eq!(q.weapon_accuracy(Weapon::Railgun), 0.95);
+        assert_eq!(q.weapon_accuracy(Weapon::Shotgun), 0.8);
+
+        let x = Character::parse(Family::Xon, "aimy", "axes:\n  aim: 2\n").unwrap();
+        assert!(matches!(x.traits, BrainTraits::Xon(x) if x.aim() == 7.0));
+        let m = Character::parse(Family::Main, "rail", "weapon_pref: railgun\n").unwrap();
+        assert!(matches!(m.traits, BrainTraits::Main(p) if p.weapon_pref == Some(Weapon::Railgun)));
+    }
+
+    #[test]
+    fn to_yaml_round_trips_every_family() {
+        let chars = shipped();
+        // Scripts are code, not YAML; there is nothing to round-trip.
+        let yaml = Family::ALL.into_iter().filter(|&f| f != Family::Script);
+        for c in yaml.flat_map(|f| chars.of(f)) {
+            let text = c.to_yaml();
+            let back = Character::parse(c.family(), &c.name, &text).unwrap();
+            assert_eq!(&back, c.as_ref(), "{}:\n{text}", c.name);
+        }
+        let mut q = Q3Character::grunt();
+        q.per_weapon_accuracy = Some([0.25; 10]);
+        let c = Character {
+            name: "x".into(),
+            code: "x".into(),
+            skin: None,
+            traits: BrainTraits::Q3(q),
+        };
+        assert_eq!(Character::parse(Family::Q3, "x", &c.to_yaml()).unwrap(), c);
+    }
+
+    #[test]
+    fn bad_files_are_rejected_with_a_reason() {
+        let err = |f, n, t| Character::parse(f, n, t).unwrap_err();
+        assert!(err(Family::Q3, "x", "aim_acuracy: 0.5\n").contains("aim_acuracy"));
+        assert!(err(Family::Q3, "x", "jumper: 1.5\n").contains("outside"));
+        assert!(err(Family::Xon, "x", "axes:\n  aim: 40\n").contains("axes.aim"));
+        assert!(err(Family::Main, "x", "weapon_pref: spoon\n").contains("spoon"));
+        assert!(err(Family::Main, "x", "code: toolong\n").contains("code"));
+        assert!(err(Family::Main, "Bad_Name", "").contains("name"));
+        assert!(err(Family::Main, "x", "skin: athena\n").contains("model/skin"));
+
+        let mut chars = Characters::default();
+        chars
+            .insert(Character::parse(Family::Q3, "grunt", "code: gru\n").unwrap())
+            .unwrap();
+        let clash = Character::parse(Family::Q3, "gruff", "").unwrap();
+        assert!(chars.insert(clash).unwrap_err().contains("clashes"));
+        let elsewhere = Character::parse(Family::Xon, "grunt", "").unwrap();
+        assert!(chars.insert(elsewhere).is_ok());
+        assert!(chars
+            .resolve(Family::Main, "grunt")
+            .unwrap_err()
+            .contains("no main characters"));
+    }
+}
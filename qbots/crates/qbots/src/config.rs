@@ -1,7 +1,9 @@
 //! Load `config.yaml` — server address, on-disk Q2 paths, and the bot fleet roster.
 
+use crate::characters::{Character, Characters, Family};
 use serde::Deserialize;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
@@ -25,6 +27,64 @@ pub struct Server {
 pub struct Paths {
     pub server_cfg: PathBuf,
     pub baseq2: PathBuf,
+    /// Where each bot keeps its memory of the players it has met, one `<bot name>.txt` per bot
+    /// (see `brain::rivals`). Default `data/rivals`.
+    #[serde(default = "default_rivals_dir")]
+    pub rivals: PathBuf,
+    /// Where the fleet saves what it learned about each map's kill zones, one `<map>.txt` per
+    /// map (see `brain::heatmap`). Default `data/heatmaps`.
+    #[serde(default = "default_heatmaps_dir")]
+    pub heatmaps: PathBuf,
+    /// The character files `persona`/`char`/`xonchar`/`script`/`utility` names resolve against,
+    /// one subdirectory per brain family (see `crate::characters`). Default `characters`.
+    #[serde(default = "default_characters_dir")]
+    pub characters: PathBuf,
+    /// Where each bot writes its decision timeline, one `<unix_ts>.<bot>.<map>.jsonl` per map
+    /// it plays (see `brain::timeline`). Unset (the default) = no timelines; `--timeline`
+    /// sets it for one run.
+    #[serde(default)]
+    pub timelines: Option<PathBuf>,
+    /// Where each bot writes a replay capture of its brain's inputs and outputs, one
+    /// `<unix_ts>.<bot>.capture.jsonl` per connection (see `brain::replay`). Unset (the default)
+    /// = no captures; `--capture` sets it for one run.
+    #[serde(default)]
+    pub captures: Option<PathBuf>,
+}
+
+fn default_rivals_dir() -> PathBuf {
+    PathBuf::from("data/rivals")
+}
+
+fn default_heatmaps_dir() -> PathBuf {
+    PathBuf::from("data/heatmaps")
+}
+
+fn default_characters_dir() -> PathBuf {
+    PathBuf::from("characters")
+}
+
+impl Paths {
+    /// `bot`'s rival memory file.
+    pub fn rivals_file(&self, bot: &str) -> PathBuf {
+        self.rivals.join(format!("{bot}.txt"))
+    }
+
+    /// `map`'s saved heat.
+    pub fn heatmap_file(&self, map: &str) -> PathBuf {
+        self.heatmaps.join(format!("{map}.txt"))
+    }
+
+    /// The timeline `bot` starts on `map` at `unix_ts`; `None` when timelines are off.
+    pub fn timeline_file(&self, bot: &str, map: &str, unix_ts: u64) -> Option<PathBuf> {
+        let dir = self.timelines.as_ref()?;
+        Some(dir.join(format!("{unix_ts}.{bot}.{map}.jsonl")))
+    }
+
+    /// The replay capture `bot` starts at `unix_ts`; `None` when captures are off.
+    pub fn capture_file(&self, bot: &str, unix_ts: u64) -> Option<PathBuf> {
+        let dir = self.captures.as_ref()?;
+        Some(dir.join(format!("{unix_ts}.{bot}.capture.jsonl")))
+    }
 }
 
 /// Optional serverframe beacon (Plan 66) — publishes the fleet's view of `sv.framenum`
@@ -96,12 +156,30 @@ pub struct Fleet {
     /// `None`/absent → `main`. The CLI `--brain` overrides this. Independent of the nav backend
     /// (`--navmode`).
     pub brain: Option<String>,
-    /// Q3 personality for the fleet when `brain = "q3"`: `grunt`/`major`/`sarge`/`camper`.
+    /// Persona for `main`-brain fleet bots: a `characters/main/` name or code. `None`/absent →
+    /// the behavior-preserving default persona. CLI `--persona` overrides this.
+    pub persona: Option<String>,
+    /// Q3 personality for the fleet when `brain = "q3"`: a `characters/q3/` name or code.
     /// `None`/absent → the skill-derived default character. CLI `--char` overrides this.
     pub char: Option<String>,
-    /// Xonotic personality for `xon`-brain fleet bots (`rus`/`shp`/`trt`/`nob` or long names;
-    /// Plan 62). `None`/absent → a neutral XonSkill at the master skill level.
+    /// Xonotic personality for `xon`-brain fleet bots: a `characters/xon/` name or code.
+    /// `None`/absent → a neutral XonSkill at the master skill level. CLI `--xonchar` overrides.
     pub xonchar: Option<String>,
+    /// Script for `scr`-brain fleet bots: a `characters/script/` name or code. Required by that
+    /// brain (without one its bots stand idle). CLI `--script` overrides this.
+    pub script: Option<String>,
+    /// Utility profile for `uti`-brain fleet bots: a `characters/utility/` name or code.
+    /// `None`/absent → the default curves. CLI `--utility` overrides this.
+    pub utility: Option<String>,
+    /// Team for CTF / skin-teams deathmatch: `red`, `blue`, or `split` (alternate bots).
+    /// `None`/absent → free-for-all. See [`Self::team_for`].
+    pub team: Option<String>,
+    /// Start each bot's heatmap on a map from the heat saved by earlier runs, instead of
+    /// relearning the kill zones from zero. Off by default; saving happens either way.
+    pub seed_heatmaps: bool,
+    /// Aim through a modeled mouse hand (flicks, corrections, tracking lag) instead of each
+    /// brain's turn-rate aim. Off by default. See `brain::BrainConfig::human_aim`.
+    pub human_aim: bool,
 }
 
 impl Default for Fleet {
@@ -117,8 +195,14 @@ impl Default for Fleet {
             connect_timeout_ms: 10_000,
             stall_timeout_ms: 10_000,
             brain: None,
+            persona: None,
             char: None,
             xonchar: None,
+            script: None,
+            utility: None,
+            team: None,
+            seed_heatmaps: false,
+            human_aim: false,
         }
     }
 }
@@ -142,34 +226,50 @@ impl Fleet {
         }
     }
 
-    /// Parse the configured `char` string into a `CharPreset`. `None` (absent) → `None`
-    /// (skill-derived default); an unrecognized value falls back to `None` (logged).
-    pub fn char_preset(&self) -> Option<brain::CharPreset> {
-        use clap::ValueEnum;
-        match self.char.as_deref() {
-            None => None,
-            Some(s) => brain::CharPreset::from_str(s, true)
-                .map(Some)
-                .unwrap_or_else(|_| {
-                    tracing::warn!(char = s, "unknown [fleet].char; ignoring");
-                    None
-                }),
+    /// The configured name for `family`'s character (`persona`, `char`, `xonchar`, `script` or
+    /// `utility`).
+    pub fn character_name(&self, family: Family) -> Option<&str> {
+        match family {
+            Family::Main => self.persona.as_deref(),
+            Family::Q3 => self.char.as_deref(),
+            Family::Xon => self.xonchar.as_deref(),
+            Family::Script => self.script.as_deref(),
+            Family::Utility => self.utility.as_deref(),
         }
     }
 
-    /// Parse the configured `xonchar` string into an `XonCharPreset` (same contract as
-    /// [`Self::char_preset`]).
-    pub fn xonchar_preset(&self) -> Option<brain::XonCharPreset> {
-        use clap::ValueEnum;
-        match self.xonchar.as_deref() {
-            None => None,
-            Some(s) => brain::XonCharPreset::from_str(s, true)
-                .map(Some)
-                .unwrap_or_else(|_| {
-                    tracing::warn!(xonchar = s, "unknown [fleet].xonchar; ignoring");
-                    None
-                }),
+    /// Resolve the configured character for a `brain` fleet against the loaded `characters`.
+    /// Absent → `None` (the brain's default); an unknown name also falls back to `None` (logged),
+    /// so a stale config still runs.
+    pub fn character(
+        &self,
+        brain: brain::BrainKind,
+        characters: &Characters,
+    ) -> Option<Arc<Character>> {
+        let family = Family::for_brain(brain)?;
+        let name = self.character_name(family)?;
+        characters
+            .resolve(family, name)
+            .map_err(|e| tracing::warn!("[fleet].{}: {e}; ignoring", family.key()))
+            .ok()
+    }
+
+    /// Bot `i`'s team: the configured `red`/`blue`, or alternating red/blue for `split`.
+    /// `None` (absent) → no team; an unrecognized value → no team (logged).
+    pub fn team_for(&self, i: usize) -> Option<brain::Team> {
+        let s = self.team.as_deref()?;
+        if s.eq_ignore_ascii_case("split") {
+            return Some(if i.is_multiple_of(2) {
+                brain::Team::Red
+            } else {
+                brain::Team::Blue
+            });
         }
+        let team = brain::Team::parse(s);
+        if team.is_none() {
+            tracing::warn!(team = s, "unknown [fleet].team; playing free-for-all");
+        }
+        team
     }
 
     /// Is the fleet enabled (any bots to spawn)?
@@ -222,6 +322,29 @@ paths:
         );
         // Fleet defaults when absent.
         assert!(!cfg.fleet.enabled());
+        assert_eq!(
+            cfg.paths.rivals_file("qb0"),
+            PathBuf::from("data/rivals/qb0.txt")
+        );
+        assert_eq!(
+            cfg.paths.heatmap_file("q2dm1"),
+            PathBuf::from("data/heatmaps/q2dm1.txt")
+        );
+        assert!(!cfg.fleet.seed_heatmaps);
+        assert!(!cfg.fleet.human_aim);
+        assert_eq!(cfg.paths.timeline_file("qb0", "q2dm1", 7), None);
+        let mut paths = cfg.paths.clone();
+        paths.timelines = Some(PathBuf::from("logs/timeline"));
+        assert_eq!(
+            paths.timeline_file("qb0", "q2dm1", 7),
+            Some(PathBuf::from("logs/timeline/7.qb0.q2dm1.jsonl"))
+        );
+        assert_eq!(cfg.paths.capture_file("qb0", 7), None);
+        paths.captures = Some(PathBuf::from("logs/capture"));
+        assert_eq!(
+            paths.capture_file("qb0", 7),
+            Some(PathBuf::from("logs/capture/7.qb0.capture.jsonl"))
+        );
     }
 
     #[test]
@@ -243,6 +366,46 @@ fleet:
         assert_eq!(cfg.fleet.bot_name(5), "bot5");
         assert_eq!(cfg.fleet.qport_base, 28000);
         assert_eq!(cfg.fleet.connect_stagger_ms, 300);
+        assert_eq!(cfg.fleet.team_for(0), None);
+    }
+
+    #[test]
+    fn fleet_team_split_alternates() {
+        let yaml = "\
+server: { host: noir.lan, port: 27910 }
+paths: { server_cfg: /x, baseq2: /y }
+fleet: { count: 4, team: split }
+";
+        let cfg: Config = serde_yaml::from_str(yaml).unwrap();
+        let teams: Vec<_> = (0..4).map(|i| cfg.fleet.team_for(i)).collect();
+        use brain::Team::{Blue, Red};
+        assert_eq!(teams, vec![Some(Red), Some(Blue), Some(Red), Some(Blue)]);
+
+        let mut fleet = cfg.fleet.clone();
+        fleet.team = Some("blue".into());
+        assert_eq!(fleet.team_for(0), Some(Blue));
+        fleet.team = Some("green".into());
+        assert_eq!(fleet.team_for(0), None);
+    }
+
+    #[test]
+    fn fleet_character_follows_the_brain() {
+        let yaml = "\
+server: { host: noir.lan, port: 27910 }
+paths: { server_cfg: /x, baseq2: /y }
+fleet: { count: 4, persona: guard, char: maj, xonchar: nonesuch, script: hunter, utility: bra }
+";
+        let cfg: Config = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(cfg.paths.characters, PathBuf::from("characters"));
+        let chars = crate::characters::shipped();
+        let pick = |b| cfg.fleet.character(b, &chars).map(|c| c.name.clone());
+        assert_eq!(pick(brain::BrainKind::Main).as_deref(), Some("guard"));
+        assert_eq!(pick(brain::BrainKind::Quake3).as_deref(), Some("major"));
+        assert_eq!(pick(brain::BrainKind::Script).as_deref(), Some("hunter"));
+        assert_eq!(pick(brain::BrainKind::Utility).as_deref(), Some("brawler"));
+        // An unknown name falls back to the brain's default; brains without characters get none.
+        assert_eq!(pick(brain::BrainKind::Xon), None);
+        assert_eq!(pick(brain::BrainKind::Zb2), None);
     }
 
     /// Every config in the wild predates Plan 66 and has no `beacon:` block. Those configs
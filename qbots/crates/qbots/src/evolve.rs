@@ -0,0 +1,895 @@
+//! `qbots evolve` — tune a character by evolution over live competition rounds.
+//!
+//! Hand tuning a personality (Plan 38's Q3 presets, Plan 62's Xonotic re-tunes) is `competition`
+//! and `acceptance` on repeat: field a variant, read its K/D, judge it against the noise, nudge a
+//! trait, go again. This runs that loop unattended. A population of trait vectors for one
+//! character family is fielded as competition groups — one per candidate, plus an unchanged
+//! **control** (the seed character) — for one fixed-length round per generation. Each group's K/D
+//! is read off the FINAL scoreboard, and a small elitist GA breeds the next generation:
+//!
+//! - the best `elite` candidates survive and are fielded again, so their K/D becomes a mean over
+//!   more and more rounds instead of one lucky map cycle. A surviving elite only loses its place
+//!   to a newcomer whose mean beats its own by more than the noise floor (below);
+//! - every other slot is a child of two tournament-picked parents: uniform crossover, then a
+//!   Gaussian nudge of `sigma` × each trait's search range.
+//!
+//! Significance is the acceptance tool's rule (`tools/src/bin/acceptance.rs`): the control is the
+//! unchanged brain, so its round-to-round K/D spread is the noise floor, and a candidate whose mean
+//! beats the control's by less than that is noise, not an improvement. The run ends by writing
+//! the ranked survivors as character files (`<out>/<family>/<name>.yaml`), each with its numbers and
+//! verdict in the header, plus the final table as `report.txt`.
+//!
+//! Only the continuous traits evolve; a persona's `camper`/`weapon_pref`, a Q3 character's
+//! `per_weapon_accuracy`, a utility profile's curves and the skin are carried over from the seed.
+//! A utility profile evolves its action weights.
+
+use crate::characters::{Character, Family};
+use crate::config::Config;
+use crate::skins::Rng;
+use crate::supervisor::{self, GroupSpec, Shutdown};
+use brain::brains::utility::Action;
+use brain::BrainTraits;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Largest population: every candidate is at least one bot on the server.
+pub(crate) const MAX_POPULATION: usize = 32;
+
+/// The control group's character code (candidates are `e00`, `e01`, …).
+const CONTROL_CODE: &str = "ctl";
+
+/// One evolved trait and the range the search explores — inside what the character file
+/// accepts, but narrowed where the file's range is wider than anything sensible (a 5 s reaction
+/// time, a ±10 Xonotic axis).
+struct Gene {
+    name: &'static str,
+    lo: f32,
+    hi: f32,
+}
+
+const fn gene(name: &'static str, lo: f32, hi: f32) -> Gene {
+    Gene { name, lo, hi }
+}
+
+const MAIN_GENES: &[Gene] = &[
+    gene("aggression", 0.0, 1.0),
+    gene("risk_tolerance", 0.0, 1.0),
+    gene("chase_commit", 0.0, 1.0),
+    gene("item_greed", 0.0, 1.0),
+    gene("shadow_pref", 0.0, 1.0),
+];
+
+const Q3_GENES: &[Gene] = &[
+    gene("attack_skill", 0.0, 1.0),
+    gene("reaction_time", 0.0, 2.0),
+    gene("aim_accuracy", 0.0, 1.0),
+    gene("aim_skill", 0.0, 1.0),
+    gene("croucher", 0.0, 1.0),
+    gene("jumper", 0.0, 1.0),
+    gene("walker", 0.0, 1.0),
+    gene("aggression", 0.0, 1.0),
+    gene("self_preservation", 0.0, 1.0),
+    gene("vengefulness", 0.0, 1.0),
+    gene("camper", 0.0, 1.0),
+    gene("easy_fragger", 0.0, 1.0),
+    gene("alertness", 0.0, 1.0),
+    gene("firethrottle", 0.0, 1.0),
+];
+
+/// `skill` plus the 12 axes, which the vendor's bot table keeps within about ±3.
+const XON_GENES: &[Gene] = &[
+    gene("skill", 0.0, 10.0),
+    gene("axes.keyboard", -3.0, 3.0),
+    gene("axes.movement", -3.0, 3.0),
+    gene("axes.dodge", -3.0, 3.0),
+    gene("axes.ping", -3.0, 3.0),
+    gene("axes.weapon", -3.0, 3.0),
+    gene("axes.aggres", -3.0, 3.0),
+    gene("axes.rangepref", -3.0, 3.0),
+    gene("axes.aim", -3.0, 3.0),
+    gene("axes.offset", -3.0, 3.0),
+    gene("axes.mouse", -3.0, 3.0),
+    gene("axes.think", -3.0, 3.0),
+    gene("axes.ai", -3.0, 3.0),
+];
+
+/// A utility profile's action weights, in `Action::ALL` order. The curves stay the seed's.
+const UTILITY_GENES: &[Gene] = &[
+    gene("weights.engage", 0.0, 2.0),
+    gene("weights.retreat", 0.0, 2.0),
+    gene("weights.grab", 0.0, 2.0),
+    gene("weights.hunt", 0.0, 2.0),
+    gene("weights.camp", 0.0, 2.0),
+    gene("weights.dodge", 0.0, 2.0),
+    gene("weights.roam", 0.0, 0.5),
+];
+
+fn genes_of(family: Family) -> &'static [Gene] {
+    match family {
+        Family::Main => MAIN_GENES,
+        Family::Q3 => Q3_GENES,
+        Family::Xon => XON_GENES,
+        // A script's behavior is its code; there is nothing numeric to mutate.
+        Family::Script => &[],
+        Family::Utility => UTILITY_GENES,
+    }
+}
+
+/// `traits` as a gene vector, in [`genes_of`] order.
+fn encode(traits: &BrainTraits) -> Vec<f32> {
+    match traits {
+        BrainTraits::Main(p) => vec![
+            p.aggression,
+            p.risk_tolerance,
+            p.chase_commit,
+            p.item_greed,
+            p.shadow_pref,
+        ],
+        BrainTraits::Q3(c) => vec![
+            c.attack_skill,
+            c.reaction_time,
+            c.aim_accuracy,
+            c.aim_skill,
+            c.croucher,
+            c.jumper,
+            c.walker,
+            c.aggression,
+            c.self_preservation,
+            c.vengefulness,
+            c.camper,
+            c.easy_fragger,
+            c.alertness,
+            c.firethrottle,
+        ],
+        BrainTraits::Xon(x) => {
+            let a = &x.axes;
+            vec![
+                x.skill,
+                a.keyboard,
+                a.movement,
+                a.dodge,
+                a.ping,
+                a.weapon,
+                a.aggres,
+                a.rangepref,
+                a.aim,
+                a.offset,
+                a.mouse,
+                a.think,
+                a.ai,
+            ]
+        }
+        BrainTraits::Script(_) => Vec::new(),
+        BrainTraits::Utility(u) => Action::ALL.iter().map(|&a| u.weight(a)).collect(),
+    }
+}
+
+/// `base` with its evolved traits replaced by `g` (the inverse of [`encode`]); whatever doesn't
+/// evolve is kept from `base`.
+fn decode(base: &BrainTraits, g: &[f32]) -> BrainTraits {
+    match *base {
+        BrainTraits::Main(mut p) => {
+            [
+                p.aggression,
+                p.risk_tolerance,
+                p.chase_commit,
+                p.item_greed,
+                p.shadow_pref,
+            ] = [g[0], g[1], g[2], g[3], g[4]];
+            BrainTraits::Main(p)
+        }
+        BrainTraits::Q3(mut c) => {
+            [
+                c.attack_skill,
+                c.reaction_time,
+                c.aim_accuracy,
+                c.aim_skill,
+                c.croucher,
+                c.jumper,
+                c.walker,
+                c.aggression,
+                c.self_preservation,
+                c.vengefulness,
+                c.camper,
+                c.easy_fragger,
+                c.alertness,
+                c.firethrottle,
+            ] = std::array::from_fn(|i| g[i]);
+            BrainTraits::Q3(c)
+        }
+        BrainTraits::Xon(mut x) => {
+            let a = &mut x.axes;
+            x.skill = g[0];
+            [
+                a.keyboard,
+                a.movement,
+                a.dodge,
+                a.ping,
+                a.weapon,
+                a.aggres,
+                a.rangepref,
+                a.aim,
+                a.offset,
+                a.mouse,
+                a.think,
+                a.ai,
+            ] = std::array::from_fn(|i| g[i + 1]);
+            BrainTraits::Xon(x)
+        }
+        BrainTraits::Script(ref path) => BrainTraits::Script(path.clone()),
+        BrainTraits::Utility(ref u) => {
+            let mut u = u.clone();
+            for (&a, &w) in Action::ALL.iter().zip(g) {
+                u.set_weight(a, w);
+            }
+            BrainTraits::Utility(u)
+        }
+    }
+}
+
+/// One member of the population.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Candidate {
+    /// Stable across generations — the `-e<id>` in its name.
+    pub(crate) id: u32,
+    pub(crate) genes: Vec<f32>,
+    /// Its K/D in every round it was fielded.
+    pub(crate) kds: Vec<f32>,
+}
+
+impl Candidate {
+    pub(crate) fn mean_kd(&self) -> f32 {
+        mean(&self.kds)
+    }
+}
+
+fn mean(xs: &[f32]) -> f32 {
+    if xs.is_empty() {
+        0.0
+    } else {
+        xs.iter().sum::<f32>() / xs.len() as f32
+    }
+}
+
+/// Best mean K/D first; more rounds break a tie (the better-measured one leads), then the id.
+fn by_rank(a: &Candidate, b: &Candidate) -> std::cmp::Ordering {
+    b.mean_kd()
+        .total_cmp(&a.mean_kd())
+        .then_with(|| b.kds.len().cmp(&a.kds.len()))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+/// Round-to-round spread (max − min) — the acceptance tool's noise band.
+fn spread(xs: &[f32]) -> f32 {
+    let max = xs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let min = xs.iter().copied().fold(f32::INFINITY, f32::min);
+    if xs.is_empty() {
+        0.0
+    } else {
+        max - min
+    }
+}
+
+/// How a candidate's mean K/D compares with the control's, judged against the noise floor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Verdict {
+    /// The gap (candidate − control) exceeds the floor — a real difference, either way.
+    Signal(f32),
+    /// The gap is within the floor: inconclusive.
+    Noise(f32),
+    /// The control has run fewer than two rounds, so there is no floor yet.
+    NoFloor(f32),
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Signal(gap) => write!(f, "SIGNAL ({gap:+.2} vs control)"),
+            Verdict::Noise(gap) => write!(f, "noise — inconclusive ({gap:+.2} vs control)"),
+            Verdict::NoFloor(gap) => write!(f, "no noise floor yet ({gap:+.2} vs control)"),
+        }
+    }
+}
+
+/// The GA state: the seed, the population, and the control's record.
+pub(crate) struct Evolution {
+    /// The seed character: generation 0's first candidate, the control, and the source of every
+    /// trait that doesn't evolve.
+    base: Character,
+    pop: Vec<Candidate>,
+    /// The control group's K/D per round.
+    control: Vec<f32>,
+    /// Ids of the last [`Evolution::breed`]'s survivors, which a newcomer only displaces by
+    /// more than the noise floor.
+    elites: Vec<u32>,
+    population: usize,
+    elite: usize,
+    sigma: f32,
+    next_id: u32,
+    generation: u32,
+    rng: Rng,
+}
+
+impl Evolution {
+    /// Generation 0: the seed itself plus `population − 1` mutants of it.
+    pub(crate) fn new(
+        base: Character,
+        population: usize,
+        elite: usize,
+        sigma: f32,
+        rng: Rng,
+    ) -> Result<Self, String> {
+        if !(2..=MAX_POPULATION).contains(&population) {
+            return Err(format!(
+                "population must be 2..={MAX_POPULATION}, got {population}"
+            ));
+        }
+        if elite == 0 || elite >= population {
+            return Err(format!(
+                "elite must be 1..{population} (below the population), got {elite}"
+            ));
+        }
+        if !(sigma > 0.0 && sigma <= 1.0) {
+            return Err(format!("sigma must be in (0, 1], got {sigma}"));
+        }
+        if genes_of(base.family()).is_empty() {
+            return Err(format!(
+                "{} characters have no traits to evolve",
+                base.family().dir()
+            ));
+        }
+        let mut evo = Self {
+            pop: Vec::new(),
+            control: Vec::new(),
+            elites: Vec::new(),
+            population,
+            elite,
+            sigma,
+            next_id: 0,
+            generation: 0,
+            rng,
+            base,
+        };
+        let seed = evo.clamp(&encode(&evo.base.traits));
+        evo.push(seed.clone());
+        while evo.pop.len() < population {
+            let child = evo.mutate(&seed);
+            evo.push(child);
+        }
+        Ok(evo)
+    }
+
+    pub(crate) fn family(&self) -> Family {
+        self.base.family()
+    }
+
+    fn genes(&self) -> &'static [Gene] {
+        genes_of(self.family())
+    }
+
+    fn push(&mut self, genes: Vec<f32>) {
+        self.pop.push(Candidate {
+            id: self.next_id,
+            genes,
+            kds: Vec::new(),
+        });
+        self.next_id += 1;
+    }
+
+    /// Clamp each gene into its search range and round it to the hundredth, so the files this run
+    /// writes read like hand-made ones.
+    fn clamp(&self, g: &[f32]) -> Vec<f32> {
+        g.iter()
+            .zip(self.genes())
+            .map(|(&x, r)| (x.clamp(r.lo, r.hi) * 100.0).round() / 100.0)
+            .collect()
+    }
+
+    /// Nudge every gene by `sigma` × its range.
+    fn mutate(&mut self, g: &[f32]) -> Vec<f32> {
+        let nudged: Vec<f32> = g
+            .iter()
+            .zip(self.genes())
+            .map(|(&x, r)| x + self.rng.normal() * self.sigma * (r.hi - r.lo))
+            .collect();
+        self.clamp(&nudged)
+    }
+
+    /// The candidate at `slot` as a fieldable character (code `e<slot>`).
+    fn candidate_character(&self, c: &Candidate, slot: usize) -> Character {
+        Character {
+            name: format!("{}-e{}", self.base.name, c.id),
+            code: format!("e{slot:02}"),
+            skin: self.base.skin.clone(),
+            traits: decode(&self.base.traits, &c.genes),
+        }
+    }
+
+    /// This generation's groups: the control first, then every candidate in slot order.
+    pub(crate) fn fielded(&self) -> Vec<Arc<Character>> {
+        let control = Character {
+            code: CONTROL_CODE.to_string(),
+            ..self.base.clone()
+        };
+        std::iter::once(control)
+            .chain(
+                self.pop
+                    .iter()
+                    .enumerate()
+                    .map(|(slot, c)| self.candidate_character(c, slot)),
+            )
+            .map(Arc::new)
+            .collect()
+    }
+
+    /// Log one finished round: the control's K/D, then each candidate's in slot order (the
+    /// order of [`Evolution::fielded`] after the control). `None` is a group the round didn't
+    /// measure; it gets no entry rather than a K/D of 0.
+    pub(crate) fn record(&mut self, control: Option<f32>, kds: &[Option<f32>]) {
+        self.control.extend(control);
+        for (c, &kd) in self.pop.iter_mut().zip(kds) {
+            c.kds.extend(kd);
+        }
+    }
+
+    /// The control's round-to-round K/D spread; `None` until it has run twice.
+    pub(crate) fn noise_floor(&self) -> Option<f32> {
+        (self.control.len() >= 2).then(|| spread(&self.control))
+    }
+
+    pub(crate) fn verdict(&self, c: &Candidate) -> Verdict {
+        self.judge(c.mean_kd() - mean(&self.control))
+    }
+
+    /// A K/D gap judged against the noise floor.
+    fn judge(&self, gap: f32) -> Verdict {
+        match self.noise_floor() {
+            None => Verdict::NoFloor(gap),
+            Some(floor) if gap.abs() > floor => Verdict::Signal(gap),
+            Some(_) => Verdict::Noise(gap),
+        }
+    }
+
+    /// Candidates fielded at least once, best mean K/D first; more rounds break a tie (the
+    /// better-measured one leads).
+    pub(crate) fn ranked(&self) -> Vec<&Candidate> {
+        let mut r: Vec<&Candidate> = self.pop.iter().filter(|c| !c.kds.is_empty()).collect();
+        r.sort_by(|a, b| by_rank(a, b));
+        r
+    }
+
+    /// Whether `challenger` beats `incumbent` by more than the noise floor (by anything at all
+    /// while there is no floor yet).
+    fn displaces(&self, challenger: &Candidate, incumbent: &Candidate) -> bool {
+        match self.judge(challenger.mean_kd() - incumbent.mean_kd()) {
+            Verdict::Signal(gap) | Verdict::NoFloor(gap) => gap > 0.0,
+            Verdict::Noise(_) => false,
+        }
+    }
+
+    /// The `elite` survivors: the best by mean K/D, except that a previous elite which fell
+    /// below the cut takes back the place of the weakest newcomer above it unless that
+    /// newcomer's lead is [`Verdict::Signal`].
+    fn select(&self, ranked: &[Candidate]) -> Vec<Candidate> {
+        let incumbent = |c: &Candidate| self.elites.contains(&c.id);
+        let mut kept: Vec<Candidate> = ranked.iter().take(self.elite).cloned().collect();
+        for inc in ranked.iter().skip(self.elite).filter(|c| incumbent(c)) {
+            let Some(weakest) = kept.iter().rposition(|c| !incumbent(c)) else {
+                break;
+            };
+            if !self.displaces(&kept[weakest], inc) {
+                kept[weakest] = inc.clone();
+            }
+        }
+        kept.sort_by(by_rank);
+        kept
+    }
+
+    /// The next generation: the [`select`](Evolution::select)ed elites survive with their
+    /// records, candidates never measured are fielded again, and the rest are children.
+    pub(crate) fn breed(&mut self) {
+        let ranked: Vec<Candidate> = self.ranked().into_iter().cloned().collect();
+        if ranked.is_empty() {
+            return; // nothing was measured — field the same population again
+        }
+        let unmeasured: Vec<Candidate> = self
+            .pop
+            .iter()
+            .filter(|c| c.kds.is_empty())
+            .cloned()
+            .collect();
+        self.pop = self.select(&ranked);
+        self.elites = self.pop.iter().map(|c| c.id).collect();
+        let room = self.population.saturating_sub(self.pop.len());
+        self.pop.extend(unmeasured.into_iter().take(room));
+        while self.pop.len() < self.population {
+            let a = self.tournament(&ranked);
+            let b = self.tournament(&ranked);
+            let crossed: Vec<f32> = a
+                .genes
+                .iter()
+                .zip(&b.genes)
+                .map(|(&x, &y)| if self.rng.unit() < 0.5 { x } else { y })
+                .collect();
+            let child = self.mutate(&crossed);
+            self.push(child);
+        }
+        self.generation += 1;
+    }
+
+    /// The better of two random picks.
+    fn tournament<'a>(&mut self, ranked: &'a [Candidate]) -> &'a Candidate {
+        let a = &ranked[self.rng.below(ranked.len())];
+        let b = &ranked[self.rng.below(ranked.len())];
+        if b.mean_kd() > a.mean_kd() {
+            b
+        } else {
+            a
+        }
+    }
+
+    /// The standings table: one row per measured candidate plus the control and the floor.
+    pub(crate) fn report(&self) -> String {
+        let floor = self
+            .noise_floor()
+            .map_or_else(|| "n/a".to_string(), |f| format!("{f:.2}"));
+        let mut out = format!(
+            "generation {} — control {} mean_kd {:.2} over {} rounds, noise floor {floor}\n",
+            self.generation,
+            self.base.name,
+            mean(&self.control),
+            self.control.len(),
+        );
+        out += "rank  candidate             mean_kd  [min..max]   runs  verdict\n";
+        for (i, c) in self.ranked().iter().enumerate() {
+            let lo = c.kds.iter().copied().fold(f32::INFINITY, f32::min);
+            let hi = c.kds.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            out += &format!(
+                "#{:<4} {:<20} {:>7.2}  [{lo:.2}..{hi:.2}]  {:>4}  {}\n",
+                i + 1,
+                format!("{}-e{}", self.base.name, c.id),
+                c.mean_kd(),
+                c.kds.len(),
+                self.verdict(c),
+            );
+        }
+        out
+    }
+
+    /// The traits `c` moved away from the seed, e.g. `aim_accuracy 0.40→0.62`.
+    fn changes(&self, c: &Candidate) -> String {
+        let seed = encode(&self.base.traits);
+        let moved: Vec<String> = self
+            .genes()
+            .iter()
+            .zip(seed.iter().zip(&c.genes))
+            .filter(|(_, (a, b))| (*a - *b).abs() >= 0.005)
+            .map(|(g, (a, b))| format!("{} {a:.2}→{b:.2}", g.name))
+            .collect();
+        if moved.is_empty() {
+            "unchanged from the seed".to_string()
+        } else {
+            moved.join(", ")
+        }
+    }
+
+    /// Write the `keep` best as character files under `<dir>/<family>/`, plus `report.txt`.
+    /// Each file is named `<seed>-e<id>` with code `<seed initial><rank>`; returns the paths.
+    pub(crate) fn write(&self, dir: &Path, keep: usize) -> Result<Vec<PathBuf>, String> {
+        let sub = dir.join(self.family().dir());
+        std::fs::create_dir_all(&sub).map_err(|e| format!("create {}: {e}", sub.display()))?;
+        let initial = self.base.name.chars().next().unwrap_or('e');
+        let mut paths = Vec::new();
+        for (i, c) in self.ranked().into_iter().take(keep).enumerate() {
+            let rank = i + 1;
+            let character = Character {
+                code: format!("{initial}{rank}"),
+                ..self.candidate_character(c, i)
+            };
+            let header = format!(
+                "# {} — evolved from {} by `qbots evolve` (generation {}, rank {rank}).\n\
+                 # mean K/D {:.2} over {} rounds; {}.\n\
+                 # {}\n",
+                character.name,
+                self.base.name,
+                self.generation,
+                c.mean_kd(),
+                c.kds.len(),
+                self.verdict(c),
+                self.changes(c),
+            );
+            let path = sub.join(format!("{}.yaml", character.name));
+            std::fs::write(&path, header + character.to_yaml().as_str())
+                .map_err(|e| format!("write {}: {e}", path.display()))?;
+            paths.push(path);
+        }
+        let report = dir.join("report.txt");
+        std::fs::write(&report, self.report())
+            .map_err(|e| format!("write {}: {e}", report.display()))?;
+        Ok(paths)
+    }
+}
+
+/// How to field each generation.
+pub(crate) struct Setup {
+    pub(crate) mode: crate::NavMode,
+    /// Bots per candidate group.
+    pub(crate) count: usize,
+    pub(crate) generations: u32,
+    pub(crate) round: Duration,
+    /// How many of the ranked survivors to write out.
+    pub(crate) keep: usize,
+    pub(crate) out: PathBuf,
+    pub(crate) qport_base: Option<u16>,
+    pub(crate) loose_botcap: bool,
+}
+
+/// Run `setup.generations` timed competition rounds, breeding between them, then write the
+/// ranked result. Ctrl-C ends the run early: the interrupted round is discarded and what was
+/// measured so far is still written.
+pub(crate) async fn run(
+    cfg: Arc<Config>,
+    addr: SocketAddr,
+    mut evo: Evolution,
+    setup: Setup,
+) -> std::io::Result<()> {
+    let abort = Shutdown::new();
+    let _signals = supervisor::spawn_signal_listener(abort.clone());
+    let brain = evo.family().brain();
+    let mut failed = Ok(());
+    for g in 0..setup.generations {
+        let fielded = evo.fielded();
+        let specs: Vec<GroupSpec> = fielded
+            .iter()
+            .map(|c| GroupSpec {
+                mode: setup.mode,
+                brain,
+                character: Some(Arc::clone(c)),
+                count: setup.count,
+                skin: c.skin.clone(),
+                team: None,
+                tag: supervisor::group_tag(setup.mode, brain, Some(c)),
+            })
+            .collect();
+        tracing::info!(
+            generation = g,
+            groups = specs.len(),
+            secs = setup.round.as_secs(),
+            "evolve: fielding generation"
+        );
+        // The round ends on its timer, or early on Ctrl-C.
+        let round_end = Shutdown::new();
+        let timer = tokio::spawn({
+            let (abort, round_end, len) = (abort.clone(), round_end.clone(), setup.round);
+            async move {
+                abort.sleep_or_cancel(len).await;
+                round_end.fire();
+            }
+        });
+        let round = supervisor::compete(
+            Arc::clone(&cfg),
+            addr,
+            specs,
+            setup.qport_base,
+            setup.loose_botcap,
+            round_end,
+        )
+        .await;
+        timer.abort();
+        let round = match round {
+            Ok(r) => r,
+            Err(e) => {
+                failed = Err(e);
+                break;
+            }
+        };
+        if let Err(e) = round.joined {
+            failed = Err(e);
+            break;
+        }
+        if abort.requested() {
+            tracing::warn!(
+                generation = g,
+                "evolve: interrupted — discarding the unfinished round"
+            );
+            break;
+        }
+        let kd_of = |spec: &GroupSpec| {
+            round
+                .board
+                .iter()
+                .find(|s| s.tag == spec.tag)
+                .and_then(supervisor::ModeScore::measured_kd)
+        };
+        let kds: Vec<Option<f32>> = round.specs.iter().map(kd_of).collect();
+        for (spec, _) in round.specs.iter().zip(&kds).filter(|(_, kd)| kd.is_none()) {
+            tracing::warn!(
+                generation = g,
+                group = spec.tag,
+                "evolve: group traded no frags; not scored this round"
+            );
+        }
+        evo.record(kds[0], &kds[1..]);
+        for line in evo.report().lines() {
+            tracing::info!("evolve: {line}");
+        }
+        if g + 1 < setup.generations {
+            evo.breed();
+        }
+    }
+    match evo.write(&setup.out, setup.keep) {
+        Ok(paths) if paths.is_empty() => {
+            tracing::warn!("evolve: no round finished; nothing to write")
+        }
+        Ok(paths) => {
+            for p in &paths {
+                tracing::info!(path = %p.display(), "evolve: wrote character");
+            }
+            tracing::info!(
+                dir = %setup.out.display(),
+                "evolve: copy the ones worth keeping into characters/{}/ (rename + recode first)",
+                evo.family().dir()
+            );
+        }
+        Err(e) => tracing::error!("evolve: {e}"),
+    }
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::characters::shipped;
+
+    fn evo(family: Family, name: &str) -> Evolution {
+        let base = shipped().resolve(family, name).unwrap().as_ref().clone();
+        Evolution::new(base, 6, 2, 0.15, Rng::seeded(7)).unwrap()
+    }
+
+    #[test]
+    fn encode_decode_round_trips_every_family() {
+        let chars = shipped();
+        for c in Family::ALL.into_iter().flat_map(|f| chars.of(f)) {
+            let g = encode(&c.traits);
+            assert_eq!(g.len(), genes_of(c.family()).len(), "{}", c.name);
+            assert_eq!(decode(&c.traits, &g), c.traits, "{}", c.name);
+        }
+        let hunter = chars.resolve(Family::Script, "hunter").unwrap();
+        let evo = Evolution::new(hunter.as_ref().clone(), 6, 2, 0.15, Rng::seeded(7));
+        assert!(evo.is_err_and(|e| e.contains("no traits")));
+    }
+
+    #[test]
+    fn mutants_stay_inside_the_search_ranges_and_parse_back() {
+        for (family, name) in [
+            (Family::Main, "guard"),
+            (Family::Q3, "grunt"),
+            (Family::Xon, "sharp"),
+            (Family::Utility, "brawler"),
+        ] {
+            let e = evo(family, name);
+            let fielded = e.fielded();
+            assert_eq!(fielded.len(), 7, "control + 6 candidates");
+            assert_eq!(fielded[0].code, "ctl");
+            assert_eq!(fielded[0].traits, e.base.traits);
+            assert_eq!(fielded[1].traits, e.base.traits, "candidate 0 is the seed");
+            assert_ne!(fielded[2].traits, e.base.traits);
+            for c in &e.pop {
+                for (x, r) in c.genes.iter().zip(genes_of(family)) {
+                    assert!((r.lo..=r.hi).contains(x), "{} = {x}", r.name);
+                }
+            }
+            // What the run writes must load back as a character file.
+            for c in &fielded {
+                let back = Character::parse(family, &c.name, &c.to_yaml()).unwrap();
+                assert_eq!(back.traits, c.traits);
+            }
+        }
+    }
+
+    #[test]
+    fn elites_survive_with_their_record_and_children_fill_the_rest() {
+        let mut e = evo(Family::Q3, "grunt");
+        e.record(Some(1.0), &[0.5, 2.0, 0.1, 1.5, 0.3, 0.2].map(Some));
+        let best: Vec<u32> = e.ranked().iter().take(2).map(|c| c.id).collect();
+        assert_eq!(best, [1, 3]);
+        e.breed();
+        assert_eq!(e.generation, 1);
+        assert_eq!(e.pop.len(), 6);
+        assert_eq!(e.pop[0].id, 1);
+        assert_eq!(e.pop[0].kds, [2.0]);
+        assert_eq!(e.pop[1].id, 3);
+        assert!(e.pop[2..].iter().all(|c| c.kds.is_empty() && c.id >= 6));
+        // Unmeasured children don't rank.
+        assert_eq!(e.ranked().len(), 2);
+    }
+
+    #[test]
+    fn unmeasured_candidates_are_not_scored_and_are_fielded_again() {
+        let mut e = evo(Family::Q3, "grunt");
+        e.record(
+            None,
+            &[Some(0.5), None, Some(0.1), Some(1.5), None, Some(0.2)],
+        );
+        assert!(
+            e.control.is_empty(),
+            "an unmeasured control widens no floor"
+        );
+        assert!(e.pop[1].kds.is_empty() && e.pop[4].kds.is_empty());
+        assert_eq!(e.ranked().len(), 4);
+        e.breed();
+        let ids: Vec<u32> = e.pop.iter().take(4).map(|c| c.id).collect();
+        assert_eq!(ids, [3, 0, 1, 4], "elites, then the unmeasured");
+        assert_eq!(e.pop.len(), 6);
+    }
+
+    #[test]
+    fn the_control_spread_decides_significance() {
+        // Plan 30's q3 control numbers: 1.00, 0.86, 2.60 — a 1.74 floor.
+        let mut e = evo(Family::Q3, "grunt");
+        e.record(Some(1.0), &[3.0, 1.5, 1.0, 1.0, 1.0, 1.0].map(Some));
+        assert!(matches!(e.verdict(&e.pop[0]), Verdict::NoFloor(_)));
+        e.record(Some(0.86), &[3.0, 1.5, 1.0, 1.0, 1.0, 1.0].map(Some));
+        e.record(Some(2.6), &[3.0, 1.5, 1.0, 1.0, 1.0, 1.0].map(Some));
+        assert!((e.noise_floor().unwrap() - 1.74).abs() < 1e-5);
+        // control mean ≈ 1.49: +1.51 is still inside the floor, so it's not a win yet.
+        assert!(matches!(e.verdict(&e.pop[0]), Verdict::Noise(_)));
+        e.record(Some(1.0), &[5.0, 1.5, 1.0, 1.0, 1.0, 1.0].map(Some));
+        assert!(matches!(e.verdict(&e.pop[0]), Verdict::Signal(g) if g > 1.74));
+        assert!(e.report().contains("SIGNAL"));
+    }
+
+    #[test]
+    fn an_elite_keeps_its_place_unless_beaten_beyond_the_floor() {
+        let mut e = evo(Family::Q3, "grunt");
+        // Control 1.0 then 2.0: a 1.0 floor. Elites after the first breed: ids 1 and 3.
+        e.record(Some(1.0), &[0.5, 2.0, 0.1, 1.5, 0.3, 0.2].map(Some));
+        e.breed();
+        assert_eq!(e.elites, [1, 3]);
+        // A child edges past elite 3 (1.5 → mean 1.4) by less than the floor: 3 stays.
+        e.record(Some(2.0), &[2.0, 1.3, 1.9, 0.0, 0.0, 0.0].map(Some));
+        let child = e.pop[2].id;
+        e.breed();
+        assert_eq!(
+            e.elites,
+            [1, 3],
+            "a noise-level lead doesn't displace an elite"
+        );
+        // A child clears elite 3 by more than the floor: it takes the place.
+        e.record(Some(1.5), &[2.0, 1.4, 3.0, 0.0, 0.0, 0.0].map(Some));
+        let strong = e.pop[2].id;
+        assert_ne!(strong, child);
+        e.breed();
+        assert_eq!(e.elites, [strong, 1]);
+    }
+
+    #[test]
+    fn write_emits_ranked_loadable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut e = evo(Family::Xon, "sharp");
+        e.record(Some(1.0), &[0.5, 2.0, 0.1, 1.5, 0.3, 0.2].map(Some));
+        let paths = e.write(dir.path(), 2).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert!(paths[0].ends_with("xon/sharp-e1.yaml"));
+        let loaded = crate::characters::Characters::load(dir.path()).unwrap();
+        assert_eq!(loaded.resolve(Family::Xon, "s1").unwrap().name, "sharp-e1");
+        assert_eq!(loaded.resolve(Family::Xon, "s2").unwrap().name, "sharp-e3");
+        assert!(dir.path().join("report.txt").exists());
+    }
+
+    #[test]
+    fn bad_parameters_are_refused() {
+        let base = shipped()
+            .resolve(Family::Q3, "grunt")
+            .unwrap()
+            .as_ref()
+            .clone();
+        let new = |p, k, s| Evolution::new(base.clone(), p, k, s, Rng::seeded(1)).err();
+        assert!(new(1, 1, 0.1).unwrap().contains("population"));
+        assert!(new(4, 4, 0.1).unwrap().contains("elite"));
+        assert!(new(4, 1, 0.0).unwrap().contains("sigma"));
+        assert!(new(4, 1, 0.1).is_none());
+    }
+}
@@ -25,7 +25,9 @@ macro_rules! fatal {
 }
 
 mod beacon;
+mod characters;
 mod config;
+mod evolve;
 mod roster;
 mod scenario;
 mod skins;
@@ -157,18 +159,34 @@ enum Cmd {
         /// Quake 3-derived brain). Independent of `--navmode`.
         #[arg(long, value_enum, default_value_t = brain::BrainKind::Main)]
         brain: brain::BrainKind,
-        /// Q3 personality (only for `--brain q3`): `grunt`/`major`/`sarge`/`camper`. Absent →
-        /// the skill-derived default character.
-        #[arg(long, value_enum)]
-        char: Option<brain::CharPreset>,
-        /// Persona (only for `--brain main`): `rusher`/`sniper`/`scavenger`/`guard`. Absent →
-        /// the behavior-preserving default persona.
+        /// Q3 personality (only for `--brain q3`): a `characters/q3/` name or code (shipped:
+        /// `grunt`/`major`/`sarge`/`camper`). Absent → the skill-derived default character.
+        #[arg(long)]
+        char: Option<String>,
+        /// Persona (only for `--brain main`): a `characters/main/` name or code (shipped:
+        /// `rusher`/`sniper`/`scavenger`/`guard`). Absent → the behavior-preserving default.
         #[arg(long)]
         persona: Option<String>,
-        /// Xonotic personality (only for `--brain xon`): `rus`/`shp`/`trt`/`nob` (Plan 60).
-        /// Absent → a neutral XonSkill at the master skill level.
-        #[arg(long, value_enum)]
-        xonchar: Option<brain::XonCharPreset>,
+        /// Xonotic personality (only for `--brain xon`): a `characters/xon/` name or code
+        /// (shipped: `rus`/`shp`/`trt`/`nob`). Absent → a neutral XonSkill at the master skill.
+        #[arg(long)]
+        xonchar: Option<String>,
+        /// Script (required by `--brain scr`): a `characters/script/` name or code (shipped:
+        /// `hunter`). The bot reloads it whenever the file changes.
+        #[arg(long)]
+        script: Option<String>,
+        /// Utility profile (only for `--brain uti`): a `characters/utility/` name or code
+        /// (shipped: `balanced`/`brawler`/`survivor`). Absent → the default curves.
+        #[arg(long)]
+        utility: Option<String>,
+        /// Write the bot's per-tick decision timeline under this directory (overrides
+        /// `[paths].timelines`); render it with the `timeline` tool.
+        #[arg(long)]
+        timeline: Option<std::path::PathBuf>,
+        /// Record the bot's brain inputs and outputs under this directory for `qbots replay`
+        /// (overrides `[paths].captures`).
+        #[arg(long)]
+        capture: Option<std::path::PathBuf>,
     },
     /// Launch the full bot fleet from the config's `[fleet]` roster.
     Run {
@@ -209,11 +227,35 @@ enum Cmd {
         /// Give each bot a random female skin.
         #[arg(long, group = "skin_sel")]
         skin_random_female: bool,
-        /// Q3 personality for the whole fleet (only for `--brain q3`):
-        /// `grunt`/`major`/`sarge`/`camper`. Overrides `[fleet].char`. Pins each bot's skin to
-        /// the character's. Absent → the skill-derived default character.
-        #[arg(long, value_enum)]
-        char: Option<brain::CharPreset>,
+        /// Q3 personality for the whole fleet (only for `--brain q3`), from `characters/q3/`.
+        /// Overrides `[fleet].char`. Pins each bot's skin to the character's. Absent → the
+        /// skill-derived default character.
+        #[arg(long)]
+        char: Option<String>,
+        /// Persona for the whole fleet (only for `--brain main`), from `characters/main/`.
+        /// Overrides `[fleet].persona`.
+        #[arg(long)]
+        persona: Option<String>,
+        /// Xonotic personality for the whole fleet (only for `--brain xon`), from
+        /// `characters/xon/`. Overrides `[fleet].xonchar`.
+        #[arg(long)]
+        xonchar: Option<String>,
+        /// Script for the whole fleet (required by `--brain scr`), from `characters/script/`.
+        /// Overrides `[fleet].script`.
+        #[arg(long)]
+        script: Option<String>,
+        /// Utility profile for the whole fleet (only for `--brain uti`), from
+        /// `characters/utility/`. Overrides `[fleet].utility`.
+        #[arg(long)]
+        utility: Option<String>,
+        /// Write every bot's per-tick decision timeline under this directory (overrides
+        /// `[paths].timelines`); render one with the `timeline` tool.
+        #[arg(long)]
+        timeline: Option<std::path::PathBuf>,
+        /// Record every bot's brain inputs and outputs under this directory for `qbots replay`
+        /// (overrides `[paths].captures`).
+        #[arg(long)]
+        capture: Option<std::path::PathBuf>,
         /// Proceed with warnings instead of failing when a bot can't join (e.g. the
         /// server's `maxclients` is full). Default: any join failure aborts the fleet
         /// with a non-zero exit.
@@ -231,9 +273,15 @@ enum Cmd {
         addr: Option<String>,
         /// Field an explicit hand-picked group list from a YAML file instead of the CLI matrix
         /// (see `crates/qbots/src/roster.rs` for the schema). Mutually exclusive with
-        /// `--count`/`--navmodes`/`--brains`/`--chars`/`--xonchars`. Every competition run also
-        /// *emits* a ranked roster to `./logs/roster/<ts>.yaml` — trim it and pass it back here.
-        #[arg(long, conflicts_with_all = ["count", "modes", "brains", "chars", "xonchars"])]
+        /// `--count`/`--navmodes`/`--brains`/`--personas`/`--chars`/`--xonchars`/`--scripts`/
+        /// `--utilities`. Every competition run also *emits* a ranked roster to
+        /// `./logs/roster/<ts>.yaml` — trim it and pass it back here.
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "count", "modes", "brains", "personas", "chars", "xonchars", "scripts", "utilities"
+            ]
+        )]
         roster: Option<String>,
         /// Bots to spawn **per group** (default 8), a group = one (navmode, brain) pair. Total =
         /// navmodes × brains × count, clamped by `[fleet].max_bots` (server maxclients headroom).
@@ -247,16 +295,30 @@ enum Cmd {
         /// Spawns the full `{modes} × {brains}` cross product. e.g. `--brains main,q3`.
         #[arg(long = "brains", value_enum, value_delimiter = ',')]
         brains: Vec<brain::BrainKind>,
-        /// Q3 personalities to field for the `q3` brain, comma-separated (e.g.
-        /// `--chars grunt,major,sarge,camper`). Each becomes its own group/skin. Ignored by
+        /// Personas to field for the `main` brain, comma-separated `characters/main/` names (e.g.
+        /// `--personas rusher,guard`). Each becomes its own group. Ignored by non-`main` brains.
+        /// Absent → one default-persona `main` group.
+        #[arg(long = "personas", value_delimiter = ',')]
+        personas: Vec<String>,
+        /// Q3 personalities to field for the `q3` brain, comma-separated `characters/q3/` names
+        /// (e.g. `--chars grunt,major,sarge,camper`). Each becomes its own group/skin. Ignored by
         /// non-`q3` brains. Absent → one default-character `q3` group.
-        #[arg(long = "chars", value_enum, value_delimiter = ',')]
-        chars: Vec<brain::CharPreset>,
-        /// Xonotic personalities to field for the `xon` brain, comma-separated (e.g.
-        /// `--xonchars rus,shp,trt,nob`). Each becomes its own group/skin. Ignored by
+        #[arg(long = "chars", value_delimiter = ',')]
+        chars: Vec<String>,
+        /// Xonotic personalities to field for the `xon` brain, comma-separated `characters/xon/`
+        /// names (e.g. `--xonchars rus,shp,trt,nob`). Each becomes its own group/skin. Ignored by
         /// non-`xon` brains. Absent → one neutral `xon` group.
-        #[arg(long = "xonchars", value_enum, value_delimiter = ',')]
-        xonchars: Vec<brain::XonCharPreset>,
+        #[arg(long = "xonchars", value_delimiter = ',')]
+        xonchars: Vec<String>,
+        /// Scripts to field for the `scr` brain, comma-separated `characters/script/` names (e.g.
+        /// `--scripts hunter`). Each becomes its own group. Required when `--brains` has `scr`.
+        #[arg(long = "scripts", value_delimiter = ',')]
+        scripts: Vec<String>,
+        /// Utility profiles to field for the `uti` brain, comma-separated `characters/utility/`
+        /// names (e.g. `--utilities brawler,survivor`). Each becomes its own group/skin. Ignored
+        /// by non-`uti` brains. Absent → one default-profile `uti` group.
+        #[arg(long = "utilities", value_delimiter = ',')]
+        utilities: Vec<String>,
         /// Base qport; group `g` bot `i` uses `base + g*count + i` (disjoint per-group blocks,
         /// group = a (mode,brain[,char]) tuple). Per-process default if omitted.
         #[arg(long)]
@@ -267,6 +329,58 @@ enum Cmd {
         #[arg(long)]
         loose_botcap: bool,
     },
+    /// Tune one brain's character by evolution: field a population of trait variants (plus the
+    /// unchanged seed as a control) as competition groups for `--generations` timed rounds,
+    /// breed the best by K/D, and write the ranked survivors as character files. A candidate only
+    /// counts as better when it beats the control by more than the control's own round-to-round
+    /// spread (the `acceptance` noise floor). Ctrl-C stops early and still writes the results.
+    Evolve {
+        /// Server address (defaults to config's server).
+        #[arg(long)]
+        addr: Option<String>,
+        /// The brain to tune: `main` (personas), `q3` or `xon`.
+        #[arg(long, value_enum, default_value_t = brain::BrainKind::Quake3)]
+        brain: brain::BrainKind,
+        /// The seed character (name or code in the brain's family): generation 0 is built around
+        /// it and it plays every round unchanged as the control. Absent → the family default.
+        #[arg(long)]
+        from: Option<String>,
+        /// Nav backend every group uses.
+        #[arg(long = "navmode", value_enum, default_value_t = NavMode::Astar)]
+        mode: NavMode,
+        /// Candidates per generation (2–32).
+        #[arg(long, default_value_t = 8)]
+        population: usize,
+        /// Best candidates carried into the next generation unchanged.
+        #[arg(long, default_value_t = 2)]
+        elite: usize,
+        #[arg(long, default_value_t = 10)]
+        generations: u32,
+        /// Length of each generation's round, in seconds.
+        #[arg(long, default_value_t = 300)]
+        round_secs: u64,
+        /// Bots per candidate (and for the control).
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+        /// Mutation step, as a fraction of each trait's range.
+        #[arg(long, default_value_t = 0.15)]
+        sigma: f32,
+        /// Seed the GA's random draws, to make a run's breeding reproducible.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How many of the ranked survivors to write out (default: the elite).
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Output directory (default `./logs/evolve/<ts>/`); files land in `<out>/<family>/`.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+        /// Base qport; per-process default if omitted.
+        #[arg(long)]
+        qport_base: Option<u16>,
+        /// Proceed with warnings instead of failing when a bot can't join.
+        #[arg(long)]
+        loose_botcap: bool,
+    },
     /// Print the loaded config (server + paths + fleet) and exit.
     Config,
     /// Query the server's connectionless `status` (map + player list). The fleet
@@ -284,6 +398,10 @@ enum Cmd {
     Pvs { map: String },
     /// Generate the nav graph for a map and find a corner-to-corner path.
     Nav { map: String },
+    /// Feed a brain capture (`--capture`) back into a freshly built brain of the same kind,
+    /// character and seed, and diff its output tick by tick. Exits 1 on the first divergence.
+    /// Needs the captured map's nav cache; no server.
+    Replay { capture: std::path::PathBuf },
     /// Drive one bot from spawn to the farthest DM spawn point; log movement; stop.
     /// The measurement lens for movement quality (Plan 10).
     SpawnToSpawn {
@@ -813,8 +931,9 @@ async fn resolve_addr(addr: &str) -> Result<SocketAddr, String> {
 fn build_roster_specs(
     path: &str,
     baseq2: &std::path::Path,
+    chars: &characters::Characters,
 ) -> Result<Vec<supervisor::GroupSpec>, String> {
-    let mut specs = roster::Roster::load(path)?.into_specs()?;
+    let mut specs = roster::Roster::load(path)?.into_specs(chars)?;
     let need = specs.iter().filter(|s| s.skin.is_none()).count();
     if need > 0 {
         let mut rng = skins::Rng::new();
@@ -826,6 +945,77 @@ fn build_roster_specs(
     Ok(specs)
 }
 
+/// Load the configured characters directory, logging what was found. A bad file is fatal: a
+/// fleet shouldn't launch half-configured.
+fn load_characters(cfg: &Config) -> Result<characters::Characters, ExitCode> {
+    let dir = &cfg.paths.characters;
+    match characters::Characters::load(dir) {
+        Ok(chars) => {
+            if chars.is_empty() {
+                tracing::warn!(dir = %dir.display(), "no characters found; bots play the defaults");
+            } else {
+                tracing::info!(count = chars.len(), dir = %dir.display(), "loaded characters");
+            }
+            Ok(chars)
+        }
+        Err(e) => {
+            tracing::error!("characters: {e}");
+            Err(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// The character a `brain` bot plays from the per-family CLI flags: only the flag of the
+/// brain's own family applies (the rest are ignored, as they always were). `None` when that flag
+/// is absent; an unknown name is an error.
+fn cli_character(
+    chars: &characters::Characters,
+    brain: brain::BrainKind,
+    persona: Option<&str>,
+    char: Option<&str>,
+    xonchar: Option<&str>,
+    script: Option<&str>,
+    utility: Option<&str>,
+) -> Result<Option<Arc<characters::Character>>, String> {
+    use characters::Family;
+    let Some(family) = Family::for_brain(brain) else {
+        return Ok(None);
+    };
+    let name = match family {
+        Family::Main => persona,
+        Family::Q3 => char,
+        Family::Xon => xonchar,
+        Family::Script => script,
+        Family::Utility => utility,
+    };
+    name.map(|n| chars.resolve(family, n)).transpose()
+}
+
+/// Leave this bot's heatmap session with the fleet's [`supervisor::HeatBank`].
+fn bank_heat(
+    heat: &supervisor::HeatBank,
+    obs: Option<&brain::HeatmapObserver>,
+    key: Option<&(String, u64)>,
+) {
+    if let (Some(obs), Some((map, fp))) = (obs, key) {
+        heat.deposit(map, *fp, obs.record());
+    }
+}
+
+/// A UDP socket on a fresh local port, connected to `addr`.
+async fn rebind(addr: SocketAddr) -> std::io::Result<tokio::net::UdpSocket> {
+    let sock = tokio::net::UdpSocket::bind("0.0.0.0:0").await?;
+    sock.connect(addr).await?;
+    Ok(sock)
+}
+
+/// Best-effort save of a bot's rival memory.
+fn save_rivals(book: &brain::RivalBook, path: &std::path::Path) {
+    if let Err(e) = book.save(path) {
+        tracing::warn!("could not save rival memory: {e}");
+    }
+}
+
 /// Wrapper that adds signal handling for graceful shutdown.
 /// Sends a disconnect packet before teardown when SIGINT/SIGTERM received.
 /// One bot's connection → frames → brain loop. Shares the nav graph via
@@ -837,30 +1027,31 @@ pub(crate) async fn bot_task(
     name: &str,
     qport: u16,
     skin: Option<&str>,
+    // CTF / skin-teams team: wears the team skin and sends `team <name>` on every map.
+    team: Option<brain::Team>,
     cfg: &Config,
     nav_cache: &supervisor::NavCache,
     shutdown: &supervisor::Shutdown,
     stats: &supervisor::FleetStats,
+    // Where this bot leaves its heatmap's session totals when it leaves a level.
+    heat: &supervisor::HeatBank,
     // Plan 66: relays `sv.framenum` to qctrl. `None` when the beacon is disabled (the
     // default), in which case this bot behaves exactly as it did before.
     beacon: Option<&beacon::Beacon>,
     mode: NavMode,
     brain_kind: brain::BrainKind,
-    char: Option<brain::CharPreset>,
-    persona: Option<brain::persona::Persona>,
-    xonchar: Option<brain::XonCharPreset>,
+    // The character file this bot plays, if any — always of `brain_kind`'s own family.
+    character: Option<&characters::Character>,
 ) -> std::io::Result<()> {
     use brain::perception::Worldview;
     // `Brain` is the plugin trait (its methods resolve on the `Box<dyn Brain>` the factory
     // returns); `build_brain`/`BrainKind` select the implementation, mirroring `build_navigator`.
     use brain::{
-        build_brain, BotSkill, Brain, BrainConfig, BrainContext, BrainMap, MovementController,
-        Navigator,
+        build_brain, BotSkill, Brain, BrainConfig, BrainContext, MovementController, Navigator,
     };
     use client::{Conn, ConnState};
     use q2proto::Usercmd;
     use std::time::Duration;
-    use tokio::net::UdpSocket;
     use tokio::time;
 
     // T1 diagnostic toggle: log live brush-model (`*N`) entity origins each frame (read-only).
@@ -882,10 +1073,12 @@ pub(crate) async fn bot_task(
 
     // Mutable since Plan 64: a hard server restart (svc_reconnect) rebinds to a fresh
     // local port so stale packets from the dead connection can't poison the new one.
-    let mut sock = UdpSocket::bind("0.0.0.0:0").await?;
-    sock.connect(addr).await?;
+    let mut sock = rebind(addr).await?;
     let mut conn = Conn::new(addr, name, qport);
-    if let Some(s) = skin {
+    // A team bot wears its team skin (keeping the model): CTF forces it anyway, and a
+    // skin-teams deathmatch groups players by it.
+    let team_skin = team.map(|t| t.skin_for(skin));
+    if let Some(s) = team_skin.as_deref().or(skin) {
         // Userinfo skin is sent in the `connect` handshake, so set it before `start`.
         conn.userinfo.set("skin", s);
         tracing::info!(skin = s, "using skin");
@@ -919,16 +1112,21 @@ pub(crate) async fn bot_task(
     let mut rejoin_hold: Option<time::Instant> = None;
 
     let mut move_ctrl = MovementController::new();
+    // A replay capture (`[paths].captures`) of everything the brain is fed. Its brain is
+    // seeded from the bot's name instead of the process-wide ordinal so a replay can match it.
+    let mut capture = open_capture(cfg, name, brain_kind, character);
     // The decision layer (Plan 22): owns combat/FSM/dodge/steering/recovery/skill/roam.
     // Built early; learns the nav graph at map load via `set_map`. The `Navigator` is
     // injected into `brain.tick` each frame — the brain uses nav, never owns it.
     let mut brain: Box<dyn Brain + Send> = build_brain(
         brain_kind,
         BotSkill::default(),
-        BrainConfig::default(),
-        char,
-        persona,
-        xonchar,
+        BrainConfig {
+            seed: capture.as_ref().map(|_| capture_seed(name)),
+            human_aim: cfg.fleet.human_aim,
+            ..BrainConfig::default()
+        },
+        character.map(|c| c.traits.clone()),
     );
     // Boxed behind the `Navigator` trait so the tick loop is backend-agnostic: `--navmode`
     // picks A* (waypoint graph) or navmesh (polygons + funnel) at map load. `+ Send`
@@ -937,6 +1135,10 @@ pub(crate) async fn bot_task(
     // Collision model the nav graph was built from — for LOS gating (Plan 11) and
     // reactive wall probes (Plan 13). Set when the map loads.
     let mut collision: Option<Arc<world::CollisionModel>> = None;
+    // This bot's live door/lift/train state, cloned from the map's template at load.
+    let mut nav_state: Option<world::NavState> = None;
+    // The map's lightmaps, for the `lightlevel` each usercmd reports.
+    let mut light: Option<Arc<world::LightMap>> = None;
     let mut map_loaded = false;
     // Plan 64: the servercount the loaded map belongs to. Every SV_SpawnServer bumps it,
     // so a mismatch against the live serverdata means the server changed (or restarted)
@@ -959,11 +1161,33 @@ pub(crate) async fn bot_task(
     // at last time we were alive (death attribution, before the respawn teleport).
     let mut heatmap_obs: Option<brain::HeatmapObserver> = None;
     let mut last_alive_pos: Option<Vec3> = None;
+    // `(map, nav fingerprint)` the heatmap was built on — what its record is banked under.
+    let mut heat_key: Option<(String, u64)> = None;
+
+    // Rival memory: what this bot has learned about the players it meets, kept across
+    // sessions. Per-bot like the heatmap — it only knows what this bot saw. A file we
+    // can't read starts a fresh book, saved over it.
+    let rivals_file = cfg.paths.rivals_file(name);
+    let mut rivals = match brain::RivalBook::load(&rivals_file) {
+        Ok(book) => {
+            tracing::info!(records = book.len(), "loaded rival memory");
+            book
+        }
+        Err(e) => {
+            tracing::warn!("rival memory not loaded, starting a fresh one: {e}");
+            brain::RivalBook::new()
+        }
+    };
+    // Route danger from the rivals on the server, refreshed with the roster.
+    let mut rival_danger: Vec<f32> = Vec::new();
 
     // Plan 51: wall-press/stall episode detector — one `EVT wall_press` line per
     // sustained intent-vs-motion mismatch (brain-agnostic, observational only).
     let mut stall_mon = brain::StallMonitor::new();
 
+    // The decision timeline for the current map, with when it started (`[paths].timelines`).
+    let mut timeline: Option<(brain::TimelineWriter, Instant)> = None;
+
     // Plan 53: connect-phase deadline. A bot that never reaches `Active` within this
     // window (e.g. a silently-dropped handshake the reject parse can't classify) fails
     // its join instead of hanging forever. Per bot_task invocation, so it resets on each
@@ -995,7 +1219,9 @@ pub(crate) async fn bot_task(
     let mut last_send = Instant::now();
     let mut send_timing = client::SendTiming::new();
 
-    loop {
+    // Every way out of the session — shutdown, the server dropping us, a timeout or socket error
+    // the supervisor retries on — breaks out here, so the level's heat and rival memory are kept.
+    let exit = 'session: loop {
         if shutdown.requested() {
             // Plan 64: also send the clean disconnect while merely Connected (mid
             // map-change re-handshake) — otherwise our slot lingers server-side as a
@@ -1008,12 +1234,15 @@ pub(crate) async fn bot_task(
                 }
                 time::sleep(Duration::from_millis(100)).await;
             }
-            return Ok(());
+            break 'session Ok(());
         }
 
         tokio::select! {
             res = sock.recv(&mut buf) => {
-                let n = res?;
+                let n = match res {
+                    Ok(n) => n,
+                    Err(e) => break 'session Err(e),
+                };
                 // Plan 57: remember which frame we held before parsing, so we can detect a
                 // freshly-decoded snapshot below and ack it on arrival.
                 let prev_sf = conn.frame.as_ref().map(|f| f.serverframe);
@@ -1045,8 +1274,10 @@ pub(crate) async fn bot_task(
                     // ghost slots piled up to "Server is full."). A fresh local port
                     // makes the stale copies undeliverable, like a real client restart.
                     if now_state == ConnState::Connecting {
-                        sock = UdpSocket::bind("0.0.0.0:0").await?;
-                        sock.connect(addr).await?;
+                        sock = match rebind(addr).await {
+                            Ok(s) => s,
+                            Err(e) => break 'session Err(e),
+                        };
                         // Hold the getchallenge too — the ticker sends it (and its
                         // 2 s resends) once the jitter window passes.
                         reply = None;
@@ -1062,7 +1293,7 @@ pub(crate) async fn bot_task(
                         tracing::warn!(text = %text.trim_end(), "server print at disconnect");
                     }
                     tracing::info!("disconnected");
-                    return Ok(());
+                    break 'session Ok(());
                 }
                 // Plan 53: the server refused our join (Server is full., Bad challenge., …).
                 // Surface it as a fatal, non-retryable error so the fleet can fail loudly.
@@ -1080,7 +1311,8 @@ pub(crate) async fn bot_task(
                     } else {
                         std::io::ErrorKind::ConnectionRefused
                     };
-                    return Err(std::io::Error::new(kind, format!("join rejected: {reason}")));
+                    let msg = format!("join rejected: {reason}");
+                    break 'session Err(std::io::Error::new(kind, msg));
                 }
 
                 // Plan 57: ack the just-arrived server frame immediately. The server
@@ -1144,7 +1376,7 @@ pub(crate) async fn bot_task(
                     } else {
                         std::io::ErrorKind::TimedOut
                     };
-                    return Err(std::io::Error::new(kind, "connect handshake timed out"));
+                    break 'session Err(std::io::Error::new(kind, "connect handshake timed out"));
                 }
 
                 // Plan 65: frame-stall watchdog (see `last_frame_seen` above). While not
@@ -1159,7 +1391,7 @@ pub(crate) async fn bot_task(
                         stall_timeout_ms = cfg.fleet.stall_timeout_ms,
                         "no server frames while Active — slot presumed dead, re-handshaking"
                     );
-                    return Err(std::io::Error::new(
+                    break 'session Err(std::io::Error::new(
                         std::io::ErrorKind::ConnectionReset,
                         "server frames stalled while Active",
                     ));
@@ -1285,7 +1517,12 @@ pub(crate) async fn bot_task(
                     beacon_map.clear();
                     nav_driver = None;
                     collision = None;
+                    nav_state = None;
+                    light = None;
+                    bank_heat(heat, heatmap_obs.as_ref(), heat_key.take().as_ref());
                     heatmap_obs = None;
+                    rival_danger.clear();
+                    save_rivals(&rivals, &rivals_file);
                     last_serverframe = None;
                     last_health = None;
                     last_armor = None;
@@ -1294,10 +1531,12 @@ pub(crate) async fn bot_task(
                     last_alive_pos = None;
                     stall_mon = brain::StallMonitor::new();
                     send_timing = client::SendTiming::new();
+                    timeline = None;
                     // Same semantics as the respawn teleport: clears enemy/goal/FSM state
                     // that would otherwise reference the old map. `set_map` below re-feeds
                     // the graph/items when the new nav graph loads.
                     brain.on_death();
+                    record_capture(&mut capture, &brain::replay::Event::Death);
                 }
 
                 if !map_loaded && state == ConnState::Active {
@@ -1311,6 +1550,10 @@ pub(crate) async fn bot_task(
                                 .unwrap_or(&bsp_path)
                                 .to_owned();
                             map_loaded = true;
+                            if let Some(t) = team {
+                                // CTF's `team` command (`CTFTeam_f`); a no-op elsewhere.
+                                conn.queue_stringcmd(&format!("team {}", t.name()));
+                            }
                             map_servercount = servercount;
                             beacon_map = map.clone(); // Plan 66
                             // Plan 70: note this level fleet-wide. Keyed on servercount, so all N
@@ -1319,17 +1562,23 @@ pub(crate) async fn bot_task(
                             if let Some(sc) = servercount {
                                 stats.record_map_load(sc, &map);
                             }
+                            // Fleet-mates are not rivals.
+                            for (bot, _) in stats.snapshot() {
+                                rivals.exclude(&bot);
+                            }
                             tracing::info!(map, bsp = %bsp_path, "loading nav graph");
                             // Shared across the fleet: built once per map, reused as Arc.
                             if let Some(map_nav) = nav_cache.get_or_build(cfg, &map) {
-                                // The navmesh backend can't path to a bare A* node index,
-                                // so it resolves roam goals to world positions instead.
-                                brain.set_map(BrainMap {
-                                    roam_nodes: map_nav.roam_nodes.clone(),
-                                    nav_graph: Arc::clone(&map_nav.graph),
-                                    roam_as_position: matches!(mode, NavMode::Navmesh),
-                                    items: map_nav.items.clone(),
-                                });
+                                let roam_as_position = matches!(mode, NavMode::Navmesh);
+                                brain.set_map(map_nav.brain_map(roam_as_position));
+                                record_capture(
+                                    &mut capture,
+                                    &brain::replay::Event::Map(brain::replay::MapRef {
+                                        name: map.clone(),
+                                        fingerprint: map_nav.fingerprint,
+                                        roam_as_position,
+                                    }),
+                                );
                                 nav_driver = Some(build_navigator(
                                     mode,
                                     Arc::clone(&map_nav.graph),
@@ -1342,10 +1591,27 @@ pub(crate) async fn bot_task(
                                     },
                                 ));
                                 collision = Some(Arc::clone(&map_nav.cm));
-                                heatmap_obs = Some(brain::HeatmapObserver::new(
+                                nav_state = Some(map_nav.nav_state.clone());
+                                light = Some(Arc::clone(&map_nav.light));
+                                let mut obs = brain::HeatmapObserver::new(
                                     Arc::clone(&map_nav.graph),
                                     name,
-                                ));
+                                );
+                                if cfg.fleet.seed_heatmaps {
+                                    let path = cfg.paths.heatmap_file(&map);
+                                    match brain::HeatRecord::load(&path, map_nav.fingerprint) {
+                                        Ok(Some(rec)) if obs.seed(&rec) => tracing::info!(
+                                            map,
+                                            sessions = rec.sessions,
+                                            "seeded heatmap from earlier runs"
+                                        ),
+                                        Ok(_) => {}
+                                        Err(e) => tracing::warn!("heatmap not seeded: {e}"),
+                                    }
+                                }
+                                heatmap_obs = Some(obs);
+                                heat_key = Some((map.clone(), map_nav.fingerprint));
+                                timeline = open_timeline(cfg, name, &map, mode, brain_kind);
                             }
                         }
                     }
@@ -1381,7 +1647,9 @@ pub(crate) async fn bot_task(
                     }
                 } else if state == ConnState::Active {
                     if let Some(frame) = frame_opt {
-                        let view = Worldview::from_frame(&frame, &cs, playernum);
+                        let mut view = Worldview::from_frame(&frame, &cs, playernum);
+                        // Rank known killers above strangers for every target picker.
+                        rivals.mark_threats(&mut view, &cs);
 
                         // T1 (diagnostic): with QBOTS_OBSERVE_MOVERS set, log MOVING non-player
                         // entities each frame — their live wire origin + per-frame delta. Brush
@@ -1460,6 +1728,7 @@ pub(crate) async fn bot_task(
                                     // next frame's playerstate re-feed both. `on_death`
                                     // also eases the Eraser auto-skill down.
                                     brain.on_death();
+                                    record_capture(&mut capture, &brain::replay::Event::Death);
                                     stats.record_death(name);
                                     // Plan 08: record where we died (highest-confidence
                                     // danger) and force a replan so the new path avoids it.
@@ -1493,6 +1762,7 @@ pub(crate) async fn bot_task(
                             if current_frags > prev {
                                 tracing::info!(frags = current_frags, gained = current_frags - prev, "*** FRAG ***");
                                 brain.on_kill();
+                                record_capture(&mut capture, &brain::replay::Event::Kill);
                                 stats.record_kill(name);
                             }
                         }
@@ -1526,6 +1796,16 @@ pub(crate) async fn bot_task(
                             }
                         }
 
+                        // Movers: feed the brush entities we can see into this bot's nav
+                        // state, then let the navigator plan around shut doors and hold at
+                        // closed ones that will open (no-op unless the state changed).
+                        if let (Some(nav), Some(ns)) = (nav_driver.as_mut(), nav_state.as_mut()) {
+                            ns.observe(view.entities().filter(|e| !e.is_stale).filter_map(
+                                |e| Some((e.inline_model?, e.origin.to_array())),
+                            ));
+                            nav.note_nav_state(ns);
+                        }
+
                         // Drain server prints once per frame. First classify our own
                         // environmental suicides (lava/slime/drown/squish/…) from the
                         // obituary — the wire carries no means-of-death, only the print —
@@ -1553,8 +1833,35 @@ pub(crate) async fn bot_task(
                             for text in &prints {
                                 obs.on_print(text, name, frame.serverframe);
                             }
+                            // `beacon_map` is cleared on a level change, so nothing from the
+                            // new level is filed under the old map.
+                            rivals.observe(
+                                &beacon_map,
+                                name,
+                                obs,
+                                &view,
+                                &cs,
+                                &prints,
+                                frame.serverframe,
+                                HEATMAP_DT,
+                            );
                             let (w_danger, w_pop) = brain.heatmap_weights();
-                            let overlay = obs.cost_overlay(w_danger, w_pop);
+                            let mut overlay = obs.cost_overlay(w_danger, w_pop);
+                            // The haunts of whichever rivals are on the server count as
+                            // danger too. The roster changes slowly; refresh every ~5 s.
+                            if rival_danger.len() != overlay.len() || ticks.is_multiple_of(50) {
+                                let names = brain::perception::player_names(&cs);
+                                let present: Vec<&str> = names.iter().map(String::as_str).collect();
+                                rival_danger =
+                                    rivals.route_danger(&beacon_map, &present, obs.graph());
+                            }
+                            for (o, d) in overlay.iter_mut().zip(&rival_danger) {
+                                *o += w_danger * d;
+                            }
+                            // Periodic save, so a crash costs minutes, not the session.
+                            if ticks.is_multiple_of(3000) {
+                                save_rivals(&rivals, &rivals_file);
+                            }
                             if let Some(nav) = nav_driver.as_mut() {
                                 nav.set_risk_overlay(overlay);
                             }
@@ -1591,7 +1898,7 @@ pub(crate) async fn bot_task(
                         // goal selection, steering, stuck recovery, jump-edge, dodge. The
                         // nav driver is injected (used, never owned); the brain returns a
                         // MovementIntent + an optional weapon switch.
-                        let out = brain.tick(BrainContext {
+                        let ctx = BrainContext {
                             view: &view,
                             nav: nav_driver.as_deref_mut().map(|n| n as &mut dyn Navigator),
                             cm: collision.as_deref(),
@@ -1599,7 +1906,29 @@ pub(crate) async fn bot_task(
                             ticks,
                             // The live fleet brain drives its own FSM/item/roam goal ladder.
                             goal_override: None,
-                        });
+                        };
+                        let out = if capture.is_some() {
+                            let (out, event) = brain::replay::tick_taped(&mut *brain, ctx);
+                            record_capture(&mut capture, &event);
+                            out
+                        } else {
+                            brain.tick(ctx)
+                        };
+
+                        if let Some((w, started)) = timeline.as_mut() {
+                            let rec = brain::TickRecord::capture(
+                                started.elapsed().as_secs_f32(),
+                                frame.serverframe,
+                                &view,
+                                &*brain,
+                                nav_driver.as_deref().map(|n| n as &dyn Navigator),
+                                &out,
+                            );
+                            if let Err(e) = w.record(&rec) {
+                                tracing::warn!("decision timeline stopped: {e}");
+                                timeline = None;
+                            }
+                        }
 
                         // Plan 51: feed the wall-press/stall detector. The wall probe
                         // only runs on hindered ticks (pushing but not moving).
@@ -1670,6 +1999,13 @@ pub(crate) async fn bot_task(
                         }
 
                         move_ctrl.set_msec(dt);
+                        // Report how lit our eye is, as a real client would (`vieworg` =
+                        // origin + viewoffset; crouching lowers it).
+                        if let Some(light) = light.as_deref() {
+                            let mut eye = view.self_state().origin.to_array();
+                            eye[2] += frame.playerstate.viewoffset[2];
+                            move_ctrl.set_lightlevel(light.lightlevel(eye));
+                        }
                         move_ctrl.build_cmd(out.intent)
                     } else {
                         Usercmd::default()
@@ -1728,6 +2064,176 @@ pub(crate) async fn bot_task(
                 }
             }
         }
+    };
+    save_rivals(&rivals, &rivals_file);
+    bank_heat(heat, heatmap_obs.as_ref(), heat_key.as_ref());
+    exit
+}
+
+/// Start this map's decision timeline, if `[paths].timelines` is set. A file we can't create
+/// is logged and the bot plays on without one.
+fn open_timeline(
+    cfg: &Config,
+    bot: &str,
+    map: &str,
+    mode: NavMode,
+    brain_kind: brain::BrainKind,
+) -> Option<(brain::TimelineWriter, Instant)> {
+    let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let path = cfg.paths.timeline_file(bot, map, started)?;
+    let header = brain::timeline::Header {
+        bot: bot.to_string(),
+        brain: brain::brain_tag(brain_kind).to_string(),
+        navmode: supervisor::mode_code(mode).to_string(),
+        map: map.to_string(),
+        started,
+    };
+    match brain::TimelineWriter::create(&path, &header) {
+        Ok(w) => {
+            tracing::info!(path = %path.display(), "writing decision timeline");
+            Some((w, Instant::now()))
+        }
+        Err(e) => {
+            tracing::warn!("decision timeline {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Start this connection's replay capture, if `[paths].captures` is set. Like a timeline, a
+/// file we can't create is logged and the bot plays on without one.
+fn open_capture(
+    cfg: &Config,
+    bot: &str,
+    brain_kind: brain::BrainKind,
+    character: Option<&characters::Character>,
+) -> Option<brain::replay::CaptureWriter> {
+    let started = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let path = cfg.paths.capture_file(bot, started)?;
+    let header = brain::replay::CaptureHeader {
+        bot: bot.to_string(),
+        brain: brain::brain_tag(brain_kind).to_string(),
+        character: character.map(|c| c.name.clone()),
+        traits: character.map(|c| match c.script() {
+            Some(path) => path.display().to_string(),
+            None => c.to_yaml(),
+        }),
+        seed: capture_seed(bot),
+        combat: true,
+        human_aim: cfg.fleet.human_aim,
+        started,
+    };
+    match brain::replay::CaptureWriter::create(&path, &header) {
+        Ok(w) => {
+            tracing::info!(path = %path.display(), "writing brain capture");
+            Some(w)
+        }
+        Err(e) => {
+            tracing::warn!("brain capture {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// A captured bot's brain seed: a hash of its name, stable across runs.
+fn capture_seed(bot: &str) -> u32 {
+    bot.bytes()
+        .fold(0u32, |a, b| a.wrapping_mul(31).wrapping_add(b as u32))
+}
+
+/// Append `event` to the capture; on a write error, log it and stop capturing.
+fn record_capture(
+    capture: &mut Option<brain::replay::CaptureWriter>,
+    event: &brain::replay::Event,
+) {
+    if let Some(w) = capture.as_mut() {
+        if let Err(e) = w.record(event) {
+            tracing::warn!("brain capture stopped: {e}");
+            *capture = None;
+        }
+    }
+}
+
+/// `qbots replay`: rebuild the captured brain, feed it the capture, and report the first tick
+/// whose output differs.
+fn run_replay(cfg: &Config, path: &std::path::Path) -> ExitCode {
+    use brain::replay::{replay, Capture};
+    use clap::ValueEnum;
+
+    let cap = match Capture::read(path) {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let h = &cap.header;
+    let Ok(kind) = brain::BrainKind::from_str(&h.brain, true) else {
+        tracing::error!(brain = h.brain, "unknown brain in capture");
+        return ExitCode::FAILURE;
+    };
+    let character = match (&h.character, &h.traits, characters::Family::for_brain(kind)) {
+        (Some(name), Some(traits), Some(characters::Family::Script)) => {
+            characters::Character::from_script(name, std::path::Path::new(traits)).map(Some)
+        }
+        (Some(name), Some(traits), Some(family)) => {
+            characters::Character::parse(family, name, traits).map(Some)
+        }
+        _ => Ok(None),
+    };
+    let character = match character {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::error!("captured character: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut brain = brain::build_brain(
+        kind,
+        brain::BotSkill::default(),
+        brain::BrainConfig {
+            combat_enabled: h.combat,
+            seed: Some(h.seed),
+            human_aim: h.human_aim,
+        },
+        character.map(|c| c.traits),
+    );
+    let nav_cache = supervisor::NavCache::new();
+    let report = replay(&mut *brain, &cap.events, |m| {
+        let map_nav = nav_cache
+            .get_or_build(cfg, &m.name)
+            .ok_or_else(|| format!("no nav for {}", m.name))?;
+        if map_nav.fingerprint != m.fingerprint {
+            return Err(format!(
+                "{}: the map has changed since the capture (nav fingerprint {:x}, captured {:x})",
+                m.name, map_nav.fingerprint, m.fingerprint
+            ));
+        }
+        Ok(map_nav.brain_map(m.roam_as_position))
+    });
+    match report {
+        Ok(r) if r.identical() => {
+            println!("{}: {} ticks replayed, identical", path.display(), r.ticks);
+            ExitCode::SUCCESS
+        }
+        Ok(r) => {
+            println!(
+                "{}: {} of {} ticks diverged; first at {}",
+                path.display(),
+                r.diverged,
+                r.ticks,
+                r.first.map(|d| d.to_string()).unwrap_or_default()
+            );
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            tracing::error!("replay: {e}");
+            ExitCode::FAILURE
+        }
     }
 }
 
@@ -1842,42 +2348,16 @@ async fn run_scenario_cmd(
 }
 
 /// Enumerate all available map names under `baseq2`: loose `.bsp` files in
-/// `<baseq2>/maps/` and entries matching `maps/*.bsp` in `pak0`–`pak9`.
+/// `<baseq2>/maps/` and `maps/*.bsp` entries in any `.pak` / `.pkz` archive.
 /// Returns deduplicated, sorted map names (no extension, no `maps/` prefix).
 fn enumerate_maps(baseq2: &std::path::Path) -> Vec<String> {
-    let mut names: std::collections::HashSet<String> = std::collections::HashSet::new();
-
-    // Loose files.
-    let maps_dir = baseq2.join("maps");
-    if let Ok(rd) = std::fs::read_dir(&maps_dir) {
-        for entry in rd.flatten() {
-            let p = entry.path();
-            if p.extension().and_then(|e| e.to_str()) == Some("bsp") {
-                if let Some(stem) = p.file_stem().and_then(|s| s.to_str()) {
-                    names.insert(stem.to_ascii_lowercase());
-                }
-            }
-        }
-    }
-
-    // Pak files.
-    for n in 0..10u8 {
-        let pak_path = baseq2.join(format!("pak{n}.pak"));
-        if let Ok(pak) = world::Pak::open(&pak_path) {
-            for name in pak.names() {
-                let low = name.to_ascii_lowercase();
-                if let Some(rest) = low.strip_prefix("maps/") {
-                    if let Some(stem) = rest.strip_suffix(".bsp") {
-                        names.insert(stem.to_string());
-                    }
-                }
-            }
-        }
-    }
-
-    let mut v: Vec<String> = names.into_iter().collect();
-    v.sort();
-    v
+    let Ok(vfs) = world::Vfs::open(baseq2) else {
+        return Vec::new();
+    };
+    vfs.list("maps/", ".bsp")
+        .into_iter()
+        .filter_map(|n| Some(n.strip_prefix("maps/")?.strip_suffix(".bsp")?.to_string()))
+        .collect()
 }
 
 /// Match a map name against a simple `*`-only glob (e.g. `q2dm*` matches `q2dm1`).
@@ -2314,7 +2794,7 @@ async fn main() -> ExitCode {
 
     let cli = Cli::parse();
 
-    let cfg = match Config::load(&cli.config) {
+    let mut cfg = match Config::load(&cli.config) {
         Ok(c) => c,
         Err(e) => {
             tracing::error!("config: {e}");
@@ -2332,20 +2812,42 @@ async fn main() -> ExitCode {
             char,
             persona,
             xonchar,
+            script,
+            utility,
+            timeline,
+            capture,
         } => {
-            // Resolve the persona name (Plan 27) → a preset; unknown names are a hard error so a
+            if timeline.is_some() {
+                cfg.paths.timelines = timeline;
+            }
+            if capture.is_some() {
+                cfg.paths.captures = capture;
+            }
+            // Resolve the character flag for this brain; unknown names are a hard error so a
             // typo isn't silently ignored.
-            let persona = match persona.as_deref().map(brain::persona::Persona::preset) {
-                Some(Some(p)) => Some(p),
-                Some(None) => {
-                    tracing::error!(
-                        "unknown --persona (want one of: {})",
-                        brain::persona::Persona::PRESET_NAMES.join(", ")
-                    );
+            let chars = match load_characters(&cfg) {
+                Ok(c) => c,
+                Err(code) => return code,
+            };
+            let character = match cli_character(
+                &chars,
+                brain,
+                persona.as_deref(),
+                char.as_deref(),
+                xonchar.as_deref(),
+                script.as_deref(),
+                utility.as_deref(),
+            ) {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::error!("{e}");
                     return ExitCode::FAILURE;
                 }
-                None => None,
             };
+            if brain == brain::BrainKind::Script && character.is_none() {
+                tracing::error!("the scr brain needs a script: pass --script <name>");
+                return ExitCode::FAILURE;
+            }
             let name = name.unwrap_or_else(|| "qbots".to_string());
             let qport = qport.unwrap_or_else(default_qport);
             let addr_str = addr.unwrap_or_else(|| cfg.server_addr());
@@ -2363,7 +2865,13 @@ async fn main() -> ExitCode {
             tracing::info!("connecting '{name}' to {addr} (qport {qport})…  Ctrl-C to stop.");
 
             match supervisor::run_single(
-                &cfg, addr, &name, qport, mode, brain, char, persona, xonchar,
+                &cfg,
+                addr,
+                &name,
+                qport,
+                mode,
+                brain,
+                character.as_deref(),
             )
             .await
             {
@@ -2385,8 +2893,20 @@ async fn main() -> ExitCode {
             skin_random_male,
             skin_random_female,
             char,
+            persona,
+            xonchar,
+            script,
+            utility,
+            timeline,
+            capture,
             loose_botcap,
         } => {
+            if timeline.is_some() {
+                cfg.paths.timelines = timeline;
+            }
+            if capture.is_some() {
+                cfg.paths.captures = capture;
+            }
             // `--count` can enable a fleet even when the config roster is empty (and a
             // `--count 0` disables one the config would otherwise enable).
             let fleet_enabled = count.map_or(cfg.fleet.enabled(), |c| c > 0);
@@ -2412,10 +2932,35 @@ async fn main() -> ExitCode {
             // CLI `--brain` overrides `[fleet].brain` (which defaults to `main`).
             let brain = brain.unwrap_or_else(|| cfg.fleet.brain_kind());
             tracing::info!(brain = brain::brain_tag(brain), "fleet brain selection");
-            // CLI `--char` overrides `[fleet].char`; only meaningful for `--brain q3`.
-            let char = char.or_else(|| cfg.fleet.char_preset());
-            if let Some(q) = char {
-                tracing::info!(char = q.tag(), "fleet q3 character");
+            // The brain's own character flag (`--persona`/`--char`/`--xonchar`/`--script`/
+            // `--utility`) overrides the matching `[fleet]` key.
+            let chars = match load_characters(&cfg) {
+                Ok(c) => c,
+                Err(code) => return code,
+            };
+            let character = match cli_character(
+                &chars,
+                brain,
+                persona.as_deref(),
+                char.as_deref(),
+                xonchar.as_deref(),
+                script.as_deref(),
+                utility.as_deref(),
+            ) {
+                Ok(c) => c.or_else(|| cfg.fleet.character(brain, &chars)),
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if brain == brain::BrainKind::Script && character.is_none() {
+                tracing::error!(
+                    "the scr brain needs a script: pass --script or set [fleet].script"
+                );
+                return ExitCode::FAILURE;
+            }
+            if let Some(c) = &character {
+                tracing::info!(character = %c.name, "fleet character");
             }
             let addr_str = addr.unwrap_or_else(|| cfg.server_addr());
             let addr = match resolve_addr(&addr_str).await {
@@ -2439,7 +2984,7 @@ async fn main() -> ExitCode {
                 count,
                 qport_base,
                 skin_sel,
-                char,
+                character,
                 loose_botcap,
             )
             .await
@@ -2457,15 +3002,22 @@ async fn main() -> ExitCode {
             count,
             modes,
             brains,
+            personas,
             chars,
             xonchars,
+            scripts,
+            utilities,
             qport_base,
             loose_botcap,
         } => {
+            let characters = match load_characters(&cfg) {
+                Ok(c) => c,
+                Err(code) => return code,
+            };
             // Two ways to build the group list: a roster file (explicit, hand-picked) or the CLI
             // matrix. clap's `conflicts_with_all` guarantees they're never both set.
             let specs = if let Some(path) = roster {
-                match build_roster_specs(&path, &cfg.paths.baseq2) {
+                match build_roster_specs(&path, &cfg.paths.baseq2, &characters) {
                     Ok(s) => s,
                     Err(e) => {
                         tracing::error!("roster {path}: {e}");
@@ -2498,6 +3050,32 @@ async fn main() -> ExitCode {
                     }
                     brains
                 };
+                // Each family's list resolves against its own characters directory.
+                let picked: Result<Vec<_>, String> = [
+                    (characters::Family::Main, personas),
+                    (characters::Family::Q3, chars),
+                    (characters::Family::Xon, xonchars),
+                    (characters::Family::Script, scripts),
+                    (characters::Family::Utility, utilities),
+                ]
+                .into_iter()
+                .flat_map(|(f, names)| names.into_iter().map(move |n| (f, n)))
+                .map(|(f, n)| characters.resolve(f, &n))
+                .collect();
+                let picked = match picked {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::error!("{e}");
+                        return ExitCode::FAILURE;
+                    }
+                };
+                let has_script = picked
+                    .iter()
+                    .any(|c| c.family() == characters::Family::Script);
+                if brains.contains(&brain::BrainKind::Script) && !has_script {
+                    tracing::error!("the scr brain needs at least one --scripts <name>");
+                    return ExitCode::FAILURE;
+                }
                 // One distinct skin per mode so the fleets are tellable apart on sight.
                 let mut rng = skins::Rng::new();
                 let skins_per_mode: Vec<Option<String>> =
@@ -2505,7 +3083,7 @@ async fn main() -> ExitCode {
                         .into_iter()
                         .map(Some)
                         .collect();
-                supervisor::matrix_specs(&modes, &brains, &chars, &xonchars, count, &skins_per_mode)
+                supervisor::matrix_specs(&modes, &brains, &picked, count, &skins_per_mode)
             };
             let addr_str = addr.unwrap_or_else(|| cfg.server_addr());
             let addr = match resolve_addr(&addr_str).await {
@@ -2529,6 +3107,87 @@ async fn main() -> ExitCode {
                 }
             }
         }
+        Cmd::Evolve {
+            addr,
+            brain,
+            from,
+            mode,
+            population,
+            elite,
+            generations,
+            round_secs,
+            count,
+            sigma,
+            seed,
+            keep,
+            out,
+            qport_base,
+            loose_botcap,
+        } => {
+            // A script's behavior is its code, not a trait vector — nothing to evolve.
+            let evolvable =
+                characters::Family::for_brain(brain).filter(|&f| f != characters::Family::Script);
+            let Some(family) = evolvable else {
+                tracing::error!(
+                    "the {} brain has no character to evolve (want main, q3, xon or uti)",
+                    brain::brain_tag(brain)
+                );
+                return ExitCode::FAILURE;
+            };
+            if count == 0 || generations == 0 || round_secs == 0 {
+                tracing::error!("--count, --generations and --round-secs must be >= 1");
+                return ExitCode::FAILURE;
+            }
+            let chars = match load_characters(&cfg) {
+                Ok(c) => c,
+                Err(code) => return code,
+            };
+            let base = match from {
+                Some(name) => chars.resolve(family, &name).map(|c| c.as_ref().clone()),
+                None => characters::Character::parse(family, "default", ""),
+            };
+            let rng = seed.map_or_else(skins::Rng::new, skins::Rng::seeded);
+            let evo =
+                match base.and_then(|b| evolve::Evolution::new(b, population, elite, sigma, rng)) {
+                    Ok(e) => e,
+                    Err(e) => {
+                        tracing::error!("{e}");
+                        return ExitCode::FAILURE;
+                    }
+                };
+            let out = out.unwrap_or_else(|| {
+                let ts = time::OffsetDateTime::now_utc().unix_timestamp().max(0);
+                std::path::Path::new("logs/evolve").join(ts.to_string())
+            });
+            let addr_str = addr.unwrap_or_else(|| cfg.server_addr());
+            let addr = match resolve_addr(&addr_str).await {
+                Ok(a) => a,
+                Err(e) => {
+                    tracing::error!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(code) = preflight_map(&cfg, addr, None, world::GRID_SPACING, false).await {
+                return code;
+            }
+            let setup = evolve::Setup {
+                mode,
+                count,
+                generations,
+                round: Duration::from_secs(round_secs),
+                keep: keep.unwrap_or(elite),
+                out,
+                qport_base,
+                loose_botcap,
+            };
+            match evolve::run(Arc::new(cfg), addr, evo, setup).await {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    tracing::error!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
         Cmd::Config => {
             tracing::info!("server      : {}", cfg.server_addr());
             tracing::info!("server_cfg  : {}", cfg.paths.server_cfg.display());
@@ -2691,6 +3350,7 @@ async fn main() -> ExitCode {
                 ExitCode::FAILURE
             }
         },
+        Cmd::Replay { capture } => run_replay(&cfg, &capture),
         Cmd::Nav { map } => match world::Bsp::load(&cfg.paths.baseq2, &map) {
             Ok(bsp) => {
                 let cm = world::CollisionModel::from_bsp(&bsp);
@@ -2951,6 +3611,8 @@ mod tests {
             ["--navmodes", "as"],
             ["--chars", "cam"],
             ["--xonchars", "shp"],
+            ["--scripts", "hunter"],
+            ["--utilities", "brawler"],
             ["--count", "4"],
         ] {
             let res = Cli::try_parse_from([
@@ -2974,6 +3636,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn evolve_defaults_to_a_q3_population_of_eight() {
+        let cli = Cli::try_parse_from(["qbots", "evolve", "--from", "grunt"]).unwrap();
+        match cli.cmd {
+            Cmd::Evolve {
+                brain,
+                from,
+                population,
+                elite,
+                keep,
+                ..
+            } => {
+                assert_eq!(brain, brain::BrainKind::Quake3);
+                assert_eq!(from.as_deref(), Some("grunt"));
+                assert_eq!((population, elite, keep), (8, 2, None));
+            }
+            _ => panic!("expected evolve subcommand"),
+        }
+    }
+
     #[test]
     fn competition_matrix_switches_parse_without_roster() {
         // The matrix path is unaffected — switches still combine freely when --roster is absent.
@@ -5,28 +5,35 @@
 //! *emits* a ranked roster (see [`emit_ranked_yaml`]) you trim down for the next round.
 //!
 //! The YAML mirrors the scoreboard's own vocabulary — the same short codes the board prints are
-//! valid tokens here (they're the clap `ValueEnum` names), so an emitted roster round-trips back
-//! through [`Roster::into_specs`] unchanged:
+//! valid tokens here (the clap `ValueEnum` names, and each character file's `code`), so an emitted
+//! roster round-trips back through [`Roster::into_specs`] unchanged:
 //!
 //! ```yaml
 //! count: 2            # optional file-wide default per-group count (fallback 8)
 //! groups:
 //!   - brain: mai      # BrainKind token — short code (mai) or long alias (main)
 //!     navmode: sg
+//!     persona: guard  # characters/main/ name or code — only valid with a main brain
 //!   - brain: q3
 //!     navmode: sg
-//!     char: cam       # CharPreset — only valid with a q3 brain
+//!     char: cam       # characters/q3/ — only valid with a q3 brain
 //!   - brain: xon
 //!     navmode: nm
-//!     xonchar: shp    # XonCharPreset — only valid with a xon brain
+//!     xonchar: shp    # characters/xon/ — only valid with a xon brain
 //!     count: 4        # per-group override
 //!     tag: shpkings   # optional custom scoreboard tag (default: auto <brain>_<mode>[_<char>])
 //!     skin: female/athena   # optional
+//!     team: red     # optional CTF / skin-teams team (red|blue); wears the team skin
+//!   - brain: scr
+//!     navmode: as
+//!     script: hunter  # characters/script/ — required by (and only valid with) a scr brain
+//!   - brain: uti
+//!     navmode: nm
+//!     utility: survivor  # characters/utility/ — only valid with a uti brain
 //! ```
 
-use crate::supervisor::{
-    brain_code, char_code, group_tag, mode_code, xon_char_code, GroupChar, GroupSpec, ModeScore,
-};
+use crate::characters::{Characters, Family};
+use crate::supervisor::{brain_code, group_tag, mode_code, GroupSpec, ModeScore};
 
 /// The default per-group bot count when neither the group nor the file sets one — matches the
 /// `--count` CLI default so a roster with bare groups behaves like `competition --count 8`.
@@ -49,22 +56,31 @@ pub struct Roster {
 }
 
 /// One group entry in a roster file. All enum fields are strings parsed at [`Roster::into_specs`]
-/// time (via `ValueEnum::from_str`), so a typo yields a pointed error rather than a serde failure.
+/// time (via `ValueEnum::from_str`, or against the loaded characters), so a typo yields a pointed
+/// error rather than a serde failure.
 #[derive(Debug, serde::Deserialize)]
 #[serde(deny_unknown_fields)]
 struct RosterGroup {
     brain: String,
     navmode: String,
     #[serde(default)]
+    persona: Option<String>,
+    #[serde(default)]
     char: Option<String>,
     #[serde(default)]
     xonchar: Option<String>,
     #[serde(default)]
+    script: Option<String>,
+    #[serde(default)]
+    utility: Option<String>,
+    #[serde(default)]
     count: Option<usize>,
     #[serde(default)]
     tag: Option<String>,
     #[serde(default)]
     skin: Option<String>,
+    #[serde(default)]
+    team: Option<String>,
 }
 
 impl Roster {
@@ -74,9 +90,10 @@ impl Roster {
         serde_yaml::from_str(&text).map_err(|e| format!("parse {path}: {e}"))
     }
 
-    /// Validate + lower the roster into `GroupSpec`s. Returns a human-readable error (with the
-    /// offending group's 1-based index) on any rule violation. See the module docs for the rules.
-    pub fn into_specs(self) -> Result<Vec<GroupSpec>, String> {
+    /// Validate + lower the roster into `GroupSpec`s, resolving character names against
+    /// `characters`. Returns a human-readable error (with the offending group's 1-based index) on
+    /// any rule violation. See the module docs for the rules.
+    pub fn into_specs(self, characters: &Characters) -> Result<Vec<GroupSpec>, String> {
         if self.groups.is_empty() {
             return Err("roster has no groups".to_string());
         }
@@ -85,7 +102,7 @@ impl Roster {
         for (idx, g) in self.groups.into_iter().enumerate() {
             let n = idx + 1; // 1-based for messages
             let spec = g
-                .into_spec(self.count)
+                .into_spec(self.count, characters)
                 .map_err(|e| format!("group {n}: {e}"))?;
             if seen_tags.contains(&spec.tag) {
                 return Err(format!(
@@ -103,7 +120,11 @@ impl Roster {
 
 impl RosterGroup {
     /// Parse + validate one group into a `GroupSpec`. `file_count` is the roster-wide default.
-    fn into_spec(self, file_count: Option<usize>) -> Result<GroupSpec, String> {
+    fn into_spec(
+        self,
+        file_count: Option<usize>,
+        characters: &Characters,
+    ) -> Result<GroupSpec, String> {
         use clap::ValueEnum;
 
         let brain = brain::BrainKind::from_str(&self.brain, true)
@@ -114,30 +135,36 @@ impl RosterGroup {
         let mode = crate::NavMode::from_str(&self.navmode, true)
             .map_err(|_| format!("unknown navmode '{}'", self.navmode))?;
 
-        // The char axis: `char` only for q3, `xonchar` only for xon, never both.
-        if self.char.is_some() && self.xonchar.is_some() {
-            return Err("set only one of char/xonchar".to_string());
-        }
-        let gc = match (&self.char, &self.xonchar) {
-            (Some(c), None) => {
-                if brain != brain::BrainKind::Quake3 {
-                    return Err(format!("char '{c}' is only valid for the q3 brain"));
-                }
-                let cp = brain::CharPreset::from_str(c, true)
-                    .map_err(|_| format!("unknown char '{c}'"))?;
-                GroupChar::Q3(cp)
-            }
-            (None, Some(x)) => {
-                if brain != brain::BrainKind::Xon {
-                    return Err(format!("xonchar '{x}' is only valid for the xon brain"));
+        // The character axis: at most one of persona/char/xonchar/script/utility, and only the one
+        // whose family matches the brain (persona → main, char → q3, xonchar → xon, script → scr,
+        // utility → uti).
+        let named: Vec<(Family, &String)> = [
+            (Family::Main, &self.persona),
+            (Family::Q3, &self.char),
+            (Family::Xon, &self.xonchar),
+            (Family::Script, &self.script),
+            (Family::Utility, &self.utility),
+        ]
+        .into_iter()
+        .filter_map(|(f, n)| n.as_ref().map(|n| (f, n)))
+        .collect();
+        let character = match named.as_slice() {
+            [] => None,
+            [(family, name)] => {
+                if family.brain() != brain {
+                    return Err(format!(
+                        "{} '{name}' is only valid for the {} brain",
+                        family.key(),
+                        brain::brain_tag(family.brain())
+                    ));
                 }
-                let xp = brain::XonCharPreset::from_str(x, true)
-                    .map_err(|_| format!("unknown xonchar '{x}'"))?;
-                GroupChar::Xon(xp)
+                Some(characters.resolve(*family, name)?)
             }
-            (None, None) => GroupChar::None,
-            (Some(_), Some(_)) => unreachable!("both-set case handled above"),
+            _ => return Err("set only one of persona/char/xonchar/script/utility".to_string()),
         };
+        if brain == brain::BrainKind::Script && character.is_none() {
+            return Err("a scr brain needs a `script:`".to_string());
+        }
 
         let count = self.count.or(file_count).unwrap_or(DEFAULT_COUNT);
         if count == 0 {
@@ -157,7 +184,7 @@ impl RosterGroup {
                 }
                 t
             }
-            None => group_tag(mode, brain, gc),
+            None => group_tag(mode, brain, character.as_deref()),
         };
         // Widest bot name is `<tag>_<count>` (largest index). `1 + digits(count)` is the suffix.
         let suffix = 1 + count.to_string().len();
@@ -169,14 +196,21 @@ impl RosterGroup {
         }
 
         // Skin: explicit → the character's own skin → None (dispatch fills None via distinct_skins).
-        let skin = self.skin.or_else(|| gc.skin());
+        let skin = self
+            .skin
+            .or_else(|| character.as_ref().and_then(|c| c.skin.clone()));
+        let team = match self.team {
+            Some(t) => Some(brain::Team::parse(&t).ok_or_else(|| format!("unknown team '{t}'"))?),
+            None => None,
+        };
 
         Ok(GroupSpec {
             mode,
             brain,
-            gc,
+            character,
             count,
             skin,
+            team,
             tag,
         })
     }
@@ -206,15 +240,16 @@ pub fn emit_ranked_yaml(ranked: &[ModeScore], specs: &[GroupSpec]) -> String {
         ));
         out.push_str(&format!("  - brain: {}\n", brain_code(spec.brain)));
         out.push_str(&format!("    navmode: {}\n", mode_code(spec.mode)));
-        match spec.gc {
-            GroupChar::Q3(c) => out.push_str(&format!("    char: {}\n", char_code(c))),
-            GroupChar::Xon(x) => out.push_str(&format!("    xonchar: {}\n", xon_char_code(x))),
-            GroupChar::None => {}
+        if let Some(c) = &spec.character {
+            out.push_str(&format!("    {}: {}\n", c.family().key(), c.code));
         }
         out.push_str(&format!("    count: {}\n", spec.count));
+        if let Some(t) = spec.team {
+            out.push_str(&format!("    team: {}\n", t.name()));
+        }
         // Emit a custom tag only when it isn't the auto `<brain>_<mode>[_<char>]` (which the
         // loader would reconstruct anyway) — keeps the dump minimal and round-trip-clean.
-        if spec.tag != group_tag(spec.mode, spec.brain, spec.gc) {
+        if spec.tag != group_tag(spec.mode, spec.brain, spec.character.as_deref()) {
             out.push_str(&format!("    tag: {}\n", spec.tag));
         }
     }
@@ -224,10 +259,11 @@ pub fn emit_ranked_yaml(ranked: &[ModeScore], specs: &[GroupSpec]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use brain::BrainTraits;
 
     fn specs_from(yaml: &str) -> Result<Vec<GroupSpec>, String> {
         let r: Roster = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
-        r.into_specs()
+        r.into_specs(&crate::characters::shipped())
     }
 
     #[test]
@@ -237,6 +273,9 @@ mod tests {
              groups:\n\
              \x20 - brain: mai\n\
              \x20   navmode: sg\n\
+             \x20 - brain: mai\n\
+             \x20   navmode: sg\n\
+             \x20   persona: sniper\n\
              \x20 - brain: q3\n\
              \x20   navmode: sg\n\
              \x20   char: cam\n\
@@ -245,19 +284,30 @@ mod tests {
              \x20   xonchar: shp\n\
              \x20   count: 4\n\
              \x20   tag: shpkings\n\
-             \x20   skin: female/athena\n",
+             \x20   skin: female/athena\n\
+             \x20   team: blue\n",
         )
         .unwrap();
-        assert_eq!(specs.len(), 3);
+        assert_eq!(specs.len(), 4);
         // File-wide count applies to groups without their own.
         assert_eq!(specs[0].tag, "mai_sg");
         assert_eq!(specs[0].count, 2);
-        assert_eq!(specs[1].tag, "q3_sg_cam");
-        assert_eq!(specs[1].count, 2);
+        assert_eq!(specs[1].tag, "mai_sg_sni");
+        assert_eq!(specs[2].tag, "q3_sg_cam");
+        assert_eq!(specs[2].count, 2);
         // Per-group count + custom tag + explicit skin all take effect.
-        assert_eq!(specs[2].tag, "shpkings");
-        assert_eq!(specs[2].count, 4);
-        assert_eq!(specs[2].skin.as_deref(), Some("female/athena"));
+        assert_eq!(specs[3].tag, "shpkings");
+        assert_eq!(specs[3].count, 4);
+        assert_eq!(specs[3].skin.as_deref(), Some("female/athena"));
+        assert_eq!(specs[3].team, Some(brain::Team::Blue));
+        assert_eq!(specs[0].team, None);
+    }
+
+    #[test]
+    fn rejects_unknown_team() {
+        let err =
+            specs_from("groups:\n  - brain: mai\n    navmode: sg\n    team: green\n").unwrap_err();
+        assert!(err.contains("unknown team 'green'"), "{err}");
     }
 
     #[test]
@@ -281,14 +331,11 @@ mod tests {
     }
 
     #[test]
-    fn char_skin_defaults_to_the_preset_skin() {
+    fn char_skin_defaults_to_the_character_skin() {
         let specs =
             specs_from("groups:\n  - brain: q3\n    navmode: as\n    char: gru\n    count: 1\n")
                 .unwrap();
-        assert_eq!(
-            specs[0].skin.as_deref(),
-            Some(brain::CharPreset::Grunt.skin())
-        );
+        assert_eq!(specs[0].skin.as_deref(), Some("male/grunt"));
     }
 
     #[test]
@@ -305,6 +352,11 @@ mod tests {
         assert!(specs_from("groups:\n  - brain: mai\n    navmode: warp\n")
             .unwrap_err()
             .contains("unknown navmode"));
+        assert!(
+            specs_from("groups:\n  - brain: q3\n    navmode: as\n    char: grumpy\n")
+                .unwrap_err()
+                .contains("unknown char 'grumpy'")
+        );
     }
 
     #[test]
@@ -327,13 +379,43 @@ mod tests {
         assert!(err.contains("only valid for the xon brain"), "got: {err}");
     }
 
+    #[test]
+    fn a_script_brain_needs_its_script() {
+        let err = specs_from("groups:\n  - brain: scr\n    navmode: as\n").unwrap_err();
+        assert!(err.contains("needs a `script:`"), "got: {err}");
+        let specs = specs_from("groups:\n  - brain: script\n    navmode: as\n    script: hunter\n")
+            .unwrap();
+        assert_eq!(specs[0].tag, "scr_as_hun");
+        assert!(specs[0].character.as_ref().unwrap().script().is_some());
+    }
+
+    #[test]
+    fn a_utility_group_takes_its_profile() {
+        let specs = specs_from("groups:\n  - brain: uti\n    navmode: nm\n    utility: survivor\n")
+            .unwrap();
+        assert_eq!(specs[0].tag, "uti_nm_sur");
+        assert!(matches!(
+            specs[0].character.as_ref().unwrap().traits,
+            BrainTraits::Utility(_)
+        ));
+        let err =
+            specs_from("groups:\n  - brain: q3\n    navmode: as\n    utility: sur\n").unwrap_err();
+        assert!(
+            err.contains("only valid for the utility brain"),
+            "got: {err}"
+        );
+    }
+
     #[test]
     fn rejects_both_char_and_xonchar() {
         let err = specs_from(
             "groups:\n  - brain: q3\n    navmode: as\n    char: cam\n    xonchar: shp\n",
         )
         .unwrap_err();
-        assert!(err.contains("only one of char/xonchar"), "got: {err}");
+        assert!(
+            err.contains("only one of persona/char/xonchar"),
+            "got: {err}"
+        );
     }
 
     #[test]
@@ -396,29 +478,33 @@ mod tests {
     #[test]
     fn emitted_roster_round_trips_and_is_ranked() {
         // Spec order is NOT rank order — the emit must follow the ranking, not the spec list.
+        let chars = crate::characters::shipped();
         let specs = vec![
             GroupSpec {
                 mode: crate::NavMode::HybridSegment,
                 brain: brain::BrainKind::Main,
-                gc: GroupChar::None,
+                character: None,
                 count: 2,
                 skin: Some("male/random".into()),
+                team: None,
                 tag: "mai_sg".into(),
             },
             GroupSpec {
                 mode: crate::NavMode::HybridSegment,
                 brain: brain::BrainKind::Quake3,
-                gc: GroupChar::Q3(brain::CharPreset::Camper),
+                character: Some(chars.resolve(Family::Q3, "camper").unwrap()),
                 count: 2,
                 skin: None,
+                team: None,
                 tag: "q3_sg_cam".into(),
             },
             GroupSpec {
                 mode: crate::NavMode::Navmesh,
                 brain: brain::BrainKind::Xon,
-                gc: GroupChar::Xon(brain::XonCharPreset::Sharp),
+                character: Some(chars.resolve(Family::Xon, "sharp").unwrap()),
                 count: 4,
                 skin: None,
+                team: Some(brain::Team::Blue),
                 tag: "shpkings".into(), // custom tag ≠ auto xon_nm_shp
             },
         ];
@@ -444,7 +530,16 @@ mod tests {
         // Re-parse → same competitive identity, now in RANK order.
         let reloaded = specs_from(&yaml).unwrap();
         assert_eq!(reloaded.len(), 3);
-        let ident = |s: &GroupSpec| (s.mode, s.brain, s.gc, s.count, s.tag.clone());
+        let ident = |s: &GroupSpec| {
+            (
+                s.mode,
+                s.brain,
+                s.character.clone(),
+                s.count,
+                s.team,
+                s.tag.clone(),
+            )
+        };
         assert_eq!(ident(&reloaded[0]), ident(&specs[2])); // shpkings
         assert_eq!(ident(&reloaded[1]), ident(&specs[0])); // mai_sg
         assert_eq!(ident(&reloaded[2]), ident(&specs[1])); // q3_sg_cam
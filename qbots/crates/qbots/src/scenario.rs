@@ -4,7 +4,8 @@
 //! bot like `connect-one`, but pin its nav goal to a scenario target, **disable
 //! combat**, and feed every server frame to a [`MovementRecorder`]. The run stops on
 //! goal-reach (settled), a `max_secs` cap, or a disconnect, then dumps a structured
-//! log + prints the SUMMARY line that Plans 11–14 must beat.
+//! log + prints the SUMMARY line that Plans 11–14 must beat. The brain's decision
+//! timeline (`brain::timeline`) is streamed beside the log as `<ts>.<bot>.timeline.jsonl`.
 //!
 //! This deliberately reuses the brain's nav/steering primitives (it does **not**
 //! duplicate combat/aim logic) — only the connect + tick scaffolding is mirrored
@@ -22,6 +23,7 @@ use tokio::net::UdpSocket;
 use brain::nav::NavGoal;
 use brain::perception::Worldview;
 use brain::recorder::{CmWallProbe, MovementRecorder, Sample, WallProbe};
+use brain::timeline::{Header, TickRecord, TimelineWriter};
 use brain::{
     build_brain, BotSkill, Brain, BrainConfig, BrainContext, BrainKind, BrainMap,
     MovementController, Navigator,
@@ -208,8 +210,8 @@ pub async fn run_scenario(
                     // Once, log the edge-kind composition of a winning path — tells us which
                     // special traversals (Ride/Jump/Swim) the brain must execute to arrive.
                     if let (false, Some(p)) = (logged_kinds, path) {
-                        let (mut walk, mut jump, mut swim, mut ride, mut teleport) =
-                            (0, 0, 0, 0, 0);
+                        let (mut walk, mut jump, mut swim, mut ride, mut teleport, mut rocket) =
+                            (0, 0, 0, 0, 0, 0);
                         for w in p.windows(2) {
                             match graph.edge_kind(w[0], w[1]) {
                                 world::EdgeKind::Walk => walk += 1,
@@ -217,6 +219,7 @@ pub async fn run_scenario(
                                 world::EdgeKind::Swim => swim += 1,
                                 world::EdgeKind::Ride => ride += 1,
                                 world::EdgeKind::Teleport => teleport += 1,
+                                world::EdgeKind::RocketJump { .. } => rocket += 1,
                             }
                         }
                         tracing::info!(
@@ -227,6 +230,7 @@ pub async fn run_scenario(
                             swim,
                             ride,
                             teleport,
+                            rocket,
                             "goal path edge-kind composition"
                         );
                         // Dump each ride edge's endpoints (board→dismount) so we can see the
@@ -353,10 +357,11 @@ pub async fn run_scenario(
         BotSkill::default(),
         BrainConfig {
             combat_enabled: false,
+            ..Default::default()
         },
-        None, // scenarios don't select a Q3 personality (no combat)
-        None, // ...nor a main persona (combat off)
-        None, // ...nor an xon character (Plan 60; neutral skill is fine goal-driven)
+        // No character: combat is off, so each brain's neutral traits are fine goal-driven (a
+        // `scr` scenario brain would idle with `script-error`).
+        None,
     );
 
     // Drive through the `Navigator` trait so the tick loop is backend-agnostic. `+ Send`
@@ -377,6 +382,8 @@ pub async fn run_scenario(
         // Static item table (Plan 30) — populated for `--brain main` A/B runs; combat is off in
         // scenarios so health-seek never fires, but keeping it consistent avoids a divergent path.
         items: brain::items::build_map_items(&bsp, &graph),
+        node_light: Vec::new(),
+        cm: Some(Arc::clone(&cm)),
     });
     let mut last_serverframe: Option<i32> = None;
     // Monotonic tick counter for `BrainContext` (drives jitter/roam in the `--brain main` A/B;
@@ -401,6 +408,8 @@ pub async fn run_scenario(
     let probe: Arc<dyn WallProbe> = Arc::new(CmWallProbe::new(Arc::clone(&cm)));
 
     let mut recorder: Option<MovementRecorder> = None;
+    // The decision timeline, written beside the movement log and aligned with it by frame.
+    let mut timeline: Option<TimelineWriter> = None;
     let mut buf = vec![0u8; 4096];
     // Plan 57 opt-out: this movement harness deliberately keeps the free-running 100 ms
     // send (no ack-on-frame re-phasing). The Plan 10–14 baselines in
@@ -468,6 +477,14 @@ pub async fn run_scenario(
                                     &map,
                                     &started_iso,
                                 ));
+                                timeline = open_timeline(
+                                    &scenario_name,
+                                    name,
+                                    &map,
+                                    unix_ts,
+                                    mode,
+                                    brain_kind,
+                                );
                             }
 
                             // dt from observed serverframe delta (clamped) — the brain
@@ -495,6 +512,20 @@ pub async fn run_scenario(
                             // `intent_forward` is the recorder's hindered-flag input (the
                             // nav-step forward; 0 during recovery/backoff) — preserved by the brain.
                             let intent_forward = out.intent_forward;
+                            if let Some(w) = timeline.as_mut() {
+                                let rec = TickRecord::capture(
+                                    elapsed,
+                                    frame.serverframe,
+                                    &view,
+                                    &*brain,
+                                    Some(&*nav_driver as &dyn Navigator),
+                                    &out,
+                                );
+                                if let Err(e) = w.record(&rec) {
+                                    tracing::warn!("decision timeline stopped: {e}");
+                                    timeline = None;
+                                }
+                            }
 
                             move_ctrl.set_delta_angles(frame.playerstate.pmove.delta_angles);
                             move_ctrl.set_msec(dt);
@@ -733,6 +764,31 @@ fn farthest_reachable_spawn(
     from
 }
 
+/// Start the decision timeline beside the run's movement log:
+/// `./logs/<scenario>/<unix_ts>.<bot>.timeline.jsonl`.
+fn open_timeline(
+    scenario_name: &str,
+    name: &str,
+    map: &str,
+    unix_ts: u64,
+    mode: crate::NavMode,
+    brain_kind: BrainKind,
+) -> Option<TimelineWriter> {
+    let path = std::path::Path::new("logs")
+        .join(scenario_name)
+        .join(format!("{unix_ts}.{name}.timeline.jsonl"));
+    let header = Header {
+        bot: name.to_string(),
+        brain: brain::brain_tag(brain_kind).to_string(),
+        navmode: crate::supervisor::mode_code(mode).to_string(),
+        map: map.to_string(),
+        started: unix_ts,
+    };
+    TimelineWriter::create(&path, &header)
+        .inspect_err(|e| tracing::warn!("decision timeline {}: {e}", path.display()))
+        .ok()
+}
+
 /// Dump the recorder log + emit the SUMMARY line; map outcome → exit code.
 fn finalize(
     recorder: Option<&MovementRecorder>,
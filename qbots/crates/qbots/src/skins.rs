@@ -187,8 +187,13 @@ impl Rng {
         Self(nanos ^ (std::process::id() as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
     }
 
+    /// A fixed-seed generator, for runs that must be reproducible (`evolve --seed`).
+    pub fn seeded(seed: u64) -> Self {
+        Self(seed)
+    }
+
     /// Next 64-bit value (SplitMix64).
-    fn next_u64(&mut self) -> u64 {
+    pub(crate) fn next_u64(&mut self) -> u64 {
         self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
         let mut z = self.0;
         z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
@@ -196,6 +201,23 @@ impl Rng {
         z ^ (z >> 31)
     }
 
+    /// Uniform in `[0, 1)` (the top 24 bits, so every value is an exact `f32`).
+    pub(crate) fn unit(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// A standard normal draw (Box–Muller).
+    pub(crate) fn normal(&mut self) -> f32 {
+        let u = 1.0 - self.unit(); // (0, 1]: keeps ln() finite
+        let v = self.unit();
+        (-2.0 * u.ln()).sqrt() * (std::f32::consts::TAU * v).cos()
+    }
+
+    /// A uniform index in `0..n` (`n > 0`).
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
     /// A uniformly chosen element of `items` (`None` only if empty).
     fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
         if items.is_empty() {
@@ -14,6 +14,7 @@ use std::time::Duration;
 use tokio::task::JoinHandle;
 use tokio::time;
 
+use crate::characters::{Character, Family};
 use crate::config::Config;
 
 pub use crate::stats::FleetStats;
@@ -35,6 +36,31 @@ pub struct MapNav {
     /// Static item table (Plan 30) — every `item_*`/`weapon_*`/`ammo_*` spawn from the BSP,
     /// classified + nearest-node-resolved once per map and shared to every bot via `BrainMap`.
     pub items: Vec<brain::brains::core::MapItem>,
+    /// Door/lift/train gating template: which edges each mover gates, all movers unseen.
+    /// Every bot clones it at map load and feeds its own sightings in each frame.
+    pub nav_state: world::NavState,
+    /// Baked lightmaps, sampled at the eye each tick for `usercmd.lightlevel`.
+    pub light: Arc<world::LightMap>,
+    /// [`world::LightMap::node_levels`] over the graph — where the shadows are, for personas
+    /// that ambush from the dark.
+    pub node_light: Vec<u8>,
+    /// [`world::Fingerprint::digest`] of the graph — the key saved heat is valid against.
+    pub fingerprint: u64,
+}
+
+impl MapNav {
+    /// What a brain learns of this map at `set_map`. The navmesh backend can't path to a bare
+    /// A* node index, so it takes roam goals as world positions (`roam_as_position`).
+    pub fn brain_map(&self, roam_as_position: bool) -> brain::BrainMap {
+        brain::BrainMap {
+            roam_nodes: self.roam_nodes.clone(),
+            nav_graph: Arc::clone(&self.graph),
+            roam_as_position,
+            items: self.items.clone(),
+            node_light: self.node_light.clone(),
+            cm: Some(Arc::clone(&self.cm)),
+        }
+    }
 }
 
 /// Process-wide cache of nav graphs keyed by map name. The first bot to discover
@@ -109,21 +135,103 @@ fn build_map_nav(cfg: &Config, map: &str) -> Option<MapNav> {
         ms = t0.elapsed().as_millis() as u64,
         "nav graph ready"
     );
+    // Warm the shared cluster hierarchy now rather than in the first bot's first plan.
+    if let Some(hier) = built.graph.hier() {
+        tracing::info!(map, clusters = hier.cluster_count(), "nav hierarchy ready");
+    }
     let model = &built.bsp.models[0];
     let bounds = (model.mins, model.maxs);
     // Static item table (Plan 30) — built here where the full BSP entity lump is still in scope,
     // before `built.graph` is moved into the shared `Arc`.
     let items = brain::items::build_map_items(&built.bsp, &built.graph);
     tracing::info!(map, item_spawns = items.len(), "map item table built");
+    let nav_state = world::NavState::new(&built.graph, &built.bsp);
+    tracing::info!(map, movers = nav_state.mover_count(), "mover gating built");
+    let light = world::LightMap::from_bsp(&built.bsp);
+    let node_light = light.node_levels(&built.graph);
+    let fingerprint = world::Fingerprint::from_bsp(&built.bsp, world::GRID_SPACING).digest();
     Some(MapNav {
         graph: Arc::new(built.graph),
         cm: built.cm,
         roam_nodes: built.largest,
         bounds,
         items,
+        nav_state,
+        light: Arc::new(light),
+        node_light,
+        fingerprint,
     })
 }
 
+/// The heat every bot banked this run, per map (see `brain::heatmap`). A bot deposits its
+/// heatmap's session record when it leaves a level or its connection ends, however it
+/// ends; [`Self::save`] folds the run into each map's file when the run ends. Written once
+/// per level per bot and read only at exit, so — like [`FleetStats`] — it never lets one
+/// bot perceive what another sees during play.
+#[derive(Clone, Default)]
+pub struct HeatBank {
+    maps: Arc<Mutex<HashMap<String, (u64, brain::HeatRecord)>>>,
+}
+
+impl HeatBank {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Average one bot's session on `map` into the run's record. A record for another graph
+    /// (the cache was rebuilt mid-run) replaces the old one.
+    pub fn deposit(&self, map: &str, fingerprint: u64, rec: brain::HeatRecord) {
+        if rec.is_empty() {
+            return;
+        }
+        let mut maps = self.maps.lock().unwrap();
+        if let Some((fp, run)) = maps.get_mut(map) {
+            if *fp == fingerprint && run.merge(&rec) {
+                return;
+            }
+        }
+        maps.insert(map.to_string(), (fingerprint, rec));
+    }
+
+    /// Merge the run into every map's saved heat. Best-effort: errors are logged. A saved
+    /// file that can't be read is moved aside to `<file>.bad` and the run starts it afresh,
+    /// so one cut-off write never stops a map's heat from being kept.
+    pub fn save(&self, cfg: &Config) {
+        for (map, (fp, run)) in self.maps.lock().unwrap().iter() {
+            let path = cfg.paths.heatmap_file(map);
+            let rec = match brain::HeatRecord::load(&path, *fp) {
+                Ok(Some(mut saved)) => {
+                    if saved.merge(run) {
+                        saved
+                    } else {
+                        run.clone()
+                    }
+                }
+                Ok(None) => run.clone(),
+                Err(e) => {
+                    let mut bad = path.as_os_str().to_owned();
+                    bad.push(".bad");
+                    if let Err(mv) = std::fs::rename(&path, &bad) {
+                        tracing::warn!(map, "not saving heat over an unreadable file: {e} ({mv})");
+                        continue;
+                    }
+                    tracing::warn!(map, "moved an unreadable heat file aside: {e}");
+                    run.clone()
+                }
+            };
+            match rec.save(&path, *fp) {
+                Ok(()) => tracing::info!(
+                    map,
+                    sessions = rec.sessions,
+                    path = %path.display(),
+                    "saved map heat"
+                ),
+                Err(e) => tracing::warn!(map, "could not save map heat: {e}"),
+            }
+        }
+    }
+}
+
 /// Process-global navmesh cache so the N bots of a `--navmode navmesh` run share one built
 /// mesh instead of each rebuilding it (mirrors [`NavCache`]). Keyed by map name; the first
 /// bot to ask builds it under the lock, the rest clone the `Arc`. Honors `QBOTS_ERODE`.
@@ -256,6 +364,7 @@ struct FleetShared {
     nav: NavCache,
     shutdown: Shutdown,
     stats: FleetStats,
+    heat: HeatBank,
     /// First fatal join failure (server full / connect timeout), if any. Set once by the
     /// first bot that fails to join; the run returns an error unless `loose_botcap`.
     join_failure: Arc<Mutex<Option<String>>>,
@@ -286,7 +395,7 @@ pub async fn run_fleet(
     count_override: Option<usize>,
     qport_base_override: Option<u16>,
     skin: crate::skins::SkinSelection,
-    char: Option<brain::CharPreset>,
+    character: Option<Arc<Character>>,
     loose_botcap: bool,
 ) -> std::io::Result<()> {
     // Apply the maxclients guard: never spawn more than `max_bots` (leave slots
@@ -326,6 +435,7 @@ pub async fn run_fleet(
         nav: nav_cache,
         shutdown: shutdown.clone(),
         stats: stats.clone(),
+        heat: HeatBank::new(),
         join_failure: Arc::new(Mutex::new(None)),
         loose_botcap,
         beacon,
@@ -347,18 +457,19 @@ pub async fn run_fleet(
             None => cfg.fleet.bot_name(i),
         };
         let qport = qport_base.wrapping_add(i as u16);
-        // A selected Q3 character pins its recognizable skin; else draw once per bot (kept
+        // A selected character pins its recognizable skin; else draw once per bot (kept
         // across reconnects); `None` keeps the userinfo default.
-        let xonchar = cfg.fleet.xonchar_preset();
-        let bot_skin = char
-            .map(|q| q.skin().to_string())
-            .or_else(|| xonchar.map(|x| x.skin().to_string()))
+        let team = cfg.fleet.team_for(i);
+        let bot_skin = character
+            .as_ref()
+            .and_then(|c| c.skin.clone())
             .or_else(|| skin.per_bot(&mut skin_rng));
         let cfg = Arc::clone(&cfg);
         let shared = shared.clone();
+        let character = character.clone();
         tasks.push(tokio::spawn(async move {
             bot_supervisor_loop(
-                addr, name, qport, bot_skin, cfg, shared, reconnect, mode, brain, char, xonchar,
+                addr, name, qport, bot_skin, team, cfg, shared, reconnect, mode, brain, character,
             )
             .await;
         }));
@@ -397,6 +508,7 @@ pub async fn run_fleet(
     // All bots have now disconnected (each sends `disconnect` on shutdown before
     // exiting) — emit the final tally.
     log_final_stats(&stats);
+    shared.heat.save(&cfg);
     tracing::info!("fleet exited");
     fleet_join_result(&shared)
 }
@@ -511,59 +623,8 @@ pub(crate) fn brain_code(brain: brain::BrainKind) -> &'static str {
         brain::BrainKind::Quake3 => "q3",
         brain::BrainKind::Zb2 => "zb2",
         brain::BrainKind::Xon => "xon",
-    }
-}
-
-/// The per-group personality axis: q3 groups may carry a Q3 character, xon groups an
-/// Xonotic character; every other brain has none (Plan 62 T1).
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub(crate) enum GroupChar {
-    None,
-    Q3(brain::CharPreset),
-    Xon(brain::XonCharPreset),
-}
-
-impl GroupChar {
-    pub(crate) fn q3(self) -> Option<brain::CharPreset> {
-        match self {
-            GroupChar::Q3(c) => Some(c),
-            _ => None,
-        }
-    }
-    pub(crate) fn xon(self) -> Option<brain::XonCharPreset> {
-        match self {
-            GroupChar::Xon(x) => Some(x),
-            _ => None,
-        }
-    }
-    pub(crate) fn skin(self) -> Option<String> {
-        match self {
-            GroupChar::Q3(c) => Some(c.skin().to_string()),
-            GroupChar::Xon(x) => Some(x.skin().to_string()),
-            GroupChar::None => None,
-        }
-    }
-}
-
-/// 3-char code for an Xonotic character (xon brain only) — same 15-char-name budget as
-/// [`char_code`]. The full name is [`brain::XonCharPreset::tag`].
-pub(crate) fn xon_char_code(x: brain::XonCharPreset) -> &'static str {
-    match x {
-        brain::XonCharPreset::Rusher => "rus",
-        brain::XonCharPreset::Sharp => "shp",
-        brain::XonCharPreset::Turtle => "trt",
-        brain::XonCharPreset::Noob => "nob",
-    }
-}
-
-/// 3-char code for a Q3 character (q3 brain only), used in competition bot names (see
-/// [`mode_code`]). The full, log-facing name is [`brain::CharPreset::tag`].
-pub(crate) fn char_code(char: brain::CharPreset) -> &'static str {
-    match char {
-        brain::CharPreset::Grunt => "gru",
-        brain::CharPreset::Major => "maj",
-        brain::CharPreset::Sarge => "sar",
-        brain::CharPreset::Camper => "cam",
+        brain::BrainKind::Script => "scr",
+        brain::BrainKind::Utility => "uti",
     }
 }
 
@@ -575,49 +636,60 @@ pub(crate) fn char_code(char: brain::CharPreset) -> &'static str {
 pub(crate) struct GroupSpec {
     pub(crate) mode: crate::NavMode,
     pub(crate) brain: brain::BrainKind,
-    pub(crate) gc: GroupChar,
+    /// The group's character — always of the brain's own family; `None` = the brain's default.
+    pub(crate) character: Option<Arc<Character>>,
     pub(crate) count: usize,
     pub(crate) skin: Option<String>,
+    /// CTF / skin-teams team every bot in the group joins; `None` = free-for-all.
+    pub(crate) team: Option<brain::Team>,
     pub(crate) tag: String,
 }
 
 /// Expand the CLI matrix (`modes × brains × chars_for`) into the flat group list, preserving the
-/// historical **mode-major, brain-minor, char-innermost** ordering and the `gc.skin().or(mode_skin)`
+/// historical **mode-major, brain-minor, char-innermost** ordering and the `character.skin.or(mode_skin)`
 /// rule — so the matrix competition path is byte-for-byte what it was before the `GroupSpec`
 /// refactor (see the equivalence test). `skins_per_mode` is positionally indexed by `modes` (as it
 /// was in `run_competition`). Every group gets the same `per_group_count`; roster files vary it.
 pub(crate) fn matrix_specs(
     modes: &[crate::NavMode],
     brains: &[brain::BrainKind],
-    chars: &[brain::CharPreset],
-    xonchars: &[brain::XonCharPreset],
+    chars: &[Arc<Character>],
     per_group_count: usize,
     skins_per_mode: &[Option<String>],
 ) -> Vec<GroupSpec> {
-    // The `char` axis only expands the `q3`/`xon` brains (others get a single `None` sub-group).
-    let chars_for = |bk: brain::BrainKind| -> Vec<GroupChar> {
-        if bk == brain::BrainKind::Quake3 && !chars.is_empty() {
-            chars.iter().map(|&c| GroupChar::Q3(c)).collect()
-        } else if bk == brain::BrainKind::Xon && !xonchars.is_empty() {
-            xonchars.iter().map(|&x| GroupChar::Xon(x)).collect()
+    // The character axis expands each brain by the characters of its own family (a brain with
+    // none listed gets a single `None` sub-group).
+    let chars_for = |bk: brain::BrainKind| -> Vec<Option<Arc<Character>>> {
+        let own: Vec<_> = chars
+            .iter()
+            .filter(|c| Family::for_brain(bk) == Some(c.family()))
+            .map(|c| Some(Arc::clone(c)))
+            .collect();
+        if own.is_empty() {
+            vec![None]
         } else {
-            vec![GroupChar::None]
+            own
         }
     };
     let mut specs = Vec::new();
     for (mi, &mode) in modes.iter().enumerate() {
         let mode_skin = skins_per_mode.get(mi).cloned().flatten();
         for &brain in brains {
-            for gc in chars_for(brain) {
+            for character in chars_for(brain) {
                 // A named character wears its own recognizable skin; else the per-mode skin.
-                let skin = gc.skin().or_else(|| mode_skin.clone());
+                let skin = character
+                    .as_ref()
+                    .and_then(|c| c.skin.clone())
+                    .or_else(|| mode_skin.clone());
+                let tag = group_tag(mode, brain, character.as_deref());
                 specs.push(GroupSpec {
                     mode,
                     brain,
-                    gc,
+                    character,
                     count: per_group_count,
                     skin,
-                    tag: group_tag(mode, brain, gc),
+                    team: None,
+                    tag,
                 });
             }
         }
@@ -635,7 +707,7 @@ pub(crate) fn matrix_specs(
 pub async fn run_competition(
     cfg: Arc<Config>,
     addr: SocketAddr,
-    mut specs: Vec<GroupSpec>,
+    specs: Vec<GroupSpec>,
     qport_base_override: Option<u16>,
     loose_botcap: bool,
 ) -> std::io::Result<()> {
@@ -643,6 +715,46 @@ pub async fn run_competition(
         tracing::error!("competition needs at least one group");
         return Ok(());
     }
+    let shutdown = Shutdown::new();
+    let _signals = spawn_signal_listener(shutdown.clone());
+    let round = compete(
+        cfg,
+        addr,
+        specs,
+        qport_base_override,
+        loose_botcap,
+        shutdown,
+    )
+    .await?;
+    // Plan 69: emit a ranked, ready-to-edit roster of every group — trim it and pass it back via
+    // `--roster` for the next round. Written before the join result so a join-failure run
+    // (which returns Err) still leaves the standings on disk.
+    dump_final_roster(&round.board, &round.specs);
+    tracing::info!("competition exited");
+    round.joined
+}
+
+/// One finished [`compete`] round.
+pub(crate) struct Round {
+    /// The groups as fielded (after the `max_bots` clamp).
+    pub(crate) specs: Vec<GroupSpec>,
+    /// The FINAL standings, ranked.
+    pub(crate) board: Vec<ModeScore>,
+    /// `Err` when a bot's initial join failed (strict botcap) and stopped the round early.
+    pub(crate) joined: std::io::Result<()>,
+}
+
+/// Field `specs` until `shutdown` fires and every bot has left, then report the standings. The
+/// body of [`run_competition`]; `qbots evolve` drives it once per generation with a timed
+/// `shutdown` of its own.
+pub(crate) async fn compete(
+    cfg: Arc<Config>,
+    addr: SocketAddr,
+    mut specs: Vec<GroupSpec>,
+    qport_base_override: Option<u16>,
+    loose_botcap: bool,
+    shutdown: Shutdown,
+) -> std::io::Result<Round> {
     // maxclients guard: clamp per-group counts so the total leaves human headroom. Scale each
     // group proportionally to its requested count (for uniform counts this is bit-identical to the
     // old `(max_bots / num_groups).max(1)` — integer identity, asserted in the clamp test).
@@ -670,13 +782,12 @@ pub async fn run_competition(
         enabled: cfg.fleet.reconnect,
         max_attempts: cfg.fleet.max_reconnects,
     };
-    let shutdown = Shutdown::new();
     let stats = FleetStats::new();
-    let _signals = spawn_signal_listener(shutdown.clone());
     let shared = FleetShared {
         nav: NavCache::new(), // ONE shared cache across every mode (the in-process perf win)
         shutdown: shutdown.clone(),
         stats: stats.clone(),
+        heat: HeatBank::new(),
         join_failure: Arc::new(Mutex::new(None)),
         loose_botcap,
         beacon: start_beacon(&cfg, addr, &shutdown),
@@ -705,22 +816,13 @@ pub async fn run_competition(
             let name = format!("{}_{}", spec.tag, i + 1);
             let qport = qport_base.wrapping_add((qport_offset + i) as u16);
             let bot_skin = spec.skin.clone();
-            let (mode, bk, gc) = (spec.mode, spec.brain, spec.gc);
+            let (mode, bk, team) = (spec.mode, spec.brain, spec.team);
+            let character = spec.character.clone();
             let cfg = Arc::clone(&cfg);
             let shared = shared.clone();
             tasks.push(tokio::spawn(async move {
                 bot_supervisor_loop(
-                    addr,
-                    name,
-                    qport,
-                    bot_skin,
-                    cfg,
-                    shared,
-                    reconnect,
-                    mode,
-                    bk,
-                    gc.q3(),
-                    gc.xon(),
+                    addr, name, qport, bot_skin, team, cfg, shared, reconnect, mode, bk, character,
                 )
                 .await;
             }));
@@ -751,12 +853,12 @@ pub async fn run_competition(
     status.abort();
     log_competition_scoreboard(&stats, &group_tags, "FINAL");
     log_map_changes(&stats);
-    // Plan 69: emit a ranked, ready-to-edit roster of every group — trim it and pass it back via
-    // `--roster` for the next round. Written before `fleet_join_result` so a join-failure run
-    // (which returns Err) still leaves the standings on disk.
-    dump_final_roster(&stats, &group_tags, &specs);
-    tracing::info!("competition exited");
-    fleet_join_result(&shared)
+    shared.heat.save(&cfg);
+    Ok(Round {
+        board: mode_scoreboard(&stats, &group_tags),
+        specs,
+        joined: fleet_join_result(&shared),
+    })
 }
 
 /// Report how many map changes the run went through — one aggregated fleet-wide number plus the
@@ -781,9 +883,8 @@ fn log_map_changes(stats: &FleetStats) {
 
 /// Write the FINAL standings as a ranked roster YAML to `./logs/roster/<unix_ts>.yaml` (Plan 69).
 /// Best-effort: an IO error is logged, never fatal — the competition already ran.
-fn dump_final_roster(stats: &FleetStats, group_tags: &[String], specs: &[GroupSpec]) {
-    let ranked = mode_scoreboard(stats, group_tags);
-    let yaml = crate::roster::emit_ranked_yaml(&ranked, specs);
+fn dump_final_roster(ranked: &[ModeScore], specs: &[GroupSpec]) {
+    let yaml = crate::roster::emit_ranked_yaml(ranked, specs);
     let dir = std::path::Path::new("logs/roster");
     if let Err(e) = std::fs::create_dir_all(dir) {
         tracing::warn!("could not create {}: {e}", dir.display());
@@ -812,17 +913,13 @@ fn log_competition_legend(specs: &[GroupSpec]) {
     }
     let mut modes: Vec<crate::NavMode> = Vec::new();
     let mut brains: Vec<brain::BrainKind> = Vec::new();
-    let mut chars: Vec<brain::CharPreset> = Vec::new();
-    let mut xonchars: Vec<brain::XonCharPreset> = Vec::new();
+    let mut chars: Vec<&Character> = Vec::new();
     for s in specs {
         push_unique(&mut modes, s.mode);
         push_unique(&mut brains, s.brain);
-        if let Some(c) = s.gc.q3() {
+        if let Some(c) = s.character.as_deref() {
             push_unique(&mut chars, c);
         }
-        if let Some(x) = s.gc.xon() {
-            push_unique(&mut xonchars, x);
-        }
     }
     let join = |pairs: Vec<String>| pairs.join(", ");
     let brain_leg = join(
@@ -839,23 +936,17 @@ fn log_competition_legend(specs: &[GroupSpec]) {
     );
     tracing::info!("name-code legend — brain: {brain_leg}");
     tracing::info!("name-code legend — mode:  {mode_leg}");
-    if !chars.is_empty() {
-        let char_leg = join(
+    for family in Family::ALL {
+        let leg = join(
             chars
                 .iter()
-                .map(|&c| format!("{}={}", char_code(c), c.tag()))
-                .collect(),
-        );
-        tracing::info!("name-code legend — char:  {char_leg}");
-    }
-    if !xonchars.is_empty() {
-        let xc_leg = join(
-            xonchars
-                .iter()
-                .map(|&x| format!("{}={}", xon_char_code(x), x.tag()))
+                .filter(|c| c.family() == family)
+                .map(|c| format!("{}={}", c.code, c.name))
                 .collect(),
         );
-        tracing::info!("name-code legend — xonchar: {xc_leg}");
+        if !leg.is_empty() {
+            tracing::info!("name-code legend — {}: {leg}", family.key());
+        }
     }
 }
 
@@ -864,18 +955,14 @@ fn log_competition_legend(specs: &[GroupSpec]) {
 /// `<brain>_<mode>[_<char>]` (e.g. `mai_as`, `q3_rc`, `q3_rc_gru`). Short codes keep the
 /// `<tag>_<i>` bot name inside Q2's 15-char `netname` limit. Every token is `_`-free, so the
 /// name still index-splits on its trailing `_` in [`mode_scoreboard`].
-pub(crate) fn group_tag(mode: crate::NavMode, brain: brain::BrainKind, gc: GroupChar) -> String {
-    match gc {
-        GroupChar::Q3(c) => format!("{}_{}_{}", brain_code(brain), mode_code(mode), char_code(c)),
-        GroupChar::Xon(x) => {
-            format!(
-                "{}_{}_{}",
-                brain_code(brain),
-                mode_code(mode),
-                xon_char_code(x)
-            )
-        }
-        GroupChar::None => format!("{}_{}", brain_code(brain), mode_code(mode)),
+pub(crate) fn group_tag(
+    mode: crate::NavMode,
+    brain: brain::BrainKind,
+    character: Option<&Character>,
+) -> String {
+    match character {
+        Some(c) => format!("{}_{}_{}", brain_code(brain), mode_code(mode), c.code),
+        None => format!("{}_{}", brain_code(brain), mode_code(mode)),
     }
 }
 
@@ -905,6 +992,12 @@ impl ModeScore {
             self.kills as f32
         }
     }
+
+    /// [`Self::kd`], or `None` when the group traded no frags at all — it never joined, or the
+    /// round was too short to say anything about it.
+    pub(crate) fn measured_kd(&self) -> Option<f32> {
+        (self.kills + self.deaths > 0).then(|| self.kd())
+    }
 }
 
 /// Group the fleet's per-bot tallies by the `<group_tag>_<i>` name prefix and sum kills/deaths,
@@ -994,13 +1087,13 @@ async fn bot_supervisor_loop(
     name: String,
     qport: u16,
     skin: Option<String>,
+    team: Option<brain::Team>,
     cfg: Arc<Config>,
     shared: FleetShared,
     reconnect: Reconnect,
     mode: crate::NavMode,
     brain: brain::BrainKind,
-    char: Option<brain::CharPreset>,
-    xonchar: Option<brain::XonCharPreset>,
+    character: Option<Arc<Character>>,
 ) {
     let mut attempts: u32 = 0;
     let mut backoff_ms: u64 = 1000;
@@ -1034,6 +1127,7 @@ async fn bot_supervisor_loop(
             let skin = skin.clone();
             let cfg = Arc::clone(&cfg);
             let shared = shared.clone();
+            let character = character.clone();
             async move {
                 tracing::Instrument::instrument(
                     crate::bot_task(
@@ -1041,16 +1135,16 @@ async fn bot_supervisor_loop(
                         &name,
                         qport,
                         skin.as_deref(),
+                        team,
                         &cfg,
                         &shared.nav,
                         &shared.shutdown,
                         &shared.stats,
+                        &shared.heat,
                         shared.beacon.as_ref(),
                         mode,
                         brain,
-                        char,
-                        None, // TODO(P27): per-bot fleet persona from config
-                        xonchar,
+                        character.as_deref(),
                     ),
                     span,
                 )
@@ -1128,29 +1222,29 @@ pub async fn run_single(
     qport: u16,
     mode: crate::NavMode,
     brain: brain::BrainKind,
-    char: Option<brain::CharPreset>,
-    persona: Option<brain::persona::Persona>,
-    xonchar: Option<brain::XonCharPreset>,
+    character: Option<&Character>,
 ) -> std::io::Result<()> {
     let nav = NavCache::new();
     let shutdown = Shutdown::new();
     let stats = FleetStats::new();
+    let heat = HeatBank::new();
     let _signals = spawn_signal_listener(shutdown.clone());
     // A selected character wears its recognizable skin even as a single bot.
-    let skin = char.map(|q| q.skin()).or(xonchar.map(|x| x.skin()));
+    let skin = character.and_then(|c| c.skin.as_deref());
     let span = tracing::info_span!("bot", %name, qport);
     let res = tracing::Instrument::instrument(
         // No beacon for `connect-one`: it's a single-bot dev tool, and the beacon is a
         // fleet-level facility that qctrl expects to be fed by a running fleet.
         crate::bot_task(
-            addr, name, qport, skin, cfg, &nav, &shutdown, &stats, None, mode, brain, char,
-            persona, xonchar,
+            addr, name, qport, skin, None, cfg, &nav, &shutdown, &stats, &heat, None, mode, brain,
+            character,
         ),
         span,
     )
     .await;
     // bot_task has disconnected (or errored) — emit the single-bot tally.
     log_final_stats(&stats);
+    heat.save(cfg);
     res
 }
 
@@ -1206,8 +1300,13 @@ fn log_final_stats(stats: &FleetStats) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::characters::shipped;
     use crate::NavMode;
-    use brain::{BrainKind, CharPreset};
+    use brain::BrainKind;
+
+    fn q3(chars: &crate::characters::Characters, name: &str) -> Arc<Character> {
+        chars.resolve(Family::Q3, name).unwrap()
+    }
 
     /// The running qport offset each spec's bots start at — the `GroupSpec` analog of the old
     /// `g * per_group_count` block base. Test helper mirroring `run_competition`'s cumsum.
@@ -1224,11 +1323,11 @@ mod tests {
     #[test]
     fn matrix_specs_reproduces_the_historical_group_order_and_qports() {
         // modes=[as,nm] × brains=[mai,q3] × chars=[gru,cam], count=2, one skin per mode.
+        let chars = shipped();
         let specs = matrix_specs(
             &[NavMode::Astar, NavMode::Navmesh],
             &[BrainKind::Main, BrainKind::Quake3],
-            &[CharPreset::Grunt, CharPreset::Camper],
-            &[],
+            &[q3(&chars, "grunt"), q3(&chars, "camper")],
             2,
             &[Some("male/grunt".into()), Some("female/athena".into())],
         );
@@ -1251,11 +1350,11 @@ mod tests {
 
     #[test]
     fn matrix_specs_char_skin_beats_mode_skin() {
+        let chars = shipped();
         let specs = matrix_specs(
             &[NavMode::Astar],
             &[BrainKind::Main, BrainKind::Quake3],
-            &[CharPreset::Grunt],
-            &[],
+            &[q3(&chars, "grunt")],
             1,
             &[Some("male/mode".into())],
         );
@@ -1263,7 +1362,25 @@ mod tests {
         let mai = specs.iter().find(|s| s.tag == "mai_as").unwrap();
         let q3 = specs.iter().find(|s| s.tag == "q3_as_gru").unwrap();
         assert_eq!(mai.skin.as_deref(), Some("male/mode"));
-        assert_eq!(q3.skin.as_deref(), Some(CharPreset::Grunt.skin()));
+        assert_eq!(q3.skin.as_deref(), Some("male/grunt"));
+    }
+
+    #[test]
+    fn matrix_specs_give_each_brain_only_its_own_family() {
+        let chars = shipped();
+        let picks = [
+            chars.resolve(Family::Main, "guard").unwrap(),
+            chars.resolve(Family::Xon, "sharp").unwrap(),
+        ];
+        let specs = matrix_specs(
+            &[NavMode::Astar],
+            &[BrainKind::Main, BrainKind::Quake3, BrainKind::Xon],
+            &picks,
+            1,
+            &[None],
+        );
+        let tags: Vec<&str> = specs.iter().map(|s| s.tag.as_str()).collect();
+        assert_eq!(tags, ["mai_as_gua", "q3_as", "xon_as_shp"]);
     }
 
     #[test]
@@ -1271,14 +1388,13 @@ mod tests {
         let specs = matrix_specs(
             &[NavMode::Navmesh],
             &[BrainKind::Xon],
-            &[],
-            &[], // no xonchars → a single default (None) xon group
+            &[], // no characters → a single default (None) xon group
             3,
             &[None],
         );
         assert_eq!(specs.len(), 1);
         assert_eq!(specs[0].tag, "xon_nm");
-        assert_eq!(specs[0].gc, GroupChar::None);
+        assert_eq!(specs[0].character, None);
         assert_eq!(specs[0].count, 3);
     }
 
@@ -1368,6 +1484,51 @@ mod tests {
         assert_eq!(board[2].tag, "zeta");
     }
 
+    #[test]
+    fn heat_bank_averages_bots_on_the_same_graph() {
+        let rec = |d: f32| brain::HeatRecord {
+            danger: vec![d, 0.0],
+            presence: vec![0.0; 2],
+            sessions: 1,
+        };
+        let bank = HeatBank::new();
+        bank.deposit("q2dm1", 7, rec(2.0));
+        bank.deposit("q2dm1", 7, rec(4.0));
+        bank.deposit("q2dm1", 7, rec(0.0)); // a bot that learned nothing doesn't dilute
+        let run = |map: &str| bank.maps.lock().unwrap().get(map).cloned();
+        let (_, r) = run("q2dm1").unwrap();
+        assert_eq!((r.danger[0], r.sessions), (3.0, 2));
+        // The cache was regenerated mid-run: the new graph's heat replaces the old.
+        bank.deposit("q2dm1", 8, rec(1.0));
+        assert_eq!(run("q2dm1").map(|(fp, r)| (fp, r.sessions)), Some((8, 1)));
+        assert!(run("q2dm2").is_none());
+    }
+
+    #[test]
+    fn heat_bank_replaces_an_unreadable_heat_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = format!(
+            "server: {{host: h, port: 1}}\npaths: {{server_cfg: s, baseq2: b, heatmaps: {}}}\n",
+            dir.path().display()
+        );
+        let cfg: Config = serde_yaml::from_str(&yaml).unwrap();
+        let path = cfg.paths.heatmap_file("q2dm1");
+        std::fs::write(&path, "# qbots heatmap\nfingerprint 00").unwrap(); // cut off mid-write
+        let bank = HeatBank::new();
+        let rec = brain::HeatRecord {
+            danger: vec![2.0, 0.0],
+            presence: vec![0.0; 2],
+            sessions: 1,
+        };
+        bank.deposit("q2dm1", 7, rec.clone());
+        bank.save(&cfg);
+        assert_eq!(brain::HeatRecord::load(&path, 7), Ok(Some(rec)));
+        assert!(
+            path.with_extension("txt.bad").exists(),
+            "old file kept aside"
+        );
+    }
+
     #[test]
     fn fits_capacity_respects_free_slots() {
         // 55/64 in use → 9 free.
@@ -1389,67 +1550,53 @@ mod tests {
     #[test]
     fn group_tag_uses_short_codes_brain_first() {
         // Brain code first, then nav-plan code, then optional character code; underscore-joined.
+        let chars = shipped();
+        assert_eq!(group_tag(NavMode::Astar, BrainKind::Main, None), "mai_as");
         assert_eq!(
-            group_tag(NavMode::Astar, BrainKind::Main, GroupChar::None),
-            "mai_as"
-        );
-        assert_eq!(
-            group_tag(NavMode::HybridRace, BrainKind::Quake3, GroupChar::None),
+            group_tag(NavMode::HybridRace, BrainKind::Quake3, None),
             "q3_rc"
         );
         assert_eq!(
             group_tag(
                 NavMode::HybridRace,
                 BrainKind::Quake3,
-                GroupChar::Q3(CharPreset::Grunt)
+                Some(&q3(&chars, "grunt"))
             ),
             "q3_rc_gru"
         );
         assert_eq!(
-            group_tag(NavMode::Navmesh, BrainKind::Sentry, GroupChar::None),
+            group_tag(NavMode::Navmesh, BrainKind::Sentry, None),
             "sen_nm"
         );
         assert_eq!(
-            group_tag(NavMode::HybridFallback, BrainKind::Zb2, GroupChar::None),
+            group_tag(NavMode::HybridFallback, BrainKind::Zb2, None),
             "zb2_fb"
         );
         assert_eq!(
             group_tag(
                 NavMode::XonGoal,
                 BrainKind::Xon,
-                GroupChar::Xon(brain::XonCharPreset::Rusher)
+                Some(&chars.resolve(Family::Xon, "rusher").unwrap())
             ),
             "xon_xg_rus"
         );
     }
 
-    /// Every brain × mode × {no char, each char} combo must fit Q2's 15-char `netname` limit,
-    /// even at a two-digit (or three-digit) bot index — the whole point of the short codes.
+    /// Every brain × mode × {no char, a widest-code char} combo must fit Q2's 15-char `netname`
+    /// limit, even at a three-digit bot index — the whole point of the short codes.
     #[test]
     fn every_competition_name_fits_15_chars() {
-        use brain::{BrainKind, CharPreset, XonCharPreset};
         use clap::ValueEnum;
-        let chars: Vec<GroupChar> = std::iter::once(GroupChar::None)
-            .chain(
-                CharPreset::value_variants()
-                    .iter()
-                    .map(|&c| GroupChar::Q3(c)),
-            )
-            .chain(
-                XonCharPreset::value_variants()
-                    .iter()
-                    .map(|&x| GroupChar::Xon(x)),
-            )
-            .collect();
+        let widest = Character::parse(Family::Q3, "wide", "code: abc\n").unwrap();
         for &mode in crate::NavMode::value_variants() {
             for &brain in BrainKind::value_variants() {
-                for &char in &chars {
-                    let tag = group_tag(mode, brain, char);
+                for character in [None, Some(&widest)] {
+                    let tag = group_tag(mode, brain, character);
                     // Worst realistic index is 3 digits → `_999` (4 chars) appended.
                     let name_len = tag.len() + "_999".len();
                     assert!(
                         name_len <= 15,
-                        "name `{tag}_999` is {name_len} chars (> 15): {brain:?}/{mode:?}/{char:?}"
+                        "name `{tag}_999` is {name_len} chars (> 15): {brain:?}/{mode:?}"
                     );
                 }
             }
@@ -0,0 +1,539 @@
+//! mapreport — item-aware static analysis of one map, as JSON and/or a self-contained HTML
+//! page. Loads the BSP + nav graph (cache, or live with `QBOTS_LIVE=1`), builds the navmesh,
+//! and reports what a level designer (or a bot-tuning session) wants to know up front:
+//!
+//! - graph / navmesh component counts and spawns stranded off the largest component;
+//! - spawn → item travel times (seconds at `MAX_SPEED`) for every item pad, the same set
+//!   [`brain::items::build_map_items`] feeds the bots, plus items no spawn can reach;
+//! - per-spawn time to the nearest weapon;
+//! - choke points: graph articulation nodes whose removal cuts off `>= --choke-min` nodes;
+//! - hazard zones: clusters of nodes whose landing strip falls into lava/slime
+//!   ([`world::deadly::landing_strip_deadly`]).
+//!
+//! Usage:
+//!   cargo run -p tools --bin mapreport -- <baseq2> <map> [--json <out>] [--html <out>] [--choke-min <n>]
+//!   cargo run -p tools --bin mapreport -- baseq2 q2dm1 --html q2dm1.html
+//!
+//! With neither `--json` nor `--html` the JSON goes to stdout. Env vars: `QBOTS_SPACING`,
+//! `QBOTS_LIVE` (same meaning as in `navinspect`).
+
+use brain::items::build_map_items;
+use brain::move_ctrl::MAX_SPEED;
+use brain::perception::EntityClass;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use world::deadly::landing_strip_deadly;
+use world::{cached_map_nav, CollisionModel, NavGraph};
+
+/// Default `--choke-min`: a cut node must strand at least this many graph nodes (~two
+/// 24u-grid rooms' worth of floor) to count as a choke point rather than a dangling stub.
+const DEFAULT_CHOKE_MIN: usize = 32;
+
+#[derive(Serialize)]
+struct Report {
+    map: String,
+    spacing: f32,
+    graph: GraphSummary,
+    navmesh: NavmeshSummary,
+    spawns: Vec<SpawnReport>,
+    items: Vec<ItemReport>,
+    /// `travel_secs[s][i]` = seconds from spawn `s` to item `i`; `None` = unreachable.
+    travel_secs: Vec<Vec<Option<f32>>>,
+    unreachable_spawns: Vec<usize>,
+    unreachable_items: Vec<usize>,
+    choke_points: Vec<ChokePoint>,
+    hazard_zones: Vec<HazardZone>,
+}
+
+#[derive(Serialize)]
+struct GraphSummary {
+    nodes: usize,
+    edges: usize,
+    components: usize,
+    /// Sizes of the biggest components, largest first (at most 8).
+    top_component_sizes: Vec<usize>,
+    spawns_in_largest: usize,
+    total_spawns: usize,
+}
+
+#[derive(Serialize)]
+struct NavmeshSummary {
+    polys: usize,
+    components: usize,
+    spawns_in_largest: usize,
+}
+
+#[derive(Serialize)]
+struct SpawnReport {
+    index: usize,
+    origin: [f32; 3],
+    nav_node: Option<usize>,
+    nearest_weapon: Option<String>,
+    nearest_weapon_secs: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct ItemReport {
+    index: usize,
+    classname: String,
+    class: String,
+    origin: [f32; 3],
+    nav_node: Option<usize>,
+    /// Fastest spawn → item time over all spawns.
+    min_secs: Option<f32>,
+    /// Mean over the spawns that can reach it.
+    mean_secs: Option<f32>,
+}
+
+#[derive(Serialize)]
+struct ChokePoint {
+    node: usize,
+    origin: [f32; 3],
+    cut_off: usize,
+}
+
+#[derive(Serialize)]
+struct HazardZone {
+    nodes: usize,
+    center: [f32; 3],
+    mins: [f32; 3],
+    maxs: [f32; 3],
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 3 {
+        eprintln!(
+            "usage: mapreport <baseq2> <map> [--json <out>] [--html <out>] [--choke-min <n>]"
+        );
+        std::process::exit(2);
+    }
+    let baseq2 = Path::new(&args[1]);
+    let map = &args[2];
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let json_out = flag("--json");
+    let html_out = flag("--html");
+    let choke_min: usize = flag("--choke-min")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_CHOKE_MIN);
+
+    let spacing: f32 = std::env::var("QBOTS_SPACING")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(world::GRID_SPACING);
+    let live = std::env::var("QBOTS_LIVE").is_ok_and(|v| v != "0" && !v.is_empty());
+    let built = if live {
+        eprintln!("[mapreport] QBOTS_LIVE set — building nav live (bypassing cache + gate)");
+        world::generate_map_nav(baseq2, map, spacing)?
+    } else {
+        cached_map_nav(baseq2, map, Some(Path::new("data/mapcache")), spacing)?
+    };
+    let g = &built.graph;
+    let cm = &built.cm;
+    let spawns = &built.spawn_origins;
+
+    // --- components ---
+    let comps = g.components();
+    let largest = g.largest_spawn_component(spawns);
+    let mut in_largest = vec![false; g.node_count()];
+    for &n in &largest {
+        in_largest[n] = true;
+    }
+    let spawn_nodes: Vec<Option<usize>> = spawns.iter().map(|s| g.nearest(s)).collect();
+    let unreachable_spawns: Vec<usize> = spawn_nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| !n.is_some_and(|n| in_largest[n]))
+        .map(|(i, _)| i)
+        .collect();
+    let graph = GraphSummary {
+        nodes: g.node_count(),
+        edges: g.edge_count(),
+        components: comps.len(),
+        top_component_sizes: comps.iter().take(8).map(Vec::len).collect(),
+        spawns_in_largest: spawns.len() - unreachable_spawns.len(),
+        total_spawns: spawns.len(),
+    };
+    let navmesh = navmesh_summary(&built.bsp, cm, spawns);
+
+    // --- items: exactly the set `build_map_items` feeds the bots; the classname (weapon vs
+    // ammo pad, which share a class) is looked up by origin for the report ---
+    let classnames: HashMap<[u32; 3], &str> = built
+        .bsp
+        .entities
+        .iter()
+        .filter_map(|e| Some((e.origin()?.map(f32::to_bits), e.classname.as_str())))
+        .collect();
+    let mut items: Vec<ItemReport> = build_map_items(&built.bsp, g)
+        .into_iter()
+        .enumerate()
+        .map(|(index, it)| {
+            let origin = it.origin.to_array();
+            ItemReport {
+                index,
+                classname: classnames
+                    .get(&origin.map(f32::to_bits))
+                    .map_or_else(String::new, |n| n.to_string()),
+                class: class_label(it.class).to_string(),
+                origin,
+                nav_node: it.nav_node,
+                min_secs: None,
+                mean_secs: None,
+            }
+        })
+        .collect();
+
+    // --- spawn → item travel times: one flood per spawn, graph cost is distance ---
+    let travel_secs: Vec<Vec<Option<f32>>> = spawn_nodes
+        .iter()
+        .map(|sn| {
+            let costs = sn.map(|n| g.flood_costs(n));
+            items
+                .iter()
+                .map(|it| {
+                    let c = costs.as_ref()?[it.nav_node?];
+                    c.is_finite().then_some(c / MAX_SPEED)
+                })
+                .collect()
+        })
+        .collect();
+    for (i, it) in items.iter_mut().enumerate() {
+        let times: Vec<f32> = travel_secs.iter().filter_map(|row| row[i]).collect();
+        if !times.is_empty() {
+            it.min_secs = times.iter().copied().reduce(f32::min);
+            it.mean_secs = Some(times.iter().sum::<f32>() / times.len() as f32);
+        }
+    }
+    let unreachable_items: Vec<usize> = items
+        .iter()
+        .filter(|it| it.min_secs.is_none())
+        .map(|it| it.index)
+        .collect();
+
+    let spawn_reports: Vec<SpawnReport> = spawns
+        .iter()
+        .enumerate()
+        .map(|(si, &origin)| {
+            let best = items
+                .iter()
+                .filter(|it| it.classname.starts_with("weapon_"))
+                .filter_map(|it| Some((it, travel_secs[si][it.index]?)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            SpawnReport {
+                index: si,
+                origin,
+                nav_node: spawn_nodes[si],
+                nearest_weapon: best.map(|(it, _)| it.classname.clone()),
+                nearest_weapon_secs: best.map(|(_, t)| t),
+            }
+        })
+        .collect();
+
+    // --- choke points ---
+    let mut choke_points: Vec<ChokePoint> = g
+        .articulation_points()
+        .into_iter()
+        .filter(|&(_, cut_off)| cut_off >= choke_min)
+        .map(|(node, cut_off)| ChokePoint {
+            node,
+            origin: g.nodes[node],
+            cut_off,
+        })
+        .collect();
+    choke_points.sort_by_key(|c| std::cmp::Reverse(c.cut_off));
+
+    let report = Report {
+        map: map.clone(),
+        spacing,
+        graph,
+        navmesh,
+        spawns: spawn_reports,
+        items,
+        travel_secs,
+        unreachable_spawns,
+        unreachable_items,
+        choke_points,
+        hazard_zones: hazard_zones(g, cm),
+    };
+
+    let json = serde_json::to_string_pretty(&report)?;
+    if let Some(path) = &json_out {
+        std::fs::write(path, &json)?;
+        eprintln!("[mapreport] wrote {path}");
+    }
+    if let Some(path) = &html_out {
+        std::fs::write(path, render_html(&report, g))?;
+        eprintln!("[mapreport] wrote {path}");
+    }
+    if json_out.is_none() && html_out.is_none() {
+        println!("{json}");
+    }
+    Ok(())
+}
+
+fn class_label(class: EntityClass) -> &'static str {
+    match class {
+        EntityClass::ItemHealth => "health",
+        EntityClass::ItemArmor => "armor",
+        EntityClass::ItemWeapon => "weapon",
+        EntityClass::ItemPowerup => "powerup",
+        _ => "other",
+    }
+}
+
+/// Navmesh build at the supervisor's settings (8u cells, 1-cell erode, drop links) and its
+/// spawn connectivity — the navmesh analog of the graph's largest-spawn-component check.
+fn navmesh_summary(bsp: &world::Bsp, cm: &CollisionModel, spawns: &[[f32; 3]]) -> NavmeshSummary {
+    let model = &bsp.models[0];
+    let params = world::VoxelParams {
+        cell_size: 8.0,
+        ..Default::default()
+    };
+    let mut hf = world::Heightfield::build(cm, (model.mins, model.maxs), params);
+    let drops = hf.find_drops(cm);
+    hf.erode(1);
+    let mut mesh = world::NavMesh::build(&hf, params.walkable_climb, Some(cm));
+    mesh.add_drops(&drops);
+    let comps = mesh.components();
+    let mut comp_of = vec![usize::MAX; mesh.polys.len()];
+    for (ci, c) in comps.iter().enumerate() {
+        for &p in c {
+            comp_of[p] = ci;
+        }
+    }
+    let mut per_comp: HashMap<usize, usize> = HashMap::new();
+    for sp in spawns {
+        if let Some(p) = mesh.nearest_poly(*sp) {
+            *per_comp.entry(comp_of[p]).or_insert(0) += 1;
+        }
+    }
+    NavmeshSummary {
+        polys: mesh.polys.len(),
+        components: comps.len(),
+        spawns_in_largest: per_comp.values().copied().max().unwrap_or(0),
+    }
+}
+
+/// Hazard-adjacent nodes — a skid off the node in any horizontal direction falls into
+/// lava/slime (`landing_strip_deadly` samples the given axis plus both perpendiculars, so
+/// `±X` covers all four) — grouped into zones by graph adjacency. Largest zone first.
+fn hazard_zones(g: &NavGraph, cm: &CollisionModel) -> Vec<HazardZone> {
+    let hazard: Vec<bool> = g
+        .nodes
+        .iter()
+        .map(|&p| {
+            landing_strip_deadly(cm, p, [1.0, 0.0]) || landing_strip_deadly(cm, p, [-1.0, 0.0])
+        })
+        .collect();
+    let mut seen = vec![false; g.node_count()];
+    let mut zones = Vec::new();
+    for start in 0..g.node_count() {
+        if !hazard[start] || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut members = Vec::new();
+        while let Some(n) = stack.pop() {
+            members.push(n);
+            for &(nb, _) in g.neighbors(n) {
+                if hazard[nb] && !seen[nb] {
+                    seen[nb] = true;
+                    stack.push(nb);
+                }
+            }
+        }
+        let mut mins = [f32::INFINITY; 3];
+        let mut maxs = [f32::NEG_INFINITY; 3];
+        let mut sum = [0.0f32; 3];
+        for &n in &members {
+            let p = g.nodes[n];
+            for k in 0..3 {
+                mins[k] = mins[k].min(p[k]);
+                maxs[k] = maxs[k].max(p[k]);
+                sum[k] += p[k];
+            }
+        }
+        let len = members.len() as f32;
+        zones.push(HazardZone {
+            nodes: members.len(),
+            center: [sum[0] / len, sum[1] / len, sum[2] / len],
+            mins,
+            maxs,
+        });
+    }
+    zones.sort_by_key(|z| std::cmp::Reverse(z.nodes));
+    zones
+}
+
+fn fmt_secs(t: Option<f32>) -> String {
+    t.map_or_else(|| "—".to_string(), |t| format!("{t:.1}s"))
+}
+
+/// Self-contained HTML: summary + tables + a top-down SVG plot (grey graph nodes, red hazard
+/// zones, orange choke points, green spawns, blue items).
+fn render_html(r: &Report, g: &NavGraph) -> String {
+    const SIZE: f32 = 800.0;
+    let (mut lo, mut hi) = ([f32::INFINITY; 2], [f32::NEG_INFINITY; 2]);
+    for p in &g.nodes {
+        for k in 0..2 {
+            lo[k] = lo[k].min(p[k]);
+            hi[k] = hi[k].max(p[k]);
+        }
+    }
+    let scale = SIZE / (hi[0] - lo[0]).max(hi[1] - lo[1]).max(1.0);
+    // SVG y grows downward; flip so +Y (Q2 north) is up.
+    let xy = |p: [f32; 3]| ((p[0] - lo[0]) * scale, SIZE - (p[1] - lo[1]) * scale);
+
+    let mut h = String::new();
+    let _ = write!(
+        h,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>mapreport {map}</title>\
+         <style>body{{font-family:sans-serif;margin:2em}}table{{border-collapse:collapse;margin-bottom:2em}}\
+         td,th{{border:1px solid #ccc;padding:2px 6px;text-align:right}}th{{background:#eee}}\
+         .bad{{color:#c00}}</style></head><body><h1>{map}</h1>",
+        map = r.map
+    );
+    let _ = write!(
+        h,
+        "<p>spacing {sp} · graph {n} nodes / {e} edges / {c} components (top {top:?}) · \
+         spawns in largest {sl}/{st} · navmesh {mp} polys / {mc} components, spawns in largest {ms}</p>",
+        sp = r.spacing,
+        n = r.graph.nodes,
+        e = r.graph.edges,
+        c = r.graph.components,
+        top = r.graph.top_component_sizes,
+        sl = r.graph.spawns_in_largest,
+        st = r.graph.total_spawns,
+        mp = r.navmesh.polys,
+        mc = r.navmesh.components,
+        ms = r.navmesh.spawns_in_largest,
+    );
+    if !r.unreachable_spawns.is_empty() || !r.unreachable_items.is_empty() {
+        let _ = write!(
+            h,
+            "<p class=\"bad\">unreachable spawns {:?} · unreachable items {:?}</p>",
+            r.unreachable_spawns, r.unreachable_items
+        );
+    }
+
+    // Plot.
+    let _ = write!(
+        h,
+        "<svg width=\"{SIZE}\" height=\"{SIZE}\" style=\"background:#111\">"
+    );
+    for &p in &g.nodes {
+        let (x, y) = xy(p);
+        let _ = write!(
+            h,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"1\" fill=\"#555\"/>"
+        );
+    }
+    for z in &r.hazard_zones {
+        let (x0, y1) = xy(z.mins);
+        let (x1, y0) = xy(z.maxs);
+        let _ = write!(
+            h,
+            "<rect x=\"{x0:.1}\" y=\"{y0:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"#f00\" fill-opacity=\"0.3\"/>",
+            (x1 - x0).max(2.0),
+            (y1 - y0).max(2.0)
+        );
+    }
+    for c in &r.choke_points {
+        let (x, y) = xy(c.origin);
+        let _ = write!(
+            h,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"4\" fill=\"#f90\"><title>choke {} cuts {}</title></circle>",
+            c.node, c.cut_off
+        );
+    }
+    for s in &r.spawns {
+        let (x, y) = xy(s.origin);
+        let _ = write!(
+            h,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"5\" fill=\"#0c0\"><title>spawn {}</title></circle>",
+            s.index
+        );
+    }
+    for it in &r.items {
+        let (x, y) = xy(it.origin);
+        let _ = write!(
+            h,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"6\" height=\"6\" fill=\"#39f\"><title>{} {}</title></rect>",
+            x - 3.0,
+            y - 3.0,
+            it.index,
+            it.classname
+        );
+    }
+    h.push_str("</svg>");
+
+    // Spawns.
+    h.push_str("<h2>Spawns</h2><table><tr><th>#</th><th>origin</th><th>nearest weapon</th><th>time</th></tr>");
+    for s in &r.spawns {
+        let _ = write!(
+            h,
+            "<tr><td>{}</td><td>{:.0} {:.0} {:.0}</td><td>{}</td><td>{}</td></tr>",
+            s.index,
+            s.origin[0],
+            s.origin[1],
+            s.origin[2],
+            s.nearest_weapon.as_deref().unwrap_or("—"),
+            fmt_secs(s.nearest_weapon_secs)
+        );
+    }
+    h.push_str("</table>");
+
+    // Spawn × item matrix.
+    h.push_str(
+        "<h2>Spawn → item travel time</h2><table><tr><th>item</th><th>min</th><th>mean</th>",
+    );
+    for s in &r.spawns {
+        let _ = write!(h, "<th>s{}</th>", s.index);
+    }
+    h.push_str("</tr>");
+    for it in &r.items {
+        let _ = write!(
+            h,
+            "<tr><th>{} {}</th><td>{}</td><td>{}</td>",
+            it.index,
+            it.classname,
+            fmt_secs(it.min_secs),
+            fmt_secs(it.mean_secs)
+        );
+        for row in &r.travel_secs {
+            let _ = write!(h, "<td>{}</td>", fmt_secs(row[it.index]));
+        }
+        h.push_str("</tr>");
+    }
+    h.push_str("</table>");
+
+    // Choke points + hazards.
+    h.push_str(
+        "<h2>Choke points</h2><table><tr><th>node</th><th>origin</th><th>cuts off</th></tr>",
+    );
+    for c in &r.choke_points {
+        let _ = write!(
+            h,
+            "<tr><td>{}</td><td>{:.0} {:.0} {:.0}</td><td>{}</td></tr>",
+            c.node, c.origin[0], c.origin[1], c.origin[2], c.cut_off
+        );
+    }
+    h.push_str("</table><h2>Hazard zones</h2><table><tr><th>nodes</th><th>center</th></tr>");
+    for z in &r.hazard_zones {
+        let _ = write!(
+            h,
+            "<tr><td>{}</td><td>{:.0} {:.0} {:.0}</td></tr>",
+            z.nodes, z.center[0], z.center[1], z.center[2]
+        );
+    }
+    h.push_str("</table></body></html>\n");
+    h
+}
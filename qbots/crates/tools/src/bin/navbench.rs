@@ -0,0 +1,106 @@
+//! navbench — nav graph generation benchmark over the stock deathmatch maps. For each map
+//! it times the grid phase (`NavGraph::generate`) single-threaded and on the full rayon
+//! pool, the post-grid passes (`finish_map_nav`), and a one-tile incremental rebuild
+//! (`NavGraph::regenerate_tiles`), and checks that every variant yields byte-identical
+//! cache output — the determinism `generate-map-cache` relies on.
+//!
+//! Usage:
+//!   cargo run --release -p tools --bin navbench -- <baseq2> [map ...]
+//!   cargo run --release -p tools --bin navbench -- vendor/baseq2 q2dm1 q2dm3
+//!
+//! Maps default to `q2dm1`…`q2dm8`; ones missing from `<baseq2>` are skipped. The parallel
+//! pool honours `RAYON_NUM_THREADS`; `QBOTS_SPACING` picks the grid spacing. Exits non-zero
+//! if any variant's output differs.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use world::{
+    finish_map_nav, mapcache, world_bounds, Bsp, CollisionModel, Fingerprint, NavGraph, Vfs,
+    TILE_COLUMNS,
+};
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let t0 = Instant::now();
+    let out = f();
+    (out, t0.elapsed())
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("usage: navbench <baseq2> [map ...]");
+        std::process::exit(2);
+    }
+    let baseq2 = Path::new(&args[1]);
+    let maps: Vec<String> = if args.len() > 2 {
+        args[2..].to_vec()
+    } else {
+        (1..=8).map(|i| format!("q2dm{i}")).collect()
+    };
+    let spacing: f32 = std::env::var("QBOTS_SPACING")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(world::GRID_SPACING);
+    let vfs = Vfs::open(baseq2)?;
+    let serial_pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+
+    println!(
+        "{:<8} {:>7} {:>10} {:>10} {:>7} {:>10} {:>10} {:>9}",
+        "map", "nodes", "serial ms", "par ms", "speedup", "post ms", "tile ms", "identical"
+    );
+    let mut mismatches = 0;
+    for map in &maps {
+        if !vfs.exists(&format!("maps/{map}.bsp")) {
+            eprintln!("[navbench] {map}: not in {} — skipped", baseq2.display());
+            continue;
+        }
+        let bsp = Bsp::load(baseq2, map)?;
+        let cm = Arc::new(CollisionModel::from_bsp(&bsp));
+        let bounds = world_bounds(&bsp, map)?;
+        let fp = Fingerprint::from_bsp(&bsp, spacing);
+
+        let (serial, serial_t) =
+            timed(|| serial_pool.install(|| NavGraph::generate(&cm, bounds, spacing)));
+        let (grid, par_t) = timed(|| NavGraph::generate(&cm, bounds, spacing));
+        let grid_bytes = mapcache::to_bytes(&grid, &fp);
+
+        // Rebuild the tile under the world's centre against the same geometry: the result
+        // must match the full grid exactly.
+        let centre = [0, 1].map(|a| ((bounds.0[a] + bounds.1[a]) / 2.0 / spacing).round() as i32);
+        let tile = (
+            centre[0].div_euclid(TILE_COLUMNS),
+            centre[1].div_euclid(TILE_COLUMNS),
+        );
+        let (rebuilt, tile_t) = timed(|| grid.regenerate_tiles(&cm, bounds, spacing, &[tile]));
+
+        let nodes = grid.node_count();
+        let (_, post_t) = timed(|| finish_map_nav(map, bsp, Arc::clone(&cm), grid));
+
+        let identical = mapcache::to_bytes(&serial, &fp) == grid_bytes
+            && mapcache::to_bytes(&rebuilt, &fp) == grid_bytes;
+        if !identical {
+            mismatches += 1;
+        }
+        println!(
+            "{:<8} {:>7} {:>10.0} {:>10.0} {:>6.1}x {:>10.0} {:>10.0} {:>9}",
+            map,
+            nodes,
+            ms(serial_t),
+            ms(par_t),
+            serial_t.as_secs_f64() / par_t.as_secs_f64().max(1e-9),
+            ms(post_t),
+            ms(tile_t),
+            if identical { "yes" } else { "NO" }
+        );
+    }
+    if mismatches > 0 {
+        eprintln!("[navbench] {mismatches} map(s) produced non-identical output");
+        std::process::exit(1);
+    }
+    Ok(())
+}
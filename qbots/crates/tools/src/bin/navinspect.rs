@@ -24,6 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         eprintln!(
             "usage: navinspect <baseq2> <map> <x> <y> <z> [radius]\n\
              modes: navinspect <baseq2> <map> heightfield [cell_size]\n\
+             \x20      navinspect <baseq2> <map> export <out.obj> [cell_size]\n\
              \x20      navinspect <baseq2> <map> scan <x0> <y0> <x1> <y1> <zq> <step> <tz> [band]\n\
              e.g.   navinspect /srv/q2/baseq2 q2dm1 1519 567 472 160"
         );
@@ -224,6 +225,41 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // EXPORT mode: `navinspect <baseq2> <map> export <out.obj> [cell_size]`
+    // Writes the collision hull, nav nodes (by component), edges (by kind), the navmesh and
+    // spawn/item markers as OBJ + MTL (`world::export`) for any 3D viewer.
+    if args[3] == "export" {
+        let out = Path::new(args.get(4).ok_or("export needs <out.obj>")?);
+        let cell: f32 = args.get(5).and_then(|s| s.parse().ok()).unwrap_or(8.0);
+        let model = &built.bsp.models[0];
+        let bounds = (model.mins, model.maxs);
+        let params = world::VoxelParams {
+            cell_size: cell,
+            ..Default::default()
+        };
+        let mut hf = world::Heightfield::build(cm, bounds, params);
+        let drops = hf.find_drops(cm);
+        hf.erode(1);
+        let mut mesh = world::NavMesh::build(&hf, params.walkable_climb, Some(cm));
+        mesh.add_drops(&drops);
+        let scene = world::export::Scene {
+            cm,
+            bounds,
+            graph: Some(g),
+            mesh: Some(&mesh),
+            markers: world::export::markers_from_bsp(&built.bsp),
+        };
+        world::export::export_obj(out, &scene)?;
+        println!(
+            "wrote {} (+ .mtl): {} nodes, {} polys, {} markers",
+            out.display(),
+            g.node_count(),
+            mesh.polys.len(),
+            scene.markers.len()
+        );
+        return Ok(());
+    }
+
     // NAVQUERY mode: `navinspect <baseq2> <map> navquery <x> <y> <z> [cell]`
     // Builds the navmesh and reports what nearest_poly returns for the point + every poly whose
     // rectangle covers that cell column (their heights + components), so a z-level mismatch
@@ -0,0 +1,232 @@
+//! timeline — read a bot's decision timeline (`brain::timeline`) and show what it was thinking.
+//!
+//! Without `--html` it prints the run as spans of one headline each (the chosen alternative, or
+//! the brain's state label when it doesn't score):
+//!
+//! ```text
+//!    0.0s –   3.1s   32 ticks  roam
+//!    3.2s –   9.8s   67 ticks  grab@4
+//! ```
+//!
+//! `--html <out>` writes a self-contained page instead: a coloured strip of the spans, a slider
+//! (and ←/→) to scrub tick by tick, and for the tick under the cursor its state, target, goal,
+//! weapon, nav backend and every scored alternative as a bar. With a movement log (`--movement`,
+//! found automatically beside a scenario's `<ts>.<bot>.timeline.jsonl`) each tick also shows the
+//! recorder's speed and flags for the same serverframe.
+//!
+//! Usage:
+//!   cargo run -p tools --bin timeline -- <file.jsonl> [--movement <log>] [--html <out>]
+//!   cargo run -p tools --bin timeline -- logs/spawn-to-spawn/1760000000.qb.timeline.jsonl --html t.html
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use brain::timeline::Timeline;
+use serde::Serialize;
+
+/// What the movement log says about one serverframe.
+#[derive(Serialize)]
+struct Motion {
+    speed: f32,
+    flags: String,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("usage: timeline <file.jsonl> [--movement <log>] [--html <out>]");
+        std::process::exit(2);
+    }
+    let path = Path::new(&args[1]);
+    let flag = |name: &str| {
+        args.iter()
+            .position(|a| a == name)
+            .and_then(|i| args.get(i + 1))
+            .cloned()
+    };
+    let tl = Timeline::read(path)?;
+    let movement = flag("--movement")
+        .map(PathBuf::from)
+        .or_else(|| sibling_log(path).filter(|p| p.exists()));
+    let motion = match &movement {
+        Some(p) => read_movement(p)?,
+        None => HashMap::new(),
+    };
+
+    let Some(out) = flag("--html") else {
+        let h = &tl.header;
+        println!(
+            "{} · {} · {} on {} · {} ticks",
+            h.bot,
+            h.brain,
+            h.navmode,
+            h.map,
+            tl.ticks.len()
+        );
+        for s in tl.spans() {
+            let (a, b) = (&tl.ticks[s.first], &tl.ticks[s.last]);
+            println!(
+                "{:>7.1}s – {:>6.1}s {:>5} ticks  {}",
+                a.t,
+                b.t,
+                s.last - s.first + 1,
+                s.headline
+            );
+        }
+        return Ok(());
+    };
+    std::fs::write(&out, render_html(&tl, &motion))?;
+    eprintln!(
+        "[timeline] wrote {out} ({} ticks{})",
+        tl.ticks.len(),
+        movement.map_or(String::new(), |m| format!(
+            ", movement from {}",
+            m.display()
+        ))
+    );
+    Ok(())
+}
+
+/// A scenario writes `<ts>.<bot>.timeline.jsonl` beside its `<ts>.<bot>.log`.
+fn sibling_log(timeline: &Path) -> Option<PathBuf> {
+    let name = timeline.file_name()?.to_str()?;
+    let stem = name.strip_suffix(".timeline.jsonl")?;
+    Some(timeline.with_file_name(format!("{stem}.log")))
+}
+
+/// Frame rows of a movement log (`brain::recorder` schema), keyed by serverframe.
+fn read_movement(path: &Path) -> Result<HashMap<i32, Motion>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut rows = HashMap::new();
+    for line in text.lines().filter(|l| !l.starts_with('#')) {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        let (Some(frame), Some(speed), Some(flags)) = (cols.get(1), cols.get(8), cols.get(15))
+        else {
+            continue;
+        };
+        if let (Ok(frame), Ok(speed)) = (frame.parse(), speed.parse()) {
+            let flags = flags.to_string();
+            rows.insert(frame, Motion { speed, flags });
+        }
+    }
+    Ok(rows)
+}
+
+fn render_html(tl: &Timeline, motion: &HashMap<i32, Motion>) -> String {
+    let h = &tl.header;
+    let spans: Vec<_> = tl
+        .spans()
+        .into_iter()
+        .map(|s| (s.headline, s.first, s.last))
+        .collect();
+    let motion: Vec<Option<&Motion>> = tl.ticks.iter().map(|t| motion.get(&t.frame)).collect();
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>timeline {bot} {map}</title>\
+         <style>body{{font-family:sans-serif;margin:2em}}#strip{{display:flex;height:28px;\
+         cursor:pointer;margin:1em 0}}#strip div{{height:100%}}#strip div:hover{{opacity:.7}}\
+         #cursor{{position:relative;height:0}}#cursor div{{position:absolute;top:-36px;width:2px;\
+         height:36px;background:#000}}input[type=range]{{width:100%}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:2px 6px;text-align:left}}th{{background:#eee}}\
+         .bar{{background:#39f;height:10px}}.chosen{{font-weight:bold}}#panes{{display:flex;gap:3em}}\
+         </style></head><body><h1>{bot} · {brain} · {navmode} on {map}</h1>\
+         <div id=\"strip\"></div><div id=\"cursor\"><div></div></div>\
+         <input type=\"range\" id=\"scrub\" min=\"0\" max=\"{last}\" value=\"0\">\
+         <div id=\"panes\"><table id=\"tick\"></table><table id=\"options\"></table></div>",
+        bot = escape(&h.bot),
+        brain = escape(&h.brain),
+        navmode = escape(&h.navmode),
+        map = escape(&h.map),
+        last = tl.ticks.len().saturating_sub(1),
+    );
+    let _ = write!(
+        out,
+        "<script>const ticks={ticks};const spans={spans};const motion={motion};{SCRIPT}</script>\
+         </body></html>",
+        ticks = data(&tl.ticks),
+        spans = data(&spans),
+        motion = data(&motion),
+    );
+    out
+}
+
+/// `s` safe to place in HTML text or a quoted attribute.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// `v` as a JS literal; `</` is escaped so a label can't close the script block early.
+fn data<T: Serialize>(v: &T) -> String {
+    serde_json::to_string(v)
+        .unwrap_or_else(|_| "null".into())
+        .replace("</", "<\\/")
+}
+
+/// The page's behaviour: draw the span strip, then show whichever tick the slider is on.
+const SCRIPT: &str = r#"
+const scrub = document.getElementById('scrub');
+const n = Math.max(ticks.length, 1);
+const hue = s => { let h = 0; for (const c of s.split(/[@#:]/)[0]) h = (h * 31 + c.charCodeAt(0)) % 360; return h; };
+const strip = document.getElementById('strip');
+for (const [head, first, last] of spans) {
+  const d = document.createElement('div');
+  d.style.width = (100 * (last - first + 1) / n) + '%';
+  d.style.background = `hsl(${hue(head)},60%,60%)`;
+  d.title = `${head}  ${ticks[first].t.toFixed(1)}s – ${ticks[last].t.toFixed(1)}s`;
+  d.onclick = () => { scrub.value = first; show(); };
+  strip.appendChild(d);
+}
+const fmt = v => Array.isArray(v) ? v.map(x => Math.round(x)).join(' ') : (v ?? '—');
+// Every cell is set through textContent: states and labels come from the log, not from us.
+const cell = (tag, text) => { const c = document.createElement(tag); c.textContent = text; return c; };
+const row = (...cells) => { const r = document.createElement('tr'); r.append(...cells); return r; };
+function show() {
+  const k = +scrub.value, t = ticks[k];
+  if (!t) return;
+  document.querySelector('#cursor div').style.left = (100 * (k + 0.5) / n) + '%';
+  const m = motion[k];
+  const rows = [
+    ['t', t.t.toFixed(2) + ' s'], ['frame', t.frame], ['state', t.state],
+    ['pos', fmt(t.pos)], ['health', t.health], ['target', fmt(t.target)], ['goal', fmt(t.goal)],
+    ['weapon', fmt(t.weapon) + (t.switch_to ? ' → ' + t.switch_to : '')], ['fire', t.fire ? 'yes' : ''],
+    ['nav', t.nav], ['waypoint', fmt(t.waypoint)],
+  ];
+  if (m) rows.push(['speed', Math.round(m.speed)], ['flags', m.flags]);
+  document.getElementById('tick').replaceChildren(
+    ...rows.map(([a, b]) => row(cell('th', a), cell('td', b))));
+  const opts = t.options || [];
+  const top = Math.max(...opts.map(o => o.score), 1e-6);
+  const table = document.getElementById('options');
+  if (!opts.length) {
+    table.replaceChildren(row(cell('td', 'no scored alternatives')));
+    return;
+  }
+  table.replaceChildren(row(cell('th', 'alternative'), cell('th', 'score'), cell('th', '')),
+    ...opts.map(o => {
+      const bar = document.createElement('div');
+      bar.className = 'bar';
+      bar.style.width = (100 * o.score / top) + '%';
+      const barCell = cell('td', '');
+      barCell.style.width = '200px';
+      barCell.appendChild(bar);
+      const r = row(cell('td', o.label), cell('td', o.score.toFixed(3)), barCell);
+      if (o.chosen) r.className = 'chosen';
+      return r;
+    }));
+}
+scrub.oninput = show;
+document.onkeydown = e => {
+  if (e.key === 'ArrowRight') scrub.value = +scrub.value + 1;
+  else if (e.key === 'ArrowLeft') scrub.value = +scrub.value - 1;
+  else return;
+  show();
+};
+show();
+"#;
@@ -0,0 +1,159 @@
+//! Long-range risk-weighted planning: flat `path_weighted` A* against the shared cluster
+//! hierarchy (`HierGraph::path`) on the same far node pairs and heat overlay, plus the
+//! one-off hierarchy build.
+//!
+//! Runs on q2dm1 when its pak is available (`QBOTS_BASEQ2` or `vendor/baseq2`); otherwise on
+//! a synthetic 2048² pillar arena compiled into a temporary pak, so the bench still runs
+//! without id game data.
+//!
+//! `cargo bench -p world --bench hier`
+
+use std::path::PathBuf;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use world::testmap::BspBuilder;
+use world::{generate_map_nav, HierGraph, NavGraph, PakWriter, GRID_SPACING};
+
+/// Pairs benched per iteration; each is at least `MIN_SPAN` units apart.
+const PAIRS: usize = 32;
+const MIN_SPAN: f32 = 1200.0;
+
+fn baseq2_dir() -> Option<PathBuf> {
+    if let Ok(p) = std::env::var("QBOTS_BASEQ2") {
+        let pb = PathBuf::from(p);
+        return pb.join("pak0.pak").exists().then_some(pb);
+    }
+    let pb = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../vendor/baseq2");
+    pb.join("pak0.pak").exists().then_some(pb)
+}
+
+/// A 2048² arena with a 7×7 grid of pillars and a ceiling.
+fn arena() -> Vec<u8> {
+    let mut b = BspBuilder::new();
+    b.solid([-1040.0, -1040.0, -16.0], [1040.0, 1040.0, 0.0])
+        .solid([-1040.0, -1040.0, 384.0], [1040.0, 1040.0, 400.0])
+        .solid([-1040.0, -1040.0, 0.0], [-1024.0, 1040.0, 384.0])
+        .solid([1024.0, -1040.0, 0.0], [1040.0, 1040.0, 384.0])
+        .solid([-1024.0, -1040.0, 0.0], [1024.0, -1024.0, 384.0])
+        .solid([-1024.0, 1024.0, 0.0], [1024.0, 1040.0, 384.0])
+        .spawn([-900.0, -900.0, 24.0]);
+    for i in -3..=3 {
+        for j in -3..=3 {
+            let (x, y) = (i as f32 * 256.0 + 64.0, j as f32 * 256.0 + 64.0);
+            b.solid([x, y, 0.0], [x + 48.0, y + 48.0, 384.0]);
+        }
+    }
+    b.to_bytes()
+}
+
+fn load_graph() -> (String, NavGraph) {
+    if let Some(baseq2) = baseq2_dir() {
+        let built = generate_map_nav(&baseq2, "q2dm1", GRID_SPACING).expect("build q2dm1 nav");
+        return ("q2dm1".into(), built.graph);
+    }
+    eprintln!("q2dm1 pak not found (set QBOTS_BASEQ2); benching the synthetic arena");
+    let tmp = tempfile::tempdir().unwrap();
+    PakWriter::new()
+        .add("maps/arena.bsp", &arena())
+        .write(&tmp.path().join("pak0.pak"))
+        .unwrap();
+    let built = generate_map_nav(tmp.path(), "arena", GRID_SPACING).expect("build arena nav");
+    ("arena".into(), built.graph)
+}
+
+/// Deterministic far pairs that flat A* can connect.
+fn far_pairs(g: &NavGraph) -> Vec<(usize, usize)> {
+    let n = g.node_count();
+    let mut s: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = || {
+        s ^= s << 13;
+        s ^= s >> 7;
+        s ^= s << 17;
+        (s % n as u64) as usize
+    };
+    let span = |a: usize, b: usize| {
+        let (p, q) = (g.node_pos(a), g.node_pos(b));
+        ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2) + (p[2] - q[2]).powi(2)).sqrt()
+    };
+    let mut out = Vec::new();
+    for _ in 0..100_000 {
+        let (a, b) = (next(), next());
+        if span(a, b) >= MIN_SPAN && g.path(a, b).is_some() {
+            out.push((a, b));
+            if out.len() == PAIRS {
+                break;
+            }
+        }
+    }
+    out
+}
+
+/// Heat blobs on every 16th node's neighbourhood, fading with distance like a decayed
+/// heatmap — most nodes stay cold.
+fn heat_overlay(g: &NavGraph) -> Vec<f32> {
+    let hot: Vec<[f32; 3]> = (0..g.node_count())
+        .step_by(16 * 16)
+        .map(|i| g.node_pos(i))
+        .collect();
+    (0..g.node_count())
+        .map(|i| {
+            let p = g.node_pos(i);
+            hot.iter()
+                .map(|h| {
+                    let d = ((p[0] - h[0]).powi(2) + (p[1] - h[1]).powi(2)).sqrt();
+                    (300.0 * (1.0 - d / 96.0)).max(0.0)
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn bench_hier(c: &mut Criterion) {
+    let (map, g) = load_graph();
+    let pairs = far_pairs(&g);
+    let overlay = heat_overlay(&g);
+    let hier = HierGraph::build(&g);
+    eprintln!(
+        "{map}: {} nodes, {} clusters, {} entrances, {} pairs",
+        g.node_count(),
+        hier.cluster_count(),
+        hier.entrance_count(),
+        pairs.len()
+    );
+
+    let mut group = c.benchmark_group(format!("hier/{map}"));
+    group.bench_function("flat_path_weighted", |b| {
+        b.iter(|| {
+            for &(a, z) in &pairs {
+                black_box(g.path_weighted(a, z, black_box(&overlay)));
+            }
+        })
+    });
+    group.bench_function("hier_path", |b| {
+        b.iter(|| {
+            for &(a, z) in &pairs {
+                black_box(hier.path(&g, a, z, black_box(&overlay)));
+            }
+        })
+    });
+    group.bench_function("flat_path_cold", |b| {
+        b.iter(|| {
+            for &(a, z) in &pairs {
+                black_box(g.path_weighted(a, z, &[]));
+            }
+        })
+    });
+    group.bench_function("hier_path_cold", |b| {
+        b.iter(|| {
+            for &(a, z) in &pairs {
+                black_box(hier.path(&g, a, z, &[]));
+            }
+        })
+    });
+    group.sample_size(10);
+    group.bench_function("build", |b| b.iter(|| HierGraph::build(black_box(&g))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_hier);
+criterion_main!(benches);
@@ -0,0 +1,97 @@
+//! Collision trace throughput: single point/hull traces, and 32-ray fans (a full ring and
+//! a narrow cone) traced ray by ray vs through `trace_many`.
+//!
+//! `cargo bench -p world --bench trace`
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use world::collision::MASK_SOLID;
+use world::testmap::BspBuilder;
+use world::CollisionModel;
+
+const HULL_MINS: [f32; 3] = [-16.0, -16.0, -24.0];
+const HULL_MAXS: [f32; 3] = [16.0, 16.0, 32.0];
+
+/// A 2048² arena with a 7×7 grid of pillars and a ceiling: a few hundred leafs.
+fn arena() -> CollisionModel {
+    let mut b = BspBuilder::new();
+    b.solid([-1024.0, -1024.0, -16.0], [1024.0, 1024.0, 0.0])
+        .solid([-1024.0, -1024.0, 384.0], [1024.0, 1024.0, 400.0]);
+    for i in -3..=3 {
+        for j in -3..=3 {
+            let (x, y) = (i as f32 * 256.0 + 64.0, j as f32 * 256.0 + 64.0);
+            b.solid(
+                [x, y, 0.0],
+                [x + 48.0, y + 48.0, 64.0 + (i + j + 6) as f32 * 32.0],
+            );
+        }
+    }
+    CollisionModel::from_bsp(&b.build())
+}
+
+/// `n` rays of length `len` from `start`, spread evenly over `arc` radians.
+fn fan(start: [f32; 3], n: usize, arc: f32, len: f32) -> Vec<[f32; 3]> {
+    (0..n)
+        .map(|i| {
+            let a = i as f32 * arc / n as f32;
+            [start[0] + a.cos() * len, start[1] + a.sin() * len, start[2]]
+        })
+        .collect()
+}
+
+fn bench_trace(c: &mut Criterion) {
+    let cm = arena();
+    let start = [0.0, 0.0, 24.0];
+    let end = [900.0, 700.0, 24.0];
+    c.bench_function("trace/point", |b| {
+        b.iter(|| {
+            cm.trace(
+                black_box(&start),
+                black_box(&end),
+                &[0.0; 3],
+                &[0.0; 3],
+                MASK_SOLID,
+            )
+        })
+    });
+    c.bench_function("trace/hull", |b| {
+        b.iter(|| {
+            cm.trace(
+                black_box(&start),
+                black_box(&end),
+                &HULL_MINS,
+                &HULL_MAXS,
+                MASK_SOLID,
+            )
+        })
+    });
+
+    let tau = std::f32::consts::TAU;
+    for (name, n, arc, len) in [
+        ("ring32", 32, tau, 900.0),
+        ("cone32", 32, 0.5, 900.0),
+        ("probe16", 16, tau, 256.0),
+    ] {
+        let ends = fan(start, n, arc, len);
+        c.bench_function(&format!("{name}/single"), |b| {
+            b.iter(|| {
+                ends.iter()
+                    .map(|e| {
+                        cm.trace(&start, e, &HULL_MINS, &HULL_MAXS, MASK_SOLID)
+                            .fraction
+                    })
+                    .sum::<f32>()
+            })
+        });
+        c.bench_function(&format!("{name}/trace_many"), |b| {
+            b.iter(|| {
+                cm.trace_many(&start, black_box(&ends), &HULL_MINS, &HULL_MAXS, MASK_SOLID)
+                    .iter()
+                    .map(|t| t.fraction)
+                    .sum::<f32>()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, bench_trace);
+criterion_main!(benches);
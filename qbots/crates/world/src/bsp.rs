@@ -2,13 +2,14 @@
 //!
 //! Parses the header + the collision-relevant lumps (planes/nodes/leafs/brushes/
 //! brushsides/leafbrushes/models) into typed arrays. Visibility/areas/nav land in T2–T4.
+//! The face/texinfo/lighting lumps are kept too, for lightmap sampling ([`crate::light`]).
 
 use std::collections::HashMap;
 use std::path::Path;
 
 use q2proto::{DecodeError, Reader};
 
-use crate::pak::Pak;
+use crate::vfs::Vfs;
 
 pub const BSP_VERSION: i32 = 38;
 /// `HEADER_LUMPS` (`files.h:292`).
@@ -17,10 +18,16 @@ pub const NUM_LUMPS: usize = 19;
 // Lump indices (`files.h:273`).
 const LUMP_ENTITIES: usize = 0;
 const LUMP_PLANES: usize = 1;
+const LUMP_VERTEXES: usize = 2;
 const LUMP_VISIBILITY: usize = 3;
 const LUMP_NODES: usize = 4;
+const LUMP_TEXINFO: usize = 5;
+const LUMP_FACES: usize = 6;
+const LUMP_LIGHTING: usize = 7;
 const LUMP_LEAFS: usize = 8;
 const LUMP_LEAFBRUSHES: usize = 10;
+const LUMP_EDGES: usize = 11;
+const LUMP_SURFEDGES: usize = 12;
 const LUMP_MODELS: usize = 13;
 const LUMP_BRUSHES: usize = 14;
 const LUMP_BRUSHSIDES: usize = 15;
@@ -51,7 +58,7 @@ pub struct Plane {
     pub typ: i32,
 }
 
-/// `dnode_t` (`files.h:381`) — faces dropped (renderer-only).
+/// `dnode_t` (`files.h:381`).
 #[derive(Debug, Clone, Copy)]
 pub struct Node {
     pub planenum: i32,
@@ -59,6 +66,34 @@ pub struct Node {
     pub children: [i32; 2],
     pub mins: [i16; 3],
     pub maxs: [i16; 3],
+    /// Faces lying on this node's plane (`firstface`/`numfaces`) — what `R_LightPoint`
+    /// samples where a trace crosses the node.
+    pub firstface: u16,
+    pub numfaces: u16,
+}
+
+/// `texinfo_t` (`files.h:398`) — texture name/value/chain dropped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TexInfo {
+    /// `[s, t]` texture axes: `s = dot(p, vecs[0][..3]) + vecs[0][3]`.
+    pub vecs: [[f32; 4]; 2],
+    /// `SURF_*` flags (`q_shared.h:386`).
+    pub flags: i32,
+}
+
+/// `dface_t` (`files.h:408`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Face {
+    pub planenum: u16,
+    pub side: i16,
+    /// Into [`Bsp::surfedges`]; negative surfedges walk the edge backwards.
+    pub firstedge: i32,
+    pub numedges: i16,
+    pub texinfo: i16,
+    /// Light styles baked into this face's lightmaps; 255 ends the list.
+    pub styles: [u8; 4],
+    /// Byte offset into [`Bsp::lighting`], or -1 for an unlit face.
+    pub lightofs: i32,
 }
 
 /// `dleaf_t` (`files.h:423`) — leaffaces dropped (renderer-only).
@@ -143,6 +178,14 @@ pub struct Bsp {
     pub vis: Vec<u8>,
     /// Parsed `LUMP_ENTITIES` text block → map entities (spawns, items, weapons).
     pub entities: Vec<BspEntity>,
+    pub vertexes: Vec<[f32; 3]>,
+    /// `dedge_t` vertex pairs.
+    pub edges: Vec<[u16; 2]>,
+    pub surfedges: Vec<i32>,
+    pub texinfo: Vec<TexInfo>,
+    pub faces: Vec<Face>,
+    /// Raw lighting lump: RGB lightmap samples, addressed by [`Face::lightofs`].
+    pub lighting: Vec<u8>,
 }
 
 impl Bsp {
@@ -193,6 +236,15 @@ impl Bsp {
         let vis = slice(LUMP_VISIBILITY).unwrap_or(&[]).to_vec();
         // `LUMP_ENTITIES` is a NUL-terminated text block; missing/empty → no entities.
         let entities = parse_entities(slice(LUMP_ENTITIES).unwrap_or(&[]));
+        // Render lumps: only lightmap sampling reads them, so a missing lump just means an
+        // unlit (or fullbright) map, never a load failure.
+        let render = |i: usize| slice(i).unwrap_or(&[]);
+        let vertexes = parse_vertexes(render(LUMP_VERTEXES)).map_err(|e| e.to_string())?;
+        let edges = parse_edges(render(LUMP_EDGES)).map_err(|e| e.to_string())?;
+        let surfedges = parse_surfedges(render(LUMP_SURFEDGES)).map_err(|e| e.to_string())?;
+        let texinfo = parse_texinfo(render(LUMP_TEXINFO)).map_err(|e| e.to_string())?;
+        let faces = parse_faces(render(LUMP_FACES)).map_err(|e| e.to_string())?;
+        let lighting = render(LUMP_LIGHTING).to_vec();
 
         Ok(Self {
             version,
@@ -205,29 +257,22 @@ impl Bsp {
             models,
             vis,
             entities,
+            vertexes,
+            edges,
+            surfedges,
+            texinfo,
+            faces,
+            lighting,
         })
     }
 
     /// Locate `<baseq2>/maps/<map>.bsp` — loose, or inside `pak*.pak` — and parse it.
     pub fn load(baseq2: &Path, map: &str) -> Result<Self, String> {
         let name = format!("maps/{map}.bsp");
-        let loose = baseq2.join(&name);
-        if loose.exists() {
-            let data =
-                std::fs::read(&loose).map_err(|e| format!("read {}: {e}", loose.display()))?;
+        // Engine search order: paks (highest-numbered first) shadow loose files.
+        if let Some(data) = Vfs::open(baseq2)?.read(&name) {
             return Self::from_bytes(&data);
         }
-        // Stock DM maps live in pak1; search pak0..pak9 ascending.
-        for n in 0..=9 {
-            let pakpath = baseq2.join(format!("pak{n}.pak"));
-            if !pakpath.exists() {
-                continue;
-            }
-            let pak = Pak::open(&pakpath)?;
-            if let Some(data) = pak.read(&name) {
-                return Self::from_bytes(data);
-            }
-        }
         Err(format!(
             "map '{map}' not found loose or in any pak under {}",
             baseq2.display()
@@ -362,12 +407,15 @@ fn parse_nodes(buf: &[u8]) -> Result<Vec<Node>, DecodeError> {
         let children = [r.read_i32()?, r.read_i32()?];
         let mins = read_i16_3(&mut r)?;
         let maxs = read_i16_3(&mut r)?;
-        r.skip(4)?; // firstface + numface (u16 each)
+        let firstface = r.read_u16()?;
+        let numfaces = r.read_u16()?;
         out.push(Node {
             planenum,
             children,
             mins,
             maxs,
+            firstface,
+            numfaces,
         });
     }
     Ok(out)
@@ -463,6 +511,67 @@ fn parse_models(buf: &[u8]) -> Result<Vec<Model>, DecodeError> {
     Ok(out)
 }
 
+fn parse_vertexes(buf: &[u8]) -> Result<Vec<[f32; 3]>, DecodeError> {
+    let mut r = Reader::new(buf);
+    let mut out = Vec::with_capacity(buf.len() / 12);
+    while r.remaining() >= 12 {
+        out.push(read_f3(&mut r)?);
+    }
+    Ok(out)
+}
+
+fn parse_edges(buf: &[u8]) -> Result<Vec<[u16; 2]>, DecodeError> {
+    let mut r = Reader::new(buf);
+    let mut out = Vec::with_capacity(buf.len() / 4);
+    while r.remaining() >= 4 {
+        out.push([r.read_u16()?, r.read_u16()?]);
+    }
+    Ok(out)
+}
+
+fn parse_surfedges(buf: &[u8]) -> Result<Vec<i32>, DecodeError> {
+    let mut r = Reader::new(buf);
+    let mut out = Vec::with_capacity(buf.len() / 4);
+    while r.remaining() >= 4 {
+        out.push(r.read_i32()?);
+    }
+    Ok(out)
+}
+
+fn parse_texinfo(buf: &[u8]) -> Result<Vec<TexInfo>, DecodeError> {
+    const SIZE: usize = 76; // vecs(32)+flags(4)+value(4)+texture(32)+nexttexinfo(4)
+    let mut r = Reader::new(buf);
+    let mut out = Vec::with_capacity(buf.len() / SIZE);
+    while r.remaining() >= SIZE {
+        let mut vecs = [[0.0; 4]; 2];
+        for v in vecs.iter_mut().flatten() {
+            *v = r.read_f32()?;
+        }
+        let flags = r.read_i32()?;
+        r.skip(40)?; // value + texture + nexttexinfo
+        out.push(TexInfo { vecs, flags });
+    }
+    Ok(out)
+}
+
+fn parse_faces(buf: &[u8]) -> Result<Vec<Face>, DecodeError> {
+    const SIZE: usize = 20;
+    let mut r = Reader::new(buf);
+    let mut out = Vec::with_capacity(buf.len() / SIZE);
+    while r.remaining() >= SIZE {
+        out.push(Face {
+            planenum: r.read_u16()?,
+            side: r.read_i16()?,
+            firstedge: r.read_i32()?,
+            numedges: r.read_i16()?,
+            texinfo: r.read_i16()?,
+            styles: [r.read_u8()?, r.read_u8()?, r.read_u8()?, r.read_u8()?],
+            lightofs: r.read_i32()?,
+        });
+    }
+    Ok(out)
+}
+
 fn read_f3(r: &mut Reader) -> Result<[f32; 3], DecodeError> {
     Ok([r.read_f32()?, r.read_f32()?, r.read_f32()?])
 }
@@ -9,9 +9,11 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::bsp::{Bsp, BspEntity};
-use crate::collision::CollisionModel;
+use crate::collision::{CollisionModel, CONTENTS_LADDER};
+use crate::cover::VisTable;
 use crate::mapcache::{self, Fingerprint};
 use crate::navgraph::NavGraph;
+use crate::vis::Pvs;
 
 /// Grid spacing (units) for `NavGraph::generate`'s waypoint sampling.
 pub const GRID_SPACING: f32 = 24.0;
@@ -128,18 +130,32 @@ pub fn cached_map_nav(
 pub fn generate_map_nav(baseq2: &Path, map: &str, spacing: f32) -> Result<MapNavBuild, String> {
     let bsp = Bsp::load(baseq2, map)?;
     let cm = Arc::new(CollisionModel::from_bsp(&bsp));
+    let bounds = world_bounds(&bsp, map)?;
+    let grid = NavGraph::generate(&cm, bounds, spacing);
+    Ok(finish_map_nav(map, bsp, cm, grid))
+}
 
-    let bounds = {
-        let model = bsp
-            .models
-            .first()
-            .ok_or_else(|| format!("BSP for '{map}' has no models"))?;
-        (model.mins, model.maxs)
-    };
+/// The world model's bounds — the box `NavGraph::generate` samples.
+pub fn world_bounds(bsp: &Bsp, map: &str) -> Result<([f32; 3], [f32; 3]), String> {
+    let model = bsp
+        .models
+        .first()
+        .ok_or_else(|| format!("BSP for '{map}' has no models"))?;
+    Ok((model.mins, model.maxs))
+}
 
+/// Every pass of [`generate_map_nav`] after grid sampling: spawn seeding, lifts, trains,
+/// ladders, bridging, the false-edge prune, teleporters, jumps, rocket jumps and the
+/// visibility table. `graph` is a bare grid from `NavGraph::generate` — or from
+/// `NavGraph::regenerate_tiles`, so an editor loop re-probes only the tiles it touched.
+pub fn finish_map_nav(
+    map: &str,
+    bsp: Bsp,
+    cm: Arc<CollisionModel>,
+    mut graph: NavGraph,
+) -> MapNavBuild {
     let spawn_origins: Vec<[f32; 3]> = bsp.spawn_points().iter().map(|s| s.origin).collect();
 
-    let mut graph = NavGraph::generate(&cm, bounds, spacing);
     let seeded = graph.seed_spawns(&cm, &spawn_origins);
     add_elevator_edges(&mut graph, &cm, &bsp);
     add_train_edges(&mut graph, &cm, &bsp);
@@ -188,10 +204,32 @@ pub fn generate_map_nav(baseq2: &Path, map: &str, spacing: f32) -> Result<MapNav
             "rescued stranded spawn components via deep jump-down links"
         );
     }
+    // Rocket-jump edges LAST: they live outside `adj`, so they neither affect the
+    // component/spawn analysis above nor face the prune. They exist only for bots holding a
+    // rocket launcher; `QBOTS_NO_ROCKET_JUMPS=1` skips the pass (diagnostic, like
+    // `QBOTS_NO_PRUNE`).
+    if std::env::var("QBOTS_NO_ROCKET_JUMPS").is_ok() {
+        tracing::warn!(
+            map,
+            "QBOTS_NO_ROCKET_JUMPS set — skipping rocket-jump edges (diagnostic)"
+        );
+    } else {
+        let rocket_jumps = graph.detect_rocket_jump_edges(&cm);
+        tracing::info!(map, rocket_jumps, "added rocket-jump edges");
+    }
+    // Node visibility + cover LAST: it indexes the final node set.
+    let pvs = Pvs::from_lump(bsp.vis.clone());
+    let vis = VisTable::build(&cm, &graph, pvs.as_ref());
+    let visible_pairs: usize = (0..vis.node_count())
+        .map(|i| vis.visible_from(i).count())
+        .sum::<usize>()
+        / 2;
+    tracing::info!(map, visible_pairs, "built node visibility table");
+    graph.set_vis_table(vis);
     let (in_largest, total_spawns) = graph.spawns_in_largest_component(&spawn_origins);
     let largest = graph.largest_spawn_component(&spawn_origins);
 
-    Ok(MapNavBuild {
+    MapNavBuild {
         bsp,
         cm,
         graph,
@@ -201,7 +239,7 @@ pub fn generate_map_nav(baseq2: &Path, map: &str, spacing: f32) -> Result<MapNav
         in_largest,
         total_spawns,
         largest,
-    })
+    }
 }
 
 /// Parse `func_plat` (elevator) entities from the BSP and add nav nodes at the
@@ -489,8 +527,6 @@ fn nearest_ground_3d(graph: &NavGraph, pos: [f32; 3], max_h: f32, max_dz: f32) -
     best
 }
 
-/// `CONTENTS_LADDER` (`files.h:368`) — a climbable brush volume.
-const CONTENTS_LADDER: i32 = 0x2000_0000;
 /// Horizontal radius (units) to find the floor node adjacent to a ladder's base/top. In cache via VERSION.
 pub const LADDER_RADIUS: f32 = 96.0;
 /// Vertical tolerance (units) when matching a ladder's base/top to a floor node. In cache via VERSION.
@@ -917,6 +953,12 @@ mod tests {
             models: vec![],
             vis: vec![],
             entities,
+            vertexes: vec![],
+            edges: vec![],
+            surfedges: vec![],
+            texinfo: vec![],
+            faces: vec![],
+            lighting: vec![],
         }
     }
 
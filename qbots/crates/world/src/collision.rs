@@ -5,7 +5,7 @@
 //! "what does a swept box hit?". The trace sweeps via `CM_RecursiveHullCheck`, clipping
 //! against brushes in touched leafs (`CM_ClipBoxToBrush`).
 
-use std::collections::HashSet;
+use std::cell::RefCell;
 
 use crate::bsp::{Brush, Bsp};
 
@@ -18,6 +18,7 @@ pub const CONTENTS_WINDOW: i32 = 2;
 pub const CONTENTS_LAVA: i32 = 8;
 pub const CONTENTS_SLIME: i32 = 16;
 pub const CONTENTS_WATER: i32 = 32;
+pub const CONTENTS_LADDER: i32 = 0x2000_0000;
 /// Solid + window: the mask for "is this blocking movement?".
 pub const MASK_SOLID: i32 = CONTENTS_SOLID | CONTENTS_WINDOW;
 pub const MASK_WATER: i32 = CONTENTS_WATER | CONTENTS_LAVA | CONTENTS_SLIME;
@@ -84,6 +85,15 @@ pub struct Trace {
 }
 
 impl Trace {
+    /// Set `endpos` from the final fraction.
+    fn finish(&mut self, start: &[f32; 3], end: &[f32; 3]) {
+        self.endpos = if self.fraction == 1.0 {
+            *end
+        } else {
+            std::array::from_fn(|i| start[i] + self.fraction * (end[i] - start[i]))
+        };
+    }
+
     fn open(end: &[f32; 3]) -> Self {
         Self {
             allsolid: false,
@@ -252,8 +262,55 @@ impl CollisionModel {
         self.leafs[leaf].cluster
     }
 
+    /// Boundary polygons of every brush whose contents intersect `mask`, as
+    /// `(contents, winding)` — the visible hull for export/inspection, not used by traces.
+    /// Each side's plane starts as a huge quad (`BaseWindingForPlane`, `polylib.c`) and is
+    /// chopped by the brush's other sides plus the `bounds` box, which also caps the
+    /// unbounded half-space brushes of the test worlds. Windings wind counter-clockwise seen
+    /// from outside; bevel sides (zero area) are dropped.
+    pub fn brush_faces(
+        &self,
+        mask: i32,
+        bounds: ([f32; 3], [f32; 3]),
+    ) -> Vec<(i32, Vec<[f32; 3]>)> {
+        let (lo, hi) = bounds;
+        let boxp = [
+            ([1.0, 0.0, 0.0], hi[0]),
+            ([-1.0, 0.0, 0.0], -lo[0]),
+            ([0.0, 1.0, 0.0], hi[1]),
+            ([0.0, -1.0, 0.0], -lo[1]),
+            ([0.0, 0.0, 1.0], hi[2]),
+            ([0.0, 0.0, -1.0], -lo[2]),
+        ];
+        let extent = (0..3).map(|k| hi[k] - lo[k]).fold(0.0f32, f32::max) * 2.0 + 1024.0;
+        let mut out = Vec::new();
+        for brush in self.brushes.iter().filter(|b| b.contents & mask != 0) {
+            let sides = &self.brushsides[brush.firstside..brush.firstside + brush.numsides];
+            for (i, side) in sides.iter().enumerate() {
+                let p = &self.planes[side.plane];
+                let mut w = base_winding(p.normal, p.dist, extent);
+                for (j, other) in sides.iter().enumerate() {
+                    if j != i && !w.is_empty() {
+                        let q = &self.planes[other.plane];
+                        w = chop_winding(&w, q.normal, q.dist);
+                    }
+                }
+                for &(n, d) in &boxp {
+                    w = chop_winding(&w, n, d);
+                }
+                if w.len() >= 3 && winding_area(&w) > 0.1 {
+                    out.push((brush.contents, w));
+                }
+            }
+        }
+        out
+    }
+
     /// `CM_BoxTrace` — sweep a box from `start` to `end` (mins/maxs relative to origin;
     /// both zero = a point trace) against brushes matching `mask`. Returns the impact.
+    ///
+    /// Allocation-free once the calling thread is warm: brushes are deduplicated with
+    /// per-thread `checkcount` stamps, as the engine does, not a per-call set.
     pub fn trace(
         &self,
         start: &[f32; 3],
@@ -262,57 +319,152 @@ impl CollisionModel {
         maxs: &[f32; 3],
         mask: i32,
     ) -> Trace {
-        let mut ctx = Ctx {
-            trace: Trace::open(end),
-            start: *start,
-            end: *end,
-            mins: *mins,
-            maxs: *maxs,
-            extents: [0.0; 3],
-            ispoint: false,
-            mask,
-            seen: HashSet::new(),
-        };
+        SCRATCH.with(|s| {
+            let mut s = s.borrow_mut();
+            let checkcount = s.begin(self.brushes.len());
+            let Scratch { stamps, leafs, .. } = &mut *s;
+            let sw = Sweep::new(start, end, mins, maxs, mask);
+            let mut trace = Trace::open(end);
+            let mut ctx = Ctx {
+                sw: &sw,
+                trace: &mut trace,
+                stamps,
+                checkcount,
+            };
 
-        if start == end {
-            // position test: gather touched leafs, test for "inside a brush".
-            let mut leafs = Vec::new();
-            let bbox = box_bounds(start, start, mins, maxs);
-            self.box_leafnums(self.headnode, &bbox, &mut leafs);
-            for &l in &leafs {
-                self.test_in_leaf(&mut ctx, l);
-                if ctx.trace.allsolid {
-                    break;
+            if start == end {
+                // position test: gather touched leafs, test for "inside a brush".
+                leafs.clear();
+                let bbox = box_bounds(start, start, mins, maxs);
+                self.box_leafnums(self.headnode, &bbox, leafs);
+                for &l in leafs.iter() {
+                    self.test_in_leaf(&mut ctx, l);
+                    if ctx.trace.allsolid {
+                        break;
+                    }
                 }
+                trace.endpos = *start;
+                return trace;
             }
-            ctx.trace.endpos = *start;
-            return ctx.trace;
-        }
 
-        // point vs box extents
-        ctx.ispoint = mins == &[0.0, 0.0, 0.0] && maxs == &[0.0, 0.0, 0.0];
-        for i in 0..3 {
-            ctx.extents[i] = (-mins[i]).max(maxs[i]);
-        }
-
-        self.recursive_hull_check(&mut ctx, self.headnode, 0.0, 1.0, start, end);
+            self.recursive_hull_check(&mut ctx, self.headnode, 0.0, 1.0, start, end);
+            trace.finish(start, end);
+            trace
+        })
+    }
 
-        // finalize endpos
-        if ctx.trace.fraction == 1.0 {
-            ctx.trace.endpos = *end;
-        } else {
-            let f = ctx.trace.fraction;
-            let mut ep = [0.0; 3];
+    /// [`trace`](Self::trace) for many rays sharing one `start` (a fan of probes, an
+    /// eye-to-many LOS sweep). The BSP is walked once, for the box around the whole bundle,
+    /// to gather the brushes any ray could touch; each ray then clips only the candidates
+    /// whose axial bounds its swept box crosses. Results match `trace` ray for ray, in
+    /// `ends` order (on an exact tie between two brushes the reported plane may differ).
+    ///
+    /// On the benchmark arena it is about 1.5× a loop over `trace` for a narrow cone or a
+    /// short probe fan, but ~20% slower for a full ring of long rays, whose box takes in most
+    /// of the map — loop over `trace` there (`cargo bench -p world --bench trace`).
+    pub fn trace_many(
+        &self,
+        start: &[f32; 3],
+        ends: &[[f32; 3]],
+        mins: &[f32; 3],
+        maxs: &[f32; 3],
+        mask: i32,
+    ) -> Vec<Trace> {
+        // A zero-length ray is a position test, the same for every such end.
+        let position = ends
+            .contains(start)
+            .then(|| self.trace(start, start, mins, maxs, mask));
+        let mut bbox = box_bounds(start, start, mins, maxs);
+        for end in ends {
+            let b = box_bounds(start, end, mins, maxs);
             for i in 0..3 {
-                ep[i] = start[i] + f * (end[i] - start[i]);
+                bbox.mins[i] = bbox.mins[i].min(b.mins[i]);
+                bbox.maxs[i] = bbox.maxs[i].max(b.maxs[i]);
             }
-            ctx.trace.endpos = ep;
         }
-        ctx.trace
+        SCRATCH.with(|s| {
+            let mut s = s.borrow_mut();
+            let checkcount = s.begin(self.brushes.len());
+            let Scratch {
+                stamps,
+                leafs,
+                candidates,
+                ..
+            } = &mut *s;
+            leafs.clear();
+            self.box_leafnums(self.headnode, &bbox, leafs);
+            candidates.clear();
+            for &l in leafs.iter() {
+                let leaf = &self.leafs[l];
+                if leaf.contents & mask == 0 {
+                    continue;
+                }
+                for k in 0..leaf.numleafbrushes as usize {
+                    let bi = self.leafbrushes[leaf.firstleafbrush as usize + k] as usize;
+                    if first_visit(stamps, checkcount, bi) && self.brushes[bi].contents & mask != 0
+                    {
+                        let bounds = self.brush_bounds(bi, mins, maxs);
+                        candidates.push((box_distance(start, &bounds), bi, bounds));
+                    }
+                }
+            }
+            // Nearest first: every ray shares `start`, so once a ray's hit is nearer than
+            // the next candidate's distance, no later candidate can shorten it.
+            candidates.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+            ends.iter()
+                .map(|end| {
+                    if let Some(position) = position.as_ref().filter(|_| start == end) {
+                        return position.clone();
+                    }
+                    let sw = Sweep::new(start, end, mins, maxs, mask);
+                    let mut trace = Trace::open(end);
+                    let len = dist(start, end);
+                    for (near, bi, bounds) in candidates.iter() {
+                        if *near > trace.fraction * len {
+                            break;
+                        }
+                        if segment_hits_box(start, end, bounds) {
+                            self.clip_box_to_brush(&sw, &mut trace, *bi);
+                            if trace.fraction == 0.0 {
+                                break;
+                            }
+                        }
+                    }
+                    trace.finish(start, end);
+                    trace
+                })
+                .collect()
+        })
     }
 
     // ---- internals ----
 
+    /// Axial bounds of `brush` grown by the box `mins`/`maxs` (plus the trace epsilon), so
+    /// a box whose origin stays outside them can't touch the brush. Brushes without an
+    /// axial side on some face stay unbounded there.
+    fn brush_bounds(&self, brush: usize, mins: &[f32; 3], maxs: &[f32; 3]) -> Aabb {
+        let b = &self.brushes[brush];
+        let mut bounds = Aabb {
+            mins: [f32::NEG_INFINITY; 3],
+            maxs: [f32::INFINITY; 3],
+        };
+        for side in &self.brushsides[b.firstside..b.firstside + b.numsides] {
+            let plane = &self.planes[side.plane];
+            if let Some(i) = (0..3).find(|&i| plane.normal[i].abs() == 1.0) {
+                if plane.normal[i] > 0.0 {
+                    bounds.maxs[i] = bounds.maxs[i].min(plane.dist);
+                } else {
+                    bounds.mins[i] = bounds.mins[i].max(-plane.dist);
+                }
+            }
+        }
+        for i in 0..3 {
+            bounds.mins[i] -= maxs[i] + 1.0;
+            bounds.maxs[i] -= mins[i] - 1.0;
+        }
+        bounds
+    }
+
     /// `CM_PointLeafnum_r` — descend to the leaf containing `p`.
     fn point_leafnum(&self, p: &[f32; 3], mut num: i32) -> usize {
         while num >= 0 {
@@ -348,6 +500,19 @@ impl CollisionModel {
         }
     }
 
+    /// How far the swept box reaches across `plane` (`offset` in `CM_RecursiveHullCheck`).
+    fn plane_offset(plane: &Plane, sw: &Sweep) -> f32 {
+        if plane.typ < 3 {
+            sw.extents[plane.typ as usize]
+        } else if sw.ispoint {
+            0.0
+        } else {
+            sw.extents[0].abs() * plane.normal[0].abs()
+                + sw.extents[1].abs() * plane.normal[1].abs()
+                + sw.extents[2].abs() * plane.normal[2].abs()
+        }
+    }
+
     /// `CM_RecursiveHullCheck`.
     fn recursive_hull_check(
         &self,
@@ -365,85 +530,44 @@ impl CollisionModel {
             self.trace_to_leaf(ctx, (-1 - num) as usize);
             return;
         }
-
         let node = &self.nodes[num as usize];
         let plane = &self.planes[node.plane];
-        let (t1, t2, offset) = if plane.typ < 3 {
-            let ax = plane.typ as usize;
-            (p1[ax] - plane.dist, p2[ax] - plane.dist, ctx.extents[ax])
-        } else {
-            let t1 = plane.dist_to(p1);
-            let t2 = plane.dist_to(p2);
-            let off = if ctx.ispoint {
-                0.0
-            } else {
-                ctx.extents[0].abs() * plane.normal[0].abs()
-                    + ctx.extents[1].abs() * plane.normal[1].abs()
-                    + ctx.extents[2].abs() * plane.normal[2].abs()
-            };
-            (t1, t2, off)
-        };
-
-        if t1 >= offset && t2 >= offset {
-            self.recursive_hull_check(ctx, node.children[0], p1f, p2f, p1, p2);
-            return;
-        }
-        if t1 < -offset && t2 < -offset {
-            self.recursive_hull_check(ctx, node.children[1], p1f, p2f, p1, p2);
-            return;
-        }
-
-        // crosses the plane — split into near/far segments
-        let (side, frac, frac2) = if t1 < t2 {
-            let idist = 1.0 / (t1 - t2);
-            (
-                1,
-                (t1 - offset + DIST_EPSILON) * idist,
-                (t1 + offset + DIST_EPSILON) * idist,
-            )
-        } else if t1 > t2 {
-            let idist = 1.0 / (t1 - t2);
-            (
-                0,
-                (t1 + offset + DIST_EPSILON) * idist,
-                (t1 - offset - DIST_EPSILON) * idist,
-            )
-        } else {
-            (0, 1.0, 0.0)
-        };
-
-        let frac = frac.clamp(0.0, 1.0);
-        let mut midf = p1f + (p2f - p1f) * frac;
-        let mut mid = [0.0f32; 3];
-        for i in 0..3 {
-            mid[i] = p1[i] + frac * (p2[i] - p1[i]);
-        }
-        self.recursive_hull_check(ctx, node.children[side], p1f, midf, p1, &mid);
-
-        let frac2 = frac2.clamp(0.0, 1.0);
-        midf = p1f + (p2f - p1f) * frac2;
-        for i in 0..3 {
-            mid[i] = p1[i] + frac2 * (p2[i] - p1[i]);
+        let offset = Self::plane_offset(plane, ctx.sw);
+        match split_segment(
+            plane.dist_to(p1),
+            plane.dist_to(p2),
+            offset,
+            p1f,
+            p2f,
+            p1,
+            p2,
+        ) {
+            Split::Whole(side) => {
+                self.recursive_hull_check(ctx, node.children[side], p1f, p2f, p1, p2);
+            }
+            Split::Both { side, near, far } => {
+                self.recursive_hull_check(ctx, node.children[side], p1f, near.0, p1, &near.1);
+                self.recursive_hull_check(ctx, node.children[side ^ 1], far.0, p2f, &far.1, p2);
+            }
         }
-        self.recursive_hull_check(ctx, node.children[side ^ 1], midf, p2f, &mid, p2);
     }
 
     /// `CM_TraceToLeaf` — clip against each (matching, unseen) brush in the leaf.
     fn trace_to_leaf(&self, ctx: &mut Ctx, leaf: usize) {
         let leaf = &self.leafs[leaf];
-        if leaf.contents & ctx.mask == 0 {
+        if leaf.contents & ctx.sw.mask == 0 {
             return;
         }
         for k in 0..leaf.numleafbrushes as usize {
             let bi = self.leafbrushes[leaf.firstleafbrush as usize + k] as usize;
-            if !ctx.seen.insert(bi) {
+            if !first_visit(ctx.stamps, ctx.checkcount, bi) {
                 continue;
             }
             let b = &self.brushes[bi];
-            if b.contents & ctx.mask == 0 {
+            if b.contents & ctx.sw.mask == 0 {
                 continue;
             }
-            self.clip_box_to_brush(ctx, bi);
+            self.clip_box_to_brush(ctx.sw, ctx.trace, bi);
             if ctx.trace.fraction == 0.0 {
                 return;
             }
@@ -453,19 +577,19 @@ impl CollisionModel {
     /// `CM_TestInLeaf` — position-test variant (start==end).
     fn test_in_leaf(&self, ctx: &mut Ctx, leaf: usize) {
         let leaf = &self.leafs[leaf];
-        if leaf.contents & ctx.mask == 0 {
+        if leaf.contents & ctx.sw.mask == 0 {
             return;
         }
         for k in 0..leaf.numleafbrushes as usize {
             let bi = self.leafbrushes[leaf.firstleafbrush as usize + k] as usize;
-            if !ctx.seen.insert(bi) {
+            if !first_visit(ctx.stamps, ctx.checkcount, bi) {
                 continue;
             }
             let b = &self.brushes[bi];
-            if b.contents & ctx.mask == 0 {
+            if b.contents & ctx.sw.mask == 0 {
                 continue;
             }
-            self.test_box_in_brush(ctx, bi);
+            self.test_box_in_brush(ctx.sw, ctx.trace, bi);
             if ctx.trace.fraction == 0.0 {
                 return;
             }
@@ -473,7 +597,7 @@ impl CollisionModel {
     }
 
     /// `CM_ClipBoxToBrush` — sweep-clip the box against one brush's planes.
-    fn clip_box_to_brush(&self, ctx: &mut Ctx, brush: usize) {
+    fn clip_box_to_brush(&self, sw: &Sweep, trace: &mut Trace, brush: usize) {
         let b = &self.brushes[brush];
         if b.numsides == 0 {
             return;
@@ -490,14 +614,14 @@ impl CollisionModel {
             let plane = &self.planes[side.plane];
 
             // push the plane out by the box extents
-            let dist = if ctx.ispoint {
+            let dist = if sw.ispoint {
                 plane.dist
             } else {
                 let ofs: [f32; 3] = std::array::from_fn(|j| {
                     if plane.normal[j] < 0.0 {
-                        ctx.maxs[j]
+                        sw.maxs[j]
                     } else {
-                        ctx.mins[j]
+                        sw.mins[j]
                     }
                 });
                 plane.dist
@@ -506,13 +630,13 @@ impl CollisionModel {
                         + ofs[2] * plane.normal[2])
             };
 
-            let d1 = ctx.start[0] * plane.normal[0]
-                + ctx.start[1] * plane.normal[1]
-                + ctx.start[2] * plane.normal[2]
+            let d1 = sw.start[0] * plane.normal[0]
+                + sw.start[1] * plane.normal[1]
+                + sw.start[2] * plane.normal[2]
                 - dist;
-            let d2 = ctx.end[0] * plane.normal[0]
-                + ctx.end[1] * plane.normal[1]
-                + ctx.end[2] * plane.normal[2]
+            let d2 = sw.end[0] * plane.normal[0]
+                + sw.end[1] * plane.normal[1]
+                + sw.end[2] * plane.normal[2]
                 - dist;
 
             if d2 > 0.0 {
@@ -544,22 +668,22 @@ impl CollisionModel {
         }
 
         if !startout {
-            ctx.trace.startsolid = true;
+            trace.startsolid = true;
             if !getout {
-                ctx.trace.allsolid = true;
+                trace.allsolid = true;
             }
             return;
         }
-        if enterfrac < leavefrac && enterfrac > -1.0 && enterfrac < ctx.trace.fraction {
+        if enterfrac < leavefrac && enterfrac > -1.0 && enterfrac < trace.fraction {
             let enterfrac = enterfrac.max(0.0);
-            ctx.trace.fraction = enterfrac;
-            ctx.trace.plane = self.planes[clipplane.expect("clipplane set when enterfrac>prev")];
-            ctx.trace.contents = b.contents;
+            trace.fraction = enterfrac;
+            trace.plane = self.planes[clipplane.expect("clipplane set when enterfrac>prev")];
+            trace.contents = b.contents;
         }
     }
 
     /// `CM_TestBoxInBrush` — is the box (at `start`) entirely inside this brush?
-    fn test_box_in_brush(&self, ctx: &mut Ctx, brush: usize) {
+    fn test_box_in_brush(&self, sw: &Sweep, trace: &mut Trace, brush: usize) {
         let b = &self.brushes[brush];
         if b.numsides == 0 {
             return;
@@ -569,30 +693,30 @@ impl CollisionModel {
             let plane = &self.planes[side.plane];
             let ofs: [f32; 3] = std::array::from_fn(|j| {
                 if plane.normal[j] < 0.0 {
-                    ctx.maxs[j]
+                    sw.maxs[j]
                 } else {
-                    ctx.mins[j]
+                    sw.mins[j]
                 }
             });
             let dist = plane.dist
                 - (ofs[0] * plane.normal[0] + ofs[1] * plane.normal[1] + ofs[2] * plane.normal[2]);
-            let d1 = ctx.start[0] * plane.normal[0]
-                + ctx.start[1] * plane.normal[1]
-                + ctx.start[2] * plane.normal[2]
+            let d1 = sw.start[0] * plane.normal[0]
+                + sw.start[1] * plane.normal[1]
+                + sw.start[2] * plane.normal[2]
                 - dist;
             if d1 > 0.0 {
                 return; // in front of a face — not inside
             }
         }
-        ctx.trace.startsolid = true;
-        ctx.trace.allsolid = true;
-        ctx.trace.fraction = 0.0;
-        ctx.trace.contents = b.contents;
+        trace.startsolid = true;
+        trace.allsolid = true;
+        trace.fraction = 0.0;
+        trace.contents = b.contents;
     }
 }
 
-struct Ctx {
-    trace: Trace,
+/// The swept box of one ray.
+struct Sweep {
     start: [f32; 3],
     end: [f32; 3],
     mins: [f32; 3],
@@ -600,7 +724,128 @@ struct Ctx {
     extents: [f32; 3],
     ispoint: bool,
     mask: i32,
-    seen: HashSet<usize>,
+}
+
+impl Sweep {
+    fn new(start: &[f32; 3], end: &[f32; 3], mins: &[f32; 3], maxs: &[f32; 3], mask: i32) -> Self {
+        Self {
+            start: *start,
+            end: *end,
+            mins: *mins,
+            maxs: *maxs,
+            extents: std::array::from_fn(|i| (-mins[i]).max(maxs[i])),
+            ispoint: mins == &[0.0; 3] && maxs == &[0.0; 3],
+            mask,
+        }
+    }
+}
+
+struct Ctx<'a> {
+    sw: &'a Sweep,
+    trace: &'a mut Trace,
+    stamps: &'a mut [u32],
+    checkcount: u32,
+}
+
+/// Per-thread trace scratch, so a warm trace allocates nothing.
+#[derive(Default)]
+struct Scratch {
+    /// Per brush: the `checkcount` of the trace that last clipped it (`brush->checkcount`,
+    /// `CM_BoxTrace`).
+    stamps: Vec<u32>,
+    checkcount: u32,
+    leafs: Vec<usize>,
+    /// `trace_many`'s gathered brushes: distance from the shared start, index, grown bounds.
+    candidates: Vec<(f32, usize, Aabb)>,
+}
+
+impl Scratch {
+    /// Start a new trace over `brushes` brushes: a fresh `checkcount`, stamps wiped only
+    /// when the counter wraps.
+    fn begin(&mut self, brushes: usize) -> u32 {
+        if self.stamps.len() < brushes {
+            self.stamps.resize(brushes, 0);
+        }
+        self.checkcount = self.checkcount.wrapping_add(1);
+        if self.checkcount == 0 {
+            self.stamps.fill(0);
+            self.checkcount = 1;
+        }
+        self.checkcount
+    }
+}
+
+thread_local! {
+    static SCRATCH: RefCell<Scratch> = RefCell::default();
+}
+
+/// Stamp `brush` for trace `checkcount`; `false` if it was already stamped.
+#[inline]
+fn first_visit(stamps: &mut [u32], checkcount: u32, brush: usize) -> bool {
+    let fresh = stamps[brush] != checkcount;
+    stamps[brush] = checkcount;
+    fresh
+}
+
+/// How a segment meets a node plane: wholly on one side, or crossing it — a `near` piece
+/// ending and a `far` piece starting at (fraction, point), overlapping by the box offset.
+enum Split {
+    Whole(usize),
+    Both {
+        side: usize,
+        near: (f32, [f32; 3]),
+        far: (f32, [f32; 3]),
+    },
+}
+
+/// The plane test + split of `CM_RecursiveHullCheck`, given the ends' plane distances.
+#[inline]
+fn split_segment(
+    t1: f32,
+    t2: f32,
+    offset: f32,
+    p1f: f32,
+    p2f: f32,
+    p1: &[f32; 3],
+    p2: &[f32; 3],
+) -> Split {
+    if t1 >= offset && t2 >= offset {
+        return Split::Whole(0);
+    }
+    if t1 < -offset && t2 < -offset {
+        return Split::Whole(1);
+    }
+
+    // crosses the plane — split into near/far segments
+    let (side, frac, frac2) = if t1 < t2 {
+        let idist = 1.0 / (t1 - t2);
+        (
+            1,
+            (t1 - offset + DIST_EPSILON) * idist,
+            (t1 + offset + DIST_EPSILON) * idist,
+        )
+    } else if t1 > t2 {
+        let idist = 1.0 / (t1 - t2);
+        (
+            0,
+            (t1 + offset + DIST_EPSILON) * idist,
+            (t1 - offset - DIST_EPSILON) * idist,
+        )
+    } else {
+        (0, 1.0, 0.0)
+    };
+    let at = |f: f32| {
+        let f = f.clamp(0.0, 1.0);
+        (
+            p1f + (p2f - p1f) * f,
+            std::array::from_fn(|i| p1[i] + f * (p2[i] - p1[i])),
+        )
+    };
+    Split::Both {
+        side,
+        near: at(frac),
+        far: at(frac2),
+    }
 }
 
 /// An axis-aligned box (mins/maxs in world space).
@@ -619,6 +864,41 @@ fn box_bounds(start: &[f32; 3], end: &[f32; 3], mins: &[f32; 3], maxs: &[f32; 3]
     Aabb { mins: c1, maxs: c2 }
 }
 
+/// Distance from `p` to the nearest point of `b` (0 inside).
+fn box_distance(p: &[f32; 3], b: &Aabb) -> f32 {
+    let d: [f32; 3] = std::array::from_fn(|i| (b.mins[i] - p[i]).max(p[i] - b.maxs[i]).max(0.0));
+    (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+}
+
+fn dist(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    let d: [f32; 3] = std::array::from_fn(|i| b[i] - a[i]);
+    (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+}
+
+/// Slab test: does the segment `start → end` pass through `b`?
+fn segment_hits_box(start: &[f32; 3], end: &[f32; 3], b: &Aabb) -> bool {
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for i in 0..3 {
+        let d = end[i] - start[i];
+        if d == 0.0 {
+            if start[i] < b.mins[i] || start[i] > b.maxs[i] {
+                return false;
+            }
+            continue;
+        }
+        let (mut a, mut z) = ((b.mins[i] - start[i]) / d, (b.maxs[i] - start[i]) / d);
+        if a > z {
+            std::mem::swap(&mut a, &mut z);
+        }
+        t0 = t0.max(a);
+        t1 = t1.min(z);
+        if t0 > t1 {
+            return false;
+        }
+    }
+    true
+}
+
 /// `BoxOnPlaneSide` — returns 1 (front), 2 (back), 3 (both). Corners method (`shared.c:375`).
 fn box_on_plane_side(b: &Aabb, p: &Plane) -> i8 {
     let corners: [[f32; 3]; 2] = [
@@ -954,6 +1234,171 @@ pub fn closet_world(half_extent: f32) -> CollisionModel {
     }
 }
 
+/// `BaseWindingForPlane` (`polylib.c`): a square of half-size `extent` lying in the plane,
+/// wound counter-clockwise seen from the front (along `normal`).
+pub(crate) fn base_winding(normal: [f32; 3], dist: f32, extent: f32) -> Vec<[f32; 3]> {
+    let major = (0..3)
+        .max_by(|&a, &b| normal[a].abs().total_cmp(&normal[b].abs()))
+        .unwrap_or(2);
+    let mut up = if major == 2 {
+        [1.0, 0.0, 0.0]
+    } else {
+        [0.0, 0.0, 1.0]
+    };
+    let d = dot(up, normal);
+    for k in 0..3 {
+        up[k] -= normal[k] * d;
+    }
+    let len = dot(up, up).sqrt();
+    let right = cross(up, normal);
+    let org = [normal[0] * dist, normal[1] * dist, normal[2] * dist];
+    let at = |su: f32, sr: f32| {
+        [0, 1, 2].map(|k| org[k] + (up[k] / len * su + right[k] / len * sr) * extent)
+    };
+    vec![at(1.0, -1.0), at(-1.0, -1.0), at(-1.0, 1.0), at(1.0, 1.0)]
+}
+
+/// `ChopWindingInPlace` (`polylib.c`): keep the part of `w` behind the plane (inside the
+/// brush), Sutherland–Hodgman style.
+pub(crate) fn chop_winding(w: &[[f32; 3]], normal: [f32; 3], dist: f32) -> Vec<[f32; 3]> {
+    const ON_EPSILON: f32 = 0.1;
+    let mut out = Vec::with_capacity(w.len() + 1);
+    for (i, &a) in w.iter().enumerate() {
+        let b = w[(i + 1) % w.len()];
+        let (da, db) = (dot(a, normal) - dist, dot(b, normal) - dist);
+        if da <= ON_EPSILON {
+            out.push(a);
+        }
+        if (da > ON_EPSILON && db < -ON_EPSILON) || (da < -ON_EPSILON && db > ON_EPSILON) {
+            let t = da / (da - db);
+            out.push([0, 1, 2].map(|k| a[k] + (b[k] - a[k]) * t));
+        }
+    }
+    out
+}
+
+fn winding_area(w: &[[f32; 3]]) -> f32 {
+    let mut sum = [0.0f32; 3];
+    for i in 1..w.len() - 1 {
+        let e1 = [0, 1, 2].map(|k| w[i][k] - w[0][k]);
+        let e2 = [0, 1, 2].map(|k| w[i + 1][k] - w[0][k]);
+        let c = cross(e1, e2);
+        for k in 0..3 {
+            sum[k] += c[k];
+        }
+    }
+    dot(sum, sum).sqrt() * 0.5
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// A single-ledge world for rocket-jump tests: a solid floor for all `z < 0` and a solid block
+/// for `x ≥ 64` rising to `z = height`; air everywhere else. Floor nodes stand at origin
+/// `z = 24`, ledge-top nodes at `z = height + 24`. Test-support only — exposed (doc-hidden) so
+/// `brain::traverse` tests can reuse it.
+#[doc(hidden)]
+pub fn ledge_world(height: f32) -> CollisionModel {
+    let mk = |normal: [f32; 3], dist: f32, typ: i32| {
+        let sb = (0..3).fold(0u8, |b, j| if normal[j] < 0.0 { b | (1 << j) } else { b });
+        // Negative-facing axial planes take the general path (see `closet_world`).
+        let typ = if typ < 3 && normal[typ as usize] < 0.0 {
+            3
+        } else {
+            typ
+        };
+        Plane {
+            normal,
+            dist,
+            typ,
+            signbits: sb,
+        }
+    };
+    // P0: z=0 floor top, P1: x=64 block face (node split), P2: z=height block top,
+    // P3: x≥64 as an outward-facing brush side.
+    let planes = vec![
+        mk([0.0, 0.0, 1.0], 0.0, 2),
+        mk([1.0, 0.0, 0.0], 64.0, 0),
+        mk([0.0, 0.0, 1.0], height, 2),
+        mk([-1.0, 0.0, 0.0], -64.0, 0),
+    ];
+    // Leaf children encode as -(leaf+1): L0→-1 air, L1→-2 floor, L2→-3 block.
+    // N0 P0: front(z≥0)→N1, back(z<0)→L1 floor.
+    // N1 P1: front(x≥64)→N2, back(x<64)→L0 air.
+    // N2 P2: front(z≥height)→L0 air, back→L2 block.
+    let nodes = vec![
+        Node {
+            plane: 0,
+            children: [1, -2],
+        },
+        Node {
+            plane: 1,
+            children: [2, -1],
+        },
+        Node {
+            plane: 2,
+            children: [-1, -3],
+        },
+    ];
+    let leafs = vec![
+        Leaf {
+            contents: 0,
+            cluster: 0,
+            firstleafbrush: 0,
+            numleafbrushes: 0,
+        }, // L0 air
+        Leaf {
+            contents: CONTENTS_SOLID,
+            cluster: -1,
+            firstleafbrush: 0,
+            numleafbrushes: 1,
+        }, // L1 floor
+        Leaf {
+            contents: CONTENTS_SOLID,
+            cluster: -1,
+            firstleafbrush: 1,
+            numleafbrushes: 1,
+        }, // L2 block
+    ];
+    // Floor brush: z ≤ 0. Block brush: x ≥ 64 and z ≤ height.
+    let brushsides = vec![
+        BrushSide { plane: 0 },
+        BrushSide { plane: 3 },
+        BrushSide { plane: 2 },
+    ];
+    let brushes = vec![
+        BrushCol {
+            firstside: 0,
+            numsides: 1,
+            contents: CONTENTS_SOLID,
+        },
+        BrushCol {
+            firstside: 1,
+            numsides: 2,
+            contents: CONTENTS_SOLID,
+        },
+    ];
+    let leafbrushes = vec![0u16, 1u16];
+    CollisionModel {
+        planes,
+        nodes,
+        leafs,
+        brushes,
+        brushsides,
+        leafbrushes,
+        headnode: 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1198,4 +1643,61 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn brush_faces_outline_ledge_world() {
+        let w = ledge_world(64.0);
+        let faces = w.brush_faces(MASK_SOLID, ([-128.0, -128.0, -64.0], [256.0, 128.0, 128.0]));
+        // Floor top (z=0), block front (x=64) and block top (z=64).
+        assert_eq!(faces.len(), 3);
+        let normal = |f: &[[f32; 3]]| {
+            let e1 = [0, 1, 2].map(|k| f[1][k] - f[0][k]);
+            let e2 = [0, 1, 2].map(|k| f[2][k] - f[0][k]);
+            let c = cross(e1, e2);
+            let l = dot(c, c).sqrt();
+            c.map(|v| (v / l).round())
+        };
+        let normals: Vec<[f32; 3]> = faces.iter().map(|(_, f)| normal(f)).collect();
+        assert!(normals.contains(&[0.0, 0.0, 1.0]));
+        assert!(normals.contains(&[-1.0, 0.0, 0.0]));
+        let front = &faces[normals.iter().position(|n| n[0] < 0.0).unwrap()].1;
+        assert!(front.iter().all(|p| (p[0] - 64.0).abs() < 0.01));
+        assert!(front.iter().all(|p| p[2] >= -64.01 && p[2] <= 64.01));
+    }
+
+    #[test]
+    fn trace_many_matches_single_traces() {
+        let mut b = crate::testmap::BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [512.0, 512.0, 0.0])
+            .solid([-512.0, -512.0, 256.0], [512.0, 512.0, 272.0])
+            .solid([96.0, -40.0, 0.0], [160.0, 40.0, 256.0])
+            .solid([-200.0, 100.0, 0.0], [-120.0, 300.0, 48.0]);
+        let w = CollisionModel::from_bsp(&b.build());
+        let start = [0.0, 0.0, 24.0];
+        let ends: Vec<[f32; 3]> = (0..24)
+            .map(|i| {
+                let a = i as f32 * std::f32::consts::TAU / 24.0;
+                [
+                    a.cos() * 600.0,
+                    a.sin() * 600.0,
+                    24.0 + (i % 3) as f32 * 40.0 - 40.0,
+                ]
+            })
+            .chain([start])
+            .collect();
+        for (mins, maxs) in [
+            ([0.0; 3], [0.0; 3]),
+            ([-16.0, -16.0, -24.0], [16.0, 16.0, 32.0]),
+        ] {
+            let many = w.trace_many(&start, &ends, &mins, &maxs, MASK_SOLID);
+            for (end, t) in ends.iter().zip(&many) {
+                let one = w.trace(&start, end, &mins, &maxs, MASK_SOLID);
+                assert_eq!(
+                    (t.fraction, t.endpos, t.startsolid, t.allsolid),
+                    (one.fraction, one.endpos, one.startsolid, one.allsolid),
+                    "{end:?}"
+                );
+            }
+        }
+    }
 }
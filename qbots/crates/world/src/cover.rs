@@ -0,0 +1,430 @@
+//! Precomputed node-to-node visibility + per-node cover — "can node A see node B?" and
+//! "where is the nearest spot enemy X can't see?" answered by lookup instead of a trace.
+//!
+//! Built once per map alongside the nav graph and stored in the map cache. Node pairs within
+//! [`VIS_RANGE`] get one eye-to-eye point trace (eyes [`VIS_EYE_Z`] above the node origin,
+//! the same height `brain::los` traces from). Candidates come from a grid of
+//! [`VIS_RANGE`]-sized cells, so finding them never scans every pair, and each node keeps
+//! only its [`MAX_VIS_PAIRS`] nearest. A pair is traced if either end keeps it, so a dense
+//! open area costs at most `n · MAX_VIS_PAIRS` traces however big the map is. Nodes are also
+//! bucketed by PVS cluster (`CM_ClusterPVS`, `collision.c:1936`) and pairs in clusters that
+//! can't see each other are culled without a trace.
+//!
+//! Rows are stored as sorted `[start, end)` runs of node indices: generated nodes are
+//! numbered in grid-scan order, so what one node sees compresses to a few dozen runs.
+//! Pairs farther apart than [`VIS_RANGE`], or past both ends' nearest [`MAX_VIS_PAIRS`], are
+//! reported not visible — beyond them a bot's aim is poor enough that "unseen" is the useful
+//! answer. Nodes appended after the table was
+//! built (none in a cached graph) are outside it and also report not visible.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use rayon::prelude::*;
+
+use crate::collision::{CollisionModel, MASK_SOLID};
+use crate::navgraph::{FOrd, NavGraph};
+use crate::vis::Pvs;
+
+/// Max eye-to-eye distance (units) a pair is traced at. In the cache fingerprint.
+pub const VIS_RANGE: f32 = 1024.0;
+/// Eye height above a node origin — `brain::los::EYE_Z` (`pm_viewheight`).
+pub const VIS_EYE_Z: f32 = 22.0;
+/// Most in-range nodes one node pairs with (its nearest). Bounds the build to
+/// `O(n · MAX_VIS_PAIRS)` traces.
+pub const MAX_VIS_PAIRS: usize = 256;
+
+/// Node visibility runs + cover scores for one [`NavGraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisTable {
+    /// Per node: sorted, disjoint `[start, end)` runs of the node indices it can see.
+    rows: Vec<Vec<(u32, u32)>>,
+    /// Per node: share of the nodes it is paired with that can NOT see it — 0 = seen by
+    /// everything around it, 1 = seen by nothing.
+    cover: Vec<f32>,
+}
+
+fn eye(p: [f32; 3]) -> [f32; 3] {
+    [p[0], p[1], p[2] + VIS_EYE_Z]
+}
+
+fn dist2(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|k| (a[k] - b[k]) * (a[k] - b[k])).sum()
+}
+
+/// Grid cell (of side [`VIS_RANGE`]) holding a point.
+fn cell(p: [f32; 3]) -> (i32, i32, i32) {
+    let c = |v: f32| (v / VIS_RANGE).floor() as i32;
+    (c(p[0]), c(p[1]), c(p[2]))
+}
+
+/// Per node, every other node within [`VIS_RANGE`] with its squared distance, found through a
+/// grid of [`VIS_RANGE`]-sized cells (only the 27 around a node can hold one in range).
+fn in_range(eyes: &[[f32; 3]]) -> Vec<Vec<(u32, f32)>> {
+    let mut grid: HashMap<(i32, i32, i32), Vec<u32>> = HashMap::new();
+    for (i, &e) in eyes.iter().enumerate() {
+        grid.entry(cell(e)).or_default().push(i as u32);
+    }
+    let range2 = VIS_RANGE * VIS_RANGE;
+    (0..eyes.len())
+        .into_par_iter()
+        .map(|a| {
+            let (x, y, z) = cell(eyes[a]);
+            let mut near = Vec::new();
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        for &b in grid.get(&(x + dx, y + dy, z + dz)).into_iter().flatten() {
+                            let d = dist2(eyes[a], eyes[b as usize]);
+                            if b as usize != a && d <= range2 {
+                                near.push((b, d));
+                            }
+                        }
+                    }
+                }
+            }
+            near
+        })
+        .collect()
+}
+
+/// Collapse a sorted index list into `[start, end)` runs.
+fn runs(sorted: &[u32]) -> Vec<(u32, u32)> {
+    let mut out: Vec<(u32, u32)> = Vec::new();
+    for &i in sorted {
+        match out.last_mut() {
+            Some((_, end)) if *end == i => *end += 1,
+            _ => out.push((i, i + 1)),
+        }
+    }
+    out
+}
+
+impl VisTable {
+    /// Trace every PVS-plausible node pair within [`VIS_RANGE`] that one end keeps among its
+    /// [`MAX_VIS_PAIRS`] nearest. `pvs` is `None` for maps without a vis lump — then no pair is
+    /// culled by cluster.
+    pub fn build(cm: &CollisionModel, graph: &NavGraph, pvs: Option<&Pvs>) -> Self {
+        let n = graph.node_count();
+        let eyes: Vec<[f32; 3]> = graph.nodes.iter().map(|&p| eye(p)).collect();
+        let clusters: Vec<i16> = eyes.iter().map(|e| cm.point_cluster(e)).collect();
+
+        // Decompressed PVS row per occupied cluster. Cluster -1 (an eye in the void) and a
+        // missing lump have no row: such nodes are traced against everything.
+        let pvs_rows: HashMap<i16, Vec<u8>> = match pvs {
+            Some(p) => {
+                let mut occupied: Vec<i16> = clusters.iter().copied().filter(|&c| c >= 0).collect();
+                occupied.sort_unstable();
+                occupied.dedup();
+                occupied.into_iter().map(|c| (c, p.decompress(c))).collect()
+            }
+            None => HashMap::new(),
+        };
+        let potentially_visible = |a: i16, b: i16| match (pvs_rows.get(&a), b) {
+            (Some(row), b) if b >= 0 => row
+                .get(b as usize / 8)
+                .is_none_or(|byte| byte & (1 << (b % 8)) != 0),
+            _ => true,
+        };
+
+        // Each node's pairing radius²: its MAX_VIS_PAIRS-th nearest, or the full range.
+        let near = in_range(&eyes);
+        let reach: Vec<f32> = near
+            .par_iter()
+            .map(|near| {
+                if near.len() <= MAX_VIS_PAIRS {
+                    return VIS_RANGE * VIS_RANGE;
+                }
+                let mut d: Vec<f32> = near.iter().map(|&(_, d)| d).collect();
+                *d.select_nth_unstable_by(MAX_VIS_PAIRS - 1, f32::total_cmp)
+                    .1
+            })
+            .collect();
+        // Each node's partners: pairs that either end keeps.
+        let partners: Vec<Vec<u32>> = near
+            .into_par_iter()
+            .enumerate()
+            .map(|(a, near)| {
+                near.into_iter()
+                    .filter(|&(b, d)| d <= reach[a].max(reach[b as usize]))
+                    .map(|(b, _)| b)
+                    .collect()
+            })
+            .collect();
+
+        // Upper triangle only (b > a): the trace is symmetric.
+        let upper: Vec<Vec<u32>> = (0..n)
+            .into_par_iter()
+            .map(|a| {
+                partners[a]
+                    .iter()
+                    .copied()
+                    .filter(|&b| b as usize > a)
+                    .filter(|&b| potentially_visible(clusters[a], clusters[b as usize]))
+                    .filter(|&b| {
+                        let tr = cm.trace(
+                            &eyes[a],
+                            &eyes[b as usize],
+                            &[0.0; 3],
+                            &[0.0; 3],
+                            MASK_SOLID,
+                        );
+                        tr.fraction >= 1.0 && !tr.startsolid
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut full: Vec<Vec<u32>> = vec![Vec::new(); n];
+        for (a, seen) in upper.into_iter().enumerate() {
+            for b in seen {
+                full[a].push(b);
+                full[b as usize].push(a as u32);
+            }
+        }
+        let in_range: Vec<usize> = partners.iter().map(Vec::len).collect();
+
+        let mut rows = Vec::with_capacity(n);
+        let mut cover = Vec::with_capacity(n);
+        for (mut seen, total) in full.into_iter().zip(in_range) {
+            seen.sort_unstable();
+            cover.push(if total == 0 {
+                1.0
+            } else {
+                1.0 - seen.len() as f32 / total as f32
+            });
+            rows.push(runs(&seen));
+        }
+        Self { rows, cover }
+    }
+
+    /// Number of nodes the table covers.
+    pub fn node_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// True if nodes `a` and `b` see each other eye to eye (a node always sees itself).
+    pub fn visible(&self, a: usize, b: usize) -> bool {
+        if a == b {
+            return a < self.rows.len();
+        }
+        let Some(row) = self.rows.get(a) else {
+            return false;
+        };
+        let b = b as u32;
+        let i = row.partition_point(|&(_, end)| end <= b);
+        row.get(i).is_some_and(|&(start, _)| start <= b)
+    }
+
+    /// Every node `a` sees (itself excluded), ascending.
+    pub fn visible_from(&self, a: usize) -> impl Iterator<Item = usize> + '_ {
+        self.rows
+            .get(a)
+            .into_iter()
+            .flatten()
+            .flat_map(|&(s, e)| (s as usize)..(e as usize))
+    }
+
+    /// Cover score of node `a` in `[0, 1]`: the share of nodes within range that can't see
+    /// it. Unknown nodes score 0 (assume exposed).
+    pub fn cover(&self, a: usize) -> f32 {
+        self.cover.get(a).copied().unwrap_or(0.0)
+    }
+
+    /// The cheapest-to-reach node (walk cost, at most `max_cost`) that none of `threats`
+    /// can see — the retreat spot. `from` itself qualifies if it is already hidden.
+    pub fn nearest_hidden(
+        &self,
+        graph: &NavGraph,
+        from: usize,
+        threats: &[usize],
+        max_cost: f32,
+    ) -> Option<usize> {
+        let hidden = |v: usize| v < self.rows.len() && !threats.iter().any(|&t| self.visible(t, v));
+        let mut best: HashMap<usize, f32> = HashMap::from([(from, 0.0)]);
+        let mut open = BinaryHeap::from([Reverse((FOrd(0.0), from))]);
+        while let Some(Reverse((FOrd(g), u))) = open.pop() {
+            if g > best.get(&u).copied().unwrap_or(f32::INFINITY) {
+                continue;
+            }
+            if hidden(u) {
+                return Some(u);
+            }
+            for &(v, cost) in graph.neighbors(u) {
+                let ng = g + cost;
+                if ng <= max_cost && ng < best.get(&v).copied().unwrap_or(f32::INFINITY) {
+                    best.insert(v, ng);
+                    open.push(Reverse((FOrd(ng), v)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Up to `limit` nodes that see `target` while being well covered themselves — ambush
+    /// and camping spots overlooking it — best cover first (ties: lower index).
+    pub fn ambush_spots(&self, target: usize, limit: usize) -> Vec<usize> {
+        let mut spots: Vec<usize> = self.visible_from(target).collect();
+        spots.sort_by(|&a, &b| self.cover(b).total_cmp(&self.cover(a)).then(a.cmp(&b)));
+        spots.truncate(limit);
+        spots
+    }
+
+    /// Serialize for the map cache: `node_count u32`, then per node `cover f32`,
+    /// `run_count u32`, and `(start u32, end u32)` runs.
+    pub(crate) fn write(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&(self.rows.len() as u32).to_le_bytes());
+        for (row, cover) in self.rows.iter().zip(&self.cover) {
+            buf.extend_from_slice(&cover.to_le_bytes());
+            buf.extend_from_slice(&(row.len() as u32).to_le_bytes());
+            for &(s, e) in row {
+                buf.extend_from_slice(&s.to_le_bytes());
+                buf.extend_from_slice(&e.to_le_bytes());
+            }
+        }
+    }
+
+    /// Inverse of [`Self::write`]; `None` on truncation or a run past `node_count`.
+    pub(crate) fn read(data: &[u8], pos: &mut usize) -> Option<Self> {
+        let u32_at = |pos: &mut usize| -> Option<u32> {
+            let v = u32::from_le_bytes(data.get(*pos..*pos + 4)?.try_into().ok()?);
+            *pos += 4;
+            Some(v)
+        };
+        let n = u32_at(pos)?;
+        let mut rows = Vec::with_capacity(n as usize);
+        let mut cover = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            cover.push(f32::from_bits(u32_at(pos)?));
+            let rc = u32_at(pos)?;
+            let mut row = Vec::with_capacity(rc as usize);
+            for _ in 0..rc {
+                let (s, e) = (u32_at(pos)?, u32_at(pos)?);
+                if s >= e || e > n {
+                    return None;
+                }
+                row.push((s, e));
+            }
+            rows.push(row);
+        }
+        Some(Self { rows, cover })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testmap::BspBuilder;
+
+    /// Two 256² rooms joined by a 64u-wide doorway in the dividing wall at x=0, with a
+    /// straight 1-D line of nodes through each room and the doorway.
+    fn two_rooms() -> (CollisionModel, NavGraph) {
+        let mut b = BspBuilder::new();
+        b.solid([-272.0, -144.0, -16.0], [272.0, 144.0, 0.0])
+            .solid([-272.0, -144.0, 0.0], [272.0, -128.0, 128.0])
+            .solid([-272.0, 128.0, 0.0], [272.0, 144.0, 128.0])
+            .solid([-272.0, -128.0, 0.0], [-256.0, 128.0, 128.0])
+            .solid([256.0, -128.0, 0.0], [272.0, 128.0, 128.0])
+            .solid([-8.0, -128.0, 0.0], [8.0, 64.0, 128.0]);
+        let cm = CollisionModel::from_bsp(&b.build());
+        // Node 0-3: west room (y = -64), 4: doorway (y = 96), 5-8: east room (y = -64).
+        let nodes = vec![
+            [-224.0, -64.0, 24.0],
+            [-160.0, -64.0, 24.0],
+            [-96.0, -64.0, 24.0],
+            [-32.0, 96.0, 24.0],
+            [0.0, 96.0, 24.0],
+            [32.0, 96.0, 24.0],
+            [96.0, -64.0, 24.0],
+            [160.0, -64.0, 24.0],
+            [224.0, -64.0, 24.0],
+        ];
+        let mut adj = vec![Vec::new(); nodes.len()];
+        for i in 0..nodes.len() - 1 {
+            let d = dist2(nodes[i], nodes[i + 1]).sqrt();
+            adj[i].push((i + 1, d));
+            adj[i + 1].push((i, d));
+        }
+        (cm, NavGraph::from_raw(nodes, adj))
+    }
+
+    #[test]
+    fn wall_blocks_and_doorway_sees_both_rooms() {
+        let (cm, g) = two_rooms();
+        let t = VisTable::build(&cm, &g, None);
+        assert!(t.visible(0, 2) && t.visible(2, 0), "same room");
+        assert!(!t.visible(1, 7), "through the dividing wall");
+        assert!(
+            t.visible(4, 0) && t.visible(4, 8),
+            "doorway overlooks both rooms"
+        );
+        assert!(t.visible(3, 3));
+        assert!(!t.visible(0, 99), "unknown node");
+        let seen: Vec<usize> = t.visible_from(4).collect();
+        assert!(seen.windows(2).all(|w| w[0] < w[1]) && !seen.contains(&4));
+        // Doorway nodes are the most exposed; room corners see only their own room.
+        assert!(t.cover(4) < t.cover(0));
+    }
+
+    #[test]
+    fn nearest_hidden_retreats_out_of_sight() {
+        let (cm, g) = two_rooms();
+        let t = VisTable::build(&cm, &g, None);
+        // Enemy in the west room at node 1; we stand in the doorway (node 4) seen by it.
+        assert!(t.visible(1, 4));
+        let spot = t
+            .nearest_hidden(&g, 4, &[1], 1000.0)
+            .expect("a hidden node");
+        assert!(spot >= 5 && !t.visible(1, spot), "retreat east, got {spot}");
+        // Already hidden → stay put; no budget → nothing reachable.
+        assert_eq!(t.nearest_hidden(&g, 8, &[1], 1000.0), Some(8));
+        assert_eq!(t.nearest_hidden(&g, 4, &[1], 10.0), None);
+
+        let ambush = t.ambush_spots(7, 3);
+        assert!(!ambush.is_empty() && ambush.iter().all(|&a| t.visible(a, 7)));
+        assert!(ambush.windows(2).all(|w| t.cover(w[0]) >= t.cover(w[1])));
+    }
+
+    #[test]
+    fn dense_open_area_pairs_only_the_nearest() {
+        // A 20×20 node grid 32u apart in one open room: every pair is in range and in view,
+        // but a node pairs only with its MAX_VIS_PAIRS nearest and the nodes that keep it.
+        let mut b = BspBuilder::new();
+        b.solid([-64.0, -64.0, -16.0], [700.0, 700.0, 0.0]);
+        let cm = CollisionModel::from_bsp(&b.build());
+        let side = 20;
+        let nodes: Vec<[f32; 3]> = (0..side * side)
+            .map(|i| [(i % side) as f32 * 32.0, (i / side) as f32 * 32.0, 24.0])
+            .collect();
+        let n = nodes.len();
+        let g = NavGraph::from_raw(nodes, vec![Vec::new(); n]);
+        let t = VisTable::build(&cm, &g, None);
+        assert!(t.visible(0, 1) && t.visible(0, side + 1), "neighbours");
+        assert!(!t.visible(0, n - 1), "opposite corners are past both caps");
+        // Every pair is kept by one of its ends, so the traces stay within n · MAX_VIS_PAIRS.
+        let pairs: usize = (0..n).map(|a| t.visible_from(a).count()).sum::<usize>() / 2;
+        assert!(
+            pairs <= n * MAX_VIS_PAIRS && pairs < n * (n - 1) / 2,
+            "{pairs} pairs"
+        );
+        assert!((0..n).all(|a| t.visible_from(a).count() >= MAX_VIS_PAIRS));
+    }
+
+    #[test]
+    fn serialization_round_trips() {
+        let (cm, g) = two_rooms();
+        let t = VisTable::build(&cm, &g, None);
+        let mut buf = Vec::new();
+        t.write(&mut buf);
+        let mut pos = 0;
+        assert_eq!(VisTable::read(&buf, &mut pos), Some(t));
+        assert_eq!(pos, buf.len());
+        assert!(VisTable::read(&buf[..buf.len() - 1], &mut 0).is_none());
+    }
+
+    #[test]
+    fn runs_collapse_consecutive_indices() {
+        assert_eq!(runs(&[1, 2, 3, 7, 9, 10]), vec![(1, 4), (7, 8), (9, 11)]);
+        assert!(runs(&[]).is_empty());
+    }
+}
@@ -0,0 +1,380 @@
+//! Wavefront OBJ + MTL export of a map's nav data, for looking at a generated graph in any
+//! 3D viewer (Blender, MeshLab, f3d) instead of reading `navinspect` text dumps.
+//!
+//! One `.obj` holds named objects, each with its own material so a viewer can toggle them:
+//!
+//! - `hull` — collision brush faces (solid grey, translucent; lava / slime / water tinted);
+//! - `nodes` — a small octahedron per nav node, coloured by connected component (the eight
+//!   largest get distinct colours, the rest share `comp_other`);
+//! - `edges` — one line per edge, material by [`EdgeKind`] (`edge_walk` … `edge_rocket_jump`);
+//! - `navmesh` — each navmesh rectangle as a quad at its floor height;
+//! - `markers` — bigger octahedra for spawns and item pads.
+//!
+//! Q2 is Z-up; OBJ viewers default to Y-up, so coordinates are written as `(x, z, -y)`.
+
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+use crate::bsp::Bsp;
+use crate::collision::{CollisionModel, CONTENTS_LAVA, CONTENTS_SLIME, CONTENTS_WATER, MASK_SOLID};
+use crate::navgraph::{EdgeKind, NavGraph};
+use crate::navmesh::NavMesh;
+
+/// Half-size of a node marker (units) — small enough that the 24u grid stays readable.
+const NODE_SIZE: f32 = 4.0;
+/// Half-size of a spawn/item marker.
+const MARKER_SIZE: f32 = 12.0;
+/// Components given their own colour; smaller ones share `comp_other`.
+const COMPONENT_COLOURS: usize = 8;
+
+/// `(name, rgb, opacity)` for every material the OBJ can reference.
+const MATERIALS: &[(&str, [f32; 3], f32)] = &[
+    ("hull_solid", [0.55, 0.55, 0.55], 0.25),
+    ("hull_lava", [0.9, 0.25, 0.0], 0.6),
+    ("hull_slime", [0.3, 0.8, 0.1], 0.6),
+    ("hull_water", [0.1, 0.3, 0.9], 0.4),
+    ("comp0", [0.1, 0.8, 0.2], 1.0),
+    ("comp1", [0.9, 0.6, 0.0], 1.0),
+    ("comp2", [0.6, 0.2, 0.9], 1.0),
+    ("comp3", [0.0, 0.7, 0.9], 1.0),
+    ("comp4", [0.9, 0.2, 0.5], 1.0),
+    ("comp5", [0.7, 0.7, 0.1], 1.0),
+    ("comp6", [0.2, 0.4, 0.9], 1.0),
+    ("comp7", [0.5, 0.3, 0.1], 1.0),
+    ("comp_other", [0.9, 0.1, 0.1], 1.0),
+    ("edge_walk", [0.8, 0.8, 0.8], 1.0),
+    ("edge_jump", [1.0, 0.8, 0.0], 1.0),
+    ("edge_swim", [0.2, 0.5, 1.0], 1.0),
+    ("edge_ride", [0.0, 1.0, 0.6], 1.0),
+    ("edge_teleport", [1.0, 0.0, 1.0], 1.0),
+    ("edge_rocket_jump", [1.0, 0.3, 0.0], 1.0),
+    ("navmesh", [0.2, 0.6, 1.0], 0.5),
+    ("marker_spawn", [0.0, 1.0, 0.0], 1.0),
+    ("marker_weapon", [1.0, 0.2, 0.2], 1.0),
+    ("marker_ammo", [0.8, 0.5, 0.2], 1.0),
+    ("marker_health", [1.0, 1.0, 1.0], 1.0),
+    ("marker_armor", [1.0, 1.0, 0.0], 1.0),
+    ("marker_item", [0.0, 1.0, 1.0], 1.0),
+];
+
+/// What to export. Everything but the collision model is optional.
+pub struct Scene<'a> {
+    pub cm: &'a CollisionModel,
+    /// Clip box for the hull (the world model's bounds).
+    pub bounds: ([f32; 3], [f32; 3]),
+    pub graph: Option<&'a NavGraph>,
+    pub mesh: Option<&'a NavMesh>,
+    /// `(material, origin)` — see [`markers_from_bsp`].
+    pub markers: Vec<(&'static str, [f32; 3])>,
+}
+
+/// Spawn and item-pad markers from the BSP entity lump, with the material for each.
+pub fn markers_from_bsp(bsp: &Bsp) -> Vec<(&'static str, [f32; 3])> {
+    let mut out: Vec<(&'static str, [f32; 3])> = bsp
+        .spawn_points()
+        .iter()
+        .map(|s| ("marker_spawn", s.origin))
+        .collect();
+    for e in &bsp.entities {
+        let c = e.classname.as_str();
+        let mat = if c.starts_with("weapon_") {
+            "marker_weapon"
+        } else if c.starts_with("ammo_") {
+            "marker_ammo"
+        } else if c.starts_with("item_health") {
+            "marker_health"
+        } else if c.starts_with("item_armor") {
+            "marker_armor"
+        } else if c.starts_with("item_") {
+            "marker_item"
+        } else {
+            continue;
+        };
+        if let Some(origin) = e.origin() {
+            out.push((mat, origin));
+        }
+    }
+    out
+}
+
+/// Write `<path>` (OBJ) and its sibling `.mtl`.
+pub fn export_obj(path: &Path, scene: &Scene) -> io::Result<()> {
+    let mtl_path = path.with_extension("mtl");
+    let mtl_name = mtl_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("nav.mtl");
+    std::fs::write(path, obj_string(scene, mtl_name))?;
+    std::fs::write(&mtl_path, mtl_string())
+}
+
+/// The MTL library covering every material [`obj_string`] uses.
+pub fn mtl_string() -> String {
+    let mut s = String::new();
+    for (name, [r, g, b], d) in MATERIALS {
+        let _ = writeln!(s, "newmtl {name}\nKd {r} {g} {b}\nKa {r} {g} {b}\nd {d}\n");
+    }
+    s
+}
+
+/// OBJ text for `scene`, referencing `mtllib` for materials.
+pub fn obj_string(scene: &Scene, mtllib: &str) -> String {
+    let mut w = ObjWriter::default();
+    let _ = writeln!(w.s, "# qbots nav export\nmtllib {mtllib}");
+
+    w.object("hull");
+    let mask = MASK_SOLID | CONTENTS_LAVA | CONTENTS_SLIME | CONTENTS_WATER;
+    let mut faces = scene.cm.brush_faces(mask, scene.bounds);
+    faces.sort_by_key(|(c, _)| hull_material(*c));
+    let mut current = "";
+    for (contents, face) in &faces {
+        let mat = hull_material(*contents);
+        if mat != current {
+            w.material(mat);
+            current = mat;
+        }
+        w.polygon(face);
+    }
+
+    if let Some(g) = scene.graph {
+        w.object("nodes");
+        let mut colour = vec![COMPONENT_COLOURS; g.node_count()];
+        for (ci, comp) in g.components().iter().take(COMPONENT_COLOURS).enumerate() {
+            for &n in comp {
+                colour[n] = ci;
+            }
+        }
+        for ci in 0..=COMPONENT_COLOURS {
+            let nodes: Vec<usize> = (0..g.node_count()).filter(|&n| colour[n] == ci).collect();
+            if nodes.is_empty() {
+                continue;
+            }
+            if ci == COMPONENT_COLOURS {
+                w.material("comp_other");
+            } else {
+                w.material(&format!("comp{ci}"));
+            }
+            for n in nodes {
+                w.octahedron(g.nodes[n], NODE_SIZE);
+            }
+        }
+
+        w.object("edges");
+        let base = w.vertex_count;
+        for p in &g.nodes {
+            w.vertex(*p);
+        }
+        let mut by_kind: Vec<(&str, Vec<(usize, usize)>)> = Vec::new();
+        for a in 0..g.node_count() {
+            for &(b, _) in g.neighbors(a) {
+                // Two-way walk pairs once; every other kind per direction.
+                let kind = edge_material(g.edge_kind(a, b));
+                if kind == "edge_walk"
+                    && a > b
+                    && g.neighbors(b).iter().any(|&(x, _)| x == a)
+                    && edge_material(g.edge_kind(b, a)) == "edge_walk"
+                {
+                    continue;
+                }
+                push_kind(&mut by_kind, kind, (a, b));
+            }
+        }
+        for (a, b, _, _) in g.raw_rocket_jumps() {
+            push_kind(&mut by_kind, "edge_rocket_jump", (a, b));
+        }
+        for (mat, edges) in &by_kind {
+            w.material(mat);
+            for &(a, b) in edges {
+                let _ = writeln!(w.s, "l {} {}", base + a + 1, base + b + 1);
+            }
+        }
+    }
+
+    if let Some(mesh) = scene.mesh {
+        w.object("navmesh");
+        w.material("navmesh");
+        for p in &mesh.polys {
+            let x0 = mesh.min[0] + p.ix as f32 * mesh.cell_size;
+            let y0 = mesh.min[1] + p.iy as f32 * mesh.cell_size;
+            let (x1, y1) = (
+                x0 + p.w as f32 * mesh.cell_size,
+                y0 + p.h as f32 * mesh.cell_size,
+            );
+            // Player origin → floor, lifted 1u off the hull face.
+            let z = p.oz - 23.0;
+            w.polygon(&[[x0, y0, z], [x1, y0, z], [x1, y1, z], [x0, y1, z]]);
+        }
+    }
+
+    if !scene.markers.is_empty() {
+        w.object("markers");
+        let mut markers = scene.markers.clone();
+        markers.sort_by_key(|m| m.0);
+        let mut current = "";
+        for (mat, origin) in markers {
+            if mat != current {
+                w.material(mat);
+                current = mat;
+            }
+            w.octahedron(origin, MARKER_SIZE);
+        }
+    }
+    w.s
+}
+
+fn hull_material(contents: i32) -> &'static str {
+    if contents & MASK_SOLID != 0 {
+        "hull_solid"
+    } else if contents & CONTENTS_LAVA != 0 {
+        "hull_lava"
+    } else if contents & CONTENTS_SLIME != 0 {
+        "hull_slime"
+    } else {
+        "hull_water"
+    }
+}
+
+fn edge_material(kind: EdgeKind) -> &'static str {
+    match kind {
+        EdgeKind::Walk => "edge_walk",
+        EdgeKind::Jump { .. } => "edge_jump",
+        EdgeKind::Swim => "edge_swim",
+        EdgeKind::Ride => "edge_ride",
+        EdgeKind::Teleport => "edge_teleport",
+        EdgeKind::RocketJump { .. } => "edge_rocket_jump",
+    }
+}
+
+fn push_kind(
+    by_kind: &mut Vec<(&'static str, Vec<(usize, usize)>)>,
+    kind: &'static str,
+    edge: (usize, usize),
+) {
+    match by_kind.iter_mut().find(|(k, _)| *k == kind) {
+        Some((_, v)) => v.push(edge),
+        None => by_kind.push((kind, vec![edge])),
+    }
+}
+
+/// OBJ text accumulator tracking the running (1-based) vertex index.
+#[derive(Default)]
+struct ObjWriter {
+    s: String,
+    vertex_count: usize,
+}
+
+impl ObjWriter {
+    fn object(&mut self, name: &str) {
+        let _ = writeln!(self.s, "o {name}");
+    }
+
+    fn material(&mut self, name: &str) {
+        let _ = writeln!(self.s, "usemtl {name}");
+    }
+
+    /// Q2 Z-up → OBJ Y-up.
+    fn vertex(&mut self, p: [f32; 3]) {
+        let _ = writeln!(self.s, "v {} {} {}", p[0], p[2], -p[1]);
+        self.vertex_count += 1;
+    }
+
+    fn polygon(&mut self, pts: &[[f32; 3]]) {
+        let first = self.vertex_count + 1;
+        for &p in pts {
+            self.vertex(p);
+        }
+        self.s.push('f');
+        for i in 0..pts.len() {
+            let _ = write!(self.s, " {}", first + i);
+        }
+        self.s.push('\n');
+    }
+
+    fn octahedron(&mut self, c: [f32; 3], r: f32) {
+        let first = self.vertex_count + 1;
+        for d in [
+            [r, 0.0, 0.0],
+            [-r, 0.0, 0.0],
+            [0.0, r, 0.0],
+            [0.0, -r, 0.0],
+            [0.0, 0.0, r],
+            [0.0, 0.0, -r],
+        ] {
+            self.vertex([c[0] + d[0], c[1] + d[1], c[2] + d[2]]);
+        }
+        // Vertex order: +x −x +y −y +z −z.
+        for [a, b, t] in [
+            [0, 2, 4],
+            [2, 1, 4],
+            [1, 3, 4],
+            [3, 0, 4],
+            [2, 0, 5],
+            [1, 2, 5],
+            [3, 1, 5],
+            [0, 3, 5],
+        ] {
+            let _ = writeln!(self.s, "f {} {} {}", first + a, first + b, first + t);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::ledge_world;
+
+    #[test]
+    fn obj_export_covers_every_layer() {
+        let cm = ledge_world(64.0);
+        // 0↔1 walk, 1→2 jump, 3 isolated (second component).
+        let mut g = NavGraph::from_raw_with_jumps(
+            vec![
+                [0.0, 0.0, 24.0],
+                [24.0, 0.0, 24.0],
+                [48.0, 0.0, 24.0],
+                [200.0, 0.0, 88.0],
+            ],
+            vec![vec![(1, 24.0)], vec![(0, 24.0), (2, 24.0)], vec![], vec![]],
+            vec![(1, 2, 0.0)],
+        );
+        g.set_rocket_jumps(vec![(2, 3, 0.0, 8.0)]);
+        let scene = Scene {
+            cm: &cm,
+            bounds: ([-128.0, -128.0, -64.0], [256.0, 128.0, 128.0]),
+            graph: Some(&g),
+            mesh: None,
+            markers: vec![("marker_spawn", [0.0, 0.0, 24.0])],
+        };
+        let obj = obj_string(&scene, "t.mtl");
+        let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+        // 3 hull quads + 4 node octahedra + 1 marker octahedron (8 faces each).
+        assert_eq!(count("f "), 3 + 5 * 8);
+        // Walk pair once, the jump, the rocket jump.
+        assert_eq!(count("l "), 3);
+        for mat in [
+            "edge_walk",
+            "edge_jump",
+            "edge_rocket_jump",
+            "comp0",
+            "comp1",
+        ] {
+            assert!(obj.contains(&format!("usemtl {mat}\n")), "missing {mat}");
+        }
+
+        // Every referenced material is defined; every index is in range.
+        let mtl = mtl_string();
+        let verts = count("v ");
+        for line in obj.lines() {
+            if let Some(mat) = line.strip_prefix("usemtl ") {
+                assert!(mtl.contains(&format!("newmtl {mat}\n")), "undefined {mat}");
+            }
+            if line.starts_with("f ") || line.starts_with("l ") {
+                for idx in line[2..].split(' ') {
+                    let i: usize = idx.parse().unwrap();
+                    assert!(i >= 1 && i <= verts, "index {i} out of range");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,743 @@
+//! Hierarchical (cluster-level) pathfinding over the [`NavGraph`] — HPA*-style (Botea,
+//! Müller & Schaeffer 2004, "Near Optimal Hierarchical Path-Finding").
+//!
+//! Nodes are bucketed into fixed-size spatial clusters. Every directed edge crossing a
+//! cluster boundary is an entrance candidate; each contiguous run of them between the same
+//! ordered pair of clusters collapses to one representative edge (three for wide openings),
+//! whose endpoints become **abstract nodes**. Each cluster precomputes its exact
+//! border-to-border routes (Dijkstra restricted to the cluster), so a long-range query
+//! searches a few hundred abstract nodes instead of tens of thousands of 24u grid nodes,
+//! then expands each abstract hop from the stored route.
+//!
+//! Collapsing openings is the only approximation: a route is forced through a
+//! representative edge of every opening it uses, so the detour is bounded by about one
+//! opening width per cluster crossed. Reachability is never lost — a query the abstract
+//! graph can't answer falls back to flat [`NavGraph::path_weighted`], so [`HierGraph::path`]
+//! returns `None` iff the flat search does.
+//!
+//! The tables hold base costs only, so one hierarchy per map serves every bot
+//! ([`NavGraph::hier`]) whatever its risk overlay. The overlay is applied at query time with
+//! `path_weighted`'s semantics — edge `u→v` costs `(base + overlay[u]).max(EPS)`: an abstract
+//! hop in a cluster with heat is priced along its stored route (cold clusters read their
+//! table as is), and a chosen hop whose route crosses heat is re-searched inside its cluster
+//! when the path is expanded, so the detour around a hot spot is found locally. Runtime-only — no mapcache format impact.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use rayon::prelude::*;
+
+use crate::navgraph::{FOrd, NavGraph, EPS};
+
+/// Horizontal cluster edge (units): 10×10 nodes of the 24u grid.
+pub const CLUSTER_SIZE: f32 = 240.0;
+/// Vertical cluster edge (units) — one storey, so stacked floors don't share a cluster.
+pub const CLUSTER_HEIGHT: f32 = 128.0;
+/// Graphs at least this big get a hierarchy ([`NavGraph::hier`]); smaller ones (test rigs,
+/// tiny maps) stay on flat A*, where the abstraction costs more than it saves.
+pub const HIER_MIN_NODES: usize = 4000;
+/// An opening wider than this many crossing edges also keeps its two extreme edges as
+/// entrances (HPA*'s "two transitions for wide entrances"), so a route along the far side
+/// of a wide doorway isn't dragged through its middle.
+const WIDE_ENTRANCE: usize = 6;
+
+/// Precomputed cluster abstraction of one [`NavGraph`]. Queries take the graph it was built
+/// from and the caller's overlay.
+#[derive(Clone)]
+pub struct HierGraph {
+    /// Horizontal cluster edge; queries shorter than this stay on flat A*.
+    size: f32,
+    /// `cluster_of[node]` = cluster id.
+    cluster_of: Vec<usize>,
+    /// `members[cluster]` = its graph nodes; `local[node]` = index into that list.
+    members: Vec<Vec<usize>>,
+    local: Vec<usize>,
+    /// Reverse of the intra-cluster edges: `radj[v]` = `(u, base)` for each `u→v`.
+    radj: Vec<Vec<(usize, f32)>>,
+    /// `entrances[a]` = graph node of abstract node `a`; `abs_of[node]` the inverse
+    /// (`usize::MAX` = not an entrance).
+    entrances: Vec<usize>,
+    abs_of: Vec<usize>,
+    /// `cluster_entrances[cluster]` = its abstract nodes.
+    cluster_entrances: Vec<Vec<usize>>,
+    /// `intra[a]` = `(b, cost)` for every entrance `b` of the same cluster reachable from `a`
+    /// inside the cluster, at base cost; `intra_paths[a][i]` = the `(node, base cost into it)`
+    /// steps after `a` on that route, so refinement is a copy rather than a search.
+    intra: Vec<Vec<(usize, f32)>>,
+    intra_paths: Vec<Vec<Vec<(usize, f32)>>>,
+    /// `cross[a]` = `(b, base)` — representative boundary edges (overlay applied at query).
+    cross: Vec<Vec<(usize, f32)>>,
+}
+
+impl HierGraph {
+    /// Build at the default [`CLUSTER_SIZE`] × [`CLUSTER_HEIGHT`].
+    pub fn build(graph: &NavGraph) -> Self {
+        Self::build_with(graph, CLUSTER_SIZE, CLUSTER_HEIGHT)
+    }
+
+    /// Build with explicit cluster dimensions (tests, tuning).
+    pub fn build_with(graph: &NavGraph, size: f32, height: f32) -> Self {
+        let n = graph.node_count();
+        // Cluster ids in sorted key order, so two builds of one graph agree.
+        let keys: Vec<(i32, i32, i32)> = graph
+            .nodes
+            .iter()
+            .map(|p| {
+                (
+                    (p[0] / size).floor() as i32,
+                    (p[1] / size).floor() as i32,
+                    (p[2] / height).floor() as i32,
+                )
+            })
+            .collect();
+        let mut uniq = keys.clone();
+        uniq.sort_unstable();
+        uniq.dedup();
+        let cluster_of: Vec<usize> = keys
+            .iter()
+            .map(|k| uniq.binary_search(k).expect("key present"))
+            .collect();
+        let mut members = vec![Vec::new(); uniq.len()];
+        let mut local = vec![0; n];
+        for (node, &c) in cluster_of.iter().enumerate() {
+            local[node] = members[c].len();
+            members[c].push(node);
+        }
+
+        // Boundary links keyed by UNORDERED cluster pair, oriented low cluster → high, with
+        // the cost of each direction that exists — both directions of one doorway then share
+        // the same entrance nodes.
+        let mut radj = vec![Vec::new(); n];
+        let mut crossing: HashMap<(usize, usize), HashMap<(usize, usize), Link>> = HashMap::new();
+        for u in 0..n {
+            for &(v, c) in graph.neighbors(u) {
+                let (cu, cv) = (cluster_of[u], cluster_of[v]);
+                if cu == cv {
+                    radj[v].push((u, c));
+                    continue;
+                }
+                let (p, q) = if cu < cv { (u, v) } else { (v, u) };
+                let link = crossing
+                    .entry((cu.min(cv), cu.max(cv)))
+                    .or_default()
+                    .entry((p, q))
+                    .or_default();
+                if cu < cv {
+                    link.up = Some(c);
+                } else {
+                    link.down = Some(c);
+                }
+            }
+        }
+
+        let mut hg = Self {
+            size,
+            cluster_of,
+            members,
+            local,
+            radj,
+            entrances: Vec::new(),
+            abs_of: vec![usize::MAX; n],
+            cluster_entrances: vec![Vec::new(); uniq.len()],
+            intra: Vec::new(),
+            intra_paths: Vec::new(),
+            cross: Vec::new(),
+        };
+        let mut pairs: Vec<(usize, usize)> = crossing.keys().copied().collect();
+        pairs.sort_unstable();
+        for pair in pairs {
+            let mut links: Vec<((usize, usize), Link)> =
+                crossing[&pair].iter().map(|(&k, &l)| (k, l)).collect();
+            links.sort_unstable_by_key(|&(k, _)| k);
+            for ((p, q), link) in representatives(graph, &links) {
+                let (a, b) = (hg.entrance(p), hg.entrance(q));
+                if let Some(c) = link.up {
+                    hg.cross[a].push((b, c));
+                }
+                if let Some(c) = link.down {
+                    hg.cross[b].push((a, c));
+                }
+            }
+        }
+        hg.solve_clusters(graph);
+        hg
+    }
+
+    /// Abstract node for graph node `node`, creating it on first use.
+    fn entrance(&mut self, node: usize) -> usize {
+        if self.abs_of[node] == usize::MAX {
+            self.abs_of[node] = self.entrances.len();
+            self.entrances.push(node);
+            self.cross.push(Vec::new());
+            self.cluster_entrances[self.cluster_of[node]].push(self.abs_of[node]);
+        }
+        self.abs_of[node]
+    }
+
+    /// Number of graph nodes this hierarchy was built over.
+    pub fn node_count(&self) -> usize {
+        self.cluster_of.len()
+    }
+
+    /// Number of clusters.
+    pub fn cluster_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Number of abstract (entrance) nodes.
+    pub fn entrance_count(&self) -> usize {
+        self.entrances.len()
+    }
+
+    /// Solve every cluster's border-to-border table at base cost (parallel per cluster).
+    fn solve_clusters(&mut self, graph: &NavGraph) {
+        type Row = (usize, Vec<(usize, f32)>, Vec<Vec<(usize, f32)>>);
+        let tables: Vec<Vec<Row>> = (0..self.members.len())
+            .into_par_iter()
+            .map(|c| {
+                let ents = &self.cluster_entrances[c];
+                ents.iter()
+                    .map(|&a| {
+                        let from = self.entrances[a];
+                        let (d, parent) = self.local_tree(graph, from, false, &[]);
+                        let (mut row, mut paths) = (Vec::new(), Vec::new());
+                        for &b in ents.iter().filter(|&&b| b != a) {
+                            let to = self.entrances[b];
+                            let cost = d[self.local[to]];
+                            if cost.is_finite() {
+                                row.push((b, cost));
+                                // unwind gives `…, from` walking back from `to`.
+                                let mut path = self.unwind(&parent, to, from);
+                                path.pop();
+                                path.reverse();
+                                path.push(to);
+                                let mut prev = d[self.local[from]];
+                                let steps = path
+                                    .into_iter()
+                                    .map(|n| {
+                                        let g = d[self.local[n]];
+                                        let step = (n, g - prev);
+                                        prev = g;
+                                        step
+                                    })
+                                    .collect();
+                                paths.push(steps);
+                            }
+                        }
+                        (a, row, paths)
+                    })
+                    .collect()
+            })
+            .collect();
+        self.intra = vec![Vec::new(); self.entrances.len()];
+        self.intra_paths = vec![Vec::new(); self.entrances.len()];
+        for (a, row, paths) in tables.into_iter().flatten() {
+            self.intra[a] = row;
+            self.intra_paths[a] = paths;
+        }
+    }
+
+    /// Cost of the stored route `a → intra[a][i]` under `overlay`, and whether any node on
+    /// it carries overlay (the route might then not be the cheapest inside the cluster).
+    fn route_cost(&self, a: usize, i: usize, overlay: &[f32]) -> (f32, bool) {
+        if overlay.is_empty() {
+            return (self.intra[a][i].1, false);
+        }
+        let (mut cost, mut hot) = (0.0, false);
+        let mut from = self.entrances[a];
+        for &(node, base) in &self.intra_paths[a][i] {
+            let add = overlay.get(from).copied().unwrap_or(0.0);
+            hot |= add != 0.0;
+            cost += (base + add).max(EPS);
+            from = node;
+        }
+        (cost, hot)
+    }
+
+    /// Dijkstra from `from` restricted to its cluster under `overlay`: `(cost, parent)`
+    /// indexed by `local` (`parent` holds graph nodes, `usize::MAX` at the root/unreached).
+    /// `reverse` walks edges backwards, giving every member's cost TO `from` and its next hop
+    /// toward it.
+    fn local_tree(
+        &self,
+        graph: &NavGraph,
+        from: usize,
+        reverse: bool,
+        overlay: &[f32],
+    ) -> (Vec<f32>, Vec<usize>) {
+        let c = self.cluster_of[from];
+        let mut g = vec![f32::INFINITY; self.members[c].len()];
+        let mut parent = vec![usize::MAX; g.len()];
+        let mut closed = vec![false; g.len()];
+        g[self.local[from]] = 0.0;
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((FOrd(0.0), from)));
+        while let Some(Reverse((FOrd(gc), cur))) = open.pop() {
+            let lc = self.local[cur];
+            if closed[lc] {
+                continue;
+            }
+            closed[lc] = true;
+            let mut relax = |nb: usize, cost: f32| {
+                let ln = self.local[nb];
+                let ng = gc + cost;
+                if !closed[ln] && ng < g[ln] {
+                    g[ln] = ng;
+                    parent[ln] = cur;
+                    open.push(Reverse((FOrd(ng), nb)));
+                }
+            };
+            if reverse {
+                for &(nb, base) in &self.radj[cur] {
+                    relax(nb, edge_cost(overlay, nb, base));
+                }
+            } else {
+                for &(nb, base) in graph.neighbors(cur) {
+                    if self.cluster_of[nb] == c {
+                        relax(nb, edge_cost(overlay, cur, base));
+                    }
+                }
+            }
+        }
+        (g, parent)
+    }
+
+    /// Follow `parent` links from `node` until `root`, returning the nodes visited AFTER
+    /// `node` (ending at `root`). For a forward tree reverse the result to get root → node.
+    fn unwind(&self, parent: &[usize], node: usize, root: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut at = node;
+        while at != root {
+            at = parent[self.local[at]];
+            out.push(at);
+        }
+        out
+    }
+
+    /// A* over the abstract graph with `start`/`goal` spliced in through their cluster trees
+    /// (`from_start`: forward costs from `start`; `to_goal`: reverse costs to `goal`). Returns
+    /// the entrances visited, in order, and the route cost.
+    fn abstract_route(
+        &self,
+        graph: &NavGraph,
+        start: usize,
+        goal: usize,
+        from_start: &[f32],
+        to_goal: &[f32],
+        overlay: &[f32],
+    ) -> Option<(Vec<usize>, f32)> {
+        let (cs, cg) = (self.cluster_of[start], self.cluster_of[goal]);
+        // Clusters without overlay read their tables as stored.
+        let hot: Vec<bool> = self
+            .members
+            .iter()
+            .map(|m| m.iter().any(|&n| overlay.get(n).is_some_and(|&v| v != 0.0)))
+            .collect();
+        let na = self.entrances.len();
+        let (s, t) = (na, na + 1);
+        let goal_pos = graph.nodes[goal];
+        let h = |a: usize| match a {
+            _ if a == s => dist(&graph.nodes[start], &goal_pos),
+            _ if a == t => 0.0,
+            _ => dist(&graph.nodes[self.entrances[a]], &goal_pos),
+        };
+
+        let mut g = vec![f32::INFINITY; na + 2];
+        let mut came = vec![usize::MAX; na + 2];
+        let mut closed = vec![false; na + 2];
+        g[s] = 0.0;
+        let mut open = BinaryHeap::new();
+        open.push(Reverse((FOrd(h(s)), s)));
+        let mut out: Vec<(usize, f32)> = Vec::new();
+        while let Some(Reverse((_, cur))) = open.pop() {
+            if cur == t {
+                let mut hops = Vec::new();
+                let mut at = came[t];
+                while at != s {
+                    hops.push(at);
+                    at = came[at];
+                }
+                hops.reverse();
+                return Some((hops, g[t]));
+            }
+            if closed[cur] {
+                continue;
+            }
+            closed[cur] = true;
+            out.clear();
+            if cur == s {
+                for &b in &self.cluster_entrances[cs] {
+                    out.push((b, from_start[self.local[self.entrances[b]]]));
+                }
+            } else {
+                let node = self.entrances[cur];
+                if hot[self.cluster_of[node]] {
+                    out.extend(
+                        (0..self.intra[cur].len())
+                            .map(|i| (self.intra[cur][i].0, self.route_cost(cur, i, overlay).0)),
+                    );
+                } else {
+                    out.extend_from_slice(&self.intra[cur]);
+                }
+                out.extend(
+                    self.cross[cur]
+                        .iter()
+                        .map(|&(b, base)| (b, edge_cost(overlay, node, base))),
+                );
+                if self.cluster_of[node] == cg {
+                    out.push((t, to_goal[self.local[node]]));
+                }
+            }
+            for &(nb, cost) in &out {
+                let ng = g[cur] + cost;
+                if !closed[nb] && ng < g[nb] {
+                    g[nb] = ng;
+                    came[nb] = cur;
+                    open.push(Reverse((FOrd(ng + h(nb)), nb)));
+                }
+            }
+        }
+        None
+    }
+
+    /// True when `start → goal` is worth an abstract search: different clusters AND at least
+    /// a cluster width apart. Anything closer is cheap for flat A*, and the entrance detour
+    /// would dominate a short route.
+    fn long_range(&self, graph: &NavGraph, start: usize, goal: usize) -> bool {
+        self.cluster_of[start] != self.cluster_of[goal]
+            && dist(&graph.nodes[start], &graph.nodes[goal]) >= self.size
+    }
+
+    /// Path `start → goal` (node indices) under `overlay` (missing entries read 0), or `None`
+    /// if unreachable. Short queries go straight to flat A*; long-range ones search the
+    /// abstract graph and refine each hop inside its cluster.
+    pub fn path(
+        &self,
+        graph: &NavGraph,
+        start: usize,
+        goal: usize,
+        overlay: &[f32],
+    ) -> Option<Vec<usize>> {
+        let n = graph.node_count();
+        if start >= n || goal >= n {
+            return None;
+        }
+        if start == goal {
+            return Some(vec![start]);
+        }
+        if self.long_range(graph, start, goal) {
+            let (from_start, fwd) = self.local_tree(graph, start, false, overlay);
+            let (to_goal, rev) = self.local_tree(graph, goal, true, overlay);
+            let route = self.abstract_route(graph, start, goal, &from_start, &to_goal, overlay);
+            if let Some((hops, _)) = route {
+                return Some(self.refine(graph, start, goal, &hops, &fwd, &rev, overlay));
+            }
+        }
+        graph.path_weighted(start, goal, overlay)
+    }
+
+    /// Overlay-weighted cost of a node path (each hop must be a graph edge).
+    pub fn path_cost(&self, graph: &NavGraph, path: &[usize], overlay: &[f32]) -> f32 {
+        path.windows(2)
+            .map(|w| {
+                let base = graph
+                    .neighbors(w[0])
+                    .iter()
+                    .find(|&&(nb, _)| nb == w[1])
+                    .map_or(f32::INFINITY, |&(_, c)| c);
+                edge_cost(overlay, w[0], base)
+            })
+            .sum()
+    }
+
+    /// Expand the entrance sequence into graph nodes: `start`'s tree to the first entrance,
+    /// stored intra-cluster routes (re-searched under `overlay` when they cross heat) and
+    /// single boundary edges between entrances, then the goal's reverse tree from the last.
+    #[allow(clippy::too_many_arguments)]
+    fn refine(
+        &self,
+        graph: &NavGraph,
+        start: usize,
+        goal: usize,
+        hops: &[usize],
+        fwd: &[usize],
+        rev: &[usize],
+        overlay: &[f32],
+    ) -> Vec<usize> {
+        let first = self.entrances[hops[0]];
+        let mut out = self.unwind(fwd, first, start);
+        out.reverse();
+        out.push(first);
+        for w in hops.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            match self.intra[a].iter().position(|&(nb, _)| nb == b) {
+                Some(i) if self.route_cost(a, i, overlay).1 => {
+                    let (from, to) = (self.entrances[a], self.entrances[b]);
+                    let (_, parent) = self.local_tree(graph, from, false, overlay);
+                    let mut hop = self.unwind(&parent, to, from);
+                    hop.pop();
+                    hop.reverse();
+                    out.extend(hop);
+                    out.push(to);
+                }
+                Some(i) => out.extend(self.intra_paths[a][i].iter().map(|&(n, _)| n)),
+                None => out.push(self.entrances[b]),
+            }
+        }
+        let last = self.entrances[hops[hops.len() - 1]];
+        out.extend(self.unwind(rev, last, goal));
+        out
+    }
+}
+
+/// Base costs of one boundary link `p ↔ q` (`p` in the lower-numbered cluster), per direction.
+#[derive(Clone, Copy, Default)]
+struct Link {
+    up: Option<f32>,
+    down: Option<f32>,
+}
+
+/// Representative links of one cluster pair: group the links into openings (low-side nodes
+/// connected to each other through the graph) and keep the link nearest each opening's
+/// centroid, plus its two extremes when the opening is wider than [`WIDE_ENTRANCE`]. A
+/// direction the opening carries but no kept link does (one-way drops) adds the nearest
+/// link that carries it, so no opening loses a direction.
+fn representatives(
+    graph: &NavGraph,
+    links: &[((usize, usize), Link)],
+) -> Vec<((usize, usize), Link)> {
+    let low: HashSet<usize> = links.iter().map(|&((p, _), _)| p).collect();
+    let mid = |(p, q): (usize, usize)| {
+        let (a, b) = (graph.nodes[p], graph.nodes[q]);
+        [
+            (a[0] + b[0]) / 2.0,
+            (a[1] + b[1]) / 2.0,
+            (a[2] + b[2]) / 2.0,
+        ]
+    };
+    let mut seen: HashSet<usize> = HashSet::new();
+    let mut reps: Vec<((usize, usize), Link)> = Vec::new();
+    for &((root, _), _) in links {
+        if !seen.insert(root) {
+            continue;
+        }
+        let mut run: HashSet<usize> = HashSet::from([root]);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for &(w, _) in graph.neighbors(u) {
+                if low.contains(&w) && seen.insert(w) {
+                    run.insert(w);
+                    queue.push_back(w);
+                }
+            }
+        }
+        let opening: Vec<((usize, usize), Link)> = links
+            .iter()
+            .copied()
+            .filter(|&((p, _), _)| run.contains(&p))
+            .collect();
+        let mut c = [0.0f32; 3];
+        for &(k, _) in &opening {
+            let m = mid(k);
+            for (ck, mk) in c.iter_mut().zip(m) {
+                *ck += mk / opening.len() as f32;
+            }
+        }
+        let pick = |p: [f32; 3], nearest: bool, keep: &dyn Fn(&Link) -> bool| {
+            let key = |l: &&((usize, usize), Link)| FOrd(dist(&mid(l.0), &p));
+            let it = opening.iter().filter(|l| keep(&l.1));
+            if nearest {
+                it.min_by_key(key).copied()
+            } else {
+                it.max_by_key(key).copied()
+            }
+        };
+        let any = |_: &Link| true;
+        let mut chosen = vec![pick(c, true, &any).expect("non-empty opening")];
+        if opening.len() > WIDE_ENTRANCE {
+            let end_a = pick(c, false, &any).expect("non-empty opening");
+            let end_b = pick(mid(end_a.0), false, &any).expect("non-empty opening");
+            chosen.extend([end_a, end_b]);
+        }
+        let has_up = |l: &Link| l.up.is_some();
+        let has_down = |l: &Link| l.down.is_some();
+        for dir in [&has_up as &dyn Fn(&Link) -> bool, &has_down] {
+            if !chosen.iter().any(|r| dir(&r.1)) {
+                chosen.extend(pick(c, true, dir));
+            }
+        }
+        for l in chosen {
+            if !reps.iter().any(|r| r.0 == l.0) {
+                reps.push(l);
+            }
+        }
+    }
+    reps
+}
+
+/// `path_weighted`'s edge cost: base plus the overlay at the edge's source (missing reads 0).
+fn edge_cost(overlay: &[f32], from: usize, base: f32) -> f32 {
+    (base + overlay.get(from).copied().unwrap_or(0.0)).max(EPS)
+}
+
+fn dist(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SP: f32 = 24.0;
+
+    /// `w × h` 8-connected grid at the 24u spacing; `wall(x, y)` cells get no edges.
+    fn grid(w: usize, h: usize, wall: impl Fn(usize, usize) -> bool) -> NavGraph {
+        let idx = |x: usize, y: usize| y * w + x;
+        let mut nodes = Vec::new();
+        let mut adj = vec![Vec::new(); w * h];
+        for y in 0..h {
+            for x in 0..w {
+                nodes.push([x as f32 * SP, y as f32 * SP, 0.0]);
+                if wall(x, y) {
+                    continue;
+                }
+                for (dx, dy) in [
+                    (-1, -1),
+                    (0, -1),
+                    (1, -1),
+                    (-1, 0),
+                    (1, 0),
+                    (-1, 1),
+                    (0, 1),
+                    (1, 1),
+                ] {
+                    let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                    if nx < 0 || ny < 0 || nx >= w as i32 || ny >= h as i32 {
+                        continue;
+                    }
+                    let (nx, ny) = (nx as usize, ny as usize);
+                    if !wall(nx, ny) {
+                        let cost = SP * ((dx * dx + dy * dy) as f32).sqrt();
+                        adj[idx(x, y)].push((idx(nx, ny), cost));
+                    }
+                }
+            }
+        }
+        NavGraph::from_raw(nodes, adj)
+    }
+
+    /// Two walls with narrow gaps, so long routes must thread specific openings.
+    fn walled(x: usize, y: usize) -> bool {
+        (x == 17 && !matches!(y, 4 | 5 | 31)) || (y == 23 && !matches!(x, 9..=11 | 40))
+    }
+
+    fn assert_valid(g: &NavGraph, path: &[usize], start: usize, goal: usize) {
+        assert_eq!((path[0], *path.last().unwrap()), (start, goal));
+        for w in path.windows(2) {
+            assert!(
+                g.neighbors(w[0]).iter().any(|&(nb, _)| nb == w[1]),
+                "{} -> {} is not an edge",
+                w[0],
+                w[1]
+            );
+        }
+    }
+
+    fn pairs(n: usize, count: usize) -> Vec<(usize, usize)> {
+        let mut s: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut next = || {
+            s ^= s << 13;
+            s ^= s >> 7;
+            s ^= s << 17;
+            (s % n as u64) as usize
+        };
+        (0..count).map(|_| (next(), next())).collect()
+    }
+
+    /// Hierarchical cost stays within the suboptimality bound of flat A* under `overlay`.
+    /// Endpoints inside heat are skipped: leaving a hot cluster through its representative
+    /// boundary edge can pay one more hot node than the flat route's nearest exit.
+    fn assert_bounded(g: &NavGraph, hg: &HierGraph, overlay: &[f32], count: usize) {
+        let (mut sum, mut n) = (0.0, 0);
+        let hot = |i: usize| overlay.get(i).is_some_and(|&v| v != 0.0);
+        for (a, b) in pairs(g.node_count(), count) {
+            if hot(a) || hot(b) {
+                continue;
+            }
+            let flat = g.path_weighted(a, b, overlay);
+            let hier = hg.path(g, a, b, overlay);
+            assert_eq!(flat.is_some(), hier.is_some(), "reachability {a}->{b}");
+            let (Some(flat), Some(hier)) = (flat, hier) else {
+                continue;
+            };
+            assert_valid(g, &hier, a, b);
+            let (fc, hc) = (
+                hg.path_cost(g, &flat, overlay),
+                hg.path_cost(g, &hier, overlay),
+            );
+            assert!(
+                hc <= fc * 1.3 + 2.0 * SP,
+                "{a}->{b}: hier {hc} vs flat {fc}"
+            );
+            if fc > 0.0 {
+                sum += hc / fc;
+                n += 1;
+            }
+        }
+        assert!(sum / n as f32 <= 1.1, "mean ratio {}", sum / n as f32);
+    }
+
+    #[test]
+    fn hier_path_within_bounded_suboptimality_of_flat() {
+        let g = grid(48, 48, walled);
+        let hg = HierGraph::build(&g);
+        assert!(hg.cluster_count() > 1 && hg.entrance_count() < g.node_count() / 4);
+        assert_bounded(&g, &hg, &[], 300);
+    }
+
+    #[test]
+    fn overlay_applies_at_query_time() {
+        let g = grid(48, 48, |_, _| false);
+        let hg = HierGraph::build(&g);
+        // Danger blob across the straight line between the two corners' midpoints.
+        let mut overlay = vec![0.0; g.node_count()];
+        for y in 18..30 {
+            for x in 18..30 {
+                overlay[y * 48 + x] = 500.0;
+            }
+        }
+        let (a, b) = (24 * 48 + 2, 24 * 48 + 45);
+        let p = hg.path(&g, a, b, &overlay).unwrap();
+        assert!(
+            p.iter().all(|&n| overlay[n] == 0.0),
+            "route avoids the blob"
+        );
+        let calm = hg.path(&g, a, b, &[]).unwrap();
+        assert!(
+            calm.iter().any(|&n| overlay[n] > 0.0),
+            "same tables, no overlay"
+        );
+        assert_bounded(&g, &hg, &overlay, 100);
+        // A hot spot inside one cluster is detoured around locally, not through the tables.
+        let mut spot = vec![0.0; g.node_count()];
+        for y in 22..27 {
+            spot[y * 48 + 24] = 500.0;
+        }
+        let p = hg.path(&g, a, b, &spot).unwrap();
+        assert_valid(&g, &p, a, b);
+        assert!(
+            p.iter().all(|&n| spot[n] == 0.0),
+            "hop re-searched around the spot"
+        );
+    }
+
+    #[test]
+    fn unreachable_and_same_cluster_match_flat() {
+        let g = grid(48, 48, walled);
+        let hg = HierGraph::build(&g);
+        let wall_node = 23 * 48 + 30;
+        assert!(hg.path(&g, 0, wall_node, &[]).is_none());
+        assert_eq!(hg.path(&g, 0, 49, &[]), g.path(0, 49));
+        assert_eq!(hg.path(&g, 3, 3, &[]), Some(vec![3]));
+    }
+}
@@ -7,30 +7,44 @@
 pub mod bsp;
 pub mod build;
 pub mod collision;
+pub mod cover;
 pub mod deadly;
+pub mod export;
+pub mod hier;
+pub mod light;
 pub mod mapcache;
 pub mod navgraph;
 pub mod navmesh;
+pub mod navstate;
 pub mod pak;
+#[doc(hidden)]
+pub mod testmap;
+pub mod vfs;
 pub mod vis;
 
 pub use bsp::{
-    Brush, BrushSide, Bsp, BspEntity, Header, Leaf, Lump, Model, Node, Plane as BspPlane,
-    SpawnPoint, NUM_LUMPS,
+    Brush, BrushSide, Bsp, BspEntity, Face, Header, Leaf, Lump, Model, Node, Plane as BspPlane,
+    SpawnPoint, TexInfo, NUM_LUMPS,
 };
 pub use build::{
-    cached_map_nav, check_spawn_connectivity, generate_map_nav, spacing_subdir, MapNavBuild,
-    GRID_SPACING, JUMP_SPACING,
+    cached_map_nav, check_spawn_connectivity, finish_map_nav, generate_map_nav, spacing_subdir,
+    world_bounds, MapNavBuild, GRID_SPACING, JUMP_SPACING,
 };
 pub use collision::{
-    water_channel_world, CollisionModel, Trace, CONTENTS_LAVA, CONTENTS_SLIME, CONTENTS_SOLID,
-    CONTENTS_WATER, CONTENTS_WINDOW, MASK_SOLID, MASK_WATER,
+    water_channel_world, CollisionModel, Trace, CONTENTS_LADDER, CONTENTS_LAVA, CONTENTS_SLIME,
+    CONTENTS_SOLID, CONTENTS_WATER, CONTENTS_WINDOW, MASK_SOLID, MASK_WATER,
 };
+pub use cover::{VisTable, VIS_RANGE};
 pub use deadly::{floor_is_deadly, landing_strip_deadly, segment_has_floor};
+pub use hier::{HierGraph, HIER_MIN_NODES};
+pub use light::LightMap;
 pub use mapcache::{load as load_mapcache, save as save_mapcache, Fingerprint};
 pub use navgraph::{
-    walkable_stair, EdgeKind, NavGraph, RideInfo, HULL_MAXS, HULL_MINS, STAIR_MAX, STEP,
+    tiles_touching, walkable_stair, EdgeKind, NavGraph, RideInfo, RocketJumpInfo, TileKey,
+    HULL_MAXS, HULL_MINS, STAIR_MAX, STEP, TILE_COLUMNS,
 };
 pub use navmesh::{Heightfield, NavMesh, VoxelParams};
-pub use pak::Pak;
+pub use navstate::{EdgeState, MoverState, NavState};
+pub use pak::{Pak, PakWriter, Pkz};
+pub use vfs::Vfs;
 pub use vis::Pvs;
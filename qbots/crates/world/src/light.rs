@@ -0,0 +1,304 @@
+//! Lightmap sampling — the renderer's `R_LightPoint` (`gl1_light.c`) over the BSP's baked
+//! face lightmaps, without a renderer.
+//!
+//! A point's light is read off the first lit surface straight below it: the trace walks the
+//! world BSP front-to-back (`RecursiveLightPoint`), and where it crosses a node's plane the
+//! faces on that node are tested for the crossing point inside their lightmap extents
+//! (`CalcSurfaceExtents`, `gl1_model.c`). The luxel under the point, summed over the face's
+//! light styles, is the light. [`LightMap::lightlevel`] then reduces it exactly like the
+//! client's `R_SetLightLevel` → `cl_lightlevel` → `usercmd.lightlevel`.
+//!
+//! Light styles are taken at their normal brightness (`"m"` = 1.0): the bot doesn't track
+//! flicker or switched lights. Dynamic lights (muzzle flashes, rockets) are ignored.
+
+use std::ops::Range;
+
+use crate::bsp::Bsp;
+use crate::cover::VIS_EYE_Z;
+use crate::navgraph::NavGraph;
+
+/// `SURF_SKY | SURF_WARP` (`q_shared.h:388`): drawn as `SURF_DRAWSKY`/`SURF_DRAWTURB`,
+/// which `RecursiveLightPoint` skips.
+const SURF_UNLIT: i32 = 0x4 | 0x8;
+/// How far below the point `R_LightPoint` looks for a surface.
+const LIGHT_TRACE: f32 = 2048.0;
+/// `MAXLIGHTMAPS` (`files.h:404`).
+const MAX_STYLES: usize = 4;
+
+/// A face as the lightmap lookup needs it (`msurface_t`).
+struct Surface {
+    vecs: [[f32; 4]; 2],
+    texturemins: [i32; 2],
+    extents: [i32; 2],
+    styles: [u8; MAX_STYLES],
+    /// Offset of the first lightmap in [`LightMap::lighting`]; `None` = no samples.
+    samples: Option<usize>,
+    lit: bool,
+}
+
+struct LightNode {
+    normal: [f32; 3],
+    dist: f32,
+    children: [i32; 2],
+    surfaces: Range<usize>,
+}
+
+/// A map's baked lighting, queryable at any point. Build once per map with
+/// [`LightMap::from_bsp`]; it owns its data, so share it behind an `Arc`.
+pub struct LightMap {
+    nodes: Vec<LightNode>,
+    head: i32,
+    surfaces: Vec<Surface>,
+    lighting: Vec<u8>,
+}
+
+fn dot(a: [f32; 3], b: &[f32]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// `CalcSurfaceExtents`: the face's texture-space bounds snapped out to whole luxels.
+fn surface_extents(bsp: &Bsp, firstedge: i32, numedges: i16, vecs: &[[f32; 4]; 2]) -> Surface {
+    let mut mins = [f32::MAX; 2];
+    let mut maxs = [f32::MIN; 2];
+    for i in 0..numedges.max(0) as usize {
+        let Some(&e) = bsp.surfedges.get(firstedge as usize + i) else {
+            break;
+        };
+        let edge = bsp.edges.get(e.unsigned_abs() as usize);
+        let v = edge.and_then(|ed| bsp.vertexes.get(ed[usize::from(e < 0)] as usize));
+        let Some(&v) = v else { break };
+        for j in 0..2 {
+            let val = dot(v, &vecs[j]) + vecs[j][3];
+            mins[j] = mins[j].min(val);
+            maxs[j] = maxs[j].max(val);
+        }
+    }
+    let mut texturemins = [0; 2];
+    let mut extents = [0; 2];
+    for j in 0..2 {
+        if mins[j] > maxs[j] {
+            continue; // no vertices: a zero-size surface nothing can land in
+        }
+        let bmin = (mins[j] / 16.0).floor() as i32;
+        let bmax = (maxs[j] / 16.0).ceil() as i32;
+        texturemins[j] = bmin * 16;
+        extents[j] = (bmax - bmin) * 16;
+    }
+    Surface {
+        vecs: *vecs,
+        texturemins,
+        extents,
+        styles: [255; MAX_STYLES],
+        samples: None,
+        lit: false,
+    }
+}
+
+impl LightMap {
+    /// Index the world model's nodes and faces for point sampling.
+    pub fn from_bsp(bsp: &Bsp) -> Self {
+        let surfaces = bsp
+            .faces
+            .iter()
+            .map(|f| {
+                let tex = bsp.texinfo.get(f.texinfo as usize);
+                let vecs = tex.map_or([[0.0; 4]; 2], |t| t.vecs);
+                let mut s = surface_extents(bsp, f.firstedge, f.numedges, &vecs);
+                s.styles = f.styles;
+                s.samples = (f.lightofs >= 0).then_some(f.lightofs as usize);
+                s.lit = tex.is_some_and(|t| t.flags & SURF_UNLIT == 0);
+                s
+            })
+            .collect::<Vec<_>>();
+        let nodes = bsp
+            .nodes
+            .iter()
+            .map(|n| {
+                let plane = bsp.planes[n.planenum as usize];
+                let first = (n.firstface as usize).min(surfaces.len());
+                let end = (first + n.numfaces as usize).min(surfaces.len());
+                LightNode {
+                    normal: plane.normal,
+                    dist: plane.dist,
+                    children: n.children,
+                    surfaces: first..end,
+                }
+            })
+            .collect();
+        Self {
+            nodes,
+            head: bsp.models.first().map_or(-1, |m| m.headnode),
+            surfaces,
+            lighting: bsp.lighting.clone(),
+        }
+    }
+
+    /// RGB light at `point`, in lightmap units (0..255 per style): the luxel of the first
+    /// lit surface below it. A map compiled without lighting is fullbright (255); a point
+    /// with no surface within 2048u below it is black, as in `R_LightPoint`.
+    pub fn light_at(&self, point: [f32; 3]) -> [f32; 3] {
+        if self.lighting.is_empty() {
+            return [255.0; 3];
+        }
+        let end = [point[0], point[1], point[2] - LIGHT_TRACE];
+        self.recursive_light_point(self.head, point, end)
+            .unwrap_or([0.0; 3])
+    }
+
+    /// The `usercmd.lightlevel` a client standing with its eye at `eye` reports: the
+    /// brightest channel scaled by 150/255 (`R_SetLightLevel`), saturating at 255.
+    pub fn lightlevel(&self, eye: [f32; 3]) -> u8 {
+        let c = self.light_at(eye);
+        (150.0 * c[0].max(c[1]).max(c[2]) / 255.0).min(255.0) as u8
+    }
+
+    /// [`lightlevel`](Self::lightlevel) at eye height over every graph node, indexed by
+    /// node — how exposed a bot standing there is.
+    pub fn node_levels(&self, graph: &NavGraph) -> Vec<u8> {
+        graph
+            .nodes
+            .iter()
+            .map(|p| self.lightlevel([p[0], p[1], p[2] + VIS_EYE_Z]))
+            .collect()
+    }
+
+    /// `Some(color)` once the segment lands on a surface (black if it's unlit), `None` if
+    /// it reaches a leaf without crossing any.
+    fn recursive_light_point(&self, node: i32, start: [f32; 3], end: [f32; 3]) -> Option<[f32; 3]> {
+        if node < 0 {
+            return None;
+        }
+        let n = &self.nodes[node as usize];
+        let front = dot(start, &n.normal) - n.dist;
+        let back = dot(end, &n.normal) - n.dist;
+        let side = usize::from(front < 0.0);
+        if (back < 0.0) == (side == 1) {
+            return self.recursive_light_point(n.children[side], start, end);
+        }
+        let frac = front / (front - back);
+        let mid = [
+            start[0] + (end[0] - start[0]) * frac,
+            start[1] + (end[1] - start[1]) * frac,
+            start[2] + (end[2] - start[2]) * frac,
+        ];
+        // Front side first: something nearer than this plane wins.
+        if let Some(c) = self.recursive_light_point(n.children[side], start, mid) {
+            return Some(c);
+        }
+        for surf in &self.surfaces[n.surfaces.clone()] {
+            if !surf.lit {
+                continue;
+            }
+            // Truncating casts, as the engine's `int s, t`.
+            let s = (dot(mid, &surf.vecs[0]) + surf.vecs[0][3]) as i32;
+            let t = (dot(mid, &surf.vecs[1]) + surf.vecs[1][3]) as i32;
+            let ds = s - surf.texturemins[0];
+            let dt = t - surf.texturemins[1];
+            if ds < 0 || dt < 0 || ds > surf.extents[0] || dt > surf.extents[1] {
+                continue;
+            }
+            return Some(self.sample(surf, ds >> 4, dt >> 4));
+        }
+        // Go down the back side.
+        self.recursive_light_point(n.children[1 - side], mid, end)
+    }
+
+    /// Luxel `(ds, dt)` of `surf`, summed over its light styles.
+    fn sample(&self, surf: &Surface, ds: i32, dt: i32) -> [f32; 3] {
+        let mut color = [0.0; 3];
+        let Some(base) = surf.samples else {
+            return color;
+        };
+        let smax = ((surf.extents[0] >> 4) + 1) as usize;
+        let tmax = ((surf.extents[1] >> 4) + 1) as usize;
+        let mut ofs = base + 3 * (dt as usize * smax + ds as usize);
+        for _ in surf.styles.iter().take_while(|&&s| s != 255) {
+            let Some(rgb) = self.lighting.get(ofs..ofs + 3) else {
+                break;
+            };
+            for (c, &v) in color.iter_mut().zip(rgb) {
+                *c += f32::from(v);
+            }
+            ofs += 3 * smax * tmax;
+        }
+        color
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bsp::{Face, TexInfo};
+    use crate::testmap::BspBuilder;
+
+    /// A 512×512 floor slab (top at z=0) whose top face carries a lightmap: dark (10) over
+    /// x < 0, bright (200) over x ≥ 0. The builder emits no faces, so they're patched in.
+    fn lit_floor(lighting: bool) -> Bsp {
+        let mut b = BspBuilder::new();
+        b.solid([-256.0, -256.0, -16.0], [256.0, 256.0, 0.0]);
+        let mut bsp = b.build();
+        bsp.vertexes = vec![
+            [-256.0, -256.0, 0.0],
+            [256.0, -256.0, 0.0],
+            [256.0, 256.0, 0.0],
+            [-256.0, 256.0, 0.0],
+        ];
+        bsp.edges = vec![[0, 0], [0, 1], [1, 2], [2, 3], [3, 0]];
+        bsp.surfedges = vec![1, 2, 3, 4];
+        bsp.texinfo = vec![TexInfo {
+            vecs: [[1.0, 0.0, 0.0, 256.0], [0.0, 1.0, 0.0, 256.0]],
+            flags: 0,
+        }];
+        bsp.faces = vec![Face {
+            planenum: 0,
+            side: 0,
+            firstedge: 0,
+            numedges: 4,
+            texinfo: 0,
+            styles: [0, 255, 255, 255],
+            lightofs: 0,
+        }];
+        // 512/16 + 1 = 33 luxels a side.
+        if lighting {
+            for _t in 0..33 {
+                for s in 0..33 {
+                    let v = if s < 16 { 10 } else { 200 };
+                    bsp.lighting.extend_from_slice(&[v, v, v]);
+                }
+            }
+        }
+        for n in &mut bsp.nodes {
+            let p = bsp.planes[n.planenum as usize];
+            if p.normal == [0.0, 0.0, 1.0] && p.dist == 0.0 {
+                n.firstface = 0;
+                n.numfaces = 1;
+            }
+        }
+        bsp
+    }
+
+    #[test]
+    fn samples_the_luxel_under_the_point() {
+        let light = LightMap::from_bsp(&lit_floor(true));
+        assert_eq!(light.light_at([-100.0, 0.0, 46.0]), [10.0; 3]);
+        assert_eq!(light.light_at([100.0, 50.0, 46.0]), [200.0; 3]);
+        // 150 · 200/255 — what the engine client would send standing there.
+        assert_eq!(light.lightlevel([100.0, 0.0, 46.0]), 117);
+        assert_eq!(light.lightlevel([-100.0, 0.0, 46.0]), 5);
+        // Off the slab: nothing below → black.
+        assert_eq!(light.light_at([1000.0, 0.0, 46.0]), [0.0; 3]);
+    }
+
+    #[test]
+    fn unlit_map_is_fullbright_and_nodes_get_levels() {
+        let light = LightMap::from_bsp(&lit_floor(false));
+        assert_eq!(light.lightlevel([0.0, 0.0, 46.0]), 150);
+
+        let light = LightMap::from_bsp(&lit_floor(true));
+        let g = NavGraph::from_raw(
+            vec![[-128.0, 0.0, 24.0], [128.0, 0.0, 24.0]],
+            vec![vec![], vec![]],
+        );
+        assert_eq!(light.node_levels(&g), vec![5, 117]);
+    }
+}
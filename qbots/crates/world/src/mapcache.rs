@@ -21,6 +21,10 @@
 //! for each ride edge: from u32, to u32, board[3] f32, far[3] f32, dismount[3] f32, model_index u32
 //! [cont.]  teleport_count u32     (Plan 52)
 //! for each teleport edge: from u32, to u32
+//! [cont.]  rocket_jump_count u32
+//! for each rocket-jump edge: from u32, to u32, launch_yaw f32, health_cost f32
+//! [cont.]  vis_node_count u32     (0 = no visibility table)
+//! for each node: cover f32, run_count u32, (start u32, end u32) × run_count
 //! ```
 //!
 //! A fingerprint mismatch on load returns `None` — never an error — so callers
@@ -34,6 +38,7 @@ use std::path::Path;
 
 use crate::bsp::Bsp;
 use crate::build::{BRIDGE_HDIST, JUMP_SPACING, PRUNE_MAX_HD};
+use crate::cover::{VisTable, VIS_RANGE};
 use crate::navgraph::{NavGraph, CONNECT_RADIUS, STAIR_MAX, STEP, SWIM_COST_FACTOR, SWIM_SPACING};
 
 const MAGIC: &[u8; 7] = b"QBNAVC2";
@@ -87,7 +92,13 @@ const MAGIC: &[u8; 7] = b"QBNAVC2";
 // a skid-off fall ending in lava is death regardless of height; live entries were 100–280u
 // below their landings) and samples the two perpendicular rays (bots skid sideways off
 // landings, 22u lateral in telemetry). Jump/drop edge sets change → regen.
-const VERSION: u8 = 26;
+// Version 27: rocket-jump edges (`EdgeKind::RocketJump`, kept outside `adj`) serialized after
+// the teleport edges. Format grows a section → regen.
+// Version 28: node visibility + cover table (`cover::VisTable`) serialized after the rocket-jump
+// edges, plus a 14th fingerprint field (VIS_RANGE); fingerprint is now 56 bytes → regen.
+// Version 29: the visibility table pairs each node with at most its `MAX_VIS_PAIRS` nearest
+// (grid-bucketed build); far pairs in dense areas drop out → regen.
+const VERSION: u8 = 29;
 
 /// Generation-constant + BSP-structural snapshot for cache invalidation.
 #[derive(Debug, Clone, PartialEq)]
@@ -120,6 +131,9 @@ pub struct Fingerprint {
     /// `SWIM_COST_FACTOR` (f32 bits) — swim-edge cost multiplier (Plan 39). Part of the
     /// generated graph's edge costs, so changing it must invalidate stale caches.
     swim_cost_factor_bits: u32,
+    /// `VIS_RANGE` (f32 bits) — the visibility table's max traced pair distance. Changing it
+    /// changes the stored table, so it must invalidate stale caches.
+    vis_range_bits: u32,
 }
 
 impl Fingerprint {
@@ -157,9 +171,21 @@ impl Fingerprint {
             connect_radius_bits: CONNECT_RADIUS.to_bits(),
             swim_spacing_bits: SWIM_SPACING.to_bits(),
             swim_cost_factor_bits: SWIM_COST_FACTOR.to_bits(),
+            vis_range_bits: VIS_RANGE.to_bits(),
         }
     }
 
+    /// A 64-bit digest of this fingerprint and the cache format version — a compact key for
+    /// data learned on top of a graph (per-node heat), which is stale exactly when the cache is.
+    pub fn digest(&self) -> u64 {
+        let mut buf = vec![VERSION];
+        self.write(&mut buf);
+        // FNV-1a, 64-bit.
+        buf.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| {
+            (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
     fn write(&self, buf: &mut Vec<u8>) {
         for &v in &[
             self.plane_count,
@@ -175,6 +201,7 @@ impl Fingerprint {
             self.connect_radius_bits,
             self.swim_spacing_bits,
             self.swim_cost_factor_bits,
+            self.vis_range_bits,
         ] {
             buf.extend_from_slice(&v.to_le_bytes());
         }
@@ -184,7 +211,7 @@ impl Fingerprint {
         if data.len() < FP_BYTES {
             return None;
         }
-        let mut fields = [0u32; 13];
+        let mut fields = [0u32; 14];
         for (i, f) in fields.iter_mut().enumerate() {
             *f = u32::from_le_bytes(data[i * 4..i * 4 + 4].try_into().ok()?);
         }
@@ -202,15 +229,28 @@ impl Fingerprint {
             connect_radius_bits: fields[10],
             swim_spacing_bits: fields[11],
             swim_cost_factor_bits: fields[12],
+            vis_range_bits: fields[13],
         })
     }
 }
 
-/// Fingerprint on-disk size in bytes (13 × u32) — `lift_penalty_bits` removed in v20 (Plan 31).
-const FP_BYTES: usize = 52;
+/// Fingerprint on-disk size in bytes (14 × u32) — `lift_penalty_bits` removed in v20 (Plan 31),
+/// `vis_range_bits` added in v28.
+const FP_BYTES: usize = 56;
 
 /// Write a nav graph to `path`. Overwrites any existing file.
 pub fn save(path: &Path, graph: &NavGraph, fingerprint: &Fingerprint) -> io::Result<()> {
+    let buf = to_bytes(graph, fingerprint);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::File::create(path)?;
+    file.write_all(&buf)?;
+    file.flush()
+}
+
+/// The exact cache-file bytes [`save`] writes for `graph`.
+pub fn to_bytes(graph: &NavGraph, fingerprint: &Fingerprint) -> Vec<u8> {
     let (nodes, adj, jump_triples) = graph.raw_parts();
     let mut buf: Vec<u8> = Vec::with_capacity(
         8 + FP_BYTES + 4 + nodes.len() * 12 + nodes.len() * 4 + 4 + jump_triples.len() * 12,
@@ -293,12 +333,22 @@ pub fn save(path: &Path, graph: &NavGraph, fingerprint: &Fingerprint) -> io::Res
         buf.extend_from_slice(&(*to as u32).to_le_bytes());
     }
 
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+    // Rocket-jump edges: directed (from, to, launch_yaw, health_cost).
+    let rocket_jumps = graph.raw_rocket_jumps();
+    buf.extend_from_slice(&(rocket_jumps.len() as u32).to_le_bytes());
+    for (from, to, yaw, health) in &rocket_jumps {
+        buf.extend_from_slice(&(*from as u32).to_le_bytes());
+        buf.extend_from_slice(&(*to as u32).to_le_bytes());
+        buf.extend_from_slice(&yaw.to_le_bytes());
+        buf.extend_from_slice(&health.to_le_bytes());
     }
-    let mut file = fs::File::create(path)?;
-    file.write_all(&buf)?;
-    file.flush()
+
+    // Visibility + cover table (count 0 when the graph has none).
+    match graph.vis_table() {
+        Some(table) => table.write(&mut buf),
+        None => buf.extend_from_slice(&0u32.to_le_bytes()),
+    }
+    buf
 }
 
 /// Load a cached nav graph from `path`. Returns `None` if the file is absent,
@@ -416,10 +466,28 @@ fn parse(data: &[u8], expected: &Fingerprint) -> Option<NavGraph> {
         teleports.push((from, to));
     }
 
+    // Rocket-jump edges.
+    let rc = read_u32(data, &mut pos)? as usize;
+    let mut rocket_jumps = Vec::with_capacity(rc);
+    for _ in 0..rc {
+        let from = read_u32(data, &mut pos)? as usize;
+        let to = read_u32(data, &mut pos)? as usize;
+        let yaw = read_f32(data, &mut pos)?;
+        let health = read_f32(data, &mut pos)?;
+        rocket_jumps.push((from, to, yaw, health));
+    }
+
+    // Visibility + cover table; an empty one means the build didn't compute it.
+    let vis = VisTable::read(data, &mut pos)?;
+
     let mut graph = NavGraph::from_raw_with_jumps(nodes, adj, jump_triples);
     graph.set_swim_and_water(swim, water);
     graph.set_rides(rides);
     graph.set_teleports(teleports);
+    graph.set_rocket_jumps(rocket_jumps);
+    if vis.node_count() > 0 {
+        graph.set_vis_table(vis);
+    }
     Some(graph)
 }
 
@@ -450,6 +518,12 @@ mod tests {
         g.set_swim_and_water(vec![(1, 2), (2, 1)], vec![2]);
         // One-way teleporter edge 2→0 (Plan 52).
         g.add_teleport_edge(2, 0, 32.0);
+        // Rocket jump 0→1 (outside adj, so edge_count is unchanged).
+        g.set_rocket_jumps(vec![(0, 1, 0.0, 50.0)]);
+        // Open world (floor far below): every node sees every other.
+        let open = crate::collision::CollisionModel::half_space([0.0, 0.0, 1.0], -1000.0);
+        let vis = VisTable::build(&open, &g, None);
+        g.set_vis_table(vis);
         g
     }
 
@@ -468,6 +542,7 @@ mod tests {
             connect_radius_bits: CONNECT_RADIUS.to_bits(),
             swim_spacing_bits: SWIM_SPACING.to_bits(),
             swim_cost_factor_bits: SWIM_COST_FACTOR.to_bits(),
+            vis_range_bits: VIS_RANGE.to_bits(),
         }
     }
 
@@ -509,6 +584,36 @@ mod tests {
             loaded.edge_kind(2, 0),
             crate::navgraph::EdgeKind::Teleport
         ));
+
+        // Rocket-jump edge survives the round-trip.
+        assert_eq!(loaded.raw_rocket_jumps(), vec![(0, 1, 0.0, 50.0)]);
+        assert!(!loaded.is_rocket_jump_edge(1, 0));
+
+        // Visibility table survives the round-trip.
+        let vis = loaded.vis_table().expect("vis table loaded");
+        assert_eq!(Some(vis), g.vis_table());
+        assert!(vis.visible(0, 2));
+    }
+
+    #[test]
+    fn graph_without_vis_table_round_trips_without_one() {
+        let mut g = simple_graph();
+        g.set_rocket_jumps(vec![]);
+        let plain = NavGraph::from_raw(g.nodes.clone(), vec![vec![]; 3]);
+        let fp = test_fingerprint();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plain.qnav");
+        save(&path, &plain, &fp).expect("save failed");
+        assert!(load(&path, &fp).unwrap().vis_table().is_none());
+    }
+
+    #[test]
+    fn digest_tracks_the_fingerprint() {
+        let fp = test_fingerprint();
+        assert_eq!(fp.digest(), test_fingerprint().digest());
+        let mut other = fp.clone();
+        other.plane_hash ^= 1;
+        assert_ne!(fp.digest(), other.digest());
     }
 
     #[test]
@@ -5,14 +5,18 @@
 //! player-box trace, then A* over the graph.
 
 use std::cmp::{Ordering, Reverse};
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet};
+use std::sync::OnceLock;
 
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::collision::{
     CollisionModel, CONTENTS_LAVA, CONTENTS_SLIME, CONTENTS_WATER, MASK_SOLID, MASK_WATER,
 };
+use crate::cover::VisTable;
 use crate::deadly::{floor_is_deadly, landing_strip_deadly};
+use crate::hier::{HierGraph, HIER_MIN_NODES};
 // Re-exported so long-standing `world::navgraph::segment_has_floor` imports (brain, tests)
 // keep working after the Plan 63 move to `crate::deadly` (shared with the navmesh builder).
 pub use crate::deadly::segment_has_floor;
@@ -81,7 +85,34 @@ pub fn connect_cells(spacing: f32) -> i32 {
 }
 /// Minimum edge cost in the weighted pathfinder, so a popularity overlay can't
 /// drive an edge to zero/negative (Plan 08 T3).
-const EPS: f32 = 1.0;
+pub(crate) const EPS: f32 = 1.0;
+
+/// Q2 `sv_gravity` default (units/s²). Drives the rocket-jump ballistic model.
+pub const GRAVITY: f32 = 800.0;
+/// Vertical launch speed of a plain jump (`pmove.c` `PM_CheckJump`: `velocity[2] = 270`).
+pub const JUMP_VELOCITY: f32 = 270.0;
+/// Rocket splash damage AND radius (`g_weapon.c` `fire_rocket(…, 120, 120)` from
+/// `Weapon_RocketLauncher_Fire`). `T_RadiusDamage` deals `damage − 0.5·dist`, halved for the
+/// shooter's own body.
+pub const ROCKET_RADIUS_DAMAGE: f32 = 120.0;
+/// Explosion → player-bbox-center distance for the standard rocket jump (jump, then fire
+/// straight down at the floor): the rocket detonates at the floor ~24u below the origin while
+/// the bot has already left the ground, so the blast sits ~40u from the box center.
+pub const ROCKET_JUMP_BLAST_DIST: f32 = 40.0;
+/// Fraction of the ideal apex height a rocket-jump edge may climb. The model ignores the few
+/// frames of gravity between the jump and the blast and the imperfect straight-down aim, so
+/// edges stay well inside what a real launch delivers.
+pub const ROCKET_JUMP_RISE_MARGIN: f32 = 0.8;
+/// Minimum climb (units) for a rocket-jump edge — anything lower is a plain hop or stair.
+pub const ROCKET_JUMP_MIN_RISE: f32 = 56.0;
+/// Max horizontal offset (units) from the launch node to the landing ledge. Air control during
+/// the ~0.8 s rise carries the bot well past this; the cap keeps edges to genuine ledge climbs.
+pub const ROCKET_JUMP_MAX_HDIST: f32 = 160.0;
+/// Path-cost units charged per point of self-damage on a rocket-jump edge, so A* only spends
+/// health where the walk route is genuinely long (≈50 hp → +400u).
+pub const ROCKET_JUMP_HEALTH_COST: f32 = 8.0;
+/// Rockets a rocket jump consumes.
+pub const ROCKET_JUMP_AMMO: i32 = 1;
 
 /// The kind of an edge in the nav graph (Plan 14 T2).
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -106,11 +137,17 @@ pub enum EdgeKind {
     /// destination (`g_misc.c` teleporter_touch), so traversal is just "walk to the
     /// source node" and the leg completes at the destination node after the snap.
     Teleport,
+    /// Rocket-jump edge: one-way climb to a ledge no walk/jump reaches. The bot stands on the
+    /// source node facing `launch_yaw`, looks straight down, jumps and fires at its feet; the
+    /// self-knockback carries it up onto the target. Only planned through when the bot holds a
+    /// rocket launcher (see [`NavGraph::path_with_rocket_jumps`]); per-edge cost data lives in
+    /// [`RocketJumpInfo`].
+    RocketJump { launch_yaw: f32 },
 }
 
 /// Per-edge data for an [`EdgeKind::Ride`] moving-platform edge (Plan 42). The brain reads
 /// this to drive the approach → wait → board → ride → dismount sequence.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct RideInfo {
     /// Where the bot waits and boards — the platform's path endpoint nearest the source node.
     pub board: [f32; 3],
@@ -142,7 +179,39 @@ pub struct RideInfo {
     pub stand_offset: [f32; 3],
 }
 
+/// Per-edge data for an [`EdgeKind::RocketJump`] edge. `launch`/`landing` are the source and
+/// target node positions, copied in so a traversal machine needs no graph lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RocketJumpInfo {
+    /// Where the bot stands to fire (the source node).
+    pub launch: [f32; 3],
+    /// The ledge node it lands on.
+    pub landing: [f32; 3],
+    /// World-space yaw (degrees) to face while launching — toward the landing.
+    pub launch_yaw: f32,
+    /// Predicted self-damage (health points, before armor) of the launch.
+    pub health_cost: f32,
+}
+
+/// Vertical launch speed and self-damage of a rocket jump whose blast lands `blast_dist` units
+/// from the player's bbox center. `T_RadiusDamage` (`g_combat.c`): `points = (120 − 0.5·dist)`,
+/// halved for the shooter; `T_Damage` then applies the rocket-jump knockback hack
+/// `kvel = 1600·points / mass` (mass 200) on top of the 270 u/s jump.
+pub fn rocket_jump_launch(blast_dist: f32) -> (f32, f32) {
+    let points = ((ROCKET_RADIUS_DAMAGE - 0.5 * blast_dist) * 0.5).max(0.0);
+    let kick = 1600.0 * points / 200.0;
+    (JUMP_VELOCITY + kick, points)
+}
+
+/// Highest climb (units) a rocket-jump edge may span: the ballistic apex `v²/2g` of the
+/// standard launch, scaled by [`ROCKET_JUMP_RISE_MARGIN`].
+pub fn rocket_jump_max_rise() -> f32 {
+    let (vz, _) = rocket_jump_launch(ROCKET_JUMP_BLAST_DIST);
+    vz * vz / (2.0 * GRAVITY) * ROCKET_JUMP_RISE_MARGIN
+}
+
 /// A navigation graph: waypoints (bot-origin positions) + LOS-checked edges.
+#[derive(Clone)]
 pub struct NavGraph {
     pub nodes: Vec<[f32; 3]>,
     adj: Vec<Vec<(usize, f32)>>,         // (neighbor index, edge cost)
@@ -163,181 +232,129 @@ pub struct NavGraph {
     /// Directed `(pad, dest)` teleporter edges (Plan 52). Always one-way — a return
     /// trip needs its own teleporter (or a walk route).
     teleport_edges: HashSet<(usize, usize)>,
+    /// Directed rocket-jump edges → their launch data. Kept OUT of `adj`: they are only usable
+    /// while the bot holds a rocket launcher, so the plain pathfinders, component analysis and
+    /// the prune never see them — only [`Self::path_with_rocket_jumps`] walks them.
+    rocket_jumps: HashMap<(usize, usize), RocketJumpInfo>,
+    /// Outgoing rocket-jump edges per source node `(target, cost)`, mirroring `adj`.
+    rocket_jump_out: HashMap<usize, Vec<(usize, f32)>>,
+    /// Precomputed node visibility + cover ([`VisTable`]), when the build computed one.
+    vis: Option<VisTable>,
+    /// Cluster hierarchy ([`Self::hier`]), built on first use.
+    hier: OnceLock<HierGraph>,
 }
 
 impl NavGraph {
     /// Sample walkable floor on a `spacing`-unit grid over `bounds`, connect 8-neighbors
-    /// whose edge is clear and step is small. Grid sampling and edge connectivity are
-    /// both parallelized with rayon; node ordering is sorted by grid key so two runs
-    /// on the same BSP produce byte-identical caches.
+    /// whose edge is clear and step is small. Column probing fans out over
+    /// [`TILE_COLUMNS`]² tiles and edge tests over nodes, both with rayon; node ordering is
+    /// sorted by grid key so two runs on the same BSP — at any thread count — produce
+    /// byte-identical caches.
     pub fn generate(cm: &CollisionModel, bounds: ([f32; 3], [f32; 3]), spacing: f32) -> Self {
-        // --- Phase 1: collect all candidate (x, y) grid columns ------------------
-        let mut columns: Vec<(f32, f32, i32, i32)> = Vec::new();
-        let mut x = bounds.0[0];
-        while x <= bounds.1[0] {
-            let mut y = bounds.0[1];
-            while y <= bounds.1[1] {
-                let gx = (x / spacing).round() as i32;
-                let gy = (y / spacing).round() as i32;
-                columns.push((x, y, gx, gy));
-                y += spacing;
-            }
-            x += spacing;
-        }
-
-        // --- Phase 2: parallel multi-floor probe (CollisionModel is Sync) ----------
-        // Each column can yield multiple floors (e.g. roof-top + indoor level below).
-        // flat_map_iter keeps rayon work-stealing while returning a sequential iterator
-        // per column; the move captures (gx,gy) into each emitted pair.
-        // Each emitted node carries an `is_water` tag (Plan 39): dry floor nodes from
-        // `floor_waypoints_multi` (false) plus submerged/surface nodes from
-        // `water_waypoints_multi` (true). Water nodes are connected in 3-D by the swim
-        // edge pass below; dry nodes use the existing XY-grid stair logic.
-        let mut hits: Vec<((i32, i32), [f32; 3], bool)> = columns
-            .par_iter()
-            .flat_map_iter(|&(x, y, gx, gy)| {
-                let dry = floor_waypoints_multi(cm, x, y, bounds)
-                    .into_iter()
-                    .map(move |wp| ((gx, gy), wp, false));
-                let wet = water_waypoints_multi(cm, x, y, bounds)
-                    .into_iter()
-                    .map(move |wp| ((gx, gy), wp, true));
-                dry.chain(wet)
+        let hits = probe_tiles(cm, bounds, spacing, |_| true);
+        Self::connect_grid(cm, spacing, hits, None)
+    }
+
+    /// Incremental [`Self::generate`]: re-probe only the columns in `dirty` tiles against
+    /// `cm` and re-test only the edges of nodes within connection reach of them; every
+    /// other node and edge is carried over from `self`. The result is identical to a full
+    /// `generate(cm, bounds, spacing)` as long as the geometry changed only inside the
+    /// tiles [`tiles_touching`] reports for the edited region, and `bounds` and `spacing`
+    /// are the ones `self` was built with.
+    ///
+    /// `self` must be a bare grid straight out of `generate`/`regenerate_tiles` — the
+    /// build pipeline's later passes (spawn seeding, bridges, lifts, jumps) add nodes and
+    /// edges that belong to no grid column.
+    pub fn regenerate_tiles(
+        &self,
+        cm: &CollisionModel,
+        bounds: ([f32; 3], [f32; 3]),
+        spacing: f32,
+        dirty: &[TileKey],
+    ) -> Self {
+        debug_assert!(
+            self.jump_edges.is_empty() && self.ride_edges.is_empty(),
+            "regenerate_tiles needs a bare grid"
+        );
+        let dirty: HashSet<TileKey> = dirty.iter().copied().collect();
+        let mut hits: Vec<ColumnHit> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &pos)| {
+                let col = grid_column(pos, spacing);
+                (!dirty.contains(&tile_of(col.0, col.1))).then_some(ColumnHit {
+                    col,
+                    pos,
+                    water: self.water_nodes.contains(&i),
+                    old: Some(i),
+                })
             })
             .collect();
+        hits.extend(probe_tiles(cm, bounds, spacing, |t| dirty.contains(&t)));
+        Self::connect_grid(cm, spacing, hits, Some((self, &dirty)))
+    }
 
+    /// Phase 3 of [`Self::generate`]: sort the probed column hits into the deterministic
+    /// node order, then connect them. With `reuse = (old, dirty)`, nodes carried over
+    /// from `old` that are out of reach of every dirty tile keep their old edge lists
+    /// (re-indexed) instead of being re-traced.
+    fn connect_grid(
+        cm: &CollisionModel,
+        spacing: f32,
+        mut hits: Vec<ColumnHit>,
+        reuse: Option<(&NavGraph, &HashSet<TileKey>)>,
+    ) -> Self {
         // Sort by (grid_key, z, is_water) so node indices are deterministic across runs.
-        hits.sort_by(|((ax, ay), aw, awet), ((bx, by), bw, bwet)| {
-            (*ax, *ay)
-                .cmp(&(*bx, *by))
-                .then_with(|| aw[2].total_cmp(&bw[2]))
-                .then_with(|| awet.cmp(bwet))
+        hits.sort_by(|a, b| {
+            a.col
+                .cmp(&b.col)
+                .then_with(|| a.pos[2].total_cmp(&b.pos[2]))
+                .then_with(|| a.water.cmp(&b.water))
         });
 
         // Grid maps each column to all node indices it contains (ordered by z asc).
         let mut nodes: Vec<[f32; 3]> = Vec::with_capacity(hits.len());
         let mut grid: HashMap<(i32, i32), Vec<usize>> = HashMap::with_capacity(hits.len());
         let mut water_nodes: HashSet<usize> = HashSet::new();
-        for ((gx, gy), wp, is_water) in hits {
+        let mut renumber: HashMap<usize, usize> = HashMap::new();
+        for hit in &hits {
             let idx = nodes.len();
-            grid.entry((gx, gy)).or_default().push(idx);
-            if is_water {
+            grid.entry(hit.col).or_default().push(idx);
+            if hit.water {
                 water_nodes.insert(idx);
             }
-            nodes.push(wp);
+            if let Some(old) = hit.old {
+                renumber.insert(old, idx);
+            }
+            nodes.push(hit.pos);
         }
 
-        // --- Phase 3: parallel edge computation -----------------------------------
-        // For each node, look at the 8 adjacent columns and try to connect to every
-        // node in each column whose |dz| is within STAIR_MAX. This handles the case
-        // where adjacent columns have nodes on different floors: the z-distance filter
-        // keeps only floor-level peers; the stair trace rejects walls.
-        // No shared mutable state during the parallel section.
-        // Cell radius derived from the world-unit CONNECT_RADIUS, so changing `spacing`
-        // keeps the absolute connection radius constant (see CONNECT_RADIUS docs).
+        // A node's edges depend on the geometry and the nodes within `cells` columns of it;
+        // one extra column covers the hull's reach past the segment between two nodes.
         let cells = connect_cells(spacing);
-        // Each edge carries an `is_swim` flag so the sequential merge below can populate
-        // `swim_edges` (Plan 39). Water-involved pairs use 3-D swim connectivity; dry pairs
-        // keep the XY-grid stair logic.
-        let per_node: Vec<Vec<(usize, f32, bool)>> = nodes
+        let reach = cells + 1;
+        let near_dirty = |(gx, gy): (i32, i32), dirty: &HashSet<TileKey>| {
+            let (lo, hi) = (
+                tile_of(gx - reach, gy - reach),
+                tile_of(gx + reach, gy + reach),
+            );
+            (lo.0..=hi.0).any(|tx| (lo.1..=hi.1).any(|ty| dirty.contains(&(tx, ty))))
+        };
+
+        // For each node, look at the adjacent columns and try to connect to every node in
+        // each column whose |dz| is within STAIR_MAX — see `connect_node`. No shared
+        // mutable state during the parallel section. Each edge carries an `is_swim` flag
+        // so the sequential merge below can populate `swim_edges` (Plan 39).
+        let per_node: Vec<Vec<(usize, f32, bool)>> = hits
             .par_iter()
             .enumerate()
-            .map(|(i, &a)| {
-                let gx = (a[0] / spacing).round() as i32;
-                let gy = (a[1] / spacing).round() as i32;
-                let a_water = water_nodes.contains(&i);
-                let mut edges: Vec<(usize, f32, bool)> = Vec::new();
-                // Connect a neighbourhood of ±cells grid cells, not just the 8 immediate
-                // neighbours. A ±1 (24u) connection misses real walkable links that span
-                // 2-4 cells — e.g. across a ramp/step where the intermediate column has no
-                // sampled node — which fragments the graph into dozens of false components
-                // (proven by tools/compgaps: 934 missed walkable links on q2dm1). The
-                // per-pair hull/stair check below still rejects wall-separated pairs, so
-                // widening only adds genuinely walkable edges.
-                for ddx in -cells..=cells {
-                    for ddy in -cells..=cells {
-                        // Same column (0,0): only water nodes link here (vertical swim
-                        // lattice). Dry floors never stack walkably in one column.
-                        if ddx == 0 && ddy == 0 {
-                            if !a_water {
-                                continue;
-                            }
-                            if let Some(col) = grid.get(&(gx, gy)) {
-                                for &j in col {
-                                    if j == i || !water_nodes.contains(&j) {
-                                        continue;
-                                    }
-                                    if let Some(c) = try_swim_edge(cm, &a, &nodes[j], true) {
-                                        edges.push((j, c, true));
-                                    }
-                                }
-                            }
-                            continue;
-                        }
-                        let Some(col) = grid.get(&(gx + ddx, gy + ddy)) else {
-                            continue;
-                        };
-                        for &j in col {
-                            if j == i {
-                                continue;
-                            }
-                            let b = nodes[j];
-                            // Exact per-axis connection window: the cell scan over-covers
-                            // (ceil), so trim to ±CONNECT_RADIUS world units. Keeps the
-                            // absolute radius identical for every grid spacing (grid=24 →
-                            // ±72u = the old ±3 cells; grid=16 → ±72u, not the rounded 80u).
-                            if (b[0] - a[0]).abs() > CONNECT_RADIUS
-                                || (b[1] - a[1]).abs() > CONNECT_RADIUS
-                            {
-                                continue;
-                            }
-                            let b_water = water_nodes.contains(&j);
-                            // Water-involved pair (swim↔swim, or dry↔water entry/exit):
-                            // 3-D reduced-hull connectivity that bypasses the STEP/STAIR
-                            // gates. The exit edge (water-surface → dry railgun ledge) is
-                            // the critical bridge that fuses the railgun room (Plan 39 T4).
-                            if a_water || b_water {
-                                if let Some(c) = try_swim_edge(cm, &a, &b, a_water && b_water) {
-                                    edges.push((j, c, true));
-                                }
-                                continue;
-                            }
-                            let dz = b[2] - a[2];
-                            if dz.abs() > STAIR_MAX {
-                                continue; // too steep for stairs — cliff or void
-                            }
-                            let ok = if dz.abs() <= STEP {
-                                // Flat or gentle slope: try direct hull trace first.
-                                // Fall back to the step-climb trace when the direct trace
-                                // fails — stair risers can clip the diagonal even for
-                                // small height deltas.
-                                // The hull trace flies at body height and clears freely
-                                // over a lava trench narrower than CONNECT_RADIUS between
-                                // two safe rim nodes — a passing trace must ALSO show
-                                // continuous non-deadly floor (Plan 50 E1, cache v22).
-                                let t = cm.trace(&a, &b, &HULL_MINS, &HULL_MAXS, MASK_SOLID);
-                                if !t.startsolid && t.fraction >= 1.0 {
-                                    segment_has_floor(cm, a, b)
-                                } else if dz.abs() > 0.5 {
-                                    let (lower, upper) = if dz > 0.0 { (a, b) } else { (b, a) };
-                                    walkable_stair(cm, lower, upper)
-                                } else {
-                                    false
-                                }
-                            } else {
-                                // Height diff in (STEP, STAIR_MAX]: the diagonal trace
-                                // would clip stair risers. Use the step-climb trace that
-                                // mirrors Q2 pmove's up→forward movement pattern.
-                                let (lower, upper) = if dz > 0.0 { (a, b) } else { (b, a) };
-                                walkable_stair(cm, lower, upper)
-                            };
-                            if ok {
-                                edges.push((j, dist(&a, &b), false));
-                            }
-                        }
-                    }
-                }
-                edges
+            .map(|(i, hit)| match (reuse, hit.old) {
+                (Some((old, dirty)), Some(oi)) if !near_dirty(hit.col, dirty) => old.adj[oi]
+                    .iter()
+                    .map(|&(oj, cost)| (renumber[&oj], cost, old.swim_edges.contains(&(oi, oj))))
+                    .collect(),
+                _ => connect_node(cm, spacing, cells, &nodes, &grid, &water_nodes, i),
             })
             .collect();
 
@@ -365,6 +382,10 @@ impl NavGraph {
             ride_edges: HashSet::new(),
             ride_info: HashMap::new(),
             teleport_edges: HashSet::new(),
+            rocket_jumps: HashMap::new(),
+            rocket_jump_out: HashMap::new(),
+            vis: None,
+            hier: OnceLock::new(),
         }
     }
 
@@ -529,6 +550,10 @@ impl NavGraph {
             ride_edges: HashSet::new(),
             ride_info: HashMap::new(),
             teleport_edges: HashSet::new(),
+            rocket_jumps: HashMap::new(),
+            rocket_jump_out: HashMap::new(),
+            vis: None,
+            hier: OnceLock::new(),
         }
     }
 
@@ -560,6 +585,10 @@ impl NavGraph {
             ride_edges: HashSet::new(),
             ride_info: HashMap::new(),
             teleport_edges: HashSet::new(),
+            rocket_jumps: HashMap::new(),
+            rocket_jump_out: HashMap::new(),
+            vis: None,
+            hier: OnceLock::new(),
         }
     }
 
@@ -761,6 +790,87 @@ impl NavGraph {
         comps
     }
 
+    /// Articulation (cut) nodes of the UNDIRECTED view of the graph — the same view
+    /// [`Self::components`] groups over — as `(node, cut_off)` sorted by node. Removing a cut
+    /// node splits its component; `cut_off` is how many nodes then lie OUTSIDE the largest
+    /// remaining piece, so a dangling one-node stub scores 1 while a corridor between two
+    /// arenas scores the smaller arena. These are the map's choke points (a lone corridor node,
+    /// a single stair tread, a lift pad). Iterative Tarjan low-link with subtree sizes, so
+    /// q2dm-sized graphs can't blow the stack.
+    pub fn articulation_points(&self) -> Vec<(usize, usize)> {
+        let n = self.nodes.len();
+        let mut und: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (u, nbs) in self.adj.iter().enumerate() {
+            for &(v, _) in nbs {
+                if u != v {
+                    und[u].push(v);
+                    und[v].push(u);
+                }
+            }
+        }
+        for l in &mut und {
+            l.sort_unstable();
+            l.dedup();
+        }
+        const UNSEEN: usize = usize::MAX;
+        let mut disc = vec![UNSEEN; n];
+        let mut low = vec![0usize; n];
+        let mut size = vec![1usize; n];
+        // Per node: sizes of the DFS subtrees that removing it disconnects.
+        let mut pieces: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut out = Vec::new();
+        let mut time = 0usize;
+        for root in 0..n {
+            if disc[root] != UNSEEN {
+                continue;
+            }
+            disc[root] = time;
+            low[root] = time;
+            time += 1;
+            let mut visited = vec![root];
+            // (node, parent, next-neighbor cursor)
+            let mut stack: Vec<(usize, usize, usize)> = vec![(root, UNSEEN, 0)];
+            while let Some(&mut (u, parent, ref mut cursor)) = stack.last_mut() {
+                if let Some(&v) = und[u].get(*cursor) {
+                    *cursor += 1;
+                    if disc[v] == UNSEEN {
+                        disc[v] = time;
+                        low[v] = time;
+                        time += 1;
+                        visited.push(v);
+                        stack.push((v, u, 0));
+                    } else if v != parent {
+                        low[u] = low[u].min(disc[v]);
+                    }
+                } else {
+                    stack.pop();
+                    if parent != UNSEEN {
+                        low[parent] = low[parent].min(low[u]);
+                        size[parent] += size[u];
+                        if low[u] >= disc[parent] {
+                            pieces.entry(parent).or_default().push(size[u]);
+                        }
+                    }
+                }
+            }
+            // The root's every child subtree is a piece; it is a cut node only with ≥2.
+            let comp = size[root];
+            for &u in &visited {
+                let Some(p) = pieces.get(&u) else {
+                    continue;
+                };
+                if u == root && p.len() < 2 {
+                    continue;
+                }
+                let rest = comp - 1 - p.iter().sum::<usize>();
+                let largest = p.iter().copied().max().unwrap_or(0).max(rest);
+                out.push((u, comp - 1 - largest));
+            }
+        }
+        out.sort_unstable();
+        out
+    }
+
     /// Number of outgoing edges from node `idx` (for diagnostics).
     pub fn adj_count(&self, idx: usize) -> usize {
         self.adj.get(idx).map_or(0, |v| v.len())
@@ -839,15 +949,26 @@ impl NavGraph {
     /// [`Self::path_weighted`]: edge cost `cur→nb` = `(base_cost + overlay[cur]).max(EPS)`;
     /// missing overlay entries read 0.
     pub fn flood_costs_weighted(&self, from: usize, overlay: &[f32]) -> Vec<f32> {
+        self.flood_inner(&[(from, 0.0)], overlay)
+    }
+
+    /// [`Self::flood_costs`] from several sources at once, each starting at its own cost:
+    /// `g[v] = min over (s, c) of c + dist(s, v)`. Out-of-range sources are ignored.
+    pub fn flood_costs_multi(&self, sources: &[(usize, f32)]) -> Vec<f32> {
+        self.flood_inner(sources, &[])
+    }
+
+    fn flood_inner(&self, sources: &[(usize, f32)], overlay: &[f32]) -> Vec<f32> {
         let n = self.nodes.len();
         let mut g = vec![f32::INFINITY; n];
-        if from >= n {
-            return g;
-        }
         let mut closed = vec![false; n];
-        g[from] = 0.0;
         let mut open: BinaryHeap<Reverse<(FOrd, usize)>> = BinaryHeap::new();
-        open.push(Reverse((FOrd(0.0), from)));
+        for &(s, c) in sources {
+            if s < n && c < g[s] {
+                g[s] = c;
+                open.push(Reverse((FOrd(c), s)));
+            }
+        }
         while let Some(Reverse((_, cur))) = open.pop() {
             if closed[cur] {
                 continue;
@@ -1709,6 +1830,266 @@ impl NavGraph {
         added
     }
 
+    /// Detect one-way **rocket-jump** edges: ledges [`ROCKET_JUMP_MIN_RISE`]..[`rocket_jump_max_rise`]
+    /// above a dry node, within [`ROCKET_JUMP_MAX_HDIST`] horizontally, that no walk/jump route
+    /// reaches more cheaply than the launch's own cost (distance + self-damage ×
+    /// [`ROCKET_JUMP_HEALTH_COST`]). The arc is validated as three hull traces — straight up to
+    /// a step above the ledge, across, then down onto the landing node — plus the Plan 50
+    /// landing-strip check. At most one edge per 45° sector per launch node (the cheapest),
+    /// so a long ledge doesn't fan out into dozens of parallel launches.
+    ///
+    /// Edges are stored OUTSIDE `adj` (see [`Self::path_with_rocket_jumps`]). Same two-phase
+    /// shape as [`Self::detect_jump_edges`]: parallel per-node search, sequential apply.
+    /// Returns the number of edges added.
+    ///
+    /// There are no grenade-jump edges: a Q2 grenade ignores its owner's touch
+    /// (`Grenade_Touch`), so the jumper would have to stand over it for the 2.5 s fuse — not
+    /// a climb worth planning.
+    pub fn detect_rocket_jump_edges(&mut self, cm: &CollisionModel) -> usize {
+        let max_rise = rocket_jump_max_rise();
+        let (_, health) = rocket_jump_launch(ROCKET_JUMP_BLAST_DIST);
+        let cell = ROCKET_JUMP_MAX_HDIST;
+        let key = |p: &[f32; 3]| ((p[0] / cell).floor() as i32, (p[1] / cell).floor() as i32);
+        let mut buckets: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (i, p) in self.nodes.iter().enumerate() {
+            if !self.water_nodes.contains(&i) {
+                buckets.entry(key(p)).or_default().push(i);
+            }
+        }
+        let n = self.nodes.len();
+        let graph: &NavGraph = self;
+
+        // Phase 1 (PARALLEL): per launch node, the cheapest valid landing per sector.
+        let per_node: Vec<Vec<(usize, f32, RocketJumpInfo)>> = (0..n)
+            .into_par_iter()
+            .map(|a| {
+                if graph.water_nodes.contains(&a) {
+                    return Vec::new();
+                }
+                let an = graph.nodes[a];
+                let (cx, cy) = key(&an);
+                let mut cands: Vec<(usize, f32)> = Vec::new();
+                for gx in cx - 1..=cx + 1 {
+                    for gy in cy - 1..=cy + 1 {
+                        for &b in buckets.get(&(gx, gy)).map(Vec::as_slice).unwrap_or(&[]) {
+                            let bn = graph.nodes[b];
+                            let rise = bn[2] - an[2];
+                            let hd = ((bn[0] - an[0]).powi(2) + (bn[1] - an[1]).powi(2)).sqrt();
+                            if (ROCKET_JUMP_MIN_RISE..=max_rise).contains(&rise)
+                                && hd <= ROCKET_JUMP_MAX_HDIST
+                                && !graph.adj[a].iter().any(|&(nb, _)| nb == b)
+                            {
+                                cands.push((b, dist(&an, &bn) + health * ROCKET_JUMP_HEALTH_COST));
+                            }
+                        }
+                    }
+                }
+                if cands.is_empty() {
+                    return Vec::new();
+                }
+                // Only worth a rocket (and the health) if walking there is dearer.
+                let limit = cands.iter().map(|&(_, c)| c).fold(0.0f32, f32::max);
+                let walk = graph.bounded_walk_costs(a, limit);
+                let mut best: [Option<(usize, f32, RocketJumpInfo)>; 8] = [None; 8];
+                for (b, cost) in cands {
+                    if walk.get(&b).is_some_and(|&w| w <= cost) {
+                        continue;
+                    }
+                    let bn = graph.nodes[b];
+                    let yaw = (bn[1] - an[1]).atan2(bn[0] - an[0]).to_degrees();
+                    let sector = (((yaw + 180.0) / 45.0).floor() as usize).min(7);
+                    if best[sector].is_some_and(|(_, c, _)| c <= cost) {
+                        continue;
+                    }
+                    if !rocket_jump_arc_clear(cm, an, bn) {
+                        continue;
+                    }
+                    let info = RocketJumpInfo {
+                        launch: an,
+                        landing: bn,
+                        launch_yaw: yaw,
+                        health_cost: health,
+                    };
+                    best[sector] = Some((b, cost, info));
+                }
+                best.into_iter().flatten().collect()
+            })
+            .collect();
+
+        // Phase 2 (SEQUENTIAL): apply.
+        let mut added = 0;
+        for (a, list) in per_node.into_iter().enumerate() {
+            for (b, cost, info) in list {
+                self.rocket_jump_out.entry(a).or_default().push((b, cost));
+                self.rocket_jumps.insert((a, b), info);
+                added += 1;
+            }
+        }
+        added
+    }
+
+    /// Dijkstra over `adj` from `from`, settling only nodes within `limit` cost. Sparse map so
+    /// the per-node rocket-jump search doesn't allocate O(n) per launch node.
+    fn bounded_walk_costs(&self, from: usize, limit: f32) -> HashMap<usize, f32> {
+        let mut g: HashMap<usize, f32> = HashMap::new();
+        let mut open: BinaryHeap<Reverse<(FOrd, usize)>> = BinaryHeap::new();
+        g.insert(from, 0.0);
+        open.push(Reverse((FOrd(0.0), from)));
+        while let Some(Reverse((FOrd(d), cur))) = open.pop() {
+            if d > g[&cur] {
+                continue;
+            }
+            for &(nb, cost) in &self.adj[cur] {
+                let nd = d + cost.max(EPS);
+                if nd <= limit && g.get(&nb).is_none_or(|&old| nd < old) {
+                    g.insert(nb, nd);
+                    open.push(Reverse((FOrd(nd), nb)));
+                }
+            }
+        }
+        g
+    }
+
+    /// True if the directed edge `(a, b)` is a rocket-jump edge.
+    pub fn is_rocket_jump_edge(&self, a: usize, b: usize) -> bool {
+        self.rocket_jumps.contains_key(&(a, b))
+    }
+
+    /// The [`RocketJumpInfo`] for the directed rocket-jump edge `(from, to)`, if it is one.
+    pub fn rocket_jump_info(&self, from: usize, to: usize) -> Option<RocketJumpInfo> {
+        self.rocket_jumps.get(&(from, to)).copied()
+    }
+
+    /// Number of rocket-jump edges.
+    pub fn rocket_jump_count(&self) -> usize {
+        self.rocket_jumps.len()
+    }
+
+    /// Inject pre-serialized rocket-jump edges (mapcache deserialization). Each tuple is
+    /// `(from, to, launch_yaw, health_cost)`; positions and path cost are rebuilt from the nodes.
+    pub fn set_rocket_jumps(&mut self, jumps: Vec<(usize, usize, f32, f32)>) {
+        self.rocket_jumps.clear();
+        self.rocket_jump_out.clear();
+        for (a, b, launch_yaw, health_cost) in jumps {
+            if a >= self.nodes.len() || b >= self.nodes.len() {
+                continue;
+            }
+            let (an, bn) = (self.nodes[a], self.nodes[b]);
+            let cost = dist(&an, &bn) + health_cost * ROCKET_JUMP_HEALTH_COST;
+            self.rocket_jump_out.entry(a).or_default().push((b, cost));
+            self.rocket_jumps.insert(
+                (a, b),
+                RocketJumpInfo {
+                    launch: an,
+                    landing: bn,
+                    launch_yaw,
+                    health_cost,
+                },
+            );
+        }
+    }
+
+    /// The precomputed visibility/cover table, if this graph has one.
+    pub fn vis_table(&self) -> Option<&VisTable> {
+        self.vis.as_ref()
+    }
+
+    /// Attach a visibility/cover table built for this graph's nodes.
+    pub fn set_vis_table(&mut self, table: VisTable) {
+        self.vis = Some(table);
+    }
+
+    /// The cluster hierarchy for long-range weighted queries, or `None` below
+    /// [`HIER_MIN_NODES`]. Built once on first call and shared by every holder of this
+    /// graph, so call it only once the graph is final — later edits don't refresh it.
+    pub fn hier(&self) -> Option<&HierGraph> {
+        if self.node_count() < HIER_MIN_NODES {
+            return None;
+        }
+        let hier = self.hier.get_or_init(|| HierGraph::build(self));
+        debug_assert_eq!(
+            hier.node_count(),
+            self.node_count(),
+            "graph edited after hier()"
+        );
+        Some(hier)
+    }
+
+    /// Rocket-jump edges for serialization, sorted for determinism:
+    /// `(from, to, launch_yaw, health_cost)`.
+    pub fn raw_rocket_jumps(&self) -> Vec<(usize, usize, f32, f32)> {
+        let mut v: Vec<(usize, usize, f32, f32)> = self
+            .rocket_jumps
+            .iter()
+            .map(|(&(a, b), info)| (a, b, info.launch_yaw, info.health_cost))
+            .collect();
+        v.sort_by_key(|&(a, b, _, _)| (a, b));
+        v
+    }
+
+    /// A* that may also take rocket-jump edges — for a bot holding a rocket launcher with the
+    /// ammo and health to spend. `overlay` is the per-source-node additive cost (empty = none);
+    /// `node_bl`/`edge_bl` are penalised exactly like [`Self::path_excluding_edges`]. Without
+    /// any rocket-jump edges this is just that search with the overlay folded in.
+    pub fn path_with_rocket_jumps(
+        &self,
+        start: usize,
+        goal: usize,
+        overlay: &[f32],
+        node_bl: &HashSet<usize>,
+        edge_bl: &HashSet<(usize, usize)>,
+    ) -> Option<Vec<usize>> {
+        const PENALTY: f32 = 1_000_000.0;
+        if start == goal {
+            return Some(vec![start]);
+        }
+        let n = self.nodes.len();
+        let mut g = vec![f32::INFINITY; n];
+        let mut came: Vec<Option<usize>> = vec![None; n];
+        let mut closed = vec![false; n];
+        g[start] = 0.0;
+        let mut open: BinaryHeap<Reverse<(FOrd, usize)>> = BinaryHeap::new();
+        open.push(Reverse((
+            FOrd(dist(&self.nodes[start], &self.nodes[goal])),
+            start,
+        )));
+
+        while let Some(Reverse((_, cur))) = open.pop() {
+            if cur == goal {
+                return Some(reconstruct(&came, start, goal));
+            }
+            if closed[cur] {
+                continue;
+            }
+            closed[cur] = true;
+            let node_cost = overlay.get(cur).copied().unwrap_or(0.0)
+                + if node_bl.contains(&cur) { PENALTY } else { 0.0 };
+            let rj = self
+                .rocket_jump_out
+                .get(&cur)
+                .map(Vec::as_slice)
+                .unwrap_or(&[]);
+            for &(nb, cost) in self.adj[cur].iter().chain(rj) {
+                if closed[nb] {
+                    continue;
+                }
+                let edge_cost = if edge_bl.contains(&(cur, nb)) {
+                    PENALTY
+                } else {
+                    0.0
+                };
+                let ng = g[cur] + (cost + node_cost + edge_cost).max(EPS);
+                if ng < g[nb] {
+                    g[nb] = ng;
+                    came[nb] = Some(cur);
+                    let f = ng + dist(&self.nodes[nb], &self.nodes[goal]);
+                    open.push(Reverse((FOrd(f), nb)));
+                }
+            }
+        }
+        None
+    }
+
     /// Returns the [`EdgeKind`] of edge `(from, to)`. Returns `Walk` if the
     /// edge is not in the jump-edge set (or doesn't exist).
     pub fn edge_kind(&self, from: usize, to: usize) -> EdgeKind {
@@ -1720,6 +2101,10 @@ impl NavGraph {
             EdgeKind::Ride
         } else if self.teleport_edges.contains(&(from, to)) {
             EdgeKind::Teleport
+        } else if let Some(info) = self.rocket_jumps.get(&(from, to)) {
+            EdgeKind::RocketJump {
+                launch_yaw: info.launch_yaw,
+            }
         } else {
             EdgeKind::Walk
         }
@@ -1796,6 +2181,207 @@ impl NavGraph {
     }
 }
 
+/// Column-probe output for one node: its grid column, position, water tag, and — for a
+/// node carried over by [`NavGraph::regenerate_tiles`] — its index in the old graph.
+struct ColumnHit {
+    col: (i32, i32),
+    pos: [f32; 3],
+    water: bool,
+    old: Option<usize>,
+}
+
+/// Side length, in grid columns, of a generation tile: the unit [`NavGraph::generate`]
+/// fans column probing out over and [`NavGraph::regenerate_tiles`] rebuilds.
+pub const TILE_COLUMNS: i32 = 16;
+
+/// A generation tile: grid column `(gx, gy)` lies in tile
+/// `(gx.div_euclid(TILE_COLUMNS), gy.div_euclid(TILE_COLUMNS))`.
+pub type TileKey = (i32, i32);
+
+pub fn tile_of(gx: i32, gy: i32) -> TileKey {
+    (gx.div_euclid(TILE_COLUMNS), gy.div_euclid(TILE_COLUMNS))
+}
+
+/// Grid column of a world position (the key `generate` files a node under).
+fn grid_column(pos: [f32; 3], spacing: f32) -> (i32, i32) {
+    (
+        (pos[0] / spacing).round() as i32,
+        (pos[1] / spacing).round() as i32,
+    )
+}
+
+/// Every tile whose column probes can see geometry inside the world box `mins..maxs` —
+/// the `dirty` set to pass [`NavGraph::regenerate_tiles`] after editing that box. Pads
+/// by the hull half-width plus one column so the floor/hull probes of columns just
+/// outside the box are re-run too.
+pub fn tiles_touching(mins: [f32; 3], maxs: [f32; 3], spacing: f32) -> Vec<TileKey> {
+    let pad = HULL_MAXS[0] + spacing;
+    let lo = grid_column([mins[0] - pad, mins[1] - pad, 0.0], spacing);
+    let hi = grid_column([maxs[0] + pad, maxs[1] + pad, 0.0], spacing);
+    let (tlo, thi) = (tile_of(lo.0, lo.1), tile_of(hi.0, hi.1));
+    (tlo.0..=thi.0)
+        .flat_map(|tx| (tlo.1..=thi.1).map(move |ty| (tx, ty)))
+        .collect()
+}
+
+/// Phases 1–2 of [`NavGraph::generate`]: lay the `spacing` grid over `bounds` and probe
+/// every column of each tile `keep` accepts, tiles in parallel (`CollisionModel` is
+/// Sync). Each column can yield multiple floors (e.g. roof-top + indoor level below)
+/// plus water nodes (Plan 39): dry floor nodes from `floor_waypoints_multi` and
+/// submerged/surface nodes from `water_waypoints_multi`. Unsorted — `connect_grid`
+/// imposes the deterministic order.
+fn probe_tiles(
+    cm: &CollisionModel,
+    bounds: ([f32; 3], [f32; 3]),
+    spacing: f32,
+    keep: impl Fn(TileKey) -> bool,
+) -> Vec<ColumnHit> {
+    // Accumulate x/y exactly as the original single-pass loop did, so a tile rebuild
+    // probes bit-identical column coordinates.
+    // (x, y, grid column) per probe site.
+    type Column = (f32, f32, (i32, i32));
+    let mut tiles: BTreeMap<TileKey, Vec<Column>> = BTreeMap::new();
+    let mut x = bounds.0[0];
+    while x <= bounds.1[0] {
+        let mut y = bounds.0[1];
+        while y <= bounds.1[1] {
+            let col = grid_column([x, y, 0.0], spacing);
+            let tile = tile_of(col.0, col.1);
+            if keep(tile) {
+                tiles.entry(tile).or_default().push((x, y, col));
+            }
+            y += spacing;
+        }
+        x += spacing;
+    }
+    let tiles: Vec<Vec<Column>> = tiles.into_values().collect();
+    tiles
+        .par_iter()
+        .flat_map_iter(|columns| {
+            columns.iter().flat_map(move |&(x, y, col)| {
+                let dry = floor_waypoints_multi(cm, x, y, bounds)
+                    .into_iter()
+                    .map(move |pos| (pos, false));
+                let wet = water_waypoints_multi(cm, x, y, bounds)
+                    .into_iter()
+                    .map(move |pos| (pos, true));
+                dry.chain(wet).map(move |(pos, water)| ColumnHit {
+                    col,
+                    pos,
+                    water,
+                    old: None,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Edge tests for node `i` of a freshly sampled grid: every node in the ±`cells` columns
+/// around it that a walk, stair climb or swim can reach.
+fn connect_node(
+    cm: &CollisionModel,
+    spacing: f32,
+    cells: i32,
+    nodes: &[[f32; 3]],
+    grid: &HashMap<(i32, i32), Vec<usize>>,
+    water_nodes: &HashSet<usize>,
+    i: usize,
+) -> Vec<(usize, f32, bool)> {
+    let a = nodes[i];
+    let (gx, gy) = grid_column(a, spacing);
+    let a_water = water_nodes.contains(&i);
+    let mut edges: Vec<(usize, f32, bool)> = Vec::new();
+    // Connect a neighbourhood of ±cells grid cells, not just the 8 immediate
+    // neighbours. A ±1 (24u) connection misses real walkable links that span
+    // 2-4 cells — e.g. across a ramp/step where the intermediate column has no
+    // sampled node — which fragments the graph into dozens of false components
+    // (proven by tools/compgaps: 934 missed walkable links on q2dm1). The
+    // per-pair hull/stair check below still rejects wall-separated pairs, so
+    // widening only adds genuinely walkable edges.
+    for ddx in -cells..=cells {
+        for ddy in -cells..=cells {
+            // Same column (0,0): only water nodes link here (vertical swim
+            // lattice). Dry floors never stack walkably in one column.
+            if ddx == 0 && ddy == 0 {
+                if !a_water {
+                    continue;
+                }
+                if let Some(col) = grid.get(&(gx, gy)) {
+                    for &j in col {
+                        if j == i || !water_nodes.contains(&j) {
+                            continue;
+                        }
+                        if let Some(c) = try_swim_edge(cm, &a, &nodes[j], true) {
+                            edges.push((j, c, true));
+                        }
+                    }
+                }
+                continue;
+            }
+            let Some(col) = grid.get(&(gx + ddx, gy + ddy)) else {
+                continue;
+            };
+            for &j in col {
+                if j == i {
+                    continue;
+                }
+                let b = nodes[j];
+                // Exact per-axis connection window: the cell scan over-covers
+                // (ceil), so trim to ±CONNECT_RADIUS world units. Keeps the
+                // absolute radius identical for every grid spacing (grid=24 →
+                // ±72u = the old ±3 cells; grid=16 → ±72u, not the rounded 80u).
+                if (b[0] - a[0]).abs() > CONNECT_RADIUS || (b[1] - a[1]).abs() > CONNECT_RADIUS {
+                    continue;
+                }
+                let b_water = water_nodes.contains(&j);
+                // Water-involved pair (swim↔swim, or dry↔water entry/exit):
+                // 3-D reduced-hull connectivity that bypasses the STEP/STAIR
+                // gates. The exit edge (water-surface → dry railgun ledge) is
+                // the critical bridge that fuses the railgun room (Plan 39 T4).
+                if a_water || b_water {
+                    if let Some(c) = try_swim_edge(cm, &a, &b, a_water && b_water) {
+                        edges.push((j, c, true));
+                    }
+                    continue;
+                }
+                let dz = b[2] - a[2];
+                if dz.abs() > STAIR_MAX {
+                    continue; // too steep for stairs — cliff or void
+                }
+                let ok = if dz.abs() <= STEP {
+                    // Flat or gentle slope: try direct hull trace first.
+                    // Fall back to the step-climb trace when the direct trace
+                    // fails — stair risers can clip the diagonal even for
+                    // small height deltas.
+                    // The hull trace flies at body height and clears freely
+                    // over a lava trench narrower than CONNECT_RADIUS between
+                    // two safe rim nodes — a passing trace must ALSO show
+                    // continuous non-deadly floor (Plan 50 E1, cache v22).
+                    let t = cm.trace(&a, &b, &HULL_MINS, &HULL_MAXS, MASK_SOLID);
+                    if !t.startsolid && t.fraction >= 1.0 {
+                        segment_has_floor(cm, a, b)
+                    } else if dz.abs() > 0.5 {
+                        let (lower, upper) = if dz > 0.0 { (a, b) } else { (b, a) };
+                        walkable_stair(cm, lower, upper)
+                    } else {
+                        false
+                    }
+                } else {
+                    // Height diff in (STEP, STAIR_MAX]: the diagonal trace
+                    // would clip stair risers. Use the step-climb trace that
+                    // mirrors Q2 pmove's up→forward movement pattern.
+                    let (lower, upper) = if dz > 0.0 { (a, b) } else { (b, a) };
+                    walkable_stair(cm, lower, upper)
+                };
+                if ok {
+                    edges.push((j, dist(&a, &b), false));
+                }
+            }
+        }
+    }
+    edges
+}
+
 /// Sample ALL walkable floors at grid column (x, y) by probing downward repeatedly,
 /// stepping through solid-brush layers to reach indoor floors.
 ///
@@ -2226,6 +2812,34 @@ fn jump_down_link(
     None
 }
 
+/// Validate a rocket-jump arc from `lo` up onto `hi` (see
+/// [`NavGraph::detect_rocket_jump_edges`]): hull-clear straight up to a step above the landing
+/// height, across to above the landing, then down onto it; the landing strip must not be deadly.
+fn rocket_jump_arc_clear(cm: &CollisionModel, lo: [f32; 3], hi: [f32; 3]) -> bool {
+    // Lift the start 1u: a node origin sits exactly 24u above its floor, so a hull trace
+    // from it is flush with the floor and reports startsolid.
+    let lo = [lo[0], lo[1], lo[2] + 1.0];
+    let top = hi[2] + STEP;
+    let up = [lo[0], lo[1], top];
+    let over = [hi[0], hi[1], top];
+    let clear = |a: &[f32; 3], b: &[f32; 3]| {
+        let t = cm.trace(a, b, &HULL_MINS, &HULL_MAXS, MASK_SOLID);
+        !t.startsolid && t.fraction >= 0.95
+    };
+    if !(clear(&lo, &up) && clear(&up, &over) && clear(&over, &hi)) {
+        return false;
+    }
+    let dx = hi[0] - lo[0];
+    let dy = hi[1] - lo[1];
+    let h = (dx * dx + dy * dy).sqrt();
+    let travel = if h < 1.0 {
+        [0.0, 0.0]
+    } else {
+        [dx / h, dy / h]
+    };
+    !landing_strip_deadly(cm, hi, travel)
+}
+
 fn dist(a: &[f32; 3], b: &[f32; 3]) -> f32 {
     dist2(a, b).sqrt()
 }
@@ -2247,7 +2861,7 @@ fn reconstruct(came: &[Option<usize>], start: usize, goal: usize) -> Vec<usize>
 
 // f32 ordering wrapper for the BinaryHeap (no NaN in our costs).
 #[derive(Clone, Copy, PartialEq)]
-struct FOrd(f32);
+pub(crate) struct FOrd(pub(crate) f32);
 impl Eq for FOrd {}
 impl PartialOrd for FOrd {
     fn partial_cmp(&self, o: &Self) -> Option<Ordering> {
@@ -2388,6 +3002,24 @@ mod tests {
         assert_eq!(g.path_weighted(0, 3, &overlay).unwrap(), vec![0, 2, 3]);
     }
 
+    /// Several sources, each with its own starting cost: every node takes the cheapest.
+    #[test]
+    fn flood_costs_multi_takes_the_cheapest_source() {
+        // 0 —64— 1 —64— 2 —64— 3.
+        let g = NavGraph::from_raw(
+            (0..4).map(|i| [i as f32 * 64.0, 0.0, 0.0]).collect(),
+            vec![
+                vec![(1, 64.0)],
+                vec![(0, 64.0), (2, 64.0)],
+                vec![(1, 64.0), (3, 64.0)],
+                vec![(2, 64.0)],
+            ],
+        );
+        let c = g.flood_costs_multi(&[(0, 100.0), (3, 0.0), (99, 0.0)]);
+        assert_eq!(c, vec![100.0, 128.0, 64.0, 0.0]);
+        assert_eq!(g.flood_costs_multi(&[(0, 0.0)]), g.flood_costs(0));
+    }
+
     /// Plan 39: a water channel between two dry ledges. `generate` must sample water nodes,
     /// connect them with swim edges, bridge the ledges through the water (entry/exit), and
     /// A* must find a path from the left ledge to the right ledge that crosses water.
@@ -2432,6 +3064,70 @@ mod tests {
         );
     }
 
+    /// A 1056² walled floor spanning several generation tiles, with a raised step block and
+    /// a water pool; `pillar` adds a wall segment in one corner.
+    fn tiled_world(pillar: bool) -> (CollisionModel, ([f32; 3], [f32; 3])) {
+        let mut b = crate::testmap::BspBuilder::new();
+        b.solid([-528.0, -528.0, -64.0], [528.0, 528.0, 0.0])
+            .solid([-528.0, -528.0, 0.0], [-512.0, 528.0, 128.0])
+            .solid([512.0, -528.0, 0.0], [528.0, 528.0, 128.0])
+            .solid([-512.0, -528.0, 0.0], [512.0, -512.0, 128.0])
+            .solid([-512.0, 512.0, 0.0], [512.0, 528.0, 128.0])
+            .solid([-128.0, -128.0, 0.0], [128.0, 128.0, 16.0])
+            .water([200.0, 200.0, 0.0], [360.0, 360.0, 48.0]);
+        if pillar {
+            b.solid([-400.0, -400.0, 0.0], [-240.0, -368.0, 128.0]);
+        }
+        let bsp = b.build();
+        let bounds = (bsp.models[0].mins, bsp.models[0].maxs);
+        (CollisionModel::from_bsp(&bsp), bounds)
+    }
+
+    fn assert_same_grid(a: &NavGraph, b: &NavGraph) {
+        assert_eq!(a.raw_parts(), b.raw_parts());
+        assert_eq!(a.raw_swim_and_water(), b.raw_swim_and_water());
+    }
+
+    #[test]
+    fn generate_is_identical_at_any_thread_count() {
+        let (cm, bounds) = tiled_world(false);
+        let parallel = NavGraph::generate(&cm, bounds, 32.0);
+        let serial = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap()
+            .install(|| NavGraph::generate(&cm, bounds, 32.0));
+        assert!((0..parallel.node_count()).any(|i| parallel.is_water_node(i)));
+        assert_same_grid(&parallel, &serial);
+    }
+
+    #[test]
+    fn regenerate_tiles_matches_full_generate() {
+        let (before_cm, bounds) = tiled_world(false);
+        let (after_cm, after_bounds) = tiled_world(true);
+        assert_eq!(bounds, after_bounds);
+        let before = NavGraph::generate(&before_cm, bounds, 32.0);
+        let full = NavGraph::generate(&after_cm, bounds, 32.0);
+        assert_ne!(
+            before.raw_parts(),
+            full.raw_parts(),
+            "the pillar changes the grid"
+        );
+
+        let dirty = tiles_touching([-400.0, -400.0, 0.0], [-240.0, -368.0, 128.0], 32.0);
+        let total = tiles_touching(bounds.0, bounds.1, 32.0).len();
+        assert!(
+            dirty.len() < total,
+            "{} of {total} tiles dirty",
+            dirty.len()
+        );
+        let incremental = before.regenerate_tiles(&after_cm, bounds, 32.0, &dirty);
+        assert_same_grid(&incremental, &full);
+        // Undoing the edit is just as incremental.
+        let undone = full.regenerate_tiles(&before_cm, bounds, 32.0, &dirty);
+        assert_same_grid(&undone, &before);
+    }
+
     #[test]
     fn nearest_picks_closest() {
         let g = NavGraph::from_raw(
@@ -2667,6 +3363,104 @@ mod tests {
         // We just verify edge_kind for the pair we added.
     }
 
+    /// Choke points: the middle of a line and the shared vertex of a bow-tie are cut nodes; a
+    /// cycle has none. `cut_off` counts the nodes outside the biggest remaining piece. One-way edges count (undirected view, like `components`).
+    #[test]
+    fn articulation_points_find_choke_nodes() {
+        let line = NavGraph::from_raw(
+            vec![[0.0; 3]; 3],
+            vec![vec![(1, 1.0)], vec![(0, 1.0), (2, 1.0)], vec![(1, 1.0)]],
+        );
+        assert_eq!(line.articulation_points(), vec![(1, 1)]);
+
+        let mut ring = NavGraph::from_raw(vec![[0.0; 3]; 4], vec![vec![]; 4]);
+        for i in 0..4 {
+            ring.add_edge(i, (i + 1) % 4, 1.0);
+        }
+        assert!(ring.articulation_points().is_empty());
+
+        // Bow-tie: triangles {0,1,2} and {2,3,4} share node 2; 4→5 is a one-way drop. Cutting
+        // 2 strands the {0,1} side; cutting 4 strands only 5.
+        let mut bow = NavGraph::from_raw(vec![[0.0; 3]; 6], vec![vec![]; 6]);
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2)] {
+            bow.add_edge(a, b, 1.0);
+        }
+        bow.adj[4].push((5, 1.0));
+        assert_eq!(bow.articulation_points(), vec![(2, 2), (4, 1)]);
+    }
+
+    /// Standard launch: 50 hp self-damage, 670 u/s up → a ~224u usable climb.
+    #[test]
+    fn rocket_jump_launch_model() {
+        let (vz, hp) = rocket_jump_launch(ROCKET_JUMP_BLAST_DIST);
+        assert_eq!(hp, 50.0);
+        assert_eq!(vz, 670.0);
+        let rise = rocket_jump_max_rise();
+        assert!((220.0..230.0).contains(&rise), "max rise {rise}");
+        // Out of blast range: no kick, no damage.
+        assert_eq!(rocket_jump_launch(400.0), (JUMP_VELOCITY, 0.0));
+    }
+
+    fn ledge_pair(height: f32) -> (CollisionModel, NavGraph) {
+        let cm = crate::collision::ledge_world(height);
+        let g = NavGraph::from_raw(
+            vec![[0.0, 0.0, 24.0], [112.0, 0.0, height + 24.0]],
+            vec![vec![], vec![]],
+        );
+        (cm, g)
+    }
+
+    /// A 160u ledge is a one-way rocket jump up; A* only uses it when asked to.
+    #[test]
+    fn rocket_jump_edge_climbs_ledge() {
+        let (cm, mut g) = ledge_pair(160.0);
+        assert_eq!(g.detect_rocket_jump_edges(&cm), 1);
+        assert!(g.is_rocket_jump_edge(0, 1));
+        assert!(!g.is_rocket_jump_edge(1, 0), "rocket jumps only go up");
+        assert!(matches!(g.edge_kind(0, 1), EdgeKind::RocketJump { .. }));
+        let info = g.rocket_jump_info(0, 1).unwrap();
+        assert!(info.launch_yaw.abs() < 1.0, "launch faces +x");
+        assert_eq!(info.health_cost, 50.0);
+
+        let none = HashSet::new();
+        let edges = HashSet::new();
+        assert_eq!(g.path(0, 1), None, "plain A* never rocket-jumps");
+        assert_eq!(
+            g.path_with_rocket_jumps(0, 1, &[], &none, &edges),
+            Some(vec![0, 1])
+        );
+        assert_eq!(g.path_with_rocket_jumps(1, 0, &[], &none, &edges), None);
+    }
+
+    /// Too tall for the launch (400u) → no edge.
+    #[test]
+    fn rocket_jump_rejects_unreachable_ledge() {
+        let (cm, mut g) = ledge_pair(400.0);
+        assert_eq!(g.detect_rocket_jump_edges(&cm), 0);
+    }
+
+    /// A cheap walk route up makes the rocket jump not worth its health.
+    #[test]
+    fn rocket_jump_skipped_when_walk_is_cheaper() {
+        let (cm, mut g) = ledge_pair(160.0);
+        let mid = g.add_node([40.0, 0.0, 100.0]);
+        g.add_edge(0, mid, 100.0);
+        g.add_edge(mid, 1, 100.0);
+        assert_eq!(g.detect_rocket_jump_edges(&cm), 0);
+    }
+
+    /// Serialized rocket jumps rebuild identical info.
+    #[test]
+    fn rocket_jump_raw_roundtrip() {
+        let (cm, mut g) = ledge_pair(160.0);
+        g.detect_rocket_jump_edges(&cm);
+        let raw = g.raw_rocket_jumps();
+        let (_, mut h) = ledge_pair(160.0);
+        h.set_rocket_jumps(raw.clone());
+        assert_eq!(h.raw_rocket_jumps(), raw);
+        assert_eq!(h.rocket_jump_info(0, 1), g.rocket_jump_info(0, 1));
+    }
+
     /// Plan 52: the floor probe samples a V-groove duct via the hull-rest fallback —
     /// the point floor at the groove seam is too deep for the 32×32 hull (stationary
     /// check startsolid), but the hull rests on the slopes 16u higher, like pmove.
@@ -0,0 +1,509 @@
+//! Live mover state over a frozen [`NavGraph`]: which door, lift and train edges are
+//! passable *right now*.
+//!
+//! The graph is built once per map from the world model alone, so a closed `func_door`
+//! never cuts the walk edges through its doorway and a ride edge never knows where its
+//! platform is. [`NavState`] resolves every mover brush entity (`func_door`, `func_plat`,
+//! vertical-door lifts, `func_train`) to the graph edges it gates, then reads each
+//! mover's live **wire origin** from frame entities (a brush model's origin is its offset
+//! from the BSP pose — `g_func.c` moves the entity, never the brush) and classifies each
+//! gated edge as [`EdgeState::Open`], [`EdgeState::Wait`] (closed for now, opens on its
+//! own or when approached) or [`EdgeState::Blocked`] (a triggered door that is shut).
+//!
+//! One `NavState` per bot: each sees movers through its own PVS. A mover unseen for
+//! [`STALE_FRAMES`] reverts to [`MoverState::Unknown`], whose edges count as open — the
+//! same optimism the graph had before, so out-of-sight state never strands a route.
+
+use std::collections::HashMap;
+
+use crate::bsp::{Bsp, BspEntity};
+use crate::navgraph::{NavGraph, HULL_MAXS, HULL_MINS};
+
+/// Frames (10 Hz) a mover may go unseen before its state reverts to `Unknown`.
+pub const STALE_FRAMES: u32 = 20;
+/// Wire-origin distance (units) within which a door counts as sitting at a rest pose.
+const POSE_TOL: f32 = 2.0;
+/// Pad-height tolerance (units) for "the lift is at the board level".
+const LIFT_LEVEL_TOL: f32 = 16.0;
+/// A train is at the board corner within this distance of [`crate::RideInfo::board_ent`]
+/// (`brain::ride::platform_present` uses the same radius).
+const TRAIN_AT_CORNER: f32 = 48.0;
+
+/// Whether a gated edge can be walked or ridden now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeState {
+    Open,
+    /// Closed for now, but it opens without help: an auto-door shut or swinging, a lift
+    /// pad at the other level, a train away from the board corner. Hold, don't give up.
+    Wait,
+    /// Shut until something else (a button, a trigger) opens it. Plan around it.
+    Blocked,
+}
+
+/// Last observed state of one mover.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoverState {
+    /// Not seen within [`STALE_FRAMES`] (out of PVS, or never).
+    Unknown,
+    DoorClosed,
+    DoorMoving,
+    DoorOpen,
+    /// A lift whose pad top surface is at `pad_z`.
+    Lift {
+        pad_z: f32,
+    },
+    /// A train at wire origin `origin`.
+    Train {
+        origin: [f32; 3],
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MoverKind {
+    /// `open` is the wire origin at the open pose; `triggered` doors (with a
+    /// `targetname`) only open from a button or trigger, never by walking up.
+    Door {
+        open: [f32; 3],
+        triggered: bool,
+    },
+    /// `top` is the pad's top surface z in the BSP pose.
+    Lift {
+        top: f32,
+    },
+    Train,
+}
+
+#[derive(Debug, Clone)]
+struct Mover {
+    model: u32,
+    kind: MoverKind,
+    origin: [f32; 3],
+    last_seen: Option<u32>,
+}
+
+/// What a gated edge needs from its mover.
+#[derive(Debug, Clone, Copy)]
+enum Gate {
+    /// Walk edge through a door's closed volume.
+    Door,
+    /// Vertical ride edge boarding at bot-origin height `board_z`.
+    Lift { board_z: f32 },
+    /// Train ride edge boarding when the train sits at `board_ent`.
+    Train { board_ent: [f32; 3] },
+}
+
+/// Live traversability of the mover-gated edges of one map's [`NavGraph`].
+#[derive(Debug, Clone)]
+pub struct NavState {
+    movers: Vec<Mover>,
+    by_model: HashMap<u32, usize>,
+    /// Directed edge → (mover index, gate).
+    edges: HashMap<(usize, usize), (usize, Gate)>,
+    frame: u32,
+    revision: u64,
+}
+
+/// Inline model number of a brush entity's `"model" "*N"`.
+fn inline_model(entity: &BspEntity) -> Option<u32> {
+    entity
+        .fields
+        .get("model")
+        .and_then(|s| s.strip_prefix('*'))
+        .and_then(|s| s.trim().parse().ok())
+}
+
+fn field_f32(entity: &BspEntity, key: &str) -> Option<f32> {
+    entity.fields.get(key).and_then(|s| s.trim().parse().ok())
+}
+
+/// `G_SetMovedir` (`g_utils.c`): `angle` -1 is up, -2 down, anything else a yaw.
+fn movedir(entity: &BspEntity) -> [f32; 3] {
+    match field_f32(entity, "angle").unwrap_or(0.0) {
+        a if (a + 1.0).abs() < 0.5 => [0.0, 0.0, 1.0],
+        a if (a + 2.0).abs() < 0.5 => [0.0, 0.0, -1.0],
+        yaw => {
+            let (s, c) = yaw.to_radians().sin_cos();
+            [c, s, 0.0]
+        }
+    }
+}
+
+/// Does the segment `a→b` pass through the box `mins..maxs` (slab test)?
+fn segment_hits_box(a: [f32; 3], b: [f32; 3], mins: [f32; 3], maxs: [f32; 3]) -> bool {
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for k in 0..3 {
+        let d = b[k] - a[k];
+        if d.abs() < 1e-6 {
+            if a[k] < mins[k] || a[k] > maxs[k] {
+                return false;
+            }
+            continue;
+        }
+        let (mut lo, mut hi) = ((mins[k] - a[k]) / d, (maxs[k] - a[k]) / d);
+        if lo > hi {
+            std::mem::swap(&mut lo, &mut hi);
+        }
+        t0 = t0.max(lo);
+        t1 = t1.min(hi);
+        if t0 > t1 {
+            return false;
+        }
+    }
+    true
+}
+
+fn dist(a: [f32; 3], b: [f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+impl NavState {
+    /// Resolve `bsp`'s movers to the edges of `graph` they gate: walk edges whose hull
+    /// sweep crosses a horizontal `func_door`'s closed volume, and the ride edges
+    /// `build::add_elevator_edges`/`add_train_edges` tagged with the mover's model.
+    pub fn new(graph: &NavGraph, bsp: &Bsp) -> Self {
+        let mut state = NavState {
+            movers: Vec::new(),
+            by_model: HashMap::new(),
+            edges: HashMap::new(),
+            frame: 0,
+            revision: 0,
+        };
+
+        // Ride edges already carry their mover's model index.
+        for (a, b, info) in graph.raw_rides() {
+            if info.ladder || info.model_index == 0 {
+                continue;
+            }
+            let Some(model) = bsp.models.get(info.model_index as usize) else {
+                continue;
+            };
+            let (kind, gate) = if info.vertical {
+                (
+                    // Loaded model bounds carry yquake2's 1u collision margin.
+                    MoverKind::Lift {
+                        top: model.maxs[2] - 1.0,
+                    },
+                    Gate::Lift {
+                        board_z: info.board[2],
+                    },
+                )
+            } else {
+                (
+                    MoverKind::Train,
+                    Gate::Train {
+                        board_ent: info.board_ent,
+                    },
+                )
+            };
+            let m = state.add_mover(info.model_index, kind);
+            state.edges.insert((a, b), (m, gate));
+        }
+
+        // Horizontal doors: their doorway floor is world geometry, so the grid walked
+        // straight through them. Gate every walk edge whose hull sweep meets the closed brush.
+        for entity in bsp.find_class("func_door") {
+            let dir = movedir(entity);
+            let Some(n) = inline_model(entity) else {
+                continue;
+            };
+            let Some(model) = bsp.models.get(n as usize) else {
+                continue;
+            };
+            if dir[2] != 0.0 {
+                continue; // vertical door = a lift, gated by its ride edges above
+            }
+            let size: Vec<f32> = (0..3).map(|k| model.maxs[k] - model.mins[k]).collect();
+            let travel = (0..3).map(|k| dir[k] * size[k]).sum::<f32>().abs()
+                - field_f32(entity, "lip").unwrap_or(8.0);
+            let open = dir.map(|d| d * travel);
+            let triggered = entity.fields.contains_key("targetname");
+            let mins: [f32; 3] = std::array::from_fn(|k| model.mins[k] - HULL_MAXS[k]);
+            let maxs: [f32; 3] = std::array::from_fn(|k| model.maxs[k] - HULL_MINS[k]);
+            let mut gated = Vec::new();
+            for a in 0..graph.node_count() {
+                for &(b, _) in graph.neighbors(a) {
+                    if !graph.is_ride_edge(a, b)
+                        && !graph.is_teleport_edge(a, b)
+                        && segment_hits_box(graph.nodes[a], graph.nodes[b], mins, maxs)
+                    {
+                        gated.push((a, b));
+                    }
+                }
+            }
+            if gated.is_empty() {
+                continue;
+            }
+            let m = state.add_mover(n, MoverKind::Door { open, triggered });
+            for e in gated {
+                state.edges.insert(e, (m, Gate::Door));
+            }
+        }
+        state
+    }
+
+    /// Index of the mover for inline model `model`, registering it on first sight.
+    fn add_mover(&mut self, model: u32, kind: MoverKind) -> usize {
+        if let Some(&m) = self.by_model.get(&model) {
+            return m;
+        }
+        self.movers.push(Mover {
+            model,
+            kind,
+            origin: [0.0; 3],
+            last_seen: None,
+        });
+        self.by_model.insert(model, self.movers.len() - 1);
+        self.movers.len() - 1
+    }
+
+    /// Feed one frame's brush-model entities as `(inline model N, wire origin)` pairs —
+    /// every visible entity whose model configstring is `*N`. Call once per frame, even
+    /// with nothing visible, so unseen movers age toward `Unknown`.
+    pub fn observe(&mut self, seen: impl IntoIterator<Item = (u32, [f32; 3])>) {
+        let before: Vec<MoverState> = (0..self.movers.len()).map(|m| self.state_of(m)).collect();
+        self.frame += 1;
+        for (model, origin) in seen {
+            if let Some(&m) = self.by_model.get(&model) {
+                self.movers[m].origin = origin;
+                self.movers[m].last_seen = Some(self.frame);
+            }
+        }
+        if (0..self.movers.len()).any(|m| self.state_of(m) != before[m]) {
+            self.revision += 1;
+        }
+    }
+
+    /// Bumped whenever any mover's state changes — compare to skip replans when nothing moved.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Number of movers that gate at least one edge.
+    pub fn mover_count(&self) -> usize {
+        self.movers.len()
+    }
+
+    /// Live state of the mover with inline model `model`, if it gates any edge.
+    pub fn mover_state(&self, model: u32) -> Option<MoverState> {
+        self.by_model.get(&model).map(|&m| self.state_of(m))
+    }
+
+    fn state_of(&self, m: usize) -> MoverState {
+        let mover = &self.movers[m];
+        let fresh = mover
+            .last_seen
+            .is_some_and(|f| self.frame - f <= STALE_FRAMES);
+        if !fresh {
+            return MoverState::Unknown;
+        }
+        match mover.kind {
+            MoverKind::Door { open, .. } => {
+                if dist(mover.origin, [0.0; 3]) <= POSE_TOL {
+                    MoverState::DoorClosed
+                } else if dist(mover.origin, open) <= POSE_TOL {
+                    MoverState::DoorOpen
+                } else {
+                    MoverState::DoorMoving
+                }
+            }
+            MoverKind::Lift { top } => MoverState::Lift {
+                pad_z: top + mover.origin[2],
+            },
+            MoverKind::Train => MoverState::Train {
+                origin: mover.origin,
+            },
+        }
+    }
+
+    /// Can the directed edge `a→b` be taken now? Edges no mover gates are always `Open`.
+    pub fn edge_state(&self, a: usize, b: usize) -> EdgeState {
+        let Some(&(m, gate)) = self.edges.get(&(a, b)) else {
+            return EdgeState::Open;
+        };
+        match (self.state_of(m), gate) {
+            (MoverState::Unknown, _) | (MoverState::DoorOpen, _) => EdgeState::Open,
+            (MoverState::DoorMoving, _) => EdgeState::Wait,
+            (MoverState::DoorClosed, _) => match self.movers[m].kind {
+                MoverKind::Door {
+                    triggered: true, ..
+                } => EdgeState::Blocked,
+                _ => EdgeState::Wait,
+            },
+            // Bot origin stands 24u above the pad's top surface.
+            (MoverState::Lift { pad_z }, Gate::Lift { board_z }) => {
+                if (pad_z + 24.0 - board_z).abs() <= LIFT_LEVEL_TOL {
+                    EdgeState::Open
+                } else {
+                    EdgeState::Wait
+                }
+            }
+            (MoverState::Train { origin }, Gate::Train { board_ent }) => {
+                if dist(origin, board_ent) <= TRAIN_AT_CORNER {
+                    EdgeState::Open
+                } else {
+                    EdgeState::Wait
+                }
+            }
+            _ => EdgeState::Open,
+        }
+    }
+
+    /// Every gated directed edge currently in `state`, sorted.
+    pub fn edges_in(&self, state: EdgeState) -> Vec<(usize, usize)> {
+        let mut out: Vec<(usize, usize)> = self
+            .edges
+            .keys()
+            .copied()
+            .filter(|&(a, b)| self.edge_state(a, b) == state)
+            .collect();
+        out.sort_unstable();
+        out
+    }
+
+    /// Inline model numbers of every gating mover, for callers that filter frame entities.
+    pub fn mover_models(&self) -> impl Iterator<Item = u32> + '_ {
+        self.movers.iter().map(|m| m.model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::CONTENTS_SOLID;
+    use crate::navgraph::RideInfo;
+    use crate::testmap::BspBuilder;
+
+    /// A corridor along x with a 16u-thick door across it at x=0 (model `*1`), opening
+    /// sideways along +y; walk nodes either side and one beside the corridor.
+    fn door_world(triggered: bool) -> (NavGraph, Bsp) {
+        let mut b = BspBuilder::new();
+        b.solid([-256.0, -64.0, -16.0], [256.0, 64.0, 0.0]);
+        let fields: &[(&str, &str)] = if triggered {
+            &[("angle", "90"), ("targetname", "gate")]
+        } else {
+            &[("angle", "90")]
+        };
+        b.brush_entity(
+            "func_door",
+            fields,
+            CONTENTS_SOLID,
+            [-8.0, -64.0, 0.0],
+            [8.0, 64.0, 128.0],
+        );
+        let nodes = vec![
+            [-48.0, 0.0, 24.0],
+            [48.0, 0.0, 24.0],
+            [-48.0, 200.0, 24.0],
+            [-144.0, 0.0, 24.0],
+        ];
+        let adj = vec![
+            vec![(1, 96.0), (2, 200.0), (3, 96.0)],
+            vec![(0, 96.0)],
+            vec![(0, 200.0)],
+            vec![(0, 96.0)],
+        ];
+        (NavGraph::from_raw(nodes, adj), b.build())
+    }
+
+    #[test]
+    fn door_gates_only_the_edges_through_it() {
+        let (g, bsp) = door_world(false);
+        let mut s = NavState::new(&g, &bsp);
+        assert_eq!(s.mover_count(), 1);
+        // Never seen: optimistic.
+        assert_eq!(s.mover_state(1), Some(MoverState::Unknown));
+        assert_eq!(s.edge_state(0, 1), EdgeState::Open);
+
+        s.observe([(1, [0.0, 0.0, 0.0])]);
+        assert_eq!(s.mover_state(1), Some(MoverState::DoorClosed));
+        assert_eq!(s.edge_state(0, 1), EdgeState::Wait, "auto door: wait");
+        assert_eq!(s.edge_state(1, 0), EdgeState::Wait);
+        assert_eq!(s.edge_state(0, 3), EdgeState::Open, "edge misses the door");
+        assert_eq!(s.edges_in(EdgeState::Wait), vec![(0, 1), (1, 0)]);
+
+        // Open pose: +y by size.y - lip = (128 + the 1u model margin each side) - 8.
+        let rev = s.revision();
+        s.observe([(1, [0.0, 122.0, 0.0])]);
+        assert_eq!(s.mover_state(1), Some(MoverState::DoorOpen));
+        assert_eq!(s.edge_state(0, 1), EdgeState::Open);
+        assert!(s.revision() > rev);
+        s.observe([(1, [0.0, 60.0, 0.0])]);
+        assert_eq!(s.edge_state(0, 1), EdgeState::Wait, "swinging");
+
+        // Out of sight long enough → unknown → open again.
+        for _ in 0..=STALE_FRAMES {
+            s.observe([]);
+        }
+        assert_eq!(s.mover_state(1), Some(MoverState::Unknown));
+        assert_eq!(s.edge_state(0, 1), EdgeState::Open);
+    }
+
+    #[test]
+    fn triggered_door_blocks_while_shut() {
+        let (g, bsp) = door_world(true);
+        let mut s = NavState::new(&g, &bsp);
+        s.observe([(1, [0.0, 0.0, 0.0])]);
+        assert_eq!(s.edges_in(EdgeState::Blocked), vec![(0, 1), (1, 0)]);
+        let rev = s.revision();
+        s.observe([(1, [0.0, 0.0, 0.0])]);
+        assert_eq!(s.revision(), rev, "no change, no bump");
+    }
+
+    #[test]
+    fn lift_and_train_edges_follow_their_platform() {
+        let mut b = BspBuilder::new();
+        b.solid([-512.0, -512.0, -16.0], [512.0, 512.0, 0.0])
+            .plat([-32.0, -32.0, 0.0], [32.0, 32.0, 128.0]) // *1: pad top at 128
+            .brush_entity(
+                "func_train",
+                &[],
+                CONTENTS_SOLID,
+                [200.0, -32.0, 0.0],
+                [264.0, 32.0, 16.0],
+            ); // *2
+        let bsp = b.build();
+        let ride = |model_index, vertical, board: [f32; 3], far: [f32; 3]| RideInfo {
+            board,
+            far,
+            dismount: far,
+            model_index,
+            vertical,
+            board_ent: [0.0; 3],
+            far_ent: [300.0, 0.0, 0.0],
+            ladder: false,
+            stand_offset: [0.0; 3],
+        };
+        let mut g = NavGraph::from_raw(vec![[0.0; 3]; 4], vec![Vec::new(); 4]);
+        g.add_ride_edge(
+            0,
+            1,
+            120.0,
+            ride(1, true, [0.0, 0.0, 32.0], [0.0, 0.0, 152.0]),
+        );
+        g.add_ride_edge(
+            2,
+            3,
+            300.0,
+            ride(2, false, [232.0, 0.0, 40.0], [532.0, 0.0, 40.0]),
+        );
+        let mut s = NavState::new(&g, &bsp);
+        assert_eq!(s.mover_count(), 2);
+
+        // Pad at the top (BSP pose): the up-ride waits, the down-ride boards.
+        s.observe([(1, [0.0, 0.0, 0.0]), (2, [0.0, 0.0, 0.0])]);
+        assert_eq!(s.mover_state(1), Some(MoverState::Lift { pad_z: 128.0 }));
+        assert_eq!(s.edge_state(0, 1), EdgeState::Wait);
+        assert_eq!(s.edge_state(1, 0), EdgeState::Open);
+        // Lowered by its travel (130 padded - lip 8): pad top at 6, bot origin 30 → boards up.
+        s.observe([(1, [0.0, 0.0, -122.0])]);
+        assert_eq!(s.edge_state(0, 1), EdgeState::Open);
+        assert_eq!(s.edge_state(1, 0), EdgeState::Wait);
+
+        // Train at its board corner for 2→3, away from the 3→2 corner.
+        assert_eq!(s.edge_state(2, 3), EdgeState::Open);
+        assert_eq!(s.edge_state(3, 2), EdgeState::Wait);
+        s.observe([(2, [300.0, 0.0, 0.0])]);
+        assert_eq!(s.edge_state(2, 3), EdgeState::Wait);
+        assert_eq!(s.edge_state(3, 2), EdgeState::Open);
+    }
+}
@@ -1,54 +1,87 @@
-//! Q2 `.pak` archive reader — extracts files (e.g. BSPs) from `pak0/pak1.pak`.
+//! Q2 archive readers + a `.pak` writer — extracts files (e.g. BSPs) from `pak0/pak1.pak`
+//! and from the zip-format `.pkz` / `.pk3` / `.zip` archives Yamagi and q2pro also load.
 //!
-//! Format (`files.h:30`): a header `[magic "PACK", dirofs, dirlen]` followed by a
+//! `.pak` format (`files.h:30`): a header `[magic "PACK", dirofs, dirlen]` followed by a
 //! directory of 64-byte `dpackfile_t` entries `[name[56], filepos, filelen]`. The stock
 //! deathmatch maps (`q2dm1`…`q2dm8`) live in `pak1.pak`, not as loose files.
+//!
+//! Zip archives are read through the central directory (stored and deflate members; no
+//! zip64, no encryption — neither engine accepts those either). Both readers memory-map
+//! the archive: a 300 MB `pak0.pak` costs address space, not RSS, and only the entries
+//! actually read are paged in. Search order across archives lives in [`crate::vfs`].
 
-use std::fs;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
 use std::path::Path;
 
+use memmap2::Mmap;
+
+/// `dpackfile_t.name` length (`files.h:36`) — names must fit with their NUL.
+const PAK_NAME_LEN: usize = 56;
+
+/// Memory-map `path` read-only.
+fn map_file(path: &Path) -> Result<Mmap, String> {
+    let file = File::open(path).map_err(|e| format!("open {}: {e}", path.display()))?;
+    // SAFETY: the map is read-only and private to this process. Mutating the archive on
+    // disk while it is mapped would be UB — the same contract the engines rely on; game
+    // data directories are not rewritten under a running server.
+    unsafe { Mmap::map(&file) }.map_err(|e| format!("mmap {}: {e}", path.display()))
+}
+
+fn le32(b: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([b[at], b[at + 1], b[at + 2], b[at + 3]])
+}
+
+fn le16(b: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([b[at], b[at + 1]])
+}
+
 /// An opened `.pak` archive.
 pub struct Pak {
-    data: Vec<u8>,
+    data: Mmap,
     entries: Vec<(String, u32, u32)>, // (name, filepos, filelen)
+    /// Lower-cased name → index into `entries`.
+    index: HashMap<String, usize>,
 }
 
 impl Pak {
     /// Open and index a `.pak` file.
     pub fn open(path: &Path) -> Result<Self, String> {
-        let data = fs::read(path).map_err(|e| format!("read {}: {e}", path.display()))?;
+        let data = map_file(path)?;
         if data.len() < 12 || &data[0..4] != b"PACK" {
             return Err(format!("{}: not a pak (bad magic)", path.display()));
         }
-        let dirofs = i32::from_le_bytes([data[4], data[5], data[6], data[7]]) as usize;
-        let dirlen = i32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize;
+        let dirofs = le32(&data, 4) as usize;
+        let dirlen = le32(&data, 8) as usize;
 
         let mut entries = Vec::new();
         let end = dirofs.saturating_add(dirlen).min(data.len());
         let mut p = dirofs;
         while p + 64 <= end {
-            let name = cstr(&data[p..p + 56]);
-            let filepos =
-                i32::from_le_bytes([data[p + 56], data[p + 57], data[p + 58], data[p + 59]]) as u32;
-            let filelen =
-                i32::from_le_bytes([data[p + 60], data[p + 61], data[p + 62], data[p + 63]]) as u32;
-            entries.push((name, filepos, filelen));
+            let name = cstr(&data[p..p + PAK_NAME_LEN]);
+            entries.push((name, le32(&data, p + 56), le32(&data, p + 60)));
             p += 64;
         }
-        Ok(Self { data, entries })
+        // First entry wins on duplicates, matching the engine's linear directory scan.
+        let mut index = HashMap::new();
+        for (i, (name, _, _)) in entries.iter().enumerate() {
+            index.entry(name.to_ascii_lowercase()).or_insert(i);
+        }
+        Ok(Self {
+            data,
+            entries,
+            index,
+        })
     }
 
     /// Borrow a file's bytes by exact name (e.g. `"maps/q2dm1.bsp"`), case-insensitively.
     pub fn read(&self, name: &str) -> Option<&[u8]> {
-        let want = name.to_ascii_lowercase();
-        self.entries
-            .iter()
-            .find(|(n, _, _)| n.to_ascii_lowercase() == want)
-            .and_then(|(_, off, len)| {
-                let s = *off as usize;
-                let e = s.checked_add(*len as usize)?;
-                (e <= self.data.len()).then_some(&self.data[s..e])
-            })
+        let &i = self.index.get(&name.to_ascii_lowercase())?;
+        let (_, off, len) = &self.entries[i];
+        let s = *off as usize;
+        let e = s.checked_add(*len as usize)?;
+        (e <= self.data.len()).then_some(&self.data[s..e])
     }
 
     /// Iterate entry names (for debugging / listing).
@@ -57,12 +90,209 @@ impl Pak {
     }
 }
 
+/// Builds a `.pak` in memory — test fixtures, and repacking custom maps for a server.
+#[derive(Default)]
+pub struct PakWriter {
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl PakWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a file. Names are stored as given (`/`-separated, e.g. `"maps/foo.bsp"`).
+    pub fn add(&mut self, name: &str, data: &[u8]) -> &mut Self {
+        self.files.push((name.to_string(), data.to_vec()));
+        self
+    }
+
+    /// Serialize: header, file data in insertion order, then the directory.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(b"PACK");
+        buf.extend_from_slice(&[0; 8]); // dirofs/dirlen, fixed up below
+        let mut dir = Vec::with_capacity(self.files.len() * 64);
+        for (name, data) in &self.files {
+            if name.len() >= PAK_NAME_LEN {
+                return Err(format!("pak entry name too long (max 55): {name}"));
+            }
+            let pos = i32::try_from(buf.len()).map_err(|_| "pak exceeds 2 GiB")?;
+            let len = i32::try_from(data.len()).map_err(|_| "pak entry exceeds 2 GiB")?;
+            buf.extend_from_slice(data);
+            let mut entry = [0u8; 64];
+            entry[..name.len()].copy_from_slice(name.as_bytes());
+            entry[56..60].copy_from_slice(&pos.to_le_bytes());
+            entry[60..64].copy_from_slice(&len.to_le_bytes());
+            dir.extend_from_slice(&entry);
+        }
+        let dirofs = i32::try_from(buf.len()).map_err(|_| "pak exceeds 2 GiB")?;
+        buf.extend_from_slice(&dir);
+        buf[4..8].copy_from_slice(&dirofs.to_le_bytes());
+        buf[8..12].copy_from_slice(&(dir.len() as i32).to_le_bytes());
+        Ok(buf)
+    }
+
+    /// Serialize to `path`.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_bytes()?).map_err(|e| format!("write {}: {e}", path.display()))
+    }
+}
+
+/// One zip central-directory entry.
+struct ZipEntry {
+    name: String,
+    method: u16,
+    csize: u32,
+    uncompressed_size: u32,
+    local_ofs: u32,
+}
+
+/// An opened zip-format archive (`.pkz` / `.pk3` / `.zip`).
+pub struct Pkz {
+    data: Mmap,
+    entries: Vec<ZipEntry>,
+    index: HashMap<String, usize>,
+}
+
+impl Pkz {
+    /// Open and index a zip archive via its end-of-central-directory record.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let data = map_file(path)?;
+        let bad = |why: &str| format!("{}: {why}", path.display());
+        // EOCD is 22 bytes + a comment of up to 64 KiB; scan back for its signature.
+        let floor = data.len().saturating_sub(22 + 0xFFFF);
+        let eocd = (floor..data.len().saturating_sub(21))
+            .rev()
+            .find(|&i| le32(&data, i) == 0x0605_4b50)
+            .ok_or_else(|| bad("not a zip (no end of central directory)"))?;
+        let count = le16(&data, eocd + 10) as usize;
+        let cd_ofs = le32(&data, eocd + 16);
+        if cd_ofs == u32::MAX || count == 0xFFFF {
+            return Err(bad("zip64 archives are not supported"));
+        }
+
+        let mut entries = Vec::with_capacity(count);
+        let mut p = cd_ofs as usize;
+        for _ in 0..count {
+            if p + 46 > data.len() || le32(&data, p) != 0x0201_4b50 {
+                return Err(bad("corrupt central directory"));
+            }
+            let (nlen, elen, clen) = (
+                le16(&data, p + 28) as usize,
+                le16(&data, p + 30) as usize,
+                le16(&data, p + 32) as usize,
+            );
+            let name_end = (p + 46 + nlen).min(data.len());
+            let name = String::from_utf8_lossy(&data[p + 46..name_end]).into_owned();
+            if !name.ends_with('/') {
+                entries.push(ZipEntry {
+                    name,
+                    method: le16(&data, p + 10),
+                    csize: le32(&data, p + 20),
+                    uncompressed_size: le32(&data, p + 24),
+                    local_ofs: le32(&data, p + 42),
+                });
+            }
+            p += 46 + nlen + elen + clen;
+        }
+        let mut index = HashMap::new();
+        for (i, e) in entries.iter().enumerate() {
+            index.entry(e.name.to_ascii_lowercase()).or_insert(i);
+        }
+        Ok(Self {
+            data,
+            entries,
+            index,
+        })
+    }
+
+    /// A file's bytes by name, case-insensitively: borrowed for stored members, inflated
+    /// for deflated ones. `None` if absent, corrupt, or an unsupported method.
+    pub fn read(&self, name: &str) -> Option<Cow<'_, [u8]>> {
+        let e = &self.entries[*self.index.get(&name.to_ascii_lowercase())?];
+        let lh = e.local_ofs as usize;
+        if lh + 30 > self.data.len() || le32(&self.data, lh) != 0x0403_4b50 {
+            return None;
+        }
+        // The local header's name/extra lengths can differ from the central copy.
+        let start =
+            lh + 30 + le16(&self.data, lh + 26) as usize + le16(&self.data, lh + 28) as usize;
+        let raw = self.data.get(start..start.checked_add(e.csize as usize)?)?;
+        match e.method {
+            0 => Some(Cow::Borrowed(raw)),
+            8 => miniz_oxide::inflate::decompress_to_vec_with_limit(
+                raw,
+                e.uncompressed_size as usize,
+            )
+            .ok()
+            .map(Cow::Owned),
+            _ => None,
+        }
+    }
+
+    /// Iterate entry names (directories excluded).
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|e| e.name.as_str())
+    }
+}
+
 /// Read a NUL-terminated C string from the front of `b` (lossy UTF-8).
 fn cstr(b: &[u8]) -> String {
     let end = b.iter().position(|&c| c == 0).unwrap_or(b.len());
     String::from_utf8_lossy(&b[..end]).into_owned()
 }
 
+/// Build a zip archive in memory: `(name, data, deflate)` members. Test-support only —
+/// exposed (doc-hidden) so the [`crate::vfs`] tests can lay out `.pkz` fixtures.
+#[doc(hidden)]
+pub fn zip_bytes(files: &[(&str, &[u8], bool)]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    let mut cd = Vec::new();
+    for &(name, data, deflate) in files {
+        let body = if deflate {
+            miniz_oxide::deflate::compress_to_vec(data, 6)
+        } else {
+            data.to_vec()
+        };
+        let method: u16 = if deflate { 8 } else { 0 };
+        let ofs = buf.len() as u32;
+        // Local header: sig, version, flags, method, time, date, crc (unchecked here),
+        // sizes, name/extra lengths.
+        buf.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        buf.extend_from_slice(&[20, 0, 0, 0]);
+        buf.extend_from_slice(&method.to_le_bytes());
+        buf.extend_from_slice(&[0; 8]);
+        buf.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buf.extend_from_slice(&0u16.to_le_bytes());
+        buf.extend_from_slice(name.as_bytes());
+        buf.extend_from_slice(&body);
+
+        cd.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        cd.extend_from_slice(&[20, 0, 20, 0, 0, 0]);
+        cd.extend_from_slice(&method.to_le_bytes());
+        cd.extend_from_slice(&[0; 8]);
+        cd.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        cd.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        cd.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        cd.extend_from_slice(&[0; 12]);
+        cd.extend_from_slice(&ofs.to_le_bytes());
+        cd.extend_from_slice(name.as_bytes());
+    }
+    let cd_ofs = buf.len() as u32;
+    buf.extend_from_slice(&cd);
+    buf.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    buf.extend_from_slice(&[0; 4]);
+    buf.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    buf.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    buf.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+    buf.extend_from_slice(&cd_ofs.to_le_bytes());
+    buf.extend_from_slice(&0u16.to_le_bytes());
+    buf
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +334,54 @@ mod tests {
         // case-insensitive
         assert_eq!(pak.read("MAPS/Q2DM1.BSP").unwrap(), b"IBSPDATA");
     }
+
+    #[test]
+    fn pak_writer_matches_hand_built_pak_and_round_trips() {
+        let mut w = PakWriter::new();
+        w.add("maps/q2dm1.bsp", b"IBSPDATA");
+        assert_eq!(
+            w.to_bytes().unwrap(),
+            one_entry_pak("maps/q2dm1.bsp", b"IBSPDATA")
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("multi.pak");
+        w.add("pics/colormap.pcx", &[7u8; 300])
+            .add("env/empty.txt", b"");
+        w.write(&path).unwrap();
+        let pak = Pak::open(&path).unwrap();
+        assert_eq!(pak.names().count(), 3);
+        assert_eq!(pak.read("pics/colormap.pcx").unwrap(), &[7u8; 300]);
+        assert_eq!(pak.read("env/empty.txt").unwrap(), b"");
+
+        let long = "x".repeat(PAK_NAME_LEN);
+        assert!(PakWriter::new().add(&long, b"").to_bytes().is_err());
+    }
+
+    #[test]
+    fn reads_stored_and_deflated_zip_members() {
+        let big = b"IBSP".repeat(1000);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maps.pkz");
+        std::fs::write(
+            &path,
+            zip_bytes(&[
+                ("maps/stored.bsp", b"IBSPDATA", false),
+                ("maps/Deflated.bsp", &big, true),
+            ]),
+        )
+        .unwrap();
+        let pkz = Pkz::open(&path).unwrap();
+        assert_eq!(pkz.read("maps/stored.bsp").unwrap().as_ref(), b"IBSPDATA");
+        assert!(matches!(
+            pkz.read("maps/stored.bsp"),
+            Some(Cow::Borrowed(_))
+        ));
+        assert_eq!(pkz.read("MAPS/deflated.BSP").unwrap().as_ref(), &big[..]);
+        assert!(pkz.read("maps/missing.bsp").is_none());
+        assert_eq!(pkz.names().count(), 2);
+
+        std::fs::write(&path, b"not a zip at all, just bytes").unwrap();
+        assert!(Pkz::open(&path).is_err());
+    }
 }
@@ -0,0 +1,664 @@
+//! Programmatic IBSP v38 writer for hermetic test worlds — no id pak files needed.
+//!
+//! A tiny `qbsp`: convex brushes (axis-aligned boxes or arbitrary half-space sets) become
+//! plane pairs (`FindFloatPlane`, `map.c`), then the world is split on brush faces until
+//! every leaf lies wholly inside or outside each brush (`BrushBSP`, `brushbsp.c`). Leaf
+//! contents are the OR of the brushes containing it, and those brushes are its leafbrushes.
+//! Brush entities (`func_plat`, `trigger_teleport`, …) get their own inline model `*N`
+//! with a separate tree, exactly like a compiled map.
+//!
+//! What is NOT emitted: faces, texinfo, lighting, portals. Visibility is a single cluster
+//! that sees itself, so PVS checks always pass. Good for collision, nav generation and
+//! entity-driven passes (ladders, lifts, teleporters); not for rendering.
+//! Test-support only — exposed (doc-hidden) so dependent crates' tests can build worlds.
+
+use std::collections::HashMap;
+
+use crate::bsp::{Bsp, NUM_LUMPS};
+use crate::collision::{
+    base_winding, chop_winding, CONTENTS_LADDER, CONTENTS_LAVA, CONTENTS_SOLID, CONTENTS_WATER,
+};
+
+/// Half-size of the root region every tree splits down from (Q2 worlds live in ±4096).
+const WORLD_EXTENT: f32 = 8192.0;
+/// Classification slop (units), `ON_EPSILON` in `qbsp`.
+const ON_EPSILON: f32 = 0.1;
+
+/// A brush face: outward `normal` + `dist`; the brush interior is `normal·p <= dist`.
+type Face = ([f32; 3], f32);
+
+struct BrushDef {
+    sides: Vec<Face>,
+    contents: i32,
+    /// Owning model: 0 = world, `N` = the brush entity with `"model" "*N"`.
+    model: usize,
+}
+
+/// Builder for a synthetic `.bsp`: add brushes and entities, then [`BspBuilder::to_bytes`]
+/// (e.g. to pack with [`crate::PakWriter`]) or [`BspBuilder::build`] for a parsed [`Bsp`].
+pub struct BspBuilder {
+    brushes: Vec<BrushDef>,
+    entities: Vec<Vec<(String, String)>>,
+    models: usize,
+    teleports: usize,
+}
+
+impl Default for BspBuilder {
+    fn default() -> Self {
+        Self {
+            brushes: Vec::new(),
+            entities: vec![vec![("classname".into(), "worldspawn".into())]],
+            models: 1,
+            teleports: 0,
+        }
+    }
+}
+
+/// `"x y z"` as the entity lump writes it.
+fn vec_str(v: [f32; 3]) -> String {
+    format!("{} {} {}", v[0], v[1], v[2])
+}
+
+fn box_sides(mins: [f32; 3], maxs: [f32; 3]) -> Vec<Face> {
+    let mut sides = Vec::with_capacity(6);
+    for ax in 0..3 {
+        let mut n = [0.0; 3];
+        n[ax] = 1.0;
+        sides.push((n, maxs[ax]));
+        n[ax] = -1.0;
+        sides.push((n, -mins[ax]));
+    }
+    sides
+}
+
+impl BspBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An axis-aligned box brush in the world model.
+    pub fn brush(&mut self, contents: i32, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.push_brush(box_sides(mins, maxs), contents, 0)
+    }
+
+    pub fn solid(&mut self, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.brush(CONTENTS_SOLID, mins, maxs)
+    }
+
+    pub fn water(&mut self, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.brush(CONTENTS_WATER, mins, maxs)
+    }
+
+    pub fn lava(&mut self, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.brush(CONTENTS_LAVA, mins, maxs)
+    }
+
+    /// A climbable ladder: solid (you press against it) plus `CONTENTS_LADDER`.
+    pub fn ladder(&mut self, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.brush(CONTENTS_SOLID | CONTENTS_LADDER, mins, maxs)
+    }
+
+    /// A convex brush from outward-facing planes `(normal, dist)` — ramps, wedges. Normals
+    /// need not be unit length. Axial bevels are added as `AddBrushBevels` (`map.c`) does,
+    /// so swept-box traces stop at the brush's bounds rather than its extended planes.
+    pub fn convex(&mut self, contents: i32, planes: &[([f32; 3], f32)]) -> &mut Self {
+        let mut sides: Vec<Face> = planes
+            .iter()
+            .map(|&(n, d)| {
+                let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+                (n.map(|c| c / len), d / len)
+            })
+            .collect();
+        let verts = polytope_verts(&sides);
+        assert!(!verts.is_empty(), "convex brush is empty or unbounded");
+        let (mins, maxs) = aabb(&verts);
+        for (n, d) in box_sides(mins, maxs) {
+            let has = sides
+                .iter()
+                .any(|(m, _)| (0..3).all(|k| (m[k] - n[k]).abs() < 1e-4));
+            if !has {
+                sides.push((n, d));
+            }
+        }
+        self.push_brush(sides, contents, 0)
+    }
+
+    /// A point entity with extra `"key" "value"` fields.
+    pub fn entity(&mut self, classname: &str, fields: &[(&str, &str)]) -> &mut Self {
+        let mut kv = vec![("classname".to_string(), classname.to_string())];
+        kv.extend(fields.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        self.entities.push(kv);
+        self
+    }
+
+    /// An `info_player_deathmatch` at `origin` (the standing origin, floor + 24).
+    pub fn spawn(&mut self, origin: [f32; 3]) -> &mut Self {
+        self.entity("info_player_deathmatch", &[("origin", &vec_str(origin))])
+    }
+
+    /// A pickup (`weapon_*`, `item_*`, `ammo_*`) at `origin`.
+    pub fn item(&mut self, classname: &str, origin: [f32; 3]) -> &mut Self {
+        self.entity(classname, &[("origin", &vec_str(origin))])
+    }
+
+    /// A brush entity: the box becomes inline model `*N` (not part of the world tree) and
+    /// the entity gets `"model" "*N"` plus `fields`.
+    pub fn brush_entity(
+        &mut self,
+        classname: &str,
+        fields: &[(&str, &str)],
+        contents: i32,
+        mins: [f32; 3],
+        maxs: [f32; 3],
+    ) -> &mut Self {
+        let model = self.models;
+        self.models += 1;
+        self.push_brush(box_sides(mins, maxs), contents, model);
+        let name = format!("*{model}");
+        let mut all = vec![("model", name.as_str())];
+        all.extend_from_slice(fields);
+        self.entity(classname, &all)
+    }
+
+    /// A `func_plat` whose platform box rests at the top of its travel (as in the map).
+    pub fn plat(&mut self, mins: [f32; 3], maxs: [f32; 3]) -> &mut Self {
+        self.brush_entity("func_plat", &[], CONTENTS_SOLID, mins, maxs)
+    }
+
+    /// A `misc_teleporter` pad at `pad` linked to a `misc_teleporter_dest` at `dest`.
+    pub fn teleporter(&mut self, pad: [f32; 3], dest: [f32; 3]) -> &mut Self {
+        self.teleports += 1;
+        let name = format!("tele{}", self.teleports);
+        self.entity(
+            "misc_teleporter",
+            &[("origin", &vec_str(pad)), ("target", &name)],
+        );
+        self.entity(
+            "misc_teleporter_dest",
+            &[("origin", &vec_str(dest)), ("targetname", &name)],
+        )
+    }
+
+    fn push_brush(&mut self, sides: Vec<Face>, contents: i32, model: usize) -> &mut Self {
+        self.brushes.push(BrushDef {
+            sides,
+            contents,
+            model,
+        });
+        self
+    }
+
+    /// Compile to IBSP v38 bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut c = Compiler::default();
+        // Leaf 0 is the shared solid leaf every map starts with (`CMod_LoadLeafs` checks it).
+        c.leafs.push(LeafRec {
+            contents: CONTENTS_SOLID,
+            cluster: -1,
+            area: 0,
+            bounds: ([0.0; 3], [0.0; 3]),
+            first: 0,
+            num: 0,
+        });
+        // Brush sides in brush order; each brush's planes are registered as pairs.
+        let mut brushes = Vec::with_capacity(self.brushes.len());
+        for b in &self.brushes {
+            let first = c.brushsides.len() as i32;
+            for &(n, d) in &b.sides {
+                let p = c.find_plane(n, d);
+                c.brushsides.push(p as u16);
+            }
+            brushes.push((first, b.sides.len() as i32, b.contents));
+        }
+        let verts: Vec<Vec<[f32; 3]>> = self
+            .brushes
+            .iter()
+            .map(|b| polytope_verts(&b.sides))
+            .collect();
+
+        let mut models = Vec::with_capacity(self.models);
+        for m in 0..self.models {
+            let ids: Vec<usize> = (0..self.brushes.len())
+                .filter(|&i| self.brushes[i].model == m)
+                .collect();
+            let all: Vec<[f32; 3]> = ids.iter().flat_map(|&i| verts[i].clone()).collect();
+            let bounds = if all.is_empty() {
+                ([0.0; 3], [0.0; 3])
+            } else {
+                aabb(&all)
+            };
+            let root = box_sides([-WORLD_EXTENT; 3], [WORLD_EXTENT; 3]);
+            let mut head = c.build(self, &verts, &root, &ids);
+            if head < 0 {
+                // A headnode must be a node: wrap a lone leaf in a dummy split.
+                let plane = c.find_plane([0.0, 0.0, 1.0], WORLD_EXTENT) as u32;
+                c.nodes.push(NodeRec {
+                    plane,
+                    children: [head, head],
+                    bounds,
+                });
+                head = c.nodes.len() as i32 - 1;
+            }
+            models.push((bounds, head));
+        }
+
+        // ---- serialize ----
+        let mut lumps: Vec<Vec<u8>> = vec![Vec::new(); NUM_LUMPS];
+        lumps[0] = self.entity_text().into_bytes();
+        for p in &c.planes {
+            let l = &mut lumps[1];
+            p.normal
+                .iter()
+                .for_each(|v| l.extend_from_slice(&v.to_le_bytes()));
+            l.extend_from_slice(&p.dist.to_le_bytes());
+            l.extend_from_slice(&p.typ.to_le_bytes());
+        }
+        // dvis_t: one cluster, PVS/PHS rows at 12/13, each "cluster 0 visible".
+        lumps[3] = [1i32, 12, 13]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        lumps[3].extend_from_slice(&[1, 1]);
+        for n in &c.nodes {
+            let l = &mut lumps[4];
+            l.extend_from_slice(&(n.plane as i32).to_le_bytes());
+            n.children
+                .iter()
+                .for_each(|v| l.extend_from_slice(&v.to_le_bytes()));
+            push_bounds16(l, n.bounds);
+            l.extend_from_slice(&[0; 4]); // firstface + numfaces
+        }
+        for lf in &c.leafs {
+            let l = &mut lumps[8];
+            l.extend_from_slice(&lf.contents.to_le_bytes());
+            l.extend_from_slice(&lf.cluster.to_le_bytes());
+            l.extend_from_slice(&lf.area.to_le_bytes());
+            push_bounds16(l, lf.bounds);
+            l.extend_from_slice(&[0; 4]); // firstleafface + numleaffaces
+            l.extend_from_slice(&lf.first.to_le_bytes());
+            l.extend_from_slice(&lf.num.to_le_bytes());
+        }
+        lumps[10] = c.leafbrushes.iter().flat_map(|v| v.to_le_bytes()).collect();
+        for ((mins, maxs), head) in &models {
+            let l = &mut lumps[13];
+            for v in mins.iter().chain(maxs).chain(&[0.0; 3]) {
+                l.extend_from_slice(&v.to_le_bytes());
+            }
+            l.extend_from_slice(&head.to_le_bytes());
+            l.extend_from_slice(&[0; 8]); // firstface + numfaces
+        }
+        for (first, num, contents) in &brushes {
+            let l = &mut lumps[14];
+            [first, num, contents]
+                .iter()
+                .for_each(|v| l.extend_from_slice(&v.to_le_bytes()));
+        }
+        for side in &c.brushsides {
+            lumps[15].extend_from_slice(&side.to_le_bytes());
+            lumps[15].extend_from_slice(&(-1i16).to_le_bytes()); // texinfo: none
+        }
+        // dareas: area 0 is the solid placeholder, area 1 holds every empty leaf.
+        lumps[17] = vec![0; 16];
+
+        let mut buf = b"IBSP".to_vec();
+        buf.extend_from_slice(&crate::bsp::BSP_VERSION.to_le_bytes());
+        let dir = buf.len();
+        buf.resize(dir + NUM_LUMPS * 8, 0);
+        for (i, lump) in lumps.iter().enumerate() {
+            while !buf.len().is_multiple_of(4) {
+                buf.push(0);
+            }
+            let ofs = buf.len() as i32;
+            buf.extend_from_slice(lump);
+            buf[dir + i * 8..dir + i * 8 + 4].copy_from_slice(&ofs.to_le_bytes());
+            buf[dir + i * 8 + 4..dir + i * 8 + 8]
+                .copy_from_slice(&(lump.len() as i32).to_le_bytes());
+        }
+        buf
+    }
+
+    /// Compile and parse back — the [`Bsp`] a real map load would produce.
+    pub fn build(&self) -> Bsp {
+        Bsp::from_bytes(&self.to_bytes()).expect("BspBuilder emits a parseable bsp")
+    }
+
+    fn entity_text(&self) -> String {
+        let mut s = String::new();
+        for kv in &self.entities {
+            s.push_str("{\n");
+            for (k, v) in kv {
+                s.push_str(&format!("\"{k}\" \"{v}\"\n"));
+            }
+            s.push_str("}\n");
+        }
+        s.push('\0');
+        s
+    }
+}
+
+struct PlaneRec {
+    normal: [f32; 3],
+    dist: f32,
+    typ: i32,
+}
+
+struct NodeRec {
+    plane: u32,
+    children: [i32; 2],
+    bounds: ([f32; 3], [f32; 3]),
+}
+
+struct LeafRec {
+    contents: i32,
+    cluster: i16,
+    area: i16,
+    bounds: ([f32; 3], [f32; 3]),
+    first: u16,
+    num: u16,
+}
+
+#[derive(Default)]
+struct Compiler {
+    planes: Vec<PlaneRec>,
+    plane_index: HashMap<[i64; 4], usize>,
+    nodes: Vec<NodeRec>,
+    leafs: Vec<LeafRec>,
+    leafbrushes: Vec<u16>,
+    brushsides: Vec<u16>,
+}
+
+impl Compiler {
+    /// `FindFloatPlane` — index of the plane `(n, d)`, adding it as a pair if new. The even
+    /// member faces "positive" (largest normal component > 0); nodes always reference it.
+    fn find_plane(&mut self, n: [f32; 3], d: f32) -> usize {
+        let n = n.map(|c| {
+            if (c.abs() - 1.0).abs() < 1e-5 {
+                c.signum()
+            } else if c.abs() < 1e-5 {
+                0.0
+            } else {
+                c
+            }
+        });
+        let major = (0..3)
+            .max_by(|&a, &b| n[a].abs().total_cmp(&n[b].abs()))
+            .unwrap_or(2);
+        let flip = n[major] < 0.0;
+        let (cn, cd) = if flip { (n.map(|c| -c), -d) } else { (n, d) };
+        let key = [
+            (cn[0] * 65536.0).round() as i64,
+            (cn[1] * 65536.0).round() as i64,
+            (cn[2] * 65536.0).round() as i64,
+            (cd * 64.0).round() as i64,
+        ];
+        let even = *self.plane_index.entry(key).or_insert_with(|| {
+            let axial = cn.iter().filter(|c| **c != 0.0).count() == 1;
+            // Flipped axial planes take the general path: the trace's `p[typ] - dist`
+            // shortcut assumes a +1 normal (see `closet_world`).
+            let (t0, t1) = if axial {
+                (major as i32, 3 + major as i32)
+            } else {
+                (3 + major as i32, 3 + major as i32)
+            };
+            self.planes.push(PlaneRec {
+                normal: cn,
+                dist: cd,
+                typ: t0,
+            });
+            self.planes.push(PlaneRec {
+                normal: cn.map(|c| -c),
+                dist: -cd,
+                typ: t1,
+            });
+            self.planes.len() - 2
+        });
+        even + flip as usize
+    }
+
+    /// Split `region` (a convex cell) on brush faces until no brush face crosses it.
+    /// Returns a node index, or `-(leaf+1)`.
+    fn build(
+        &mut self,
+        b: &BspBuilder,
+        brush_verts: &[Vec<[f32; 3]>],
+        region: &[Face],
+        brushes: &[usize],
+    ) -> i32 {
+        let verts = polytope_verts(region);
+        let span = |(n, d): Face, vs: &[[f32; 3]]| {
+            vs.iter().fold((f32::MAX, f32::MIN), |(lo, hi), v| {
+                let s = dot(n, *v) - d;
+                (lo.min(s), hi.max(s))
+            })
+        };
+        // Brushes overlapping the cell: no face of either separates the two.
+        let inside: Vec<usize> = brushes
+            .iter()
+            .copied()
+            .filter(|&i| {
+                let sep_b = b.brushes[i]
+                    .sides
+                    .iter()
+                    .any(|&f| span(f, &verts).0 >= -ON_EPSILON);
+                let sep_r = region
+                    .iter()
+                    .any(|&f| span(f, &brush_verts[i]).0 >= -ON_EPSILON);
+                !sep_b && !sep_r
+            })
+            .collect();
+
+        // Pick a splitting face: axial first, then fewest brushes cut, then balance.
+        let mut best: Option<(i64, Face)> = None;
+        for &i in &inside {
+            for &f in &b.brushes[i].sides {
+                let (lo, hi) = span(f, &verts);
+                if lo >= -ON_EPSILON || hi <= ON_EPSILON {
+                    continue;
+                }
+                let (mut front, mut back, mut cut) = (0i64, 0i64, 0i64);
+                for &j in &inside {
+                    let (lo, hi) = span(f, &brush_verts[j]);
+                    if lo < -ON_EPSILON && hi > ON_EPSILON {
+                        cut += 1;
+                    } else if lo >= -ON_EPSILON {
+                        front += 1;
+                    } else {
+                        back += 1;
+                    }
+                }
+                let axial = f.0.iter().filter(|c| **c != 0.0).count() == 1;
+                let score = if axial { 0 } else { 1000 } + cut * 5 + (front - back).abs();
+                if best.is_none_or(|(s, _)| score < s) {
+                    best = Some((score, f));
+                }
+            }
+        }
+
+        let bounds = aabb(&verts);
+        let Some((_, (n, d))) = best else {
+            // Every overlapping brush contains the whole cell.
+            let contents = inside.iter().fold(0, |c, &i| c | b.brushes[i].contents);
+            let solid = contents & CONTENTS_SOLID != 0;
+            self.leafs.push(LeafRec {
+                contents,
+                cluster: if solid { -1 } else { 0 },
+                area: if solid { 0 } else { 1 },
+                bounds,
+                first: self.leafbrushes.len() as u16,
+                num: inside.len() as u16,
+            });
+            self.leafbrushes.extend(inside.iter().map(|&i| i as u16));
+            return -(self.leafs.len() as i32);
+        };
+
+        let plane = self.find_plane(n, d) & !1;
+        let (pn, pd) = (self.planes[plane].normal, self.planes[plane].dist);
+        let node = self.nodes.len();
+        self.nodes.push(NodeRec {
+            plane: plane as u32,
+            children: [0, 0],
+            bounds,
+        });
+        let mut front = region.to_vec();
+        front.push((pn.map(|c| -c), -pd));
+        let mut back = region.to_vec();
+        back.push((pn, pd));
+        let f = self.build(b, brush_verts, &front, &inside);
+        let k = self.build(b, brush_verts, &back, &inside);
+        self.nodes[node].children = [f, k];
+        node as i32
+    }
+}
+
+/// Corners of the convex polytope `faces` bound (each face's winding, chopped by the rest).
+fn polytope_verts(faces: &[Face]) -> Vec<[f32; 3]> {
+    let mut out = Vec::new();
+    for (i, &(n, d)) in faces.iter().enumerate() {
+        let mut w = base_winding(n, d, WORLD_EXTENT * 4.0);
+        for (j, &(m, e)) in faces.iter().enumerate() {
+            if j != i && !w.is_empty() {
+                w = chop_winding(&w, m, e);
+            }
+        }
+        if w.len() >= 3 {
+            out.extend(w);
+        }
+    }
+    out
+}
+
+fn aabb(verts: &[[f32; 3]]) -> ([f32; 3], [f32; 3]) {
+    verts
+        .iter()
+        .fold(([f32::MAX; 3], [f32::MIN; 3]), |(lo, hi), v| {
+            (
+                [0, 1, 2].map(|k| lo[k].min(v[k])),
+                [0, 1, 2].map(|k| hi[k].max(v[k])),
+            )
+        })
+}
+
+fn push_bounds16(l: &mut Vec<u8>, (mins, maxs): ([f32; 3], [f32; 3])) {
+    let q = |v: f32| v.clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    mins.iter()
+        .for_each(|&v| l.extend_from_slice(&q(v.floor()).to_le_bytes()));
+    maxs.iter()
+        .for_each(|&v| l.extend_from_slice(&q(v.ceil()).to_le_bytes()));
+}
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{CollisionModel, MASK_SOLID, MASK_WATER};
+    use crate::navgraph::{HULL_MAXS, HULL_MINS};
+
+    /// A 512² room: floor top at z=0, 16u walls to z=256, a water pool and a lava strip
+    /// sunk into the floor, and a ramp.
+    fn room() -> BspBuilder {
+        let mut b = BspBuilder::new();
+        b.solid([-256.0, -256.0, -16.0], [256.0, 256.0, 0.0])
+            .solid([-272.0, -272.0, -16.0], [-256.0, 272.0, 256.0])
+            .solid([256.0, -272.0, -16.0], [272.0, 272.0, 256.0])
+            .solid([-256.0, -272.0, -16.0], [256.0, -256.0, 256.0])
+            .solid([-256.0, 256.0, -16.0], [256.0, 272.0, 256.0])
+            .water([-200.0, -200.0, 0.0], [-100.0, -100.0, 64.0])
+            .lava([100.0, -200.0, 0.0], [200.0, -150.0, 8.0])
+            // A 45° wedge rising toward +x: top plane z = x - 100 for 100 ≤ x ≤ 164.
+            .convex(
+                CONTENTS_SOLID,
+                &[
+                    ([-1.0, 0.0, 1.0], -100.0),
+                    ([0.0, 0.0, -1.0], 0.0),
+                    ([1.0, 0.0, 0.0], 164.0),
+                    ([0.0, 1.0, 0.0], 200.0),
+                    ([0.0, -1.0, 0.0], -100.0),
+                ],
+            )
+            .spawn([0.0, 0.0, 24.0])
+            .item("weapon_railgun", [64.0, 64.0, 16.0]);
+        b
+    }
+
+    #[test]
+    fn compiled_room_round_trips_and_traces() {
+        let bsp = room().build();
+        assert_eq!(bsp.leafs[0].contents, CONTENTS_SOLID, "leaf 0 is solid");
+        assert_eq!(bsp.models.len(), 1);
+        assert_eq!(
+            bsp.models[0].mins,
+            [-273.0, -273.0, -17.0],
+            "bounds + 1u margin"
+        );
+        assert_eq!(bsp.spawn_points()[0].origin, [0.0, 0.0, 24.0]);
+        assert_eq!(bsp.find_class("weapon_railgun").len(), 1);
+
+        let cm = CollisionModel::from_bsp(&bsp);
+        assert_eq!(cm.point_contents(&[0.0, 0.0, 10.0]), 0, "open air");
+        assert!(cm.is_solid(&[0.0, 0.0, -8.0]), "inside the floor");
+        assert!(cm.is_solid(&[-264.0, 0.0, 100.0]), "inside a wall");
+        assert_eq!(cm.point_contents(&[-150.0, -150.0, 32.0]), CONTENTS_WATER);
+        assert_eq!(cm.point_contents(&[150.0, -170.0, 4.0]), CONTENTS_LAVA);
+        assert!(cm.is_solid(&[130.0, 150.0, 20.0]), "under the ramp surface");
+        assert!(
+            !cm.is_solid(&[130.0, 150.0, 40.0]),
+            "above the ramp surface"
+        );
+
+        // A hull dropped onto the floor rests with its origin 24 above it.
+        let tr = cm.trace(
+            &[0.0, 0.0, 100.0],
+            &[0.0, 0.0, -100.0],
+            &HULL_MINS,
+            &HULL_MAXS,
+            MASK_SOLID,
+        );
+        assert!((tr.endpos[2] - 24.0).abs() < 0.1, "{:?}", tr.endpos);
+        // A walk into the east wall stops at x = 256 - 16.
+        let tr = cm.trace(
+            &[0.0, 0.0, 24.0],
+            &[400.0, 0.0, 24.0],
+            &HULL_MINS,
+            &HULL_MAXS,
+            MASK_SOLID,
+        );
+        assert!((tr.endpos[0] - 240.0).abs() < 0.1, "{:?}", tr.endpos);
+        // Water doesn't block movement; a water-masked trace does stop at it.
+        let (a, b) = ([-150.0, -150.0, 100.0], [-150.0, -150.0, 1.0]);
+        assert_eq!(
+            cm.trace(&a, &b, &[0.0; 3], &[0.0; 3], MASK_SOLID).fraction,
+            1.0
+        );
+        let tr = cm.trace(&a, &b, &[0.0; 3], &[0.0; 3], MASK_WATER);
+        assert!((tr.endpos[2] - 64.0).abs() < 0.1, "{:?}", tr.endpos);
+    }
+
+    #[test]
+    fn brush_entities_get_their_own_inline_models() {
+        let mut b = room();
+        b.plat([-32.0, 100.0, -16.0], [32.0, 164.0, 128.0]);
+        b.teleporter([-64.0, 64.0, 8.0], [64.0, -64.0, 8.0]);
+        let bsp = b.build();
+        assert_eq!(bsp.models.len(), 2);
+        let plat = &bsp.find_class("func_plat")[0];
+        assert_eq!(plat.fields.get("model").map(String::as_str), Some("*1"));
+        assert_eq!(bsp.models[1].maxs, [33.0, 165.0, 129.0]);
+        // The plat is not part of the world tree: model 0 traces pass through it.
+        let cm = CollisionModel::from_bsp(&bsp);
+        assert!(!cm.is_solid(&[0.0, 130.0, 64.0]));
+        let tele = &bsp.find_class("misc_teleporter")[0];
+        let dest = &bsp.find_class("misc_teleporter_dest")[0];
+        assert_eq!(tele.fields.get("target"), dest.fields.get("targetname"));
+    }
+
+    #[test]
+    fn empty_world_still_has_a_headnode() {
+        let bsp = BspBuilder::new().build();
+        assert!(bsp.models[0].headnode >= 0);
+        let cm = CollisionModel::from_bsp(&bsp);
+        assert_eq!(cm.point_contents(&[0.0; 3]), 0);
+    }
+}
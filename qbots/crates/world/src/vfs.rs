@@ -0,0 +1,225 @@
+//! Search-ordered virtual filesystem over a game directory's loose files and archives.
+//!
+//! Mirrors `FS_AddGameDirectory` (`files.c`): the directory itself is added first, then
+//! each archive is *prepended* to the search path — so `pak1.pak` shadows `pak0.pak`, and
+//! any pak shadows a loose file of the same name. Order within a game dir: numbered
+//! `pak0`…`pak99.pak`, then other `*.pak` alphabetically, then the zip-format
+//! `*.pkz`/`*.pk3`/`*.zip` alphabetically (Yamagi's extension). A game dir added later
+//! (a mod on top of `baseq2`) shadows everything before it.
+
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::pak::{Pak, Pkz};
+
+enum Source {
+    Dir(PathBuf),
+    Pak(Pak),
+    Pkz(Pkz),
+}
+
+/// Loose files + `.pak` + zip archives, highest priority first.
+#[derive(Default)]
+pub struct Vfs {
+    search: Vec<Source>,
+    /// Lower-cased names of every mounted archive's entries, for [`Vfs::exists`].
+    archived: HashSet<String>,
+}
+
+/// Archive sort key within one game dir: numbered paks, other paks, then zips.
+fn archive_rank(name: &str) -> Option<(u8, u32, String)> {
+    let low = name.to_ascii_lowercase();
+    if let Some(stem) = low.strip_suffix(".pak") {
+        if let Some(n) = stem.strip_prefix("pak").and_then(|d| d.parse::<u32>().ok()) {
+            return Some((0, n, low));
+        }
+        return Some((1, 0, low));
+    }
+    [".pkz", ".pk3", ".zip"]
+        .iter()
+        .any(|ext| low.ends_with(ext))
+        .then_some((2, 0, low))
+}
+
+impl Vfs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A VFS over a single game directory (usually `baseq2`).
+    pub fn open(gamedir: &Path) -> Result<Self, String> {
+        let mut vfs = Self::new();
+        vfs.add_game_dir(gamedir)?;
+        Ok(vfs)
+    }
+
+    /// Add a game directory on top of the current search path. A missing directory adds
+    /// nothing searchable; an unreadable or unsupported archive (truncated pak, zip64) is
+    /// logged and skipped, as the engine does, so one bad file doesn't hide every map.
+    pub fn add_game_dir(&mut self, dir: &Path) -> Result<(), String> {
+        self.search.insert(0, Source::Dir(dir.to_path_buf()));
+        let mut archives: Vec<((u8, u32, String), PathBuf)> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+            .filter_map(|e| {
+                let rank = archive_rank(e.file_name().to_str()?)?;
+                Some((rank, e.path()))
+            })
+            .collect();
+        archives.sort();
+        for ((kind, _, _), path) in archives {
+            let archived = &mut self.archived;
+            let src = if kind == 2 {
+                Pkz::open(&path).map(|z| {
+                    archived.extend(z.names().map(str::to_ascii_lowercase));
+                    Source::Pkz(z)
+                })
+            } else {
+                Pak::open(&path).map(|p| {
+                    archived.extend(p.names().map(str::to_ascii_lowercase));
+                    Source::Pak(p)
+                })
+            };
+            match src {
+                Ok(src) => self.search.insert(0, src),
+                Err(e) => tracing::warn!(path = %path.display(), "skipping archive: {e}"),
+            }
+        }
+        Ok(())
+    }
+
+    /// The highest-priority copy of `name` (`/`-separated, e.g. `"maps/q2dm1.bsp"`).
+    /// Archive lookups are case-insensitive; loose lookups follow the host filesystem.
+    pub fn read(&self, name: &str) -> Option<Cow<'_, [u8]>> {
+        self.search.iter().find_map(|src| match src {
+            Source::Dir(d) => std::fs::read(d.join(name)).ok().map(Cow::Owned),
+            Source::Pak(p) => p.read(name).map(Cow::Borrowed),
+            Source::Pkz(z) => z.read(name),
+        })
+    }
+
+    /// Whether any source has `name`: one lookup in the archive index built at mount, then
+    /// a stat per loose game dir.
+    pub fn exists(&self, name: &str) -> bool {
+        self.archived.contains(&name.to_ascii_lowercase())
+            || self.search.iter().any(|src| match src {
+                Source::Dir(d) => d.join(name).is_file(),
+                Source::Pak(_) | Source::Pkz(_) => false,
+            })
+    }
+
+    /// Every file directly under `dir` (e.g. `"maps/"`) ending in `ext` (e.g. `".bsp"`),
+    /// across all sources: lower-cased full names, deduplicated and sorted.
+    pub fn list(&self, dir: &str, ext: &str) -> Vec<String> {
+        let (dir, ext) = (dir.to_ascii_lowercase(), ext.to_ascii_lowercase());
+        let keep = |low: &str| {
+            low.strip_prefix(&dir)
+                .is_some_and(|rest| rest.ends_with(&ext) && !rest.contains('/'))
+        };
+        let mut out = BTreeSet::new();
+        let mut add = |name: &str| {
+            let low = name.to_ascii_lowercase();
+            if keep(&low) {
+                out.insert(low);
+            }
+        };
+        for src in &self.search {
+            match src {
+                Source::Dir(d) => {
+                    for e in std::fs::read_dir(d.join(&dir))
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                    {
+                        if let Some(f) = e.file_name().to_str() {
+                            add(&format!("{dir}{f}"));
+                        }
+                    }
+                }
+                Source::Pak(p) => p.names().for_each(&mut add),
+                Source::Pkz(z) => z.names().for_each(&mut add),
+            }
+        }
+        out.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pak::{zip_bytes, PakWriter};
+
+    fn pak(path: &Path, files: &[(&str, &[u8])]) {
+        let mut w = PakWriter::new();
+        for (n, d) in files {
+            w.add(n, d);
+        }
+        w.write(path).unwrap();
+    }
+
+    #[test]
+    fn search_order_matches_engine() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("baseq2");
+        std::fs::create_dir_all(base.join("maps")).unwrap();
+        std::fs::write(base.join("maps/loose.bsp"), b"loose").unwrap();
+        std::fs::write(base.join("maps/a.bsp"), b"loose").unwrap();
+        pak(
+            &base.join("pak0.pak"),
+            &[("maps/a.bsp", b"pak0"), ("maps/b.bsp", b"pak0")],
+        );
+        pak(&base.join("pak10.pak"), &[("maps/b.bsp", b"pak10")]);
+        pak(&base.join("pak2.pak"), &[("maps/b.bsp", b"pak2")]);
+        std::fs::write(
+            base.join("extra.pkz"),
+            zip_bytes(&[("maps/c.bsp", b"pkz", true), ("maps/sub/x.bsp", b"", false)]),
+        )
+        .unwrap();
+
+        let mut vfs = Vfs::open(&base).unwrap();
+        let read = |v: &Vfs, n: &str| v.read(n).map(|d| d.into_owned());
+        // Paks shadow loose files; higher-numbered paks (numerically) shadow lower ones.
+        assert_eq!(read(&vfs, "maps/a.bsp").unwrap(), b"pak0");
+        assert_eq!(read(&vfs, "maps/b.bsp").unwrap(), b"pak10");
+        assert_eq!(read(&vfs, "maps/loose.bsp").unwrap(), b"loose");
+        assert_eq!(read(&vfs, "maps/c.bsp").unwrap(), b"pkz");
+        assert!(vfs.exists("MAPS/C.BSP"));
+        assert!(!vfs.exists("maps/none.bsp"));
+        assert_eq!(
+            vfs.list("maps/", ".bsp"),
+            ["maps/a.bsp", "maps/b.bsp", "maps/c.bsp", "maps/loose.bsp"]
+        );
+
+        // A mod dir shadows baseq2, loose files included.
+        let modd = tmp.path().join("mymod");
+        std::fs::create_dir_all(modd.join("maps")).unwrap();
+        std::fs::write(modd.join("maps/b.bsp"), b"mod").unwrap();
+        vfs.add_game_dir(&modd).unwrap();
+        assert_eq!(read(&vfs, "maps/b.bsp").unwrap(), b"mod");
+        assert_eq!(read(&vfs, "maps/a.bsp").unwrap(), b"pak0");
+    }
+
+    #[test]
+    fn corrupt_archive_is_skipped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("baseq2");
+        std::fs::create_dir_all(&base).unwrap();
+        pak(&base.join("pak0.pak"), &[("maps/a.bsp", b"pak0")]);
+        std::fs::write(base.join("pak9.pak"), b"PACK\x0c\0\0").unwrap(); // truncated header
+        std::fs::write(base.join("broken.pkz"), b"PK\x03\x04 not really").unwrap();
+
+        let vfs = Vfs::open(&base).unwrap();
+        assert_eq!(vfs.read("maps/a.bsp").unwrap().into_owned(), b"pak0");
+        assert_eq!(vfs.list("maps/", ".bsp"), ["maps/a.bsp"]);
+    }
+
+    #[test]
+    fn missing_dir_is_empty() {
+        let vfs = Vfs::open(Path::new("/nonexistent/baseq2")).unwrap();
+        assert!(vfs.read("maps/q2dm1.bsp").is_none());
+        assert!(vfs.list("maps/", ".bsp").is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+//! Hermetic end-to-end run of the nav build pipeline on a compiled synthetic map: a
+//! `BspBuilder` world packed into `pak0.pak` via `PakWriter`, loaded through the VFS by
+//! `generate_map_nav` exactly like a stock map. No id game data needed.
+//!
+//! Layout: a 1024×512 hall with a 192u-high ledge along its east end, reachable only by a
+//! ladder on the ledge face; a teleporter pad in the west corner drops onto the ledge.
+
+use world::testmap::BspBuilder;
+use world::{generate_map_nav, EdgeKind, PakWriter, GRID_SPACING};
+
+const WEST_SPAWN: [f32; 3] = [-400.0, 0.0, 24.0];
+const LEDGE_SPAWN: [f32; 3] = [440.0, 0.0, 216.0];
+
+fn hall() -> Vec<u8> {
+    let mut b = BspBuilder::new();
+    b.solid([-528.0, -272.0, -16.0], [528.0, 272.0, 0.0]) // floor
+        .solid([-528.0, -272.0, 0.0], [-512.0, 272.0, 320.0])
+        .solid([512.0, -272.0, 0.0], [528.0, 272.0, 320.0])
+        .solid([-512.0, -272.0, 0.0], [512.0, -256.0, 320.0])
+        .solid([-512.0, 256.0, 0.0], [512.0, 272.0, 320.0])
+        .solid([-512.0, -272.0, 320.0], [512.0, 272.0, 336.0]) // ceiling
+        .solid([352.0, -256.0, 0.0], [512.0, 256.0, 192.0]) // ledge
+        .ladder([336.0, -32.0, 0.0], [352.0, 32.0, 192.0])
+        .spawn(WEST_SPAWN)
+        .spawn(LEDGE_SPAWN)
+        .teleporter([-440.0, 200.0, 8.0], [440.0, 200.0, 200.0]);
+    b.to_bytes()
+}
+
+#[test]
+fn synthetic_map_builds_connected_nav_with_ladder_and_teleporter() {
+    let tmp = tempfile::tempdir().unwrap();
+    PakWriter::new()
+        .add("maps/synth.bsp", &hall())
+        .write(&tmp.path().join("pak0.pak"))
+        .unwrap();
+
+    let built = generate_map_nav(tmp.path(), "synth", GRID_SPACING).expect("build synth nav");
+    let g = &built.graph;
+    assert_eq!(built.total_spawns, 2);
+    assert_eq!(built.in_largest, 2, "ladder joins the hall and the ledge");
+
+    let floor = g.nearest(&WEST_SPAWN).unwrap();
+    let ledge = g.nearest(&LEDGE_SPAWN).unwrap();
+    assert!(
+        (g.node_pos(ledge)[2] - 216.0).abs() < 1.0,
+        "a node stands on the ledge"
+    );
+
+    let up = g.path(floor, ledge).expect("floor → ledge");
+    let kinds: Vec<EdgeKind> = up.windows(2).map(|w| g.edge_kind(w[0], w[1])).collect();
+    assert!(
+        kinds
+            .iter()
+            .any(|k| matches!(k, EdgeKind::Teleport | EdgeKind::Ride)),
+        "the climb uses the ladder or the teleporter: {kinds:?}"
+    );
+    assert!(
+        g.raw_rides().iter().any(|(_, _, info)| info.ladder),
+        "the ladder brush became a ladder climb edge"
+    );
+    assert!(g.path(ledge, floor).is_some(), "ledge → floor");
+    let teleports = g.raw_teleports();
+    assert_eq!(teleports.len(), 1, "one teleporter pair");
+    let (pad, dest) = teleports[0];
+    assert!(g.node_pos(pad)[0] < 0.0 && g.node_pos(dest)[2] > 200.0);
+}